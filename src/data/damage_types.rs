@@ -0,0 +1,154 @@
+//! Damage Types - Not every blow lands the same
+//!
+//! Every attack carries one of five damage types:
+//! - Steel: plain typed words and ripostes
+//! - Word: heavy strikes, whole sentences spoken with intent
+//! - Arcane, Nature, Void: spells, by element
+//!
+//! Enemies resist or are weak to types. Resistances come from the enemy
+//! template when authored, otherwise from its typing theme - a library
+//! horror made of words shrugs off Word but dreads the Void.
+//!
+//! Design: a resistance is a damage multiplier (1.0 = neutral); missing
+//! entries are neutral so templates only list what matters.
+
+use serde::{Deserialize, Serialize};
+
+/// Multiplier at or above which a type counts as a weakness
+const WEAK_AT: f32 = 1.2;
+/// Multiplier at or below which a type counts as resisted
+const RESIST_AT: f32 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DamageType {
+    Steel,
+    Arcane,
+    Nature,
+    Void,
+    Word,
+}
+
+impl DamageType {
+    pub const ALL: [DamageType; 5] = [Self::Steel, Self::Arcane, Self::Nature, Self::Void, Self::Word];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Steel => "Steel",
+            Self::Arcane => "Arcane",
+            Self::Nature => "Nature",
+            Self::Void => "Void",
+            Self::Word => "Word",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Steel => "🗡",
+            Self::Arcane => "✦",
+            Self::Nature => "🌿",
+            Self::Void => "◐",
+            Self::Word => "✎",
+        }
+    }
+}
+
+/// Damage multipliers by type; unlisted types take normal damage
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Resistances {
+    pub entries: Vec<(DamageType, f32)>,
+}
+
+impl Resistances {
+    pub fn new(entries: &[(DamageType, f32)]) -> Self {
+        Self { entries: entries.to_vec() }
+    }
+
+    /// Default resistances for an enemy's typing theme
+    pub fn for_theme(theme: &str) -> Self {
+        use DamageType::*;
+        match theme {
+            "corruption" => Self::new(&[(Void, 0.5), (Word, 1.5)]),
+            "void" | "chaos" => Self::new(&[(Void, 0.25), (Steel, 0.75), (Word, 1.5)]),
+            "ancient" | "genesis" => Self::new(&[(Steel, 0.75), (Arcane, 1.25)]),
+            "magic" | "forbidden" | "arcane" => Self::new(&[(Arcane, 0.5), (Steel, 1.25)]),
+            "technology" | "temporal" => Self::new(&[(Steel, 0.75), (Nature, 1.5)]),
+            "fire" => Self::new(&[(Nature, 0.5), (Void, 1.25)]),
+            "ice" => Self::new(&[(Steel, 0.75), (Arcane, 1.5)]),
+            "library" | "philosophy" => Self::new(&[(Word, 0.5), (Void, 1.5)]),
+            "nature" => Self::new(&[(Nature, 0.5), (Arcane, 1.25)]),
+            "dark" => Self::new(&[(Void, 0.5), (Steel, 0.75), (Nature, 1.5)]),
+            "fantasy" => Self::new(&[(Arcane, 1.25)]),
+            _ => Self::default(),
+        }
+    }
+
+    /// Damage multiplier against this type
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        self.entries.iter()
+            .find(|(t, _)| *t == damage_type)
+            .map(|(_, m)| *m)
+            .unwrap_or(1.0)
+    }
+
+    pub fn weaknesses(&self) -> Vec<DamageType> {
+        DamageType::ALL.into_iter().filter(|t| self.multiplier(*t) >= WEAK_AT).collect()
+    }
+
+    pub fn resisted(&self) -> Vec<DamageType> {
+        DamageType::ALL.into_iter().filter(|t| self.multiplier(*t) <= RESIST_AT).collect()
+    }
+
+    /// Short label for the combat UI, e.g. "Weak: ✎ Word | Resists: ◐ Void"
+    pub fn summary(&self) -> String {
+        let list = |types: Vec<DamageType>| {
+            types.iter().map(|t| format!("{} {}", t.icon(), t.name())).collect::<Vec<_>>().join(", ")
+        };
+        let mut parts = Vec::new();
+        let weak = self.weaknesses();
+        if !weak.is_empty() {
+            parts.push(format!("Weak: {}", list(weak)));
+        }
+        let resisted = self.resisted();
+        if !resisted.is_empty() {
+            parts.push(format!("Resists: {}", list(resisted)));
+        }
+        parts.join(" | ")
+    }
+}
+
+/// Battle log suffix for how well an attack landed
+pub fn effectiveness_note(multiplier: f32) -> &'static str {
+    if multiplier >= WEAK_AT {
+        " It's super effective!"
+    } else if multiplier <= RESIST_AT {
+        " It's resisted..."
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_resistances() {
+        let corrupted = Resistances::for_theme("corruption");
+        assert_eq!(corrupted.multiplier(DamageType::Word), 1.5);
+        assert_eq!(corrupted.multiplier(DamageType::Void), 0.5);
+        assert_eq!(corrupted.multiplier(DamageType::Steel), 1.0);
+        assert_eq!(corrupted.weaknesses(), vec![DamageType::Word]);
+        assert_eq!(corrupted.resisted(), vec![DamageType::Void]);
+
+        assert!(Resistances::for_theme("easy").summary().is_empty());
+        assert_eq!(Resistances::for_theme("dark").weaknesses(), vec![DamageType::Nature]);
+        assert_eq!(Resistances::for_theme("fantasy").weaknesses(), vec![DamageType::Arcane]);
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Resistances::for_theme("library").summary();
+        assert!(summary.contains("Weak: ◐ Void"));
+        assert!(summary.contains("Resists: ✎ Word"));
+    }
+}
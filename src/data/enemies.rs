@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::damage_types::{DamageType, Resistances};
+
 /// Database of all enemy types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnemyDatabase {
@@ -27,6 +29,9 @@ pub struct EnemyTemplate {
     pub attack_messages: Vec<String>,
     pub death_message: String,
     pub special_ability: Option<SpecialAbility>,
+    /// Authored resistances; None falls back to the typing theme's defaults
+    #[serde(default)]
+    pub resistances: Option<Resistances>,
 }
 
 /// Boss-specific template with phases
@@ -78,6 +83,13 @@ pub enum SpecialAbility {
     Enrage { damage_mult: f32, duration: f32 },
 }
 
+impl EnemyTemplate {
+    /// Resistances this enemy spawns with
+    pub fn resistances(&self) -> Resistances {
+        self.resistances.clone().unwrap_or_else(|| Resistances::for_theme(&self.typing_theme))
+    }
+}
+
 impl Default for EnemyDatabase {
     fn default() -> Self {
         Self::embedded()
@@ -126,6 +138,7 @@ impl EnemyDatabase {
             ],
             death_message: "The goblin falls with a pitiful screech.".to_string(),
             special_ability: None,
+            resistances: None,
         });
         
         enemies.insert("word_wisp".to_string(), EnemyTemplate {
@@ -150,6 +163,7 @@ impl EnemyDatabase {
             ],
             death_message: "The wisp dissipates into ethereal mist.".to_string(),
             special_ability: None,
+            resistances: None,
         });
         
         // === TIER 2-3: Early Game ===
@@ -176,6 +190,7 @@ impl EnemyDatabase {
             ],
             death_message: "The spider curls and goes still.".to_string(),
             special_ability: Some(SpecialAbility::Corruption { extra_chars: 2 }),
+            resistances: None,
         });
         
         enemies.insert("vowel_vampire".to_string(), EnemyTemplate {
@@ -202,6 +217,7 @@ impl EnemyDatabase {
             ],
             death_message: "The vampire crumbles to ash and bone.".to_string(),
             special_ability: Some(SpecialAbility::Blind { duration: 2.0 }),
+            resistances: None,
         });
         
         // === TIER 4-5: Mid Game ===
@@ -230,6 +246,7 @@ impl EnemyDatabase {
             ],
             death_message: "The thrall crumbles, finally at peace".to_string(),
             special_ability: Some(SpecialAbility::WordScramble),
+            resistances: None,
         });
         
         enemies.insert("meaning_eater".to_string(), EnemyTemplate {
@@ -256,6 +273,7 @@ impl EnemyDatabase {
             ],
             death_message: "The devourer releases its stolen souls in a blinding flash.".to_string(),
             special_ability: Some(SpecialAbility::TimeWarp { reduction: 3.0 }),
+            resistances: None,
         });
         
         // === TIER 6-7: Late Game ===
@@ -283,6 +301,7 @@ impl EnemyDatabase {
             ],
             death_message: "The golem crumbles into inert rubble.".to_string(),
             special_ability: Some(SpecialAbility::Enrage { damage_mult: 1.5, duration: 5.0 }),
+            resistances: None,
         });
         
         enemies.insert("void_scribe".to_string(), EnemyTemplate {
@@ -310,6 +329,7 @@ impl EnemyDatabase {
             ],
             death_message: "The walker fades back into the darkness.".to_string(),
             special_ability: Some(SpecialAbility::Mirror),
+            resistances: None,
         });
         
         // === TIER 8-10: Endgame ===
@@ -337,6 +357,7 @@ impl EnemyDatabase {
             ],
             death_message: "The weaver's shadows disperse into nothing.".to_string(),
             special_ability: Some(SpecialAbility::WordScramble),
+            resistances: None,
         });
         
         enemies.insert("paragraph_phantom".to_string(), EnemyTemplate {
@@ -363,6 +384,7 @@ impl EnemyDatabase {
             ],
             death_message: "The wraith fades with a final mournful wail.".to_string(),
             special_ability: Some(SpecialAbility::Blind { duration: 3.0 }),
+            resistances: None,
         });
         
         enemies.insert("lexicon_leviathan".to_string(), EnemyTemplate {
@@ -389,6 +411,7 @@ impl EnemyDatabase {
             ],
             death_message: "The wyrm crashes down, its reign ended.".to_string(),
             special_ability: Some(SpecialAbility::Summon { enemy_id: "word_wisp".to_string(), count: 2 }),
+            resistances: None,
         });
         
         enemies.insert("silence_incarnate".to_string(), EnemyTemplate {
@@ -415,6 +438,7 @@ impl EnemyDatabase {
             ],
             death_message: "The knight falls, armor clattering.".to_string(),
             special_ability: Some(SpecialAbility::TimeWarp { reduction: 5.0 }),
+            resistances: None,
         });
         
         // === BOSSES ===
@@ -576,6 +600,7 @@ impl EnemyDatabase {
             ],
             death_message: "The sprite settles into stillness.".to_string(),
             special_ability: None,
+            resistances: None,
         });
 
         enemies.insert("paper_phantom".to_string(), EnemyTemplate {
@@ -602,6 +627,7 @@ impl EnemyDatabase {
             ],
             death_message: "The phantom unfolds into blank pages.".to_string(),
             special_ability: None,
+            resistances: None,
         });
 
         enemies.insert("ink_wraith".to_string(), EnemyTemplate {
@@ -628,6 +654,7 @@ impl EnemyDatabase {
             ],
             death_message: "The wraith dissolves into a puddle of ink.".to_string(),
             special_ability: Some(SpecialAbility::Blind { duration: 1.5 }),
+            resistances: None,
         });
 
         enemies.insert("shelf_specter".to_string(), EnemyTemplate {
@@ -654,6 +681,7 @@ impl EnemyDatabase {
             ],
             death_message: "'Return... your books...' it whispers, fading.".to_string(),
             special_ability: Some(SpecialAbility::WordScramble),
+            resistances: None,
         });
 
         // ═══════════════════════════════════════════════════════════════════
@@ -684,6 +712,12 @@ impl EnemyDatabase {
             ],
             death_message: "The cipher melts into cryptic puddles.".to_string(),
             special_ability: Some(SpecialAbility::TimeWarp { reduction: 1.0 }),
+            // A living code: frozen hard, but plain words crack it
+            resistances: Some(Resistances::new(&[
+                (DamageType::Steel, 0.75),
+                (DamageType::Arcane, 1.5),
+                (DamageType::Word, 1.25),
+            ])),
         });
 
         enemies.insert("sealed_secret".to_string(), EnemyTemplate {
@@ -710,6 +744,7 @@ impl EnemyDatabase {
             ],
             death_message: "The secret reseals itself, dormant once more.".to_string(),
             special_ability: Some(SpecialAbility::Corruption { extra_chars: 3 }),
+            resistances: None,
         });
 
         enemies.insert("archive_guardian".to_string(), EnemyTemplate {
@@ -737,6 +772,7 @@ impl EnemyDatabase {
             ],
             death_message: "The guardian crumbles, its duty finally ended.".to_string(),
             special_ability: Some(SpecialAbility::Regenerate { percent: 5.0 }),
+            resistances: None,
         });
 
         // ═══════════════════════════════════════════════════════════════════
@@ -766,6 +802,7 @@ impl EnemyDatabase {
             ],
             death_message: "The sprite flickers out with a sigh.".to_string(),
             special_ability: None,
+            resistances: None,
         });
 
         enemies.insert("ash_wraith".to_string(), EnemyTemplate {
@@ -792,6 +829,7 @@ impl EnemyDatabase {
             ],
             death_message: "The wraith finally finds rest in the flames.".to_string(),
             special_ability: Some(SpecialAbility::Blind { duration: 2.0 }),
+            resistances: None,
         });
 
         enemies.insert("burning_tome".to_string(), EnemyTemplate {
@@ -819,6 +857,7 @@ impl EnemyDatabase {
             ],
             death_message: "The tome's fire finally consumes it entirely.".to_string(),
             special_ability: Some(SpecialAbility::Enrage { damage_mult: 1.5, duration: 3.0 }),
+            resistances: None,
         });
 
         // ═══════════════════════════════════════════════════════════════════
@@ -849,6 +888,7 @@ impl EnemyDatabase {
             ],
             death_message: "The guardian shatters into a thousand fragments.".to_string(),
             special_ability: Some(SpecialAbility::Mirror),
+            resistances: None,
         });
 
         enemies.insert("frozen_thought".to_string(), EnemyTemplate {
@@ -875,6 +915,7 @@ impl EnemyDatabase {
             ],
             death_message: "The thought finally crystallizes into understanding.".to_string(),
             special_ability: Some(SpecialAbility::TimeWarp { reduction: 2.0 }),
+            resistances: None,
         });
 
         enemies.insert("time_shard".to_string(), EnemyTemplate {
@@ -901,6 +942,7 @@ impl EnemyDatabase {
             ],
             death_message: "The shard collapses into the present moment.".to_string(),
             special_ability: Some(SpecialAbility::WordScramble),
+            resistances: None,
         });
 
         // ═══════════════════════════════════════════════════════════════════
@@ -931,6 +973,7 @@ impl EnemyDatabase {
             ],
             death_message: "The crawler retreats into the margins.".to_string(),
             special_ability: Some(SpecialAbility::Blind { duration: 2.5 }),
+            resistances: None,
         });
 
         enemies.insert("null_word".to_string(), EnemyTemplate {
@@ -957,6 +1000,7 @@ impl EnemyDatabase {
             ],
             death_message: "The null word gains definition in death.".to_string(),
             special_ability: Some(SpecialAbility::Corruption { extra_chars: 4 }),
+            resistances: None,
         });
 
         enemies.insert("entropy_wisp".to_string(), EnemyTemplate {
@@ -983,6 +1027,7 @@ impl EnemyDatabase {
             ],
             death_message: "The entropy disperses into random noise.".to_string(),
             special_ability: Some(SpecialAbility::WordScramble),
+            resistances: None,
         });
 
         // ═══════════════════════════════════════════════════════════════════
@@ -1012,6 +1057,7 @@ impl EnemyDatabase {
             ],
             death_message: "The letter echoes eternally, never truly gone.".to_string(),
             special_ability: Some(SpecialAbility::Enrage { damage_mult: 1.8, duration: 4.0 }),
+            resistances: None,
         });
 
         enemies.insert("genesis_construct".to_string(), EnemyTemplate {
@@ -1039,6 +1085,7 @@ impl EnemyDatabase {
             ],
             death_message: "The construct returns to the first silence.".to_string(),
             special_ability: Some(SpecialAbility::Regenerate { percent: 8.0 }),
+            resistances: None,
         });
 
         enemies.insert("alpha_word".to_string(), EnemyTemplate {
@@ -1066,6 +1113,7 @@ impl EnemyDatabase {
             ],
             death_message: "The Alpha Word falls silent... but meaning persists.".to_string(),
             special_ability: Some(SpecialAbility::Corruption { extra_chars: 5 }),
+            resistances: None,
         });
 
         // ═══════════════════════════════════════════════════════════════════
@@ -1097,6 +1145,7 @@ impl EnemyDatabase {
             ],
             death_message: "The librarian's corruption fades, revealing peaceful features.".to_string(),
             special_ability: Some(SpecialAbility::Summon { enemy_id: "paper_phantom".to_string(), count: 2 }),
+            resistances: None,
         });
 
         enemies.insert("word_devourer".to_string(), EnemyTemplate {
@@ -1123,6 +1172,7 @@ impl EnemyDatabase {
             ],
             death_message: "The devourer regurgitates a fountain of lost words.".to_string(),
             special_ability: Some(SpecialAbility::Corruption { extra_chars: 6 }),
+            resistances: None,
         });
 
         // ═══════════════════════════════════════════════════════════════════
@@ -23,6 +23,9 @@ pub struct Equipment {
     pub rarity: Rarity,
     pub stats: StatBonus,
     pub typing_effect: Option<TypingEffect>,
+    /// Adjustments to per-keystroke impact while worn
+    #[serde(default)]
+    pub impact: ImpactTuning,
     pub flavor_text: String,
     pub gold_value: i32,
 }
@@ -37,6 +40,9 @@ pub struct Consumable {
     pub effect: ConsumableEffect,
     pub stack_max: u32,
     pub gold_value: i32,
+    /// Whether merchants stock it
+    #[serde(default)]
+    pub sold_in_shops: bool,
 }
 
 /// Relics - passive items with powerful effects
@@ -51,13 +57,74 @@ pub struct Relic {
     pub gold_value: i32,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum EquipmentSlot {
     Weapon,
     Armor,
     Accessory,
     Gloves,
     Boots,
+    Keycaps,
+    Tome,
+}
+
+impl EquipmentSlot {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Weapon => "Weapon",
+            Self::Armor => "Armor",
+            Self::Accessory => "Accessory",
+            Self::Gloves => "Gloves",
+            Self::Boots => "Boots",
+            Self::Keycaps => "Keycaps",
+            Self::Tome => "Tome",
+        }
+    }
+}
+
+/// How a piece of gear bends the feel of each keystroke.
+/// Every field is added on top of the base typing impact.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct ImpactTuning {
+    /// Extra milliseconds of timing drift still counted as "in rhythm"
+    pub rhythm_window_ms: u32,
+    /// Raises the ceiling on the speed damage multiplier
+    pub speed_cap: f32,
+    /// Wrong keystrokes per word that don't count against accuracy
+    pub typo_forgiveness: u32,
+    /// Extra pending damage per correct keystroke
+    pub base_damage: f32,
+}
+
+impl ImpactTuning {
+    /// Sum of two tunings (gear stacks)
+    pub fn plus(self, other: ImpactTuning) -> ImpactTuning {
+        ImpactTuning {
+            rhythm_window_ms: self.rhythm_window_ms + other.rhythm_window_ms,
+            speed_cap: self.speed_cap + other.speed_cap,
+            typo_forgiveness: self.typo_forgiveness + other.typo_forgiveness,
+            base_damage: self.base_damage + other.base_damage,
+        }
+    }
+
+    /// Short stat line, e.g. "+20ms rhythm, +1 forgiven typo"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.rhythm_window_ms > 0 {
+            parts.push(format!("+{}ms rhythm", self.rhythm_window_ms));
+        }
+        if self.speed_cap > 0.0 {
+            parts.push(format!("+{:.2}x speed cap", self.speed_cap));
+        }
+        if self.typo_forgiveness > 0 {
+            parts.push(format!("+{} forgiven typo", self.typo_forgiveness));
+        }
+        if self.base_damage > 0.0 {
+            parts.push(format!("+{:.1} per key", self.base_damage));
+        }
+        parts.join(", ")
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -102,6 +169,17 @@ pub enum ConsumableEffect {
     SkipWord,
     DoubleGold { duration_combats: u32 },
     DoubleXP { duration_combats: u32 },
+    /// Undo the first mistake in the word being typed
+    EraseMistake,
+    /// Hold the word timer for a few seconds
+    PauseTimer { seconds: f32 },
+}
+
+impl ConsumableEffect {
+    /// Whether it can be used mid-fight by typing its name
+    pub fn usable_in_combat(&self) -> bool {
+        matches!(self, Self::Heal { .. } | Self::HealPercent { .. } | Self::EraseMistake | Self::PauseTimer { .. })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,6 +221,7 @@ impl ItemDatabase {
             rarity: Rarity::Common,
             stats: StatBonus { damage: 3, typing_speed_bonus: 0.05, ..Default::default() },
             typing_effect: None,
+            impact: ImpactTuning::default(),
             flavor_text: "The pen is mightier than the sword, especially when enchanted.".into(),
             gold_value: 50,
         });
@@ -155,6 +234,7 @@ impl ItemDatabase {
             rarity: Rarity::Uncommon,
             stats: StatBonus { damage: 8, crit_chance: 0.05, ..Default::default() },
             typing_effect: Some(TypingEffect::ReducedWordLength { chars: 1 }),
+            impact: ImpactTuning::default(),
             flavor_text: "Each stroke writes a new chapter of your enemy's demise.".into(),
             gold_value: 150,
         });
@@ -167,6 +247,7 @@ impl ItemDatabase {
             rarity: Rarity::Rare,
             stats: StatBonus { damage: 12, max_hp: 10, crit_chance: 0.08, ..Default::default() },
             typing_effect: Some(TypingEffect::ComboMultiplier { bonus: 0.1 }),
+            impact: ImpactTuning::default(),
             flavor_text: "Knowledge is power. This is a lot of knowledge.".into(),
             gold_value: 350,
         });
@@ -179,6 +260,7 @@ impl ItemDatabase {
             rarity: Rarity::Epic,
             stats: StatBonus { damage: 18, crit_chance: 0.12, typing_speed_bonus: 0.1, ..Default::default() },
             typing_effect: Some(TypingEffect::AccuracyForgiveness { errors_forgiven: 1 }),
+            impact: ImpactTuning::default(),
             flavor_text: "It writes what was never meant to be written.".into(),
             gold_value: 750,
         });
@@ -191,6 +273,7 @@ impl ItemDatabase {
             rarity: Rarity::Legendary,
             stats: StatBonus { damage: 30, max_hp: 25, crit_chance: 0.15, typing_speed_bonus: 0.15, ..Default::default() },
             typing_effect: Some(TypingEffect::DoubleLetterBonus),
+            impact: ImpactTuning::default(),
             flavor_text: "In the beginning was the Word, and the Word was with you.".into(),
             gold_value: 2000,
         });
@@ -207,6 +290,7 @@ impl ItemDatabase {
             rarity: Rarity::Common,
             stats: StatBonus { defense: 2, max_hp: 5, ..Default::default() },
             typing_effect: None,
+            impact: ImpactTuning::default(),
             flavor_text: "It's not fancy, but it keeps the pointy bits out.".into(),
             gold_value: 40,
         });
@@ -219,6 +303,7 @@ impl ItemDatabase {
             rarity: Rarity::Uncommon,
             stats: StatBonus { defense: 4, max_hp: 10, xp_bonus: 0.1, ..Default::default() },
             typing_effect: None,
+            impact: ImpactTuning::default(),
             flavor_text: "The ink stains tell a thousand stories.".into(),
             gold_value: 120,
         });
@@ -231,6 +316,7 @@ impl ItemDatabase {
             rarity: Rarity::Rare,
             stats: StatBonus { defense: 8, max_hp: 20, ..Default::default() },
             typing_effect: Some(TypingEffect::ExtendedTime { seconds: 1.0 }),
+            impact: ImpactTuning::default(),
             flavor_text: "Each link is inscribed with a word of clarity.".into(),
             gold_value: 300,
         });
@@ -243,6 +329,7 @@ impl ItemDatabase {
             rarity: Rarity::Epic,
             stats: StatBonus { defense: 12, max_hp: 35, typing_speed_bonus: 0.05, ..Default::default() },
             typing_effect: Some(TypingEffect::AccuracyForgiveness { errors_forgiven: 1 }),
+            impact: ImpactTuning::default(),
             flavor_text: "You can feel the nothing pressing against you, protecting you.".into(),
             gold_value: 700,
         });
@@ -259,6 +346,7 @@ impl ItemDatabase {
             rarity: Rarity::Common,
             stats: StatBonus { typing_speed_bonus: 0.08, ..Default::default() },
             typing_effect: None,
+            impact: ImpactTuning { speed_cap: 0.25, ..Default::default() },
             flavor_text: "Feel the keys beneath your fingertips.".into(),
             gold_value: 35,
         });
@@ -271,10 +359,71 @@ impl ItemDatabase {
             rarity: Rarity::Rare,
             stats: StatBonus { typing_speed_bonus: 0.15, crit_chance: 0.05, ..Default::default() },
             typing_effect: Some(TypingEffect::ReducedWordLength { chars: 1 }),
+            impact: ImpactTuning { speed_cap: 0.5, ..Default::default() },
             flavor_text: "Your fingers flow like mercury across the keys.".into(),
             gold_value: 280,
         });
 
+        // ═══════════════════════════════════════════════
+        // KEYCAPS
+        // ═══════════════════════════════════════════════
+
+        equipment.insert("clicky_keycaps".into(), Equipment {
+            id: "clicky_keycaps".into(),
+            name: "Clicky Keycaps".into(),
+            description: "Every press lands with a crisp, audible click.".into(),
+            slot: EquipmentSlot::Keycaps,
+            rarity: Rarity::Common,
+            stats: StatBonus::default(),
+            typing_effect: None,
+            impact: ImpactTuning { rhythm_window_ms: 20, ..Default::default() },
+            flavor_text: "Your neighbours have opinions. Your rhythm does not care.".into(),
+            gold_value: 45,
+        });
+
+        equipment.insert("bone_keycaps".into(), Equipment {
+            id: "bone_keycaps".into(),
+            name: "Bone Keycaps".into(),
+            description: "Carved from something that once had a steady heartbeat.".into(),
+            slot: EquipmentSlot::Keycaps,
+            rarity: Rarity::Rare,
+            stats: StatBonus::default(),
+            typing_effect: None,
+            impact: ImpactTuning { rhythm_window_ms: 40, speed_cap: 0.25, ..Default::default() },
+            flavor_text: "They keep time better than you do.".into(),
+            gold_value: 260,
+        });
+
+        // ═══════════════════════════════════════════════
+        // TOMES
+        // ═══════════════════════════════════════════════
+
+        equipment.insert("book_of_errata".into(), Equipment {
+            id: "book_of_errata".into(),
+            name: "Book of Errata".into(),
+            description: "A slim volume listing every mistake ever made, yours included.".into(),
+            slot: EquipmentSlot::Tome,
+            rarity: Rarity::Uncommon,
+            stats: StatBonus::default(),
+            typing_effect: None,
+            impact: ImpactTuning { typo_forgiveness: 1, ..Default::default() },
+            flavor_text: "Already corrected. You're welcome.".into(),
+            gold_value: 140,
+        });
+
+        equipment.insert("primer_of_strokes".into(), Equipment {
+            id: "primer_of_strokes".into(),
+            name: "Primer of Strokes".into(),
+            description: "A child's first writing book, heavy with old power.".into(),
+            slot: EquipmentSlot::Tome,
+            rarity: Rarity::Rare,
+            stats: StatBonus::default(),
+            typing_effect: None,
+            impact: ImpactTuning { base_damage: 0.5, ..Default::default() },
+            flavor_text: "Every letter, practised until it could cut.".into(),
+            gold_value: 280,
+        });
+
         // ═══════════════════════════════════════════════
         // ACCESSORIES
         // ═══════════════════════════════════════════════
@@ -287,6 +436,7 @@ impl ItemDatabase {
             rarity: Rarity::Common,
             stats: StatBonus { gold_bonus: 0.05, ..Default::default() },
             typing_effect: None,
+            impact: ImpactTuning::default(),
             flavor_text: "Every copper counts.".into(),
             gold_value: 25,
         });
@@ -299,6 +449,7 @@ impl ItemDatabase {
             rarity: Rarity::Rare,
             stats: StatBonus { max_hp: 15, ..Default::default() },
             typing_effect: Some(TypingEffect::WordPreview { words_ahead: 2 }),
+            impact: ImpactTuning::default(),
             flavor_text: "See the words before they arrive.".into(),
             gold_value: 400,
         });
@@ -311,6 +462,7 @@ impl ItemDatabase {
             rarity: Rarity::Epic,
             stats: StatBonus { crit_chance: 0.2, damage: 5, ..Default::default() },
             typing_effect: Some(TypingEffect::ComboMultiplier { bonus: 0.2 }),
+            impact: ImpactTuning::default(),
             flavor_text: "Perfection is not a goal, it's a standard.".into(),
             gold_value: 600,
         });
@@ -327,6 +479,7 @@ impl ItemDatabase {
             effect: ConsumableEffect::Heal { amount: 30 },
             stack_max: 5,
             gold_value: 25,
+            sold_in_shops: false,
         });
 
         consumables.insert("greater_health_potion".into(), Consumable {
@@ -337,6 +490,7 @@ impl ItemDatabase {
             effect: ConsumableEffect::Heal { amount: 75 },
             stack_max: 3,
             gold_value: 60,
+            sold_in_shops: false,
         });
 
         consumables.insert("elixir_of_restoration".into(), Consumable {
@@ -347,6 +501,7 @@ impl ItemDatabase {
             effect: ConsumableEffect::HealPercent { percent: 0.5 },
             stack_max: 2,
             gold_value: 150,
+            sold_in_shops: false,
         });
 
         consumables.insert("focus_tonic".into(), Consumable {
@@ -357,6 +512,7 @@ impl ItemDatabase {
             effect: ConsumableEffect::TemporaryTypingBonus { bonus: 0.2, duration_words: 5 },
             stack_max: 5,
             gold_value: 30,
+            sold_in_shops: false,
         });
 
         consumables.insert("clarity_draught".into(), Consumable {
@@ -367,6 +523,7 @@ impl ItemDatabase {
             effect: ConsumableEffect::ClearNegativeEffects,
             stack_max: 3,
             gold_value: 50,
+            sold_in_shops: false,
         });
 
         consumables.insert("revealing_scroll".into(), Consumable {
@@ -377,6 +534,7 @@ impl ItemDatabase {
             effect: ConsumableEffect::RevealWord,
             stack_max: 10,
             gold_value: 15,
+            sold_in_shops: false,
         });
 
         consumables.insert("skip_scroll".into(), Consumable {
@@ -387,6 +545,7 @@ impl ItemDatabase {
             effect: ConsumableEffect::SkipWord,
             stack_max: 2,
             gold_value: 100,
+            sold_in_shops: false,
         });
 
         consumables.insert("gold_incense".into(), Consumable {
@@ -397,6 +556,41 @@ impl ItemDatabase {
             effect: ConsumableEffect::DoubleGold { duration_combats: 3 },
             stack_max: 2,
             gold_value: 80,
+            sold_in_shops: false,
+        });
+
+        // Combat consumables: type their name after `/` mid-fight
+        consumables.insert("ink_vial".into(), Consumable {
+            id: "ink_vial".into(),
+            name: "Ink Vial".into(),
+            description: "Restores 20 HP mid-fight.".into(),
+            rarity: Rarity::Common,
+            effect: ConsumableEffect::Heal { amount: 20 },
+            stack_max: 5,
+            gold_value: 20,
+            sold_in_shops: true,
+        });
+
+        consumables.insert("whiteout".into(), Consumable {
+            id: "whiteout".into(),
+            name: "Whiteout".into(),
+            description: "Erases your first mistake in the current word.".into(),
+            rarity: Rarity::Common,
+            effect: ConsumableEffect::EraseMistake,
+            stack_max: 5,
+            gold_value: 25,
+            sold_in_shops: true,
+        });
+
+        consumables.insert("bookmark".into(), Consumable {
+            id: "bookmark".into(),
+            name: "Bookmark".into(),
+            description: "Holds the enemy's timer for 8 seconds.".into(),
+            rarity: Rarity::Uncommon,
+            effect: ConsumableEffect::PauseTimer { seconds: 8.0 },
+            stack_max: 3,
+            gold_value: 35,
+            sold_in_shops: true,
         });
 
         // ═══════════════════════════════════════════════
@@ -496,6 +690,23 @@ impl ItemDatabase {
         self.consumables.get(id)
     }
 
+    /// Consumables merchants stock, sorted by id
+    pub fn shop_consumables(&self) -> Vec<&Consumable> {
+        let mut stock: Vec<&Consumable> = self.consumables.values()
+            .filter(|c| c.sold_in_shops)
+            .collect();
+        stock.sort_by(|a, b| a.id.cmp(&b.id));
+        stock
+    }
+
+    /// The combat consumable a typed name refers to, by name or id (case-insensitive)
+    pub fn consumable_named(&self, input: &str) -> Option<&Consumable> {
+        let input = input.trim();
+        self.consumables.values()
+            .filter(|c| c.effect.usable_in_combat())
+            .find(|c| c.name.eq_ignore_ascii_case(input) || c.id.eq_ignore_ascii_case(&input.replace(' ', "_")))
+    }
+
     pub fn get_relic(&self, id: &str) -> Option<&Relic> {
         self.relics.get(id)
     }
@@ -507,4 +718,55 @@ impl ItemDatabase {
     pub fn get_equipment_by_rarity(&self, rarity: Rarity) -> Vec<&Equipment> {
         self.equipment.values().filter(|e| e.rarity == rarity).collect()
     }
+
+    /// Gear that changes how keystrokes land (keycaps, gloves, tomes...), sorted by id
+    pub fn typing_gear(&self) -> Vec<&Equipment> {
+        let mut gear: Vec<&Equipment> = self.equipment.values()
+            .filter(|e| e.impact != ImpactTuning::default())
+            .collect();
+        gear.sort_by(|a, b| a.id.cmp(&b.id));
+        gear
+    }
+
+    /// Combined tuning of the given equipment ids (unknown ids are ignored)
+    pub fn impact_of<'a>(&self, ids: impl IntoIterator<Item = &'a String>) -> ImpactTuning {
+        ids.into_iter()
+            .filter_map(|id| self.get_equipment(id))
+            .fold(ImpactTuning::default(), |total, e| total.plus(e.impact))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gear_tuning_stacks() {
+        let db = ItemDatabase::embedded();
+        let worn = vec!["clicky_keycaps".to_string(), "book_of_errata".to_string(), "no_such_item".to_string()];
+        let tuning = db.impact_of(&worn);
+        assert_eq!(tuning.rhythm_window_ms, 20);
+        assert_eq!(tuning.typo_forgiveness, 1);
+        assert_eq!(tuning.summary(), "+20ms rhythm, +1 forgiven typo");
+    }
+
+    #[test]
+    fn test_consumable_names() {
+        let db = ItemDatabase::embedded();
+        assert_eq!(db.consumable_named(" ink vial ").map(|c| c.id.as_str()), Some("ink_vial"));
+        assert_eq!(db.consumable_named("WHITEOUT").map(|c| c.id.as_str()), Some("whiteout"));
+        // Not usable mid-fight
+        assert!(db.consumable_named("gold incense").is_none());
+        let stock: Vec<&str> = db.shop_consumables().iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(stock, vec!["bookmark", "ink_vial", "whiteout"]);
+    }
+
+    #[test]
+    fn test_gear_survives_ron_round_trip() {
+        let db = ItemDatabase::embedded();
+        let ron = ron::to_string(&db).unwrap();
+        let loaded: ItemDatabase = ron::from_str(&ron).unwrap();
+        assert_eq!(loaded.typing_gear().len(), db.typing_gear().len());
+        assert!(db.typing_gear().iter().any(|e| e.slot == EquipmentSlot::Tome));
+    }
 }
@@ -7,6 +7,13 @@
 use rand::seq::SliceRandom;
 use rand::Rng;
 
+use crate::game::setting_packs;
+use crate::game::word_chain;
+use crate::game::world_integration::FloorZone;
+
+/// Last floor of the story; every floor past it is the endless descent
+pub const FINAL_FLOOR: u32 = 10;
+
 /// Zone-specific word pools that immerse players in each area's atmosphere
 pub struct LoreWords;
 
@@ -355,26 +362,87 @@ impl LoreWords {
     
     /// Get words appropriate for the current floor zone
     pub fn get_zone_words(floor: u32) -> Vec<&'static str> {
-        match floor {
-            1..=2 => Self::shattered_halls_words(),
-            3..=4 => Self::sunken_archives_words(),
-            5..=6 => Self::blighted_gardens_words(),
-            7..=8 => Self::clockwork_depths_words(),
-            9..=10 => Self::voids_edge_words(),
-            _ => Self::the_breach_words(),
+        if floor > FINAL_FLOOR {
+            let (lap, echo) = Self::endless_echo(floor);
+            return Self::mutate(lap, [Self::get_zone_words(echo), Self::get_zone_words(Self::far_echo(echo)), Self::the_breach_words()]);
         }
+        Self::zone_words(FloorZone::from_floor(floor))
     }
     
     /// Get sentences appropriate for the current floor zone
     pub fn get_zone_sentences(floor: u32) -> Vec<&'static str> {
-        match floor {
-            1..=2 => Self::shattered_halls_sentences(),
-            3..=4 => Self::sunken_archives_sentences(),
-            5..=6 => Self::blighted_gardens_sentences(),
-            7..=8 => Self::clockwork_depths_sentences(),
-            9..=10 => Self::voids_edge_sentences(),
-            _ => Self::the_breach_sentences(),
+        if floor > FINAL_FLOOR {
+            let (lap, echo) = Self::endless_echo(floor);
+            return Self::mutate(lap, [Self::get_zone_sentences(echo), Self::get_zone_sentences(Self::far_echo(echo)), Self::the_breach_sentences()]);
         }
+        Self::zone_sentences(FloorZone::from_floor(floor))
+    }
+    
+    /// A zone's own words in the run's setting, wherever on the route it falls
+    pub fn zone_words(zone: FloorZone) -> Vec<&'static str> {
+        (setting_packs::pack().zone_words)(zone)
+    }
+    
+    /// A zone's own sentences in the run's setting, wherever on the route it falls
+    pub fn zone_sentences(zone: FloorZone) -> Vec<&'static str> {
+        (setting_packs::pack().zone_sentences)(zone)
+    }
+    
+    /// A zone's words in the fall of Valdris
+    pub fn valdris_zone_words(zone: FloorZone) -> Vec<&'static str> {
+        match zone {
+            FloorZone::ShatteredHalls => Self::shattered_halls_words(),
+            FloorZone::SunkenArchives => Self::sunken_archives_words(),
+            FloorZone::BlightedGardens => Self::blighted_gardens_words(),
+            FloorZone::ClockworkDepths => Self::clockwork_depths_words(),
+            FloorZone::VoidsEdge => Self::voids_edge_words(),
+            FloorZone::TheBreach => Self::the_breach_words(),
+        }
+    }
+    
+    /// A zone's sentences in the fall of Valdris
+    pub fn valdris_zone_sentences(zone: FloorZone) -> Vec<&'static str> {
+        match zone {
+            FloorZone::ShatteredHalls => Self::shattered_halls_sentences(),
+            FloorZone::SunkenArchives => Self::sunken_archives_sentences(),
+            FloorZone::BlightedGardens => Self::blighted_gardens_sentences(),
+            FloorZone::ClockworkDepths => Self::clockwork_depths_sentences(),
+            FloorZone::VoidsEdge => Self::voids_edge_sentences(),
+            FloorZone::TheBreach => Self::the_breach_sentences(),
+        }
+    }
+    
+    // =========================================
+    // ENDLESS DESCENT - The zones, again and worse
+    // =========================================
+    
+    /// Past the final floor the zones come round again: (lap, floor whose
+    /// zone this one echoes). Floor 11 is lap 1 echoing floor 1.
+    pub fn endless_echo(floor: u32) -> (u32, u32) {
+        let floor = floor.max(1);
+        ((floor - 1) / FINAL_FLOOR, (floor - 1) % FINAL_FLOOR + 1)
+    }
+    
+    /// The zone half a descent away from `echo`, mixed into its pool
+    fn far_echo(echo: u32) -> u32 {
+        (echo + FINAL_FLOOR / 2 - 1) % FINAL_FLOOR + 1
+    }
+    
+    /// Mix the pools and drop their shortest entries: a fifth more with
+    /// each lap, up to three fifths
+    fn mutate(lap: u32, pools: [Vec<&'static str>; 3]) -> Vec<&'static str> {
+        let mut pool: Vec<&'static str> = Vec::new();
+        for entry in pools.into_iter().flatten() {
+            if !pool.contains(&entry) {
+                pool.push(entry);
+            }
+        }
+        let mut lengths: Vec<usize> = pool.iter().map(|w| w.chars().count()).collect();
+        lengths.sort_unstable();
+        let dropped = pool.len() * lap.min(3) as usize / 5;
+        let shortest = lengths.get(dropped).copied().unwrap_or(0);
+        pool.retain(|w| w.chars().count() >= shortest);
+        pool
     }
     
     /// Get words based on enemy type (from typing_theme)
@@ -382,15 +450,20 @@ impl LoreWords {
         match typing_theme {
             "fantasy" => Self::undead_words(),
             "dark" => Self::spectral_words(),
-            "arcane" => Self::sunken_archives_words(),
+            "arcane" => Self::zone_words(FloorZone::SunkenArchives),
             "nature" => Self::corrupted_words(),
             "technology" => Self::mechanical_words(),
-            _ => Self::shattered_halls_words(),
+            _ => Self::zone_words(FloorZone::ShatteredHalls),
         }
     }
     
-    /// Get narrative sentences based on progression
+    /// Get narrative sentences based on progression, in the run's setting
     pub fn get_narrative_sentences(floor: u32) -> Vec<&'static str> {
+        (setting_packs::pack().narrative)(floor)
+    }
+    
+    /// Narrative sentences in the fall of Valdris
+    pub fn valdris_narrative(floor: u32) -> Vec<&'static str> {
         match floor {
             1..=3 => Self::early_narrative(),
             4..=7 => Self::mid_narrative(),
@@ -398,10 +471,22 @@ impl LoreWords {
         }
     }
     
+    /// What the boss `name` says in the run's setting, if it has lines of its own
+    pub fn boss_sentences(name: &str) -> Option<Vec<&'static str>> {
+        (setting_packs::pack().boss_sentences)(name)
+    }
+    
+    /// Boss lines in the fall of Valdris
+    pub fn valdris_boss_sentences(name: &str) -> Option<Vec<&'static str>> {
+        match name {
+            n if n.contains("Hollow Knight") => Some(Self::hollow_knight_sentences()),
+            n if n.contains("Void Herald") => Some(Self::void_herald_sentences()),
+            _ => None,
+        }
+    }
+    
     /// Get a random word from the appropriate pool
-    pub fn random_word(floor: u32, enemy_theme: Option<&str>) -> String {
-        let mut rng = rand::thread_rng();
-        
+    pub fn random_word(rng: &mut impl Rng, floor: u32, enemy_theme: Option<&str>) -> String {
         // Mix zone words with enemy-specific words
         let mut pool = Self::get_zone_words(floor);
         
@@ -409,24 +494,74 @@ impl LoreWords {
             pool.extend(Self::get_enemy_words(theme));
         }
         
-        pool.choose(&mut rng)
+        pool.choose(rng)
             .map(|s| s.to_string())
             .unwrap_or_else(|| "honor".to_string())
     }
     
+    /// Get a random word whose length falls within `min_len..=max_len`.
+    /// Falls back to the full pool if nothing in range exists.
+    pub fn random_word_in_range(rng: &mut impl Rng, floor: u32, enemy_theme: Option<&str>, min_len: usize, max_len: usize) -> String {
+        let mut pool = Self::get_zone_words(floor);
+
+        if let Some(theme) = enemy_theme {
+            pool.extend(Self::get_enemy_words(theme));
+        }
+
+        let in_range: Vec<&str> = pool.iter()
+            .copied()
+            .filter(|w| (min_len..=max_len).contains(&w.chars().count()))
+            .collect();
+
+        in_range.choose(rng)
+            .or_else(|| pool.choose(rng))
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "honor".to_string())
+    }
+
+    /// Get a random word beginning with `letter` (any case), within
+    /// `length_range` when any such word fits, or None if the pool has no
+    /// word beginning with it
+    pub fn random_word_starting_with(rng: &mut impl Rng, floor: u32, enemy_theme: Option<&str>, letter: char, length_range: Option<(usize, usize)>) -> Option<String> {
+        let mut pool = Self::get_zone_words(floor);
+
+        if let Some(theme) = enemy_theme {
+            pool.extend(Self::get_enemy_words(theme));
+        }
+
+        let chained: Vec<&str> = pool.into_iter()
+            .filter(|w| word_chain::continues(w, letter))
+            .collect();
+        let in_range: Vec<&str> = match length_range {
+            Some((min, max)) => chained.iter().copied().filter(|w| (min..=max).contains(&w.chars().count())).collect(),
+            None => Vec::new(),
+        };
+
+        in_range.choose(rng)
+            .or_else(|| chained.choose(rng))
+            .map(|s| s.to_string())
+    }
+
+    /// Get a random zone word other than `previous`, for feeding an unbroken
+    /// stream of words
+    pub fn next_stream_word(rng: &mut impl Rng, floor: u32, previous: &str) -> String {
+        let pool: Vec<&str> = Self::get_zone_words(floor)
+            .into_iter()
+            .filter(|w| *w != previous)
+            .collect();
+
+        pool.choose(rng)
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "honor".to_string())
+    }
+
     /// Get a random sentence from the appropriate pool
-    pub fn random_sentence(floor: u32, is_boss: bool, boss_name: Option<&str>) -> String {
-        let mut rng = rand::thread_rng();
-        
+    pub fn random_sentence(rng: &mut impl Rng, floor: u32, is_boss: bool, boss_name: Option<&str>) -> String {
         // Boss-specific sentences take priority
         if is_boss {
             if let Some(name) = boss_name {
-                let boss_sentences: Vec<&str> = match name {
-                    n if n.contains("Hollow Knight") => Self::hollow_knight_sentences(),
-                    n if n.contains("Void Herald") => Self::void_herald_sentences(),
-                    _ => Self::get_zone_sentences(floor),
-                };
-                return boss_sentences.choose(&mut rng)
+                let boss_sentences = Self::boss_sentences(name).unwrap_or_else(|| Self::get_zone_sentences(floor));
+                return boss_sentences.choose(rng)
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| "Face your destiny.".to_string());
             }
@@ -436,8 +571,30 @@ impl LoreWords {
         let mut pool = Self::get_zone_sentences(floor);
         pool.extend(Self::get_narrative_sentences(floor));
         
-        pool.choose(&mut rng)
+        pool.choose(rng)
             .map(|s| s.to_string())
             .unwrap_or_else(|| "The battle continues.".to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endless_floors_cycle_and_lengthen() {
+        assert_eq!(LoreWords::endless_echo(11), (1, 1));
+        assert_eq!(LoreWords::endless_echo(20), (1, 10));
+        assert_eq!(LoreWords::endless_echo(23), (2, 3));
+
+        let average = |pool: &[&str]| pool.iter().map(|w| w.len()).sum::<usize>() as f32 / pool.len() as f32;
+        let halls = LoreWords::get_zone_words(1);
+        let first_lap = LoreWords::get_zone_words(11);
+        let third_lap = LoreWords::get_zone_words(31);
+        // Mixed in from other zones, and longer on every lap
+        assert!(first_lap.iter().any(|w| !halls.contains(w)));
+        assert!(average(&first_lap) > average(&halls));
+        assert!(average(&third_lap) > average(&first_lap));
+        assert!(!LoreWords::get_zone_sentences(42).is_empty());
+    }
+}
@@ -12,16 +12,20 @@ pub mod items;
 pub mod spells;
 pub mod zones;
 pub mod achievements;
+pub mod damage_types;
+pub mod shop;
 pub use lore_words::LoreWords;
 
 use std::fs;
 use std::path::Path;
 use rand::seq::SliceRandom;
+use rand::Rng;
 
 pub use sentences::SentenceDatabase;
 pub use word_lists::WordDatabase;
 pub use enemies::EnemyDatabase;
-pub use items::{ItemDatabase, Equipment, Consumable, Relic, Rarity};
+pub use shop::{ShopDatabase, Goods};
+pub use items::{ItemDatabase, Equipment, EquipmentSlot, ImpactTuning, Consumable, Relic, Rarity};
 pub use spells::{SpellDatabase, Spell, Element, SpellTier};
 pub use zones::{ZoneDatabase, Zone, SpecialMechanic};
 pub use achievements::{AchievementDatabase, Achievement, AchievementProgress, AchievementCategory, AchievementTier};
@@ -82,6 +86,8 @@ pub struct GameData {
     pub sentences: SentenceDatabase,
     pub words: WordDatabase,
     pub enemies: EnemyDatabase,
+    pub items: ItemDatabase,
+    pub shop: ShopDatabase,
 }
 
 impl Default for GameData {
@@ -97,6 +103,8 @@ impl GameData {
             sentences: SentenceDatabase::default(),
             words: WordDatabase::default(),
             enemies: EnemyDatabase::default(),
+            items: ItemDatabase::embedded(),
+            shop: ShopDatabase::embedded(),
         }
     }
     
@@ -107,28 +115,30 @@ impl GameData {
         let sentences_path = data_path.join("sentences.ron");
         let words_path = data_path.join("words.ron");
         let enemies_path = data_path.join("enemies.ron");
+        let items_path = data_path.join("items.ron");
+        let shop_path = data_path.join("shop.ron");
         
         Self {
             sentences: load_ron(&sentences_path).unwrap_or_default(),
             words: load_ron(&words_path).unwrap_or_default(),
             enemies: load_ron(&enemies_path).unwrap_or_default(),
+            items: load_ron(&items_path).unwrap_or_else(|_| ItemDatabase::embedded()),
+            shop: load_ron(&shop_path).unwrap_or_else(|_| ShopDatabase::embedded()),
         }
     }
     
     /// Get a random word appropriate for the given difficulty (1-10)
-    pub fn get_word(&self, difficulty: u32) -> String {
+    pub fn get_word(&self, rng: &mut impl Rng, difficulty: u32) -> String {
         let words = self.words.get_by_difficulty(difficulty);
-        let mut rng = rand::thread_rng();
-        words.choose(&mut rng)
+        words.choose(rng)
             .map(|s| (*s).clone())
             .unwrap_or_else(|| "word".to_string())
     }
     
     /// Get a random sentence appropriate for the given difficulty (1-10)
-    pub fn get_sentence(&self, difficulty: u32) -> String {
+    pub fn get_sentence(&self, rng: &mut impl Rng, difficulty: u32) -> String {
         let sentences = self.sentences.get_by_difficulty(difficulty, difficulty);
-        let mut rng = rand::thread_rng();
-        sentences.choose(&mut rng)
+        sentences.choose(rng)
             .map(|e| e.text.clone())
             .unwrap_or_else(|| "Type this sentence.".to_string())
     }
@@ -160,13 +170,24 @@ impl GameData {
 
 impl GameData {
     /// Get a lore-appropriate word for the current floor and enemy
-    pub fn get_lore_word(&self, floor: u32, enemy_theme: Option<&str>) -> String {
-        LoreWords::random_word(floor, enemy_theme)
+    pub fn get_lore_word(&self, rng: &mut impl Rng, floor: u32, enemy_theme: Option<&str>) -> String {
+        LoreWords::random_word(rng, floor, enemy_theme)
+    }
+
+    /// Get a lore-appropriate word with a preferred length range
+    pub fn get_lore_word_in_range(&self, rng: &mut impl Rng, floor: u32, enemy_theme: Option<&str>, length_range: (usize, usize)) -> String {
+        LoreWords::random_word_in_range(rng, floor, enemy_theme, length_range.0, length_range.1)
+    }
+    
+    /// Get a lore-appropriate word beginning with `letter`, preferring the
+    /// length range when given; None if no word begins with it
+    pub fn get_lore_word_chained(&self, rng: &mut impl Rng, floor: u32, enemy_theme: Option<&str>, letter: char, length_range: Option<(usize, usize)>) -> Option<String> {
+        LoreWords::random_word_starting_with(rng, floor, enemy_theme, letter, length_range)
     }
     
     /// Get a lore-appropriate sentence for the current floor and enemy
-    pub fn get_lore_sentence(&self, floor: u32, is_boss: bool, boss_name: Option<&str>) -> String {
-        LoreWords::random_sentence(floor, is_boss, boss_name)
+    pub fn get_lore_sentence(&self, rng: &mut impl Rng, floor: u32, is_boss: bool, boss_name: Option<&str>) -> String {
+        LoreWords::random_sentence(rng, floor, is_boss, boss_name)
     }
     
     /// Get a word pool appropriate for the zone
@@ -191,11 +212,7 @@ impl GameData {
     pub fn get_lore_sentence_pool(&self, floor: u32, is_boss: bool, boss_name: Option<&str>) -> Vec<String> {
         if is_boss {
             if let Some(name) = boss_name {
-                let boss_sentences = match name {
-                    n if n.contains("Hollow Knight") => LoreWords::hollow_knight_sentences(),
-                    n if n.contains("Void Herald") => LoreWords::void_herald_sentences(),
-                    _ => LoreWords::get_zone_sentences(floor),
-                };
+                let boss_sentences = LoreWords::boss_sentences(name).unwrap_or_else(|| LoreWords::get_zone_sentences(floor));
                 return boss_sentences.iter().map(|s| s.to_string()).collect();
             }
         }
@@ -0,0 +1,142 @@
+//! Shop stock data - what merchants keep on their shelves
+//!
+//! Which kinds of goods a shop stocks, how many and how often, plus the
+//! healing and word lists merchants sell alongside items.
+
+use serde::{Deserialize, Serialize};
+
+/// Everything merchants can stock
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ShopDatabase {
+    pub shelves: Vec<Shelf>,
+    pub healing: Vec<HealingOffer>,
+    pub lexicons: Vec<LexiconOffer>,
+}
+
+/// One kind of goods on a shop's shelves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shelf {
+    pub goods: Goods,
+    /// How many are stocked when the shelf is filled
+    pub count: usize,
+    /// Chance the shelf is filled at all
+    pub chance: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Goods {
+    /// Potions and other everyday consumables
+    Potions,
+    /// Consumables usable from the combat command line
+    CombatConsumables,
+    /// Gear that changes how keystrokes land
+    TypingGear,
+    Jokers,
+    Healing,
+    Lexicons,
+}
+
+/// Healing sold on the spot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealingOffer {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Share of max HP restored
+    pub percent: f32,
+    pub base_price: i32,
+}
+
+/// A word list the player can learn; its words turn up in fights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LexiconOffer {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub words: Vec<String>,
+    pub base_price: i32,
+    /// First floor it is sold on
+    #[serde(default)]
+    pub min_floor: u32,
+}
+
+impl ShopDatabase {
+    pub fn embedded() -> Self {
+        let shelves = vec![
+            Shelf { goods: Goods::Potions, count: 2, chance: 1.0 },
+            Shelf { goods: Goods::CombatConsumables, count: 2, chance: 1.0 },
+            Shelf { goods: Goods::Healing, count: 1, chance: 1.0 },
+            Shelf { goods: Goods::Lexicons, count: 1, chance: 0.6 },
+            Shelf { goods: Goods::TypingGear, count: 1, chance: 0.4 },
+            Shelf { goods: Goods::Jokers, count: 1, chance: 0.3 },
+        ];
+
+        let healing = vec![
+            HealingOffer {
+                id: "field_dressing".into(),
+                name: "Field Dressing".into(),
+                description: "The merchant binds your wounds. Restores 25% HP.".into(),
+                percent: 0.25,
+                base_price: 20,
+            },
+            HealingOffer {
+                id: "surgeons_care".into(),
+                name: "Surgeon's Care".into(),
+                description: "Stitches, salve and a stiff drink. Restores 60% HP.".into(),
+                percent: 0.6,
+                base_price: 45,
+            },
+        ];
+
+        let lexicons = vec![
+            LexiconOffer {
+                id: "home_row_primer".into(),
+                name: "Home Row Primer".into(),
+                description: "Words that never leave the home row.".into(),
+                words: ["flask", "salad", "glass", "flags", "dash", "hall", "jags", "lass"]
+                    .iter().map(|w| w.to_string()).collect(),
+                base_price: 40,
+                min_floor: 1,
+            },
+            LexiconOffer {
+                id: "battle_cant".into(),
+                name: "Battle Cant".into(),
+                description: "Short, hard words soldiers shout in the crush.".into(),
+                words: ["hew", "rend", "bash", "jab", "cut", "gore", "maul", "ram"]
+                    .iter().map(|w| w.to_string()).collect(),
+                base_price: 50,
+                min_floor: 1,
+            },
+            LexiconOffer {
+                id: "scribes_glossary".into(),
+                name: "Scribe's Glossary".into(),
+                description: "The words a copyist writes a thousand times a day.".into(),
+                words: ["quill", "folio", "vellum", "gloss", "margin", "rubric", "codex", "ink"]
+                    .iter().map(|w| w.to_string()).collect(),
+                base_price: 70,
+                min_floor: 3,
+            },
+        ];
+
+        Self { shelves, healing, lexicons }
+    }
+
+    pub fn get_lexicon(&self, id: &str) -> Option<&LexiconOffer> {
+        self.lexicons.iter().find(|l| l.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shop_data_survives_ron_round_trip() {
+        let db = ShopDatabase::embedded();
+        let ron = ron::to_string(&db).unwrap();
+        let loaded: ShopDatabase = ron::from_str(&ron).unwrap();
+        assert_eq!(loaded.shelves.len(), db.shelves.len());
+        assert_eq!(loaded.get_lexicon("battle_cant").map(|l| l.words.len()), Some(8));
+        assert!(loaded.healing.iter().all(|h| h.percent > 0.0 && h.base_price > 0));
+    }
+}
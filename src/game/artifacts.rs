@@ -0,0 +1,151 @@
+//! Artifacts - Relics of the lore, carried for the run
+//!
+//! The artifacts of the deep lore can be found in treasure rooms. Each one
+//! carried grants a passive boon for the rest of the run:
+//! - The Original Manuscript: a wider rhythm window
+//! - The Songlines: one forgiven typo per word
+//! - The Staff of Binding: immunity to corruption
+//! - The Eternal Flame: a harder strike with every keystroke
+//!
+//! Carry an artifact into a fight with the enemy tied to its story and it
+//! stirs, giving up its hidden truth (kept with the run's lore).
+//!
+//! Design: the lore's `Artifact` records hold the story; this module only
+//! adds what each does in play. GameState applies the boons at the start
+//! of each fight.
+
+use crate::data::ImpactTuning;
+use super::deep_lore::{all_artifacts, Artifact};
+
+/// Chance a treasure room holds an artifact not yet carried
+pub const ARTIFACT_CHANCE: f32 = 0.25;
+
+/// What an artifact does in play
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Relic {
+    /// Name of the lore artifact
+    pub artifact: &'static str,
+    pub tuning: ImpactTuning,
+    /// Prompts can't be corrupted while it is carried
+    pub wards_corruption: bool,
+    /// Enemy whose presence draws out the artifact's hidden truth
+    pub stirred_by: &'static str,
+}
+
+const NO_TUNING: ImpactTuning = ImpactTuning {
+    rhythm_window_ms: 0,
+    speed_cap: 0.0,
+    typo_forgiveness: 0,
+    base_damage: 0.0,
+};
+
+pub const RELICS: [Relic; 4] = [
+    Relic {
+        artifact: "The Original Manuscript",
+        tuning: ImpactTuning { rhythm_window_ms: 25, ..NO_TUNING },
+        wards_corruption: false,
+        stirred_by: "Drowned Scholar",
+    },
+    Relic {
+        artifact: "The Songlines",
+        tuning: ImpactTuning { typo_forgiveness: 1, ..NO_TUNING },
+        wards_corruption: false,
+        stirred_by: "Twisted Treant",
+    },
+    Relic {
+        artifact: "The Staff of Binding",
+        tuning: NO_TUNING,
+        wards_corruption: true,
+        stirred_by: "Hollow Knight",
+    },
+    Relic {
+        artifact: "The Eternal Flame",
+        tuning: ImpactTuning { base_damage: 0.3, ..NO_TUNING },
+        wards_corruption: false,
+        stirred_by: "Void Herald",
+    },
+];
+
+impl Relic {
+    pub fn named(name: &str) -> Option<&'static Relic> {
+        RELICS.iter().find(|r| r.artifact == name)
+    }
+
+    /// The lore record behind it
+    pub fn lore(&self) -> Option<Artifact> {
+        all_artifacts().into_iter().find(|a| a.name == self.artifact)
+    }
+
+    /// What it does, for messages and the stats screen
+    pub fn boon(&self) -> String {
+        if self.wards_corruption {
+            "corruption can't touch your prompts".to_string()
+        } else {
+            self.tuning.summary()
+        }
+    }
+
+    /// Whether `enemy_name` is the enemy that stirs it
+    pub fn stirred_by(&self, enemy_name: &str) -> bool {
+        enemy_name.trim_start_matches("The ") == self.stirred_by
+    }
+}
+
+/// Combined tuning of the carried artifacts
+pub fn tuning_of(carried: &[String]) -> ImpactTuning {
+    carried.iter()
+        .filter_map(|name| Relic::named(name))
+        .fold(NO_TUNING, |total, r| total.plus(r.tuning))
+}
+
+/// Whether any carried artifact wards off corruption
+pub fn wards_corruption(carried: &[String]) -> bool {
+    carried.iter().filter_map(|name| Relic::named(name)).any(|r| r.wards_corruption)
+}
+
+/// Artifacts not yet carried, in lore order
+pub fn unfound(carried: &[String]) -> Vec<&'static Relic> {
+    RELICS.iter().filter(|r| !carried.iter().any(|c| c == r.artifact)).collect()
+}
+
+/// Hidden truths the carried artifacts give up in a fight with
+/// `enemy_name`, as (artifact, truth)
+pub fn stirred_truths(carried: &[String], enemy_name: &str) -> Vec<(&'static str, String)> {
+    carried.iter()
+        .filter_map(|name| Relic::named(name))
+        .filter(|r| r.stirred_by(enemy_name))
+        .filter_map(|r| Some((r.artifact, r.lore()?.hidden_truth?)))
+        .collect()
+}
+
+/// Title a hidden truth is kept under in the run's lore
+pub fn truth_title(artifact: &str) -> String {
+    format!("{}: Hidden Truth", artifact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_relic_has_a_lore_truth() {
+        for relic in &RELICS {
+            let lore = relic.lore().unwrap_or_else(|| panic!("{} missing from lore", relic.artifact));
+            assert!(lore.hidden_truth.is_some());
+            assert!(!relic.boon().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_carried_relics_stack_and_stir() {
+        let carried = vec!["The Songlines".to_string(), "The Staff of Binding".to_string()];
+        assert_eq!(tuning_of(&carried).typo_forgiveness, 1);
+        assert!(wards_corruption(&carried));
+        assert_eq!(unfound(&carried).len(), 2);
+
+        let truths = stirred_truths(&carried, "The Hollow Knight");
+        assert_eq!(truths.len(), 1);
+        assert!(truths[0].1.contains("cracked"));
+        assert!(stirred_truths(&carried, "Goblin Lurker").is_empty());
+    }
+}
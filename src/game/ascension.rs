@@ -0,0 +1,179 @@
+//! Ascension - Harder runs for those who have already won
+//!
+//! Each victory unlocks the next ascension level. Levels stack, every one
+//! adding a rule to the ones below it:
+//! 1. Faster timers: less time for every prompt
+//! 2. Longer words: short words stop turning up
+//! 3. Unforgiving: no typo is forgiven, whatever gear or pact says
+//! 4. Corrupted prompts: corruption creeps into prompts, wards or no
+//!
+//! Every run is scored and kept on a leaderboard with its ascension level;
+//! higher levels score more.
+//!
+//! Design: the level is picked on the class screen and held by GameState,
+//! which hands it to CombatState at the start of each fight. The record of
+//! unlocks and scores is saved beside the challenge bests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::config::get_config_dir;
+
+/// Highest ascension level
+pub const MAX_ASCENSION: u32 = 4;
+/// Time limit multiplier under Faster Timers
+pub const ASCENSION_TIME_MULT: f32 = 0.8;
+/// Shortest single word under Longer Words
+pub const ASCENSION_MIN_WORD_LEN: usize = 6;
+/// Extra per-prompt corruption chance under Corrupted Prompts
+pub const ASCENSION_CORRUPTION: f32 = 0.1;
+/// Score bonus per ascension level
+const SCORE_PER_LEVEL: f32 = 0.25;
+/// Runs kept on the leaderboard
+const LEADERBOARD_SIZE: usize = 10;
+
+/// A rule an ascension level adds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AscensionRule {
+    FasterTimers,
+    LongerWords,
+    Unforgiving,
+    CorruptedPrompts,
+}
+
+/// Rules in the order the levels add them
+const RULES: [AscensionRule; MAX_ASCENSION as usize] = [
+    AscensionRule::FasterTimers,
+    AscensionRule::LongerWords,
+    AscensionRule::Unforgiving,
+    AscensionRule::CorruptedPrompts,
+];
+
+impl AscensionRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::FasterTimers => "Faster Timers",
+            Self::LongerWords => "Longer Words",
+            Self::Unforgiving => "Unforgiving",
+            Self::CorruptedPrompts => "Corrupted Prompts",
+        }
+    }
+}
+
+/// Every rule in force at `level`
+pub fn rules(level: u32) -> &'static [AscensionRule] {
+    &RULES[..level.min(MAX_ASCENSION) as usize]
+}
+
+/// e.g. "Ascension 2: Faster Timers, Longer Words"
+pub fn describe(level: u32) -> String {
+    if level == 0 {
+        return "No ascension".to_string();
+    }
+    let names: Vec<&str> = rules(level).iter().map(|r| r.name()).collect();
+    format!("Ascension {}: {}", level, names.join(", "))
+}
+
+/// A run's score, raised by its ascension level
+pub fn ascended_score(score: u64, level: u32) -> u64 {
+    (score as f32 * (1.0 + level as f32 * SCORE_PER_LEVEL)).round() as u64
+}
+
+/// A finished run on the leaderboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AscensionRun {
+    pub level: u32,
+    pub class: String,
+    pub floor: i32,
+    pub victory: bool,
+    pub score: u64,
+}
+
+/// Ascension levels unlocked and the best runs so far
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AscensionRecord {
+    /// Highest level that can be picked
+    pub unlocked: u32,
+    /// Best runs, highest score first
+    pub leaderboard: Vec<AscensionRun>,
+}
+
+impl AscensionRecord {
+    /// Record a finished run. Returns its leaderboard place (1-based) if it
+    /// made the board, and the newly unlocked level if a victory at the
+    /// highest level opened the next.
+    pub fn record(&mut self, run: AscensionRun) -> (Option<usize>, Option<u32>) {
+        let unlocked = if run.victory && run.level == self.unlocked && self.unlocked < MAX_ASCENSION {
+            self.unlocked += 1;
+            Some(self.unlocked)
+        } else {
+            None
+        };
+        let place = self.leaderboard.iter().position(|r| r.score < run.score).unwrap_or(self.leaderboard.len());
+        self.leaderboard.insert(place, run);
+        self.leaderboard.truncate(LEADERBOARD_SIZE);
+        let place = (place < LEADERBOARD_SIZE).then_some(place + 1);
+        (place, unlocked)
+    }
+
+    /// The level after `level`, wrapping back to none past the highest unlocked
+    pub fn next_level(&self, level: u32) -> u32 {
+        if level >= self.unlocked { 0 } else { level + 1 }
+    }
+}
+
+fn record_path() -> PathBuf {
+    get_config_dir().join("ascension.ron")
+}
+
+pub fn load_record() -> AscensionRecord {
+    fs::read_to_string(record_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_record(record: &AscensionRecord) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(record, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(record_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(level: u32, victory: bool, score: u64) -> AscensionRun {
+        AscensionRun { level, class: "Scribe".to_string(), floor: 11, victory, score }
+    }
+
+    #[test]
+    fn test_rules_stack() {
+        assert!(rules(0).is_empty());
+        assert_eq!(rules(2), &[AscensionRule::FasterTimers, AscensionRule::LongerWords]);
+        assert_eq!(rules(9).len(), MAX_ASCENSION as usize);
+        assert_eq!(describe(1), "Ascension 1: Faster Timers");
+        assert_eq!(ascended_score(1000, 2), 1500);
+    }
+
+    #[test]
+    fn test_victories_unlock_levels_and_board_keeps_the_best() {
+        let mut record = AscensionRecord::default();
+        assert_eq!(record.next_level(0), 0);
+        assert_eq!(record.record(run(0, false, 500)), (Some(1), None));
+        assert_eq!(record.record(run(0, true, 900)), (Some(1), Some(1)));
+        // Winning below the highest level unlocks nothing new
+        assert_eq!(record.record(run(0, true, 700)), (Some(2), None));
+        assert_eq!(record.next_level(0), 1);
+        assert_eq!(record.next_level(1), 0);
+
+        for _ in 0..LEADERBOARD_SIZE {
+            record.record(run(1, false, 1000));
+        }
+        assert_eq!(record.leaderboard.len(), LEADERBOARD_SIZE);
+        assert_eq!(record.record(run(1, false, 10)), (None, None));
+        assert!(record.leaderboard.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+}
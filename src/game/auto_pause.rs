@@ -0,0 +1,166 @@
+//! Auto-Pause - Nobody should lose a run to an alt-tab
+//!
+//! Freezes combat timers when the terminal loses focus, the process is
+//! suspended (Ctrl+Z / SIGTSTP), the main loop stalls (tmux detach,
+//! laptop lid), or the terminal shrinks too small to draw the fight in.
+//! Resuming plays a 3-2-1 countdown before the clock runs again.
+
+use std::time::{Duration, Instant};
+
+/// How long each countdown step is shown
+pub const COUNTDOWN_STEP: Duration = Duration::from_millis(700);
+
+/// Number of countdown steps (3-2-1)
+pub const COUNTDOWN_STEPS: u32 = 3;
+
+/// A frame gap longer than this means we were stopped, not just slow
+pub const STALL_THRESHOLD: Duration = Duration::from_millis(1500);
+
+/// Why the game paused itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    /// Terminal reported focus loss
+    FocusLost,
+    /// Process was suspended with Ctrl+Z / SIGTSTP
+    Suspended,
+    /// The main loop did not run for a long time
+    Stalled,
+    /// The terminal is too small to draw the fight in
+    TooSmall,
+}
+
+impl PauseReason {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::FocusLost => "Focus lost - the battle holds its breath",
+            Self::Suspended => "Suspended - the world waited for you",
+            Self::Stalled => "Time slipped away - the battle holds",
+            Self::TooSmall => "No room to fight - the battle waits for a larger window",
+        }
+    }
+}
+
+/// Auto-pause controller
+#[derive(Debug, Clone)]
+pub struct AutoPause {
+    /// Active pause (None while running or counting down)
+    pub reason: Option<PauseReason>,
+    /// When the resume countdown began
+    countdown_started: Option<Instant>,
+    /// Audio is muted while paused
+    pub audio_muted: bool,
+    /// Last time the main loop checked in
+    last_frame: Instant,
+}
+
+impl Default for AutoPause {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AutoPause {
+    pub fn new() -> Self {
+        Self {
+            reason: None,
+            countdown_started: None,
+            audio_muted: false,
+            last_frame: Instant::now(),
+        }
+    }
+
+    /// Pause immediately. A countdown in progress is cancelled.
+    pub fn pause(&mut self, reason: PauseReason) {
+        self.reason = Some(reason);
+        self.countdown_started = None;
+        self.audio_muted = true;
+    }
+
+    /// Begin the 3-2-1 countdown back into play
+    pub fn begin_countdown(&mut self) {
+        if self.reason.is_some() {
+            self.reason = None;
+            self.countdown_started = Some(Instant::now());
+        }
+    }
+
+    /// Drop any pause state without a countdown (e.g. combat ended)
+    pub fn clear(&mut self) {
+        self.reason = None;
+        self.countdown_started = None;
+        self.audio_muted = false;
+    }
+
+    /// Whether combat timers must stay frozen
+    pub fn is_active(&self) -> bool {
+        self.reason.is_some() || self.countdown_started.is_some()
+    }
+
+    /// Whether we are hard-paused (waiting for the player to return)
+    pub fn is_paused(&self) -> bool {
+        self.reason.is_some()
+    }
+
+    /// Current countdown number (3, 2, 1), if counting down
+    pub fn countdown_value(&self) -> Option<u32> {
+        let started = self.countdown_started?;
+        let step = (started.elapsed().as_millis() / COUNTDOWN_STEP.as_millis()) as u32;
+        if step >= COUNTDOWN_STEPS {
+            None
+        } else {
+            Some(COUNTDOWN_STEPS - step)
+        }
+    }
+
+    /// Call once per frame. Finishes the countdown when it runs out and
+    /// returns true if the loop stalled long enough to warrant a pause.
+    pub fn update(&mut self) -> bool {
+        let now = Instant::now();
+        let gap = now.duration_since(self.last_frame);
+        self.last_frame = now;
+
+        if self.countdown_started.is_some() && self.countdown_value().is_none() {
+            self.countdown_started = None;
+            self.audio_muted = false;
+        }
+
+        gap >= STALL_THRESHOLD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_and_countdown() {
+        let mut pause = AutoPause::new();
+        assert!(!pause.is_active());
+
+        pause.pause(PauseReason::FocusLost);
+        assert!(pause.is_paused());
+        assert!(pause.audio_muted);
+        assert_eq!(pause.countdown_value(), None);
+
+        pause.begin_countdown();
+        assert!(!pause.is_paused());
+        assert!(pause.is_active());
+        assert_eq!(pause.countdown_value(), Some(3));
+    }
+
+    #[test]
+    fn test_countdown_requires_pause() {
+        let mut pause = AutoPause::new();
+        pause.begin_countdown();
+        assert!(!pause.is_active());
+    }
+
+    #[test]
+    fn test_clear_unmutes() {
+        let mut pause = AutoPause::new();
+        pause.pause(PauseReason::Suspended);
+        pause.clear();
+        assert!(!pause.is_active());
+        assert!(!pause.audio_muted);
+    }
+}
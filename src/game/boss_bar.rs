@@ -0,0 +1,117 @@
+//! Boss Bar - A boss's health, cut where its fight turns
+//!
+//! A boss's health is drawn as a segmented bar, with a marker at each share
+//! of health where the fight passes into a new phase, under a name plate
+//! with the boss's title and the phase it is in:
+//! - a boss with a phase table in the game data is cut at its thresholds,
+//!   and its phases go by the table's names
+//! - any other boss is cut where every enemy's posture turns: wary at three
+//!   quarters, staggered at half, wounded at a quarter, and dying at a
+//!   tenth, where an execution comes within reach
+//!
+//! The bar is filled in the theme's enemy health color and framed in the
+//! color of the zone the fight is in.
+//!
+//! Design: the plate is put together once, when the fight starts, from the
+//! deep lore's boss records (for the title) and the game data's boss
+//! templates (for the phases and, failing the lore, the title). Drawing it
+//! only asks which phase the health is in and which cells the markers fall
+//! on.
+
+use super::deep_lore::get_dungeon_lore;
+use super::enemy::Enemy;
+use super::enemy_visuals::EnemyPosture;
+use crate::data::GameData;
+
+/// A phase of a boss fight
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseMark {
+    /// The share of health the phase begins at, from above
+    pub threshold: f32,
+    pub name: String,
+}
+
+/// What the boss bar shows beside the health itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct BossPlate {
+    pub title: Option<String>,
+    /// From full health down
+    pub phases: Vec<PhaseMark>,
+}
+
+impl BossPlate {
+    pub fn for_boss(enemy: &Enemy, data: &GameData) -> Self {
+        let template = data.enemies.bosses.values().find(|b| b.name == enemy.name);
+        let title = get_dungeon_lore().bosses.into_iter()
+            .find(|b| b.name == enemy.name)
+            .map(|b| b.title)
+            .or_else(|| template.map(|t| t.title.clone()));
+        let mut phases: Vec<PhaseMark> = match template.filter(|t| !t.phases.is_empty()) {
+            Some(template) => template.phases.iter()
+                .map(|p| PhaseMark { threshold: p.hp_threshold.clamp(0.0, 1.0), name: p.name.clone() })
+                .collect(),
+            None => EnemyPosture::ALL.iter()
+                .map(|p| {
+                    let mut name = p.name().to_string();
+                    name[..1].make_ascii_uppercase();
+                    PhaseMark { threshold: p.threshold(), name }
+                })
+                .collect(),
+        };
+        phases.sort_by(|a, b| b.threshold.total_cmp(&a.threshold));
+        Self { title, phases }
+    }
+
+    /// The phase at `hp_pct` of health: the last its health has come down to
+    pub fn phase(&self, hp_pct: f32) -> Option<&PhaseMark> {
+        self.phases.iter().rev().find(|p| hp_pct <= p.threshold).or(self.phases.first())
+    }
+
+    /// The cells of a bar `width` wide that the phase markers fall on, one
+    /// for each phase begun below full health
+    pub fn markers(&self, width: usize) -> Vec<usize> {
+        self.phases.iter()
+            .filter(|p| p.threshold > 0.0 && p.threshold < 1.0)
+            .map(|p| ((p.threshold * width as f32).round() as usize).min(width.saturating_sub(1)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boss(name: &str) -> Enemy {
+        let mut enemy = Enemy::random_boss(1);
+        enemy.name = name.to_string();
+        enemy
+    }
+
+    #[test]
+    fn test_a_boss_with_a_phase_table_is_cut_at_its_thresholds() {
+        let plate = BossPlate::for_boss(&boss("The Void Herald"), &GameData::new());
+        // The lore's title comes before the template's
+        assert_eq!(plate.title.as_deref(), Some("Voice of the Breach"));
+        let names: Vec<&str> = plate.phases.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Manifesting", "Awakened", "Ascendant", "Absolute"]);
+        assert_eq!(plate.phase(1.0).unwrap().name, "Manifesting");
+        assert_eq!(plate.phase(0.6).unwrap().name, "Awakened");
+        assert_eq!(plate.phase(0.25).unwrap().name, "Absolute");
+        assert_eq!(plate.markers(40), [30, 20, 10]);
+    }
+
+    #[test]
+    fn test_any_other_boss_is_cut_where_its_posture_turns() {
+        let plate = BossPlate::for_boss(&boss("The Hollow Knight"), &GameData::new());
+        assert_eq!(plate.title.as_deref(), Some("Guardian of the Fallen Throne"));
+        assert_eq!(plate.phase(0.05).unwrap().name, "Dying");
+        assert_eq!(plate.phase(0.3).unwrap().name, "Staggered");
+        assert_eq!(plate.markers(20), [15, 10, 5, 2]);
+        // A bar too narrow for its markers keeps them inside it
+        assert!(plate.markers(1).iter().all(|m| *m == 0));
+
+        let nameless = BossPlate::for_boss(&boss("Someone Else"), &GameData::new());
+        assert_eq!(nameless.title, None);
+        assert_eq!(nameless.phase(2.0).unwrap().name, "Confident");
+    }
+}
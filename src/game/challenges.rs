@@ -0,0 +1,270 @@
+//! Community Challenges - Shareable runs with a score to beat
+//!
+//! A challenge is a small RON file bundling everything needed to replay
+//! someone else's run:
+//! - a seed (the world and its corruption)
+//! - run modifiers and their levels
+//! - an optional class lock
+//! - an optional word pack (`<config>/wordpacks/<name>.ron`, a list of words)
+//! - the target score
+//!
+//! Install one with `keyboard-warrior --import-challenge <file>` or by
+//! dropping it into `<config>/challenges/`. The Challenges screen lists
+//! everything installed with your personal best against each.
+//!
+//! Every day also brings a daily run: a challenge built from the date, so
+//! everyone playing that day gets the same seed, class and modifiers.
+//!
+//! Design: challenge files are never written by the game except on
+//! import; personal bests live in their own file so a challenge can be
+//! shared or replaced without losing them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+
+use super::config::get_config_dir;
+use super::player::Class;
+use super::run_modifiers::Modifier;
+
+/// A shareable challenge run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Challenge {
+    /// Stable id; also the installed file name
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub description: String,
+    /// Seeds the world (corruption, prophecies, motifs)
+    pub seed: u64,
+    /// Run modifiers and their levels
+    #[serde(default)]
+    pub modifiers: Vec<(Modifier, u32)>,
+    /// The class every attempt must play
+    #[serde(default)]
+    pub class_lock: Option<Class>,
+    /// Word pack to draw combat words from, by name
+    #[serde(default)]
+    pub word_pack: Option<String>,
+    /// Score to beat
+    pub target_score: u64,
+}
+
+impl Challenge {
+    /// Check a challenge is fit to install
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() || !self.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(format!("challenge id '{}' must be letters, digits, '_' or '-'", self.id));
+        }
+        if self.name.trim().is_empty() {
+            return Err("challenge has no name".to_string());
+        }
+        if self.target_score == 0 {
+            return Err("challenge target score must be above zero".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Personal best score per challenge id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChallengeBests {
+    pub bests: HashMap<String, u64>,
+}
+
+impl ChallengeBests {
+    pub fn best(&self, id: &str) -> Option<u64> {
+        self.bests.get(id).copied()
+    }
+
+    /// Record a score; returns true if it is a new personal best
+    pub fn record(&mut self, id: &str, score: u64) -> bool {
+        if self.best(id).is_some_and(|best| best >= score) {
+            return false;
+        }
+        self.bests.insert(id.to_string(), score);
+        true
+    }
+}
+
+/// Classes a daily run can lock to
+const DAILY_CLASSES: [Class; 5] = [Class::Wordsmith, Class::Scribe, Class::Spellweaver, Class::Barbarian, Class::Trickster];
+/// Score a daily run sets to beat
+const DAILY_TARGET: u64 = 10000;
+
+/// Level of each modifier a daily run draws
+const DAILY_LEVEL: u32 = 1;
+
+/// Modifiers a daily run draws two of
+fn daily_modifiers() -> [Modifier; 4] {
+    [
+        Modifier::ToughEnemies { health_multiplier: 1.3 },
+        Modifier::DangerousEnemies { damage_multiplier: 1.3 },
+        Modifier::GoldDrain { reduction_percent: 0.2 },
+        Modifier::WordChain,
+    ]
+}
+
+/// The daily run for `date`: the same for everyone who plays it that day
+pub fn daily(date: NaiveDate) -> Challenge {
+    let seed = splitmix(date.num_days_from_ce() as u64);
+    let pool = daily_modifiers();
+    let first = (seed % pool.len() as u64) as usize;
+    let second = (first + 1 + (seed >> 8) as usize % (pool.len() - 1)) % pool.len();
+    let class = DAILY_CLASSES[(seed >> 16) as usize % DAILY_CLASSES.len()];
+    Challenge {
+        id: format!("daily-{}", date.format("%Y-%m-%d")),
+        name: format!("Daily Run {}", date.format("%Y-%m-%d")),
+        author: "the Archives".to_string(),
+        description: "Today's seed, class and modifiers, the same for everyone.".to_string(),
+        seed,
+        modifiers: vec![(pool[first].clone(), DAILY_LEVEL), (pool[second].clone(), DAILY_LEVEL)],
+        class_lock: Some(class),
+        word_pack: None,
+        target_score: DAILY_TARGET,
+    }
+}
+
+/// Spread a small number over all 64 bits
+fn splitmix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Score for a finished run
+pub fn run_score(floor: i32, enemies_defeated: i32, words_typed: i32, best_wpm: f64, victory: bool) -> u64 {
+    let score = floor.max(0) as u64 * 1000
+        + enemies_defeated.max(0) as u64 * 100
+        + words_typed.max(0) as u64 * 5
+        + (best_wpm.max(0.0) * 10.0) as u64;
+    if victory { score * 2 } else { score }
+}
+
+/// Where installed challenges live
+pub fn challenges_dir() -> PathBuf {
+    get_config_dir().join("challenges")
+}
+
+/// Where word packs live
+pub fn wordpacks_dir() -> PathBuf {
+    get_config_dir().join("wordpacks")
+}
+
+fn bests_path() -> PathBuf {
+    get_config_dir().join("challenge_bests.ron")
+}
+
+/// Parse a challenge file
+pub fn load_challenge(path: &Path) -> Result<Challenge, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let challenge: Challenge = ron::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    challenge.validate()?;
+    Ok(challenge)
+}
+
+/// Every valid challenge installed, sorted by name (broken files are skipped)
+pub fn load_installed() -> Vec<Challenge> {
+    let Ok(entries) = fs::read_dir(challenges_dir()) else {
+        return Vec::new();
+    };
+    let mut challenges: Vec<Challenge> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "ron"))
+        .filter_map(|p| load_challenge(&p).ok())
+        .collect();
+    challenges.sort_by(|a, b| a.name.cmp(&b.name));
+    challenges
+}
+
+/// Install a challenge file into the challenges directory
+pub fn import(path: &Path) -> Result<Challenge, String> {
+    let challenge = load_challenge(path)?;
+    let dir = challenges_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::copy(path, dir.join(format!("{}.ron", challenge.id))).map_err(|e| e.to_string())?;
+    Ok(challenge)
+}
+
+/// Words from a named word pack (empty if missing or unreadable)
+pub fn load_word_pack(name: &str) -> Vec<String> {
+    fs::read_to_string(wordpacks_dir().join(format!("{}.ron", name)))
+        .ok()
+        .and_then(|content| ron::from_str::<Vec<String>>(&content).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|w| !w.trim().is_empty())
+        .collect()
+}
+
+pub fn load_bests() -> ChallengeBests {
+    fs::read_to_string(bests_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_bests(bests: &ChallengeBests) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(bests, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(bests_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"(
+        id: "glass_cannon",
+        name: "Glass Cannon",
+        author: "dr_baklava",
+        seed: 1337,
+        modifiers: [(DangerousEnemies(damage_multiplier: 1.5), 2)],
+        class_lock: Some(Spellweaver),
+        word_pack: Some("runes"),
+        target_score: 12000,
+    )"#;
+
+    #[test]
+    fn test_parse_challenge_file() {
+        let challenge: Challenge = ron::from_str(SAMPLE).unwrap();
+        assert!(challenge.validate().is_ok());
+        assert_eq!(challenge.class_lock, Some(Class::Spellweaver));
+        assert_eq!(challenge.modifiers.len(), 1);
+        assert!(challenge.description.is_empty());
+
+        let bad = Challenge { id: "../escape".to_string(), ..challenge };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_personal_bests() {
+        let mut bests = ChallengeBests::default();
+        assert!(bests.record("glass_cannon", 5000));
+        assert!(!bests.record("glass_cannon", 4000));
+        assert!(bests.record("glass_cannon", 9000));
+        assert_eq!(bests.best("glass_cannon"), Some(9000));
+        assert!(run_score(5, 10, 100, 80.0, true) > run_score(5, 10, 100, 80.0, false));
+    }
+
+    #[test]
+    fn test_daily_run_is_fixed_by_the_date() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let run = daily(day);
+        assert_eq!(run.id, "daily-2026-10-16");
+        assert!(run.validate().is_ok());
+        assert_eq!(run.seed, daily(day).seed);
+        assert_eq!(run.class_lock, daily(day).class_lock);
+        assert_eq!(run.modifiers.len(), 2);
+        assert_ne!(format!("{:?}", run.modifiers[0]), format!("{:?}", run.modifiers[1]));
+        assert_ne!(run.seed, daily(day.succ_opt().unwrap()).seed);
+    }
+}
@@ -0,0 +1,138 @@
+//! Channeling - A stream of words that burns for as long as it flows
+//!
+//! Typing `/channel` before a prompt opens a channel: the enemy takes damage
+//! every moment the channel holds, and zone words keep coming one after
+//! another with no enemy turn in between. Every word landed intensifies the
+//! burn. Pausing too long between keys, or mistyping a word, breaks the
+//! channel and the enemy finally gets its turn.
+//!
+//! Design: the channel only keeps the clock and the burn; CombatState feeds
+//! the words and decides when a word breaks it.
+
+use std::time::{Duration, Instant};
+
+/// What the player types on the command line to open a channel
+pub const CHANNEL_KEYWORD: &str = "channel";
+/// Longest pause between keystrokes before the channel breaks
+pub const MAX_PAUSE: Duration = Duration::from_millis(1200);
+/// Turns before another channel can be opened
+pub const CHANNEL_COOLDOWN: i32 = 4;
+/// Damage per second on floor 1, before intensity
+const BASE_DPS: f32 = 4.0;
+/// Extra damage per second per floor
+const DPS_PER_FLOOR: f32 = 0.5;
+/// Intensity gained per word landed in the channel
+const INTENSITY_PER_WORD: f32 = 0.2;
+/// Most intensity a channel can build
+const MAX_INTENSITY: f32 = 3.0;
+
+/// Whether a command-line entry asks for a channel
+pub fn is_channel_command(input: &str) -> bool {
+    input.trim().trim_start_matches('/').eq_ignore_ascii_case(CHANNEL_KEYWORD)
+}
+
+/// An open channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Channel {
+    /// Words landed while channeling
+    pub words: u32,
+    /// Damage the channel has dealt so far
+    pub dealt: i32,
+    /// Damage dealt since the last word landed
+    word_dealt: i32,
+    /// When the last key landed; None until the first
+    last_key: Option<Instant>,
+    /// Fractional damage not yet dealt
+    carry: f32,
+    dps: f32,
+}
+
+impl Channel {
+    pub fn new(floor: u32) -> Self {
+        Self {
+            words: 0,
+            dealt: 0,
+            word_dealt: 0,
+            last_key: None,
+            carry: 0.0,
+            dps: BASE_DPS + floor.saturating_sub(1) as f32 * DPS_PER_FLOOR,
+        }
+    }
+
+    /// A key landed: the channel holds
+    pub fn on_key(&mut self, now: Instant) {
+        self.last_key = Some(now);
+    }
+
+    /// Keep the pause clock frozen (while the game is paused)
+    pub fn hold(&mut self, now: Instant) {
+        if self.last_key.is_some() {
+            self.last_key = Some(now);
+        }
+    }
+
+    /// Whether the player has paused too long (only once typing began)
+    pub fn broken_by_pause(&self, now: Instant) -> bool {
+        self.last_key.is_some_and(|last| now.saturating_duration_since(last) > MAX_PAUSE)
+    }
+
+    /// A word landed; returns the damage the channel dealt during it
+    pub fn on_word(&mut self) -> i32 {
+        self.words += 1;
+        std::mem::take(&mut self.word_dealt)
+    }
+
+    /// Damage multiplier from the words landed so far
+    pub fn intensity(&self) -> f32 {
+        (1.0 + self.words as f32 * INTENSITY_PER_WORD).min(MAX_INTENSITY)
+    }
+
+    /// Burn for `secs` scaled by `mult`; returns the whole damage to deal now
+    pub fn burn(&mut self, secs: f32, mult: f32) -> i32 {
+        if self.last_key.is_none() {
+            return 0;
+        }
+        self.carry += self.dps * self.intensity() * mult * secs;
+        let damage = self.carry.floor();
+        self.carry -= damage;
+        let damage = damage as i32;
+        self.dealt += damage;
+        self.word_dealt += damage;
+        damage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_breaks_only_after_typing_starts() {
+        let start = Instant::now();
+        let mut channel = Channel::new(1);
+        assert!(!channel.broken_by_pause(start + Duration::from_secs(10)));
+        assert_eq!(channel.burn(1.0, 1.0), 0);
+        channel.on_key(start);
+        assert!(!channel.broken_by_pause(start + MAX_PAUSE));
+        assert!(channel.broken_by_pause(start + MAX_PAUSE + Duration::from_millis(1)));
+        assert!(is_channel_command(" /Channel"));
+        assert!(!is_channel_command("chan"));
+    }
+
+    #[test]
+    fn test_burn_carries_fractions_and_intensifies() {
+        let mut channel = Channel::new(1);
+        channel.on_key(Instant::now());
+        // 4 damage per second, dealt in whole points
+        assert_eq!(channel.burn(0.1, 1.0), 0);
+        assert_eq!(channel.burn(0.2, 1.0), 1);
+        assert_eq!(channel.burn(0.1, 1.0), 0);
+        assert_eq!(channel.on_word(), 1);
+        for _ in 0..20 {
+            channel.on_word();
+        }
+        assert_eq!(channel.intensity(), MAX_INTENSITY);
+        assert_eq!(channel.burn(1.0, 1.0), 12);
+        assert_eq!(channel.dealt, 13);
+    }
+}
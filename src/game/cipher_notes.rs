@@ -0,0 +1,223 @@
+//! Cipher Notes - Cipher's encoded messages, found and broken
+//!
+//! Cipher leaves notes for the player in treasure rooms, each in a
+//! substitution cipher of its own: every letter stands for another, the
+//! same one throughout the note. A note is broken by typing guesses at
+//! what it says:
+//! - every letter guessed in its right place is revealed wherever it stands
+//! - a guess that doesn't break the note also buys a hint, the commonest
+//!   letter still hidden
+//! - the note is broken once every letter in it is known
+//!
+//! A broken note earns standing with the ShadowWriters, less for every hint
+//! it took, and Cipher trusting you that far pins a clue to the clue board.
+//!
+//! Design: the notes are data in `NOTES`, like the letters. A note's key is
+//! shuffled when it is found, so no two runs read alike. Notes found are
+//! kept with the run; which have been found is a world flag, so each turns
+//! up once a timeline.
+
+use std::collections::BTreeSet;
+
+use rand::prelude::*;
+
+use super::world_state::WorldState;
+
+/// Chance a treasure room holds a note not yet found
+pub const NOTE_CHANCE: f32 = 0.2;
+
+/// ShadowWriters standing for a note broken without a hint
+pub const BREAK_STANDING: i32 = 12;
+
+/// Standing each hint costs
+pub const HINT_COST: i32 = 2;
+
+/// Least standing a broken note earns, however many hints it took
+const MIN_STANDING: i32 = 2;
+
+/// A note Cipher leaves somewhere below
+#[derive(Debug, Clone, Copy)]
+pub struct NoteDef {
+    pub id: &'static str,
+    /// Where it was tucked
+    pub found: &'static str,
+    pub plain: &'static str,
+}
+
+pub static NOTES: [NoteDef; 5] = [
+    NoteDef {
+        id: "cipher_under_the_lid",
+        found: "pinned under the chest's lid",
+        plain: "you are not the first to open this chest and you will not be the last",
+    },
+    NoteDef {
+        id: "cipher_archmage",
+        found: "folded into a hollow coin",
+        plain: "the archmage reads every letter that passes the gate so we do not use letters",
+    },
+    NoteDef {
+        id: "cipher_your_hand",
+        found: "written on the back of a torn map",
+        plain: "the journal is in your own hand and you hid it from yourself",
+    },
+    NoteDef {
+        id: "cipher_the_breach",
+        found: "scratched into the chest's iron band",
+        plain: "the breach is not a wound it is a door someone is holding shut",
+    },
+    NoteDef {
+        id: "cipher_trust",
+        found: "tucked in a glove left behind",
+        plain: "if you can read this then you think the way we think and that is enough",
+    },
+];
+
+/// A note found this run, broken or not
+#[derive(Debug, Clone)]
+pub struct CipherNote {
+    pub def: &'static NoteDef,
+    /// What each plain letter is written as, a..z
+    key: [char; 26],
+    /// Plain letters known so far
+    known: BTreeSet<char>,
+    pub guesses: u32,
+    pub hints: u32,
+}
+
+/// What a guess at a note came to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Guess {
+    /// The note is broken
+    Broken,
+    /// Not yet: letters the guess revealed, and the hint it bought
+    Closer { revealed: usize, hint: Option<char> },
+}
+
+fn index(c: char) -> Option<usize> {
+    c.is_ascii_lowercase().then(|| (c as u8 - b'a') as usize)
+}
+
+impl CipherNote {
+    /// A note with a freshly shuffled key; no letter stands for itself
+    pub fn new<R: Rng>(def: &'static NoteDef, rng: &mut R) -> Self {
+        let alphabet: Vec<char> = ('a'..='z').collect();
+        let mut key = ['a'; 26];
+        loop {
+            key.copy_from_slice(&alphabet);
+            key.shuffle(rng);
+            if key.iter().zip(&alphabet).all(|(a, b)| a != b) {
+                break;
+            }
+        }
+        Self { def, key, known: BTreeSet::new(), guesses: 0, hints: 0 }
+    }
+
+    /// The note as it was found
+    pub fn cipher_text(&self) -> String {
+        self.def.plain.chars().map(|c| index(c).map_or(c, |i| self.key[i])).collect()
+    }
+
+    /// The note as far as it is known, `·` for letters still hidden
+    pub fn partial(&self) -> String {
+        self.def.plain.chars()
+            .map(|c| if index(c).is_none() || self.known.contains(&c) { c } else { '·' })
+            .collect()
+    }
+
+    pub fn broken(&self) -> bool {
+        self.def.plain.chars().filter(|c| index(*c).is_some()).all(|c| self.known.contains(&c))
+    }
+
+    /// Standing the note earns once broken
+    pub fn standing(&self) -> i32 {
+        (BREAK_STANDING - self.hints as i32 * HINT_COST).max(MIN_STANDING)
+    }
+
+    /// The commonest letter of the note still hidden
+    fn hint(&self) -> Option<char> {
+        let mut hidden: Vec<char> = self.def.plain.chars()
+            .filter(|c| index(*c).is_some() && !self.known.contains(c))
+            .collect();
+        hidden.sort_unstable();
+        hidden.dedup();
+        let count = |c: &char| self.def.plain.chars().filter(|p| p == c).count();
+        hidden.into_iter().max_by(|a, b| count(a).cmp(&count(b)).then(b.cmp(a)))
+    }
+
+    /// Guess at the note: letters typed in their right place are revealed
+    pub fn guess(&mut self, typed: &str) -> Guess {
+        self.guesses += 1;
+        let before = self.known.len();
+        for (t, p) in typed.to_lowercase().chars().zip(self.def.plain.chars()) {
+            if t == p && index(p).is_some() {
+                self.known.insert(p);
+            }
+        }
+        if self.broken() {
+            return Guess::Broken;
+        }
+        let revealed = self.known.len() - before;
+        let hint = self.hint();
+        if let Some(letter) = hint {
+            self.known.insert(letter);
+            self.hints += 1;
+            if self.broken() {
+                return Guess::Broken;
+            }
+        }
+        Guess::Closer { revealed, hint }
+    }
+}
+
+fn flag(def: &NoteDef) -> String {
+    format!("found_{}", def.id)
+}
+
+/// Find a note not yet found this timeline, marking it found
+pub fn find<R: Rng>(world: &mut WorldState, rng: &mut R) -> Option<CipherNote> {
+    let def = NOTES.iter().filter(|d| !world.has_flag(&flag(d))).choose(rng)?;
+    world.set_flag(&flag(def));
+    Some(CipherNote::new(def, rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notes_are_found_once_and_keyed_fresh() {
+        let mut world = WorldState::default();
+        let mut rng = StdRng::seed_from_u64(3);
+        let found: Vec<CipherNote> = std::iter::from_fn(|| find(&mut world, &mut rng)).collect();
+        assert_eq!(found.len(), NOTES.len());
+        for note in &found {
+            let cipher = note.cipher_text();
+            assert_eq!(cipher.len(), note.def.plain.len());
+            assert!(cipher.chars().zip(note.def.plain.chars()).all(|(c, p)| (c == ' ') == (p == ' ') && (p == ' ' || c != p)));
+            assert!(note.partial().chars().all(|c| c == '·' || c == ' '));
+        }
+        world.start_fresh();
+        assert!(find(&mut world, &mut rng).is_some());
+    }
+
+    #[test]
+    fn test_guesses_reveal_letters_and_hints_cost_standing() {
+        let mut note = CipherNote::new(&NOTES[1], &mut StdRng::seed_from_u64(5));
+        // "the" in its right place reveals t, h and e everywhere; a hint is added
+        let Guess::Closer { revealed, hint } = note.guess("the") else {
+            panic!("three letters can't break a note");
+        };
+        assert_eq!(revealed, 3);
+        assert!(hint.is_some() && note.partial().starts_with("the "));
+        assert_eq!(note.standing(), BREAK_STANDING - HINT_COST);
+
+        assert_eq!(note.guess(NOTES[1].plain), Guess::Broken);
+        assert_eq!(note.partial(), NOTES[1].plain);
+        assert_eq!(note.guesses, 2);
+
+        // Guessing nothing at all still breaks it in the end, for little
+        let mut note = CipherNote::new(&NOTES[0], &mut StdRng::seed_from_u64(5));
+        while note.guess("") != Guess::Broken {}
+        assert_eq!(note.standing(), MIN_STANDING);
+    }
+}
@@ -0,0 +1,148 @@
+//! Class Abilities - Every class has a trick up its sleeve
+//!
+//! Typing `/` before or during a prompt opens a command line; typing an ability's
+//! keyword and pressing Enter uses it:
+//! - Wordsmith: /reforge a bad word, /temper the next strike
+//! - Codebreaker: /decrypt the enemy's weakness, /patch up a shield
+//! - Chronicler: /replay the last perfect word, /annotate for more time,
+//!   /inscribe a sentence from memory
+//! - Freelancer borrows a little from everyone
+//!
+//! Design: abilities are plain data here; CombatState applies their effects
+//! and keeps the cooldowns, counted in turns so they survive pauses.
+
+use super::player_avatar::PlayerClass;
+
+/// Opens the ability command line when typed before or during a prompt
+pub const COMMAND_PREFIX: char = '/';
+
+/// An active ability triggered by typing its keyword
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClassAbility {
+    Reforge,
+    Temper,
+    Decrypt,
+    Patch,
+    Replay,
+    Annotate,
+    Inscribe,
+}
+
+impl ClassAbility {
+    /// What the player types (after the prefix) to use it
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Reforge => "reforge",
+            Self::Temper => "temper",
+            Self::Decrypt => "decrypt",
+            Self::Patch => "patch",
+            Self::Replay => "replay",
+            Self::Annotate => "annotate",
+            Self::Inscribe => "inscribe",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Reforge => "Hammer the current word into a new one, no penalty",
+            Self::Temper => "Your next word strikes half again as hard",
+            Self::Decrypt => "Reveal the enemy's weakness and strike it this turn",
+            Self::Patch => "Raise a shield that grows with the floor",
+            Self::Replay => "Your last perfect word strikes again",
+            Self::Annotate => "Scribble notes in the margin for more time",
+            Self::Inscribe => "Study a sentence, then write it from memory for triple damage",
+        }
+    }
+
+    /// Turns before it can be used again
+    pub fn cooldown(&self) -> i32 {
+        match self {
+            Self::Reforge | Self::Annotate => 3,
+            Self::Temper | Self::Decrypt | Self::Inscribe => 4,
+            Self::Patch | Self::Replay => 5,
+        }
+    }
+}
+
+/// The abilities a class can use
+pub fn abilities_for(class: PlayerClass) -> &'static [ClassAbility] {
+    match class {
+        PlayerClass::Wordsmith => &[ClassAbility::Reforge, ClassAbility::Temper],
+        PlayerClass::Codebreaker => &[ClassAbility::Decrypt, ClassAbility::Patch],
+        PlayerClass::Chronicler => &[ClassAbility::Replay, ClassAbility::Annotate, ClassAbility::Inscribe],
+        PlayerClass::Freelancer => &[ClassAbility::Reforge, ClassAbility::Annotate],
+    }
+}
+
+/// Match a typed command against the class's abilities (case-insensitive)
+pub fn parse(input: &str, class: PlayerClass) -> Option<ClassAbility> {
+    let input = input.trim().trim_start_matches(COMMAND_PREFIX);
+    abilities_for(class)
+        .iter()
+        .copied()
+        .find(|a| a.keyword().eq_ignore_ascii_case(input))
+}
+
+/// Turn on which each used ability becomes ready again
+#[derive(Debug, Clone, Default)]
+pub struct Cooldowns {
+    ready_on: Vec<(ClassAbility, i32)>,
+}
+
+impl Cooldowns {
+    /// Turns left before an ability can be used (0 = ready)
+    pub fn turns_left(&self, ability: ClassAbility, turn: i32) -> i32 {
+        self.ready_on
+            .iter()
+            .find(|(a, _)| *a == ability)
+            .map(|(_, ready)| (ready - turn).max(0))
+            .unwrap_or(0)
+    }
+
+    pub fn is_ready(&self, ability: ClassAbility, turn: i32) -> bool {
+        self.turns_left(ability, turn) == 0
+    }
+
+    /// Put an ability on cooldown starting this turn
+    pub fn start(&mut self, ability: ClassAbility, turn: i32) {
+        self.ready_on.retain(|(a, _)| *a != ability);
+        self.ready_on.push((ability, turn + ability.cooldown()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keywords() {
+        assert_eq!(parse("reforge", PlayerClass::Wordsmith), Some(ClassAbility::Reforge));
+        assert_eq!(parse(" /Temper ", PlayerClass::Wordsmith), Some(ClassAbility::Temper));
+        // Another class's ability is not yours to use
+        assert_eq!(parse("decrypt", PlayerClass::Wordsmith), None);
+        assert_eq!(parse("", PlayerClass::Chronicler), None);
+    }
+
+    #[test]
+    fn test_every_class_has_abilities() {
+        for class in [PlayerClass::Freelancer, PlayerClass::Wordsmith,
+                      PlayerClass::Codebreaker, PlayerClass::Chronicler] {
+            let abilities = abilities_for(class);
+            assert!((2..=3).contains(&abilities.len()), "{:?}", class);
+            for ability in abilities {
+                assert_eq!(parse(ability.keyword(), class), Some(*ability));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cooldowns() {
+        let mut cooldowns = Cooldowns::default();
+        assert!(cooldowns.is_ready(ClassAbility::Replay, 1));
+        cooldowns.start(ClassAbility::Replay, 1);
+        assert_eq!(cooldowns.turns_left(ClassAbility::Replay, 2), 4);
+        assert!(!cooldowns.is_ready(ClassAbility::Replay, 5));
+        assert!(cooldowns.is_ready(ClassAbility::Replay, 6));
+        assert!(cooldowns.is_ready(ClassAbility::Annotate, 2));
+    }
+}
@@ -0,0 +1,120 @@
+//! Class Builds - What each class brings into the dungeon
+//!
+//! A class is more than its abilities:
+//! - a Discipline skill tree of its own, unlocked with points from level-ups
+//! - a starting relic carried from the first room
+//! - a bias in the prompts it is given: Codebreakers get symbol-heavy
+//!   words, Chroniclers get whole sentences
+//!
+//! Design: the Discipline trees live with the other skill trees
+//! (`SkillTree::for_class`); this module holds the rest of the kit.
+//! GameState hands it out at the start of a run, CombatState applies the
+//! prompt bias.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::player_avatar::PlayerClass;
+
+/// Skill points granted per level gained
+pub const SKILL_POINTS_PER_LEVEL: u32 = 1;
+/// Chance a single-word prompt follows the class's bias
+pub const BIAS_CHANCE: f32 = 0.3;
+
+/// Symbol-heavy words a Codebreaker is handed
+pub const SYMBOL_WORDS: [&str; 16] = [
+    "i++", "0xFF", "$PATH", "#include", "a->b", "x!=y", "<T>", "&mut",
+    "std::io", "~/.ssh", "[0..n]", "@root", "{}", "a&&b", "x^2", "%d",
+];
+
+/// What kind of prompt a class leans toward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordBias {
+    #[default]
+    None,
+    /// Words full of symbols
+    Symbols,
+    /// Whole sentences in place of words
+    Sentences,
+}
+
+impl WordBias {
+    pub fn for_class(class: PlayerClass) -> Self {
+        match class {
+            PlayerClass::Codebreaker => Self::Symbols,
+            PlayerClass::Chronicler => Self::Sentences,
+            PlayerClass::Wordsmith | PlayerClass::Freelancer => Self::None,
+        }
+    }
+
+    /// Whether this prompt follows the bias; Symbols also picks the word
+    pub fn roll(self, rng: &mut impl Rng) -> Option<BiasedPrompt> {
+        if self == Self::None || rng.gen::<f32>() >= BIAS_CHANCE {
+            return None;
+        }
+        match self {
+            Self::Symbols => SYMBOL_WORDS.choose(rng).map(|w| BiasedPrompt::Word(w.to_string())),
+            _ => Some(BiasedPrompt::Sentence),
+        }
+    }
+}
+
+/// A prompt chosen by the class's bias
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BiasedPrompt {
+    Word(String),
+    /// A sentence, drawn by the caller
+    Sentence,
+}
+
+/// The relic a class sets out carrying (a lore artifact)
+pub fn starting_relic(class: PlayerClass) -> &'static str {
+    match class {
+        PlayerClass::Wordsmith => "The Eternal Flame",
+        PlayerClass::Codebreaker => "The Staff of Binding",
+        PlayerClass::Chronicler => "The Original Manuscript",
+        PlayerClass::Freelancer => "The Songlines",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::artifacts::Relic;
+    use crate::game::skills::SkillTree;
+
+    const CLASSES: [PlayerClass; 4] = [
+        PlayerClass::Freelancer, PlayerClass::Wordsmith,
+        PlayerClass::Codebreaker, PlayerClass::Chronicler,
+    ];
+
+    #[test]
+    fn test_every_class_has_its_own_kit() {
+        for class in CLASSES {
+            assert!(Relic::named(starting_relic(class)).is_some());
+        }
+        let relics: Vec<_> = CLASSES.iter().map(|c| starting_relic(*c)).collect();
+        assert!(relics.iter().enumerate().all(|(i, r)| !relics[..i].contains(r)));
+
+        let mut rng = rand::thread_rng();
+        assert!((0..50).all(|_| WordBias::for_class(PlayerClass::Wordsmith).roll(&mut rng).is_none()));
+        let symbols: Vec<_> = (0..200).filter_map(|_| WordBias::Symbols.roll(&mut rng)).collect();
+        assert!(!symbols.is_empty() && symbols.len() < 200);
+        assert!(symbols.iter().all(|p| matches!(p, BiasedPrompt::Word(w) if SYMBOL_WORDS.contains(&w.as_str()))));
+    }
+
+    #[test]
+    fn test_discipline_unlocks_in_order() {
+        let mut tree = SkillTree::for_class(PlayerClass::Codebreaker);
+        let ids: Vec<String> = tree.discipline().iter().map(|s| s.id.clone()).collect();
+        assert_eq!(ids, ["codebreaker_1", "codebreaker_2", "codebreaker_3"]);
+
+        tree.skill_points = 3;
+        assert!(!tree.unlock_skill("codebreaker_2"));
+        assert!(tree.unlock_skill("codebreaker_1"));
+        assert!(tree.unlock_skill("codebreaker_2"));
+        assert_eq!(tree.skill_points, 0);
+        assert!((tree.get_evasion_chance() - 0.08).abs() < 1e-4);
+        assert!(SkillTree::for_class(PlayerClass::Chronicler).discipline()[0].id.starts_with("chronicler"));
+    }
+}
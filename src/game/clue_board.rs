@@ -0,0 +1,185 @@
+//! Clue Board - Pinning down who you were
+//!
+//! The player mystery's clues are pinned to a board as you find them. Each
+//! chapter of the descent has its own clues, and none can be found before
+//! its chapter. Within a chapter, each clue turns up its own way:
+//! - some are there as soon as you reach the chapter
+//! - some take a won fight, a dream, an artifact found, or one of
+//!   Cipher's notes broken
+//! - some need a faction to trust you with what it knows
+//!
+//! A pinned clue shows only what you found. What it suggests stays hidden
+//! until you stop and think it through: type the clue's short reflection
+//! cleanly enough and the suggestion is written beneath it.
+//!
+//! Design: the clues themselves come from deep_lore's PlayerMystery; this
+//! module adds how each is found and what you reflect on, and the board
+//! only records which clues are pinned and understood.
+
+use super::deep_lore::{create_player_mystery, Clue};
+use super::encounter_runner::{accuracy, TypingGrade};
+use super::faction_system::FactionRelations;
+use super::narrative::Faction;
+
+/// Standing a faction needs before it shares its clue
+pub const TRUSTED_STANDING: i32 = 25;
+
+/// How a clue turns up, once its chapter is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClueTrigger {
+    /// As soon as the chapter is reached
+    Reached,
+    /// After winning a fight
+    FightWon,
+    /// After a dream at a rest site
+    Dreamt,
+    /// After finding an artifact in the dungeon
+    Artifact,
+    /// After breaking one of Cipher's notes
+    Decoded,
+    /// Once the faction trusts you
+    Trusted(Faction),
+}
+
+/// How a mystery clue is found and what you reflect on to understand it
+#[derive(Debug, Clone, Copy)]
+pub struct ClueSource {
+    pub id: &'static str,
+    pub trigger: ClueTrigger,
+    pub reflection: &'static str,
+}
+
+pub static CLUE_SOURCES: [ClueSource; 9] = [
+    ClueSource { id: "amnesia", trigger: ClueTrigger::Reached, reflection: "no one forgets this much by accident" },
+    ClueSource { id: "strange_recognition", trigger: ClueTrigger::FightWon, reflection: "they know my face and fear it" },
+    ClueSource {
+        id: "mages_guild_file",
+        trigger: ClueTrigger::Trusted(Faction::MagesGuild),
+        reflection: "they sealed my name away",
+    },
+    ClueSource { id: "dreams_of_fire", trigger: ClueTrigger::Dreamt, reflection: "i have seen the tower burn" },
+    ClueSource { id: "malachar_portrait", trigger: ClueTrigger::Reached, reflection: "the archon wears my face" },
+    ClueSource { id: "elder_stone_resonance", trigger: ClueTrigger::Artifact, reflection: "the stone remembers my hands" },
+    ClueSource { id: "void_recognition", trigger: ClueTrigger::Reached, reflection: "the breach calls me home" },
+    ClueSource {
+        id: "the_journal",
+        trigger: ClueTrigger::Decoded,
+        reflection: "i wrote this and chose to forget",
+    },
+    ClueSource { id: "memory_return", trigger: ClueTrigger::Reached, reflection: "i remember who i was" },
+];
+
+/// What the run has done so far, for finding clues
+pub struct ClueFacts<'a> {
+    pub chapter: u32,
+    pub enemies_defeated: u32,
+    pub dreamt: bool,
+    /// Artifacts found in the dungeon (not the relic set out with)
+    pub artifacts_found: usize,
+    /// Cipher's notes broken this run
+    pub notes_broken: usize,
+    pub factions: &'a FactionRelations,
+}
+
+impl ClueTrigger {
+    pub fn met(&self, facts: &ClueFacts) -> bool {
+        match self {
+            Self::Reached => true,
+            Self::FightWon => facts.enemies_defeated > 0,
+            Self::Dreamt => facts.dreamt,
+            Self::Artifact => facts.artifacts_found > 0,
+            Self::Decoded => facts.notes_broken > 0,
+            Self::Trusted(faction) => facts.factions.standing(faction) >= TRUSTED_STANDING,
+        }
+    }
+}
+
+/// A clue on the board
+#[derive(Debug, Clone)]
+pub struct PinnedClue {
+    pub clue: Clue,
+    pub chapter: u32,
+    pub reflection: &'static str,
+    /// Reflected on, so what it suggests is shown
+    pub understood: bool,
+}
+
+/// The run's clue board
+#[derive(Debug, Clone, Default)]
+pub struct ClueBoard {
+    pub pinned: Vec<PinnedClue>,
+}
+
+impl ClueBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pin every clue `facts` now uncover; returns how many are new
+    pub fn refresh(&mut self, facts: &ClueFacts) -> usize {
+        let mystery = create_player_mystery();
+        let before = self.pinned.len();
+        for chapter in 1..=facts.chapter {
+            let Some(clues) = mystery.clues_by_chapter.get(&(chapter as i32)) else {
+                continue;
+            };
+            for clue in clues {
+                let Some(source) = CLUE_SOURCES.iter().find(|s| s.id == clue.id) else {
+                    continue;
+                };
+                if source.trigger.met(facts) && !self.pinned.iter().any(|p| p.clue.id == clue.id) {
+                    self.pinned.push(PinnedClue {
+                        clue: clue.clone(),
+                        chapter,
+                        reflection: source.reflection,
+                        understood: false,
+                    });
+                }
+            }
+        }
+        self.pinned.len() - before
+    }
+
+    /// Grade `typed` against the reflection of pinned clue `index`; the
+    /// clue is understood if it carries
+    pub fn reflect(&mut self, index: usize, typed: &str) -> Option<TypingGrade> {
+        let pinned = self.pinned.get_mut(index)?;
+        let grade = TypingGrade::of(accuracy(pinned.reflection, typed.trim()));
+        pinned.understood |= grade.carries();
+        Some(grade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_mystery_clue_has_a_source() {
+        let mystery = create_player_mystery();
+        let ids: Vec<&str> = mystery.clues_by_chapter.values().flatten().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids.len(), CLUE_SOURCES.len());
+        assert!(ids.iter().all(|id| CLUE_SOURCES.iter().any(|s| s.id == *id)));
+    }
+
+    #[test]
+    fn test_clues_wait_for_their_chapter_and_reflection() {
+        let mut factions = FactionRelations::new();
+        let mut board = ClueBoard::new();
+        fn facts(chapter: u32, factions: &FactionRelations) -> ClueFacts<'_> {
+            ClueFacts { chapter, enemies_defeated: 1, dreamt: false, artifacts_found: 0, notes_broken: 0, factions }
+        }
+        assert_eq!(board.refresh(&facts(1, &factions)), 2);
+        // Chapter 2 needs the Mages Guild's trust or a dream
+        assert_eq!(board.refresh(&facts(2, &factions)), 0);
+        factions.modify_standing(Faction::MagesGuild, TRUSTED_STANDING);
+        assert_eq!(board.refresh(&facts(2, &factions)), 1);
+        assert_eq!(board.refresh(&facts(2, &factions)), 0);
+
+        assert_eq!(board.reflect(0, "something else entirely"), Some(TypingGrade::Failure));
+        assert!(!board.pinned[0].understood);
+        let reflection = board.pinned[0].reflection;
+        assert_eq!(board.reflect(0, reflection), Some(TypingGrade::Success));
+        assert!(board.pinned[0].understood);
+    }
+}
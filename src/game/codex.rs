@@ -0,0 +1,255 @@
+//! Codex - The lore you have uncovered, and the gaps in it
+//!
+//! The codex gathers the deep lore into four sections, and your own story
+//! into a fifth:
+//! - Cosmology: the ages of the world, the Sundering, the Corruption
+//! - Factions: what each faction believes
+//! - Figures: the people who shaped them
+//! - Artifacts: the relics of the lore
+//! - Scars: the fights you nearly lost, across every run
+//!
+//! Lore fragments revealed by encounters are filed in the section their
+//! category belongs to, and each fragment also opens the deep lore it
+//! speaks of (an ancient history fragment opens the ages, a blight study
+//! the truth of the Corruption). Factions open once you have dealt with
+//! them, their figures once they think well enough of you to talk, and
+//! artifacts once you have carried them. The cosmology also keeps a
+//! reckoning of the Tomorrow Texts, once any prophecy has been put to the
+//! test. Whatever is still unknown is listed with its text blacked out, so
+//! you can see how much is left; scars are not lore to be found, and count
+//! for nothing in how much that is.
+//!
+//! Design: like the journal, the codex is rebuilt from the run's state
+//! (the revealed fragments, faction standings, artifacts carried) each time
+//! it is opened; it keeps no state of its own.
+
+use super::deep_lore::{all_artifacts, build_faction_histories, get_faction_lore};
+use super::faction_system::FactionRelations;
+use super::lore_fragments::{build_lore_fragments, LoreCategory, LoreJournal};
+use super::narrative::Faction;
+use super::scars::Scar;
+use super::setting_packs::{self, Setting};
+
+/// Standing with a faction at which its notable members will talk to you
+pub const FIGURE_STANDING: i32 = 25;
+/// Standing at which a faction shares its founder's story
+pub const FOUNDER_STANDING: i32 = 50;
+
+/// Title of the entry reckoning how often prophecies have come true
+const TOMORROW_TEXTS: &str = "The Tomorrow Texts, Tested";
+
+/// Title of the scars section's only entry, before the first scar
+const UNSCARRED: &str = "Unscarred";
+
+const FACTIONS: [Faction; 5] = [
+    Faction::MagesGuild,
+    Faction::TempleOfDawn,
+    Faction::RangersOfTheWild,
+    Faction::ShadowGuild,
+    Faction::MerchantConsortium,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodexSection {
+    Cosmology,
+    Factions,
+    Figures,
+    Artifacts,
+    Scars,
+}
+
+impl CodexSection {
+    pub const ALL: [CodexSection; 5] = [Self::Cosmology, Self::Factions, Self::Figures, Self::Artifacts, Self::Scars];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cosmology => "Cosmology",
+            Self::Factions => "Factions",
+            Self::Figures => "Figures",
+            Self::Artifacts => "Artifacts",
+            Self::Scars => "Scars",
+        }
+    }
+
+    /// The section `step` tabs along, wrapping around
+    pub fn cycle(self, step: isize) -> Self {
+        let at = Self::ALL.iter().position(|s| *s == self).unwrap_or(0) as isize;
+        Self::ALL[(at + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// Where fragments of `category` are filed
+    pub fn of(category: LoreCategory) -> Self {
+        match category {
+            LoreCategory::FactionLore => Self::Factions,
+            LoreCategory::PersonalStories | LoreCategory::PlayerHistory => Self::Figures,
+            LoreCategory::Arts => Self::Artifacts,
+            LoreCategory::AncientHistory
+            | LoreCategory::TheSundering
+            | LoreCategory::BlightStudies
+            | LoreCategory::Prophecy => Self::Cosmology,
+        }
+    }
+}
+
+/// One entry, known or not
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodexEntry {
+    pub section: CodexSection,
+    pub title: String,
+    pub text: String,
+    pub discovered: bool,
+}
+
+/// What the codex is built from
+pub struct CodexSources<'a> {
+    pub fragments: &'a LoreJournal,
+    pub factions: &'a FactionRelations,
+    /// Names of the artifacts carried
+    pub artifacts: &'a [String],
+    /// The reckoning of the Tomorrow Texts, once any has been tested
+    pub tomorrow_texts: Option<&'a str>,
+    pub scars: &'a [Scar],
+}
+
+/// `text` with every letter and digit blacked out
+pub fn redact(text: &str) -> String {
+    text.chars().map(|c| if c.is_alphanumeric() { '█' } else { c }).collect()
+}
+
+/// Share of the lore among `entries` discovered, as a percentage
+pub fn completion<'a>(entries: impl IntoIterator<Item = &'a CodexEntry>) -> f32 {
+    let (found, total) = entries.into_iter()
+        .filter(|e| e.section != CodexSection::Scars)
+        .fold((0, 0), |(f, t), e| (f + e.discovered as u32, t + 1));
+    if total == 0 {
+        0.0
+    } else {
+        found as f32 * 100.0 / total as f32
+    }
+}
+
+/// The faction a lore name like "Temple of Dawn" refers to (the faction
+/// lore is written in the fall of Valdris)
+fn faction_named(name: &str) -> Option<Faction> {
+    let bare = |n: &str| n.trim_start_matches("The ").to_string();
+    let valdris = Setting::Valdris.pack();
+    FACTIONS.into_iter().find(|f| bare(valdris.faction_name(*f)) == bare(name))
+}
+
+/// Every entry, in section order
+pub fn build(sources: &CodexSources) -> Vec<CodexEntry> {
+    let mut fragments: Vec<_> = build_lore_fragments().into_values().collect();
+    fragments.sort_by(|a, b| a.id.cmp(&b.id));
+    let revealed = |category: LoreCategory| {
+        fragments.iter().any(|f| f.category == category && sources.fragments.has_discovered(&f.id))
+    };
+    let standing = |name: &str| faction_named(name).map_or(0, |f| sources.factions.standing(&f));
+    let entry = |section, title: &str, text: &str, discovered| CodexEntry {
+        section,
+        title: title.to_string(),
+        text: text.to_string(),
+        discovered,
+    };
+
+    // The cosmology is the run's setting's own
+    let mut entries: Vec<CodexEntry> = (setting_packs::pack().cosmology)()
+        .into_iter()
+        .map(|c| entry(CodexSection::Cosmology, &c.title, &c.text, revealed(c.opened_by)))
+        .collect();
+    entries.push(match sources.tomorrow_texts {
+        Some(reckoning) => entry(CodexSection::Cosmology, TOMORROW_TEXTS, reckoning, true),
+        None => entry(CodexSection::Cosmology, TOMORROW_TEXTS, "No Tomorrow Text you have found has yet been put to the test.", false),
+    });
+
+    let factions = get_faction_lore();
+    for lore in &factions {
+        let title = faction_named(&lore.name).map_or(lore.name.as_str(), |f| f.name());
+        entries.push(entry(CodexSection::Factions, title, &lore.philosophy, standing(&lore.name) != 0));
+    }
+
+    for lore in &factions {
+        let known = standing(&lore.name) >= FIGURE_STANDING;
+        for figure in &lore.notable_members {
+            entries.push(entry(CodexSection::Figures, &format!("{}, {}", figure.name, figure.title), &figure.description, known));
+        }
+    }
+    let mut histories: Vec<_> = build_faction_histories().into_iter().collect();
+    histories.sort_by(|a, b| a.0.cmp(&b.0));
+    for (id, history) in &histories {
+        let founder = &history.founder;
+        let known = Faction::from_id(id).is_some_and(|f| sources.factions.standing(&f) >= FOUNDER_STANDING);
+        entries.push(entry(CodexSection::Figures, &format!("{}, {}", founder.name, founder.title), &founder.legacy, known));
+    }
+
+    for artifact in all_artifacts() {
+        let carried = sources.artifacts.contains(&artifact.name);
+        entries.push(entry(CodexSection::Artifacts, &artifact.name, &artifact.description, carried));
+    }
+
+    for fragment in &fragments {
+        entries.push(entry(
+            CodexSection::of(fragment.category),
+            &fragment.title,
+            &fragment.content.excerpt,
+            sources.fragments.has_discovered(&fragment.id),
+        ));
+    }
+
+    // The newest scar first
+    for scar in sources.scars.iter().rev() {
+        entries.push(entry(CodexSection::Scars, &scar.title(), &scar.text(), true));
+    }
+    if sources.scars.is_empty() {
+        entries.push(entry(CodexSection::Scars, UNSCARRED, "No fight has yet brought you to the edge and let you walk back from it.", true));
+    }
+
+    // Keep each section together, in the order the sections are listed
+    entries.sort_by_key(|e| CodexSection::ALL.iter().position(|s| *s == e.section));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_keeps_shape() {
+        assert_eq!(redact("The Age, 47."), "███ ███, ██.");
+    }
+
+    #[test]
+    fn test_fragments_and_standing_open_entries() {
+        let mut journal = LoreJournal::new();
+        let mut factions = FactionRelations::new();
+        let artifacts = vec!["The Songlines".to_string()];
+        let sources = CodexSources { fragments: &journal, factions: &factions, artifacts: &artifacts, tomorrow_texts: None, scars: &[] };
+        let entries = build(&sources);
+        assert!(CodexSection::ALL.iter().all(|s| entries.iter().any(|e| e.section == *s)));
+        assert!(entries.iter().any(|e| e.title == "The Songlines" && e.discovered));
+        let before = completion(&entries);
+        assert!(before > 0.0 && before < 100.0);
+
+        // A player-history fragment is filed under figures and opens on discovery
+        let fragment = build_lore_fragments().into_values().find(|f| f.category == LoreCategory::PlayerHistory).unwrap();
+        journal.discover(&fragment.id);
+        factions.modify_standing(Faction::MagesGuild, FIGURE_STANDING);
+        let sources = CodexSources { fragments: &journal, factions: &factions, artifacts: &artifacts, tomorrow_texts: None, scars: &[] };
+        let entries = build(&sources);
+        assert!(entries.iter().any(|e| e.title == fragment.title && e.section == CodexSection::Figures && e.discovered));
+        assert!(entries.iter().any(|e| e.section == CodexSection::Factions && e.title.contains("Mages") && e.discovered));
+        assert!(completion(&entries) > before);
+
+        // The Tomorrow Texts open once a prophecy has been tested
+        assert!(entries.iter().any(|e| e.title == TOMORROW_TEXTS && !e.discovered));
+        let sources = CodexSources { tomorrow_texts: Some("2 of 3 came true."), ..sources };
+        assert!(build(&sources).iter().any(|e| e.title == TOMORROW_TEXTS && e.discovered && e.text.contains("2 of 3")));
+
+        // Scars are filed on their own, and count for nothing in completion
+        let before = completion(&build(&sources));
+        let scars = [Scar { enemy: "Ink Wraith".to_string(), floor: 3, zone: "Sunken Archives".to_string(), word: None, descent: 2 }];
+        let entries = build(&CodexSources { scars: &scars, ..sources });
+        assert!(entries.iter().any(|e| e.section == CodexSection::Scars && e.title == "Ink Wraith, floor 3" && e.discovered));
+        assert!(!entries.iter().any(|e| e.title == UNSCARRED));
+        assert_eq!(completion(&entries), before);
+    }
+}
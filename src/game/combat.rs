@@ -5,11 +5,73 @@ use std::sync::Arc;
 use super::{player::Player, enemy::Enemy, spells::Spell};
 use super::narrative_seed::TypingModifier;
 use super::skills::SkillTree;
+use super::difficulty_director::DifficultyDirector;
+use super::prompt_corruption::{self, CorruptedSpan};
+use super::map_travel::NAMED_ROOM_POWER;
+use super::class_abilities::{self, ClassAbility, Cooldowns, COMMAND_PREFIX};
+use super::class_builds::{BiasedPrompt, WordBias};
+use super::weak_points::{self, WeakPoint, WeakPointTracker, STAGGER_MULT, STAGGER_WINDOW};
+use super::executions::{self, EXECUTION_REWARD_MULT};
+use super::enemy_visuals::{EnemyPosture, HitLocation, WoundEffects};
+use super::inscription::{Inscription, INSCRIPTION_MULT};
+use super::enemy_shields::{EnemyShield, ShieldHit};
+use super::stances::{self, Stance};
+use super::combat_replay::{CombatReplay, ReplayInput};
+use super::word_chain::{self, WordChain};
+use super::typing_challenges::{Puzzle, TypingChallengeKind, PUZZLE_TIME_BONUS};
+use super::channeling::{self, Channel, CHANNEL_COOLDOWN};
+use super::enrage::{EnrageEvent, EnrageTimer};
+use super::boss_bar::BossPlate;
+use super::hit_locations::{Aim, HEAD_MULT};
+use super::hazards::{ActiveHazard, HazardOutcome, HAZARD_CHANCE_PER_SEC, HAZARD_COOLDOWN};
+use super::dialogue_engine::ZoneContext;
+use super::combat_scheduler::{CombatMode, EnemyScheduler};
+use super::true_names::{self, TrueName, TRUE_NAME_DAMAGE};
+use super::fatigue::{Fatigue, FatigueChange, HEAVY_STRIKE_COST};
+use super::shop::{LEXICON_CHANCE, LEXICON_POWER};
+use super::curses::CurseLoad;
+use super::config::DifficultyConfig;
+use super::settings::TypoPolicy;
+use super::stats_store::WordSample;
+use super::pause::{self, FrozenClock};
+use super::run_rng::{self, Stream};
+use super::ascension::{self, AscensionRule, ASCENSION_CORRUPTION, ASCENSION_MIN_WORD_LEN, ASCENSION_TIME_MULT};
+use super::patrons::{self, Patron, SCRIBE_FORGIVENESS, SCRIBE_WPM_FLOOR};
+use super::typing_impact::{grapheme_len, graphemes, graphemes_match, last_grapheme_correct, last_grapheme_pending, pop_grapheme, text_matches, AttackType, ImpactParams};
 use crate::data::GameData;
-use rand::Rng;
+use crate::data::lore_words::LoreWords;
+use crate::data::damage_types::{effectiveness_note, DamageType};
+use crate::data::items::{Consumable, ConsumableEffect};
 use super::combat_immersion::{ImmersiveCombat, KeystrokeFeedback, WordFeedback, CombatMessage};
 use super::player_avatar::PlayerClass;
-
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// Damage multiplier for a charged heavy strike (a full zone sentence)
+const HEAVY_STRIKE_MULT: f32 = 2.5;
+/// Further multiplier when the heavy strike's sentence is typed slow and
+/// sure enough to count as a Deliberate attack
+const DELIBERATE_STRIKE_MULT: f32 = 1.4;
+/// Enemy damage multiplier when a heavy strike is fumbled
+pub const HEAVY_EXPOSURE_MULT: f32 = 1.5;
+/// Chance a sentence prompt is drawn from the run's stitched sentences
+const STITCHED_PROMPT_CHANCE: f32 = 0.3;
+/// How long a riposte stays ready after a perfect word
+const RIPOSTE_WINDOW: Duration = Duration::from_secs(2);
+/// Minimum WPM on a perfect word to open a riposte window
+const RIPOSTE_MIN_WPM: f32 = 40.0;
+/// Seconds given back when corruption mutates the prompt mid-word
+const INTERFERENCE_GRACE_SECS: f32 = 1.0;
+/// Damage multiplier on the word after /temper
+const TEMPER_MULT: f32 = 1.5;
+/// Seconds added by /annotate
+const ANNOTATE_SECS: f32 = 5.0;
+
+/// One fight, from its first word to its end.
+///
+/// The setters that shape a fight (its mode, word pack, class, patron and
+/// the like) are called right after `CombatState::new`, before the first
+/// keystroke.
 #[derive(Debug, Clone)]
 pub struct CombatState {
     pub enemy: Enemy,
@@ -44,17 +106,143 @@ pub struct CombatState {
     pub corruption_modifier: Option<TypingModifier>,
     /// Damage from corruption mistakes this combat
     pub corruption_damage_taken: i32,
+    /// The player's skills, for damage bonuses that depend on the word typed
+    pub skills: Option<SkillTree>,
     /// Skill-based modifiers computed at combat start
-    pub skill_damage_mult: f32,
     pub skill_crit_chance: f32,
     pub skill_crit_mult: f32,
     pub skill_damage_reduction: f32,
     pub skill_evasion_chance: f32,
     pub skill_transcendence_threshold: Option<f32>,
+    /// Flow state multiplier, synced from TypingFeel before each keystroke
+    pub flow_damage_mult: f32,
+    /// Typing a full sentence for one heavy strike
+    pub heavy_strike: bool,
+    /// A fumbled heavy strike left the player open to a harder hit
+    pub exposed: bool,
+    /// Sentences stitched from this run's events, used up as they appear
+    pub stitched_prompts: Vec<String>,
+    /// Words from a challenge's word pack, used in place of stock words
+    pub word_pack: Vec<String>,
+    /// Words from lexicons bought this run, mixed in among the prompts
+    pub lexicon: Vec<String>,
+    /// Wrong keystrokes on the current word
+    pub word_errors: u32,
+    /// Counter window: the next enemy attack before this instant is reflected
+    pub riposte_until: Option<Instant>,
+    /// The turn the riposte was readied on; only a blow after a later word
+    /// is caught by it
+    pub riposte_turn: i32,
+    /// Chance per prompt that corruption mutates it mid-word
+    pub interference_chance: f32,
+    /// Whether the current prompt has already been corrupted
+    pub interfered: bool,
+    /// Letters of the current prompt changed by corruption (for rendering)
+    pub corrupted_span: Option<CorruptedSpan>,
+    /// Power from fighting in a room the player named
+    pub named_room_mult: f32,
+    /// What the player's cursed loot gives and takes
+    pub curses: CurseLoad,
+    /// The difficulty preset the fight is scaled by
+    pub difficulty_scale: DifficultyConfig,
+    /// Backspaces a word allows under the typo policy, if there is a limit
+    pub backspace_limit: Option<u32>,
+    /// Backspaces spent on the current word
+    pub word_backspaces: u32,
+    /// Ascension: no slip is forgiven
+    pub unforgiving: bool,
+    /// The player's class, which decides their abilities
+    pub class: PlayerClass,
+    /// The kind of prompt the player's class leans toward
+    pub word_bias: WordBias,
+    /// Ability command being typed after `/`, if the command line is open
+    pub ability_input: Option<String>,
+    /// When each used ability is ready again
+    pub cooldowns: Cooldowns,
+    /// /temper: the next completed word hits harder
+    pub tempered: bool,
+    /// /decrypt: strike this turn's word as the enemy's weakness
+    pub exploit: Option<DamageType>,
+    /// Damage of the last word typed without a mistake, for /replay
+    pub last_perfect_damage: Option<i32>,
+    /// Whether the last word to land struck critically
+    pub last_crit: bool,
+    /// Faction patron pledged for this run
+    pub patron: Option<Patron>,
+    /// Scribes: slips forgiven on the current word
+    pub forgiven: u32,
+    /// When each keystroke of the current word landed (rhythm checks)
+    pub key_times: Vec<Instant>,
+    /// Naturalists: healing earned by rhythm, applied before the enemy acts
+    pub pending_heal: i32,
+    /// Words typed with a steady rhythm this fight
+    pub steady_words: u32,
+    /// Enemy blows riposted, or taken in the Guarded stance, this fight
+    pub blows_turned: u32,
+    /// Stamina spent by sprinting and flailing; when tired the rhythm window narrows
+    pub fatigue: Fatigue,
+    /// Impact parameters from gear, before fatigue narrows them
+    pub impact_params: ImpactParams,
+    /// Bookmark: seconds the word timer is still held
+    pub timer_hold: f32,
+    /// Slips and strikes on the current prompt's weak-point words
+    pub weak_tracker: WeakPointTracker,
+    /// A struck boss is staggered (bonus damage) until this instant
+    pub stagger_until: Option<Instant>,
+    /// Elite armor: pips break only to Precision or Flurry strikes
+    pub enemy_shield: EnemyShield,
+    /// Typing stance, switched between words
+    pub stance: Stance,
+    /// HP the Aggressive stance's mistakes cost, paid on the enemy's turn
+    pub pending_recoil: i32,
+    /// Every keystroke of the fight, for the replay viewer
+    pub replay: CombatReplay,
+    /// Word Chain modifier: the chain so far (None when the modifier is off)
+    pub word_chain: Option<WordChain>,
+    /// How this enemy presents its prompts (spectral and void enemies jumble them)
+    pub challenge_kind: TypingChallengeKind,
+    /// The jumble shown for the last prompt picked, if it was jumbled
+    pub puzzle: Option<Puzzle>,
+    /// An open channel: zone words stream in and the enemy burns
+    pub channel: Option<Channel>,
+    /// Turn on which a channel can be opened again
+    pub channel_ready_turn: i32,
+    /// How long the enemy will fight before it enrages
+    pub enrage: EnrageTimer,
+    /// The capital letter aimed at in the last prompt picked, if any
+    pub aim: Option<Aim>,
+    /// Whether prompts may carry aimed letters
+    pub aiming: bool,
+    /// A zone hazard cutting into the fight, waiting on the player
+    pub hazard: Option<ActiveHazard>,
+    /// Turn on which another hazard can strike
+    pub hazard_ready_turn: i32,
+    /// Hazard damage not yet dealt to the player
+    pub pending_hazard_damage: i32,
+    /// Decides when the enemy acts (real-time or turn-based)
+    pub scheduler: &'static dyn EnemyScheduler,
+    /// The boss's true name, if the player has read it
+    pub true_name: Option<TrueName>,
+    /// A boss's title and phases, for its health bar
+    pub boss_plate: Option<BossPlate>,
+    /// When the key being handled was read from the terminal
+    pub key_read_at: Option<Instant>,
+    /// The current prompt is a finisher phrase
+    pub execution: bool,
+    /// The enemy was executed with its finisher
+    pub executed: bool,
+    /// Damage beyond what the killing blow needed
+    pub overkill: i32,
+    /// /inscribe: the prompt is being studied, then typed from memory
+    pub inscription: Option<Inscription>,
+    /// The last word mistyped or timed out this fight, for a scar
+    pub last_failed_word: Option<String>,
     /// WPM tracking for this combat
     pub wpm_samples: Vec<f32>,
     /// Peak WPM achieved this combat
     pub peak_wpm: f32,
+    /// Every word landed this combat, for the stats store
+    pub word_samples: Vec<WordSample>,
     /// Total damage dealt this combat
     pub total_damage_dealt: i32,
     /// Total damage taken this combat
@@ -63,6 +251,10 @@ pub struct CombatState {
     pub combat_start: Instant,
     /// Immersive combat feedback system (optional)
     pub immersive: Option<ImmersiveCombat>,
+    /// Time limit multiplier from the difficulty director
+    pub time_mult: f32,
+    /// Preferred word length range from the difficulty director
+    pub word_length_range: Option<(usize, usize)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -93,18 +285,26 @@ impl CombatState {
     pub fn new(enemy: Enemy, game_data: Arc<GameData>, difficulty: u32, floor: u32, corruption: Option<TypingModifier>, skills: Option<&SkillTree>) -> Self {
         // Use sentences for bosses or high difficulty, otherwise words
         let use_sentences = enemy.is_boss || difficulty >= 5;
+        let mut rng = run_rng::stream(Stream::Words);
         let starting_word = if use_sentences {
-            game_data.get_lore_sentence(floor, enemy.is_boss, Some(&enemy.name))
+            game_data.get_lore_sentence(&mut rng, floor, enemy.is_boss, Some(&enemy.name))
         } else {
-            game_data.get_lore_word(floor, Some(&enemy.typing_theme))
+            game_data.get_lore_word(&mut rng, floor, Some(&enemy.typing_theme))
         };
         
+        let interference_chance = prompt_corruption::prompt_chance(&enemy, corruption.as_ref());
+        let enemy_shield = EnemyShield::for_enemy(enemy.enemy_type, floor);
+        let challenge_kind = TypingChallengeKind::for_enemy(&enemy);
+        let enrage = EnrageTimer::for_enemy(&enemy);
+        let boss_plate = enemy.is_boss.then(|| BossPlate::for_boss(&enemy, &game_data));
+        let puzzle = challenge_kind.roll(&starting_word, &mut run_rng::stream(Stream::Words));
+        
         // Adjust time limit based on content length
         let time_limit = if use_sentences {
             15.0 + (starting_word.len() as f32 * 0.1)
         } else {
             5.0 + (starting_word.len() as f32 * 0.2)
-        };
+        } + if puzzle.is_some() { PUZZLE_TIME_BONUS } else { 0.0 };
         
         Self {
             enemy,
@@ -135,18 +335,82 @@ impl CombatState {
             corruption_modifier: corruption,
             corruption_damage_taken: 0,
             // Skill modifiers from SkillTree
-            skill_damage_mult: skills.map(|s| s.get_damage_multiplier(0.0, 1.0, 10.0)).unwrap_or(1.0),
+            skills: skills.cloned(),
             skill_crit_chance: skills.map(|s| s.get_crit_chance()).unwrap_or(0.0),
             skill_crit_mult: skills.map(|s| s.get_crit_multiplier()).unwrap_or(1.5),
             skill_damage_reduction: skills.map(|s| s.get_damage_reduction()).unwrap_or(0.0),
             skill_evasion_chance: skills.map(|s| s.get_evasion_chance()).unwrap_or(0.0),
             skill_transcendence_threshold: skills.and_then(|s| s.get_active_effects().iter().find_map(|e| match e { super::skills::SkillEffect::Transcendence(t) => Some(*t), _ => None })),
+            flow_damage_mult: 1.0,
+            heavy_strike: false,
+            exposed: false,
+            stitched_prompts: Vec::new(),
+            word_pack: Vec::new(),
+            lexicon: Vec::new(),
+            word_errors: 0,
+            riposte_until: None,
+            riposte_turn: 0,
+            interference_chance,
+            interfered: false,
+            corrupted_span: None,
+            named_room_mult: 1.0,
+            curses: CurseLoad::default(),
+            difficulty_scale: DifficultyConfig::default(),
+            backspace_limit: None,
+            word_backspaces: 0,
+            unforgiving: false,
+            class: PlayerClass::Freelancer,
+            word_bias: WordBias::None,
+            ability_input: None,
+            cooldowns: Cooldowns::default(),
+            tempered: false,
+            exploit: None,
+            last_perfect_damage: None,
+            last_crit: false,
+            patron: None,
+            forgiven: 0,
+            key_times: Vec::new(),
+            pending_heal: 0,
+            steady_words: 0,
+            blows_turned: 0,
+            fatigue: Fatigue::new(),
+            impact_params: ImpactParams::default(),
+            timer_hold: 0.0,
+            weak_tracker: WeakPointTracker::default(),
+            stagger_until: None,
+            enemy_shield,
+            stance: Stance::default(),
+            pending_recoil: 0,
+            replay: CombatReplay::new(),
+            word_chain: None,
+            challenge_kind,
+            puzzle,
+            channel: None,
+            channel_ready_turn: 0,
+            enrage,
+            aim: None,
+            aiming: true,
+            hazard: None,
+            hazard_ready_turn: 2,
+            pending_hazard_damage: 0,
+            scheduler: CombatMode::default().scheduler(),
+            true_name: None,
+            boss_plate,
+            key_read_at: None,
+            execution: false,
+            executed: false,
+            overkill: 0,
+            inscription: None,
+            last_failed_word: None,
             wpm_samples: Vec::new(),
             peak_wpm: 0.0,
+            word_samples: Vec::new(),
             total_damage_dealt: 0,
             total_damage_taken: 0,
             combat_start: Instant::now(),
             immersive: None,
+            time_mult: 1.0,
+            word_length_range: None,
         }
 
     }
@@ -163,7 +427,7 @@ impl CombatState {
 
 
     fn select_word(&self, word_pool: &[String]) -> String {
-        let mut rng = rand::thread_rng();
+        let mut rng = run_rng::stream(Stream::Words);
         let index = rng.gen_range(0..word_pool.len());
         word_pool[index].clone()
     }
@@ -178,9 +442,38 @@ impl CombatState {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_tick);
         self.last_tick = now;
+        // Time spent thinking between turns doesn't count toward enrage
+        if self.scheduler.clock_runs() {
+            self.enrage.advance(elapsed.as_secs_f32());
+        }
         
+        if self.channel.is_some() {
+            self.burn_channel(now, elapsed.as_secs_f32());
+            if self.phase != CombatPhase::PlayerTurn {
+                return;
+            }
+        }
+        
+        if self.hazard.is_some() {
+            // The word timer waits while the hazard plays out
+            let clock_runs = self.scheduler.clock_runs();
+            if let Some(outcome) = self.hazard.as_mut().filter(|_| clock_runs).and_then(|h| h.advance(elapsed.as_secs_f32())) {
+                self.resolve_hazard(outcome);
+            }
+            return;
+        }
         if self.typing_started {
-            self.time_remaining -= elapsed.as_secs_f32();
+            self.maybe_hazard(elapsed.as_secs_f32());
+        }
+        
+        if self.typing_started && self.scheduler.clock_runs() {
+            let mut elapsed = elapsed.as_secs_f32();
+            if self.timer_hold > 0.0 {
+                let held = elapsed.min(self.timer_hold);
+                self.timer_hold -= held;
+                elapsed -= held;
+            }
+            self.time_remaining -= elapsed;
             
             if self.time_remaining <= 0.0 {
                 self.time_remaining = 0.0;
@@ -192,11 +485,166 @@ impl CombatState {
     }
 
 
+    /// Run the fight real-time or turn-based.
+    pub fn set_mode(&mut self, mode: CombatMode) {
+        self.scheduler = mode.scheduler();
+    }
+
+    /// Whether the enemy takes its turn now
+    pub fn enemy_acts_now(&self) -> bool {
+        self.scheduler.enemy_acts(self.phase, self.time_remaining)
+    }
+
+    /// Maybe let the zone throw a hazard into the fight
+    fn maybe_hazard(&mut self, secs: f32) {
+        if self.turn < self.hazard_ready_turn
+            || self.spell_mode
+            || self.channel.is_some()
+            || self.ability_input.is_some()
+            || self.execution
+        {
+            return;
+        }
+        let hazards = ZoneContext::from_floor(self.floor).hazards();
+        let mut rng = run_rng::stream(Stream::Map);
+        if hazards.is_empty() || rng.gen::<f32>() >= HAZARD_CHANCE_PER_SEC * secs {
+            return;
+        }
+        let hazard = &hazards[rng.gen_range(0..hazards.len())];
+        self.battle_log.push(format!("⚠ {} Type '{}'!", hazard.warning, hazard.prompt));
+        self.hazard = Some(ActiveHazard::new(hazard));
+    }
+
+    /// A hazard played out: the player escaped it, or it hit them
+    fn resolve_hazard(&mut self, outcome: HazardOutcome) {
+        let Some(active) = self.hazard.take() else {
+            return;
+        };
+        let hazard = active.hazard;
+        self.hazard_ready_turn = self.turn + HAZARD_COOLDOWN;
+        self.last_tick = Instant::now();
+        match outcome {
+            HazardOutcome::Avoided => {
+                // The dungeon can wound the enemy, but never finishes it
+                let backlash = hazard.backlash_on(self.floor).min(self.enemy.current_hp - 1).max(0);
+                self.enemy.current_hp -= backlash;
+                self.total_damage_dealt += backlash;
+                if backlash > 0 {
+                    self.battle_log.push(format!("✓ {} ({} damage)", hazard.avoided, backlash));
+                } else {
+                    self.battle_log.push(format!("✓ {}", hazard.avoided));
+                }
+            }
+            HazardOutcome::Struck => {
+                let damage = hazard.damage_on(self.floor);
+                self.pending_hazard_damage += damage;
+                self.battle_log.push(format!("💥 {} hits you for {} damage!", hazard.name, damage));
+            }
+        }
+    }
+
+    /// Deal hazard damage to the player as soon as it lands
+    pub fn apply_hazard_damage(&mut self, player: &mut Player) {
+        if self.pending_hazard_damage <= 0 {
+            return;
+        }
+        player.take_damage(self.pending_hazard_damage);
+        self.total_damage_taken += self.pending_hazard_damage;
+        self.pending_hazard_damage = 0;
+        if player.hp <= 0 {
+            self.phase = CombatPhase::Defeat;
+            self.finalize_result(false, false, false);
+        }
+    }
+
+    /// Keep the clock frozen (while paused) so the paused span is never
+    /// counted against the player once ticking resumes
+    pub fn hold_timer(&mut self) {
+        self.last_tick = Instant::now();
+        if let Some(channel) = &mut self.channel {
+            channel.hold(self.last_tick);
+        }
+    }
+
+
+    /// Stop every clock of the fight at `now`, for the pause menu
+    pub fn freeze(&self, now: Instant) -> FrozenClock {
+        FrozenClock {
+            time_remaining: self.time_remaining,
+            timer_hold: self.timer_hold,
+            enrage_secs: self.enrage.elapsed_secs,
+            hazard_secs: self.hazard.as_ref().map(|h| h.remaining),
+            riposte_ms: pause::ms_until(self.riposte_until, now),
+            stagger_ms: pause::ms_until(self.stagger_until, now),
+            study_ms: pause::ms_until(self.inscription.map(|i| i.hides_at), now),
+            key_ages_ms: self.key_times.iter().map(|t| pause::ms_since(*t, now)).collect(),
+            replay_word_ms: self.replay.word_started().map(|t| pause::ms_since(t, now)),
+            fight_ms: pause::ms_since(self.combat_start, now),
+            pending_heal: self.pending_heal,
+            pending_recoil: self.pending_recoil,
+            pending_hazard_damage: self.pending_hazard_damage,
+        }
+    }
+
+    /// Start the clocks again at `now`, every window as long as it was
+    /// when `clock` was frozen
+    pub fn thaw(&mut self, clock: &FrozenClock, now: Instant) {
+        self.time_remaining = clock.time_remaining;
+        self.timer_hold = clock.timer_hold;
+        self.enrage.elapsed_secs = clock.enrage_secs;
+        if let (Some(hazard), Some(secs)) = (&mut self.hazard, clock.hazard_secs) {
+            hazard.remaining = secs;
+        }
+        // A window that was open opens again; one that had shut stays shut
+        self.riposte_until = clock.riposte_ms.map(|ms| pause::after(now, ms));
+        self.stagger_until = clock.stagger_ms.map(|ms| pause::after(now, ms));
+        if let Some(inscription) = &mut self.inscription {
+            inscription.hides_at = pause::after(now, clock.study_ms.unwrap_or(0));
+        }
+        self.key_times = clock.key_ages_ms.iter().map(|ms| pause::before(now, *ms)).collect();
+        if let Some(ms) = clock.replay_word_ms {
+            self.replay.restart_word(pause::before(now, ms));
+        }
+        self.combat_start = pause::before(now, clock.fight_ms);
+        self.pending_heal = clock.pending_heal;
+        self.pending_recoil = clock.pending_recoil;
+        self.pending_hazard_damage = clock.pending_hazard_damage;
+        self.last_tick = now;
+        if let Some(channel) = &mut self.channel {
+            channel.hold(now);
+        }
+    }
+
     pub fn on_char_typed(&mut self, c: char) {
+        let now = self.key_read_at.take().unwrap_or_else(Instant::now);
         if self.phase != CombatPhase::PlayerTurn {
             return;
         }
 
+        // A hazard takes every key until it resolves
+        if let Some(hazard) = &mut self.hazard {
+            if let Some(outcome) = hazard.on_key(c) {
+                self.resolve_hazard(outcome);
+            }
+            return;
+        }
+        // The ability command line swallows keystrokes until Enter or Esc
+        if let Some(input) = &mut self.ability_input {
+            input.push(c);
+            return;
+        }
+        // Mid-word too (for a whiteout), unless the prompt itself wants a `/` here
+        let prompt_wants_prefix = graphemes(&self.current_word)
+            .get(grapheme_len(&self.typed_input))
+            .is_some_and(|g| g.starts_with(COMMAND_PREFIX));
+        if c == COMMAND_PREFIX && !self.spell_mode && (self.typed_input.is_empty() || !prompt_wants_prefix) {
+            self.ability_input = Some(String::new());
+            return;
+        }
+        if self.inscription.is_some_and(|i| i.is_previewing()) {
+            return;
+        }
+
 
         if !self.typing_started {
             self.typing_started = true;
@@ -204,192 +652,1240 @@ impl CombatState {
         }
 
 
+        self.key_times.push(now);
+
+        // Compare by grapheme so accented letters, ß and CJK count as one character
+        let graphemes_before = grapheme_len(&self.typed_input);
+        let was_correct = last_grapheme_correct(self.target(), &self.typed_input);
         self.typed_input.push(c);
-        self.total_chars += 1;
+        let graphemes_after = grapheme_len(&self.typed_input);
+        let is_correct = last_grapheme_correct(self.target(), &self.typed_input);
+
+        if graphemes_after == graphemes_before {
+            // A combining mark joined the previous character - it may have fixed it
+            if is_correct && !was_correct {
+                self.correct_chars += 1;
+            }
+        } else if !is_correct && self.forgive_slip() {
+            return;
+        } else {
+            self.total_chars += 1;
+            if let Some(imm) = &mut self.immersive {
+                imm.on_keystroke_at(c, is_correct, now);
+            }
+            if let Some(aim) = self.aim.as_mut().filter(|a| a.word == self.current_word) {
+                aim.on_key(graphemes_after - 1, is_correct);
+            }
+            if is_correct {
+                self.correct_chars += 1;
+                self.strike_weak_point(graphemes_after);
+            } else {
+                self.word_errors += 1;
+                self.weak_tracker.on_slip(graphemes_after - 1);
+                self.pending_recoil += self.stance.error_recoil();
+                let change = self.fatigue.on_error();
+                self.strain(change);
+                
+                // Corruption effect: MistakesDealDamage
+                if let Some(TypingModifier::MistakesDealDamage { damage_per_error }) = &self.corruption_modifier {
+                    self.corruption_damage_taken += damage_per_error;
+                    self.battle_log.push(format!("Corruption punishes your error! (-{} HP)", damage_per_error));
+                }
+
+            }
+        }
+        self.replay.record(&self.current_word, ReplayInput::Char { ch: c, correct: is_correct }, now);
+        if let Some(channel) = &mut self.channel {
+            channel.on_key(now);
+        }
+
+
+        // Check if word is complete; a last letter still waiting on its
+        // accent is judged once the mark arrives
+        let complete = graphemes_after >= grapheme_len(self.target())
+            && !last_grapheme_pending(self.target(), &self.typed_input);
+        if complete && self.channel.is_some() {
+            self.on_channel_word();
+        } else if complete {
+            let dealt_before = self.total_damage_dealt;
+            self.on_word_complete();
+            self.replay.finish_word(self.total_damage_dealt - dealt_before, true);
+        } else if is_correct {
+            self.maybe_interfere(graphemes_after);
+        }
+
+    }
+
+
+    pub fn on_backspace(&mut self) {
+        if self.phase != CombatPhase::PlayerTurn || self.hazard.is_some() {
+            return;
+        }
+
+        if let Some(input) = &mut self.ability_input {
+            if input.pop().is_none() {
+                self.ability_input = None;
+            }
+            return;
+        }
+        if self.typed_input.is_empty() {
+            return;
+        }
+        // The typo policy may let a slip stand
+        if self.backspace_limit.is_some_and(|limit| self.word_backspaces >= limit) {
+            return;
+        }
+        self.word_backspaces += 1;
+        self.replay.record(&self.current_word, ReplayInput::Backspace, Instant::now());
+        pop_grapheme(&mut self.typed_input);
+    }
+
+
+    fn on_word_complete(&mut self) {
+        self.words_typed += 1;
+        
+        let matched = text_matches(self.target(), &self.typed_input);
+        let wpm = self.calculate_wpm();
+        self.pace(wpm);
+        let finishing = std::mem::take(&mut self.execution);
+        if matched && self.patron == Some(Patron::Scribes) && wpm < SCRIBE_WPM_FLOOR {
+            // Correct, but beneath the Scribes' standard
+            self.combo = 0;
+            self.battle_log.push(format!(
+                "✒ {:.0} WPM - beneath the Scribes' standard. The word falls flat.",
+                wpm
+            ));
+            self.fumble_heavy_strike();
+            self.snap_chain();
+            self.phase = CombatPhase::EnemyTurn;
+        } else if matched {
+            self.words_correct += 1;
+            self.combo += 1;
+            if self.combo > self.max_combo {
+                self.max_combo = self.combo;
+            }
+            if let Some(chain) = &mut self.word_chain {
+                chain.link();
+            }
+
+            
+            // Calculate damage based on typing performance
+            let accuracy = self.calculate_accuracy();
+            let (damage, crit) = self.calculate_damage(wpm, accuracy);
+            self.last_crit = crit;
+            let aimed = self.active_aim().filter(|a| a.struck).map(|a| a.location);
+            let damage = if aimed == Some(HitLocation::Head) {
+                (damage as f32 * HEAD_MULT).round() as i32
+            } else {
+                damage
+            };
+            let word_accuracy = 1.0 - self.word_errors as f32 / grapheme_len(&self.current_word).max(1) as f32;
+            let attack = self.impact_params.thresholds.classify(wpm, word_accuracy);
+            // A heavy strike taken slow and sure escalates into a Deliberate blow
+            let deliberate = self.heavy_strike && attack == AttackType::Deliberate;
+            let damage = if deliberate {
+                (damage as f32 * DELIBERATE_STRIKE_MULT).round() as i32
+            } else {
+                damage
+            };
+            let offer_execution = !finishing
+                && executions::offers_execution(self.enemy_posture(), attack);
+            
+            self.enemy.current_hp -= damage;
+            self.total_damage_dealt += damage;
+            if finishing {
+                // The finisher always lands
+                self.enemy.current_hp = self.enemy.current_hp.min(0);
+            } else if offer_execution {
+                // Brought to its knees rather than felled
+                self.enemy.current_hp = self.enemy.current_hp.max(1);
+            }
+            if self.word_errors == 0 {
+                self.last_perfect_damage = Some(damage);
+            }
+            self.rhythm_heal();
+            if attack == AttackType::Frantic {
+                let change = self.fatigue.on_frantic();
+                self.strain(change);
+            } else if patrons::is_steady(&self.key_intervals()) {
+                self.steady_words += 1;
+                self.fatigue.on_steady_rhythm();
+                self.update_winded();
+            }
+            
+            // Track WPM
+            if wpm > 0.0 {
+                self.wpm_samples.push(wpm);
+                if wpm > self.peak_wpm {
+                    self.peak_wpm = wpm;
+                }
+            }
+            self.word_samples.push(WordSample {
+                wpm,
+                accuracy: word_accuracy,
+                damage,
+                attack,
+            });
+            
+            let note = effectiveness_note(self.enemy.resistances.multiplier(self.attack_damage_type()));
+            if self.heavy_strike {
+                let name = if deliberate { "DELIBERATE HEAVY STRIKE" } else { "HEAVY STRIKE" };
+                self.battle_log.push(format!(
+                    "🗡 {}! ({:.0} WPM, {:.0}% acc) - {} damage! [{}x combo]{}",
+                    name, wpm, accuracy * 100.0, damage, self.combo, note
+                ));
+                self.heavy_strike = false;
+            } else {
+                self.battle_log.push(format!(
+                    "✓ {} ({:.0} WPM, {:.0}% acc) - {} damage! [{}x combo]{}",
+                    self.current_word, wpm, accuracy * 100.0, damage, self.combo, note
+                ));
+            }
+            self.strike_shield(attack);
+            self.wound_enemy(aimed, damage);
+            
+            if self.enemy.current_hp <= 0 {
+                self.overkill += -self.enemy.current_hp;
+                self.enemy.current_hp = 0;
+                if finishing {
+                    self.executed = true;
+                    self.battle_log.push(executions::kill_message(&self.enemy.name));
+                }
+                self.phase = CombatPhase::Victory;
+                self.finalize_result(true, false, false);
+            } else if offer_execution {
+                self.offer_execution();
+            } else {
+                // Flawless and fast: land the next word inside the window
+                // and its blow is turned aside
+                if self.word_errors == 0 && wpm >= RIPOSTE_MIN_WPM {
+                    self.riposte_until = Some(Instant::now() + RIPOSTE_WINDOW);
+                    self.riposte_turn = self.turn;
+                    self.battle_log.push(format!(
+                        "⚔ Flawless! Land the next word inside {}s and you riposte its blow.",
+                        RIPOSTE_WINDOW.as_secs()
+                    ));
+                }
+                self.phase = CombatPhase::EnemyTurn;
+            }
+
+        } else {
+            self.combo = 0;
+            self.last_failed_word = Some(self.current_word.clone());
+            self.battle_log.push(format!(
+                "✗ Mistyped '{}' (typed '{}')",
+                self.current_word, self.typed_input
+            ));
+            if finishing {
+                self.battle_log.push(format!("{} staggers back to its feet.", self.enemy.name));
+            }
+            self.fumble_heavy_strike();
+            self.snap_chain();
+            self.phase = CombatPhase::EnemyTurn;
+        }
+        self.reset_word_state();
+        self.tempered = false;
+        self.exploit = None;
+        self.inscription = None;
+
+    }
+
+
+    fn on_word_timeout(&mut self) {
+        self.words_typed += 1;
+        self.replay.finish_word(0, false);
+        self.combo = 0;
+        self.execution = false;
+        self.last_failed_word = Some(self.current_word.clone());
+        self.battle_log.push(format!(
+            "⏰ Timeout! '{}' was too slow",
+            self.current_word
+        ));
+        self.fumble_heavy_strike();
+        self.snap_chain();
+        self.close_channel();
+        self.reset_word_state();
+        self.tempered = false;
+        self.exploit = None;
+        self.inscription = None;
+        self.phase = CombatPhase::EnemyTurn;
+    }
+
+
+    pub fn execute_enemy_turn(&mut self, player: &mut Player) {
+        if self.phase != CombatPhase::EnemyTurn {
+            return;
+        }
+
+        if self.pending_heal > 0 {
+            player.heal(self.pending_heal);
+            self.pending_heal = 0;
+        }
+        self.settle_recoil(player);
+
+
+        self.check_enrage();
+        self.enrage.on_enemy_turn();
+        let wounds = self.wound_effects();
+        
+        // Skill: Evasion check (Shadow tree)
+        let mut rng = run_rng::stream(Stream::Combat);
+        if rng.gen::<f32>() < self.skill_evasion_chance {
+            self.battle_log.push("✨ You dodge the attack!".to_string());
+            self.turn += 1;
+            self.current_word = self.next_prompt();
+            self.typed_input.clear();
+            self.time_remaining = self.time_limit;
+            self.last_tick = Instant::now();
+            self.typing_started = false;
+            self.phase = CombatPhase::PlayerTurn;
+            return;
+        }
+        
+        // Its wounds can cost it the attack
+        if rng.gen::<f32>() < wounds.stumble_chance {
+            self.battle_log.push(format!("🦵 {} stumbles on its wounded legs and loses its footing!", self.enemy.name));
+            self.begin_player_turn();
+            return;
+        }
+        if rng.gen::<f32>() < wounds.miss_chance {
+            self.battle_log.push(format!("🤕 {} swings wide, dazed by its head wound!", self.enemy.name));
+            self.begin_player_turn();
+            return;
+        }
+        
+        let damage = self.blow(&self.enrage, player);
+        
+        // A ready riposte negates the blow and sends it back
+        if self.riposte_ready() {
+            self.riposte(damage);
+            return;
+        }
+        
+        // A fumbled heavy strike leaves an opening
+        let damage = if self.exposed {
+            self.exposed = false;
+            ((damage as f32) * HEAVY_EXPOSURE_MULT).round() as i32
+        } else {
+            damage
+        };
+        
+        let actual_damage = if self.player_shield > 0 {
+            let absorbed = damage.min(self.player_shield);
+            self.player_shield -= absorbed;
+            damage - absorbed
+        } else {
+            damage
+        };
+
+        player.take_damage(actual_damage);
+        self.total_damage_taken += actual_damage;
+        if self.stance == Stance::Guarded {
+            self.blows_turned += 1;
+        }
+        
+        // Get a random attack message
+        let attack_msg = self.enemy.get_attack_message();
+        self.battle_log.push(format!(
+            "💥 {} {} for {} damage!",
+            self.enemy.name, attack_msg, actual_damage
+        ));
+
+        if player.hp <= 0 {
+            self.phase = CombatPhase::Defeat;
+            self.finalize_result(false, false, false);
+        } else {
+            self.begin_player_turn();
+        }
+
+    }
 
-        let expected_char = self.current_word.chars().nth(self.typed_input.len() - 1);
-        if expected_char == Some(c) {
-            self.correct_chars += 1;
+    /// The blow the enemy strikes with `enrage`, before a riposte, an
+    /// opening or a shield has its say
+    fn blow(&self, enrage: &EnrageTimer, player: &Player) -> i32 {
+        let raw_damage = (self.enemy.attack_power as f32 * enrage.attack_mult() * self.wound_effects().attack_mult).round() as i32;
+        let defense_reduction = (player.stats.vitality as f32 * 0.5).floor() as i32;
+        let damage = (raw_damage - defense_reduction).max(1);
+        // Apply skill damage reduction (Endurance/Shadow trees) and stance
+        ((damage as f32) * (1.0 - self.skill_damage_reduction) * self.stance.damage_taken_mult() * self.curses.damage_taken_mult * self.difficulty_scale.enemy_damage_mult).round() as i32
+    }
+
+    /// The blow the enemy's next turn will strike, its enrage counted as
+    /// the turn will count it
+    pub fn next_blow(&self, player: &Player) -> i32 {
+        let mut enrage = self.enrage;
+        enrage.check(self.words_typed);
+        enrage.on_enemy_turn();
+        self.blow(&enrage, player)
+    }
+
+    /// Mistakes typed in a reckless stance hurt once their word is done,
+    /// the killing word included. A won fight can't take the last HP.
+    pub fn settle_recoil(&mut self, player: &mut Player) {
+        let recoil = std::mem::take(&mut self.pending_recoil);
+        let recoil = if self.phase == CombatPhase::Victory { recoil.min(player.hp - 1) } else { recoil };
+        if recoil <= 0 {
+            return;
+        }
+        player.take_damage(recoil);
+        self.total_damage_taken += recoil;
+        self.battle_log.push(format!("🔥 Your reckless mistakes cost you {} HP.", recoil));
+    }
+
+    /// Start the next player turn with new content from game data
+    fn begin_player_turn(&mut self) {
+        self.turn += 1;
+        self.current_word = self.next_prompt();
+        
+        // Adjust time based on content length
+        self.time_limit = self.time_limit_for(&self.current_word);
+        
+        self.typed_input.clear();
+        self.time_remaining = self.time_limit;
+        self.last_tick = Instant::now();
+        self.typing_started = false;
+        self.phase = CombatPhase::PlayerTurn;
+    }
+
+
+    /// Warn as the enemy nears its enrage limit, and announce the enrage
+    fn check_enrage(&mut self) {
+        let hp_pct = (self.enemy.current_hp.max(0) * 100) / self.enemy.max_hp.max(1);
+        match self.enrage.check(self.words_typed) {
+            Some(EnrageEvent::Warning) => {
+                let line = match &mut self.immersive {
+                    Some(imm) => imm.on_enrage_warning(),
+                    None => format!("{} is losing patience...", self.enemy.name),
+                };
+                self.battle_log.push(format!("⏳ {}", line));
+            }
+            Some(EnrageEvent::Enraged) => {
+                let line = match &mut self.immersive {
+                    Some(imm) => imm.on_enrage(hp_pct),
+                    None => format!("The {} flies into a rage!", self.enemy.name),
+                };
+                self.battle_log.push(format!("😡 {}", line));
+                self.battle_log.push(format!("😡 {} is ENRAGED - its blows grow heavier every turn!", self.enemy.name));
+            }
+            None => {}
+        }
+    }
+
+    /// Pick the next prompt from game data, honoring any preferred word
+    /// length, and roll whether it is shown jumbled or carries an aimed letter
+    fn next_prompt(&mut self) -> String {
+        let prompt = self.pick_prompt();
+        let mut rng = run_rng::stream(Stream::Words);
+        self.puzzle = self.challenge_kind.roll(&prompt, &mut rng);
+        self.aim = if self.aiming && self.puzzle.is_none() {
+            Aim::roll(&prompt, &mut rng)
+        } else {
+            None
+        };
+        prompt
+    }
+
+    fn pick_prompt(&mut self) -> String {
+        let mut rng = run_rng::stream(Stream::Words);
+        if self.use_sentences {
+            if let Some(sentence) = self.take_stitched_prompt() {
+                return sentence;
+            }
+            self.game_data.get_lore_sentence(&mut rng, self.floor, self.enemy.is_boss, Some(&self.enemy.name))
+        } else if let Some(word) = self.chained_word() {
+            word
+        } else if let Some(word) = self.lexicon_word() {
+            word
+        } else if let Some(word) = self.pack_word() {
+            word
+        } else if let Some(prompt) = self.word_bias.roll(&mut rng) {
+            match prompt {
+                BiasedPrompt::Word(word) => word,
+                BiasedPrompt::Sentence => self.game_data.get_lore_sentence(&mut rng, self.floor, self.enemy.is_boss, Some(&self.enemy.name)),
+            }
+        } else if let Some(range) = self.word_length_range {
+            self.game_data.get_lore_word_in_range(&mut rng, self.floor, Some(&self.enemy.typing_theme), range)
+        } else {
+            self.game_data.get_lore_word(&mut rng, self.floor, Some(&self.enemy.typing_theme))
+        }
+    }
+
+
+    /// How the enemy is holding up, by remaining health
+    pub fn enemy_posture(&self) -> EnemyPosture {
+        EnemyPosture::from_health_pct(self.enemy.current_hp as f32 / self.enemy.max_hp.max(1) as f32)
+    }
+
+    /// A Dying enemy took a Precision strike: the next prompt is its finisher
+    fn offer_execution(&mut self) {
+        self.execution = true;
+        self.current_word = executions::finisher_phrase(&self.enemy.name).to_string();
+        self.typed_input.clear();
+        self.time_limit = self.time_limit_for(&self.current_word);
+        self.time_remaining = self.time_limit;
+        self.last_tick = Instant::now();
+        self.typing_started = false;
+        self.immersive_new_word();
+        self.battle_log.push(format!(
+            "☠ {} falls to its knees! Finish it: '{}'",
+            self.enemy.name, self.current_word
+        ));
+    }
+
+    /// Swap the current word for a full zone sentence: one slow, heavy strike.
+    /// Only allowed before the first keystroke of a normal attack.
+    pub fn charge_heavy_strike(&mut self) -> bool {
+        if self.phase != CombatPhase::PlayerTurn
+            || self.spell_mode
+            || self.heavy_strike
+            || self.inscription.is_some()
+            || self.channel.is_some()
+            || self.hazard.is_some()
+            || !self.typed_input.is_empty()
+        {
+            return false;
+        }
+        let Some(sentence) = self.strike_sentence() else {
+            return false;
+        };
+        if !self.fatigue.spend(HEAVY_STRIKE_COST) {
+            self.battle_log.push("😮‍💨 Too winded to wind up a heavy strike - steady your rhythm first.".to_string());
+            return false;
+        }
+        self.update_winded();
+        
+        self.heavy_strike = true;
+        self.current_word = sentence;
+        self.reset_word_state();
+        self.time_limit = (15.0 + grapheme_len(&self.current_word) as f32 * 0.1) * self.time_mult;
+        self.time_remaining = self.time_limit;
+        self.last_tick = Instant::now();
+        self.typing_started = false;
+        self.battle_log.push("🗡 You gather your strength... type it all, or be left open.".to_string());
+        self.immersive_new_word();
+        true
+    }
+
+
+    /// Corrupted enemies may mutate the untyped rest of the prompt, once per prompt.
+    /// Spell incantations are never touched.
+    fn maybe_interfere(&mut self, typed: usize) {
+        if self.interfered || self.spell_mode || self.interference_chance <= 0.0 || self.active_puzzle().is_some() {
+            return;
+        }
+        let mut rng = run_rng::stream(Stream::Enemies);
+        // Spread the per-prompt chance across its keystrokes
+        let per_key = self.interference_chance / grapheme_len(&self.current_word).max(1) as f32;
+        if rng.gen::<f32>() >= per_key {
+            return;
+        }
+        let kind = prompt_corruption::pick(prompt_corruption::is_corrupted(&self.enemy), &mut rng);
+        let Some((mutated, span)) = prompt_corruption::apply(kind, &self.current_word, typed, &mut rng) else {
+            return;
+        };
+        self.current_word = mutated;
+        self.corrupted_span = Some(span);
+        self.interfered = true;
+        self.time_remaining += INTERFERENCE_GRACE_SECS;
+        self.battle_log.push(format!("☠ {}: {}", self.enemy.name, kind.message()));
+    }
+
+
+    fn clear_interference(&mut self) {
+        self.interfered = false;
+        self.corrupted_span = None;
+    }
+
+    /// Forget the last word's slips, keystrokes and interference before a new one
+    fn reset_word_state(&mut self) {
+        self.word_errors = 0;
+        self.word_backspaces = 0;
+        self.forgiven = 0;
+        self.weak_tracker.reset();
+        self.key_times.clear();
+        self.clear_interference();
+    }
+
+
+    /// Weak-point words in the current prompt (bosses only)
+    pub fn weak_points(&self) -> Vec<WeakPoint> {
+        if self.enemy.is_boss {
+            weak_points::find(&self.current_word)
         } else {
-            // Corruption effect: MistakesDealDamage
-            if let Some(TypingModifier::MistakesDealDamage { damage_per_error }) = &self.corruption_modifier {
-                self.corruption_damage_taken += damage_per_error;
-                self.battle_log.push(format!("Corruption punishes your error! (-{} HP)", damage_per_error));
+            Vec::new()
+        }
+    }
+
+    /// A weak-point word just finished cleanly: stagger the boss
+    fn strike_weak_point(&mut self, typed_len: usize) {
+        let points = self.weak_points();
+        if let Some(point) = self.weak_tracker.on_correct(&points, typed_len) {
+            let word: String = graphemes(&self.current_word)[point.start..point.end].concat();
+            self.stagger_until = Some(Instant::now() + STAGGER_WINDOW);
+            self.battle_log.push(format!(
+                "🎯 '{}' strikes a weak point! {} staggers - strike now!",
+                word, self.enemy.name
+            ));
+        }
+    }
+
+    /// A landed word wounds the enemy where it was aimed, or anywhere
+    fn wound_enemy(&mut self, aimed: Option<HitLocation>, damage: i32) {
+        let location = aimed.unwrap_or_else(|| HitLocation::random(&mut run_rng::stream(Stream::Enemies)));
+        if let Some(imm) = &mut self.immersive {
+            imm.enemy_visuals.apply_damage(damage as f32 / self.enemy.max_hp.max(1) as f32, location);
+        }
+        if aimed.is_none() {
+            return;
+        }
+        let effects = self.wound_effects();
+        let note = match location {
+            HitLocation::Head => format!(
+                "{} reels from a blow to the head! ({:.0}% to miss)",
+                self.enemy.name, effects.miss_chance * 100.0
+            ),
+            HitLocation::LeftArm | HitLocation::RightArm => format!(
+                "{}'s arm is wounded - its attacks weaken (x{:.2}).",
+                self.enemy.name, effects.attack_mult
+            ),
+            _ => format!(
+                "{}'s legs are wounded - it may stumble ({:.0}% to lose its attack).",
+                self.enemy.name, effects.stumble_chance * 100.0
+            ),
+        };
+        self.battle_log.push(format!("🎯 {}! {}", location.name(), note));
+    }
+
+    /// How the enemy's wounds change its fighting
+    pub fn wound_effects(&self) -> WoundEffects {
+        self.immersive.as_ref().map(|imm| imm.enemy_visuals.wound_effects()).unwrap_or_default()
+    }
+
+    /// Resolve a landed word against the enemy's shield
+    fn strike_shield(&mut self, attack: AttackType) {
+        match self.enemy_shield.on_hit(attack) {
+            Some(ShieldHit::Shattered { remaining: 0 }) => {
+                self.battle_log.push(format!("💥 {}'s shield shatters! Your words strike true.", self.enemy.name));
+            }
+            Some(ShieldHit::Shattered { remaining }) => {
+                self.battle_log.push(format!("🛡 {} cracks a shield pip! ({} left)", attack.name(), remaining));
             }
+            Some(ShieldHit::Deflected) => {
+                self.battle_log.push("🛡 The shield holds - only a Precision strike or Flurry can break it.".to_string());
+            }
+            None => {}
+        }
+    }
+
+    /// Whether the boss is still reeling from a weak-point strike
+    pub fn staggered(&self) -> bool {
+        self.stagger_until.is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Whether the blow about to land is turned back: one after the word
+    /// that readied the riposte, still inside its window
+    pub fn riposte_ready(&self) -> bool {
+        self.turn > self.riposte_turn && self.riposte_until.is_some_and(|until| Instant::now() < until)
+    }
+
+
+    /// Negate an incoming attack and reflect its damage onto the enemy
+    fn riposte(&mut self, damage: i32) {
+        self.riposte_until = None;
+        self.blows_turned += 1;
+        self.enemy.current_hp -= damage;
+        self.total_damage_dealt += damage;
+        self.battle_log.push(format!(
+            "↩ RIPOSTE! You turn {}'s attack back for {} damage!",
+            self.enemy.name, damage
+        ));
+        
+        let hp_pct = (self.enemy.current_hp.max(0) * 100) / self.enemy.max_hp.max(1);
+        if let Some(ref mut imm) = self.immersive {
+            imm.on_riposte(damage, hp_pct);
+        }
+        
+        if self.enemy.current_hp <= 0 {
+            self.enemy.current_hp = 0;
+            self.phase = CombatPhase::Victory;
+            self.finalize_result(true, false, false);
+            return;
+        }
+        
+        self.turn += 1;
+        self.current_word = self.next_prompt();
+        self.time_limit = self.time_limit_for(&self.current_word);
+        self.typed_input.clear();
+        self.time_remaining = self.time_limit;
+        self.last_tick = Instant::now();
+        self.typing_started = false;
+        self.phase = CombatPhase::PlayerTurn;
+    }
+
+
+    /// Hand over sentences stitched from the run chronicle
+    pub fn set_stitched_prompts(&mut self, prompts: Vec<String>) {
+        self.stitched_prompts = prompts;
+    }
+
+    /// Turn on the Word Chain modifier for this fight.
+    pub fn enable_word_chain(&mut self) {
+        self.word_chain = Some(WordChain::default());
+        self.battle_log.push("⛓ Word Chain: each word begins where the last one ended.".to_string());
+    }
+
+    /// Draw words from a challenge's word pack.
+    pub fn set_word_pack(&mut self, words: Vec<String>) {
+        if words.is_empty() {
+            return;
+        }
+        self.word_pack = words;
+        if !self.use_sentences {
+            self.current_word = self.next_prompt();
+            self.time_limit = self.time_limit_for(&self.current_word);
+            self.time_remaining = self.time_limit;
+        }
+    }
+
+
+    /// Ward off corruption: prompts stay as written and its curse is lifted.
+    pub fn ward_corruption(&mut self) {
+        self.interference_chance = 0.0;
+        self.corruption_modifier = None;
+    }
+
+    /// Mix the player's lexicon words in among the prompts.
+    pub fn set_lexicon(&mut self, words: Vec<String>) {
+        self.lexicon = words;
+    }
+
+    /// Now and then, a word from the player's lexicon
+    fn lexicon_word(&self) -> Option<String> {
+        let mut rng = run_rng::stream(Stream::Words);
+        if self.lexicon.is_empty() || rng.gen::<f32>() >= LEXICON_CHANCE {
+            return None;
+        }
+        self.lexicon.choose(&mut rng).cloned()
+    }
+
+    /// Whether the current prompt is a word the player bought
+    fn is_lexicon_word(&self) -> bool {
+        self.lexicon.iter().any(|w| w.eq_ignore_ascii_case(&self.current_word))
+    }
+
+    /// Under Word Chain: a word beginning with the current prompt's last
+    /// letter. When none does, the chain snaps.
+    fn chained_word(&mut self) -> Option<String> {
+        self.word_chain?;
+        let letter = word_chain::next_letter(&self.current_word)?;
+        let mut rng = run_rng::stream(Stream::Words);
+        let word = if self.word_pack.is_empty() {
+            self.game_data.get_lore_word_chained(&mut rng, self.floor, Some(&self.enemy.typing_theme), letter, self.word_length_range)
+        } else {
+            let chained: Vec<&String> = self.word_pack.iter().filter(|w| word_chain::continues(w, letter)).collect();
+            let in_range: Vec<&String> = match self.word_length_range {
+                Some((min, max)) => chained.iter().copied().filter(|w| (min..=max).contains(&w.chars().count())).collect(),
+                None => Vec::new(),
+            };
+            in_range.choose(&mut rng).or_else(|| chained.choose(&mut rng)).map(|w| w.to_string())
+        };
+        // Nothing carries on from this letter: whatever comes next starts afresh
+        if word.is_none() {
+            self.snap_chain();
+        }
+        word
+    }
+
+    /// A missed word breaks the chain
+    fn snap_chain(&mut self) {
+        let Some(chain) = &mut self.word_chain else {
+            return;
+        };
+        let links = chain.snap();
+        if links >= 2 {
+            self.battle_log.push(format!("⛓ Your chain of {} words breaks.", links));
+        }
+    }
+
+    /// A word from the word pack, within the preferred length when any fit
+    fn pack_word(&self) -> Option<String> {
+        let mut rng = run_rng::stream(Stream::Words);
+        let in_range: Vec<&String> = match self.word_length_range {
+            Some((min, max)) => self.word_pack.iter().filter(|w| (min..=max).contains(&w.chars().count())).collect(),
+            None => Vec::new(),
+        };
+        in_range.choose(&mut rng).map(|w| w.to_string())
+            .or_else(|| self.word_pack.choose(&mut rng).cloned())
+    }
+
+    /// A full sentence to strike with: one of the run's own, or the zone's
+    fn strike_sentence(&mut self) -> Option<String> {
+        self.take_stitched_prompt().or_else(|| {
+            LoreWords::get_zone_sentences(self.floor)
+                .choose(&mut run_rng::stream(Stream::Words))
+                .map(|sentence| sentence.to_string())
+        })
+    }
+
+    /// Sometimes take a run-specific sentence instead of a stock one
+    fn take_stitched_prompt(&mut self) -> Option<String> {
+        if self.stitched_prompts.is_empty() || run_rng::stream(Stream::Words).gen::<f32>() >= STITCHED_PROMPT_CHANCE {
+            return None;
+        }
+        self.stitched_prompts.pop()
+    }
+
+
+    /// Open a channel (`/channel`): a stream of zone words that burns the
+    /// enemy until the player pauses or slips. Only before typing a word.
+    fn open_channel(&mut self) -> bool {
+        if self.spell_mode || self.heavy_strike || self.execution || self.inscription.is_some() || self.channel.is_some() {
+            return false;
+        }
+        if !self.typed_input.is_empty() {
+            self.battle_log.push("Open a channel before you start typing.".to_string());
+            return false;
+        }
+        if self.turn < self.channel_ready_turn {
+            self.battle_log.push(format!("/{} needs {} more turn(s).", channeling::CHANNEL_KEYWORD, self.channel_ready_turn - self.turn));
+            return false;
+        }
+        self.channel = Some(Channel::new(self.floor));
+        self.channel_ready_turn = self.turn + CHANNEL_COOLDOWN;
+        self.feed_channel();
+        self.typing_started = false;
+        self.battle_log.push(format!(
+            "≋ You open a channel. Keep typing - pause over {:.1}s and it breaks.",
+            channeling::MAX_PAUSE.as_secs_f32()
+        ));
+        true
+    }
+
+    /// Put the next zone word of the channel in front of the player
+    fn feed_channel(&mut self) {
+        self.current_word = LoreWords::next_stream_word(&mut run_rng::stream(Stream::Words), self.floor, &self.current_word);
+        self.puzzle = None;
+        self.typed_input.clear();
+        self.reset_word_state();
+        self.time_limit = self.time_limit_for(&self.current_word);
+        self.time_remaining = self.time_limit;
+        self.immersive_new_word();
+    }
+
+    /// A channel word was finished: a clean one keeps the stream flowing
+    fn on_channel_word(&mut self) {
+        self.words_typed += 1;
+        if !text_matches(&self.current_word, &self.typed_input) {
+            self.replay.finish_word(0, true);
+            self.combo = 0;
+            self.battle_log.push(format!(
+                "✗ Mistyped '{}' (typed '{}')",
+                self.current_word, self.typed_input
+            ));
+            self.snap_chain();
+            self.close_channel();
+            self.reset_word_state();
+            self.phase = CombatPhase::EnemyTurn;
+            return;
+        }
+        self.words_correct += 1;
+        self.combo += 1;
+        self.max_combo = self.max_combo.max(self.combo);
+        let dealt = self.channel.as_mut().map(|c| c.on_word()).unwrap_or(0);
+        self.replay.finish_word(dealt, true);
+        self.feed_channel();
+    }
+
+    /// Let the channel burn for `secs`, or break it if the player paused
+    fn burn_channel(&mut self, now: Instant, secs: f32) {
+        // A channel is no single word: only its speed and the clock count
+        let mult = self.skill_damage_mult(0.0, 0.0) * self.enemy_shield.damage_mult();
+        let Some(channel) = &mut self.channel else {
+            return;
+        };
+        if channel.broken_by_pause(now) {
+            self.battle_log.push("≋ Your fingers falter...".to_string());
+            self.close_channel();
+            self.replay.finish_word(0, false);
+            self.reset_word_state();
+            self.phase = CombatPhase::EnemyTurn;
+            return;
+        }
+        let damage = channel.burn(secs, mult);
+        if damage <= 0 {
+            return;
+        }
+        self.enemy.current_hp -= damage;
+        self.total_damage_dealt += damage;
+        if self.enemy.current_hp <= 0 {
+            self.overkill += -self.enemy.current_hp;
+            self.enemy.current_hp = 0;
+            self.close_channel();
+            self.phase = CombatPhase::Victory;
+            self.finalize_result(true, false, false);
+        }
+    }
+
+    /// End the channel, if one is open, and report what it did
+    fn close_channel(&mut self) {
+        if let Some(channel) = self.channel.take() {
+            self.battle_log.push(format!(
+                "≋ The channel closes after {} word(s) - {} damage.",
+                channel.words, channel.dealt
+            ));
+        }
+    }
+
+    /// A heavy strike that misses leaves the player exposed
+    fn fumble_heavy_strike(&mut self) {
+        if self.heavy_strike {
+            self.heavy_strike = false;
+            self.exposed = true;
+            self.battle_log.push("Your heavy swing goes wide - your guard is down!".to_string());
+        }
+    }
+
+
+    /// Time allowed for a prompt, scaled by the director's time pressure
+    fn time_limit_for(&self, prompt: &str) -> f32 {
+        let length = grapheme_len(prompt) as f32;
+        let mut base = if self.use_sentences {
+            15.0 + (length * 0.1)
+        } else {
+            5.0 + (length * 0.2)
+        };
+        if self.puzzle.as_ref().is_some_and(|p| p.answer == prompt) {
+            base += PUZZLE_TIME_BONUS;
+        }
+        base * self.time_mult
+    }
+
+    /// The aimed letter in the current prompt, if any
+    pub fn active_aim(&self) -> Option<&Aim> {
+        self.aim.as_ref().filter(|a| a.word == self.current_word)
+    }
+
+    /// What the player types: the current word, with any aimed letter capitalized
+    pub fn target(&self) -> &str {
+        self.active_aim().map_or(&self.current_word, |a| &a.prompt)
+    }
+
+    /// Listen-and-type prompts can't show an aimed capital
+    pub fn disable_aiming(&mut self) {
+        self.aiming = false;
+        self.aim = None;
+    }
+
+    /// The jumble standing in for the current prompt, if any
+    pub fn active_puzzle(&self) -> Option<&Puzzle> {
+        self.puzzle.as_ref().filter(|p| p.answer == self.current_word)
+    }
+
+
+    /// Apply the difficulty director's time pressure and word length preference.
+    pub fn apply_director(&mut self, director: &DifficultyDirector) {
+        if !director.enabled || director.sample_count() == 0 {
+            return;
+        }
+        self.time_mult = director.time_mult();
+        self.word_length_range = Some(director.word_length_range());
+        
+        // Re-roll the opening prompt so it respects the new preferences
+        self.current_word = self.next_prompt();
+        self.time_limit = self.time_limit_for(&self.current_word);
+        self.time_remaining = self.time_limit;
+    }
+
+
+    /// Scale the fight by the difficulty preset and bind the typo policy.
+    /// Call after `apply_director`, which sets the time pressure afresh.
+    pub fn apply_settings(&mut self, difficulty: &DifficultyConfig, typos: TypoPolicy) {
+        self.backspace_limit = typos.backspace_limit();
+        let scale = |hp: i32| ((hp as f32 * difficulty.enemy_hp_mult).round() as i32).max(1);
+        self.enemy.max_hp = scale(self.enemy.max_hp);
+        self.enemy.current_hp = scale(self.enemy.current_hp);
+        self.time_mult *= difficulty.time_mult;
+        self.time_limit = self.time_limit_for(&self.current_word);
+        self.time_remaining = self.time_limit;
+        self.difficulty_scale = difficulty.clone();
+    }
+
+    /// Bind the player's cursed loot to this fight. Call after
+    /// `apply_director` and any ward, so curses still bite.
+    pub fn apply_curses(&mut self, load: CurseLoad) {
+        if load == CurseLoad::default() {
+            return;
+        }
+        self.curses = load;
+        self.interference_chance += load.prompt_corruption;
+        self.time_mult *= load.time_mult;
+        self.time_limit = self.time_limit_for(&self.current_word);
+        self.time_remaining = self.time_limit;
+    }
 
+    /// Bind the run's ascension level to this fight. Call last, so its
+    /// rules override gear, pacts, wards and the director.
+    pub fn apply_ascension(&mut self, level: u32) {
+        let rules = ascension::rules(level);
+        if rules.is_empty() {
+            return;
         }
-
-
-        // Check if word is complete
-        if self.typed_input.len() >= self.current_word.len() {
-            self.on_word_complete();
+        for rule in rules {
+            match rule {
+                AscensionRule::FasterTimers => self.time_mult *= ASCENSION_TIME_MULT,
+                AscensionRule::LongerWords => {
+                    let (min, max) = self.word_length_range.unwrap_or((ASCENSION_MIN_WORD_LEN, usize::MAX));
+                    self.word_length_range = Some((min.max(ASCENSION_MIN_WORD_LEN), max.max(ASCENSION_MIN_WORD_LEN)));
+                }
+                AscensionRule::Unforgiving => {
+                    self.unforgiving = true;
+                    self.impact_params.typo_forgiveness = 0;
+                    self.sync_impact_params();
+                }
+                AscensionRule::CorruptedPrompts => self.interference_chance += ASCENSION_CORRUPTION,
+            }
         }
-
+        self.current_word = self.next_prompt();
+        self.time_limit = self.time_limit_for(&self.current_word);
+        self.time_remaining = self.time_limit;
+        self.battle_log.push(format!("⇑ {}", ascension::describe(level)));
     }
 
+    /// Fighting in a room you named: its name lends you power
+    pub fn apply_room_name(&mut self, name: &str) {
+        self.named_room_mult = NAMED_ROOM_POWER;
+        self.battle_log.push(format!("'{}' answers to you. Your words strike harder here.", name));
+    }
 
-    pub fn on_backspace(&mut self) {
-        if self.phase != CombatPhase::PlayerTurn {
-            return;
-        }
 
-        self.typed_input.pop();
+    /// Set the player's class so its abilities can be used.
+    pub fn set_class(&mut self, class: PlayerClass) {
+        self.class = class;
+        self.word_bias = WordBias::for_class(class);
+        let keywords: Vec<String> = class_abilities::abilities_for(class)
+            .iter()
+            .map(|a| format!("{}{}", COMMAND_PREFIX, a.keyword()))
+            .collect();
+        self.battle_log.push(format!("{} abilities: {}", class.name(), keywords.join("  ")));
     }
 
 
-    fn on_word_complete(&mut self) {
-        self.words_typed += 1;
-        
-        if self.typed_input == self.current_word {
-            self.words_correct += 1;
-            self.combo += 1;
-            if self.combo > self.max_combo {
-                self.max_combo = self.combo;
-            }
+    /// Bind the run's patron pact to this fight.
+    pub fn apply_patron(&mut self, patron: Patron) {
+        self.patron = Some(patron);
+        self.battle_log.push(format!("{} watch: {}", patron.name(), patron.mechanic()));
+    }
 
-            
-            // Calculate damage based on typing performance
-            let wpm = self.calculate_wpm();
-            let accuracy = self.calculate_accuracy();
-            let damage = self.calculate_damage(wpm, accuracy);
-            
-            self.enemy.current_hp -= damage;
-            self.total_damage_dealt += damage;
-            
-            // Track WPM
-            if wpm > 0.0 {
-                self.wpm_samples.push(wpm);
-                if wpm > self.peak_wpm {
-                    self.peak_wpm = wpm;
-                }
-            }
-            
-            self.battle_log.push(format!(
-                "✓ {} ({:.0} WPM, {:.0}% acc) - {} damage! [{}x combo]",
-                self.current_word, wpm, accuracy * 100.0, damage, self.combo
-            ));
-            
-            if self.enemy.current_hp <= 0 {
-                self.enemy.current_hp = 0;
-                self.phase = CombatPhase::Victory;
-                self.finalize_result(true, false, false);
-            } else {
-                self.phase = CombatPhase::EnemyTurn;
-            }
 
-        } else {
-            self.combo = 0;
-            self.battle_log.push(format!(
-                "✗ Mistyped '{}' (typed '{}')",
-                self.current_word, self.typed_input
-            ));
-            self.phase = CombatPhase::EnemyTurn;
+    /// Scribes: take back a wrong keystroke, once per word
+    fn forgive_slip(&mut self) -> bool {
+        if self.unforgiving || self.patron != Some(Patron::Scribes) || self.forgiven >= SCRIBE_FORGIVENESS {
+            return false;
         }
-
+        pop_grapheme(&mut self.typed_input);
+        self.forgiven += 1;
+        self.battle_log.push("✒ The Scribes strike out your slip.".to_string());
+        true
     }
 
 
-    fn on_word_timeout(&mut self) {
-        self.words_typed += 1;
-        self.combo = 0;
-        self.battle_log.push(format!(
-            "⏰ Timeout! '{}' was too slow",
-            self.current_word
-        ));
-        self.phase = CombatPhase::EnemyTurn;
+    /// Milliseconds between the current word's keystrokes
+    fn key_intervals(&self) -> Vec<f32> {
+        self.key_times
+            .windows(2)
+            .map(|w| w[1].duration_since(w[0]).as_secs_f32() * 1000.0)
+            .collect()
     }
 
-
-    pub fn execute_enemy_turn(&mut self, player: &mut Player) {
-        if self.phase != CombatPhase::EnemyTurn {
+    /// Naturalists: a word typed with a steady rhythm restores HP
+    fn rhythm_heal(&mut self) {
+        if self.patron != Some(Patron::Naturalists) {
             return;
         }
+        let heal = patrons::rhythm_heal(&self.key_intervals());
+        if heal > 0 {
+            self.pending_heal += heal;
+            self.battle_log.push(format!("🌿 Your steady rhythm restores {} HP.", heal));
+        }
+    }
 
 
-        let raw_damage = self.enemy.attack_power;
-        let defense_reduction = (player.stats.vitality as f32 * 0.5).floor() as i32;
-        let damage = (raw_damage - defense_reduction).max(1);
-        
-        // Skill: Evasion check (Shadow tree)
-        let mut rng = rand::thread_rng();
-        if rng.gen::<f32>() < self.skill_evasion_chance {
-            self.battle_log.push("✨ You dodge the attack!".to_string());
-            self.turn += 1;
-            self.current_word = if self.use_sentences {
-                self.game_data.get_lore_sentence(self.floor, self.enemy.is_boss, Some(&self.enemy.name))
-            } else {
-                self.game_data.get_lore_word(self.floor, Some(&self.enemy.typing_theme))
-            };
-            self.typed_input.clear();
-            self.time_remaining = self.time_limit;
-            self.last_tick = Instant::now();
-            self.typing_started = false;
-            self.phase = CombatPhase::PlayerTurn;
-            return;
+    /// Close the ability command line without using anything
+    pub fn cancel_ability(&mut self) {
+        self.ability_input = None;
+    }
+
+
+    /// Use the ability typed on the command line. Returns whether one was used.
+    pub fn submit_ability(&mut self) -> bool {
+        let Some(input) = self.ability_input.take() else {
+            return false;
+        };
+        if channeling::is_channel_command(&input) {
+            return self.open_channel();
         }
-        
-        // Apply skill damage reduction (Endurance/Shadow trees)
-        let damage = ((damage as f32) * (1.0 - self.skill_damage_reduction)).round() as i32;
-        
-        let actual_damage = if self.player_shield > 0 {
-            let absorbed = damage.min(self.player_shield);
-            self.player_shield -= absorbed;
-            damage - absorbed
-        } else {
-            damage
+        if self.true_name.as_ref().is_some_and(|name| name.matches(&input)) {
+            return self.speak_true_name();
+        }
+        if true_names::is_spare_command(&input) {
+            return self.try_spare();
+        }
+        let Some(ability) = class_abilities::parse(&input, self.class) else {
+            self.battle_log.push(format!("{} knows no '{}'.", self.class.name(), input.trim()));
+            return false;
         };
+        let turns_left = self.cooldowns.turns_left(ability, self.turn);
+        if turns_left > 0 {
+            self.battle_log.push(format!("/{} needs {} more turn(s).", ability.keyword(), turns_left));
+            return false;
+        }
+        if !self.use_ability(ability) {
+            return false;
+        }
+        self.cooldowns.start(ability, self.turn);
+        true
+    }
 
-        player.take_damage(actual_damage);
-        self.total_damage_taken += actual_damage;
-        
-        // Get a random attack message
-        let attack_msg = self.enemy.get_attack_message();
-        self.battle_log.push(format!(
-            "💥 {} {} for {} damage!",
-            self.enemy.name, attack_msg, actual_damage
-        ));
 
-        if player.hp <= 0 {
-            self.phase = CombatPhase::Defeat;
-            self.finalize_result(false, false, false);
-        } else {
-            self.turn += 1;
-            // Start next player turn with new content from game data
-            self.current_word = if self.use_sentences {
-                self.game_data.get_lore_sentence(self.floor, self.enemy.is_boss, Some(&self.enemy.name))
-            } else {
-                self.game_data.get_lore_word(self.floor, Some(&self.enemy.typing_theme))
-            };
-            
-            // Adjust time based on content length
-            self.time_limit = if self.use_sentences {
-                15.0 + (self.current_word.len() as f32 * 0.1)
-            } else {
-                5.0 + (self.current_word.len() as f32 * 0.2)
-            };
-            
-            self.typed_input.clear();
-            self.time_remaining = self.time_limit;
-            self.last_tick = Instant::now();
-            self.typing_started = false;
-            self.phase = CombatPhase::PlayerTurn;
+    /// Apply an ability's effect. Returns false if it had nothing to act on.
+    fn use_ability(&mut self, ability: ClassAbility) -> bool {
+        match ability {
+            ClassAbility::Reforge => {
+                let fresh = self.next_prompt();
+                let old = std::mem::replace(&mut self.current_word, fresh);
+                self.heavy_strike = false;
+                self.inscription = None;
+                self.typed_input.clear();
+                self.reset_word_state();
+                self.time_limit = self.time_limit_for(&self.current_word);
+                self.time_remaining = self.time_limit;
+                self.typing_started = false;
+                self.immersive_new_word();
+                self.battle_log.push(format!("⚒ You reforge '{}' into '{}'.", old, self.current_word));
+            }
+            ClassAbility::Temper => {
+                self.tempered = true;
+                self.battle_log.push("⚒ You temper your next word. It will bite deep.".to_string());
+            }
+            ClassAbility::Decrypt => {
+                let weakest = DamageType::ALL.into_iter().max_by(|a, b| {
+                    self.enemy.resistances.multiplier(*a).total_cmp(&self.enemy.resistances.multiplier(*b))
+                });
+                match weakest.filter(|t| self.enemy.resistances.multiplier(*t) > 1.0) {
+                    Some(weakness) => {
+                        self.exploit = Some(weakness);
+                        self.battle_log.push(format!(
+                            "🔓 Decrypted: {} is weak to {} {}. Strike now!",
+                            self.enemy.name, weakness.icon(), weakness.name()
+                        ));
+                    }
+                    None => {
+                        self.battle_log.push(format!("🔒 {} has no weakness to exploit.", self.enemy.name));
+                        return false;
+                    }
+                }
+            }
+            ClassAbility::Patch => {
+                let shield = 5 + self.floor as i32 * 2;
+                self.player_shield += shield;
+                self.battle_log.push(format!("🛡 You patch your defenses. (+{} shield)", shield));
+            }
+            ClassAbility::Replay => {
+                let Some(damage) = self.last_perfect_damage else {
+                    self.battle_log.push("Nothing perfect to replay yet.".to_string());
+                    return false;
+                };
+                self.enemy.current_hp -= damage;
+                self.total_damage_dealt += damage;
+                self.battle_log.push(format!("📖 You replay your last perfect word - {} damage!", damage));
+                if self.enemy.current_hp <= 0 {
+                    self.enemy.current_hp = 0;
+                    self.phase = CombatPhase::Victory;
+                    self.finalize_result(true, false, false);
+                }
+            }
+            ClassAbility::Annotate => {
+                self.time_remaining += ANNOTATE_SECS;
+                self.time_limit = self.time_limit.max(self.time_remaining);
+                self.battle_log.push(format!("✎ You annotate the margins. (+{:.0}s)", ANNOTATE_SECS));
+            }
+            ClassAbility::Inscribe => {
+                if !self.typed_input.is_empty() || self.heavy_strike || self.execution {
+                    self.battle_log.push("Inscribe before you start typing.".to_string());
+                    return false;
+                }
+                let Some(sentence) = self.strike_sentence() else {
+                    return false;
+                };
+                self.current_word = sentence;
+                self.clear_interference();
+                self.time_limit = self.time_limit_for(&self.current_word);
+                self.time_remaining = self.time_limit;
+                self.typing_started = false;
+                self.immersive_new_word();
+                let inscription = Inscription::begin(grapheme_len(&self.current_word));
+                self.battle_log.push(format!(
+                    "✎ Study the words... in {:.0}s they fade, and you write from memory.",
+                    inscription.preview_secs_left()
+                ));
+                self.inscription = Some(inscription);
+            }
         }
+        true
+    }
+
 
+    /// Use a consumable typed on the command line. Returns false if it had
+    /// nothing to act on (so it isn't spent).
+    pub fn use_consumable(&mut self, item: &Consumable, player: &mut Player) -> bool {
+        self.ability_input = None;
+        match item.effect {
+            ConsumableEffect::Heal { amount } => {
+                player.heal(amount);
+                self.battle_log.push(format!("🧪 {}: you restore {} HP.", item.name, amount));
+            }
+            ConsumableEffect::HealPercent { percent } => {
+                let amount = (player.max_hp as f32 * percent).round() as i32;
+                player.heal(amount);
+                self.battle_log.push(format!("🧪 {}: you restore {} HP.", item.name, amount));
+            }
+            ConsumableEffect::EraseMistake => {
+                let expected = graphemes(self.target());
+                let typed = graphemes(&self.typed_input);
+                let Some(pos) = typed.iter().zip(&expected).position(|(t, e)| !graphemes_match(e, t)) else {
+                    self.battle_log.push(format!("No mistake to erase with the {}.", item.name));
+                    return false;
+                };
+                self.typed_input = typed[..pos].concat();
+                self.word_errors = self.word_errors.saturating_sub(1);
+                self.total_chars = (self.total_chars - 1).max(self.correct_chars);
+                self.battle_log.push(format!("⬜ {}: the mistake vanishes.", item.name));
+            }
+            ConsumableEffect::PauseTimer { seconds } => {
+                self.timer_hold += seconds;
+                self.battle_log.push(format!("🔖 {}: the enemy waits. (timer held {:.0}s)", item.name, seconds));
+            }
+            _ => {
+                self.battle_log.push(format!("The {} is no use mid-fight.", item.name));
+                return false;
+            }
+        }
+        true
     }
 
 
+    /// Speed over the current word, first key to last. Taken from the keys'
+    /// own stamps, as the word timer stands still in turn-based fights.
     fn calculate_wpm(&self) -> f32 {
-        if self.time_remaining >= self.time_limit {
+        let (Some(first), Some(last)) = (self.key_times.first(), self.key_times.last()) else {
             return 0.0;
-        }
-
-        let time_taken = self.time_limit - self.time_remaining;
-        if time_taken <= 0.0 {
+        };
+        let minutes = last.duration_since(*first).as_secs_f32() / 60.0;
+        if minutes <= 0.0 {
             return 0.0;
         }
 
-        let words = self.current_word.len() as f32 / 5.0;
-        let minutes = time_taken / 60.0;
+        let words = grapheme_len(&self.current_word) as f32 / 5.0;
         words / minutes
     }
 
@@ -403,11 +1899,29 @@ impl CombatState {
     }
 
 
-    fn calculate_damage(&self, wpm: f32, accuracy: f32) -> i32 {
+    /// Typed words cut like steel; a heavy strike lands as the Word itself
+    pub fn attack_damage_type(&self) -> DamageType {
+        if let Some(exploit) = self.exploit {
+            exploit
+        } else if self.heavy_strike {
+            DamageType::Word
+        } else {
+            DamageType::Steel
+        }
+    }
+
+
+    /// Skill damage multiplier for a word typed at this speed and accuracy,
+    /// with the time left on the clock
+    fn skill_damage_mult(&self, wpm: f32, word_accuracy: f32) -> f32 {
+        self.skills.as_ref().map_or(1.0, |s| s.get_damage_multiplier(wpm, word_accuracy, self.time_remaining))
+    }
+
+    fn calculate_damage(&self, wpm: f32, accuracy: f32) -> (i32, bool) {
         let base_damage = 10;
         
-        // WPM bonus: +1 damage per 10 WPM above 30
-        let wpm_bonus = ((wpm - 30.0) / 10.0).max(0.0) as i32;
+        // WPM bonus: +1 damage per 10 WPM above 30 (half again when Aggressive)
+        let wpm_bonus = (((wpm - 30.0) / 10.0).max(0.0).floor() * self.stance.speed_bonus_mult()) as i32;
         
         // Accuracy multiplier: 1.0 at 100%, 0.5 at 50%
         let accuracy_mult = 0.5 + (accuracy * 0.5);
@@ -416,8 +1930,9 @@ impl CombatState {
         // Max 3x damage at 20 combo
         let combo_mult = 1.0 + (self.combo as f32 * 0.1).min(2.0);
         
-        // Skill-based damage multiplier (from Precision/Speed trees)
-        let skill_mult = self.skill_damage_mult;
+        // Skill-based damage multiplier (from Precision/Speed trees), judged on this word
+        let word_accuracy = 1.0 - self.word_errors as f32 / grapheme_len(self.target()).max(1) as f32;
+        let skill_mult = self.skill_damage_mult(wpm, word_accuracy);
         
         // Transcendence check: at high WPM, all damage doubled
         let transcendence_mult = match self.skill_transcendence_threshold {
@@ -429,16 +1944,46 @@ impl CombatState {
             * accuracy_mult 
             * combo_mult 
             * skill_mult
-            * transcendence_mult;
+            * transcendence_mult
+            * stances::scale_bonus(self.flow_damage_mult, self.stance.rhythm_bonus_mult())
+            * self.named_room_mult
+            * self.curses.damage_mult
+            * self.difficulty_scale.player_damage_mult;
+        
+        if self.heavy_strike {
+            damage *= HEAVY_STRIKE_MULT;
+        }
+        if self.tempered {
+            damage *= TEMPER_MULT;
+        }
+        if self.patron == Some(Patron::Mechanists) {
+            damage *= patrons::speed_mult(wpm);
+        }
+        if self.staggered() {
+            damage *= STAGGER_MULT;
+        }
+        if self.inscription.is_some() {
+            damage *= INSCRIPTION_MULT;
+        }
+        damage *= self.enemy_shield.damage_mult();
+        if let Some(chain) = &self.word_chain {
+            damage *= chain.bonus_mult();
+        }
+        if self.is_lexicon_word() {
+            damage *= LEXICON_POWER;
+        }
+        
+        damage *= self.enemy.resistances.multiplier(self.attack_damage_type());
         
         // Critical hit check (from Shadow tree)
-        let mut rng = rand::thread_rng();
-        if rng.gen::<f32>() < self.skill_crit_chance {
+        let mut rng = run_rng::stream(Stream::Combat);
+        let crit = rng.gen::<f32>() < self.skill_crit_chance * self.stance.crit_chance_mult();
+        if crit {
             damage *= self.skill_crit_mult;
         }
 
         
-        damage.round() as i32
+        (damage.round() as i32, crit)
     }
 
     pub fn try_flee(&mut self) -> bool {
@@ -448,7 +1993,7 @@ impl CombatState {
         }
 
         
-        let mut rng = rand::thread_rng();
+        let mut rng = run_rng::stream(Stream::Combat);
         let flee_chance = 0.5; // 50% base flee chance
         
         if rng.gen::<f32>() < flee_chance {
@@ -464,9 +2009,48 @@ impl CombatState {
     }
 
 
+    /// The player knows the boss's true name from a lore fragment.
+    pub fn set_true_name(&mut self, name: TrueName) {
+        self.battle_log.push(format!(
+            "📜 You remember a name from '{}'. Speak it on the command line...",
+            name.source
+        ));
+        self.true_name = Some(name);
+    }
+
+    /// Speak the boss's true name: it falters, and can be spared
+    fn speak_true_name(&mut self) -> bool {
+        let Some(name) = &mut self.true_name else {
+            return false;
+        };
+        if name.spoken {
+            self.battle_log.push(format!("{} has already heard its name.", self.enemy.name));
+            return false;
+        }
+        name.spoken = true;
+        let phrase = name.phrase.clone();
+        // Its name shakes it to the core, but never finishes it
+        let damage = ((self.enemy.max_hp as f32 * TRUE_NAME_DAMAGE).round() as i32)
+            .min(self.enemy.current_hp - 1)
+            .max(0);
+        self.enemy.current_hp -= damage;
+        self.total_damage_dealt += damage;
+        self.battle_log.push(format!(
+            "✨ \"{}.\" {} falters at its true name - {} damage! It could be spared now (/spare).",
+            phrase, self.enemy.name, damage
+        ));
+        true
+    }
+
     pub fn try_spare(&mut self) -> bool {
+        let named = self.true_name.as_ref().is_some_and(|n| n.spoken);
+        if self.enemy.is_boss && !named {
+            self.battle_log.push(format!("{} will not yield to a stranger.", self.enemy.name));
+            return false;
+        }
         // Undertale-style spare: can only spare when conditions are met
-        if self.enemy.current_hp as f32 / self.enemy.max_hp as f32 > 0.25 {
+        // (a boss that heard its true name yields at any health)
+        if !named && self.enemy.current_hp as f32 / self.enemy.max_hp as f32 > 0.25 {
             self.battle_log.push("The enemy isn't ready to be spared...".to_string());
             return false;
         }
@@ -493,6 +2077,12 @@ impl CombatState {
         } else {
             0
         };
+        let (xp, gold) = if self.executed {
+            let bonus = |reward: i32| (reward as f32 * EXECUTION_REWARD_MULT).round() as i32;
+            (bonus(xp), bonus(gold))
+        } else {
+            (xp, gold)
+        };
 
         let accuracy = if self.words_typed > 0 {
             self.words_correct as f32 / self.words_typed as f32
@@ -601,6 +2191,7 @@ impl CombatState {
         self.spell_incantation = Some(spell.incantation.clone());
         self.current_word = spell.incantation.clone();
         self.typed_input.clear();
+        self.reset_word_state();
         self.time_remaining = spell.cast_time;
         self.time_limit = spell.cast_time;
         self.battle_log.push(format!("Casting {}... Type: {}", spell.name, spell.incantation));
@@ -617,13 +2208,15 @@ impl CombatState {
 
 
         player.mp -= spell.mp_cost;
+        let resist = self.enemy.resistances.multiplier(spell.element.damage_type());
+        let note = effectiveness_note(resist);
         
         match &spell.effect {
             super::spells::SpellEffect::Damage(dmg) => {
-                let damage = (*dmg as f32 * (1.0 + player.stats.intellect as f32 * 0.05)) as i32;
+                let damage = (*dmg as f32 * (1.0 + player.stats.intellect as f32 * 0.05) * resist) as i32;
                 self.enemy.current_hp -= damage;
                 self.total_damage_dealt += damage;
-                self.battle_log.push(format!("✦ {} deals {} damage!", spell.name, damage));
+                self.battle_log.push(format!("✦ {} deals {} damage!{}", spell.name, damage, note));
             }
 
             super::spells::SpellEffect::Heal(heal) => {
@@ -638,22 +2231,22 @@ impl CombatState {
             }
 
             super::spells::SpellEffect::Drain { damage, heal_percent } => {
-                let dmg = (*damage as f32 * (1.0 + player.stats.intellect as f32 * 0.05)) as i32;
+                let dmg = (*damage as f32 * (1.0 + player.stats.intellect as f32 * 0.05) * resist) as i32;
                 self.enemy.current_hp -= dmg;
                 let heal = dmg * heal_percent / 100;
                 player.heal(heal);
-                self.battle_log.push(format!("✦ {} drains {} life!", spell.name, dmg));
+                self.battle_log.push(format!("✦ {} drains {} life!{}", spell.name, dmg, note));
             }
 
             super::spells::SpellEffect::Multi { hits, damage_per_hit } => {
                 let mut total = 0;
                 for _ in 0..*hits {
-                    let dmg = (*damage_per_hit as f32 * (1.0 + player.stats.intellect as f32 * 0.05)) as i32;
+                    let dmg = (*damage_per_hit as f32 * (1.0 + player.stats.intellect as f32 * 0.05) * resist) as i32;
                     self.enemy.current_hp -= dmg;
                     total += dmg;
                 }
 
-                self.battle_log.push(format!("✦ {} hits {} times for {} total!", spell.name, hits, total));
+                self.battle_log.push(format!("✦ {} hits {} times for {} total!{}", spell.name, hits, total, note));
             }
 
             _ => {
@@ -684,13 +2277,7 @@ impl CombatState {
     pub fn init_immersion(&mut self, player_class: &super::player::Class) {
         use super::combat_immersion::infer_enemy_theme;
         
-        let pc = match player_class {
-            super::player::Class::Wordsmith => PlayerClass::Wordsmith,
-            super::player::Class::Scribe => PlayerClass::Chronicler,
-            super::player::Class::Spellweaver => PlayerClass::Codebreaker,
-            super::player::Class::Barbarian => PlayerClass::Wordsmith,
-            super::player::Class::Trickster => PlayerClass::Freelancer,
-        };
+        let pc = PlayerClass::from_class(player_class);
         
         let theme = infer_enemy_theme(&self.enemy.name);
         
@@ -715,6 +2302,71 @@ impl CombatState {
         }
     }
     
+    /// Give the enemy's dialogue the run's motifs to draw on
+    pub fn set_motifs(&mut self, motifs: Vec<String>) {
+        if let Some(ref mut imm) = self.immersive {
+            imm.motifs = motifs;
+        }
+    }
+
+    /// Apply worn gear to the per-keystroke impact model
+    pub fn set_impact_params(&mut self, params: ImpactParams) {
+        self.impact_params = params;
+        self.sync_impact_params();
+    }
+
+    /// Switch to the next stance. Only allowed between words.
+    pub fn cycle_stance(&mut self) -> bool {
+        if self.phase != CombatPhase::PlayerTurn || !self.typed_input.is_empty() || self.ability_input.is_some() {
+            return false;
+        }
+        self.stance = self.stance.next();
+        self.sync_impact_params();
+        self.battle_log.push(format!("{} {} stance. {}", self.stance.icon(), self.stance.name(), self.stance.description()));
+        true
+    }
+
+    /// Feed a finished word's speed to the fatigue model
+    fn pace(&mut self, wpm: f32) {
+        let change = self.fatigue.on_word(wpm);
+        self.strain(change);
+    }
+
+    /// Announce a change in fatigue and retune the impact to match
+    fn strain(&mut self, change: Option<FatigueChange>) {
+        match change {
+            Some(FatigueChange::Tired) => self.battle_log.push(
+                "😮‍💨 Your hands tire from the strain - rhythm is harder to hold. Ease off for a few words.".to_string(),
+            ),
+            Some(FatigueChange::Recovered) => self.battle_log.push(
+                "💨 You catch your breath. Your rhythm steadies.".to_string(),
+            ),
+            None => {}
+        }
+        if change.is_some() {
+            self.sync_impact_params();
+        }
+        self.update_winded();
+    }
+
+    /// Let the avatar and the enemy's tone follow critical stamina
+    fn update_winded(&mut self) {
+        let winded = self.fatigue.is_critical();
+        let hp_pct = (self.enemy.current_hp.max(0) * 100) / self.enemy.max_hp.max(1);
+        if let Some(line) = self.immersive.as_mut().and_then(|imm| imm.set_winded(winded, hp_pct)) {
+            self.battle_log.push(format!("💬 {}", line));
+        }
+    }
+
+    /// Hand the keystroke impact its parameters after fatigue and stance
+    fn sync_impact_params(&mut self) {
+        let mut params = self.stance.apply(self.impact_params);
+        params.rhythm_window_ms = self.fatigue.rhythm_window(params.rhythm_window_ms);
+        if let Some(ref mut imm) = self.immersive {
+            imm.typing.params = params;
+        }
+    }
+    
     /// Get immersive keystroke feedback for a character
     /// Returns feedback if immersion is active
     pub fn immersive_keystroke(&mut self, c: char, correct: bool) -> Option<KeystrokeFeedback> {
@@ -787,3 +2439,280 @@ impl CombatState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::enemy::EnemyType;
+    use crate::game::player::Class;
+    use crate::data::damage_types::Resistances;
+
+    fn fight() -> (CombatState, Player) {
+        let enemy = Enemy {
+            name: "Ink Wraith".to_string(),
+            max_hp: 500,
+            current_hp: 500,
+            attack_power: 14,
+            defense: 0,
+            xp_reward: 0,
+            gold_reward: 0,
+            enemy_type: EnemyType::Normal,
+            ascii_art: String::new(),
+            battle_cry: String::new(),
+            defeat_message: String::new(),
+            spare_condition: None,
+            is_boss: false,
+            typing_theme: "easy".to_string(),
+            attack_messages: vec!["lashes out".to_string()],
+            resistances: Resistances::default(),
+        };
+        let mut combat = CombatState::new(enemy, Arc::new(GameData::new()), 1, 1, None, None);
+        combat.disable_aiming();
+        (combat, Player::new("Tester".to_string(), Class::Wordsmith))
+    }
+
+    /// Type `text` as if it took `secs` seconds, first key to last
+    fn type_word(combat: &mut CombatState, text: &str, secs: f32) {
+        combat.time_remaining = combat.time_limit - secs;
+        let keys = text.chars().count();
+        let start = Instant::now() - Duration::from_secs_f32(secs);
+        for (i, c) in text.chars().enumerate() {
+            let at = secs * i as f32 / keys.saturating_sub(1).max(1) as f32;
+            combat.key_read_at = Some(start + Duration::from_secs_f32(at));
+            combat.on_char_typed(c);
+        }
+    }
+
+    #[test]
+    fn test_a_riposte_is_readied_only_by_a_fast_flawless_word() {
+        let (mut combat, _) = fight();
+        let word = combat.current_word.clone();
+        type_word(&mut combat, &word, 0.1);
+        assert!(combat.riposte_until.is_some());
+
+        // A slip anywhere, even one put right, or a slow hand readies nothing
+        let (mut combat, _) = fight();
+        let word = combat.current_word.clone();
+        combat.on_char_typed('#');
+        combat.on_backspace();
+        type_word(&mut combat, &word, 0.1);
+        assert_eq!(combat.riposte_until, None);
+
+        let (mut combat, _) = fight();
+        let word = combat.current_word.clone();
+        let secs = grapheme_len(&word) as f32 / 5.0 / RIPOSTE_MIN_WPM * 60.0 * 1.5;
+        type_word(&mut combat, &word, secs);
+        assert_eq!(combat.riposte_until, None);
+    }
+
+    #[test]
+    fn test_a_riposte_turns_the_blow_after_the_next_word_back_on_the_enemy() {
+        let (mut combat, mut player) = fight();
+        let word = combat.current_word.clone();
+        type_word(&mut combat, &word, 0.1);
+        // The blow for the word that readied it still lands
+        let hp = player.hp;
+        combat.execute_enemy_turn(&mut player);
+        assert!(player.hp < hp);
+        assert!(combat.riposte_ready());
+
+        let (hp, enemy_hp) = (player.hp, combat.enemy.current_hp);
+        let blow = combat.next_blow(&player);
+        let word = combat.current_word.clone();
+        // Slow enough not to ready another
+        type_word(&mut combat, &word, 30.0);
+        let dealt = enemy_hp - combat.enemy.current_hp;
+        combat.execute_enemy_turn(&mut player);
+        assert_eq!(player.hp, hp);
+        assert_eq!(enemy_hp - combat.enemy.current_hp, dealt + blow);
+        assert_eq!(combat.riposte_until, None);
+    }
+
+    #[test]
+    fn test_a_riposte_window_that_has_closed_turns_nothing() {
+        let (mut combat, mut player) = fight();
+        let word = combat.current_word.clone();
+        type_word(&mut combat, &word, 0.1);
+        combat.execute_enemy_turn(&mut player);
+        combat.riposte_until = Some(Instant::now() - Duration::from_millis(1));
+        assert!(!combat.riposte_ready());
+
+        let hp = player.hp;
+        let word = combat.current_word.clone();
+        type_word(&mut combat, &word, 30.0);
+        combat.execute_enemy_turn(&mut player);
+        assert!(player.hp < hp);
+    }
+
+    #[test]
+    fn test_a_turn_based_fight_does_not_enrage_on_the_clock() {
+        let (mut combat, mut player) = fight();
+        combat.set_mode(CombatMode::TurnBased);
+        // A minute spent thinking before the word, then the enemy's turn
+        combat.last_tick = Instant::now() - Duration::from_secs(61);
+        combat.tick();
+        let word = combat.current_word.clone();
+        type_word(&mut combat, &word, 1.0);
+        combat.execute_enemy_turn(&mut player);
+        assert!(!combat.enrage.enraged);
+
+        let (mut combat, mut player) = fight();
+        combat.last_tick = Instant::now() - Duration::from_secs(61);
+        combat.tick();
+        let word = combat.current_word.clone();
+        type_word(&mut combat, &word, 1.0);
+        combat.execute_enemy_turn(&mut player);
+        assert!(combat.enrage.enraged);
+    }
+
+    #[test]
+    fn test_a_perfect_word_bonus_needs_a_perfect_word() {
+        let (mut combat, _) = fight();
+        let mut skills = SkillTree::new();
+        skills.unlocked_skills.push("precision_3".to_string());
+        combat.skills = Some(skills);
+        assert!((combat.skill_damage_mult(60.0, 1.0) - 1.1).abs() < 1e-6);
+        assert_eq!(combat.skill_damage_mult(60.0, 0.8), 1.0);
+
+        let clean = combat.calculate_damage(60.0, 1.0).0;
+        combat.word_errors = 1;
+        assert!(combat.calculate_damage(60.0, 1.0).0 < clean);
+    }
+
+    #[test]
+    fn test_a_chain_with_no_word_to_follow_snaps() {
+        let (mut combat, _) = fight();
+        combat.enable_word_chain();
+        combat.word_chain = Some(WordChain { links: 3 });
+        combat.word_pack = vec!["lo".to_string(), "lantern".to_string(), "night".to_string()];
+        combat.word_length_range = Some((5, 9));
+        combat.current_word = "quill".to_string();
+        assert_eq!(combat.chained_word().as_deref(), Some("lantern"));
+        assert_eq!(combat.word_chain.map(|c| c.links), Some(3));
+
+        combat.current_word = "ember".to_string();
+        assert_eq!(combat.chained_word(), None);
+        assert_eq!(combat.word_chain.map(|c| c.links), Some(0));
+    }
+
+    #[test]
+    fn test_an_aimed_capital_stays_out_of_the_word() {
+        let (mut combat, _) = fight();
+        let word = combat.current_word.clone();
+        let mut aimed: Vec<String> = graphemes(&word).iter().map(|g| g.to_string()).collect();
+        aimed[1] = aimed[1].to_uppercase();
+        combat.aim = Some(Aim {
+            word: word.clone(),
+            prompt: aimed.concat(),
+            index: 1,
+            location: HitLocation::Legs,
+            slipped: false,
+            struck: false,
+        });
+        assert_eq!(combat.current_word, word);
+        assert_ne!(combat.target(), word);
+
+        let target = combat.target().to_string();
+        type_word(&mut combat, &target, 0.5);
+        assert_eq!(combat.words_correct, 1);
+        assert!(combat.active_aim().is_some_and(|a| a.struck));
+        assert!(combat.battle_log.iter().any(|line| line.starts_with(&format!("✓ {} (", word))));
+    }
+
+    #[test]
+    fn test_a_turn_based_word_is_timed_by_its_keys() {
+        let (mut combat, _) = fight();
+        combat.set_mode(CombatMode::TurnBased);
+        // The word timer never moves; only the keys say how long it took
+        let word = combat.current_word.clone();
+        let start = Instant::now() - Duration::from_secs(2);
+        let keys = word.chars().count() as u32;
+        for (i, c) in word.chars().enumerate() {
+            combat.key_read_at = Some(start + Duration::from_secs(2) * i as u32 / keys);
+            combat.on_char_typed(c);
+        }
+        assert_eq!(combat.time_remaining, combat.time_limit);
+        assert!(combat.word_samples.last().is_some_and(|s| s.wpm > 0.0));
+    }
+
+    #[test]
+    fn test_a_heavy_strike_trades_a_longer_sentence_for_bonus_damage() {
+        let (mut combat, _) = fight();
+        let word_time = combat.time_limit;
+        assert!(combat.charge_heavy_strike());
+        assert!(combat.heavy_strike);
+        // A whole zone sentence, with the time to match: longer exposed
+        assert!(combat.current_word.contains(' '));
+        assert!(combat.time_limit > word_time);
+        // Only once, and only before typing
+        assert!(!combat.charge_heavy_strike());
+
+        let heavy = combat.calculate_damage(60.0, 1.0).0;
+        combat.heavy_strike = false;
+        let plain = combat.calculate_damage(60.0, 1.0).0;
+        assert_eq!(heavy, (plain as f32 * HEAVY_STRIKE_MULT).round() as i32);
+
+        let (mut combat, _) = fight();
+        combat.on_char_typed(combat.current_word.chars().next().unwrap());
+        assert!(!combat.charge_heavy_strike());
+    }
+
+    #[test]
+    fn test_a_heavy_strike_typed_slow_and_sure_escalates_to_deliberate() {
+        let strike = |secs: f32| {
+            let (mut combat, _) = fight();
+            combat.charge_heavy_strike();
+            let sentence = combat.current_word.clone();
+            type_word(&mut combat, &sentence, secs);
+            combat
+        };
+        let slow = strike(60.0);
+        assert_eq!(slow.word_samples[0].attack, AttackType::Deliberate);
+        assert!(slow.battle_log.iter().any(|l| l.contains("DELIBERATE HEAVY STRIKE")));
+
+        let fast = strike(1.0);
+        assert_ne!(fast.word_samples[0].attack, AttackType::Deliberate);
+        assert!(fast.battle_log.iter().any(|l| l.contains("HEAVY STRIKE") && !l.contains("DELIBERATE")));
+    }
+
+    #[test]
+    fn test_a_missed_or_slow_heavy_strike_leaves_the_player_open() {
+        let (mut combat, mut player) = fight();
+        combat.charge_heavy_strike();
+        let missed: String = combat.current_word.chars().map(|_| '#').collect();
+        type_word(&mut combat, &missed, 30.0);
+        assert!(!combat.heavy_strike);
+        assert!(combat.exposed);
+        let hp = player.hp;
+        let blow = combat.next_blow(&player);
+        combat.execute_enemy_turn(&mut player);
+        assert_eq!(hp - player.hp, (blow as f32 * HEAVY_EXPOSURE_MULT).round() as i32);
+        assert!(!combat.exposed);
+
+        // Running out of time on the sentence is a miss too
+        let (mut combat, _) = fight();
+        combat.charge_heavy_strike();
+        combat.on_word_timeout();
+        assert!(combat.exposed);
+    }
+
+    #[test]
+    fn test_a_word_ending_in_an_accent_waits_for_its_combining_mark() {
+        let (mut combat, _) = fight();
+        combat.current_word = "café".to_string();
+        type_word(&mut combat, "cafe", 1.0);
+        // The bare e is not judged before the accent can join it
+        assert_eq!(combat.phase, CombatPhase::PlayerTurn);
+        assert_eq!(combat.word_errors, 0);
+        combat.on_char_typed('\u{0301}');
+        assert_eq!(combat.phase, CombatPhase::EnemyTurn);
+        assert_eq!(combat.words_correct, 1);
+
+        // Something other than the mark settles it as a miss
+        let (mut combat, _) = fight();
+        combat.current_word = "café".to_string();
+        type_word(&mut combat, "cafe ", 1.0);
+        assert_eq!(combat.phase, CombatPhase::EnemyTurn);
+        assert_eq!(combat.words_correct, 0);
+    }
+}
@@ -10,6 +10,7 @@ use rand::Rng;
 use super::combat_events::*;
 use super::player::Player;
 use super::enemy::Enemy;
+use super::run_rng::{self, Stream};
 use crate::data::GameData;
 
 /// Pure combat state - no side effects, just data
@@ -63,9 +64,9 @@ impl CombatEngine {
         
         let use_sentences = enemy.is_boss || floor >= 5;
         let starting_word = if use_sentences {
-            game_data.get_sentence(floor.min(10))
+            game_data.get_sentence(&mut run_rng::stream(Stream::Words), floor.min(10))
         } else {
-            game_data.get_word(floor.min(10))
+            game_data.get_word(&mut run_rng::stream(Stream::Words), floor.min(10))
         };
         
         let time_limit = Self::calculate_time_limit(&starting_word, &difficulty);
@@ -393,9 +394,9 @@ impl CombatEngine {
         };
         
         self.current_word = if self.use_sentences {
-            self.game_data.get_sentence(effective_difficulty)
+            self.game_data.get_sentence(&mut run_rng::stream(Stream::Words), effective_difficulty)
         } else {
-            self.game_data.get_word(effective_difficulty)
+            self.game_data.get_word(&mut run_rng::stream(Stream::Words), effective_difficulty)
         };
         
         self.typed_input.clear();
@@ -14,7 +14,9 @@ use super::dialogue_engine::{DialogueEngine, DialogueContext, CombatMomentum, Pl
 use super::enemy_visuals::{EnemyVisualState, EnemyPosture, HitLocation};
 use super::pacing::{PacingController, PacingPhase, PacingBeat};
 use super::player_avatar::{PlayerAvatar, PlayerClass, AvatarState};
+use super::world_integration::FloorZone;
 use rand::prelude::*;
+use std::time::Instant;
 
 /// Immersive combat wrapper - enhances standard CombatState with rich feedback
 #[derive(Debug, Clone)]
@@ -49,6 +51,10 @@ pub struct ImmersiveCombat {
     pub is_boss: bool,
     /// Current typing WPM
     pub current_wpm: f32,
+    /// Stamina is critical
+    pub winded: bool,
+    /// The run's recurring motifs, woven into dialogue
+    pub motifs: Vec<String>,
 }
 
 /// Feedback for a single keystroke
@@ -59,6 +65,10 @@ pub struct KeystrokeFeedback {
     pub damage_dealt: i32,
     pub rhythm_bonus: bool,
     pub speed_rating: SpeedRating,
+    /// How hard the key landed (0.0 - 1.0), for its flash
+    pub visual_intensity: f32,
+    /// How much the key shakes the screen
+    pub screen_shake: f32,
 }
 
 /// How fast was that keystroke?
@@ -161,6 +171,8 @@ impl ImmersiveCombat {
             pending_messages: Vec::new(),
             is_boss,
             current_wpm: 0.0,
+            winded: false,
+            motifs: Vec::new(),
         }
     }
     
@@ -178,7 +190,12 @@ impl ImmersiveCombat {
     
     /// Called on each keystroke - returns feedback
     pub fn on_keystroke(&mut self, c: char, correct: bool) -> KeystrokeFeedback {
-        let result = self.typing.on_keystroke(c, correct);
+        self.on_keystroke_at(c, correct, Instant::now())
+    }
+
+    /// Called on each keystroke, stamped when it was read from the terminal
+    pub fn on_keystroke_at(&mut self, c: char, correct: bool, now: Instant) -> KeystrokeFeedback {
+        let result = self.typing.on_keystroke_at(c, correct, now);
         
         // Trigger player typing animation
         self.player.on_keystroke();
@@ -193,6 +210,8 @@ impl ImmersiveCombat {
             damage_dealt: result.damage_this_stroke as i32,
             rhythm_bonus: result.rhythm_bonus > 0.0,
             speed_rating,
+            visual_intensity: result.visual_intensity,
+            screen_shake: result.screen_shake,
         };
         
         self.last_keystroke_feedback = Some(feedback.clone());
@@ -284,6 +303,60 @@ impl ImmersiveCombat {
         message
     }
     
+    /// Called when the player reflects an attack with a riposte
+    pub fn on_riposte(&mut self, damage: i32, enemy_health_percent: i32) -> String {
+        let ctx = self.build_dialogue_context(enemy_health_percent);
+        let message = self.dialogue.generate_riposte_message(&ctx, damage);
+        
+        self.player.on_riposte();
+        self.enemy_visuals.update_from_health(enemy_health_percent as f32 / 100.0);
+        
+        self.pending_messages.push(CombatMessage {
+            text: message.clone(),
+            style: MessageStyle::Critical,
+            duration_ms: 2000,
+        });
+        
+        message
+    }
+    
+    /// The fight has dragged on: the warning before the enemy enrages
+    pub fn on_enrage_warning(&mut self) -> String {
+        self.pacing.enrage_warning(&self.enemy_name)
+    }
+    
+    /// Called when the enemy enrages
+    pub fn on_enrage(&mut self, enemy_health_percent: i32) -> String {
+        let ctx = self.build_dialogue_context(enemy_health_percent);
+        let message = self.dialogue.generate_enrage_line(&ctx);
+        
+        self.pending_messages.push(CombatMessage {
+            text: message.clone(),
+            style: MessageStyle::EnemyDialogue,
+            duration_ms: 2500,
+        });
+        
+        message
+    }
+    
+    /// Stamina went critical (or recovered). Going critical draws a gloat
+    /// from an enemy with the upper hand.
+    pub fn set_winded(&mut self, winded: bool, enemy_health_percent: i32) -> Option<String> {
+        let was_winded = std::mem::replace(&mut self.winded, winded);
+        self.player.set_winded(winded);
+        if !winded || was_winded {
+            return None;
+        }
+        let ctx = self.build_dialogue_context(enemy_health_percent);
+        let message = self.dialogue.generate_gloat(&ctx)?;
+        self.pending_messages.push(CombatMessage {
+            text: message.clone(),
+            style: MessageStyle::EnemyDialogue,
+            duration_ms: 2000,
+        });
+        Some(message)
+    }
+    
     /// Called when player takes damage (update health for visuals)
     pub fn on_player_damaged(&mut self, health_percent: i32) {
         self.player_health_percent = health_percent;
@@ -382,25 +455,21 @@ impl ImmersiveCombat {
             enemy_name: self.enemy_name.clone(),
             enemy_theme: self.enemy_theme.clone(),
             enemy_momentum: CombatMomentum::from_health_percent(enemy_health_percent),
-            player_momentum: PlayerMomentum::from_health_and_accuracy(
+            player_momentum: PlayerMomentum::from_state(
                 self.player_health_percent,
                 self.accuracy,
+                self.winded,
             ),
             zone: ZoneContext::from_floor(self.floor),
+            zone_name: FloorZone::from_floor(self.floor).name().to_string(),
+            motifs: self.motifs.clone(),
             typing_speed: self.current_wpm,
             accuracy: self.accuracy,
         }
     }
     
     fn random_hit_location(&self) -> HitLocation {
-        let mut rng = rand::thread_rng();
-        match rng.gen_range(0..5) {
-            0 => HitLocation::Head,
-            1 => HitLocation::Torso,
-            2 => HitLocation::LeftArm,
-            3 => HitLocation::RightArm,
-            _ => HitLocation::Legs,
-        }
+        HitLocation::random(&mut rand::thread_rng())
     }
 }
 
@@ -0,0 +1,279 @@
+//! Combat Replay - Every keystroke of a fight, played back afterwards
+//!
+//! While a fight runs, CombatState records each key that reached the prompt
+//! (and each backspace) with its timing, grouped by word, along with the
+//! damage each word dealt. After the fight the replay viewer plays the
+//! typing back at its original pace, so you can see where the big hits
+//! came from and where the fingers slipped.
+//!
+//! Design: timings are kept per word, relative to that word's first key.
+//! Playback strings the words together with a short pause between them
+//! rather than the real gaps (enemy turns, hesitation before a word).
+
+use std::time::Instant;
+
+/// Pause between words during playback (ms)
+pub const WORD_GAP_MS: u64 = 600;
+/// A word dealing this much more than the fight's average counts as a spike
+const SPIKE_RATIO: f32 = 1.5;
+/// Playback speeds the viewer cycles through
+const SPEEDS: [f32; 4] = [1.0, 2.0, 4.0, 0.5];
+
+/// One recorded key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayInput {
+    Char { ch: char, correct: bool },
+    Backspace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayKey {
+    pub input: ReplayInput,
+    /// Milliseconds since the word's first key
+    pub at_ms: u64,
+}
+
+/// A prompt and everything typed at it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayWord {
+    pub prompt: String,
+    pub keys: Vec<ReplayKey>,
+    /// Damage the word dealt when it completed
+    pub damage: i32,
+    /// Completed (rather than timed out or abandoned)
+    pub finished: bool,
+}
+
+impl ReplayWord {
+    pub fn errors(&self) -> usize {
+        self.keys.iter().filter(|k| matches!(k.input, ReplayInput::Char { correct: false, .. })).count()
+    }
+
+    /// Playback length of the word, pause included
+    pub fn duration_ms(&self) -> u64 {
+        self.keys.last().map(|k| k.at_ms).unwrap_or(0) + WORD_GAP_MS
+    }
+
+    /// The text on screen after the first `shown` keys, with correctness
+    pub fn typed(&self, shown: usize) -> Vec<(char, bool)> {
+        let mut typed = Vec::new();
+        for key in self.keys.iter().take(shown) {
+            match key.input {
+                ReplayInput::Char { ch, correct } => typed.push((ch, correct)),
+                ReplayInput::Backspace => {
+                    typed.pop();
+                }
+            }
+        }
+        typed
+    }
+}
+
+/// A whole fight's typing
+#[derive(Debug, Clone, Default)]
+pub struct CombatReplay {
+    pub words: Vec<ReplayWord>,
+    /// When the open word's first key landed
+    word_started: Option<Instant>,
+}
+
+impl CombatReplay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Record a key typed at `prompt`
+    pub fn record(&mut self, prompt: &str, input: ReplayInput, now: Instant) {
+        let open = self.word_started.is_some() && self.words.last().is_some_and(|w| w.prompt == prompt);
+        if !open {
+            // Backspacing into nothing isn't worth a word of its own
+            if input == ReplayInput::Backspace {
+                return;
+            }
+            self.words.push(ReplayWord { prompt: prompt.to_string(), keys: Vec::new(), damage: 0, finished: false });
+            self.word_started = Some(now);
+        }
+        let at_ms = self.word_started.map(|s| now.saturating_duration_since(s).as_millis() as u64).unwrap_or(0);
+        if let Some(word) = self.words.last_mut() {
+            word.keys.push(ReplayKey { input, at_ms });
+        }
+    }
+
+    /// Close the open word; `damage` is what it dealt (0 for a miss)
+    pub fn finish_word(&mut self, damage: i32, finished: bool) {
+        if self.word_started.take().is_none() {
+            return;
+        }
+        if let Some(word) = self.words.last_mut() {
+            word.damage = damage;
+            word.finished = finished;
+        }
+    }
+
+    /// When the open word's first key landed, if a word is open
+    pub fn word_started(&self) -> Option<Instant> {
+        self.word_started
+    }
+
+    /// Move the open word's start, as a pause lays it back out
+    pub fn restart_word(&mut self, started: Instant) {
+        if self.word_started.is_some() {
+            self.word_started = Some(started);
+        }
+    }
+
+    pub fn total_ms(&self) -> u64 {
+        self.words.iter().map(|w| w.duration_ms()).sum()
+    }
+
+    /// Where playback is at `ms`: (word index, keys shown)
+    pub fn frame_at(&self, ms: u64) -> (usize, usize) {
+        let mut start = 0;
+        for (i, word) in self.words.iter().enumerate() {
+            let end = start + word.duration_ms();
+            if ms < end {
+                let shown = word.keys.iter().take_while(|k| start + k.at_ms <= ms).count();
+                return (i, shown);
+            }
+            start = end;
+        }
+        let last = self.words.len().saturating_sub(1);
+        (last, self.words.last().map(|w| w.keys.len()).unwrap_or(0))
+    }
+
+    /// Playback time at which a word starts
+    pub fn word_start_ms(&self, index: usize) -> u64 {
+        self.words.iter().take(index).map(|w| w.duration_ms()).sum()
+    }
+
+    /// Whether a word's damage spiked well above the fight's average
+    pub fn is_spike(&self, index: usize) -> bool {
+        let hits: Vec<i32> = self.words.iter().map(|w| w.damage).filter(|d| *d > 0).collect();
+        if hits.len() < 2 {
+            return false;
+        }
+        let average = hits.iter().sum::<i32>() as f32 / hits.len() as f32;
+        self.words.get(index).is_some_and(|w| w.damage as f32 >= average * SPIKE_RATIO)
+    }
+}
+
+/// Playback state for the replay screen
+#[derive(Debug, Clone)]
+pub struct ReplayViewer {
+    pub replay: CombatReplay,
+    /// Playback position when the clock last started or stopped
+    base_ms: u64,
+    /// Set while playing
+    playing_since: Option<Instant>,
+    speed_index: usize,
+}
+
+impl ReplayViewer {
+    pub fn new(replay: CombatReplay) -> Self {
+        Self { replay, base_ms: 0, playing_since: Some(Instant::now()), speed_index: 0 }
+    }
+
+    pub fn speed(&self) -> f32 {
+        SPEEDS[self.speed_index]
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing_since.is_some()
+    }
+
+    /// Current playback position (ms)
+    pub fn position_ms(&self) -> u64 {
+        let running = self.playing_since
+            .map(|since| (since.elapsed().as_millis() as f32 * self.speed()) as u64)
+            .unwrap_or(0);
+        (self.base_ms + running).min(self.replay.total_ms())
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.is_playing() {
+            self.base_ms = self.position_ms();
+            self.playing_since = None;
+        } else {
+            if self.position_ms() >= self.replay.total_ms() {
+                self.base_ms = 0;
+            }
+            self.playing_since = Some(Instant::now());
+        }
+    }
+
+    pub fn cycle_speed(&mut self) {
+        self.rebase();
+        self.speed_index = (self.speed_index + 1) % SPEEDS.len();
+    }
+
+    /// Jump to the start of the next (or previous) word
+    pub fn skip_word(&mut self, forward: bool) {
+        let (word, _) = self.replay.frame_at(self.position_ms());
+        let target = if forward { word + 1 } else { word.saturating_sub(1) };
+        self.base_ms = self.replay.word_start_ms(target.min(self.replay.words.len()));
+        if self.is_playing() {
+            self.playing_since = Some(Instant::now());
+        }
+    }
+
+    fn rebase(&mut self) {
+        if self.is_playing() {
+            self.base_ms = self.position_ms();
+            self.playing_since = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn key(ch: char, correct: bool) -> ReplayInput {
+        ReplayInput::Char { ch, correct }
+    }
+
+    #[test]
+    fn test_records_words_and_timing() {
+        let start = Instant::now();
+        let mut replay = CombatReplay::new();
+        replay.record("ink", key('i', true), start);
+        replay.record("ink", key('m', false), start + Duration::from_millis(100));
+        replay.record("ink", ReplayInput::Backspace, start + Duration::from_millis(250));
+        replay.record("ink", key('n', true), start + Duration::from_millis(300));
+        replay.record("ink", key('k', true), start + Duration::from_millis(400));
+        replay.finish_word(12, true);
+        replay.record("quill", key('q', true), start + Duration::from_secs(3));
+
+        assert_eq!(replay.words.len(), 2);
+        let ink = &replay.words[0];
+        assert_eq!(ink.errors(), 1);
+        assert_eq!(ink.damage, 12);
+        assert_eq!(ink.typed(2), vec![('i', true), ('m', false)]);
+        assert_eq!(ink.typed(5).iter().map(|(c, _)| c).collect::<String>(), "ink");
+        // The second word's clock starts at its own first key
+        assert_eq!(replay.words[1].keys[0].at_ms, 0);
+    }
+
+    #[test]
+    fn test_playback_frames_and_spikes() {
+        let start = Instant::now();
+        let mut replay = CombatReplay::new();
+        for (i, damage) in [10, 10, 30].into_iter().enumerate() {
+            let prompt = format!("word{}", i);
+            replay.record(&prompt, key('w', true), start);
+            replay.record(&prompt, key('o', true), start + Duration::from_millis(200));
+            replay.finish_word(damage, true);
+        }
+        assert_eq!(replay.frame_at(0), (0, 1));
+        assert_eq!(replay.frame_at(200), (0, 2));
+        assert_eq!(replay.frame_at(200 + WORD_GAP_MS), (1, 1));
+        assert_eq!(replay.frame_at(u64::MAX), (2, 2));
+        assert!(replay.is_spike(2));
+        assert!(!replay.is_spike(0));
+    }
+}
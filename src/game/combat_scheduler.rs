@@ -0,0 +1,116 @@
+//! Combat Scheduler - When the enemy gets to act
+//!
+//! Two ways to run a fight:
+//! - Real-time: every word races a timer, and the enemy strikes as soon
+//!   as a word lands or the timer runs out
+//! - Turn-based: there is no timer; the enemy only acts after each
+//!   finished word, so every word can be taken at the player's own pace
+//!
+//! Design: the combat loop asks the scheduler whether the clock runs and
+//! whether the enemy acts now, and never checks the mode itself.
+
+use serde::{Deserialize, Serialize};
+
+use super::combat::CombatPhase;
+
+/// Decides when the enemy takes its turn
+pub trait EnemyScheduler: std::fmt::Debug + Sync {
+    fn mode(&self) -> CombatMode;
+
+    /// Whether timers count down against the player
+    fn clock_runs(&self) -> bool;
+
+    /// Whether the enemy takes its turn now
+    fn enemy_acts(&self, phase: CombatPhase, time_remaining: f32) -> bool;
+}
+
+/// The enemy strikes after each word or when the word timer runs out
+#[derive(Debug)]
+pub struct RealTime;
+
+impl EnemyScheduler for RealTime {
+    fn mode(&self) -> CombatMode {
+        CombatMode::RealTime
+    }
+
+    fn clock_runs(&self) -> bool {
+        true
+    }
+
+    fn enemy_acts(&self, phase: CombatPhase, time_remaining: f32) -> bool {
+        phase == CombatPhase::EnemyTurn || time_remaining <= 0.0
+    }
+}
+
+/// The enemy strikes only after each finished word
+#[derive(Debug)]
+pub struct TurnBased;
+
+impl EnemyScheduler for TurnBased {
+    fn mode(&self) -> CombatMode {
+        CombatMode::TurnBased
+    }
+
+    fn clock_runs(&self) -> bool {
+        false
+    }
+
+    fn enemy_acts(&self, phase: CombatPhase, _time_remaining: f32) -> bool {
+        phase == CombatPhase::EnemyTurn
+    }
+}
+
+/// Which scheduler fights use (saved in settings)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CombatMode {
+    #[default]
+    RealTime,
+    TurnBased,
+}
+
+impl CombatMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RealTime => "Real-time",
+            Self::TurnBased => "Turn-based",
+        }
+    }
+
+    /// The other mode (for toggling in settings)
+    pub fn next(&self) -> Self {
+        match self {
+            Self::RealTime => Self::TurnBased,
+            Self::TurnBased => Self::RealTime,
+        }
+    }
+
+    pub fn scheduler(&self) -> &'static dyn EnemyScheduler {
+        match self {
+            Self::RealTime => &RealTime,
+            Self::TurnBased => &TurnBased,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_real_time_acts_on_timeout() {
+        let scheduler = CombatMode::RealTime.scheduler();
+        assert!(scheduler.clock_runs());
+        assert!(scheduler.enemy_acts(CombatPhase::PlayerTurn, 0.0));
+        assert!(scheduler.enemy_acts(CombatPhase::EnemyTurn, 3.0));
+        assert!(!scheduler.enemy_acts(CombatPhase::PlayerTurn, 3.0));
+    }
+
+    #[test]
+    fn test_turn_based_waits_for_the_word() {
+        let scheduler = CombatMode::TurnBased.scheduler();
+        assert!(!scheduler.clock_runs());
+        assert!(!scheduler.enemy_acts(CombatPhase::PlayerTurn, 0.0));
+        assert!(scheduler.enemy_acts(CombatPhase::EnemyTurn, 0.0));
+        assert_eq!(scheduler.mode().next(), CombatMode::RealTime);
+    }
+}
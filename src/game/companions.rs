@@ -0,0 +1,206 @@
+//! Companions - NPCs who join you at the campfire
+//!
+//! Some encounter choices win an NPC over for good, and they join you as a
+//! camp companion, run after run, until a fresh timeline forgets them. Companions wait at rest sites, where each offers one
+//! service on top of the usual rest actions:
+//! - Technician Kaya: field repairs, patching up HP and MP together
+//! - Vera: drills, a lesson worth some XP
+//! - Cipher: secrets, sold on for gold
+//!
+//! Later choices can cost you a companion. Some make them leave; others get
+//! them killed. Either way they are gone for good.
+//!
+//! Design: who joins, leaves and dies on which choice is data in
+//! `COMPANIONS`; the roster only records what has happened. GameState feeds
+//! it every resolved encounter choice (the same choices the
+//! EncounterTracker records) and offers the services at rest sites.
+
+/// What a companion does for you at a rest site
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompanionService {
+    /// Restore a share of both HP and MP
+    Repairs,
+    /// Gain XP scaled by the floor
+    Drill,
+    /// Gain gold scaled by the floor
+    Secrets,
+}
+
+impl CompanionService {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Repairs => "Field Repairs (Restore 20% HP and MP)",
+            Self::Drill => "Drill (Gain XP)",
+            Self::Secrets => "Sell Secrets (Gain gold)",
+        }
+    }
+}
+
+/// An encounter choice: (encounter id, choice id)
+pub type ChoiceRef = (&'static str, &'static str);
+
+/// Who a companion is and which choices decide their fate
+#[derive(Debug, Clone, Copy)]
+pub struct CompanionDef {
+    /// Id of their NpcVoice
+    pub id: &'static str,
+    pub name: &'static str,
+    pub joins_on: ChoiceRef,
+    pub leaves_on: &'static [ChoiceRef],
+    pub dies_on: &'static [ChoiceRef],
+    pub service: CompanionService,
+    /// Said when they leave
+    pub farewell: &'static str,
+    /// Said when they die
+    pub epitaph: &'static str,
+}
+
+pub static COMPANIONS: [CompanionDef; 3] = [
+    CompanionDef {
+        id: "kaya",
+        name: "Technician Kaya",
+        joins_on: ("mechanist_breakdown", "comfort_mechanist"),
+        leaves_on: &[("shadowwriter_offer", "accept_shadow")],
+        dies_on: &[("corruption_memory_echo", "embrace_memory")],
+        service: CompanionService::Repairs,
+        farewell: "Kaya packs her tools. She won't work beside the Shadow Writers.",
+        epitaph: "The memory's corruption reaches Kaya's gears first, then Kaya. She is gone.",
+    },
+    CompanionDef {
+        id: "vera",
+        name: "Vera",
+        joins_on: ("haven_old_scribe", "offer_help"),
+        leaves_on: &[("first_archivist_meeting", "ask_third_grammar")],
+        dies_on: &[],
+        service: CompanionService::Drill,
+        farewell: "Vera warned you about the Archivists. She walks back to Haven alone.",
+        epitaph: "",
+    },
+    CompanionDef {
+        id: "cipher",
+        name: "Cipher",
+        joins_on: ("shadowwriter_offer", "accept_shadow"),
+        leaves_on: &[],
+        dies_on: &[("first_archivist_meeting", "reject_past")],
+        service: CompanionService::Secrets,
+        farewell: "",
+        epitaph: "The Archivists do not forgive a spy. Cipher's card goes blank.",
+    },
+];
+
+/// What became of a companion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompanionFate {
+    WithYou,
+    Left,
+    Dead,
+}
+
+/// A change to the roster, for the message log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RosterChange {
+    Joined(&'static CompanionDef),
+    Left(&'static CompanionDef),
+    Died(&'static CompanionDef),
+}
+
+impl RosterChange {
+    pub fn message(&self) -> String {
+        match self {
+            Self::Joined(def) => format!("{} joins you. Look for them at the campfire.", def.name),
+            Self::Left(def) => def.farewell.to_string(),
+            Self::Died(def) => def.epitaph.to_string(),
+        }
+    }
+}
+
+impl PartialEq for CompanionDef {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CompanionDef {}
+
+/// Everyone recruited this timeline, and what became of them. Like the
+/// EncounterTracker it outlasts the run; only a fresh timeline clears it.
+#[derive(Debug, Clone, Default)]
+pub struct CompanionRoster {
+    recruited: Vec<(&'static CompanionDef, CompanionFate)>,
+}
+
+impl CompanionRoster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the roster after `choice` was made in `encounter`
+    pub fn on_choice(&mut self, encounter: &str, choice: &str) -> Vec<RosterChange> {
+        let made = |r: &ChoiceRef| r.0 == encounter && r.1 == choice;
+        let mut changes = Vec::new();
+        for (def, fate) in self.recruited.iter_mut().filter(|(_, f)| *f == CompanionFate::WithYou) {
+            if def.dies_on.iter().any(made) {
+                *fate = CompanionFate::Dead;
+                changes.push(RosterChange::Died(def));
+            } else if def.leaves_on.iter().any(made) {
+                *fate = CompanionFate::Left;
+                changes.push(RosterChange::Left(def));
+            }
+        }
+        for def in COMPANIONS.iter().filter(|d| made(&d.joins_on)) {
+            if self.fate(def.id).is_none() {
+                self.recruited.push((def, CompanionFate::WithYou));
+                changes.push(RosterChange::Joined(def));
+            }
+        }
+        changes
+    }
+
+    /// What became of companion `id`, if they were ever recruited
+    pub fn fate(&self, id: &str) -> Option<CompanionFate> {
+        self.recruited.iter().find(|(d, _)| d.id == id).map(|(_, f)| *f)
+    }
+
+    /// Companions still travelling with you, in the order they joined
+    pub fn present(&self) -> Vec<&'static CompanionDef> {
+        self.recruited.iter().filter(|(_, f)| *f == CompanionFate::WithYou).map(|(d, _)| *d).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    #[test]
+    fn test_companion_choices_exist() {
+        let encounters = build_encounters();
+        let exists = |(enc, choice): &ChoiceRef| {
+            encounters.get(*enc).is_some_and(|e| e.choices.iter().any(|c| c.id == *choice))
+        };
+        for def in &COMPANIONS {
+            assert!(exists(&def.joins_on), "{} joins on a missing choice", def.id);
+            assert!(def.leaves_on.iter().chain(def.dies_on).all(exists), "{} departs on a missing choice", def.id);
+        }
+    }
+
+    #[test]
+    fn test_join_leave_and_die() {
+        let mut roster = CompanionRoster::new();
+        assert!(roster.on_choice("mechanist_breakdown", "challenge_mechanist").is_empty());
+        assert_eq!(roster.on_choice("mechanist_breakdown", "comfort_mechanist"), vec![RosterChange::Joined(&COMPANIONS[0])]);
+        assert_eq!(roster.present().len(), 1);
+
+        // Taking the Shadow Writers' card costs Kaya but brings Cipher
+        let changes = roster.on_choice("shadowwriter_offer", "accept_shadow");
+        assert_eq!(changes, vec![RosterChange::Left(&COMPANIONS[0]), RosterChange::Joined(&COMPANIONS[2])]);
+        assert_eq!(roster.fate("kaya"), Some(CompanionFate::Left));
+
+        roster.on_choice("first_archivist_meeting", "reject_past");
+        assert_eq!(roster.fate("cipher"), Some(CompanionFate::Dead));
+        assert!(roster.present().is_empty());
+
+        // The departed don't come back
+        assert!(roster.on_choice("mechanist_breakdown", "comfort_mechanist").is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+//! Reading Comprehension - Lore rewards for those who actually read
+//!
+//! After a lore passage is put away, the player may be asked a question
+//! about it and must type the answer from memory - not transcribe it.
+//! Answers are matched loosely (case, punctuation, articles, small typos)
+//! because we are testing attention, not spelling.
+//!
+//! Design: a correct answer reveals an insight - a second, deeper layer of
+//! the same fragment - per the layered-meaning philosophy in
+//! writing_guidelines.
+
+/// A question about one lore fragment, keyed by the fragment's title
+#[derive(Debug, Clone, Copy)]
+pub struct ComprehensionQuestion {
+    pub lore_title: &'static str,
+    pub question: &'static str,
+    /// Accepted answers (first is shown if the player misses)
+    pub answers: &'static [&'static str],
+    /// The deeper reading unlocked by answering correctly
+    pub insight: &'static str,
+}
+
+/// XP granted per floor for a correct answer
+pub const XP_PER_FLOOR: u64 = 10;
+
+const QUESTIONS: &[ComprehensionQuestion] = &[
+    ComprehensionQuestion {
+        lore_title: "Royal Chronicle Fragment",
+        question: "When the sky tore open, which way did the king go?",
+        answers: &["toward it", "towards it", "toward the darkness", "into the darkness", "toward the sundering"],
+        insight: "The chronicler never asks why a king would walk into the dark. Perhaps he already knew who had opened it.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Knight's Final Letter",
+        question: "Who commanded the knights to hold the throne room?",
+        answers: &["sir aldric", "aldric"],
+        insight: "Sir Aldric's name appears again in the Hollow Knight's armor. Some oaths outlive the one who swore them.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Faded Tapestry",
+        question: "What were several sections of the floor plan marked with?",
+        answers: &["blood"],
+        insight: "The bloodied rooms trace a path - from the throne room to the royal study. Someone walked it often.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Waterlogged Journal",
+        question: "What was Malachar obsessed with?",
+        answers: &["the elder stones", "elder stones", "stones"],
+        insight: "'Decades.' Malachar was already studying the Stones long before anyone he loved was in danger.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Researcher's Final Entry",
+        question: "According to the researcher, what was Malachar really trying to do to the world?",
+        answers: &["save it", "save the world", "save"],
+        insight: "The entry breaks off at 'the price'. The missing page was torn out, not lost.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Preserved Scroll",
+        question: "How many Elder Stones are there?",
+        answers: &["five", "5"],
+        insight: "Five Stones, five factions. The scroll's margin pairs each Stone with a founding house.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Love Letter",
+        question: "Where did the writer ask Malachar to come back to?",
+        answers: &["our garden", "the garden", "garden"],
+        insight: "The letter is unsigned, yet the gardens were planted in her name. The Blight began here for a reason.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Gardener's Note",
+        question: "What did the rose bloom close around?",
+        answers: &["a bird", "bird"],
+        insight: "The roses do not hunt. They hold. Whatever the Blight is, it learned tenderness from someone.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Maintenance Log",
+        question: "Which Guardian Unit began asking questions?",
+        answers: &["7", "seven", "unit 7", "guardian unit 7"],
+        insight: "'Which version of him?' The guardians have met more than one Malachar.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Artificer's Blueprint",
+        question: "Which mark of the Ritual Apparatus is this?",
+        answers: &["vii", "mark vii", "7", "seven", "mark 7"],
+        insight: "Six failures, each 'dimensional instability'. The Clockwork Depths are built on the wreckage of all six.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Malachar's Journal",
+        question: "What does the voice beyond the Veil promise to give Malachar?",
+        answers: &["her", "her back", "give her back", "to give her back"],
+        insight: "It never says who 'her' is. Malachar never needed it to.",
+    },
+    ComprehensionQuestion {
+        lore_title: "Survivor's Account",
+        question: "What was on Malachar's face at the moment of Ascension?",
+        answers: &["horror"],
+        insight: "Horror, not pain. Whatever reached back, he recognized it.",
+    },
+    ComprehensionQuestion {
+        lore_title: "The Final Truth",
+        question: "Who does the Breach claim you are?",
+        answers: &["malachar", "me", "him"],
+        insight: "'Trying again.' The cycle is not a punishment. It is a second chance, over and over.",
+    },
+    ComprehensionQuestion {
+        lore_title: "The Dreamer Stirs",
+        question: "What does the passage call the Breach?",
+        answers: &["its opening eye", "an opening eye", "opening eye", "eye"],
+        insight: "If the Breach is an eye, every word typed here is something it reads.",
+    },
+];
+
+/// The question for a lore fragment, if it has one
+pub fn question_for(lore_title: &str) -> Option<&'static ComprehensionQuestion> {
+    QUESTIONS.iter().find(|q| q.lore_title == lore_title)
+}
+
+/// Lowercase, drop punctuation and leading articles, collapse whitespace
+pub fn normalize_answer(s: &str) -> String {
+    let cleaned: String = s
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c.is_whitespace() { c } else { ' ' })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    let start = match words.first() {
+        Some(&("the" | "a" | "an")) if words.len() > 1 => 1,
+        _ => 0,
+    };
+    words[start..].join(" ")
+}
+
+/// Levenshtein distance over chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+/// Whether a typed answer matches any accepted answer.
+/// Allows one typo per five characters of the accepted answer.
+pub fn answer_matches(typed: &str, accepted: &[&str]) -> bool {
+    let typed = normalize_answer(typed);
+    if typed.is_empty() {
+        return false;
+    }
+    accepted.iter().any(|answer| {
+        let answer = normalize_answer(answer);
+        let tolerance = answer.chars().count() / 5;
+        edit_distance(&typed, &answer) <= tolerance
+    })
+}
+
+/// An in-progress comprehension question
+#[derive(Debug, Clone)]
+pub struct ComprehensionCheck {
+    pub question: &'static ComprehensionQuestion,
+    /// What the player has typed so far
+    pub input: String,
+    /// Set once the answer is submitted
+    pub correct: Option<bool>,
+}
+
+impl ComprehensionCheck {
+    pub fn new(question: &'static ComprehensionQuestion) -> Self {
+        Self {
+            question,
+            input: String::new(),
+            correct: None,
+        }
+    }
+
+    /// Check the typed answer; returns whether it was right
+    pub fn submit(&mut self) -> bool {
+        let correct = answer_matches(&self.input, self.question.answers);
+        self.correct = Some(correct);
+        correct
+    }
+
+    pub fn is_answered(&self) -> bool {
+        self.correct.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_matching() {
+        let answers = &["sir aldric", "aldric"];
+        assert!(answer_matches("Sir Aldric", answers));
+        assert!(answer_matches("aldric.", answers));
+        assert!(answer_matches("sir aldrik", answers));
+        assert!(!answer_matches("malachar", answers));
+        assert!(!answer_matches("", answers));
+    }
+
+    #[test]
+    fn test_articles_ignored() {
+        assert!(answer_matches("the garden", &["garden"]));
+        assert!(answer_matches("a bird", &["bird"]));
+        // Short answers get no typo tolerance
+        assert!(!answer_matches("bord", &["bird"]));
+    }
+
+    #[test]
+    fn test_every_question_has_lore() {
+        for q in QUESTIONS {
+            assert!(!q.answers.is_empty(), "{} has no answers", q.lore_title);
+            assert!(question_for(q.lore_title).is_some());
+        }
+    }
+}
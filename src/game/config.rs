@@ -7,6 +7,13 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use super::keyboard_layout::KeyboardLayout;
+use super::combat_scheduler::CombatMode;
+use super::status_line::StatusLineConfig;
+use super::pronunciation::PronunciationConfig;
+use super::typing_impact::AttackThresholds;
+use crate::ui::theme::IconStyle;
+
 /// Master game configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameConfig {
@@ -70,6 +77,18 @@ pub struct TypingConfig {
     
     /// Penalty per backspace (damage reduction %)
     pub backspace_penalty: f32,
+    
+    /// Physical keyboard layout (adjacency, finger travel, error classification)
+    #[serde(default)]
+    pub layout: KeyboardLayout,
+    
+    /// Measured terminal input latency (ms), taken off keystroke intervals
+    #[serde(default)]
+    pub latency_ms: u32,
+    
+    /// Custom attack type cutoffs (None = the class preset)
+    #[serde(default)]
+    pub attack_thresholds: Option<AttackThresholds>,
 }
 
 impl Default for TypingConfig {
@@ -85,6 +104,9 @@ impl Default for TypingConfig {
             allow_backspace: true,
             max_backspaces_per_word: 0, // unlimited
             backspace_penalty: 0.05,
+            layout: KeyboardLayout::default(),
+            latency_ms: 0,
+            attack_thresholds: None,
         }
     }
 }
@@ -127,6 +149,10 @@ pub struct CombatConfig {
     
     /// MP regeneration per combat victory
     pub mp_regen_per_victory: f32,
+    
+    /// Real-time (timed words) or turn-based (enemy acts after each word)
+    #[serde(default)]
+    pub mode: CombatMode,
 }
 
 impl Default for CombatConfig {
@@ -144,6 +170,7 @@ impl Default for CombatConfig {
             flee_chance_base: 40.0,
             hp_regen_per_floor: 0.0,
             mp_regen_per_victory: 0.1,
+            mode: CombatMode::default(),
         }
     }
 }
@@ -158,6 +185,18 @@ pub enum DifficultyPreset {
     Custom,   // User-defined settings
 }
 
+impl DifficultyPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Story => "Story",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Ironman => "Ironman",
+            Self::Custom => "Custom",
+        }
+    }
+}
+
 /// Difficulty configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DifficultyConfig {
@@ -292,6 +331,34 @@ pub struct DisplayConfig {
     
     /// Message log length
     pub message_log_length: usize,
+    
+    /// tmux/zellij status line export
+    #[serde(default)]
+    pub status_line: StatusLineConfig,
+    
+    /// Tell some encounter passages wrong until the codex corrects them
+    #[serde(default)]
+    pub unreliable_narrator: bool,
+    
+    /// Name of the theme to draw in (None = the Default)
+    #[serde(default)]
+    pub theme: Option<String>,
+    
+    /// Underline letters typed right and strike through those typed wrong
+    #[serde(default)]
+    pub typing_markers: bool,
+    
+    /// Icons the terminal can draw (None = probe at startup)
+    #[serde(default)]
+    pub icons: Option<IconStyle>,
+    
+    /// Draw for a terminal screen reader: no art, a log of every change
+    #[serde(default)]
+    pub screen_reader: bool,
+    
+    /// No particles or screen shake, for slow terminals or motion sickness
+    #[serde(default)]
+    pub reduced_motion: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -321,6 +388,13 @@ impl Default for DisplayConfig {
             color_scheme: ColorScheme::Default,
             screen_shake: true,
             message_log_length: 10,
+            status_line: StatusLineConfig::default(),
+            unreliable_narrator: false,
+            theme: None,
+            typing_markers: false,
+            icons: None,
+            screen_reader: false,
+            reduced_motion: false,
         }
     }
 }
@@ -339,6 +413,10 @@ pub struct AudioConfig {
     
     /// Enable typing sounds
     pub typing_sounds: bool,
+    
+    /// Speak prompts aloud / dictation mode (needs the `audio` feature)
+    #[serde(default)]
+    pub pronunciation: PronunciationConfig,
 }
 
 impl Default for AudioConfig {
@@ -348,6 +426,7 @@ impl Default for AudioConfig {
             sfx_volume: 0.8,
             music_volume: 0.6,
             typing_sounds: true,
+            pronunciation: PronunciationConfig::default(),
         }
     }
 }
@@ -398,37 +477,3 @@ pub fn save_config(config: &GameConfig) -> std::io::Result<()> {
     fs::write(get_config_path(), content)?;
     Ok(())
 }
-
-// === Keybinding Configuration ===
-
-/// Keybinding configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KeyBindings {
-    pub menu_up: Vec<String>,
-    pub menu_down: Vec<String>,
-    pub confirm: Vec<String>,
-    pub cancel: Vec<String>,
-    pub inventory: Vec<String>,
-    pub stats: Vec<String>,
-    pub explore: Vec<String>,
-    pub flee: Vec<String>,
-    pub quick_save: Vec<String>,
-    pub quick_load: Vec<String>,
-}
-
-impl Default for KeyBindings {
-    fn default() -> Self {
-        Self {
-            menu_up: vec!["Up".to_string(), "k".to_string()],
-            menu_down: vec!["Down".to_string(), "j".to_string()],
-            confirm: vec!["Enter".to_string(), "Space".to_string()],
-            cancel: vec!["Escape".to_string(), "q".to_string()],
-            inventory: vec!["i".to_string()],
-            stats: vec!["s".to_string(), "c".to_string()],
-            explore: vec!["e".to_string(), "Enter".to_string()],
-            flee: vec!["Escape".to_string()],
-            quick_save: vec!["F5".to_string()],
-            quick_load: vec!["F9".to_string()],
-        }
-    }
-}
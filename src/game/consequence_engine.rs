@@ -0,0 +1,246 @@
+//! Consequence Engine - Carrying an encounter's consequences into the game
+//!
+//! An encounter's consequences are plain data. `apply` is the one place
+//! they take effect:
+//! - reputation changes go to the faction ledger
+//! - revealed lore goes into the codex
+//! - items gained go into the inventory
+//! - world state changes set world flags
+//! - scripted effects set flags, bump counters and move standings
+//! - enabled encounters open journal chains and are favoured by the
+//!   scheduler
+//!
+//! Lasting changes (broken factions, NPCs saved, world shifts) are written
+//! to the consequence ledger, kept with the world state, as they happen.
+//!
+//! Design: `apply` only touches what it is handed in `ConsequenceTargets`
+//! and reports what changed, so GameState decides what to tell the player
+//! and what to save.
+
+use super::consequences::ChangeKind;
+use super::encounter_scheduler::EncounterScheduler;
+use super::encounter_writing::{AuthoredEncounter, EncounterTracker};
+use super::faction_system::{FactionRelations, FactionStatus};
+use super::items::Item;
+use super::lore_fragments::LoreJournal;
+use super::narrative::Faction;
+use super::narrative_script::Effect;
+use super::world_state::WorldState;
+use crate::data::items::ItemDatabase;
+
+/// Everything an encounter's consequences can change
+pub struct ConsequenceTargets<'a> {
+    pub factions: &'a mut FactionRelations,
+    pub codex: &'a mut LoreJournal,
+    pub inventory: &'a mut Vec<Item>,
+    /// Where item ids are looked up
+    pub items: &'a ItemDatabase,
+    pub world: &'a mut WorldState,
+    pub tracker: &'a mut EncounterTracker,
+    pub scheduler: &'a mut EncounterScheduler,
+}
+
+/// What applying the consequences changed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Applied {
+    pub reputation: Vec<(Faction, i32)>,
+    /// Lore ids new to the codex
+    pub lore: Vec<String>,
+    /// Names of the items granted
+    pub items: Vec<String>,
+    /// World flags newly set
+    pub flags: Vec<String>,
+    /// Follow-up encounters opened
+    pub enabled: Vec<String>,
+}
+
+impl Applied {
+    /// What the player is told
+    pub fn messages(&self) -> Vec<String> {
+        let mut messages: Vec<String> = self.reputation.iter()
+            .map(|(f, change)| format!("{} standing {:+}", f.name(), change))
+            .collect();
+        if !self.lore.is_empty() {
+            messages.push(format!("{} new lore in your codex.", self.lore.len()));
+        }
+        messages.extend(self.items.iter().map(|name| format!("Received {}!", name)));
+        if !self.enabled.is_empty() {
+            messages.push("A new lead is noted in your journal.".to_string());
+        }
+        messages
+    }
+}
+
+/// An item id as an inventory card: a consumable or a piece of gear
+fn grant(items: &ItemDatabase, id: &str) -> Option<Item> {
+    items.get_consumable(id).map(Item::from_consumable)
+        .or_else(|| items.get_equipment(id).map(Item::from_equipment))
+}
+
+/// Apply `encounter`'s consequences
+pub fn apply(encounter: &AuthoredEncounter, targets: &mut ConsequenceTargets) -> Applied {
+    let cons = &encounter.consequences;
+    let mut applied = Applied::default();
+
+    for (name, change) in &cons.reputation_changes {
+        let Some(faction) = Faction::from_id(name) else {
+            continue;
+        };
+        targets.factions.modify_standing(faction, *change);
+        applied.reputation.push((faction, *change));
+        if matches!(targets.factions.status(&faction), FactionStatus::Nemesis | FactionStatus::BloodEnemy) {
+            targets.world.ledger.record(
+                ChangeKind::FactionDestroyed,
+                faction.name(),
+                format!("{}, after '{}'", faction.name(), encounter.title),
+            );
+        }
+    }
+
+    for id in &cons.lore_revealed {
+        if !targets.codex.has_discovered(id) {
+            targets.codex.discover(id);
+            applied.lore.push(id.clone());
+        }
+    }
+
+    for id in &cons.items_gained {
+        if let Some(item) = grant(targets.items, id) {
+            applied.items.push(item.name.clone());
+            targets.inventory.push(item);
+        }
+    }
+
+    for npc in &cons.npcs_saved {
+        targets.world.ledger.record(ChangeKind::NpcSaved, npc, format!("{}, in '{}'", npc, encounter.title));
+    }
+
+    for flag in &cons.world_state_changes {
+        if targets.world.set_flag(flag) {
+            applied.flags.push(flag.clone());
+        }
+        targets.world.ledger.record(
+            ChangeKind::WorldShift,
+            flag,
+            format!("{} ('{}')", flag.replace('_', " "), encounter.title),
+        );
+    }
+
+    for effect in cons.effects.iter().flat_map(|e| &e.effects) {
+        match effect {
+            Effect::SetFlag(flag) => {
+                if targets.world.set_flag(flag) {
+                    applied.flags.push(flag.clone());
+                }
+            }
+            Effect::Bump(counter, by) => {
+                targets.world.bump(counter, *by);
+            }
+            Effect::Standing(faction, change) => {
+                targets.factions.modify_standing(*faction, *change);
+                applied.reputation.push((*faction, *change));
+            }
+        }
+    }
+
+    targets.tracker.advance_chains(&encounter.id, &cons.enables_encounters);
+    for id in &cons.enables_encounters {
+        targets.scheduler.enable(id);
+        applied.enabled.push(id.clone());
+    }
+
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    struct Fixture {
+        factions: FactionRelations,
+        codex: LoreJournal,
+        inventory: Vec<Item>,
+        items: ItemDatabase,
+        world: WorldState,
+        tracker: EncounterTracker,
+        scheduler: EncounterScheduler,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self {
+                factions: FactionRelations::new(),
+                codex: LoreJournal::new(),
+                inventory: Vec::new(),
+                items: ItemDatabase::embedded(),
+                world: WorldState::default(),
+                tracker: EncounterTracker::new(),
+                scheduler: EncounterScheduler::new(),
+            }
+        }
+
+        fn apply(&mut self, encounter: &AuthoredEncounter) -> Applied {
+            apply(encounter, &mut ConsequenceTargets {
+                factions: &mut self.factions,
+                codex: &mut self.codex,
+                inventory: &mut self.inventory,
+                items: &self.items,
+                world: &mut self.world,
+                tracker: &mut self.tracker,
+                scheduler: &mut self.scheduler,
+            })
+        }
+    }
+
+    #[test]
+    fn test_every_consequence_lands() {
+        let mut encounter = build_encounters()["athenaeum_living_book"].clone();
+        encounter.consequences.items_gained = vec!["health_potion".to_string(), "no_such_item".to_string()];
+        encounter.consequences.world_state_changes = vec!["test_flag".to_string()];
+        let mut fx = Fixture::new();
+        let before: Vec<i32> = encounter.consequences.reputation_changes.iter()
+            .filter_map(|(name, _)| Faction::from_id(name))
+            .map(|f| fx.factions.standing(&f))
+            .collect();
+
+        let applied = fx.apply(&encounter);
+        for ((name, change), was) in encounter.consequences.reputation_changes.iter().zip(before) {
+            assert_eq!(fx.factions.standing(&Faction::from_id(name).unwrap()), was + change);
+        }
+        assert!(encounter.consequences.lore_revealed.iter().all(|id| fx.codex.has_discovered(id)));
+        assert_eq!(fx.inventory.len(), 1);
+        assert_eq!(applied.items, vec![fx.inventory[0].name.clone()]);
+        assert!(fx.world.has_flag("test_flag"));
+        assert!(fx.world.ledger.has(ChangeKind::WorldShift, "test_flag"));
+        for id in &encounter.consequences.enables_encounters {
+            assert!(fx.tracker.active_chains.contains(id));
+        }
+        assert!(!applied.messages().is_empty());
+    }
+
+    #[test]
+    fn test_repeats_only_report_what_is_new() {
+        let encounter = &build_encounters()["first_archivist_meeting"];
+        let mut fx = Fixture::new();
+        let first = fx.apply(encounter);
+        assert!(!first.lore.is_empty());
+        let again = fx.apply(encounter);
+        assert!(again.lore.is_empty());
+        assert!(again.flags.is_empty());
+        assert_eq!(fx.codex.discovery_order.len(), first.lore.len());
+    }
+
+    #[test]
+    fn test_only_a_rescue_saves_an_npc() {
+        let rescue = build_encounters()["mechanist_breakdown"].clone();
+        let mut comfort = rescue.clone();
+        comfort.consequences = rescue.outcomes["mechanist_comfort"].clone();
+        let mut fx = Fixture::new();
+
+        fx.apply(&comfort);
+        assert!(fx.world.ledger.of_kind(ChangeKind::NpcSaved).is_empty());
+        fx.apply(&rescue);
+        assert!(fx.world.ledger.has(ChangeKind::NpcSaved, "Technician Kaya"));
+    }
+}
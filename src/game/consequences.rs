@@ -0,0 +1,131 @@
+//! Consequences - The ledger of what you changed in the world
+//!
+//! Every lasting mark a profile leaves on the world is written here:
+//! - factions broken by your choices
+//! - NPCs you stood up for
+//! - endings you reached
+//! - world shifts set in motion by encounters
+//!
+//! A fresh timeline wipes the ledger (and the world flags behind it) but
+//! the player's typing stats and cosmetics carry over. Past timelines are
+//! only remembered by number.
+//!
+//! Design: the ledger is append-only within a timeline and deduplicated by
+//! (kind, id), so replaying an encounter never writes the same change twice.
+
+use serde::{Deserialize, Serialize};
+
+/// What sort of change was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    FactionDestroyed,
+    NpcSaved,
+    EndingAchieved,
+    WorldShift,
+}
+
+impl ChangeKind {
+    pub const ALL: [ChangeKind; 4] = [
+        ChangeKind::FactionDestroyed,
+        ChangeKind::NpcSaved,
+        ChangeKind::EndingAchieved,
+        ChangeKind::WorldShift,
+    ];
+
+    /// Section heading on the Consequences screen
+    pub fn heading(&self) -> &'static str {
+        match self {
+            Self::FactionDestroyed => "Factions Destroyed",
+            Self::NpcSaved => "NPCs Saved",
+            Self::EndingAchieved => "Endings Achieved",
+            Self::WorldShift => "The World, Changed",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::FactionDestroyed => "✗",
+            Self::NpcSaved => "♥",
+            Self::EndingAchieved => "★",
+            Self::WorldShift => "◈",
+        }
+    }
+}
+
+/// One persistent change to the world
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldChange {
+    pub kind: ChangeKind,
+    /// Stable id (faction, NPC, ending or world flag)
+    pub id: String,
+    /// What the player sees, e.g. "Technician Kaya, in 'The Doubting Mechanist'"
+    pub description: String,
+}
+
+/// Every change on the current timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldLedger {
+    /// Which timeline this is (1 = the first)
+    pub timeline: u32,
+    pub changes: Vec<WorldChange>,
+}
+
+impl Default for WorldLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorldLedger {
+    pub fn new() -> Self {
+        Self { timeline: 1, changes: Vec::new() }
+    }
+
+    /// Write a change; returns false if it was already recorded
+    pub fn record(&mut self, kind: ChangeKind, id: &str, description: impl Into<String>) -> bool {
+        if self.has(kind, id) {
+            return false;
+        }
+        self.changes.push(WorldChange { kind, id: id.to_string(), description: description.into() });
+        true
+    }
+
+    pub fn has(&self, kind: ChangeKind, id: &str) -> bool {
+        self.changes.iter().any(|c| c.kind == kind && c.id == id)
+    }
+
+    /// Changes of one kind, oldest first
+    pub fn of_kind(&self, kind: ChangeKind) -> Vec<&WorldChange> {
+        self.changes.iter().filter(|c| c.kind == kind).collect()
+    }
+
+    /// Start over on a new timeline with a clean ledger
+    pub fn start_fresh(&mut self) {
+        self.timeline += 1;
+        self.changes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_deduplicates() {
+        let mut ledger = WorldLedger::new();
+        assert!(ledger.record(ChangeKind::NpcSaved, "kaya", "Technician Kaya"));
+        assert!(!ledger.record(ChangeKind::NpcSaved, "kaya", "Technician Kaya again"));
+        // Same id, different kind is a different change
+        assert!(ledger.record(ChangeKind::WorldShift, "kaya", "Kaya's workshop reopened"));
+        assert_eq!(ledger.of_kind(ChangeKind::NpcSaved).len(), 1);
+    }
+
+    #[test]
+    fn test_fresh_timeline() {
+        let mut ledger = WorldLedger::new();
+        ledger.record(ChangeKind::EndingAchieved, "unbound", "The Unbound Word");
+        ledger.start_fresh();
+        assert_eq!(ledger.timeline, 2);
+        assert!(ledger.changes.is_empty());
+    }
+}
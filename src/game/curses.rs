@@ -0,0 +1,196 @@
+//! Curses - Power with a price
+//!
+//! Some treasure chests are corrupted, and the loot inside is cursed: a real
+//! boost to damage with a drawback bound to it for the rest of the run:
+//! - Corrupted prompts: some prompts twist while you type them
+//! - Short fuse: less time for every word
+//! - Thin skin: enemies hit harder
+//!
+//! The more corrupted the zone, the likelier a chest is cursed and the
+//! heavier both sides of the bargain.
+//!
+//! Design: a curse is rolled with the zone's corruption baked in and kept
+//! on the player; GameState hands the combined load to CombatState at the
+//! start of each fight.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::world_integration::FloorZone;
+
+/// Chance a treasure chest is corrupted in an untouched zone
+const BASE_CURSED_CHEST_CHANCE: f32 = 0.1;
+/// Extra chance per point of zone corruption
+const CURSED_CHEST_PER_CORRUPTION: f32 = 0.4;
+
+/// What a curse takes in return
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Drawback {
+    /// Extra chance per prompt that it is corrupted as you type
+    CorruptedPrompts { chance: f32 },
+    /// Share of every word's time lost
+    ShortFuse { time_cut: f32 },
+    /// Extra share of damage taken from enemies
+    ThinSkin { damage_taken: f32 },
+}
+
+impl Drawback {
+    fn scaled(self, by: f32) -> Self {
+        match self {
+            Self::CorruptedPrompts { chance } => Self::CorruptedPrompts { chance: chance * by },
+            Self::ShortFuse { time_cut } => Self::ShortFuse { time_cut: time_cut * by },
+            Self::ThinSkin { damage_taken } => Self::ThinSkin { damage_taken: damage_taken * by },
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let percent = |x: f32| (x * 100.0).round() as i32;
+        match self {
+            Self::CorruptedPrompts { chance } => format!("{}% of prompts corrupted", percent(*chance)),
+            Self::ShortFuse { time_cut } => format!("-{}% time per word", percent(*time_cut)),
+            Self::ThinSkin { damage_taken } => format!("+{}% damage taken", percent(*damage_taken)),
+        }
+    }
+}
+
+/// A cursed item the player carries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Curse {
+    pub name: String,
+    /// Damage multiplier it grants
+    pub damage_mult: f32,
+    pub drawback: Drawback,
+}
+
+/// Cursed loot before the zone's corruption is applied
+struct CursedLoot {
+    name: &'static str,
+    /// Damage bonus in an untouched zone
+    power: f32,
+    drawback: Drawback,
+}
+
+const CURSED_LOOT: [CursedLoot; 4] = [
+    CursedLoot {
+        name: "Blackened Quill",
+        power: 0.15,
+        drawback: Drawback::CorruptedPrompts { chance: 0.05 },
+    },
+    CursedLoot {
+        name: "Word of Unmaking",
+        power: 0.25,
+        drawback: Drawback::CorruptedPrompts { chance: 0.1 },
+    },
+    CursedLoot {
+        name: "Hourglass of Ash",
+        power: 0.2,
+        drawback: Drawback::ShortFuse { time_cut: 0.1 },
+    },
+    CursedLoot {
+        name: "Flayed Gauntlets",
+        power: 0.2,
+        drawback: Drawback::ThinSkin { damage_taken: 0.15 },
+    },
+];
+
+impl FloorZone {
+    /// How deep the Unwriting runs here (0-1)
+    pub fn corruption_level(&self) -> f32 {
+        match self {
+            Self::ShatteredHalls => 0.1,
+            Self::SunkenArchives => 0.25,
+            Self::BlightedGardens => 0.45,
+            Self::ClockworkDepths => 0.6,
+            Self::VoidsEdge => 0.8,
+            Self::TheBreach => 1.0,
+        }
+    }
+}
+
+/// Chance a treasure chest is corrupted at this corruption level
+pub fn cursed_chest_chance(corruption: f32) -> f32 {
+    BASE_CURSED_CHEST_CHANCE + corruption * CURSED_CHEST_PER_CORRUPTION
+}
+
+impl Curse {
+    /// Draw cursed loot, its power and price both growing with `corruption`
+    pub fn roll(corruption: f32, rng: &mut impl Rng) -> Self {
+        let loot = CURSED_LOOT.choose(rng).unwrap_or(&CURSED_LOOT[0]);
+        let scale = 1.0 + corruption.clamp(0.0, 1.0);
+        Self {
+            name: loot.name.to_string(),
+            damage_mult: 1.0 + loot.power * scale,
+            drawback: loot.drawback.scaled(scale),
+        }
+    }
+
+    /// e.g. "+23% damage; 7% of prompts corrupted"
+    pub fn describe(&self) -> String {
+        format!(
+            "+{}% damage; {}",
+            ((self.damage_mult - 1.0) * 100.0).round() as i32,
+            self.drawback.describe()
+        )
+    }
+}
+
+/// Everything the carried curses add up to in a fight
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurseLoad {
+    pub damage_mult: f32,
+    pub prompt_corruption: f32,
+    pub time_mult: f32,
+    pub damage_taken_mult: f32,
+}
+
+impl Default for CurseLoad {
+    fn default() -> Self {
+        Self { damage_mult: 1.0, prompt_corruption: 0.0, time_mult: 1.0, damage_taken_mult: 1.0 }
+    }
+}
+
+/// The combined load of every curse carried
+pub fn load_of(curses: &[Curse]) -> CurseLoad {
+    curses.iter().fold(CurseLoad::default(), |mut load, curse| {
+        load.damage_mult *= curse.damage_mult;
+        match curse.drawback {
+            Drawback::CorruptedPrompts { chance } => load.prompt_corruption += chance,
+            Drawback::ShortFuse { time_cut } => load.time_mult *= 1.0 - time_cut,
+            Drawback::ThinSkin { damage_taken } => load.damage_taken_mult += damage_taken,
+        }
+        load
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corruption_deepens_the_bargain() {
+        let mut rng = rand::thread_rng();
+        let calm = Curse::roll(0.0, &mut rng);
+        assert!(calm.damage_mult > 1.0);
+        assert!(cursed_chest_chance(FloorZone::TheBreach.corruption_level())
+            > cursed_chest_chance(FloorZone::ShatteredHalls.corruption_level()));
+
+        let quill = CURSED_LOOT[0].drawback.scaled(2.0);
+        assert_eq!(quill, Drawback::CorruptedPrompts { chance: 0.1 });
+        assert_eq!(quill.describe(), "10% of prompts corrupted");
+    }
+
+    #[test]
+    fn test_curses_stack() {
+        let curses = vec![
+            Curse { name: "a".into(), damage_mult: 1.2, drawback: Drawback::CorruptedPrompts { chance: 0.05 } },
+            Curse { name: "b".into(), damage_mult: 1.5, drawback: Drawback::ShortFuse { time_cut: 0.2 } },
+        ];
+        let load = load_of(&curses);
+        assert!((load.damage_mult - 1.8).abs() < 1e-4);
+        assert!((load.prompt_corruption - 0.05).abs() < 1e-4);
+        assert!((load.time_mult - 0.8).abs() < 1e-4);
+        assert_eq!(load.damage_taken_mult, 1.0);
+        assert_eq!(curses[0].describe(), "+20% damage; 5% of prompts corrupted");
+    }
+}
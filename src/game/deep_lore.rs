@@ -9,6 +9,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::endings::{Requirement, VOID_CURSES};
+use super::ideology::Pole;
+
 // ===========================================================================
 // THE COSMOLOGY - What is true about this universe
 // ===========================================================================
@@ -503,11 +506,24 @@ pub struct PlayerTruth {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ending {
     pub name: String,
-    pub requirements: Vec<String>,
+    pub requirements: Vec<EndingRequirement>,
     pub description: String,
     pub consequences: String,
 }
 
+/// A requirement as the mystery words it, and what the run must show
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndingRequirement {
+    pub text: String,
+    pub check: Requirement,
+}
+
+impl EndingRequirement {
+    fn new(text: &str, check: Requirement) -> Self {
+        Self { text: text.to_string(), check }
+    }
+}
+
 pub fn create_player_mystery() -> PlayerMystery {
     let mut clues = HashMap::new();
     
@@ -623,8 +639,11 @@ pub fn create_player_mystery() -> PlayerMystery {
         possible_endings: vec![
             Ending {
                 name: "The Final Rest".to_string(),
-                requirements: vec!["Gather all five Elder Stone fragments".to_string(),
-                                   "Sacrifice yourself to seal the Breach".to_string()],
+                requirements: vec![
+                    EndingRequirement::new("Gather all five Elder Stone fragments", Requirement::AllArtifacts),
+                    EndingRequirement::new("Sacrifice yourself to seal the Breach", Requirement::Uncursed),
+                    EndingRequirement::new("Want the world kept, not unwritten", Requirement::NotLeaning(Pole::Unwrite)),
+                ],
                 description: "You give what remains of your divine power to close the wound. \
                     The Breach seals. The Blight recedes. You die, truly and finally.".to_string(),
                 consequences: "The world heals slowly. You are remembered as both villain and savior. \
@@ -632,8 +651,11 @@ pub fn create_player_mystery() -> PlayerMystery {
             },
             Ending {
                 name: "The Dark Ascension".to_string(),
-                requirements: vec!["Embrace your connection to the Void".to_string(),
-                                   "Absorb the power of the Breach".to_string()],
+                requirements: vec![
+                    EndingRequirement::new("Embrace your connection to the Void", Requirement::Cursed(VOID_CURSES)),
+                    EndingRequirement::new("Absorb the power of the Breach", Requirement::Unwarded),
+                    EndingRequirement::new("Let go of what was written", Requirement::NotLeaning(Pole::Preserve)),
+                ],
                 description: "You remember. You accept. You become what you were becoming \
                     before you flinched. The God of Endings rises.".to_string(),
                 consequences: "The world ends. Not in fire, but in silence. \
@@ -642,9 +664,12 @@ pub fn create_player_mystery() -> PlayerMystery {
             },
             Ending {
                 name: "The Third Path".to_string(),
-                requirements: vec!["Unite all five factions".to_string(),
-                                   "Find the Dreamer beneath the mountain".to_string(),
-                                   "Wake them with your choice".to_string()],
+                requirements: vec![
+                    EndingRequirement::new("Unite all five factions", Requirement::FactionsUnited),
+                    EndingRequirement::new("Find the Dreamer beneath the mountain", Requirement::ArtifactTruth),
+                    EndingRequirement::new("Wake them with your choice", Requirement::Unbound),
+                    EndingRequirement::new("Walk through rather than hold the door", Requirement::NotLeaning(Pole::Control)),
+                ],
                 description: "You discover a truth older than gods: the world dreams itself. \
                     You choose not to close the Breach or join it, but to walk through. \
                     On the other side, you find not the Void, but the Dreamer. \
@@ -688,6 +713,10 @@ pub struct BossLore {
     pub appearance: String,
     pub true_identity: String,
     pub last_words: String,
+    /// The name that reaches what is left of them, spoken mid-fight
+    pub true_name: String,
+    /// The lore fragment that reveals it
+    pub revealed_by: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -760,6 +789,8 @@ pub fn get_dungeon_lore() -> DungeonLore {
                 true_identity: "Sir Aldric, captain of the royal guard. He refused to abandon his post \
                     even when his king became a monster. His loyalty bound him here.".to_string(),
                 last_words: "My king... I failed you. I failed everyone. Rest now... please rest...".to_string(),
+                true_name: "Sir Aldric".to_string(),
+                revealed_by: "Knight's Final Letter".to_string(),
             },
             BossLore {
                 name: "The Void Herald".to_string(),
@@ -770,6 +801,8 @@ pub fn get_dungeon_lore() -> DungeonLore {
                 true_identity: "What remains of everyone Malachar sacrificed for the ritual—including \
                     his beloved. They are not angry. They are lonely.".to_string(),
                 last_words: "We waited so long... we knew you would come... we forgive you... do you forgive yourself?".to_string(),
+                true_name: "We were the price".to_string(),
+                revealed_by: "Names in the Dark".to_string(),
             },
         ],
         the_bottom: BottomLore {
@@ -1014,3 +1047,48 @@ pub fn build_faction_histories() -> HashMap<String, FactionHistory> {
     
     histories
 }
+
+/// Artifacts no faction holds any more, lost somewhere in the dungeon
+pub fn build_lost_artifacts() -> Vec<Artifact> {
+    vec![
+        Artifact {
+            name: "The Original Manuscript".to_string(),
+            description: "The first draft of the Binding Oaths, in Valdris's own hand.".to_string(),
+            origin_story: "Written over forty nights by candlelight, every line struck \
+                through and rewritten until the words held.".to_string(),
+            powers: vec![
+                "Its reader keeps time with the words as they were first written.".to_string(),
+                "Corrections in the margin still rewrite what they touch.".to_string(),
+            ],
+            current_location: ArtifactLocation::Lost,
+            who_wants_it: vec!["The Mages Guild, who claim it burned.".to_string()],
+            hidden_truth: Some("The flaw in the oaths is in Valdris's hand, crossed out \
+                and then written back in. He chose to leave it.".to_string()),
+        },
+        Artifact {
+            name: "The Songlines".to_string(),
+            description: "A braided cord of knots the Rangers sang the old roads into.".to_string(),
+            origin_story: "Each knot is a verse; each verse, a path that once led somewhere \
+                that no longer exists.".to_string(),
+            powers: vec![
+                "Whoever carries it never loses the thread of a song.".to_string(),
+                "The Blight will not take root along the roads it remembers.".to_string(),
+            ],
+            current_location: ArtifactLocation::Rumored("Somewhere in the Blighted Gardens.".to_string()),
+            who_wants_it: vec!["The Rangers of the Wild.".to_string()],
+            hidden_truth: Some("The last verse was never sung. It is the road into the \
+                Breach, and the Rangers walked it first.".to_string()),
+        },
+    ]
+}
+
+/// Every artifact in the lore: those the factions hold and the lost ones
+pub fn all_artifacts() -> Vec<Artifact> {
+    let mut histories: Vec<FactionHistory> = build_faction_histories().into_values().collect();
+    histories.sort_by(|a, b| a.faction_name.cmp(&b.faction_name));
+    histories
+        .into_iter()
+        .flat_map(|h| h.key_artifacts)
+        .chain(build_lost_artifacts())
+        .collect()
+}
@@ -0,0 +1,351 @@
+//! Dev Console - Content authoring at typing speed
+//!
+//! A debug-build overlay (F12) where writers can poke at content without
+//! playing into it:
+//! - `encounter <id>` previews an authored encounter
+//! - `enemy <id|name> [hp%]` renders enemy art at simulated damage levels
+//! - `words <floor> [theme]` prints a word pool's difficulty distribution
+//! - `voice <npc> [context]` samples lines from an NPC voice profile
+//!
+//! Design: commands are pure functions of the game data; the console only
+//! keeps the input line, scrollback, and history.
+
+use std::collections::HashMap;
+
+use crate::data::enemies::EnemyDatabase;
+use crate::data::lore_words::LoreWords;
+use crate::game::encounter_writing::AuthoredEncounter;
+use crate::game::enemy_visuals::{EnemyVisualState, HitLocation};
+use crate::game::keyboard_layout::KeyboardLayout;
+use crate::game::npc_voice::NpcVoice;
+use crate::game::voice_system::DialogueContext;
+
+/// Scrollback lines kept before the oldest are dropped
+const MAX_OUTPUT: usize = 200;
+
+/// Health levels shown when no explicit hp% is given
+const PREVIEW_LEVELS: [u32; 4] = [100, 60, 25, 5];
+
+/// Console state
+#[derive(Debug, Clone, Default)]
+pub struct DevConsole {
+    /// Whether the overlay is open
+    pub visible: bool,
+    /// Current input line
+    pub input: String,
+    /// Scrollback, oldest first
+    pub output: Vec<String>,
+    /// Previously executed commands
+    history: Vec<String>,
+    /// Position while browsing history (None = editing a fresh line)
+    history_cursor: Option<usize>,
+}
+
+/// Read-only view of the content a command can inspect
+pub struct ConsoleContext<'a> {
+    pub encounters: &'a HashMap<String, AuthoredEncounter>,
+    pub enemies: &'a EnemyDatabase,
+    pub voices: &'a HashMap<&'static str, NpcVoice>,
+    pub layout: KeyboardLayout,
+}
+
+impl DevConsole {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Run the current input line and append its output
+    pub fn submit(&mut self, ctx: &ConsoleContext) {
+        let line = std::mem::take(&mut self.input);
+        let line = line.trim();
+        self.history_cursor = None;
+        if line.is_empty() {
+            return;
+        }
+        if line == "clear" {
+            self.output.clear();
+        } else {
+            self.output.push(format!("> {}", line));
+            self.output.extend(execute(line, ctx));
+        }
+        if self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_string());
+        }
+        if self.output.len() > MAX_OUTPUT {
+            let excess = self.output.len() - MAX_OUTPUT;
+            self.output.drain(..excess);
+        }
+    }
+
+    /// Recall the previous command (Up arrow)
+    pub fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let idx = match self.history_cursor {
+            Some(0) => 0,
+            Some(i) => i - 1,
+            None => self.history.len() - 1,
+        };
+        self.history_cursor = Some(idx);
+        self.input = self.history[idx].clone();
+    }
+
+    /// Step forward through history (Down arrow)
+    pub fn history_next(&mut self) {
+        match self.history_cursor {
+            Some(i) if i + 1 < self.history.len() => {
+                self.history_cursor = Some(i + 1);
+                self.input = self.history[i + 1].clone();
+            }
+            _ => {
+                self.history_cursor = None;
+                self.input.clear();
+            }
+        }
+    }
+}
+
+/// Execute one command line and return its output
+pub fn execute(line: &str, ctx: &ConsoleContext) -> Vec<String> {
+    let mut parts = line.split_whitespace();
+    let Some(cmd) = parts.next() else {
+        return Vec::new();
+    };
+    let args: Vec<&str> = parts.collect();
+
+    match cmd {
+        "help" => vec![
+            "encounters              list encounter ids".to_string(),
+            "encounter <id>          preview an encounter".to_string(),
+            "enemies                 list enemy and boss ids".to_string(),
+            "enemy <id|name> [hp%]   render enemy at damage levels".to_string(),
+            "words <floor> [theme]   word pool difficulty".to_string(),
+            "voice <npc> [context]   sample an NPC's voice".to_string(),
+            "clear                   clear the console".to_string(),
+        ],
+        "encounters" => {
+            let mut ids: Vec<&String> = ctx.encounters.keys().collect();
+            ids.sort();
+            ids.into_iter().map(|id| format!("  {}", id)).collect()
+        }
+        "encounter" => match args.first() {
+            Some(id) => preview_encounter(id, ctx),
+            None => vec!["usage: encounter <id>".to_string()],
+        },
+        "enemies" => {
+            let mut ids: Vec<String> = ctx.enemies.enemies.keys()
+                .map(|id| format!("  {}", id))
+                .chain(ctx.enemies.bosses.keys().map(|id| format!("  {} (boss)", id)))
+                .collect();
+            ids.sort();
+            ids
+        }
+        "enemy" => {
+            let hp = args.last().and_then(|a| a.trim_end_matches('%').parse::<u32>().ok());
+            let name_args = if hp.is_some() { &args[..args.len() - 1] } else { &args[..] };
+            if name_args.is_empty() {
+                return vec!["usage: enemy <id|name> [hp%]".to_string()];
+            }
+            preview_enemy(&name_args.join(" "), hp, ctx)
+        }
+        "words" => match args.first().and_then(|f| f.parse::<u32>().ok()) {
+            Some(floor) => word_pool_report(floor, args.get(1).copied(), ctx.layout),
+            None => vec!["usage: words <floor> [theme]".to_string()],
+        },
+        "voice" => match args.first() {
+            Some(npc) => sample_voice(npc, args.get(1).copied(), ctx),
+            None => {
+                let mut ids: Vec<&str> = ctx.voices.keys().copied().collect();
+                ids.sort();
+                vec![format!("usage: voice <{}> [context]", ids.join("|"))]
+            }
+        },
+        other => vec![format!("unknown command '{}' (try help)", other)],
+    }
+}
+
+fn preview_encounter(id: &str, ctx: &ConsoleContext) -> Vec<String> {
+    let Some(enc) = ctx.encounters.get(id) else {
+        return vec![format!("no encounter '{}'", id)];
+    };
+
+    let mut out = vec![
+        format!("== {} [{}] ==", enc.title, enc.id),
+        format!("locations: {:?}", enc.valid_locations),
+    ];
+    out.extend(enc.content.description.lines().map(|l| l.to_string()));
+    for detail in &enc.content.environmental_details {
+        out.push(format!("  * {}", detail));
+    }
+    if let Some(dialogue) = &enc.content.dialogue {
+        for line in dialogue {
+            out.push(format!("  {}: \"{}\"", line.speaker, line.text));
+        }
+    }
+    if let Some(challenge) = &enc.content.typing_challenge {
+        out.push(format!("  type (d{}): {}", challenge.difficulty, challenge.prompt_text));
+    }
+    for (i, choice) in enc.choices.iter().enumerate() {
+        let requires = choice.requires.as_deref()
+            .map(|r| format!(" [needs {}]", r))
+            .unwrap_or_default();
+        out.push(format!("  {}. {}{}", i + 1, choice.text, requires));
+    }
+    out
+}
+
+fn preview_enemy(query: &str, hp: Option<u32>, ctx: &ConsoleContext) -> Vec<String> {
+    let q = query.to_lowercase();
+    let matches = |id: &str, name: &str| id.to_lowercase() == q || name.to_lowercase() == q;
+
+    let found = ctx.enemies.enemies.values()
+        .find(|e| matches(&e.id, &e.name))
+        .map(|e| (e.name.clone(), e.ascii_art.clone()))
+        .or_else(|| {
+            ctx.enemies.bosses.values()
+                .find(|b| matches(&b.id, &b.name))
+                .map(|b| (b.name.clone(), b.ascii_art.clone()))
+        });
+    let Some((name, art)) = found else {
+        return vec![format!("no enemy '{}'", query)];
+    };
+
+    let levels: Vec<u32> = match hp {
+        Some(pct) => vec![pct.min(100)],
+        None => PREVIEW_LEVELS.to_vec(),
+    };
+
+    let mut out = Vec::new();
+    for pct in levels {
+        let health = pct as f32 / 100.0;
+        let mut visual = EnemyVisualState::from_ascii(&art);
+        if pct < 100 {
+            visual.apply_damage(1.0 - health, HitLocation::Torso);
+        }
+        visual.update_from_health(health);
+        out.push(format!("-- {} @ {}% ({:?}) --", name, pct, visual.get_posture()));
+        out.extend(visual.render());
+    }
+    out
+}
+
+fn sample_voice(npc: &str, context: Option<&str>, ctx: &ConsoleContext) -> Vec<String> {
+    let Some(voice) = ctx.voices.get(npc.to_lowercase().as_str()) else {
+        return vec![format!("no voice '{}'", npc)];
+    };
+    let context = match context.map(str::to_lowercase).as_deref() {
+        None | Some("neutral") => DialogueContext::Neutral,
+        Some("greeting") => DialogueContext::Greeting,
+        Some("farewell") => DialogueContext::Farewell,
+        Some("quest") => DialogueContext::QuestGive,
+        Some("done") => DialogueContext::QuestComplete,
+        Some("warning") => DialogueContext::Warning,
+        Some("gossip") => DialogueContext::Gossip,
+        Some("thanks") => DialogueContext::Gratitude,
+        Some(other) => return vec![format!(
+            "unknown context '{}' (neutral, greeting, farewell, quest, done, warning, gossip, thanks)",
+            other
+        )],
+    };
+
+    let all: Vec<&NpcVoice> = ctx.voices.values().collect();
+    let mut rng = rand::thread_rng();
+    let mut out = vec![format!("-- {} ({:?}, {:?}) --", voice.name, voice.tier, context)];
+    out.extend((0..5).map(|_| format!("  \"{}\"", voice.generate_line(context, &all, &mut rng))));
+    out
+}
+
+fn word_pool_report(floor: u32, theme: Option<&str>, layout: KeyboardLayout) -> Vec<String> {
+    let mut pool = LoreWords::get_zone_words(floor);
+    if let Some(theme) = theme {
+        pool.extend(LoreWords::get_enemy_words(theme));
+    }
+    if pool.is_empty() {
+        return vec!["empty pool".to_string()];
+    }
+
+    let mut out = vec![format!(
+        "floor {} {}: {} words ({})",
+        floor,
+        theme.unwrap_or("(zone only)"),
+        pool.len(),
+        layout.name()
+    )];
+
+    let mut by_length: HashMap<usize, usize> = HashMap::new();
+    for word in &pool {
+        *by_length.entry(word.chars().count()).or_default() += 1;
+    }
+    let mut lengths: Vec<_> = by_length.into_iter().collect();
+    lengths.sort();
+    out.push("length:".to_string());
+    for (len, count) in lengths {
+        out.push(format!("  {:>2} {:>3} {}", len, count, "#".repeat(count)));
+    }
+
+    let mut scored: Vec<(&str, f32)> = pool.iter().map(|w| (*w, layout.word_difficulty(w))).collect();
+    scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let buckets = [("easy", 0.0, 0.5), ("medium", 0.5, 1.0), ("hard", 1.0, f32::MAX)];
+    out.push("difficulty:".to_string());
+    for (label, lo, hi) in buckets {
+        let count = scored.iter().filter(|(_, d)| *d >= lo && *d < hi).count();
+        out.push(format!("  {:<6} {:>3} {}", label, count, "#".repeat(count)));
+    }
+    let hardest: Vec<&str> = scored.iter().rev().take(5).map(|(w, _)| *w).collect();
+    out.push(format!("hardest: {}", hardest.join(", ")));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+    use crate::game::npc_voice::build_npc_voices;
+
+    #[test]
+    fn test_commands_produce_output() {
+        let encounters = build_encounters();
+        let enemies = EnemyDatabase::default();
+        let voices = build_npc_voices();
+        let ctx = ConsoleContext { encounters: &encounters, enemies: &enemies, voices: &voices, layout: KeyboardLayout::Qwerty };
+
+        let id = encounters.keys().next().unwrap().clone();
+        assert!(execute(&format!("encounter {}", id), &ctx)[0].contains(&id));
+
+        let enemy = enemies.enemies.keys().next().unwrap().clone();
+        let art = execute(&format!("enemy {} 40%", enemy), &ctx);
+        assert!(art[0].contains("40%"));
+
+        assert!(execute("words 1", &ctx)[0].starts_with("floor 1"));
+        assert!(execute("voice kaya warning", &ctx)[0].contains("Kaya"));
+        assert!(execute("bogus", &ctx)[0].contains("unknown"));
+    }
+
+    #[test]
+    fn test_history_recall() {
+        let encounters = HashMap::new();
+        let enemies = EnemyDatabase::default();
+        let voices = build_npc_voices();
+        let ctx = ConsoleContext { encounters: &encounters, enemies: &enemies, voices: &voices, layout: KeyboardLayout::Qwerty };
+
+        let mut console = DevConsole::new();
+        console.input = "help".to_string();
+        console.submit(&ctx);
+        console.input = "words 3".to_string();
+        console.submit(&ctx);
+
+        console.history_prev();
+        assert_eq!(console.input, "words 3");
+        console.history_prev();
+        assert_eq!(console.input, "help");
+        console.history_next();
+        assert_eq!(console.input, "words 3");
+        console.history_next();
+        assert!(console.input.is_empty());
+    }
+}
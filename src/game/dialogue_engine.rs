@@ -3,8 +3,13 @@
 //! Combat dialogue should feel coherent with the enemy you're fighting.
 //! A goblin talks differently than an eldritch horror.
 //! Messages respond to the current state of the fight.
+//! What each enemy says is written in the combat grammar
+//! (`data/dialogue/combat.ron`); the engine picks which rules to expand.
 
 use rand::prelude::*;
+use super::run_rng::{self, RunRng, Stream};
+use super::text_grammar;
+use super::world_integration::FloorZone;
 
 /// Combat momentum for enemies
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,6 +29,16 @@ impl CombatMomentum {
             _ => Self::Fresh,
         }
     }
+
+    /// Name used in grammar rules and the `#momentum#` slot
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fresh => "fresh",
+            Self::Bloodied => "bloodied",
+            Self::Desperate => "desperate",
+            Self::Dying => "dying",
+        }
+    }
 }
 
 /// Player's momentum in combat
@@ -36,6 +51,15 @@ pub enum PlayerMomentum {
 }
 
 impl PlayerMomentum {
+    /// Momentum with stamina counted: a winded player is in trouble
+    /// whatever their health
+    pub fn from_state(health_percent: i32, accuracy: f32, winded: bool) -> Self {
+        if winded {
+            return Self::Critical;
+        }
+        Self::from_health_and_accuracy(health_percent, accuracy)
+    }
+
     pub fn from_health_and_accuracy(health_percent: i32, accuracy: f32) -> Self {
         if health_percent < 25 {
             return Self::Critical;
@@ -62,14 +86,19 @@ pub enum ZoneContext {
 }
 
 impl ZoneContext {
+    /// Context of the zone the floor lies in on the run's route
     pub fn from_floor(floor: u32) -> Self {
-        match floor {
-            1..=2 => Self::RuinedKeep,
-            3..=4 => Self::DrownedArchives,
-            5..=6 => Self::OvergrownSanctum,
-            7..=8 => Self::ClockworkDepths,
-            9..=10 => Self::VoidBreach,
-            _ => Self::Unknown,
+        Self::from_zone(FloorZone::from_floor(floor))
+    }
+
+    pub fn from_zone(zone: FloorZone) -> Self {
+        match zone {
+            FloorZone::ShatteredHalls => Self::RuinedKeep,
+            FloorZone::SunkenArchives => Self::DrownedArchives,
+            FloorZone::BlightedGardens => Self::OvergrownSanctum,
+            FloorZone::ClockworkDepths => Self::ClockworkDepths,
+            FloorZone::VoidsEdge => Self::VoidBreach,
+            FloorZone::TheBreach => Self::Unknown,
         }
     }
 }
@@ -82,271 +111,123 @@ pub struct DialogueContext {
     pub enemy_momentum: CombatMomentum,
     pub player_momentum: PlayerMomentum,
     pub zone: ZoneContext,
+    /// Zone name as the run's setting tells it
+    pub zone_name: String,
+    /// The run's recurring motifs, for the `#motif#` slot
+    pub motifs: Vec<String>,
     pub typing_speed: f32,
     pub accuracy: f32,
 }
 
 /// Main dialogue engine
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct DialogueEngine {
-    rng: ThreadRng,
+    rng: RunRng,
+}
+
+impl Default for DialogueEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DialogueEngine {
     pub fn new() -> Self {
-        Self { rng: thread_rng() }
+        Self { rng: run_rng::stream(Stream::Dialogue) }
     }
     
     /// Generate a hit message based on context
     pub fn generate_hit_message(&mut self, ctx: &DialogueContext, damage: i32, attack_type: &crate::game::typing_impact::AttackType) -> String {
-        let base = self.get_hit_flavor(&ctx.enemy_theme, ctx.enemy_momentum, damage);
-        let modifier = self.get_attack_modifier(attack_type, ctx.enemy_momentum);
-        format!("{}{}", base, modifier)
+        use crate::game::typing_impact::AttackType;
+        let strike = match attack_type {
+            AttackType::Precision => Some("strike_precision"),
+            AttackType::Flurry => Some("strike_flurry"),
+            AttackType::Deliberate => Some("strike_deliberate"),
+            AttackType::Frantic => Some("strike_frantic"),
+            AttackType::Standard => None,
+        };
+        let falter = format!("falter_{}", ctx.enemy_momentum.name());
+        let mut parts = vec![self.say("hit", ctx, damage, true)];
+        for rule in strike.map(str::to_string).into_iter().chain([falter]) {
+            if let Some(line) = self.expand(&[rule], ctx, damage) {
+                parts.push(line);
+            }
+        }
+        parts.join(" ")
     }
     
     /// Generate enemy attack message
     pub fn generate_enemy_attack(&mut self, ctx: &DialogueContext, damage: i32) -> String {
-        match ctx.enemy_theme.as_str() {
-            "goblin" => self.goblin_attack(ctx.enemy_momentum, damage),
-            "undead" => self.undead_attack(ctx.enemy_momentum, damage),
-            "spectral" => self.spectral_attack(ctx.enemy_momentum, damage),
-            "corrupted" => self.corrupted_attack(ctx.enemy_momentum, damage),
-            "mechanical" => self.mechanical_attack(ctx.enemy_momentum, damage),
-            "void" => self.void_attack(ctx.enemy_momentum, damage),
-            _ => format!("The {} attacks for {} damage!", ctx.enemy_name, damage),
-        }
+        self.say("attack", ctx, damage, true)
+    }
+    
+    /// Generate riposte message - the enemy's own attack turned against it
+    pub fn generate_riposte_message(&mut self, ctx: &DialogueContext, damage: i32) -> String {
+        self.say("riposte", ctx, damage, false)
     }
     
     /// Generate death message
     pub fn generate_death_message(&mut self, ctx: &DialogueContext) -> String {
-        match ctx.enemy_theme.as_str() {
-            "goblin" => self.random_pick(&[
-                "The goblin squeals and collapses.".to_string(),
-                "With a pathetic whimper, the goblin falls.".to_string(),
-                "The goblin crumples, its stolen treasures scattering.".to_string(),
-            ]),
-            "undead" => self.random_pick(&[
-                "The skeleton clatters apart, finally at rest.".to_string(),
-                "Ancient bones collapse into dust.".to_string(),
-                "The undead falls, its curse finally broken.".to_string(),
-            ]),
-            "spectral" => self.random_pick(&[
-                "The spirit fades with a final, mournful wail.".to_string(),
-                "Reality reasserts itself. The phantom is gone.".to_string(),
-                "The apparition disperses like morning mist.".to_string(),
-            ]),
-            "corrupted" => self.random_pick(&[
-                "The corruption recedes. What remains is almost peaceful.".to_string(),
-                "The twisted form shudders and falls still.".to_string(),
-                "Nature, corrupted no more, returns to earth.".to_string(),
-            ]),
-            "mechanical" => self.random_pick(&[
-                "SYSTEM FAILURE. The construct powers down.".to_string(),
-                "Gears grind to a halt. Silence returns.".to_string(),
-                "The automaton collapses, its purpose ended.".to_string(),
-            ]),
-            "void" => self.random_pick(&[
-                "Reality knits itself back together where the void-touched stood.".to_string(),
-                "The darkness recedes, leaving only the memory of wrongness.".to_string(),
-                "With a sound like tearing silk reversed, it is unmade.".to_string(),
-            ]),
-            _ => format!("The {} has been defeated!", ctx.enemy_name),
-        }
+        self.say("death", ctx, 0, false)
     }
     
     /// Generate taunt from enemy
     pub fn generate_enemy_taunt(&mut self, ctx: &DialogueContext) -> Option<String> {
         if self.rng.gen::<f32>() > 0.3 { return None; }
-        
-        Some(match ctx.enemy_theme.as_str() {
-            "goblin" => match ctx.enemy_momentum {
-                CombatMomentum::Fresh => self.random_pick(&[
-                    "Gonna poke you full of holes!".to_string(),
-                    "Shinies! Give us the shinies!".to_string(),
-                ]),
-                CombatMomentum::Bloodied => self.random_pick(&[
-                    "Ow! You pay for that!".to_string(),
-                    "Not fair! NOT FAIR!".to_string(),
-                ]),
-                CombatMomentum::Desperate => self.random_pick(&[
-                    "No no no! Bad human!".to_string(),
-                    "I tells the others! They gets you!".to_string(),
-                ]),
-                CombatMomentum::Dying => "...mercy?".to_string(),
-            },
-            "void" => match ctx.enemy_momentum {
-                CombatMomentum::Fresh => self.random_pick(&[
-                    "W E   S E E   Y O U".to_string(),
-                    "Y O U   A R E   A L R E A D Y   E M P T Y".to_string(),
-                ]),
-                CombatMomentum::Bloodied | CombatMomentum::Desperate => self.random_pick(&[
-                    "T H I S   F O R M   I S   N O T H I N G".to_string(),
-                    "W E   A R E   E T E R N A L".to_string(),
-                ]),
-                CombatMomentum::Dying => "W E   W I L L   R E T U R N".to_string(),
-            },
-            _ => return None,
-        })
-    }
-    
-    /// Generate combat intro
-    pub fn generate_combat_intro(&mut self, ctx: &DialogueContext) -> String {
-        match ctx.enemy_theme.as_str() {
-            "goblin" => self.random_pick(&[
-                format!("A {} blocks your path, cackling!", ctx.enemy_name),
-                format!("The {} leaps from the shadows!", ctx.enemy_name),
-            ]),
-            "undead" => self.random_pick(&[
-                format!("A {} rises from the dust, ancient hatred burning in empty sockets.", ctx.enemy_name),
-                format!("The {} shambles forth, bones rattling.", ctx.enemy_name),
-            ]),
-            "spectral" => self.random_pick(&[
-                format!("A {} materializes from the darkness.", ctx.enemy_name),
-                format!("The temperature drops. A {} appears.", ctx.enemy_name),
-            ]),
-            "corrupted" => self.random_pick(&[
-                format!("The {} emerges from the overgrowth, twisted and wrong.", ctx.enemy_name),
-                format!("Vines part to reveal a {}, pulsing with corruption.", ctx.enemy_name),
-            ]),
-            "mechanical" => self.random_pick(&[
-                format!("INTRUDER DETECTED. A {} activates.", ctx.enemy_name),
-                format!("Gears whir to life. A {} bars your way.", ctx.enemy_name),
-            ]),
-            "void" => self.random_pick(&[
-                format!("Reality tears. A {} steps through.", ctx.enemy_name),
-                format!("The {} was always here. You just could not see it before.", ctx.enemy_name),
-            ]),
-            _ => format!("A {} appears!", ctx.enemy_name),
+        if let Some(gloat) = self.generate_gloat(ctx) {
+            return Some(gloat);
         }
+        // Only themes the grammar gives taunts to taunt at all
+        let rules = [
+            format!("taunt_{}_{}", ctx.enemy_theme, ctx.enemy_momentum.name()),
+            format!("taunt_{}", ctx.enemy_theme),
+        ];
+        self.expand(&rules, ctx, 0)
     }
     
-    fn get_hit_flavor(&mut self, theme: &str, momentum: CombatMomentum, damage: i32) -> String {
-        match theme {
-            "goblin" => match momentum {
-                CombatMomentum::Fresh => self.random_pick(&[
-                    "AIEEE! The goblin clutches the wound.".to_string(),
-                    "The goblin yelps in pain!".to_string(),
-                ]),
-                CombatMomentum::Bloodied => self.random_pick(&[
-                    "Ow! Not fair! the goblin whines.".to_string(),
-                    "The goblin staggers, looking worried.".to_string(),
-                ]),
-                CombatMomentum::Desperate | CombatMomentum::Dying => self.random_pick(&[
-                    "The goblin whimpers pathetically.".to_string(),
-                    "No more! No more!".to_string(),
-                ]),
-            },
-            "undead" => match momentum {
-                CombatMomentum::Fresh => self.random_pick(&[
-                    "Bones crack under the blow.".to_string(),
-                    "The undead feels no pain, but the damage is clear.".to_string(),
-                ]),
-                _ => self.random_pick(&[
-                    "Ancient bones shatter.".to_string(),
-                    "The skeleton is falling apart.".to_string(),
-                ]),
-            },
-            "spectral" => self.random_pick(&[
-                "The apparition SCREAMS - a sound like tearing silk.".to_string(),
-                "Your attack disrupts its form.".to_string(),
-                "The ghost flickers violently.".to_string(),
-            ]),
-            "corrupted" => self.random_pick(&[
-                "Sap-like blood oozes from the wound.".to_string(),
-                "The corrupted flesh knits wrongly.".to_string(),
-                "It does not bleed. It oozes.".to_string(),
-            ]),
-            "mechanical" => self.random_pick(&[
-                "DAMAGE SUSTAINED. Sparks fly.".to_string(),
-                "Metal shrieks as gears grind.".to_string(),
-                "ERROR: STRUCTURAL INTEGRITY COMPROMISED.".to_string(),
-            ]),
-            "void" => self.random_pick(&[
-                "Reality ripples where you strike.".to_string(),
-                "The void-touched recoils from existence.".to_string(),
-                "Something that should not be... is hurt.".to_string(),
-            ]),
-            _ => format!("You deal {} damage!", damage),
+    /// Generate the enemy's reaction to a player on the ropes, if it has
+    /// the upper hand: an enemy on its own last legs has nothing to gloat
+    /// about
+    pub fn generate_gloat(&mut self, ctx: &DialogueContext) -> Option<String> {
+        let enemy_ahead = matches!(ctx.enemy_momentum, CombatMomentum::Fresh | CombatMomentum::Bloodied);
+        if ctx.player_momentum != PlayerMomentum::Critical || !enemy_ahead {
+            return None;
         }
+        Some(self.say("gloat", ctx, 0, false))
     }
     
-    fn get_attack_modifier(&mut self, attack_type: &crate::game::typing_impact::AttackType, momentum: CombatMomentum) -> String {
-        let base = match attack_type {
-            crate::game::typing_impact::AttackType::Precision => "A precise strike!",
-            crate::game::typing_impact::AttackType::Flurry => "A rapid flurry of blows!",
-            crate::game::typing_impact::AttackType::Deliberate => "A measured, powerful hit.",
-            crate::game::typing_impact::AttackType::Frantic => "Wild swings - one connects!",
-            crate::game::typing_impact::AttackType::Standard => "",
-        };
-        
-        let momentum_mod = match momentum {
-            CombatMomentum::Bloodied => " It is wavering.",
-            CombatMomentum::Desperate => " It is faltering!",
-            CombatMomentum::Dying => " The killing blow approaches.",
-            CombatMomentum::Fresh => "",
-        };
-        
-        format!(" {}{}", base, momentum_mod)
+    /// Generate the line an enemy roars as it enrages
+    pub fn generate_enrage_line(&mut self, ctx: &DialogueContext) -> String {
+        self.say("enrage", ctx, 0, false)
     }
     
-    fn goblin_attack(&mut self, momentum: CombatMomentum, damage: i32) -> String {
-        match momentum {
-            CombatMomentum::Fresh => self.random_pick(&[
-                format!("Your shinies! MINE! It slashes at you! {} damage!", damage),
-                format!("The goblin stabs wildly! {} damage!", damage),
-            ]),
-            CombatMomentum::Bloodied => self.random_pick(&[
-                format!("The goblin attacks desperately! {} damage!", damage),
-                format!("Still gonna getcha! {} damage!", damage),
-            ]),
-            _ => format!("A feeble attack... but still {} damage.", damage),
-        }
+    /// Generate combat intro
+    pub fn generate_combat_intro(&mut self, ctx: &DialogueContext) -> String {
+        self.say("intro", ctx, 0, false)
     }
     
-    fn undead_attack(&mut self, momentum: CombatMomentum, damage: i32) -> String {
-        match momentum {
-            CombatMomentum::Fresh => self.random_pick(&[
-                format!("Bony claws rake across you! {} damage!", damage),
-                format!("The undead strikes with ancient malice! {} damage!", damage),
-            ]),
-            _ => format!("It claws at you weakly. {} damage.", damage),
+    /// Expand `kind` for the enemy's theme, most specific rule first:
+    /// `kind_theme_momentum` (when `by_momentum`), `kind_theme`, then `kind`
+    fn say(&mut self, kind: &str, ctx: &DialogueContext, damage: i32, by_momentum: bool) -> String {
+        let mut rules = Vec::new();
+        if by_momentum {
+            rules.push(format!("{}_{}_{}", kind, ctx.enemy_theme, ctx.enemy_momentum.name()));
         }
+        rules.push(format!("{}_{}", kind, ctx.enemy_theme));
+        rules.push(kind.to_string());
+        self.expand(&rules, ctx, damage).unwrap_or_default()
     }
     
-    fn spectral_attack(&mut self, _momentum: CombatMomentum, damage: i32) -> String {
-        self.random_pick(&[
-            format!("A ghostly touch chills your soul! {} damage!", damage),
-            format!("The phantom passes THROUGH you! {} damage!", damage),
-            format!("Spectral energy lashes out! {} damage!", damage),
-        ])
-    }
-    
-    fn corrupted_attack(&mut self, _momentum: CombatMomentum, damage: i32) -> String {
-        self.random_pick(&[
-            format!("Thorned vines lash at you! {} damage!", damage),
-            format!("Corrupted spores assault you! {} damage!", damage),
-            format!("The twisted thing strikes! {} damage!", damage),
-        ])
-    }
-    
-    fn mechanical_attack(&mut self, _momentum: CombatMomentum, damage: i32) -> String {
-        self.random_pick(&[
-            format!("EXECUTING COMBAT PROTOCOL. {} damage!", damage),
-            format!("Gears whir. Blades extend. {} damage!", damage),
-            format!("The construct attacks with mechanical precision! {} damage!", damage),
-        ])
-    }
-    
-    fn void_attack(&mut self, _momentum: CombatMomentum, damage: i32) -> String {
-        self.random_pick(&[
-            format!("Reality BENDS around you! {} damage!", damage),
-            format!("The void reaches into you! {} damage!", damage),
-            format!("Y O U   F E E L   E M P T Y. {} damage!", damage),
-        ])
-    }
-    
-    fn random_pick<T: Clone>(&mut self, options: &[T]) -> T {
-        options.choose(&mut self.rng).unwrap().clone()
+    fn expand(&mut self, rules: &[String], ctx: &DialogueContext, damage: i32) -> Option<String> {
+        let motif = ctx.motifs.choose(&mut self.rng).cloned().unwrap_or_else(|| "ink".to_string());
+        let slots = [
+            ("enemy", ctx.enemy_name.clone()),
+            ("zone", ctx.zone_name.clone()),
+            ("momentum", ctx.enemy_momentum.name().to_string()),
+            ("motif", motif),
+            ("damage", damage.to_string()),
+        ];
+        text_grammar::combat().expand_first(rules, &slots, &mut self.rng)
     }
 }
 
@@ -371,6 +252,8 @@ mod tests {
             enemy_momentum: CombatMomentum::Fresh,
             player_momentum: PlayerMomentum::Confident,
             zone: ZoneContext::RuinedKeep,
+            zone_name: "The Shattered Halls".to_string(),
+            motifs: vec!["broken keys".to_string()],
             typing_speed: 5.0,
             accuracy: 0.95,
         };
@@ -381,4 +264,28 @@ mod tests {
         let death = engine.generate_death_message(&ctx);
         assert!(!death.is_empty());
     }
+
+    #[test]
+    fn test_only_an_enemy_ahead_gloats() {
+        let mut engine = DialogueEngine::new();
+        let mut ctx = DialogueContext {
+            enemy_name: "Goblin Scout".to_string(),
+            enemy_theme: "goblin".to_string(),
+            enemy_momentum: CombatMomentum::Bloodied,
+            player_momentum: PlayerMomentum::Critical,
+            zone: ZoneContext::RuinedKeep,
+            zone_name: "The Shattered Halls".to_string(),
+            motifs: vec!["broken keys".to_string()],
+            typing_speed: 5.0,
+            accuracy: 0.95,
+        };
+        assert!(engine.generate_gloat(&ctx).is_some());
+
+        ctx.enemy_momentum = CombatMomentum::Dying;
+        assert!(engine.generate_gloat(&ctx).is_none());
+
+        ctx.enemy_momentum = CombatMomentum::Fresh;
+        ctx.player_momentum = PlayerMomentum::Confident;
+        assert!(engine.generate_gloat(&ctx).is_none());
+    }
 }
@@ -0,0 +1,617 @@
+//! Dialogue Trees - Conversations that branch and loop
+//!
+//! An encounter's conversation is a tree of nodes. Each node is a line
+//! someone says; after it the player picks from the node's options, or the
+//! conversation moves on to the next node by itself. Options can be:
+//! - gated: by faction standing, world flags, revealed lore, or by which
+//!   nodes have been visited (a question asked once is not offered again),
+//!   or by a scripted condition (see narrative_script)
+//! - checked: a typing check puts words in front of you, a skill check
+//!   asks whether your best WPM this run is up to it. Fail and the option
+//!   leads somewhere else
+//! - looping: an option may lead back to an earlier node, so you can
+//!   return to a hub and ask something else
+//!
+//! Encounters without an authored tree still talk: their flat dialogue
+//! lines become a tree that runs straight through.
+//!
+//! Design: trees are data (serde, so they can be written in RON); the
+//! DialogueRunner walks one, keeping the transcript and what was revealed.
+//! What an option needs is read through a `DialogueGate`, built by
+//! GameState when the player picks.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::encounter_runner::{accuracy, TypingGrade};
+use super::encounter_writing::{AuthoredEncounter, DialogueLine, EncounterTracker};
+use super::faction_system::FactionRelations;
+use super::narrative::Faction;
+use super::narrative_script::{Condition, Facts};
+use super::npc_memory::{NpcMemory, COLD_OPINION, WARM_OPINION};
+use super::revelation::{Revelation, RevelationState};
+use super::world_state::WorldState;
+
+/// What an option needs to be offered or taken
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DialogueCondition {
+    /// Standing with a faction of at least `min`
+    Reputation { faction: String, min: i32 },
+    /// A world flag is set
+    Flag(String),
+    /// A world flag is not set
+    NotFlag(String),
+    /// A lore fragment has been revealed
+    Lore(String),
+    /// A node has been visited this conversation
+    Visited(String),
+    /// A node has not been visited this conversation
+    NotVisited(String),
+    /// The story has revealed at least this much of the hidden truth
+    Revealed(Revelation),
+    /// An NPC's opinion of you is at least `min`
+    Opinion { npc: String, min: i32 },
+    /// A scripted condition holds
+    Script(Condition),
+}
+
+impl DialogueCondition {
+    /// Unmet conditions on visits, on what the story has revealed or on
+    /// an NPC's goodwill hide the option; others (an NPC refusing you
+    /// among them) show it locked
+    pub fn hides(&self) -> bool {
+        match self {
+            Self::Visited(_) | Self::NotVisited(_) | Self::Revealed(_) | Self::Script(_) => true,
+            Self::Opinion { min, .. } => *min > 0,
+            _ => false,
+        }
+    }
+
+    /// What the locked option says it needs
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Reputation { faction, min } => {
+                let name = Faction::from_id(faction).map_or(faction.as_str(), |f| f.name());
+                format!("{} standing {}", name, min)
+            }
+            Self::Flag(flag) => format!("requires {}", flag.replace('_', " ")),
+            Self::NotFlag(flag) => format!("closed by {}", flag.replace('_', " ")),
+            Self::Lore(id) => format!("requires lore: {}", id.replace('_', " ")),
+            Self::Opinion { npc, min } if *min <= 0 => format!("{} won't hear it from you", npc),
+            Self::Opinion { npc, .. } => format!("{} must trust you more", npc),
+            Self::Visited(_) | Self::NotVisited(_) | Self::Revealed(_) | Self::Script(_) => String::new(),
+        }
+    }
+}
+
+/// A check taking an option puts you to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DialogueCheck {
+    /// Type these words well enough
+    Typing(String),
+    /// Have a best WPM this run of at least this
+    Wpm(f64),
+}
+
+/// Something the player can say
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialogueOption {
+    pub text: String,
+    /// Node it leads to
+    pub goto: String,
+    #[serde(default)]
+    pub conditions: Vec<DialogueCondition>,
+    #[serde(default)]
+    pub check: Option<DialogueCheck>,
+    /// Node a failed check leads to (back to the same node if none)
+    #[serde(default)]
+    pub on_fail: Option<String>,
+}
+
+impl DialogueOption {
+    pub fn to(text: &str, goto: &str) -> Self {
+        Self { text: text.to_string(), goto: goto.to_string(), conditions: Vec::new(), check: None, on_fail: None }
+    }
+
+    pub fn when(mut self, condition: DialogueCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn checked(mut self, check: DialogueCheck, on_fail: &str) -> Self {
+        self.check = Some(check);
+        self.on_fail = Some(on_fail.to_string());
+        self
+    }
+}
+
+/// A line someone says, and where the conversation can go from it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialogueNode {
+    pub speaker: String,
+    pub text: String,
+    /// Clue noted when the line is heard
+    #[serde(default)]
+    pub reveals: Option<String>,
+    #[serde(default)]
+    pub options: Vec<DialogueOption>,
+    /// Node that follows when there are no options; the end if none
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+impl DialogueNode {
+    pub fn say(speaker: &str, text: &str) -> Self {
+        Self { speaker: speaker.to_string(), text: text.to_string(), reveals: None, options: Vec::new(), next: None }
+    }
+
+    pub fn reveals(mut self, clue: &str) -> Self {
+        self.reveals = Some(clue.to_string());
+        self
+    }
+
+    pub fn then(mut self, next: &str) -> Self {
+        self.next = Some(next.to_string());
+        self
+    }
+
+    pub fn option(mut self, option: DialogueOption) -> Self {
+        self.options.push(option);
+        self
+    }
+}
+
+/// A whole conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialogueTree {
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+impl DialogueTree {
+    pub fn new(start: &str) -> Self {
+        Self { start: start.to_string(), nodes: HashMap::new() }
+    }
+
+    pub fn node(mut self, id: &str, node: DialogueNode) -> Self {
+        self.nodes.insert(id.to_string(), node);
+        self
+    }
+
+    /// Flat dialogue lines as a tree that runs straight through
+    pub fn linear(lines: &[DialogueLine]) -> Self {
+        let mut tree = Self::new("line_0");
+        for (i, line) in lines.iter().enumerate() {
+            let mut node = DialogueNode::say(&line.speaker, &line.text);
+            node.reveals = line.reveals.clone();
+            if i + 1 < lines.len() {
+                node = node.then(&format!("line_{}", i + 1));
+            }
+            tree = tree.node(&format!("line_{}", i), node);
+        }
+        tree
+    }
+
+    /// Node ids that are pointed at but missing
+    pub fn dangling(&self) -> Vec<String> {
+        let mut targets: Vec<&String> = vec![&self.start];
+        for node in self.nodes.values() {
+            targets.extend(node.next.iter());
+            for option in &node.options {
+                targets.push(&option.goto);
+                targets.extend(option.on_fail.iter());
+            }
+        }
+        targets.into_iter().filter(|id| !self.nodes.contains_key(*id)).cloned().collect()
+    }
+}
+
+/// What the run looks like to a conversation
+pub struct DialogueGate<'a> {
+    pub factions: &'a FactionRelations,
+    pub world: &'a WorldState,
+    /// Lore ids revealed so far
+    pub lore: &'a [String],
+    pub best_wpm: f64,
+    pub revelation: RevelationState,
+    pub npcs: &'a NpcMemory,
+    pub floor: i32,
+    pub tracker: &'a EncounterTracker,
+}
+
+impl Facts for DialogueGate<'_> {
+    fn floor(&self) -> i32 {
+        self.floor
+    }
+
+    fn standing(&self, faction: Faction) -> i32 {
+        self.factions.standing(&faction)
+    }
+
+    fn world(&self) -> &WorldState {
+        self.world
+    }
+
+    fn tracker(&self) -> &EncounterTracker {
+        self.tracker
+    }
+
+    fn has_lore(&self, id: &str) -> bool {
+        self.lore.iter().any(|l| l == id)
+    }
+
+    fn opinion(&self, npc: &str) -> i32 {
+        self.npcs.opinion(npc)
+    }
+}
+
+/// An option as the player sees it
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionView {
+    /// Index into the node's options
+    pub index: usize,
+    pub text: String,
+    pub typed: bool,
+    /// Why it can't be taken yet, if it can't
+    pub locked: Option<String>,
+}
+
+/// A typing check in progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypingCheck {
+    pub option: usize,
+    pub prompt: String,
+    pub typed: String,
+}
+
+/// A conversation being had
+#[derive(Debug, Clone)]
+pub struct DialogueRunner {
+    pub tree: DialogueTree,
+    pub current: String,
+    pub visited: Vec<String>,
+    /// Everything said so far: (speaker, line)
+    pub transcript: Vec<(String, String)>,
+    /// Clues heard so far
+    pub revealed: Vec<String>,
+    pub typing: Option<TypingCheck>,
+    pub ended: bool,
+}
+
+impl DialogueRunner {
+    pub fn new(tree: DialogueTree) -> Self {
+        let start = tree.start.clone();
+        let mut runner = Self {
+            tree,
+            current: String::new(),
+            visited: Vec::new(),
+            transcript: Vec::new(),
+            revealed: Vec::new(),
+            typing: None,
+            ended: false,
+        };
+        runner.goto(&start);
+        runner
+    }
+
+    pub fn node(&self) -> Option<&DialogueNode> {
+        self.tree.nodes.get(&self.current)
+    }
+
+    fn goto(&mut self, id: &str) {
+        let Some(node) = self.tree.nodes.get(id) else {
+            self.ended = true;
+            return;
+        };
+        self.current = id.to_string();
+        self.visited.push(id.to_string());
+        self.transcript.push((node.speaker.clone(), node.text.clone()));
+        if let Some(clue) = &node.reveals {
+            if !self.revealed.contains(clue) {
+                self.revealed.push(clue.clone());
+            }
+        }
+    }
+
+    fn met(&self, condition: &DialogueCondition, gate: &DialogueGate) -> bool {
+        match condition {
+            DialogueCondition::Reputation { faction, min } => {
+                Faction::from_id(faction).is_some_and(|f| gate.factions.standing(&f) >= *min)
+            }
+            DialogueCondition::Flag(flag) => gate.world.has_flag(flag),
+            DialogueCondition::NotFlag(flag) => !gate.world.has_flag(flag),
+            DialogueCondition::Lore(id) => gate.lore.contains(id),
+            DialogueCondition::Visited(id) => self.visited.contains(id),
+            DialogueCondition::NotVisited(id) => !self.visited.contains(id),
+            DialogueCondition::Revealed(level) => gate.revelation.allows(*level),
+            DialogueCondition::Opinion { npc, min } => gate.npcs.opinion(npc) >= *min,
+            DialogueCondition::Script(condition) => condition.holds(gate),
+        }
+    }
+
+    /// The current node's options, less those hidden
+    pub fn options(&self, gate: &DialogueGate) -> Vec<OptionView> {
+        let Some(node) = self.node() else {
+            return Vec::new();
+        };
+        node.options.iter().enumerate()
+            .filter(|(_, o)| o.conditions.iter().all(|c| !c.hides() || self.met(c, gate)))
+            .map(|(index, o)| OptionView {
+                index,
+                text: o.text.clone(),
+                typed: matches!(o.check, Some(DialogueCheck::Typing(_))),
+                locked: o.conditions.iter().find(|c| !self.met(c, gate)).map(|c| c.describe()),
+            })
+            .collect()
+    }
+
+    /// Move on from a node without options
+    pub fn advance(&mut self) {
+        if self.typing.is_some() || self.node().is_some_and(|n| !n.options.is_empty()) {
+            return;
+        }
+        match self.node().and_then(|n| n.next.clone()) {
+            Some(next) => self.goto(&next),
+            None => self.ended = true,
+        }
+    }
+
+    /// Pick the `choice`th visible option
+    pub fn choose(&mut self, choice: usize, gate: &DialogueGate) {
+        let Some(view) = self.options(gate).into_iter().nth(choice).filter(|v| v.locked.is_none()) else {
+            return;
+        };
+        let Some(option) = self.node().map(|n| n.options[view.index].clone()) else {
+            return;
+        };
+        self.transcript.push(("You".to_string(), option.text.clone()));
+        match &option.check {
+            None => self.goto(&option.goto),
+            Some(DialogueCheck::Wpm(min)) => {
+                if gate.best_wpm >= *min {
+                    self.goto(&option.goto);
+                } else {
+                    self.fail(&option);
+                }
+            }
+            Some(DialogueCheck::Typing(prompt)) => {
+                self.typing = Some(TypingCheck { option: view.index, prompt: prompt.clone(), typed: String::new() });
+            }
+        }
+    }
+
+    fn fail(&mut self, option: &DialogueOption) {
+        let back = option.on_fail.clone().unwrap_or_else(|| self.current.clone());
+        self.goto(&back);
+    }
+
+    /// Grade a typing check and follow where it leads
+    pub fn submit_typing(&mut self) -> Option<TypingGrade> {
+        let check = self.typing.take()?;
+        let option = self.node()?.options.get(check.option)?.clone();
+        let grade = TypingGrade::of(accuracy(&check.prompt, &check.typed));
+        if grade.carries() {
+            self.goto(&option.goto);
+        } else {
+            self.fail(&option);
+        }
+        Some(grade)
+    }
+
+    /// Give up on a typing check and choose again
+    pub fn cancel_typing(&mut self) {
+        if self.typing.take().is_some() {
+            self.transcript.pop();
+        }
+    }
+}
+
+/// The conversation an encounter holds: its authored tree, or its flat
+/// lines run straight through
+pub fn conversation_for(encounter: &AuthoredEncounter) -> Option<DialogueTree> {
+    if let Some(tree) = authored_trees().remove(encounter.id.as_str()) {
+        return Some(tree);
+    }
+    encounter.content.dialogue.as_ref().filter(|l| !l.is_empty()).map(|l| DialogueTree::linear(l))
+}
+
+/// Conversations written as trees
+pub fn authored_trees() -> HashMap<&'static str, DialogueTree> {
+    use DialogueCondition::*;
+    let mut trees = HashMap::new();
+
+    let tech = "Mechanist Technician";
+    trees.insert("mechanist_breakdown", DialogueTree::new("doubt")
+        .node("doubt", DialogueNode::say(tech, "The elders keep saying we just need better designs. More \
+            precise mechanisms. But I've seen the truth. The Corruption isn't in the words. It's in meaning itself.")
+            .reveals("Some Mechanists are losing faith in their doctrine.")
+            .then("despair"))
+        .node("despair", DialogueNode::say(tech, "What if the Naturalists are right? What if we can't engineer \
+            our way out of this? What if the only answer is to... let it happen?")
+            .then("hub"))
+        .node("hub", DialogueNode::say(tech, "Sorry. You didn't come here to watch me fall apart. What is it?")
+            .option(DialogueOption::to("What's your name?", "name").when(NotVisited("name".into())))
+            .option(DialogueOption::to("The Works will listen if I stand with you.", "standing")
+                .when(Reputation { faction: "Mechanists".into(), min: 20 })
+                .when(NotVisited("standing".into())))
+            .option(DialogueOption::to("Let me try that word-processor.", "repaired")
+                .when(NotVisited("repaired".into()))
+                .checked(DialogueCheck::Typing("gears turn true when the words run clean".into()), "jammed"))
+            .option(DialogueOption::to("Let's talk about what comes next.", "next_steps")))
+        .node("name", DialogueNode::say(tech, "Kaya. Technician Kaya, second gear of the Lower Works. Not that \
+            the title means much now.")
+            .then("hub"))
+        .node("standing", DialogueNode::say(tech, "You'd do that? They'd hear it from you. Maybe they'd even \
+            hear it from me, after.")
+            .reveals("Kaya would take her doubts to the elders if someone stood with her.")
+            .then("hub"))
+        .node("repaired", DialogueNode::say(tech, "The keys unstick. One clean line prints, then another. Kaya \
+            stares at it like it's a letter from someone she thought was dead.")
+            .reveals("A corrupted machine can be typed back into order.")
+            .then("hub"))
+        .node("jammed", DialogueNode::say(tech, "The keys lock under your fingers and the carriage slams home. \
+            'See? It won't even let you try.'")
+            .then("hub"))
+        .node("next_steps", DialogueNode::say(tech, "Next. Right. There's always a next, isn't there?")));
+
+    let vera = "Old Scribe";
+    trees.insert("haven_old_scribe", DialogueTree::new("quickfingers")
+        .node("quickfingers", DialogueNode::say(vera, "They called me Vera Quickfingers in the old days. I could \
+            type a hundred words a minute, all true. Now I'm just Vera. The Corruption took my speed. Left me \
+            with only accuracy.")
+            .reveals("Some scribes survived the Unwriting but lost abilities.")
+            .then("hub"))
+        .node("hub", DialogueNode::say(vera, "Go on, then. Ask. My ears are better than my eyes.")
+            .option(DialogueOption::to("Show me how fast you were.", "match")
+                .when(NotVisited("match".into()))
+                .when(NotVisited("short".into()))
+                .checked(DialogueCheck::Wpm(60.0), "short"))
+            .option(DialogueOption::to("Why shouldn't I trust the Archivists?", "archivists")
+                .when(Opinion { npc: "Vera".into(), min: COLD_OPINION + 1 })
+                .when(NotVisited("archivists".into())))
+            .option(DialogueOption::to("Teach me to type true, the way you did.", "lesson")
+                .when(Opinion { npc: "Vera".into(), min: WARM_OPINION })
+                .when(NotVisited("lesson".into())))
+            .option(DialogueOption::to("I wear the Scribes' mark too.", "guild")
+                .when(Reputation { faction: "Scribes".into(), min: 10 })
+                .when(NotVisited("guild".into())))
+            .option(DialogueOption::to("I should go.", "farewell")))
+        .node("match", DialogueNode::say(vera, "Ha! Listen to that. Closer than anyone's come in years. Keep \
+            those hands honest and they'll keep you alive.")
+            .then("hub"))
+        .node("short", DialogueNode::say(vera, "Not yet. You're rushing the easy words and stalling on the hard \
+            ones. Slow down to speed up, young one.")
+            .then("hub"))
+        .node("archivists", DialogueNode::say(vera, "Want some advice, young one? Don't trust the Archivists. \
+            They know more than they tell. They were watching before the First Silence, and they're watching now.")
+            .reveals("The Archivists have been observing since before the Unwriting.")
+            .then("hub"))
+        .node("guild", DialogueNode::say(vera, "Her fingers find your wrist and trace the mark. 'Still a Scribe, \
+            after all this. Then hear this: the Restricted Section has a door with no keyhole. Type its name \
+            and it opens.'")
+            .reveals("The Restricted Section opens to a typed name.")
+            .then("hub"))
+        .node("lesson", DialogueNode::say(vera, "She sets your hands on the keys, one finger at a time. 'Never \
+            look for the next letter. Listen for it. The true word is already on its way to you.'")
+            .reveals("Vera types by listening for the next letter, not looking for it.")
+            .then("hub"))
+        .node("farewell", DialogueNode::say(vera, "Mind your pauses.")));
+
+    let voice = "Voice in the Dark";
+    trees.insert("shadowwriter_offer", DialogueTree::new("offer")
+        .node("offer", DialogueNode::say(voice, "We don't want you to do anything illegal. Nothing that would \
+            hurt anyone who doesn't deserve it. We just... collect information. Important information. \
+            Information the factions hide from each other.")
+            .reveals("The Shadow Writers spy on other factions.")
+            .then("hub"))
+        .node("hub", DialogueNode::say(voice, "Well? The dark is patient. I am less so.")
+            .option(DialogueOption::to("What could you possibly know about me?", "proof")
+                .when(NotVisited("proof".into())))
+            .option(DialogueOption::to("The Living Book told me about you.", "book")
+                .when(Flag("living_book_awakened".into()))
+                .when(NotVisited("book".into())))
+            .option(DialogueOption::to("Read the card before its text moves.", "card")
+                .when(NotVisited("card".into()))
+                .checked(DialogueCheck::Typing("some truths are better left unwritten".into()), "card_slips"))
+            .option(DialogueOption::to("Someone is calling the Void. You know who.", "calling")
+                .when(Revealed(Revelation::Deeper))
+                .when(NotVisited("calling".into())))
+            .option(DialogueOption::to("Enough riddles.", "enough")))
+        .node("calling", DialogueNode::say(voice, "A pause, long enough to be an answer. 'We know it answers. \
+            We don't yet know to whom. Work with us, and you'll be the first we tell.'")
+            .reveals("The Shadow Writers are hunting whoever calls the Void.")
+            .then("hub"))
+        .node("proof", DialogueNode::say(voice, "In exchange, we share what we know. And we know a great deal. \
+            About the Unwriting. About the First Speaker. About you.")
+            .reveals("The Shadow Writers know about the player's past.")
+            .then("hub"))
+        .node("book", DialogueNode::say(voice, "That old gossip. Then you know we aren't the worst thing in the \
+            stacks. Not by a long chapter.")
+            .reveals("The Shadow Writers and the Living Book trade secrets.")
+            .then("hub"))
+        .node("card", DialogueNode::say(voice, "The letters hold still under your eyes, just long enough. The \
+            card reads CIPHER. 'Clever. Keep it.'")
+            .reveals("The voice in the dark calls itself Cipher.")
+            .then("hub"))
+        .node("card_slips", DialogueNode::say(voice, "The silver text slides off the edge of the card. A soft \
+            laugh from the alley. 'Not many can.'")
+            .then("hub"))
+        .node("enough", DialogueNode::say(voice, "Riddles are all anyone has left. Choose.")));
+
+    trees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    #[test]
+    fn test_every_encounter_conversation_is_whole() {
+        let encounters = build_encounters();
+        for tree in authored_trees().values() {
+            assert!(tree.dangling().is_empty(), "dangling nodes {:?}", tree.dangling());
+        }
+        for encounter in encounters.values().filter(|e| e.content.dialogue.is_some()) {
+            let tree = conversation_for(encounter).unwrap();
+            assert!(tree.dangling().is_empty(), "{} has dangling nodes", encounter.id);
+        }
+        // A flat conversation runs straight through to its end
+        let mut runner = DialogueRunner::new(conversation_for(&encounters["first_archivist_meeting"]).unwrap());
+        while !runner.ended {
+            runner.advance();
+        }
+        assert_eq!(runner.transcript.len(), 3);
+        assert_eq!(runner.revealed.len(), 3);
+    }
+
+    #[test]
+    fn test_gates_checks_and_loops() {
+        let strangers = FactionRelations::new();
+        let npcs = NpcMemory::new();
+        let tracker = EncounterTracker::new();
+        let world = WorldState::default();
+        let slow = DialogueGate { factions: &strangers, world: &world, lore: &[], best_wpm: 30.0, revelation: RevelationState::new(), npcs: &npcs, floor: 1, tracker: &tracker };
+        let mut runner = DialogueRunner::new(authored_trees().remove("haven_old_scribe").unwrap());
+        runner.advance();
+        assert_eq!(runner.current, "hub");
+
+        // The guild option shows, locked, until the Scribes think well of you
+        let options = runner.options(&slow);
+        assert!(options[2].locked.is_some());
+        runner.choose(2, &slow);
+        assert_eq!(runner.current, "hub");
+
+        // A failed skill check leads elsewhere, then loops back to the hub
+        runner.choose(0, &slow);
+        assert_eq!(runner.current, "short");
+        runner.advance();
+        assert_eq!(runner.current, "hub");
+        assert_eq!(runner.options(&slow).len(), 3, "the speed question is asked only once");
+
+        let mut factions = FactionRelations::new();
+        factions.modify_standing(Faction::from_id("Scribes").unwrap(), 20);
+        let trusted = DialogueGate { factions: &factions, world: &world, lore: &[], best_wpm: 30.0, revelation: RevelationState::new(), npcs: &npcs, floor: 1, tracker: &tracker };
+        runner.choose(1, &trusted);
+        assert_eq!(runner.current, "guild");
+
+        // Typing checks grade what was typed
+        let mut runner = DialogueRunner::new(authored_trees().remove("mechanist_breakdown").unwrap());
+        runner.advance();
+        runner.advance();
+        let repair = runner.options(&trusted).iter().position(|o| o.typed).unwrap();
+        runner.choose(repair, &trusted);
+        let check = runner.typing.as_mut().unwrap();
+        check.typed = check.prompt.clone();
+        assert_eq!(runner.submit_typing(), Some(TypingGrade::Success));
+        assert_eq!(runner.current, "repaired");
+
+        // Vera refuses a topic once she has turned against you
+        let mut cold = NpcMemory::new();
+        cold.on_choice(&build_encounters()["first_archivist_meeting"], "ask_third_grammar", true);
+        let gate = DialogueGate { factions: &strangers, world: &world, lore: &[], best_wpm: 30.0, revelation: RevelationState::new(), npcs: &cold, floor: 1, tracker: &tracker };
+        let mut runner = DialogueRunner::new(authored_trees().remove("haven_old_scribe").unwrap());
+        runner.advance();
+        assert!(runner.options(&gate).iter().any(|o| o.text.contains("Archivists") && o.locked.is_some()));
+    }
+}
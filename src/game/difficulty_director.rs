@@ -0,0 +1,219 @@
+//! Difficulty Director - Keeping every typist on the edge
+//!
+//! Watches rolling typing performance across recent fights and nudges:
+//! - Enemy HP scaling (applied in `Enemy::from_template`)
+//! - Word length selection (via `LoreWords::random_word_in_range`)
+//! - Time pressure on each prompt
+//!
+//! Design: a 30 WPM player and a 120 WPM player should both feel pushed, never crushed
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use super::config::DifficultyConfig;
+
+/// Number of recent fights the director remembers
+pub const ROLLING_WINDOW: usize = 5;
+
+/// WPM considered "par" - a rating of 1.0
+pub const BASELINE_WPM: f32 = 50.0;
+
+/// Accuracy considered "par" - below this the rating is pulled down
+pub const BASELINE_ACCURACY: f32 = 0.95;
+
+/// Typing performance recorded at the end of a single fight
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FightSample {
+    /// Average WPM over the fight
+    pub wpm: f32,
+    /// Character accuracy (0.0 - 1.0)
+    pub accuracy: f32,
+}
+
+/// Tracks rolling performance and derives difficulty adjustments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultyDirector {
+    /// Whether adjustments are applied at all
+    pub enabled: bool,
+    /// Most recent fights, oldest first
+    samples: VecDeque<FightSample>,
+}
+
+impl Default for DifficultyDirector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DifficultyDirector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            samples: VecDeque::with_capacity(ROLLING_WINDOW),
+        }
+    }
+
+    /// Create a director honoring the difficulty preset's adaptive flag
+    pub fn from_config(config: &DifficultyConfig) -> Self {
+        Self {
+            enabled: config.adaptive_difficulty,
+            ..Self::new()
+        }
+    }
+
+    /// Record the outcome of a fight. Fights with no measurable WPM are ignored.
+    pub fn record_fight(&mut self, wpm: f32, accuracy: f32) {
+        if wpm <= 0.0 || !wpm.is_finite() {
+            return;
+        }
+        if self.samples.len() >= ROLLING_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(FightSample {
+            wpm,
+            accuracy: accuracy.clamp(0.0, 1.0),
+        });
+    }
+
+    /// Number of fights currently in the rolling window
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Rolling average WPM, if any fights have been recorded
+    pub fn rolling_wpm(&self) -> Option<f32> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().map(|s| s.wpm).sum::<f32>() / self.samples.len() as f32)
+    }
+
+    /// Rolling average accuracy, if any fights have been recorded
+    pub fn rolling_accuracy(&self) -> Option<f32> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().map(|s| s.accuracy).sum::<f32>() / self.samples.len() as f32)
+    }
+
+    /// Player skill relative to par (1.0). Sloppy typing drags the rating down
+    /// so speed bought with mistakes isn't rewarded with harder fights.
+    pub fn skill_rating(&self) -> f32 {
+        if !self.enabled {
+            return 1.0;
+        }
+        let (Some(wpm), Some(accuracy)) = (self.rolling_wpm(), self.rolling_accuracy()) else {
+            return 1.0;
+        };
+        let accuracy_factor = (accuracy / BASELINE_ACCURACY).clamp(0.5, 1.05);
+        (wpm / BASELINE_WPM * accuracy_factor).clamp(0.5, 2.5)
+    }
+
+    /// Multiplier applied to enemy HP
+    pub fn enemy_hp_mult(&self) -> f32 {
+        (1.0 + (self.skill_rating() - 1.0) * 0.4).clamp(0.7, 1.6)
+    }
+
+    /// Multiplier applied to prompt time limits (lower = more pressure)
+    pub fn time_mult(&self) -> f32 {
+        (1.0 / self.skill_rating().sqrt()).clamp(0.7, 1.4)
+    }
+
+    /// Preferred word length range (inclusive) for single-word prompts
+    pub fn word_length_range(&self) -> (usize, usize) {
+        match self.skill_rating() {
+            r if r < 0.8 => (3, 7),
+            r if r < 1.2 => (3, 10),
+            r if r < 1.8 => (5, 12),
+            _ => (7, usize::MAX),
+        }
+    }
+
+    /// Short label for the stats screen
+    pub fn describe(&self) -> &'static str {
+        if !self.enabled || self.samples.is_empty() {
+            return "Calibrating";
+        }
+        match self.skill_rating() {
+            r if r < 0.8 => "Easing off",
+            r if r < 1.2 => "Steady",
+            r if r < 1.8 => "Pressing",
+            _ => "Relentless",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neutral_without_samples() {
+        let director = DifficultyDirector::new();
+        assert_eq!(director.skill_rating(), 1.0);
+        assert_eq!(director.enemy_hp_mult(), 1.0);
+        assert_eq!(director.time_mult(), 1.0);
+    }
+
+    #[test]
+    fn test_fast_typist_gets_harder_fights() {
+        let mut director = DifficultyDirector::new();
+        for _ in 0..ROLLING_WINDOW {
+            director.record_fight(120.0, 0.98);
+        }
+        assert!(director.enemy_hp_mult() > 1.0);
+        assert!(director.time_mult() < 1.0);
+        assert!(director.word_length_range().0 > 3);
+    }
+
+    #[test]
+    fn test_slow_typist_gets_breathing_room() {
+        let mut director = DifficultyDirector::new();
+        for _ in 0..ROLLING_WINDOW {
+            director.record_fight(30.0, 0.9);
+        }
+        assert!(director.enemy_hp_mult() < 1.0);
+        assert!(director.time_mult() > 1.0);
+    }
+
+    #[test]
+    fn test_window_rolls_over() {
+        let mut director = DifficultyDirector::new();
+        for _ in 0..ROLLING_WINDOW {
+            director.record_fight(30.0, 1.0);
+        }
+        for _ in 0..ROLLING_WINDOW {
+            director.record_fight(100.0, 1.0);
+        }
+        assert_eq!(director.sample_count(), ROLLING_WINDOW);
+        assert_eq!(director.rolling_wpm(), Some(100.0));
+    }
+
+    #[test]
+    fn test_disabled_director_is_neutral() {
+        let mut director = DifficultyDirector::new();
+        director.enabled = false;
+        director.record_fight(150.0, 1.0);
+        assert_eq!(director.enemy_hp_mult(), 1.0);
+    }
+
+    #[test]
+    fn test_a_preset_without_adaptive_difficulty_leaves_fights_alone() {
+        use crate::data::GameData;
+        use crate::game::combat::CombatState;
+        use crate::game::config::DifficultyPreset;
+        use crate::game::enemy::Enemy;
+        use std::sync::Arc;
+
+        let mut director = DifficultyDirector::from_config(&DifficultyConfig::from_preset(DifficultyPreset::Hard));
+        assert!(!director.enabled);
+        for _ in 0..ROLLING_WINDOW {
+            director.record_fight(120.0, 0.98);
+        }
+        let mut combat = CombatState::new(Enemy::random_for_floor(2), Arc::new(GameData::new()), 1, 2, None, None);
+        let (word, time_limit) = (combat.current_word.clone(), combat.time_limit);
+        combat.apply_director(&director);
+        assert_eq!((combat.time_mult, combat.word_length_range), (1.0, None));
+        assert_eq!((combat.current_word, combat.time_limit), (word, time_limit));
+    }
+}
@@ -0,0 +1,285 @@
+//! Dreams - What you see when you sleep at a rest site
+//!
+//! Resting at a campfire is sleeping, and sleep can bring a dream: a short,
+//! surreal vignette ending in words you type half-awake. Dreams draw on
+//! their own word pools, stranger than the zone's. Type the dream back
+//! cleanly (or well enough) and you wake remembering a clue; fumble it and
+//! the dream slips away.
+//!
+//! One dream keeps coming back. The Recurring Dream has a version for each
+//! chapter of the descent (two floors to a chapter), each closer to seeing
+//! who is calling your name:
+//! - Chapter 1: a keyboard in the dark, a voice far off
+//! - Chapters 2-4: the voice draws nearer, the words keep changing
+//! - Chapter 5: the dream completes
+//!
+//! The first time you sleep in a new chapter it is always the Recurring
+//! Dream, until you have typed that chapter's version through. Other
+//! nights you may dream something else, or nothing at all.
+//!
+//! Design: dreams are static data; the DreamLog keeps the run's progress
+//! through them and the clues they gave, and a DreamRun plays one out the
+//! way an EncounterRun plays an encounter, graded the same way.
+
+use rand::prelude::*;
+
+use super::encounter_runner::{accuracy, TypingGrade};
+
+/// Chance a night's sleep brings a dream, once the Recurring Dream is done
+/// for the chapter
+pub const DREAM_CHANCE: f32 = 0.4;
+/// Words typed in a first-chapter dream; one more for each chapter after
+pub const DREAM_WORDS: usize = 4;
+
+/// A dream vignette
+#[derive(Debug)]
+pub struct Dream {
+    pub id: &'static str,
+    pub title: &'static str,
+    /// Earliest chapter it can come in
+    pub chapter: u32,
+    pub scene: &'static [&'static str],
+    /// Words the dream asks to be typed, drawn at random
+    pub words: &'static [&'static str],
+    /// What you remember on waking
+    pub clue: &'static str,
+}
+
+/// The Recurring Dream, one version per chapter
+pub static RECURRING_DREAM: [Dream; 5] = [
+    Dream {
+        id: "recurring_1",
+        title: "The Recurring Dream",
+        chapter: 1,
+        scene: &[
+            "A keyboard in the dark. You know its weight before you see it. Your fingers are already on the home row.",
+            "Somewhere far off, someone is calling a name. It might be yours.",
+        ],
+        words: &["home", "row", "dark", "keys", "far", "name", "calling", "waiting"],
+        clue: "Someone, somewhere, is calling a name you almost know.",
+    },
+    Dream {
+        id: "recurring_2",
+        title: "The Recurring Dream",
+        chapter: 2,
+        scene: &[
+            "The keyboard again. This time the keys are warm, as if someone has just stood up from them.",
+            "The voice is nearer. It says the name twice, patiently, the way you repeat a word to a child.",
+        ],
+        words: &["warm", "keys", "again", "nearer", "twice", "patient", "listen", "return"],
+        clue: "Whoever calls has called before. They are patient, and they are getting closer.",
+    },
+    Dream {
+        id: "recurring_3",
+        title: "The Recurring Dream",
+        chapter: 3,
+        scene: &[
+            "You type, and the words change under your fingers. You write 'forget' and the screen says 'forty-seven'.",
+            "The voice is in the room now. You cannot turn your head. You never can.",
+        ],
+        words: &["forget", "forty", "seven", "shelf", "again", "turn", "cannot", "room"],
+        clue: "The dream keeps writing 'forty-seven' where you meant to write 'forget'.",
+    },
+    Dream {
+        id: "recurring_4",
+        title: "The Recurring Dream",
+        chapter: 4,
+        scene: &[
+            "The voice is behind your chair. A hand rests on your shoulder, light as paper.",
+            "'You wrote this,' it says, and you see that every key bears a letter of the same name.",
+        ],
+        words: &["shoulder", "paper", "wrote", "every", "letter", "same", "behind", "almost"],
+        clue: "Every key of the dream's keyboard bears a letter of one name. You wrote it.",
+    },
+    Dream {
+        id: "recurring_5",
+        title: "The Recurring Dream",
+        chapter: 5,
+        scene: &[
+            "You turn. For the first time, you turn.",
+            "The face is your own, older, ink on its fingers. It has been calling you back to the keyboard for forty-seven lives. The dream completes. You remember.",
+        ],
+        words: &["remember", "yourself", "forty", "seven", "lives", "unwritten", "author", "complete"],
+        clue: "The one calling your name was you: the First Speaker, forty-seven lives ago.",
+    },
+];
+
+/// Dreams that come now and then
+pub static VIGNETTES: [Dream; 4] = [
+    Dream {
+        id: "library_of_rain",
+        title: "The Library of Rain",
+        chapter: 1,
+        scene: &[
+            "It rains indoors. Every drop is a letter, and the letters pool on the reading tables into sentences no one finished.",
+        ],
+        words: &["rain", "drop", "pool", "ink", "unfinished", "sentence", "table", "drown"],
+        clue: "The Sunken Archives flooded from the inside. The water was words.",
+    },
+    Dream {
+        id: "clockwork_lullaby",
+        title: "A Clockwork Lullaby",
+        chapter: 2,
+        scene: &[
+            "A music box plays a tune you have never heard and can hum perfectly. Its comb is missing one tooth, always the same one.",
+        ],
+        words: &["gear", "comb", "tooth", "hum", "tick", "lullaby", "spring", "missing"],
+        clue: "The Mechanists built their first machine to keep a song from being unwritten.",
+    },
+    Dream {
+        id: "garden_of_tongues",
+        title: "The Garden of Tongues",
+        chapter: 3,
+        scene: &[
+            "Flowers open like mouths. Each one says a single word and wilts. The gardener is weeping, and planting more.",
+        ],
+        words: &["petal", "mouth", "wilt", "seed", "whisper", "bloom", "weep", "root"],
+        clue: "The Blighted Gardens were planted to grow back the words the Unwriting took.",
+    },
+    Dream {
+        id: "dreamer_beneath",
+        title: "Beneath the Mountain",
+        chapter: 4,
+        scene: &[
+            "You walk down into the mountain. Something enormous breathes in the dark, slow as tides. It is asleep. You are what it is dreaming.",
+        ],
+        words: &["mountain", "breathe", "tide", "asleep", "dreamer", "enormous", "stir", "hush"],
+        clue: "The Dreamer sleeps beneath the mountain. The Breach is its eye, opening.",
+    },
+];
+
+/// Chapter of the descent `floor` lies in: two floors to a chapter, the
+/// last one running on past the final floor
+pub fn chapter_of(floor: i32) -> u32 {
+    (floor.max(1) as u32).div_ceil(2).min(RECURRING_DREAM.len() as u32)
+}
+
+impl Dream {
+    /// Draw the words to type from the dream's pool
+    pub fn prompt(&self, rng: &mut impl Rng) -> String {
+        let count = DREAM_WORDS + self.chapter.saturating_sub(1) as usize;
+        (0..count).filter_map(|_| self.words.choose(rng).copied()).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// The run's progress through its dreams
+#[derive(Debug, Clone, Default)]
+pub struct DreamLog {
+    /// Versions of the Recurring Dream typed through
+    pub recurring: usize,
+    /// Vignettes dreamt
+    seen: Vec<&'static str>,
+    /// What the dreams have told you
+    pub clues: Vec<String>,
+}
+
+impl DreamLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// What a night's sleep in `chapter` brings, if anything
+    pub fn next_dream(&self, chapter: u32, rng: &mut impl Rng) -> Option<&'static Dream> {
+        if let Some(dream) = RECURRING_DREAM.get(self.recurring).filter(|d| d.chapter <= chapter) {
+            return Some(dream);
+        }
+        if rng.gen::<f32>() >= DREAM_CHANCE {
+            return None;
+        }
+        let fresh: Vec<&'static Dream> = VIGNETTES.iter()
+            .filter(|d| d.chapter <= chapter && !self.seen.contains(&d.id))
+            .collect();
+        fresh.choose(rng).copied()
+    }
+
+    /// Remember how `dream` went
+    pub fn wake(&mut self, dream: &'static Dream, grade: TypingGrade) {
+        if !self.seen.contains(&dream.id) {
+            self.seen.push(dream.id);
+        }
+        if !grade.carries() {
+            return;
+        }
+        if RECURRING_DREAM.get(self.recurring).is_some_and(|d| d.id == dream.id) {
+            self.recurring += 1;
+        }
+        self.clues.push(dream.clue.to_string());
+    }
+}
+
+/// Where the dream has got to
+#[derive(Debug, Clone, PartialEq)]
+pub enum DreamStage {
+    Dreaming,
+    Typing { prompt: String, typed: String },
+    Waking { grade: TypingGrade },
+}
+
+/// A dream being dreamt
+#[derive(Debug, Clone)]
+pub struct DreamRun {
+    pub dream: &'static Dream,
+    pub stage: DreamStage,
+}
+
+impl DreamRun {
+    pub fn new(dream: &'static Dream) -> Self {
+        Self { dream, stage: DreamStage::Dreaming }
+    }
+
+    /// Move from the scene to its words
+    pub fn begin_typing(&mut self, rng: &mut impl Rng) {
+        if self.stage == DreamStage::Dreaming {
+            self.stage = DreamStage::Typing { prompt: self.dream.prompt(rng), typed: String::new() };
+        }
+    }
+
+    /// Grade the typing and wake; returns how it went
+    pub fn wake(&mut self) -> Option<TypingGrade> {
+        let DreamStage::Typing { prompt, typed } = &self.stage else {
+            return None;
+        };
+        let grade = TypingGrade::of(accuracy(prompt, typed));
+        self.stage = DreamStage::Waking { grade };
+        Some(grade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recurring_dream_escalates_by_chapter() {
+        let mut rng = rand::thread_rng();
+        let mut log = DreamLog::new();
+        assert_eq!(chapter_of(1), 1);
+        assert_eq!(chapter_of(4), 2);
+        assert_eq!(chapter_of(30), 5);
+
+        assert_eq!(log.next_dream(1, &mut rng).map(|d| d.id), Some("recurring_1"));
+        // A fumbled dream comes back
+        log.wake(&RECURRING_DREAM[0], TypingGrade::Failure);
+        assert_eq!(log.next_dream(1, &mut rng).map(|d| d.id), Some("recurring_1"));
+        log.wake(&RECURRING_DREAM[0], TypingGrade::Success);
+        assert_eq!(log.clues.len(), 1);
+        // The next version waits for the next chapter
+        assert!(log.next_dream(1, &mut rng).is_none_or(|d| d.id != "recurring_2"));
+        assert_eq!(log.next_dream(2, &mut rng).map(|d| d.id), Some("recurring_2"));
+    }
+
+    #[test]
+    fn test_dream_run_grades_its_words() {
+        let mut rng = rand::thread_rng();
+        let mut run = DreamRun::new(&RECURRING_DREAM[2]);
+        assert_eq!(run.wake(), None);
+        run.begin_typing(&mut rng);
+        let DreamStage::Typing { prompt, typed } = &mut run.stage else {
+            panic!("expected typing stage");
+        };
+        assert_eq!(prompt.split(' ').count(), DREAM_WORDS + 2);
+        assert!(prompt.split(' ').all(|w| RECURRING_DREAM[2].words.contains(&w)));
+        *typed = prompt.clone();
+        assert_eq!(run.wake(), Some(TypingGrade::Success));
+    }
+}
@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 use rand::Rng;
 use super::enemy::Enemy;
 use super::items::Item;
+use super::map_travel::{RoomCoord, VisitedRoom};
+use super::run_map::{reveal_mystery, FloorMap};
+use super::prophecies::Prophecy;
+use super::rumors::Rumor;
+use super::run_rng::{self, Stream};
 use super::world_integration::{FloorZone, get_ambient_message, get_zone_entry_message, get_floor_lore};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +28,27 @@ pub struct Dungeon {
     pub zone_message: Option<String>,
     /// Pending lore discovery
     pub pending_lore: Option<(String, String)>,
+    /// Every room entered this run, with any name the player gave it
+    #[serde(default)]
+    pub visited: Vec<VisitedRoom>,
+    /// Where the player is standing on the map
+    #[serde(default)]
+    pub here: Option<RoomCoord>,
+    /// Name typed for the room ahead, given to it on entry
+    #[serde(default)]
+    pub next_room_name: Option<String>,
+    /// This floor's rooms and the paths between them
+    #[serde(default)]
+    pub floor_map: FloorMap,
+    /// Which of the rooms ahead the player has picked
+    #[serde(default)]
+    pub route_choice: usize,
+    /// What the player has heard about the floors ahead
+    #[serde(default)]
+    pub rumors: Vec<Rumor>,
+    /// Tomorrow Texts found this run
+    #[serde(default)]
+    pub prophecies: Vec<Prophecy>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,12 +68,14 @@ pub enum RoomType {
     Shop,
     Event,
     Start,
+    /// Unknown until entered (see `run_map::reveal_mystery`)
+    Mystery,
 }
 
 impl Dungeon {
     pub fn new() -> Self {
         let zone = FloorZone::from_floor(1);
-        Self {
+        let mut dungeon = Self {
             name: "The Infinite Archives".to_string(),
             current_floor: 1,
             max_floor: 100, // Infinite-ish
@@ -67,26 +95,36 @@ impl Dungeon {
             zone_name: zone.name().to_string(),
             zone_message: None,
             pending_lore: None,
-        }
+            visited: Vec::new(),
+            here: None,
+            next_room_name: None,
+            floor_map: FloorMap::default(),
+            route_choice: 0,
+            rumors: Vec::new(),
+            prophecies: Vec::new(),
+        };
+        dungeon.floor_map = dungeon.new_floor_map();
+        dungeon.record_visit(RoomCoord::new(1, 0), RoomType::Start);
+        dungeon
     }
 
+    /// Step into the room picked on the map
     pub fn generate_next_room(&mut self) -> Room {
-        let mut rng = rand::thread_rng();
-        
-        // Check for boss room (only once per floor, floors 5 and 10)
-        if self.rooms_cleared >= self.rooms_per_floor - 1 
-            && self.current_floor % 5 == 0 
-            && !self.boss_defeated 
-        {
-            return Room {
-                room_type: RoomType::Boss,
-                cleared: false,
-                description: self.get_boss_room_description(),
-            };
+        let room = self.roll_next_room();
+        self.record_visit(self.next_coord(), room.room_type);
+        self.current_room = room.clone();
+        room
+    }
+
+    fn roll_next_room(&mut self) -> Room {
+        let mut rng = run_rng::stream(Stream::Map);
+        if self.floor_map.rows.is_empty() {
+            self.floor_map = self.new_floor_map();
         }
         
         // Check for floor complete (or final victory on floor 10)
-        if self.rooms_cleared >= self.rooms_per_floor || (self.boss_defeated && self.current_floor >= 10) {
+        let row = self.rooms_cleared.max(0) as usize;
+        if row >= self.floor_map.depth() || (self.boss_defeated && self.current_floor >= 10) {
             self.floor_complete = true;
             let zone = FloorZone::from_floor(self.current_floor as u32);
             return Room {
@@ -100,24 +138,23 @@ impl Dungeon {
             };
         }
         
+        let col = self.chosen_route().unwrap_or(0);
+        self.route_choice = 0;
+        let mut room_type = self.floor_map.enter(row, col).unwrap_or(RoomType::Combat);
+        if room_type == RoomType::Boss {
+            return Room {
+                room_type,
+                cleared: false,
+                description: self.get_boss_room_description(),
+            };
+        }
+        
         // Check for lore discovery (15% chance per room)
         self.pending_lore = get_floor_lore(self.current_floor as u32);
         
-        // Random room type
-        let roll: f32 = rng.gen();
-        let room_type = if roll < 0.50 {
-            RoomType::Combat
-        } else if roll < 0.65 {
-            RoomType::Event
-        } else if roll < 0.75 {
-            RoomType::Treasure
-        } else if roll < 0.85 {
-            RoomType::Rest
-        } else if roll < 0.92 {
-            RoomType::Shop
-        } else {
-            RoomType::Elite
-        };
+        if room_type == RoomType::Mystery {
+            room_type = reveal_mystery(&mut rng);
+        }
         
         Room {
             room_type,
@@ -126,6 +163,40 @@ impl Dungeon {
         }
     }
 
+    /// A fresh map for the current floor (boss floors end at the boss)
+    fn new_floor_map(&self) -> FloorMap {
+        let boss = self.current_floor % 5 == 0;
+        FloorMap::generate(self.rooms_per_floor.max(1) as usize, boss, &mut run_rng::stream(Stream::Map))
+    }
+
+    /// Columns of the rooms the player can enter next
+    pub fn route_choices(&self) -> Vec<usize> {
+        self.floor_map.choices(self.rooms_cleared.max(0) as usize)
+    }
+
+    /// Column of the room picked to enter next
+    pub fn chosen_route(&self) -> Option<usize> {
+        let choices = self.route_choices();
+        choices.get(self.route_choice).or(choices.first()).copied()
+    }
+
+    /// Move the pick `step` places along the rooms ahead
+    pub fn cycle_route(&mut self, step: i32) {
+        let count = self.route_choices().len() as i32;
+        if count > 0 {
+            self.route_choice = (self.route_choice as i32 + step).rem_euclid(count) as usize;
+        }
+    }
+
+    /// Pick the `n`th room ahead (0-based); false if there is none
+    pub fn pick_route(&mut self, n: usize) -> bool {
+        let valid = n < self.route_choices().len();
+        if valid {
+            self.route_choice = n;
+        }
+        valid
+    }
+
     fn get_room_description(&self, room_type: RoomType) -> String {
         let mut rng = rand::thread_rng();
         // Use ambient messages from world_integration based on current zone
@@ -183,6 +254,7 @@ impl Dungeon {
                 format!("{}\n\n{}", ambient, descriptions[rng.gen_range(0..descriptions.len())])
             }
             RoomType::Start => format!("{}\n\nYour journey begins here.", ambient),
+            RoomType::Mystery => format!("{}\n\nYou cannot tell what waits here.", ambient),
             RoomType::Boss => self.get_boss_room_description(),
         }
     }
@@ -200,6 +272,8 @@ impl Dungeon {
         self.rooms_cleared = 0;
         self.floor_complete = false;
         self.boss_defeated = false;
+        self.floor_map = self.new_floor_map();
+        self.route_choice = 0;
         
         // Check for zone transition
         let zone = FloorZone::from_floor(self.current_floor as u32);
@@ -232,6 +306,64 @@ impl Dungeon {
             cleared: true,
             description,
         };
+        self.record_visit(RoomCoord::new(self.current_floor, 0), RoomType::Start);
+    }
+
+    /// Coordinates of the room ahead
+    pub fn next_coord(&self) -> RoomCoord {
+        RoomCoord::new(self.current_floor, self.rooms_cleared + 1)
+    }
+
+    /// Rooms visited on the current floor, in map order
+    pub fn floor_rooms(&self) -> Vec<VisitedRoom> {
+        let mut rooms: Vec<VisitedRoom> = self.visited.iter()
+            .filter(|r| r.coord.floor == self.current_floor)
+            .cloned()
+            .collect();
+        rooms.sort_by_key(|r| r.coord.index);
+        rooms
+    }
+
+    /// Note that the player is standing at `coord`. Re-entering a spot
+    /// (e.g. after fleeing) keeps the name it was given.
+    fn record_visit(&mut self, coord: RoomCoord, room_type: RoomType) {
+        let name = self.next_room_name.take();
+        match self.visited.iter_mut().find(|r| r.coord == coord) {
+            Some(room) => {
+                room.room_type = room_type;
+                if name.is_some() {
+                    room.name = name;
+                }
+            }
+            None => self.visited.push(VisitedRoom { coord, room_type, name }),
+        }
+        self.here = Some(coord);
+    }
+
+    /// The visited room the player is standing in
+    pub fn here_room(&self) -> Option<&VisitedRoom> {
+        let here = self.here?;
+        self.visited.iter().find(|r| r.coord == here)
+    }
+
+    /// Name the room the player is standing in; returns its new label
+    pub fn name_here(&mut self, name: String) -> Option<String> {
+        let here = self.here?;
+        let room = self.visited.iter_mut().find(|r| r.coord == here)?;
+        room.name = Some(name);
+        Some(room.label())
+    }
+
+    /// Walk back to a room already visited; returns its label
+    pub fn return_to(&mut self, coord: RoomCoord) -> Option<String> {
+        let room = self.visited.iter().find(|r| r.coord == coord)?.clone();
+        self.here = Some(coord);
+        self.current_room = Room {
+            room_type: room.room_type,
+            cleared: true,
+            description: format!("You retrace your steps to {}. It is quiet now.", room.label()),
+        };
+        Some(room.label())
     }
 
     pub fn get_floor_name(&self) -> &'static str {
@@ -261,48 +393,35 @@ impl Dungeon {
         self.rooms_cleared += 1;
     }
 
-    pub fn get_ascii_map(&self) -> String {
-        let total_rooms = self.rooms_per_floor;
-        let cleared = self.rooms_cleared;
-        
-        let mut map = String::new();
-        map.push_str(&format!("╔═══════════════════════════════╗\n"));
-        map.push_str(&format!("║  FLOOR {} - {}  \n", 
-            self.current_floor, 
-            self.get_floor_name()
-        ));
-        map.push_str(&format!("╠═══════════════════════════════╣\n"));
-        map.push_str("║ Progress: ");
-        
-        for i in 0..total_rooms {
-            if i < cleared {
-                map.push_str("█ ");
-            } else if i == cleared {
-                map.push_str("▓ ");
-            } else {
-                map.push_str("░ ");
-            }
-        }
-        map.push_str("║\n");
-        map.push_str(&format!("║ Rooms: {}/{}", cleared, total_rooms));
-        if self.current_floor % 5 == 0 {
-            map.push_str(" [BOSS FLOOR]");
-        }
-        map.push_str(" ║\n");
-        map.push_str(&format!("╚═══════════════════════════════╝\n"));
-        
-        map
-    }
-    
     /// Get the current zone name
     pub fn get_zone_name(&self) -> String {
         self.zone_name.clone()
     }
 }
 
+impl RoomType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Combat => "Combat",
+            Self::Elite => "Elite",
+            Self::Boss => "Boss",
+            Self::Treasure => "Treasure",
+            Self::Rest => "Rest",
+            Self::Shop => "Shop",
+            Self::Event => "Event",
+            Self::Start => "Entrance",
+            Self::Mystery => "Mystery",
+        }
+    }
+}
+
 impl Room {
     pub fn get_icon(&self) -> &'static str {
-        match self.room_type {
+        Self::icon_for(self.room_type)
+    }
+
+    pub fn icon_for(room_type: RoomType) -> &'static str {
+        match room_type {
             RoomType::Combat => "⚔",
             RoomType::Elite => "󰚌",
             RoomType::Boss => "👑",
@@ -311,6 +430,7 @@ impl Room {
             RoomType::Shop => "🛒",
             RoomType::Event => "❓",
             RoomType::Start => "🚪",
+            RoomType::Mystery => "❔",
         }
     }
 }
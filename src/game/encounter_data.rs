@@ -0,0 +1,185 @@
+//! Encounter Data - Authored encounters written as data, not code
+//!
+//! Each encounter file is a RON list of encounters:
+//! - the bundled set lives in `data/encounters/` and is built into the game
+//! - encounter packs dropped into `<config>/encounters/` are found at startup
+//!
+//! Every encounter is checked before it is used. Unknown fields are refused
+//! as the file is parsed, and `validate` checks what the types can't: ids,
+//! choices, chapters, faction names. A pack encounter with the id of a
+//! bundled one replaces it. A pack that fails its checks is left out whole;
+//! `keyboard-warrior --check-encounters [file]` says why.
+//!
+//! Design: like challenges, packs are only ever read, never written. The
+//! encounters built from tables in code (NPC variants, questlines) are
+//! added by `build_encounters` on top of the bundled files.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::config::get_config_dir;
+use super::encounter_writing::{build_encounters, AuthoredEncounter};
+use super::narrative::Faction;
+
+/// The bundled encounter files: (file name, contents)
+const BUNDLED: [(&str, &str); 4] = [
+    ("haven.ron", include_str!("../../data/encounters/haven.ron")),
+    ("athenaeum.ron", include_str!("../../data/encounters/athenaeum.ron")),
+    ("corruption.ron", include_str!("../../data/encounters/corruption.ron")),
+    ("factions.ron", include_str!("../../data/encounters/factions.ron")),
+];
+
+/// Where encounter packs live
+pub fn packs_dir() -> PathBuf {
+    get_config_dir().join("encounters")
+}
+
+fn check_faction(id: &str) -> Result<(), String> {
+    match Faction::from_id(id) {
+        Some(_) => Ok(()),
+        None => Err(format!("unknown faction '{}'", id)),
+    }
+}
+
+/// Check what the schema's types can't
+pub fn validate(encounter: &AuthoredEncounter) -> Result<(), String> {
+    let safe = |id: &str| !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !safe(&encounter.id) {
+        return Err("id must be lowercase letters, digits and underscores".to_string());
+    }
+    if encounter.title.trim().is_empty() {
+        return Err("title is empty".to_string());
+    }
+    if encounter.valid_locations.is_empty() {
+        return Err("no valid_locations".to_string());
+    }
+    if encounter.choices.is_empty() {
+        return Err("no choices".to_string());
+    }
+    let mut choice_ids = HashSet::new();
+    for choice in &encounter.choices {
+        if !safe(&choice.id) || !choice_ids.insert(choice.id.as_str()) {
+            return Err(format!("choice id '{}' is invalid or repeated", choice.id));
+        }
+    }
+    let req = &encounter.requirements;
+    if let (Some(min), Some(max)) = (req.min_chapter, req.max_chapter) {
+        if min > max {
+            return Err(format!("min_chapter {} is after max_chapter {}", min, max));
+        }
+    }
+    if let Some((faction, _)) = &req.faction_reputation {
+        check_faction(faction)?;
+    }
+    for id in encounter.outcomes.keys() {
+        if !encounter.choices.iter().any(|c| &c.consequence_id == id) {
+            return Err(format!("outcome '{}' belongs to no choice", id));
+        }
+    }
+    for consequences in std::iter::once(&encounter.consequences).chain(encounter.outcomes.values()) {
+        for (faction, _) in &consequences.reputation_changes {
+            check_faction(faction)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parse and check one encounter file; `name` is used in errors
+pub fn parse(name: &str, text: &str) -> Result<Vec<AuthoredEncounter>, String> {
+    let encounters: Vec<AuthoredEncounter> = ron::from_str(text).map_err(|e| format!("{}: {}", name, e))?;
+    let mut ids = HashSet::new();
+    for encounter in &encounters {
+        validate(encounter).map_err(|e| format!("{}: {}: {}", name, encounter.id, e))?;
+        if !ids.insert(encounter.id.as_str()) {
+            return Err(format!("{}: {}: id used twice", name, encounter.id));
+        }
+    }
+    Ok(encounters)
+}
+
+/// The encounters bundled with the game
+pub fn bundled() -> Vec<AuthoredEncounter> {
+    BUNDLED.iter()
+        .flat_map(|(name, text)| parse(name, text).unwrap_or_else(|e| panic!("bundled encounters are broken: {}", e)))
+        .collect()
+}
+
+/// Parse and check an encounter pack file
+pub fn load_pack(path: &Path) -> Result<Vec<AuthoredEncounter>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse(&path.display().to_string(), &content)
+}
+
+/// Every installed pack, in file name order: the encounters from those that
+/// pass their checks, and what was wrong with those that don't
+pub fn load_packs() -> (Vec<AuthoredEncounter>, Vec<String>) {
+    let Ok(entries) = fs::read_dir(packs_dir()) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "ron"))
+        .collect();
+    paths.sort();
+    let mut encounters = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        match load_pack(&path) {
+            Ok(pack) => encounters.extend(pack),
+            Err(e) => errors.push(e),
+        }
+    }
+    (encounters, errors)
+}
+
+/// The bundled encounters with every valid pack laid over them
+pub fn load_encounters() -> HashMap<String, AuthoredEncounter> {
+    let mut encounters = build_encounters();
+    for encounter in load_packs().0 {
+        encounters.insert(encounter.id.clone(), encounter);
+    }
+    encounters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_files_pass_their_checks() {
+        for (name, text) in &BUNDLED {
+            assert!(parse(name, text).is_ok_and(|list| !list.is_empty()), "{} is broken", name);
+        }
+        let encounters = build_encounters();
+        assert!(encounters.contains_key("haven_stranger_arrival"));
+        assert!(encounters.values().all(|e| validate(e).is_ok()));
+    }
+
+    #[test]
+    fn test_packs_are_checked_against_the_schema() {
+        let pack = r#"[(
+            id: "lantern_seller",
+            title: "The Lantern Seller",
+            valid_locations: ["haven_market"],
+            content: (description: "A lantern that burns without oil."),
+            choices: [(id: "buy", text: "Buy one.", consequence_id: "buy_result")],
+            consequences: (reputation_changes: [("Archivists", 5)], narrative_result: "It lights the way."),
+        )]"#;
+        let encounters = parse("lantern.ron", pack).unwrap();
+        assert!(encounters[0].requirements.min_chapter.is_none() && !encounters[0].repeatable);
+
+        // A misspelt field, an unknown faction, and a choice given twice
+        assert!(parse("bad.ron", &pack.replace("valid_locations", "locations")).is_err());
+        assert!(parse("bad.ron", &pack.replace("Archivists", "Librarians")).unwrap_err().contains("Librarians"));
+        let choice = r#"(id: "buy", text: "Buy one.", consequence_id: "buy_result")"#;
+        let twice = pack.replace(choice, &format!("{}, {}", choice, choice));
+        assert!(parse("bad.ron", &twice).unwrap_err().contains("repeated"));
+
+        // An outcome for a choice that isn't there
+        let stray = pack.replace("narrative_result: \"It lights the way.\"),", "narrative_result: \"It lights the way.\"),\n            outcomes: { \"steal_result\": () },");
+        assert!(parse("bad.ron", &stray).unwrap_err().contains("steal_result"));
+        assert!(parse("ok.ron", &stray.replace("steal_result", "buy_result")).is_ok());
+    }
+}
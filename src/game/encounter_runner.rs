@@ -0,0 +1,219 @@
+//! Encounter Runner - Playing an authored encounter in an event room
+//!
+//! An authored encounter plays out in three stages:
+//! 1. Reading: the scene, its dialogue and the details you notice, with
+//!    the choices beneath
+//! 2. Typing: a choice that must be typed puts the encounter's typing
+//!    challenge in front of you (or the choice's own words if it has none)
+//! 3. Outcome: what your choice, and how well you typed it, led to
+//!
+//! A typing challenge is graded by accuracy: clean enough and the choice
+//! goes through, close enough for a partial success, otherwise it fails
+//! and the choice's consequences are lost. A challenge that is a naming
+//! ritual asks for the name instead, and every grade has consequences of
+//! its own (see naming_ritual).
+//!
+//! Design: the runner only tracks the stage and grades the typing;
+//! GameState applies the consequences through `resolve_encounter`.
+
+use std::time::Instant;
+
+use super::encounter_writing::{AuthoredEncounter, EncounterChoice};
+use super::naming_ritual::Ritual;
+
+/// Accuracy a typed choice needs to succeed outright
+pub const SUCCESS_ACCURACY: f32 = 0.95;
+/// Accuracy a typed choice needs for a partial success
+pub const PARTIAL_ACCURACY: f32 = 0.7;
+
+/// How a typed choice went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypingGrade {
+    Success,
+    Partial,
+    Failure,
+}
+
+impl TypingGrade {
+    pub fn of(accuracy: f32) -> Self {
+        if accuracy >= SUCCESS_ACCURACY {
+            Self::Success
+        } else if accuracy >= PARTIAL_ACCURACY {
+            Self::Partial
+        } else {
+            Self::Failure
+        }
+    }
+
+    /// Whether the choice's consequences go through
+    pub fn carries(&self) -> bool {
+        *self != Self::Failure
+    }
+}
+
+/// Share of `prompt` typed correctly, position by position
+pub fn accuracy(prompt: &str, typed: &str) -> f32 {
+    let prompt: Vec<char> = prompt.chars().collect();
+    let typed: Vec<char> = typed.chars().collect();
+    let len = prompt.len().max(typed.len());
+    if len == 0 {
+        return 1.0;
+    }
+    let correct = prompt.iter().zip(&typed).filter(|(a, b)| a == b).count();
+    correct as f32 / len as f32
+}
+
+/// Authored text with its source indentation folded away, one string per
+/// paragraph
+pub fn paragraphs(text: &str) -> Vec<String> {
+    text.split("\n\n")
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Where the encounter has got to
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncounterStage {
+    Reading,
+    Typing { choice: usize, prompt: String, typed: String },
+    Outcome { text: Vec<String> },
+}
+
+/// An authored encounter being played
+#[derive(Debug, Clone)]
+pub struct EncounterRun {
+    pub encounter: AuthoredEncounter,
+    pub stage: EncounterStage,
+    /// When the naming ritual began, while one is being typed
+    pub ritual_started: Option<Instant>,
+}
+
+impl EncounterRun {
+    pub fn new(encounter: AuthoredEncounter) -> Self {
+        Self { encounter, stage: EncounterStage::Reading, ritual_started: None }
+    }
+
+    /// The naming ritual the encounter's typing challenge is, if it is one
+    pub fn ritual(&self) -> Option<&Ritual> {
+        self.encounter.content.typing_challenge.as_ref()?.ritual.as_ref()
+    }
+
+    /// Whether a choice can be taken. Choices with a requirement are
+    /// locked; nothing yet tracks the ranks they ask for.
+    pub fn available(choice: &EncounterChoice) -> bool {
+        choice.requires.is_none()
+    }
+
+    /// Pick a choice while reading. A typed choice moves to the typing
+    /// stage and returns None; any other returns the choice to resolve.
+    pub fn choose(&mut self, index: usize) -> Option<usize> {
+        if self.stage != EncounterStage::Reading {
+            return None;
+        }
+        let choice = self.encounter.choices.get(index).filter(|c| Self::available(c))?;
+        if !choice.typing_required {
+            return Some(index);
+        }
+        let prompt = match &self.encounter.content.typing_challenge {
+            Some(challenge) => match &challenge.ritual {
+                Some(ritual) => {
+                    self.ritual_started = Some(Instant::now());
+                    ritual.name.clone()
+                }
+                None => paragraphs(&challenge.prompt_text).join(" "),
+            },
+            None => choice.text.clone(),
+        };
+        self.stage = EncounterStage::Typing { choice: index, prompt, typed: String::new() };
+        None
+    }
+
+    /// Put the choice's own outcome, if it has one, in place of the
+    /// encounter's consequences
+    pub fn settle(&mut self, choice: usize) {
+        let outcome = self.encounter.choices.get(choice)
+            .and_then(|c| self.encounter.outcomes.get(&c.consequence_id))
+            .cloned();
+        if let Some(outcome) = outcome {
+            self.encounter.consequences = outcome;
+        }
+    }
+
+    /// Grade the typing: the choice it was for and how it went
+    pub fn grade(&self) -> Option<(usize, TypingGrade)> {
+        match &self.stage {
+            EncounterStage::Typing { choice, prompt, typed } => Some((*choice, TypingGrade::of(accuracy(prompt, typed)))),
+            _ => None,
+        }
+    }
+
+    /// What the encounter says after a typed choice
+    pub fn typing_narrative(&self, grade: TypingGrade) -> Option<String> {
+        let challenge = self.encounter.content.typing_challenge.as_ref()?;
+        let text = match grade {
+            TypingGrade::Success => &challenge.success_narrative,
+            TypingGrade::Partial => challenge.partial_narrative.as_ref().unwrap_or(&challenge.success_narrative),
+            TypingGrade::Failure => &challenge.failure_narrative,
+        };
+        Some(text.clone())
+    }
+
+    /// Show how it ended
+    pub fn conclude(&mut self, text: Vec<String>) {
+        self.stage = EncounterStage::Outcome { text };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    #[test]
+    fn test_grading() {
+        assert_eq!(accuracy("word", "word"), 1.0);
+        assert_eq!(accuracy("word", "wor"), 0.75);
+        assert_eq!(TypingGrade::of(accuracy("word", "ward")), TypingGrade::Partial);
+        assert_eq!(TypingGrade::of(accuracy("word", "")), TypingGrade::Failure);
+        assert!(!TypingGrade::Failure.carries());
+        assert_eq!(paragraphs("a\n     b\n\n   c  "), vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn test_typed_choice_goes_through_typing() {
+        let encounters = build_encounters();
+        let mut run = EncounterRun::new(encounters["athenaeum_living_book"].clone());
+        let typed = run.encounter.choices.iter().position(|c| c.typing_required).unwrap();
+        let plain = run.encounter.choices.iter().position(|c| !c.typing_required && c.requires.is_none()).unwrap();
+
+        assert_eq!(run.clone().choose(plain), Some(plain));
+        assert_eq!(run.choose(typed), None);
+        let EncounterStage::Typing { prompt, typed: input, .. } = &mut run.stage else {
+            panic!("expected typing stage");
+        };
+        *input = prompt.clone();
+        assert_eq!(run.grade(), Some((typed, TypingGrade::Success)));
+        assert!(run.typing_narrative(TypingGrade::Success).is_some());
+    }
+
+    #[test]
+    fn test_a_choice_settles_on_its_own_outcome() {
+        let encounters = build_encounters();
+        let mut run = EncounterRun::new(encounters["athenaeum_living_book"].clone());
+        let refuse = run.encounter.choices.iter().position(|c| c.id == "refuse_book").unwrap();
+
+        assert_eq!(run.choose(refuse), Some(refuse));
+        run.settle(refuse);
+        let settled = &run.encounter.consequences;
+        assert!(settled.world_state_changes.is_empty());
+        assert!(settled.enables_encounters.is_empty());
+        assert!(!settled.narrative_result.contains("settles into your hands"));
+
+        // A choice with no outcome of its own keeps the encounter's
+        let mut run = EncounterRun::new(encounters["athenaeum_living_book"].clone());
+        let accept = run.encounter.choices.iter().position(|c| c.id == "accept_book").unwrap();
+        run.settle(accept);
+        assert!(run.encounter.consequences.world_state_changes.contains(&"living_book_awakened".to_string()));
+    }
+}
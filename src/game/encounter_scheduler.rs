@@ -0,0 +1,322 @@
+//! Encounter Scheduler - Which authored encounter turns up, and when
+//!
+//! When an event room is entered the scheduler filters the authored
+//! encounters down to those that fit, checking each one's:
+//! - location (the zone's places) and chapter (the floor)
+//! - faction reputation
+//! - prerequisite and blocking encounters
+//! - required lore and world flags
+//! - any scripted condition (see narrative_script)
+//! - time of day and weather
+//!
+//! Of those that fit, one is drawn by weight. Major encounters weigh more,
+//! and more again for every time they were passed over, so they aren't
+//! missed. A faction's offers weigh more the more the run's ideology shares
+//! its leanings. Follow-ups an earlier choice opened weigh more until they turn
+//! up. Repeatable ones rest for a few rooms after turning up and weigh
+//! less each time they have been seen, so they don't crowd out the rest.
+//!
+//! Time passes as rooms are entered (three rooms to each part of the day).
+//! The weather is rolled when a floor begins; the more corrupted the zone,
+//! the likelier the Corruption mist.
+//!
+//! Design: the scheduler keeps the counts and clocks; GameState gathers
+//! what it needs into a `SchedulingContext` when a room asks for an
+//! encounter.
+
+use rand::Rng;
+use std::collections::HashMap;
+
+use super::encounter_writing::{AuthoredEncounter, EncounterTracker, TimeOfDay, WeatherCondition};
+use super::faction_system::FactionRelations;
+use super::ideology::{self, Ideology};
+use super::narrative::Faction;
+use super::narrative_script::Facts;
+use super::npc_memory::NpcMemory;
+use super::world_integration::FloorZone;
+use super::world_state::WorldState;
+
+/// Rooms to each part of the day
+pub const ROOMS_PER_TIME_OF_DAY: u32 = 3;
+/// Rooms a repeatable encounter rests after turning up
+pub const REPEAT_COOLDOWN: u32 = 6;
+/// Weight of a major encounter against an ordinary one
+pub const MAJOR_WEIGHT: f32 = 4.0;
+/// Weight multiplier of a follow-up an earlier choice opened
+pub const FOLLOW_UP_WEIGHT: f32 = 2.0;
+/// Chance of the Corruption mist per point of zone corruption
+pub const MIST_PER_CORRUPTION: f32 = 0.5;
+/// Tag that marks a major encounter
+const MAJOR_TAG: &str = "major";
+
+impl TimeOfDay {
+    const CYCLE: [TimeOfDay; 4] = [TimeOfDay::Dawn, TimeOfDay::Day, TimeOfDay::Dusk, TimeOfDay::Night];
+
+    /// Time of day after `rooms` rooms entered
+    pub fn after_rooms(rooms: u32) -> Self {
+        Self::CYCLE[(rooms / ROOMS_PER_TIME_OF_DAY) as usize % Self::CYCLE.len()]
+    }
+}
+
+impl WeatherCondition {
+    /// Weather for a floor of `zone`
+    pub fn roll(zone: FloorZone, rng: &mut impl Rng) -> Self {
+        if rng.gen::<f32>() < zone.corruption_level() * MIST_PER_CORRUPTION {
+            return Self::CorruptionMist;
+        }
+        match rng.gen_range(0..6) {
+            0 => Self::Storm,
+            1 | 2 => Self::Rain,
+            _ => Self::Clear,
+        }
+    }
+}
+
+/// What the run looks like to the encounters
+pub struct SchedulingContext<'a> {
+    pub floor: i32,
+    pub tracker: &'a EncounterTracker,
+    pub factions: &'a FactionRelations,
+    pub world: &'a WorldState,
+    /// Lore ids revealed so far
+    pub lore: Vec<String>,
+    pub npcs: &'a NpcMemory,
+    pub ideology: &'a Ideology,
+}
+
+impl Facts for SchedulingContext<'_> {
+    fn floor(&self) -> i32 {
+        self.floor
+    }
+
+    fn standing(&self, faction: Faction) -> i32 {
+        self.factions.standing(&faction)
+    }
+
+    fn world(&self) -> &WorldState {
+        self.world
+    }
+
+    fn tracker(&self) -> &EncounterTracker {
+        self.tracker
+    }
+
+    fn has_lore(&self, id: &str) -> bool {
+        self.lore.iter().any(|l| l == id)
+    }
+
+    fn opinion(&self, npc: &str) -> i32 {
+        self.npcs.opinion(npc)
+    }
+}
+
+/// Counts and clocks behind the draw
+#[derive(Debug, Clone)]
+pub struct EncounterScheduler {
+    /// Rooms entered this run
+    pub rooms: u32,
+    pub weather: WeatherCondition,
+    floor: i32,
+    /// Times each encounter has turned up
+    seen: HashMap<String, u32>,
+    /// Room each encounter last turned up in
+    last_seen: HashMap<String, u32>,
+    /// Times each encounter fit but another was drawn
+    passed_over: HashMap<String, u32>,
+    /// Follow-ups opened by earlier choices, not yet drawn
+    enabled: Vec<String>,
+}
+
+impl Default for EncounterScheduler {
+    fn default() -> Self {
+        Self {
+            rooms: 0,
+            weather: WeatherCondition::Clear,
+            floor: 0,
+            seen: HashMap::new(),
+            last_seen: HashMap::new(),
+            passed_over: HashMap::new(),
+            enabled: Vec::new(),
+        }
+    }
+}
+
+impl EncounterScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        TimeOfDay::after_rooms(self.rooms)
+    }
+
+    /// A room was entered on `floor`; a new floor brings new weather
+    pub fn note_room(&mut self, floor: i32, rng: &mut impl Rng) {
+        self.rooms += 1;
+        if floor != self.floor {
+            self.floor = floor;
+            self.weather = WeatherCondition::roll(FloorZone::from_floor(floor.max(1) as u32), rng);
+        }
+    }
+
+    /// An earlier choice opened `encounter_id`; favour it until it is drawn
+    pub fn enable(&mut self, encounter_id: &str) {
+        if !self.enabled.iter().any(|id| id == encounter_id) {
+            self.enabled.push(encounter_id.to_string());
+        }
+    }
+
+    /// Whether `encounter` fits the run right now
+    pub fn eligible(&self, encounter: &AuthoredEncounter, ctx: &SchedulingContext) -> bool {
+        let req = &encounter.requirements;
+        let zone_locations = FloorZone::from_floor(ctx.floor.max(1) as u32).encounter_locations();
+        let floor_location = format!("floor_{}", ctx.floor);
+        let in_place = encounter.valid_locations.iter()
+            .any(|loc| loc == "any" || *loc == floor_location || zone_locations.contains(&loc.as_str()));
+        let fresh = if encounter.repeatable {
+            self.last_seen.get(&encounter.id).is_none_or(|room| self.rooms >= room + REPEAT_COOLDOWN)
+        } else {
+            !ctx.tracker.has_completed(&encounter.id)
+        };
+        let reputation = req.faction_reputation.as_ref().is_none_or(|(name, min)| {
+            Faction::from_id(name).is_some_and(|f| ctx.factions.standing(&f) >= *min)
+        });
+        in_place
+            && fresh
+            && req.min_chapter.is_none_or(|min| ctx.floor >= min as i32)
+            && req.max_chapter.is_none_or(|max| ctx.floor <= max as i32)
+            && reputation
+            && req.prerequisite_encounter.as_ref().is_none_or(|id| ctx.tracker.has_completed(id))
+            && req.blocking_encounter.as_ref().is_none_or(|id| !ctx.tracker.has_completed(id))
+            && req.required_lore.as_ref().is_none_or(|id| ctx.lore.contains(id))
+            && req.required_flags.iter().all(|f| ctx.world.has_flag(f))
+            && !req.blocking_flags.iter().any(|f| ctx.world.has_flag(f))
+            && req.npc_state.as_ref().is_none_or(|(npc, state)| ctx.world.npc_state(npc) == *state)
+            && req.time_of_day.is_none_or(|t| t == self.time_of_day())
+            && req.weather.is_none_or(|w| w == self.weather)
+            && req.condition.as_ref().is_none_or(|c| c.holds(ctx))
+    }
+
+    /// How heavily `encounter` weighs in the draw
+    pub fn weight(&self, encounter: &AuthoredEncounter) -> f32 {
+        let mut weight = 1.0;
+        if encounter.tags.iter().any(|t| t == MAJOR_TAG) {
+            weight *= MAJOR_WEIGHT * (1 + self.passed_over.get(&encounter.id).copied().unwrap_or(0)) as f32;
+        }
+        if encounter.repeatable {
+            weight /= (1 + self.seen.get(&encounter.id).copied().unwrap_or(0)) as f32;
+        }
+        if self.enabled.contains(&encounter.id) {
+            weight *= FOLLOW_UP_WEIGHT;
+        }
+        weight
+    }
+
+    /// Draw an encounter for the room, if any fits
+    pub fn pick(
+        &mut self,
+        encounters: &HashMap<String, AuthoredEncounter>,
+        ctx: &SchedulingContext,
+        rng: &mut impl Rng,
+    ) -> Option<AuthoredEncounter> {
+        // Sorted so a seeded run draws the same way every time
+        let mut fitting: Vec<&AuthoredEncounter> = encounters.values().filter(|e| self.eligible(e, ctx)).collect();
+        fitting.sort_by(|a, b| a.id.cmp(&b.id));
+        // A faction's offers weigh with how far the run shares its leanings
+        let weigh = |e: &AuthoredEncounter| self.weight(e) * ideology::offered_by(e).map_or(1.0, |f| ctx.ideology.affinity(f));
+        let total: f32 = fitting.iter().map(|e| weigh(e)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut roll = rng.gen::<f32>() * total;
+        let chosen = fitting.iter()
+            .find(|e| {
+                roll -= weigh(e);
+                roll < 0.0
+            })
+            .or(fitting.last())
+            .map(|e| (*e).clone())?;
+
+        for other in fitting.iter().filter(|e| e.id != chosen.id) {
+            *self.passed_over.entry(other.id.clone()).or_insert(0) += 1;
+        }
+        self.passed_over.remove(&chosen.id);
+        self.enabled.retain(|id| *id != chosen.id);
+        *self.seen.entry(chosen.id.clone()).or_insert(0) += 1;
+        self.last_seen.insert(chosen.id.clone(), self.rooms);
+        Some(chosen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    fn ctx<'a>(floor: i32, tracker: &'a EncounterTracker, factions: &'a FactionRelations, world: &'a WorldState, npcs: &'a NpcMemory) -> SchedulingContext<'a> {
+        SchedulingContext { floor, tracker, factions, world, lore: Vec::new(), npcs, ideology: &Ideology { preserve: 0, control: 0 } }
+    }
+
+    #[test]
+    fn test_requirements_filter_encounters() {
+        let encounters = build_encounters();
+        let tracker = EncounterTracker::new();
+        let factions = FactionRelations::new();
+        let world = WorldState::default();
+        let npcs = NpcMemory::new();
+        let mut scheduler = EncounterScheduler::new();
+
+        // The stranger only arrives on the first floor, in Haven
+        let stranger = &encounters["haven_stranger_arrival"];
+        assert!(scheduler.eligible(stranger, &ctx(1, &tracker, &factions, &world, &npcs)));
+        assert!(!scheduler.eligible(stranger, &ctx(3, &tracker, &factions, &world, &npcs)));
+
+        // The shadow writer whispers only at night
+        let shadow = &encounters["shadowwriter_offer"];
+        scheduler.rooms = 0;
+        assert!(!scheduler.eligible(shadow, &ctx(2, &tracker, &factions, &world, &npcs)));
+        scheduler.rooms = ROOMS_PER_TIME_OF_DAY * 3;
+        assert_eq!(scheduler.time_of_day(), TimeOfDay::Night);
+        assert!(scheduler.eligible(shadow, &ctx(2, &tracker, &factions, &world, &npcs)));
+
+        // The memory echo needs the Corruption mist
+        let echo = &encounters["corruption_memory_echo"];
+        scheduler.weather = WeatherCondition::Clear;
+        assert!(!scheduler.eligible(echo, &ctx(5, &tracker, &factions, &world, &npcs)));
+        scheduler.weather = WeatherCondition::CorruptionMist;
+        assert!(scheduler.eligible(echo, &ctx(5, &tracker, &factions, &world, &npcs)));
+    }
+
+    #[test]
+    fn test_majors_grow_and_repeatables_rest() {
+        let encounters = build_encounters();
+        let tracker = EncounterTracker::new();
+        let factions = FactionRelations::new();
+        let world = WorldState::default();
+        let npcs = NpcMemory::new();
+        let mut scheduler = EncounterScheduler::new();
+        let mut rng = rand::thread_rng();
+
+        let book = &encounters["athenaeum_living_book"];
+        let scribe = &encounters["haven_old_scribe"];
+        assert_eq!(scheduler.weight(book), MAJOR_WEIGHT);
+        scheduler.passed_over.insert(book.id.clone(), 2);
+        assert_eq!(scheduler.weight(book), MAJOR_WEIGHT * 3.0);
+        scheduler.enable(&book.id);
+        assert_eq!(scheduler.weight(book), MAJOR_WEIGHT * 3.0 * FOLLOW_UP_WEIGHT);
+
+        // Only the repeatable scribe fits on floor 1 after the stranger and
+        // the questlines that open in Haven are done
+        let mut done = EncounterTracker::new();
+        done.complete_encounter("haven_stranger_arrival", "help_stranger");
+        for quest in encounters.values().filter(|e| e.tags.iter().any(|t| t == "questline")) {
+            done.complete_encounter(&quest.id, &quest.choices[0].id);
+        }
+        let picked = scheduler.pick(&encounters, &ctx(1, &done, &factions, &world, &npcs), &mut rng);
+        assert_eq!(picked.map(|e| e.id), Some(scribe.id.clone()));
+        assert!(scheduler.weight(scribe) < 1.0);
+        assert!(scheduler.pick(&encounters, &ctx(1, &done, &factions, &world, &npcs), &mut rng).is_none());
+        scheduler.rooms += REPEAT_COOLDOWN;
+        assert!(scheduler.eligible(scribe, &ctx(1, &tracker, &factions, &world, &npcs)));
+    }
+}
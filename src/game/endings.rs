@@ -0,0 +1,300 @@
+//! Endings - How the player's mystery resolves
+//!
+//! The player's mystery names three endings, each with requirements. At the
+//! end of a victorious run they are checked against how it went:
+//! - The Third Grammar: every faction still stands with you, an artifact
+//!   has given up its hidden truth, you owe no patron, and you haven't
+//!   leaned toward Control
+//! - The Final Rest: you carry every artifact and no curse, and you haven't
+//!   leaned toward Unwrite
+//! - The Dark Ascension: you carry two curses or more and nothing that
+//!   wards off corruption, and you haven't leaned toward Preserve
+//!
+//! The first ending whose requirements are all met plays its own final
+//! sequence before the victory screen. A run that meets none ends under its
+//! patron (or unbound) as before, and its epilogue names the ending it came
+//! nearest and the requirement it fell short of.
+//!
+//! Design: the story of each ending stays in `create_player_mystery`, and
+//! each requirement written there carries the `Requirement` that checks it,
+//! so the prose and its predicate can't drift apart. This module evaluates
+//! them and pages the ending into a sequence. GameState snapshots the run
+//! into an `EndingContext`.
+
+use serde::{Deserialize, Serialize};
+
+use super::artifacts::{self, RELICS};
+use super::deep_lore::{create_player_mystery, Ending};
+use super::ideology::{Ideology, Pole};
+use super::narrative::Faction;
+use super::patrons::{Patron, THIRD_GRAMMAR_ENDING};
+
+/// Lowest standing with every faction that counts as uniting them
+pub const UNITED_STANDING: i32 = 1;
+/// Curses carried to embrace the Void
+pub const VOID_CURSES: usize = 2;
+
+const FACTIONS: [Faction; 5] = [
+    Faction::MagesGuild,
+    Faction::TempleOfDawn,
+    Faction::RangersOfTheWild,
+    Faction::ShadowGuild,
+    Faction::MerchantConsortium,
+];
+
+/// What a victorious run brings to the end
+#[derive(Debug, Clone, Default)]
+pub struct EndingContext {
+    pub patron: Option<Patron>,
+    pub artifacts: Vec<String>,
+    pub curses: usize,
+    /// Standing with each faction
+    pub standings: Vec<(Faction, i32)>,
+    /// Titles of the lore discovered this run
+    pub lore_titles: Vec<String>,
+    pub ideology: Ideology,
+}
+
+/// What the run must show to meet a requirement of an ending
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Requirement {
+    /// Every faction stands with you
+    FactionsUnited,
+    /// An artifact has given up its hidden truth
+    ArtifactTruth,
+    /// You owe no patron
+    Unbound,
+    /// You carry every artifact
+    AllArtifacts,
+    /// You carry no curse
+    Uncursed,
+    /// You carry at least this many curses
+    Cursed(usize),
+    /// You carry nothing that wards off corruption
+    Unwarded,
+    /// Your choices haven't leaned you toward this
+    NotLeaning(Pole),
+}
+
+impl Requirement {
+    /// Whether the run meets it
+    pub fn met(&self, ctx: &EndingContext) -> bool {
+        match self {
+            Self::FactionsUnited => FACTIONS.iter().all(|f| {
+                ctx.standings.iter().find(|(g, _)| g == f).map_or(0, |(_, s)| *s) >= UNITED_STANDING
+            }),
+            Self::ArtifactTruth => RELICS.iter().any(|r| ctx.lore_titles.contains(&artifacts::truth_title(r.artifact))),
+            Self::Unbound => ctx.patron.is_none(),
+            Self::AllArtifacts => artifacts::unfound(&ctx.artifacts).is_empty(),
+            Self::Uncursed => ctx.curses == 0,
+            Self::Cursed(at_least) => ctx.curses >= *at_least,
+            Self::Unwarded => !artifacts::wards_corruption(&ctx.artifacts),
+            Self::NotLeaning(pole) => !ctx.ideology.leans(*pole),
+        }
+    }
+
+    /// Whether meeting it takes doing something, not just leaving something
+    /// undone
+    pub fn earned(&self) -> bool {
+        !matches!(self, Self::Unbound | Self::Uncursed | Self::Unwarded | Self::NotLeaning(_))
+    }
+}
+
+/// One of the mystery's endings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MysteryEnding {
+    ThirdGrammar,
+    FinalRest,
+    DarkAscension,
+}
+
+impl MysteryEnding {
+    /// In the order they are checked
+    pub const ALL: [MysteryEnding; 3] = [Self::ThirdGrammar, Self::FinalRest, Self::DarkAscension];
+
+    /// Ending as (id, title), as recorded by the meta-progress
+    pub fn id_title(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::ThirdGrammar => THIRD_GRAMMAR_ENDING,
+            Self::FinalRest => ("final_rest", "The Final Rest"),
+            Self::DarkAscension => ("dark_ascension", "The Dark Ascension"),
+        }
+    }
+
+    /// Name of its record in the player's mystery
+    fn lore_name(&self) -> &'static str {
+        match self {
+            Self::ThirdGrammar => "The Third Path",
+            Self::FinalRest => "The Final Rest",
+            Self::DarkAscension => "The Dark Ascension",
+        }
+    }
+
+    /// The mystery's record of this ending
+    pub fn lore(&self) -> Option<Ending> {
+        create_player_mystery().possible_endings.into_iter().find(|e| e.name == self.lore_name())
+    }
+
+    /// Each written requirement beside whether the run met it
+    pub fn requirements(&self, ctx: &EndingContext) -> Vec<(String, bool)> {
+        let written = self.lore().map(|e| e.requirements).unwrap_or_default();
+        written.into_iter().map(|r| { let met = r.check.met(ctx); (r.text, met) }).collect()
+    }
+
+    /// Whether the run meets every requirement; an ending the mystery
+    /// doesn't record can't be reached
+    pub fn reached(&self, ctx: &EndingContext) -> bool {
+        let requirements = self.requirements(ctx);
+        !requirements.is_empty() && requirements.iter().all(|(_, met)| *met)
+    }
+
+    /// The closing line of its final sequence
+    fn last_words(&self) -> &'static str {
+        match self {
+            Self::ThirdGrammar => "You type a word no one has typed before. The Breach becomes a door.",
+            Self::FinalRest => "You type your name one last time, and let it go.",
+            Self::DarkAscension => "You stop typing. The silence types for you.",
+        }
+    }
+}
+
+/// The first ending the run has reached, if any
+pub fn resolve(ctx: &EndingContext) -> Option<MysteryEnding> {
+    MysteryEnding::ALL.into_iter().find(|e| e.reached(ctx))
+}
+
+/// The ending a run that reached none came within one requirement of, and
+/// the requirement it fell short of. Only requirements the run had to earn
+/// bring it near: a run that merely stayed uncursed came near nothing.
+pub fn nearest(ctx: &EndingContext) -> Option<(MysteryEnding, String)> {
+    if resolve(ctx).is_some() {
+        return None;
+    }
+    MysteryEnding::ALL.into_iter().find_map(|ending| {
+        let lore = ending.lore()?;
+        let (met, unmet): (Vec<_>, Vec<_>) = lore.requirements.into_iter().partition(|r| r.check.met(ctx));
+        match unmet.as_slice() {
+            [missing] if met.iter().any(|r| r.check.earned()) => Some((ending, missing.text.clone())),
+            _ => None,
+        }
+    })
+}
+
+/// The endings the run's leanings have closed, whatever else it does
+pub fn closed(ideology: &Ideology) -> Vec<MysteryEnding> {
+    let ctx = EndingContext { ideology: *ideology, ..Default::default() };
+    MysteryEnding::ALL.into_iter()
+        .filter(|e| e.lore().is_some_and(|lore| {
+            lore.requirements.iter().any(|r| matches!(r.check, Requirement::NotLeaning(_)) && !r.check.met(&ctx))
+        }))
+        .collect()
+}
+
+/// A final sequence played page by page
+#[derive(Debug, Clone)]
+pub struct Finale {
+    pub ending: MysteryEnding,
+    pub pages: Vec<String>,
+    pub page: usize,
+}
+
+impl Finale {
+    pub fn new(ending: MysteryEnding) -> Self {
+        let mystery = create_player_mystery();
+        let mut pages = vec![mystery.the_truth.who_they_were, mystery.the_truth.what_they_must_choose];
+        if let Some(lore) = ending.lore() {
+            pages.push(lore.description);
+            pages.push(lore.consequences);
+        }
+        pages.push(ending.last_words().to_string());
+        Self { ending, pages, page: 0 }
+    }
+
+    pub fn current(&self) -> &str {
+        self.pages.get(self.page).map(String::as_str).unwrap_or("")
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.page + 1 >= self.pages.len()
+    }
+
+    /// Turn the page; false once the sequence is over
+    pub fn advance(&mut self) -> bool {
+        if self.is_last() {
+            return false;
+        }
+        self.page += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn united() -> Vec<(Faction, i32)> {
+        FACTIONS.iter().map(|f| (*f, 10)).collect()
+    }
+
+    #[test]
+    fn test_every_ending_matches_its_lore() {
+        for ending in MysteryEnding::ALL {
+            let lore = ending.lore().unwrap_or_else(|| panic!("{:?} missing from the mystery", ending));
+            assert!(!lore.requirements.is_empty());
+            let finale = Finale::new(ending);
+            assert!(finale.pages.len() >= 3);
+            assert_eq!(finale.pages.last().map(String::as_str), Some(ending.last_words()));
+        }
+    }
+
+    #[test]
+    fn test_endings_resolve_from_the_run() {
+        assert_eq!(resolve(&EndingContext::default()), None);
+
+        let all_relics: Vec<String> = RELICS.iter().map(|r| r.artifact.to_string()).collect();
+        let rest = EndingContext { artifacts: all_relics.clone(), ..Default::default() };
+        assert_eq!(resolve(&rest), Some(MysteryEnding::FinalRest));
+
+        let void = EndingContext { curses: 2, artifacts: vec!["The Songlines".into()], ..Default::default() };
+        assert_eq!(resolve(&void), Some(MysteryEnding::DarkAscension));
+        let warded = EndingContext { artifacts: vec!["The Staff of Binding".into()], ..void };
+        assert_eq!(resolve(&warded), None);
+
+        let third = EndingContext {
+            standings: united(),
+            lore_titles: vec![artifacts::truth_title("The Songlines")],
+            artifacts: all_relics,
+            ..Default::default()
+        };
+        // The Third Grammar is checked first
+        assert_eq!(resolve(&third), Some(MysteryEnding::ThirdGrammar));
+        let pledged = EndingContext { patron: Some(Patron::Scribes), ..third.clone() };
+        assert_eq!(resolve(&pledged), Some(MysteryEnding::FinalRest));
+        assert!(!MysteryEnding::ThirdGrammar.requirements(&pledged)[2].1);
+
+        // Leaning toward what an ending would undo closes it
+        let mut ideology = Ideology::default();
+        ideology.lean(Pole::Control, 20);
+        ideology.lean(Pole::Unwrite, 20);
+        assert_eq!(resolve(&EndingContext { ideology, ..third }), None);
+        assert_eq!(closed(&ideology), vec![MysteryEnding::ThirdGrammar, MysteryEnding::FinalRest]);
+    }
+
+    #[test]
+    fn test_a_near_miss_names_what_was_lacking() {
+        // Staying uncursed and unbound alone comes near nothing
+        assert_eq!(nearest(&EndingContext::default()), None);
+
+        let all_relics: Vec<String> = RELICS.iter().map(|r| r.artifact.to_string()).collect();
+        let cursed = EndingContext { artifacts: all_relics, curses: 1, ..Default::default() };
+        let (ending, missing) = nearest(&cursed).unwrap();
+        assert_eq!(ending, MysteryEnding::FinalRest);
+        assert_eq!(missing, "Sacrifice yourself to seal the Breach");
+
+        let united = EndingContext { standings: united(), ..Default::default() };
+        assert_eq!(nearest(&united).map(|(e, _)| e), Some(MysteryEnding::ThirdGrammar));
+        // A run that reached an ending missed nothing
+        let reached = EndingContext { curses: 0, ..cursed };
+        assert_eq!(nearest(&reached), None);
+    }
+}
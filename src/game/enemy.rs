@@ -3,7 +3,14 @@
 use serde::{Deserialize, Serialize};
 use rand::seq::SliceRandom;
 use std::sync::Arc;
-use crate::data::{GameData, enemies::EnemyTemplate};
+use crate::data::{GameData, enemies::EnemyTemplate, damage_types::Resistances};
+use super::difficulty_director::DifficultyDirector;
+use super::run_rng::{self, Stream};
+
+/// How much tougher (and richer in XP) an ordinary enemy is on `floor`
+pub fn floor_scale(floor: i32) -> f32 {
+    1.0 + (floor as f32 - 1.0) * 0.1
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Enemy {
@@ -22,6 +29,9 @@ pub struct Enemy {
     pub is_boss: bool,
     pub typing_theme: String,
     pub attack_messages: Vec<String>,
+    /// Damage multipliers by damage type
+    #[serde(default)]
+    pub resistances: Resistances,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -33,12 +43,14 @@ pub enum EnemyType {
 
 impl Enemy {
     /// Create an enemy from a data template, scaled for floor
-    pub fn from_template(template: &EnemyTemplate, floor: i32) -> Self {
-        let scale = 1.0 + (floor as f32 - 1.0) * 0.1;
+    /// and (optionally) the player's recent typing performance
+    pub fn from_template(template: &EnemyTemplate, floor: i32, director: Option<&DifficultyDirector>) -> Self {
+        let scale = floor_scale(floor);
+        let hp_scale = scale * director.map(|d| d.enemy_hp_mult()).unwrap_or(1.0);
         Self {
             name: template.name.clone(),
-            max_hp: (template.base_hp as f32 * scale) as i32,
-            current_hp: (template.base_hp as f32 * scale) as i32,
+            max_hp: ((template.base_hp as f32 * hp_scale) as i32).max(1),
+            current_hp: ((template.base_hp as f32 * hp_scale) as i32).max(1),
             attack_power: (template.base_damage as f32 * scale) as i32,
             defense: (template.base_defense as f32 * scale) as i32,
             xp_reward: (template.xp_reward as f32 * scale) as i32,
@@ -50,12 +62,13 @@ impl Enemy {
             spare_condition: None,
             is_boss: false,
             typing_theme: template.typing_theme.clone(),
+            resistances: template.resistances(),
             attack_messages: template.attack_messages.clone(),
         }
     }
 
     /// Spawn a random enemy appropriate for the floor using GameData
-    pub fn random_for_floor_data(game_data: &GameData, floor: i32) -> Self {
+    pub fn random_for_floor_data(game_data: &GameData, floor: i32, director: Option<&DifficultyDirector>) -> Self {
         let tier = ((floor - 1) / 2 + 1).clamp(1, 7) as u32;
         let enemies = game_data.enemies.get_enemies_by_tier(tier);
         
@@ -64,22 +77,35 @@ impl Enemy {
             return Self::random_for_floor(floor);
         }
         
-        let mut rng = rand::thread_rng();
+        let mut rng = run_rng::stream(Stream::Enemies);
         let template = enemies.choose(&mut rng).unwrap();
-        Self::from_template(template, floor)
+        Self::from_template(template, floor, director)
     }
 
     /// Spawn an elite enemy using GameData
-    pub fn random_elite_data(game_data: &GameData, floor: i32) -> Self {
-        let mut enemy = Self::random_for_floor_data(game_data, floor);
-        enemy.name = format!("Elite {}", enemy.name);
-        enemy.max_hp = (enemy.max_hp as f32 * 1.5) as i32;
-        enemy.current_hp = enemy.max_hp;
-        enemy.attack_power = (enemy.attack_power as f32 * 1.3) as i32;
-        enemy.xp_reward = (enemy.xp_reward as f32 * 2.0) as i32;
-        enemy.gold_reward = (enemy.gold_reward as f32 * 2.0) as i32;
-        enemy.enemy_type = EnemyType::Elite;
-        enemy
+    pub fn random_elite_data(game_data: &GameData, floor: i32, director: Option<&DifficultyDirector>) -> Self {
+        Self::random_for_floor_data(game_data, floor, director).into_elite()
+    }
+
+    /// Spawn a zone's own elite (by template id), or any elite if the
+    /// template is missing
+    pub fn zone_elite_data(game_data: &GameData, elite_id: &str, floor: i32, director: Option<&DifficultyDirector>) -> Self {
+        match game_data.enemies.enemies.get(elite_id) {
+            Some(template) => Self::from_template(template, floor, director).into_elite(),
+            None => Self::random_elite_data(game_data, floor, director),
+        }
+    }
+
+    /// Toughen into an elite, with rewards to match
+    fn into_elite(mut self) -> Self {
+        self.name = format!("Elite {}", self.name);
+        self.max_hp = (self.max_hp as f32 * 1.5) as i32;
+        self.current_hp = self.max_hp;
+        self.attack_power = (self.attack_power as f32 * 1.3) as i32;
+        self.xp_reward = (self.xp_reward as f32 * 2.0) as i32;
+        self.gold_reward = (self.gold_reward as f32 * 2.0) as i32;
+        self.enemy_type = EnemyType::Elite;
+        self
     }
 
     /// Spawn a boss using GameData
@@ -90,7 +116,7 @@ impl Enemy {
             return Self::random_boss(floor);
         }
         
-        let mut rng = rand::thread_rng();
+        let mut rng = run_rng::stream(Stream::Enemies);
         let boss = bosses.choose(&mut rng).unwrap();
         let scale = 1.0 + (floor as f32 - 1.0) * 0.15;
         
@@ -113,6 +139,7 @@ impl Enemy {
             spare_condition: None,
             is_boss: true,
             typing_theme: "corruption".to_string(),
+            resistances: Resistances::for_theme("corruption"),
             attack_messages: boss.phase_transition_dialogue.clone(),
         }
     }
@@ -120,11 +147,16 @@ impl Enemy {
     // === Legacy methods for backwards compatibility ===
     
     pub fn random_for_floor(floor: i32) -> Self {
-        let mut rng = rand::thread_rng();
+        let mut rng = run_rng::stream(Stream::Enemies);
         let pool = Self::get_enemy_pool(floor);
         pool.choose(&mut rng).unwrap().clone()
     }
 
+    /// Names of the bosses that can wait on `floor`
+    pub fn boss_names(floor: i32) -> Vec<String> {
+        Self::get_boss_pool(floor).into_iter().map(|b| b.name).collect()
+    }
+
     pub fn random_elite(floor: i32) -> Self {
         let mut enemy = Self::random_for_floor(floor);
         enemy.name = format!("Elite {}", enemy.name);
@@ -138,14 +170,14 @@ impl Enemy {
     }
 
     pub fn random_boss(floor: i32) -> Self {
-        let mut rng = rand::thread_rng();
+        let mut rng = run_rng::stream(Stream::Enemies);
         let pool = Self::get_boss_pool(floor);
         pool.choose(&mut rng).unwrap().clone()
     }
 
     pub fn get_attack_message(&self) -> &str {
         if !self.attack_messages.is_empty() {
-            let mut rng = rand::thread_rng();
+            let mut rng = run_rng::stream(Stream::Dialogue);
             return self.attack_messages.choose(&mut rng)
                 .map(|s| s.as_str())
                 .unwrap_or("attacks");
@@ -157,7 +189,7 @@ impl Enemy {
             "hits you",
             "lunges at you",
         ];
-        let mut rng = rand::thread_rng();
+        let mut rng = run_rng::stream(Stream::Dialogue);
         messages.choose(&mut rng).unwrap()
     }
 
@@ -179,6 +211,7 @@ impl Enemy {
                 spare_condition: Some("Offer gold to flee".to_string()),
                 is_boss: false,
                 typing_theme: "fantasy".to_string(),
+                resistances: Resistances::for_theme("fantasy"),
                 attack_messages: vec!["lunges with a rusty dagger".to_string(), "throws a rock".to_string()],
             },
             Enemy {
@@ -196,6 +229,7 @@ impl Enemy {
                 spare_condition: None,
                 is_boss: false,
                 typing_theme: "fantasy".to_string(),
+                resistances: Resistances::for_theme("fantasy"),
                 attack_messages: vec!["swings a notched blade".to_string(), "charges shield-first".to_string()],
             },
             Enemy {
@@ -213,6 +247,7 @@ impl Enemy {
                 spare_condition: Some("Listen to its sorrows".to_string()),
                 is_boss: false,
                 typing_theme: "dark".to_string(),
+                resistances: Resistances::for_theme("dark"),
                 attack_messages: vec!["wails despairingly".to_string(), "reaches with spectral claws".to_string()],
             },
         ];
@@ -233,6 +268,7 @@ impl Enemy {
                 spare_condition: None,
                 is_boss: false,
                 typing_theme: "arcane".to_string(),
+                resistances: Resistances::for_theme("arcane"),
                 attack_messages: vec!["hurls arcane sparks".to_string(), "pulses with cold light".to_string()],
             },
             Enemy {
@@ -250,6 +286,7 @@ impl Enemy {
                 spare_condition: Some("Return its lost tome".to_string()),
                 is_boss: false,
                 typing_theme: "arcane".to_string(),
+                resistances: Resistances::for_theme("arcane"),
                 attack_messages: vec!["casts a waterlogged spell".to_string(), "throws a soggy book".to_string()],
             },
             Enemy {
@@ -267,6 +304,7 @@ impl Enemy {
                 spare_condition: None,
                 is_boss: false,
                 typing_theme: "fantasy".to_string(),
+                resistances: Resistances::for_theme("fantasy"),
                 attack_messages: vec!["swings a massive fist".to_string(), "stomps the ground".to_string()],
             },
         ];
@@ -287,6 +325,7 @@ impl Enemy {
                 spare_condition: None,
                 is_boss: false,
                 typing_theme: "nature".to_string(),
+                resistances: Resistances::for_theme("nature"),
                 attack_messages: vec!["spits venom".to_string(), "lunges with fangs bared".to_string()],
             },
             Enemy {
@@ -304,6 +343,7 @@ impl Enemy {
                 spare_condition: Some("Cure the corruption".to_string()),
                 is_boss: false,
                 typing_theme: "dark".to_string(),
+                resistances: Resistances::for_theme("dark"),
                 attack_messages: vec!["claws with corrupted hands".to_string(), "exhales toxic spores".to_string()],
             },
             Enemy {
@@ -321,6 +361,7 @@ impl Enemy {
                 spare_condition: Some("Purify its roots".to_string()),
                 is_boss: false,
                 typing_theme: "nature".to_string(),
+                resistances: Resistances::for_theme("nature"),
                 attack_messages: vec!["lashes with thorned vines".to_string(), "drops corrupted sap".to_string()],
             },
         ];
@@ -341,6 +382,7 @@ impl Enemy {
                 spare_condition: None,
                 is_boss: false,
                 typing_theme: "technology".to_string(),
+                resistances: Resistances::for_theme("technology"),
                 attack_messages: vec!["fires a steam bolt".to_string(), "swings a mechanical arm".to_string()],
             },
             Enemy {
@@ -358,6 +400,7 @@ impl Enemy {
                 spare_condition: Some("Show it the light".to_string()),
                 is_boss: false,
                 typing_theme: "dark".to_string(),
+                resistances: Resistances::for_theme("dark"),
                 attack_messages: vec!["strikes from the shadows".to_string(), "drains your essence".to_string()],
             },
         ];
@@ -378,6 +421,7 @@ impl Enemy {
                 spare_condition: None,
                 is_boss: false,
                 typing_theme: "dark".to_string(),
+                resistances: Resistances::for_theme("dark"),
                 attack_messages: vec!["entangles you in shadow threads".to_string(), "whispers doom".to_string()],
             },
             Enemy {
@@ -395,6 +439,7 @@ impl Enemy {
                 spare_condition: Some("Offer a fragment of your soul".to_string()),
                 is_boss: false,
                 typing_theme: "dark".to_string(),
+                resistances: Resistances::for_theme("dark"),
                 attack_messages: vec!["tears at your essence".to_string(), "feeds on your fear".to_string()],
             },
             Enemy {
@@ -412,6 +457,7 @@ impl Enemy {
                 spare_condition: Some("Speak its true name".to_string()),
                 is_boss: false,
                 typing_theme: "dark".to_string(),
+                resistances: Resistances::for_theme("dark"),
                 attack_messages: vec!["cleaves with a cursed blade".to_string(), "summons dark fire".to_string()],
             },
         ];
@@ -444,6 +490,7 @@ impl Enemy {
                     spare_condition: Some("Prove your worth through honor".to_string()),
                     is_boss: true,
                     typing_theme: "fantasy".to_string(),
+                    resistances: Resistances::for_theme("fantasy"),
                     attack_messages: vec![
                         "charges with spectral lance".to_string(),
                         "unleashes a devastating combo".to_string(),
@@ -467,6 +514,7 @@ impl Enemy {
                     spare_condition: None,
                     is_boss: true,
                     typing_theme: "dark".to_string(),
+                    resistances: Resistances::for_theme("dark"),
                     attack_messages: vec![
                         "tears reality asunder".to_string(),
                         "speaks in the language of endings".to_string(),
@@ -0,0 +1,190 @@
+//! Enemy Intent - What the enemy will do with its next turn
+//!
+//! A compact line over the enemy says what its next blow will be before it
+//! lands, so a stance or a riposte can be chosen for it rather than after:
+//! - the kind of blow: an ordinary strike, an enraged one, one that punishes
+//!   a fumbled heavy strike, or one a readied riposte will turn aside
+//! - about how much of your health it will take, and how much your shield
+//!   will take off it
+//! - when it lands: after the word being typed, or in a real-time fight
+//!   when the word timer runs out, whichever comes first
+//! - the chance it never lands, from its wounds and your evasion
+//!
+//! Design: the estimate is the enemy turn's own damage sum, asked of
+//! CombatState with the dice left out; an evasion, a stumble or a miss is
+//! shown as a chance rather than rolled. Nothing is kept: the intent is
+//! read off the fight each time it is drawn.
+
+use super::combat::{CombatPhase, CombatState, HEAVY_EXPOSURE_MULT};
+use super::player::Player;
+
+/// The kind of blow coming
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntentKind {
+    /// An ordinary blow
+    Strike,
+    /// An enraged blow, harder every turn
+    Frenzy,
+    /// A blow into the opening a fumbled heavy strike left
+    Punish,
+    /// A blow a readied riposte will send back
+    Turned,
+}
+
+impl IntentKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Strike => "Strike",
+            Self::Frenzy => "Enraged strike",
+            Self::Punish => "Punishing strike",
+            Self::Turned => "Riposted",
+        }
+    }
+}
+
+/// What the enemy's next turn will do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intent {
+    pub kind: IntentKind,
+    /// Health the blow will take, past any shield
+    pub damage: i32,
+    /// Damage the shield will take off it
+    pub absorbed: i32,
+    /// Chance the blow never lands
+    pub miss_chance: f32,
+    /// Seconds until the word timer runs out, when it runs
+    pub seconds: Option<f32>,
+}
+
+impl Intent {
+    /// The enemy's next turn, while the fight still has one
+    pub fn of(combat: &CombatState, player: &Player) -> Option<Self> {
+        if !matches!(combat.phase, CombatPhase::PlayerTurn | CombatPhase::EnemyTurn) {
+            return None;
+        }
+        let blow = combat.next_blow(player);
+        let kind = if combat.riposte_ready() {
+            IntentKind::Turned
+        } else if combat.exposed {
+            IntentKind::Punish
+        } else if combat.enrage.enraged {
+            IntentKind::Frenzy
+        } else {
+            IntentKind::Strike
+        };
+        let blow = match kind {
+            IntentKind::Turned => 0,
+            IntentKind::Punish => (blow as f32 * HEAVY_EXPOSURE_MULT).round() as i32,
+            _ => blow,
+        };
+        let absorbed = blow.min(combat.player_shield.max(0));
+        let wounds = combat.wound_effects();
+        let lands = (1.0 - combat.skill_evasion_chance) * (1.0 - wounds.stumble_chance) * (1.0 - wounds.miss_chance);
+        Some(Self {
+            kind,
+            damage: blow - absorbed,
+            absorbed,
+            miss_chance: (1.0 - lands).clamp(0.0, 1.0),
+            seconds: combat.scheduler.clock_runs().then_some(combat.time_remaining.max(0.0)),
+        })
+    }
+
+    /// When the blow lands
+    pub fn timing(&self) -> String {
+        match self.seconds {
+            Some(secs) => format!("after this word or in {:.1}s", secs),
+            None => "after this word".to_string(),
+        }
+    }
+
+    /// The intent as a line of plain text, without the clock
+    pub fn describe(&self) -> String {
+        let mut text = format!("{}, about {} damage", self.kind.name(), self.damage);
+        if self.absorbed > 0 {
+            text.push_str(&format!(", {} on your shield", self.absorbed));
+        }
+        if self.miss_chance > 0.0 {
+            text.push_str(&format!(", {:.0}% to miss", self.miss_chance * 100.0));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::enemy::{Enemy, EnemyType};
+    use crate::game::player::Class;
+    use crate::data::damage_types::Resistances;
+    use crate::game::combat_scheduler::CombatMode;
+    use crate::data::GameData;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn fight(attack: i32) -> (CombatState, Player) {
+        let enemy = Enemy {
+            name: "Ink Wraith".to_string(),
+            max_hp: 50,
+            current_hp: 50,
+            attack_power: attack,
+            defense: 0,
+            xp_reward: 0,
+            gold_reward: 0,
+            enemy_type: EnemyType::Normal,
+            ascii_art: String::new(),
+            battle_cry: String::new(),
+            defeat_message: String::new(),
+            spare_condition: None,
+            is_boss: false,
+            typing_theme: "easy".to_string(),
+            attack_messages: vec!["lashes out".to_string()],
+            resistances: Resistances::default(),
+        };
+        let combat = CombatState::new(enemy, Arc::new(GameData::new()), 1, 1, None, None);
+        (combat, Player::new("Tester".to_string(), Class::Wordsmith))
+    }
+
+    #[test]
+    fn test_the_estimate_is_what_the_enemy_turn_then_deals() {
+        let (mut combat, mut player) = fight(14);
+        let intent = Intent::of(&combat, &player).unwrap();
+        assert_eq!(intent.kind, IntentKind::Strike);
+        assert_eq!(intent.miss_chance, 0.0);
+        let before = player.hp;
+        combat.phase = CombatPhase::EnemyTurn;
+        combat.execute_enemy_turn(&mut player);
+        assert_eq!(before - player.hp, intent.damage);
+
+        // An opening makes it worse, a shield takes some, a riposte all
+        combat.exposed = true;
+        combat.player_shield = 3;
+        let punish = Intent::of(&combat, &player).unwrap();
+        assert_eq!(punish.kind, IntentKind::Punish);
+        assert_eq!(punish.absorbed, 3);
+        assert!(punish.damage + punish.absorbed > intent.damage);
+        combat.riposte_until = Some(Instant::now() + Duration::from_secs(2));
+        let turned = Intent::of(&combat, &player).unwrap();
+        assert_eq!((turned.kind, turned.damage, turned.absorbed), (IntentKind::Turned, 0, 0));
+
+        combat.phase = CombatPhase::Victory;
+        assert_eq!(Intent::of(&combat, &player), None);
+    }
+
+    #[test]
+    fn test_an_enraged_blow_counts_the_turn_it_lands_on() {
+        let (mut combat, player) = fight(20);
+        let calm = Intent::of(&combat, &player).unwrap();
+        assert!(calm.seconds.is_some());
+        combat.enrage.enraged = true;
+        let enraged = Intent::of(&combat, &player).unwrap();
+        assert_eq!(enraged.kind, IntentKind::Frenzy);
+        // The turn itself ramps it before it strikes
+        assert!(enraged.damage > calm.damage);
+        assert!(enraged.describe().starts_with("Enraged strike, about"));
+
+        combat.set_mode(CombatMode::TurnBased);
+        let waiting = Intent::of(&combat, &player).unwrap();
+        assert_eq!(waiting.seconds, None);
+        assert_eq!(waiting.timing(), "after this word");
+    }
+}
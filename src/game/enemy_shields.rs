@@ -0,0 +1,99 @@
+//! Enemy Shields - Armor that only gives way to the right kind of strike
+//!
+//! Elites come armored with a few shield pips. While any pip stands, every
+//! word only chips at the enemy. Pips shatter only to a Precision strike
+//! (flawless and fast) or a Flurry (very fast), so a player who has settled
+//! into one comfortable style has to deliberately change how they type.
+//!
+//! Design: the shield belongs to the fight, not the enemy template;
+//! CombatState rolls it from the enemy type and floor at the start.
+
+use super::enemy::EnemyType;
+use super::typing_impact::AttackType;
+
+/// Damage kept while the shield is up
+pub const SHIELDED_DAMAGE_MULT: f32 = 0.25;
+/// Pips every elite starts with
+const BASE_PIPS: u32 = 2;
+/// One extra pip per this many floors
+const FLOORS_PER_PIP: u32 = 4;
+/// Most pips a shield can have
+const MAX_PIPS: u32 = 4;
+
+/// What a landed word did to the shield
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShieldHit {
+    /// A pip shattered; this many remain
+    Shattered { remaining: u32 },
+    /// The wrong kind of strike glanced off
+    Deflected,
+}
+
+/// An enemy's shield for one fight
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnemyShield {
+    pub pips: u32,
+    pub max_pips: u32,
+}
+
+impl EnemyShield {
+    /// The shield an enemy brings to a fight on this floor (elites only)
+    pub fn for_enemy(enemy_type: EnemyType, floor: u32) -> Self {
+        if enemy_type != EnemyType::Elite {
+            return Self::default();
+        }
+        let pips = (BASE_PIPS + floor / FLOORS_PER_PIP).min(MAX_PIPS);
+        Self { pips, max_pips: pips }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.pips > 0
+    }
+
+    /// Attack types that can shatter a pip
+    pub fn breaks_to(attack: AttackType) -> bool {
+        matches!(attack, AttackType::Precision | AttackType::Flurry)
+    }
+
+    /// Damage multiplier while the shield stands
+    pub fn damage_mult(&self) -> f32 {
+        if self.is_up() { SHIELDED_DAMAGE_MULT } else { 1.0 }
+    }
+
+    /// Resolve a landed word against the shield (None once it is down)
+    pub fn on_hit(&mut self, attack: AttackType) -> Option<ShieldHit> {
+        if !self.is_up() {
+            return None;
+        }
+        if !Self::breaks_to(attack) {
+            return Some(ShieldHit::Deflected);
+        }
+        self.pips -= 1;
+        Some(ShieldHit::Shattered { remaining: self.pips })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_elites_are_shielded() {
+        assert!(!EnemyShield::for_enemy(EnemyType::Normal, 8).is_up());
+        assert!(!EnemyShield::for_enemy(EnemyType::Boss, 8).is_up());
+        assert_eq!(EnemyShield::for_enemy(EnemyType::Elite, 1).pips, BASE_PIPS);
+        assert_eq!(EnemyShield::for_enemy(EnemyType::Elite, 40).pips, MAX_PIPS);
+    }
+
+    #[test]
+    fn test_pips_break_to_precision_and_flurry() {
+        let mut shield = EnemyShield::for_enemy(EnemyType::Elite, 1);
+        assert_eq!(shield.on_hit(AttackType::Standard), Some(ShieldHit::Deflected));
+        assert_eq!(shield.on_hit(AttackType::Deliberate), Some(ShieldHit::Deflected));
+        assert_eq!(shield.on_hit(AttackType::Precision), Some(ShieldHit::Shattered { remaining: 1 }));
+        assert_eq!(shield.damage_mult(), SHIELDED_DAMAGE_MULT);
+        assert_eq!(shield.on_hit(AttackType::Flurry), Some(ShieldHit::Shattered { remaining: 0 }));
+        assert_eq!(shield.damage_mult(), 1.0);
+        assert_eq!(shield.on_hit(AttackType::Frantic), None);
+    }
+}
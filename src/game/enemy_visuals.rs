@@ -5,7 +5,11 @@
 //! - Posture shifts from confident to dying
 //! - Blood/damage effects appear
 //!
-//! Design: A dying enemy should LOOK dying
+//! Wounds aren't only for show: where they land changes how the enemy
+//! fights. Wounded arms hit softer, wounded legs stumble and lose turns,
+//! and a wounded head swings wide and misses.
+//!
+//! Design: A dying enemy should LOOK dying - and fight like it
 
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -41,15 +45,22 @@ pub enum EnemyPosture {
 }
 
 impl EnemyPosture {
-    pub fn from_health_pct(pct: f32) -> Self {
-        match pct {
-            p if p > 0.75 => Self::Confident,
-            p if p > 0.50 => Self::Wary,
-            p if p > 0.25 => Self::Staggered,
-            p if p > 0.10 => Self::Wounded,
-            _ => Self::Dying,
+    pub const ALL: [EnemyPosture; 5] = [Self::Confident, Self::Wary, Self::Staggered, Self::Wounded, Self::Dying];
+
+    /// The share of health the posture is taken at, from above
+    pub fn threshold(&self) -> f32 {
+        match self {
+            Self::Confident => 1.0,
+            Self::Wary => 0.75,
+            Self::Staggered => 0.50,
+            Self::Wounded => 0.25,
+            Self::Dying => 0.10,
         }
     }
+
+    pub fn from_health_pct(pct: f32) -> Self {
+        Self::ALL.into_iter().rev().find(|p| pct <= p.threshold()).unwrap_or(Self::Confident)
+    }
     
     pub fn name(&self) -> &'static str {
         match self {
@@ -78,6 +89,8 @@ pub struct DamageOverlays {
 pub struct WoundMarker {
     /// Position in ASCII art (row, col)
     pub position: (usize, usize),
+    /// Where on the body it landed
+    pub location: HitLocation,
     /// Severity of wound
     pub severity: WoundSeverity,
     /// Character to display
@@ -130,7 +143,7 @@ pub struct DamageParticle {
 }
 
 /// Hit location for damage
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HitLocation {
     Head,
     Torso,
@@ -141,6 +154,56 @@ pub enum HitLocation {
     Random,
 }
 
+/// Attack power lost per point of arm wound severity
+const ARM_WEAKEN: f32 = 0.04;
+/// Weakest arm wounds can make an enemy's attacks
+const MIN_ARM_MULT: f32 = 0.5;
+/// Chance to stumble per point of leg wound severity
+const LEG_STUMBLE: f32 = 0.03;
+/// Chance to miss per point of head wound severity
+const HEAD_MISS: f32 = 0.03;
+/// Highest stumble or miss chance wounds can cause
+const MAX_WOUND_CHANCE: f32 = 0.3;
+
+/// How an enemy's wounds change the way it fights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WoundEffects {
+    /// Multiplier on attack power (arm wounds)
+    pub attack_mult: f32,
+    /// Chance to stumble and lose its attack (leg wounds)
+    pub stumble_chance: f32,
+    /// Chance its attack misses (head wounds)
+    pub miss_chance: f32,
+}
+
+impl Default for WoundEffects {
+    fn default() -> Self {
+        Self { attack_mult: 1.0, stumble_chance: 0.0, miss_chance: 0.0 }
+    }
+}
+
+impl HitLocation {
+    /// A body part at random
+    pub fn random(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..5) {
+            0 => Self::Head,
+            1 => Self::Torso,
+            2 => Self::LeftArm,
+            3 => Self::RightArm,
+            _ => Self::Legs,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Head => "HEAD",
+            Self::Torso | Self::Center | Self::Random => "BODY",
+            Self::LeftArm | Self::RightArm => "ARM",
+            Self::Legs => "LEGS",
+        }
+    }
+}
+
 impl Default for EnemyVisualState {
     fn default() -> Self {
         Self::new(vec![
@@ -185,6 +248,7 @@ impl EnemyVisualState {
         // Add wound marker
         self.damage_overlays.wounds.push(WoundMarker {
             position: pos,
+            location,
             severity,
             char_override: severity.char(),
         });
@@ -204,6 +268,26 @@ impl EnemyVisualState {
         self.cached_render = None;
     }
     
+    /// Total wound severity at the given body parts
+    fn severity_at(&self, locations: &[HitLocation]) -> u32 {
+        self.damage_overlays.wounds.iter()
+            .filter(|w| locations.contains(&w.location))
+            .map(|w| w.severity.value())
+            .sum()
+    }
+    
+    /// How the wounds so far change the enemy's fighting
+    pub fn wound_effects(&self) -> WoundEffects {
+        let arms = self.severity_at(&[HitLocation::LeftArm, HitLocation::RightArm]) as f32;
+        let legs = self.severity_at(&[HitLocation::Legs]) as f32;
+        let head = self.severity_at(&[HitLocation::Head]) as f32;
+        WoundEffects {
+            attack_mult: (1.0 - arms * ARM_WEAKEN).max(MIN_ARM_MULT),
+            stumble_chance: (legs * LEG_STUMBLE).min(MAX_WOUND_CHANCE),
+            miss_chance: (head * HEAD_MISS).min(MAX_WOUND_CHANCE),
+        }
+    }
+    
     /// Update posture based on cumulative damage
     fn update_posture(&mut self) {
         self.posture = match self.damage_overlays.total_severity {
@@ -395,6 +479,10 @@ mod tests {
         assert_eq!(EnemyPosture::from_health_pct(0.3), EnemyPosture::Staggered);
         assert_eq!(EnemyPosture::from_health_pct(0.15), EnemyPosture::Wounded);
         assert_eq!(EnemyPosture::from_health_pct(0.05), EnemyPosture::Dying);
+        // Each posture is taken at its threshold, not past it
+        assert_eq!(EnemyPosture::from_health_pct(0.75), EnemyPosture::Wary);
+        assert_eq!(EnemyPosture::from_health_pct(0.10), EnemyPosture::Dying);
+        assert_eq!(EnemyPosture::from_health_pct(1.5), EnemyPosture::Confident);
     }
     
     #[test]
@@ -408,5 +496,25 @@ mod tests {
         state.apply_damage(0.20, HitLocation::Torso);
         assert!(state.damage_overlays.wounds.len() > 0);
         assert!(state.damage_overlays.total_severity > 0);
+        // A torso wound changes nothing about how it fights
+        assert_eq!(state.wound_effects(), WoundEffects::default());
+    }
+    
+    #[test]
+    fn test_wounds_shape_behavior() {
+        let mut state = EnemyVisualState::new(vec!["  O  ".to_string(), " /|\\ ".to_string()]);
+        state.apply_damage(0.30, HitLocation::LeftArm);
+        state.apply_damage(0.10, HitLocation::Legs);
+        let effects = state.wound_effects();
+        assert!((effects.attack_mult - 0.84).abs() < 1e-6);
+        assert!((effects.stumble_chance - 0.06).abs() < 1e-6);
+        assert_eq!(effects.miss_chance, 0.0);
+        for _ in 0..20 {
+            state.apply_damage(0.30, HitLocation::Head);
+            state.apply_damage(0.30, HitLocation::RightArm);
+        }
+        let effects = state.wound_effects();
+        assert_eq!(effects.attack_mult, MIN_ARM_MULT);
+        assert_eq!(effects.miss_chance, MAX_WOUND_CHANCE);
     }
 }
@@ -0,0 +1,163 @@
+//! Enrage - Long fights turn ugly
+//!
+//! Every enemy has a limit on how long it will trade blows politely. Some
+//! count seconds, others count the words thrown at them. Near the limit
+//! the fight gives a warning; past it the enemy enrages, and its attacks
+//! grow stronger with every turn until one of you falls.
+//!
+//! Design: the timer only counts and reports; CombatState feeds it fight
+//! time and words typed, applies the attack multiplier, and hands the
+//! warning and the enrage itself to the immersion layer to voice.
+
+use super::combat_immersion::infer_enemy_theme;
+use super::enemy::{Enemy, EnemyType};
+
+/// Share of the threshold at which the warning comes
+const WARNING_AT: f32 = 0.75;
+/// Attack power gained per enemy turn once enraged
+const RAMP_PER_TURN: f32 = 0.25;
+/// Most attack power an enrage can build
+const MAX_ATTACK_MULT: f32 = 3.0;
+
+/// What an enemy counts toward its enrage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnrageThreshold {
+    /// Seconds of fighting
+    Seconds(f32),
+    /// Words typed at it
+    Words(i32),
+}
+
+/// Something the timer wants announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnrageEvent {
+    /// The limit is close
+    Warning,
+    /// The limit was reached
+    Enraged,
+}
+
+/// An enemy's enrage clock for one fight
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnrageTimer {
+    pub threshold: EnrageThreshold,
+    /// Fight time counted so far (pauses excluded)
+    pub elapsed_secs: f32,
+    pub warned: bool,
+    pub enraged: bool,
+    /// Enemy turns taken since enraging
+    pub ramp: u32,
+}
+
+impl EnrageTimer {
+    /// Bosses and elites hold out longer; mechanical enemies (golems,
+    /// constructs, clockwork) count words
+    pub fn for_enemy(enemy: &Enemy) -> Self {
+        let threshold = match (enemy.enemy_type, infer_enemy_theme(&enemy.name).as_str()) {
+            (EnemyType::Boss, _) => EnrageThreshold::Seconds(120.0),
+            (EnemyType::Elite, _) => EnrageThreshold::Words(18),
+            (_, "mechanical") => EnrageThreshold::Words(12),
+            _ => EnrageThreshold::Seconds(60.0),
+        };
+        Self { threshold, elapsed_secs: 0.0, warned: false, enraged: false, ramp: 0 }
+    }
+
+    /// Count fight time
+    pub fn advance(&mut self, secs: f32) {
+        self.elapsed_secs += secs;
+    }
+
+    /// How close the enemy is to its limit (1.0 = reached)
+    pub fn progress(&self, words_typed: i32) -> f32 {
+        match self.threshold {
+            EnrageThreshold::Seconds(limit) => self.elapsed_secs / limit,
+            EnrageThreshold::Words(limit) => words_typed as f32 / limit.max(1) as f32,
+        }
+    }
+
+    /// Check the limit; each event is reported once
+    pub fn check(&mut self, words_typed: i32) -> Option<EnrageEvent> {
+        let progress = self.progress(words_typed);
+        if !self.enraged && progress >= 1.0 {
+            self.enraged = true;
+            self.warned = true;
+            Some(EnrageEvent::Enraged)
+        } else if !self.warned && progress >= WARNING_AT {
+            self.warned = true;
+            Some(EnrageEvent::Warning)
+        } else {
+            None
+        }
+    }
+
+    /// An enemy turn passed: an enraged enemy hits harder each time
+    pub fn on_enemy_turn(&mut self) {
+        if self.enraged {
+            self.ramp += 1;
+        }
+    }
+
+    /// Multiplier on the enemy's attack power
+    pub fn attack_mult(&self) -> f32 {
+        if self.enraged {
+            (1.0 + self.ramp as f32 * RAMP_PER_TURN).min(MAX_ATTACK_MULT)
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timer(threshold: EnrageThreshold) -> EnrageTimer {
+        EnrageTimer { threshold, elapsed_secs: 0.0, warned: false, enraged: false, ramp: 0 }
+    }
+
+    #[test]
+    fn test_constructs_count_words() {
+        let mut enemy = Enemy::random_for_floor(1);
+        enemy.enemy_type = EnemyType::Normal;
+        enemy.name = "Stone Golem".to_string();
+        assert_eq!(EnrageTimer::for_enemy(&enemy).threshold, EnrageThreshold::Words(12));
+        enemy.name = "Goblin Scout".to_string();
+        assert_eq!(EnrageTimer::for_enemy(&enemy).threshold, EnrageThreshold::Seconds(60.0));
+        enemy.enemy_type = EnemyType::Boss;
+        assert_eq!(EnrageTimer::for_enemy(&enemy).threshold, EnrageThreshold::Seconds(120.0));
+    }
+
+    #[test]
+    fn test_warns_then_enrages_once() {
+        let mut enrage = timer(EnrageThreshold::Seconds(60.0));
+        enrage.advance(30.0);
+        assert_eq!(enrage.check(0), None);
+        enrage.advance(15.0);
+        assert_eq!(enrage.check(0), Some(EnrageEvent::Warning));
+        assert_eq!(enrage.check(0), None);
+        enrage.advance(15.0);
+        assert_eq!(enrage.check(0), Some(EnrageEvent::Enraged));
+        assert_eq!(enrage.check(0), None);
+
+        // A word-counting enemy that blows straight past the warning
+        let mut enrage = timer(EnrageThreshold::Words(12));
+        assert_eq!(enrage.check(20), Some(EnrageEvent::Enraged));
+        assert_eq!(enrage.check(21), None);
+    }
+
+    #[test]
+    fn test_attack_ramps_after_enrage() {
+        let mut enrage = timer(EnrageThreshold::Words(4));
+        enrage.on_enemy_turn();
+        assert_eq!(enrage.attack_mult(), 1.0);
+        enrage.check(4);
+        assert_eq!(enrage.attack_mult(), 1.0);
+        enrage.on_enemy_turn();
+        enrage.on_enemy_turn();
+        assert_eq!(enrage.attack_mult(), 1.5);
+        for _ in 0..20 {
+            enrage.on_enemy_turn();
+        }
+        assert_eq!(enrage.attack_mult(), MAX_ATTACK_MULT);
+    }
+}
@@ -0,0 +1,244 @@
+//! Epilogue - The run, written up once it is over
+//!
+//! When a run ends, in death or in victory, it is told in a few short
+//! paragraphs:
+//! - how it ended: the class, the floor, the ending earned
+//! - the choices made in encounters, each told in the tone of the place it
+//!   was made (Haven plain and warm, the Athenaeum careful, the Corruption
+//!   broken, as the writing guidelines set out)
+//! - the factions whose standing moved, and the people who will remember
+//! - the clues carried out of the dark
+//!
+//! The epilogue is kept with the run in the run history, and shown beside
+//! the final stats.
+//!
+//! Design: the sentences are a template grammar (`data/epilogue.ron`), like
+//! combat dialogue. What only the run itself knows (its choices, who it
+//! moved, the standings it began with) is kept in a `RunLog` that
+//! `start_new_game` resets; the rest is read off the run's state.
+
+use std::sync::OnceLock;
+
+use rand::Rng;
+
+use super::encounter_writing::{AuthoredEncounter, EncounterChoice};
+use super::faction_system::FactionRelations;
+use super::narrative::Faction;
+use super::text_grammar::Grammar;
+use super::writing_guidelines::tone_of;
+
+/// Slots the epilogue grammar can fill
+pub const SLOTS: [&str; 11] = ["class", "floor", "ending", "encounter", "choice", "place", "faction", "npc", "clue", "count", "requirement"];
+
+/// How far a standing must move in a run to be told
+pub const STANDING_SHIFT: i32 = 5;
+
+/// Most choices told, the latest ones
+const MAX_CHOICES: usize = 4;
+
+const BUNDLED: &str = include_str!("../../data/epilogue.ron");
+
+static GRAMMAR: OnceLock<Grammar> = OnceLock::new();
+
+fn grammar() -> &'static Grammar {
+    GRAMMAR.get_or_init(|| Grammar::parse(BUNDLED).unwrap_or_else(|e| panic!("bundled epilogue is broken: {}", e)))
+}
+
+/// One choice made in an encounter this run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChoiceMade {
+    pub encounter: String,
+    pub location: String,
+    pub choice: String,
+    pub carried: bool,
+}
+
+/// What the run did that its state won't show at the end
+#[derive(Debug, Clone, Default)]
+pub struct RunLog {
+    pub choices: Vec<ChoiceMade>,
+    /// Opinion changes by person (name, net change), in order first moved
+    pub opinions: Vec<(String, i32)>,
+    pub standings_at_start: Vec<(Faction, i32)>,
+}
+
+impl RunLog {
+    /// A log for a run starting with these standings
+    pub fn new(factions: &FactionRelations) -> Self {
+        Self {
+            standings_at_start: factions.standings.iter().map(|(f, s)| (*f, *s)).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn note_choice(&mut self, encounter: &AuthoredEncounter, choice: &EncounterChoice, carried: bool) {
+        self.choices.push(ChoiceMade {
+            encounter: encounter.title.clone(),
+            location: encounter.valid_locations.first().cloned().unwrap_or_default(),
+            choice: choice.text.clone(),
+            carried,
+        });
+    }
+
+    pub fn note_opinion(&mut self, name: &str, change: i32) {
+        match self.opinions.iter_mut().find(|(n, _)| n == name) {
+            Some((_, total)) => *total += change,
+            None => self.opinions.push((name.to_string(), change)),
+        }
+    }
+}
+
+/// What the epilogue is written from
+pub struct EpilogueSources<'a> {
+    pub victory: bool,
+    pub class: &'a str,
+    pub floor: i32,
+    /// The ending earned, if any, as (id, title)
+    pub ending: Option<(&'a str, &'a str)>,
+    /// The mystery ending a run came nearest without reaching, as (title,
+    /// the requirement it fell short of)
+    pub near_miss: Option<(&'a str, &'a str)>,
+    pub log: &'a RunLog,
+    pub factions: &'a FactionRelations,
+    /// Clues pinned this run, in the order pinned
+    pub clues: &'a [String],
+}
+
+/// "haven_market" → "Haven Market"
+fn place_name(location: &str) -> String {
+    location.split('_')
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Expand the first of `rules` the epilogue grammar has
+fn say<R: Rng>(rules: &[&str], slots: &[(&str, String)], rng: &mut R) -> String {
+    let rules: Vec<String> = rules.iter().map(|r| r.to_string()).collect();
+    grammar().expand_first(&rules, slots, rng).unwrap_or_default()
+}
+
+/// Write the run's epilogue, a paragraph to an entry
+pub fn compose<R: Rng>(sources: &EpilogueSources, rng: &mut R) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+
+    let outcome = if sources.victory { "victory" } else { "death" };
+    let mut opening = say(&[&format!("opening_{}", outcome)], &[
+        ("class", sources.class.to_string()),
+        ("floor", sources.floor.to_string()),
+    ], rng);
+    if let Some((id, title)) = sources.ending {
+        opening = format!("{} {}", opening, say(&[&format!("ending_{}", id), "ending"], &[("ending", title.to_string())], rng));
+    }
+    if let Some((title, requirement)) = sources.near_miss {
+        let mut chars = requirement.chars();
+        let requirement: String = chars.next().map(|c| c.to_lowercase().chain(chars).collect()).unwrap_or_default();
+        opening = format!("{} {}", opening, say(&["near_miss"], &[("ending", title.to_string()), ("requirement", requirement)], rng));
+    }
+    paragraphs.push(opening);
+
+    let choices = &sources.log.choices;
+    let told: Vec<String> = choices[choices.len().saturating_sub(MAX_CHOICES)..].iter()
+        .map(|c| {
+            let rule = if c.carried { format!("choice_{}", tone_of(&c.location)) } else { "choice_faltered".to_string() };
+            let slots = [
+                ("encounter", c.encounter.clone()),
+                ("choice", c.choice.trim_end_matches('.').to_string()),
+                ("place", place_name(&c.location)),
+            ];
+            say(&[&rule, "choice"], &slots, rng)
+        })
+        .collect();
+    paragraphs.push(if told.is_empty() { say(&["no_choices"], &[], rng) } else { told.join(" ") });
+
+    let mut moved = Vec::new();
+    let mut shifts: Vec<(Faction, i32)> = sources.factions.standings.iter()
+        .map(|(f, now)| {
+            let before = sources.log.standings_at_start.iter().find(|(g, _)| g == f).map_or(0, |(_, s)| *s);
+            (*f, now - before)
+        })
+        .filter(|(_, shift)| shift.abs() >= STANDING_SHIFT)
+        .collect();
+    shifts.sort_by_key(|(f, shift)| (-shift.abs(), f.name()));
+    for (faction, shift) in shifts {
+        let rule = if shift > 0 { "faction_rose" } else { "faction_fell" };
+        moved.push(say(&[rule], &[("faction", faction.name().to_string())], rng));
+    }
+    for (npc, change) in sources.log.opinions.iter().filter(|(_, c)| *c != 0) {
+        let rule = if *change > 0 { "npc_warmer" } else { "npc_colder" };
+        moved.push(say(&[rule], &[("npc", npc.clone())], rng));
+    }
+    if !moved.is_empty() {
+        paragraphs.push(moved.join(" "));
+    }
+
+    if let Some(last) = sources.clues.last() {
+        paragraphs.push(say(&["clues"], &[("count", sources.clues.len().to_string()), ("clue", last.clone())], rng));
+    }
+
+    paragraphs.push(say(&[&format!("closing_{}", outcome)], &[], rng));
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bundled_epilogue_resolves() {
+        let grammar = Grammar::parse(BUNDLED).unwrap();
+        assert!(grammar.check(&SLOTS).is_empty(), "{:?}", grammar.check(&SLOTS));
+        for tone in ["haven", "athenaeum", "corruption_zone", "gearhold", "shadow_quarter", "grove"] {
+            assert!(grammar.has(&format!("choice_{}", tone)), "no choices told in the {} tone", tone);
+        }
+    }
+
+    #[test]
+    fn test_epilogue_tells_what_the_run_changed() {
+        let mut factions = FactionRelations::new();
+        let mut log = RunLog::new(&factions);
+        log.choices.push(ChoiceMade {
+            encounter: "The Sealed Letter".to_string(),
+            location: "haven_inn".to_string(),
+            choice: "Carry the letter below.".to_string(),
+            carried: true,
+        });
+        log.note_opinion("Vera", 10);
+        log.note_opinion("Vera", -4);
+        factions.modify_standing(Faction::MagesGuild, 15);
+        let clues = vec!["A name scratched out of every ledger.".to_string()];
+        let sources = EpilogueSources {
+            victory: false,
+            class: "Wordsmith",
+            floor: 4,
+            ending: None,
+            near_miss: None,
+            log: &log,
+            factions: &factions,
+            clues: &clues,
+        };
+        let paragraphs = compose(&sources, &mut StdRng::seed_from_u64(7));
+        let text = paragraphs.join("\n");
+        assert!(text.contains("floor 4") || text.contains("Wordsmith"));
+        assert!(text.contains("\"Carry the letter below\"") && text.contains("The Sealed Letter"));
+        assert!(text.contains(Faction::MagesGuild.name()) && text.contains("Vera"));
+        assert!(text.contains("ledger") && !text.contains('#'));
+
+        // A quiet run still gets an opening, a line about its choices, a close
+        let quiet = RunLog::new(&factions);
+        let sources = EpilogueSources { log: &quiet, clues: &[], ..sources };
+        assert_eq!(compose(&sources, &mut StdRng::seed_from_u64(7)).len(), 3);
+
+        // Each mystery ending is told its own way; one missed is told too
+        let won = EpilogueSources { victory: true, ending: Some(("final_rest", "The Final Rest")), ..sources };
+        assert!(compose(&won, &mut StdRng::seed_from_u64(7))[0].contains("did not come back"));
+        let missed = EpilogueSources { ending: Some(("unbound", "The Unbound Word")), near_miss: Some(("The Final Rest", "Sacrifice yourself to seal the Breach")), ..won };
+        let opening = &compose(&missed, &mut StdRng::seed_from_u64(7))[0];
+        assert!(opening.contains("It ended as The Unbound Word.") && opening.contains("sacrifice yourself to seal the Breach"));
+    }
+}
@@ -1,8 +1,10 @@
 //! Random events - Undertale-style encounters!
 
 use serde::{Deserialize, Serialize};
+use super::curses::Curse;
 use super::narrative::Faction;
 use rand::seq::SliceRandom;
+use rand::Rng;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameEvent {
@@ -31,13 +33,41 @@ pub enum EventOutcome {
     Combat,
     /// Gain or lose reputation with a faction
     FactionRep(Faction, i32),
+    /// Carry cursed loot, its power and its drawback both
+    TakeCurse(Curse),
 }
 
 impl GameEvent {
-    pub fn random() -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn random(rng: &mut impl Rng) -> Self {
         let events = Self::get_event_pool();
-        events.choose(&mut rng).unwrap().clone()
+        events.choose(rng).unwrap().clone()
+    }
+
+    /// A corrupted chest: take the cursed loot or leave it sealed
+    pub fn corrupted_chest(curse: Curse) -> Self {
+        GameEvent {
+            name: "A Corrupted Chest".to_string(),
+            description: format!(
+                "The chest's lid is furred with corruption. Inside lies the {}, humming with power ({}).",
+                curse.name, curse.describe()
+            ),
+            choices: vec![
+                EventChoice {
+                    text: format!("Take the {}", curse.name),
+                    outcome: EventOutcome::TakeCurse(curse),
+                },
+                EventChoice {
+                    text: "Leave it sealed".to_string(),
+                    outcome: EventOutcome::Nothing,
+                },
+            ],
+            ascii_art: concat!(
+                "   ___________\n",
+                "  /  ~ ~ ~ ~  \\\n",
+                " |=====[☠]=====|\n",
+                " |_____________|"
+            ).to_string(),
+        }
     }
 
     fn get_event_pool() -> Vec<Self> {
@@ -211,7 +241,7 @@ impl GameEvent {
             return easter_eggs.choose(&mut rng).unwrap().clone();
         }
         
-        Self::random()
+        Self::random(&mut rng)
     }
     
     fn get_baklava_events() -> Vec<Self> {
@@ -0,0 +1,64 @@
+//! Executions - A clean ending for an enemy on its last legs
+//!
+//! Land a Precision strike on a Dying enemy and it drops to its knees
+//! instead of falling. The next prompt is a finisher phrase: type it and
+//! the enemy is executed, with a kill message of its own and a bigger
+//! share of XP and gold. Fumble it and the fight simply goes on.
+//!
+//! Damage beyond what a killing blow needed is counted as overkill and
+//! kept in the run's statistics.
+
+use super::enemy_visuals::EnemyPosture;
+use super::typing_impact::AttackType;
+
+/// XP and gold multiplier for an executed enemy
+pub const EXECUTION_REWARD_MULT: f32 = 1.5;
+
+/// Finisher phrases and the kill line each one earns
+const FINISHERS: [(&str, &str); 5] = [
+    ("your story ends here", "closes the book on"),
+    ("i strike your name from the page", "strikes from the record"),
+    ("let the ink run dry", "drains the last ink from"),
+    ("the final word is mine", "has the final word over"),
+    ("return to the blank page", "returns to the blank page"),
+];
+
+/// Whether this blow brings a finisher within reach
+pub fn offers_execution(posture: EnemyPosture, attack: AttackType) -> bool {
+    posture == EnemyPosture::Dying && attack == AttackType::Precision
+}
+
+/// The finisher an enemy must be typed down with (always the same per enemy)
+pub fn finisher_phrase(enemy_name: &str) -> &'static str {
+    FINISHERS[finisher_index(enemy_name)].0
+}
+
+/// The kill message for executing an enemy
+pub fn kill_message(enemy_name: &str) -> String {
+    format!("☠ EXECUTION! Your final word {} {}.", FINISHERS[finisher_index(enemy_name)].1, enemy_name)
+}
+
+fn finisher_index(enemy_name: &str) -> usize {
+    enemy_name.bytes().map(usize::from).sum::<usize>() % FINISHERS.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_precision_on_dying() {
+        assert!(offers_execution(EnemyPosture::Dying, AttackType::Precision));
+        assert!(!offers_execution(EnemyPosture::Wounded, AttackType::Precision));
+        assert!(!offers_execution(EnemyPosture::Dying, AttackType::Flurry));
+    }
+
+    #[test]
+    fn test_finisher_is_stable_per_enemy() {
+        assert_eq!(finisher_phrase("Ink Wraith"), finisher_phrase("Ink Wraith"));
+        assert!(kill_message("Ink Wraith").contains("Ink Wraith"));
+        for (phrase, _) in FINISHERS {
+            assert_eq!(phrase, phrase.to_lowercase());
+        }
+    }
+}
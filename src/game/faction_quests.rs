@@ -0,0 +1,360 @@
+//! Faction Quests - A questline for each faction, ending in an oath
+//!
+//! Each faction has a questline of three encounters, drawn from what it
+//! hides and what divides it:
+//! - each step waits on the one before it
+//! - each step asks for more standing with the faction than the last
+//! - the last step is an oath, and a timeline can swear only one
+//!
+//! Playing a step raises standing with its faction (its rivals notice,
+//! through the usual ripple) and opens the next step, so a questline under
+//! way shows in the journal as a chain. Swearing an oath sets a world flag
+//! that keeps every other faction's oath away until the timeline is wiped.
+//!
+//! Design: the questlines are data in `QUESTLINES`, built into ordinary
+//! authored encounters beside the rest; chaining, gates and lockouts are
+//! all plain `EncounterRequirements`, so the scheduler needs nothing new.
+
+use std::collections::BTreeMap;
+
+use super::encounter_writing::{
+    AuthoredEncounter, DialogueLine, EncounterChoice, EncounterConsequences, EncounterContent, EncounterRequirements,
+    EncounterTypingChallenge,
+};
+
+/// Standing each step asks for with its faction, by step
+pub const STEP_STANDING: [i32; 3] = [0, 10, 25];
+/// Standing each step played earns with its faction
+pub const STEP_REWARD: i32 = 15;
+
+/// World flag set by swearing `faction`'s oath
+pub fn oath_flag(faction: &str) -> String {
+    format!("sworn_{}", faction.to_lowercase())
+}
+
+/// One encounter of a questline
+struct QuestStep {
+    id: &'static str,
+    title: &'static str,
+    locations: &'static [&'static str],
+    description: &'static str,
+    /// What they say: (speaker, line)
+    line: (&'static str, &'static str),
+    /// What you do: (id, text)
+    choice: (&'static str, &'static str),
+    narrative: &'static str,
+}
+
+/// A faction's questline; the last step is its oath
+struct Questline {
+    /// Faction id, as used in reputation changes
+    faction: &'static str,
+    steps: [QuestStep; 3],
+    /// The words of the oath, typed to swear it
+    oath: &'static str,
+}
+
+static QUESTLINES: [Questline; 5] = [
+    Questline {
+        faction: "Scribes",
+        steps: [
+            QuestStep {
+                id: "scribes_sealed_letter",
+                title: "A Sealed Letter",
+                locations: &["haven", "haven_inn"],
+                description: "A junior Scribe presses a letter into your hands and is gone before you can speak. \
+                    The seal is the Guild's, cracked and resealed in a different wax.",
+                line: ("Junior Scribe", "Take it to the Athenaeum. Not to the Archmage. To the Sealers."),
+                choice: ("carry_letter", "Carry the letter below."),
+                narrative: "The letter is warm, as though something inside it is still being written.",
+            },
+            QuestStep {
+                id: "scribes_sealers_and_harvesters",
+                title: "Sealers and Harvesters",
+                locations: &["athenaeum", "athenaeum_stacks"],
+                description: "Two circles of Scribes argue in whispers over a chained book. One wants it burned. \
+                    The other has already copied half of it, in ink that moves.",
+                line: ("Sealer", "The Archmage means to harness the Breach, not close it. Read the letter and tell us we're wrong."),
+                choice: ("side_with_sealers", "Hand the letter to the Sealers."),
+                narrative: "The Harvesters fall silent. One of them looks at you the way you'd look at a blot on a clean page.",
+            },
+            QuestStep {
+                id: "scribes_oath",
+                title: "The Binding Oath",
+                locations: &["athenaeum", "athenaeum_restricted"],
+                description: "The Sealers gather in a ring of lamplight. They have an oath older than the Sundering, \
+                    and a place in the ring with your name not yet written in it.",
+                line: ("Sealer", "Swear it, and the Breach gets no word from you but 'closed'."),
+                choice: ("swear_scribes", "Swear the Scribes' oath."),
+                narrative: "Your name is written into the ring. The ink takes a long time to dry.",
+            },
+        ],
+        oath: "what was opened i will close",
+    },
+    Questline {
+        faction: "Mechanists",
+        steps: [
+            QuestStep {
+                id: "mechanists_silent_gear",
+                title: "The Silent Gear",
+                locations: &["haven_market", "gearhold"],
+                description: "A stallholder sells you a gear that doesn't turn. Stamped on its hub is a Mechanist \
+                    sigil, and under it, scratched by hand: STILL LISTENING.",
+                line: ("Stallholder", "Came out of the Gearhold in a dead man's pocket. You want it, it's yours."),
+                choice: ("keep_gear", "Keep the gear."),
+                narrative: "Held to your ear, the gear hums a single note. It is not a note any machine should make.",
+            },
+            QuestStep {
+                id: "mechanists_forbidden_rite",
+                title: "The Forbidden Rite",
+                locations: &["gearhold", "mechanist_workshop"],
+                description: "Behind a locked workshop door, Mechanists stand around an engine built to force an \
+                    answer from the silence. Some of them are praying. Some are taking notes.",
+                line: ("Orthodox Mechanist", "They'll wake something that isn't a god. Help us jam it before they try again."),
+                choice: ("jam_engine", "Set the silent gear in the engine."),
+                narrative: "The engine grinds, shudders, and stops. Somewhere below, something that was about to answer doesn't.",
+            },
+            QuestStep {
+                id: "mechanists_oath",
+                title: "The Oath of True Gears",
+                locations: &["mechanist_workshop"],
+                description: "The Orthodox have set a bench for you among theirs. On it is a wrench, a plumb line, \
+                    and a vow typed out on a machine older than the Gearhold.",
+                line: ("Orthodox Mechanist", "No rite, no engine, no shortcut to heaven. Just the work. Swear to it."),
+                choice: ("swear_mechanists", "Swear the Mechanists' oath."),
+                narrative: "The machines around you settle into one rhythm. For a moment, it sounds like a heartbeat.",
+            },
+        ],
+        oath: "the work is the prayer",
+    },
+    Questline {
+        faction: "Naturalists",
+        steps: [
+            QuestStep {
+                id: "naturalists_blighted_seed",
+                title: "A Blighted Seed",
+                locations: &["haven", "whispering_waste"],
+                description: "A Naturalist kneels over a sapling grown through the cobbles overnight. Its leaves are \
+                    veined with the same shimmer as the Corruption.",
+                line: ("Warden", "It shouldn't be alive. It shouldn't be this alive. Carry it down for me?"),
+                choice: ("carry_seed", "Carry the sapling."),
+                narrative: "By the next landing it has put out a new leaf, and turned it toward the stairs down.",
+            },
+            QuestStep {
+                id: "naturalists_songline",
+                title: "The Broken Songline",
+                locations: &["whispering_waste", "corruption_zone"],
+                description: "The Wardens sing the land's old paths back into it, but one line of the song has gone \
+                    wrong. Where it runs, the ash grows flowers with teeth.",
+                line: ("Warden", "Some of us want to prune it. Some want to let it grow and see what it becomes."),
+                choice: ("prune_songline", "Help prune the song."),
+                narrative: "The flowers close their teeth and wither. The sapling you carried sheds a single leaf, as if in mourning.",
+            },
+            QuestStep {
+                id: "naturalists_oath",
+                title: "The Warden's Vow",
+                locations: &["whispering_waste"],
+                description: "At the edge of the Waste, the Wardens have planted the sapling in clean earth. They \
+                    ask you to stand where its roots will one day reach.",
+                line: ("Warden", "Swear to keep what grows here true, even when it grows against you."),
+                choice: ("swear_naturalists", "Swear the Naturalists' vow."),
+                narrative: "The ground under your feet is warm. You are fairly sure the roots have already found you.",
+            },
+        ],
+        oath: "i keep the living line",
+    },
+    Questline {
+        faction: "ShadowWriters",
+        steps: [
+            QuestStep {
+                id: "shadowwriters_dead_drop",
+                title: "The Dead Drop",
+                locations: &["haven_alleys", "shadow_quarter"],
+                description: "A loose brick in the alley wall. Behind it, a blank page that fills with writing when \
+                    your shadow falls across it.",
+                line: ("The Page", "YOU READ THIS, SO YOU ARE ONE OF US OR ABOUT TO BE. WAIT FOR DARK."),
+                choice: ("take_page", "Take the page."),
+                narrative: "The words fade. The page stays warm, and heavier than paper should be.",
+            },
+            QuestStep {
+                id: "shadowwriters_ledger_of_names",
+                title: "The Ledger of Names",
+                locations: &["shadow_quarter"],
+                description: "The ShadowWriters keep a ledger of every name the Void has whispered. Half the cell \
+                    wants it burned; the other half wants it answered.",
+                line: ("Cipher", "Someone has been writing back to the Void from inside this room. Find the hand."),
+                choice: ("find_the_hand", "Match the handwriting."),
+                narrative: "The hand belongs to no one in the room. It belongs to no one alive. The ledger is burned anyway.",
+            },
+            QuestStep {
+                id: "shadowwriters_oath",
+                title: "The Unwritten Oath",
+                locations: &["shadow_quarter", "haven_alleys"],
+                description: "There is no ceremony. Cipher hands you a pen with no ink and asks you to write a vow \
+                    that no one, not even you, will ever be able to read.",
+                line: ("Cipher", "We keep the secrets so the Void can't. Swear, and you're one of the keepers."),
+                choice: ("swear_shadowwriters", "Swear the ShadowWriters' oath."),
+                narrative: "The vow is written. You could not say what it says. That is rather the point.",
+            },
+        ],
+        oath: "what i know the dark will never read",
+    },
+    Questline {
+        faction: "Archivists",
+        steps: [
+            QuestStep {
+                id: "archivists_missing_volume",
+                title: "The Missing Volume",
+                locations: &["athenaeum", "athenaeum_stacks"],
+                description: "An Archivist is counting shelves, and the count keeps coming out one short. The gap \
+                    on the shelf is exactly the width of a book no catalogue lists.",
+                line: ("Archivist", "Nothing leaves the Archives. Something has. Find where it went."),
+                choice: ("trace_volume", "Trace the missing volume."),
+                narrative: "Dust on the floor shows a path. It leads down, toward the restricted stacks.",
+            },
+            QuestStep {
+                id: "archivists_restricted_stacks",
+                title: "What the Archives Hide",
+                locations: &["athenaeum_stacks", "athenaeum_restricted"],
+                description: "The missing volume is here, chained open to a page of names. Some of the Archivists \
+                    want it returned to the shelf. Others want it forgotten altogether.",
+                line: ("Archivist", "Some records are kept so they can be buried properly. Help us decide which this is."),
+                choice: ("restore_volume", "Return it to its shelf."),
+                narrative: "The book settles into its gap with a sigh. The count comes out right for the first time in years.",
+            },
+            QuestStep {
+                id: "archivists_oath",
+                title: "The Keeper's Oath",
+                locations: &["athenaeum_restricted"],
+                description: "Deep in the restricted stacks, the Archivists keep a register of those sworn to the \
+                    record. The last entry is centuries old. The next line is blank.",
+                line: ("Archivist", "Remember everything, and choose what to tell. That is the whole of it."),
+                choice: ("swear_archivists", "Swear the Archivists' oath."),
+                narrative: "Your name goes in the register. Somewhere in the stacks, a catalogue card writes itself.",
+            },
+        ],
+        oath: "nothing is lost while i remember",
+    },
+];
+
+impl Questline {
+    fn build(&self) -> Vec<AuthoredEncounter> {
+        let last = self.steps.len() - 1;
+        self.steps.iter().enumerate().map(|(n, step)| {
+            let oath = n == last;
+            let rivals = QUESTLINES.iter()
+                .filter(|q| q.faction != self.faction)
+                .map(|q| oath_flag(q.faction));
+            AuthoredEncounter {
+                id: step.id.to_string(),
+                title: step.title.to_string(),
+                valid_locations: step.locations.iter().map(|l| l.to_string()).collect(),
+                requirements: EncounterRequirements {
+                    faction_reputation: (STEP_STANDING[n] > 0).then(|| (self.faction.to_string(), STEP_STANDING[n])),
+                    prerequisite_encounter: n.checked_sub(1).map(|prev| self.steps[prev].id.to_string()),
+                    blocking_flags: if oath { rivals.collect() } else { Vec::new() },
+                    ..Default::default()
+                },
+                content: EncounterContent {
+                    description: step.description.to_string(),
+                    dialogue: Some(vec![DialogueLine {
+                        speaker: step.line.0.to_string(),
+                        text: step.line.1.to_string(),
+                        reveals: None,
+                    }]),
+                    environmental_details: Vec::new(),
+                    typing_challenge: oath.then(|| EncounterTypingChallenge {
+                        prompt_text: format!("Type the oath: '{}'", self.oath),
+                        difficulty: 3,
+                        success_narrative: "The words hold. They will hold you, too.".to_string(),
+                        failure_narrative: "The words come out crooked. An oath sworn crooked is no oath at all.".to_string(),
+                        partial_narrative: None,
+                        ritual: None,
+                    }),
+                },
+                choices: vec![EncounterChoice {
+                    id: step.choice.0.to_string(),
+                    text: step.choice.1.to_string(),
+                    requires: None,
+                    consequence_id: format!("{}_result", step.choice.0),
+                    typing_required: oath,
+                }],
+                consequences: EncounterConsequences {
+                    reputation_changes: vec![(self.faction.to_string(), STEP_REWARD)],
+                    world_state_changes: if oath { vec![oath_flag(self.faction)] } else { Vec::new() },
+                    enables_encounters: self.steps.get(n + 1).map(|next| next.id.to_string()).into_iter().collect(),
+                    narrative_result: step.narrative.to_string(),
+                    ..Default::default()
+                },
+                outcomes: BTreeMap::new(),
+                repeatable: false,
+                tags: if oath {
+                    vec!["major".to_string(), "questline".to_string(), self.faction.to_lowercase()]
+                } else {
+                    vec!["questline".to_string(), self.faction.to_lowercase()]
+                },
+            }
+        }).collect()
+    }
+}
+
+/// Every faction's questline, as encounters
+pub fn questline_encounters() -> Vec<AuthoredEncounter> {
+    QUESTLINES.iter().flat_map(Questline::build).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_scheduler::{EncounterScheduler, SchedulingContext};
+    use crate::game::encounter_writing::{build_encounters, EncounterTracker};
+    use crate::game::faction_system::FactionRelations;
+    use crate::game::ideology::Ideology;
+    use crate::game::narrative::Faction;
+    use crate::game::npc_memory::NpcMemory;
+    use crate::game::world_state::WorldState;
+
+    #[test]
+    fn test_questlines_chain_and_oaths_exclude_each_other() {
+        let encounters = build_encounters();
+        for questline in &QUESTLINES {
+            assert!(Faction::from_id(questline.faction).is_some());
+            for pair in questline.steps.windows(2) {
+                let (step, next) = (&encounters[pair[0].id], &encounters[pair[1].id]);
+                assert_eq!(step.consequences.enables_encounters, vec![next.id.clone()]);
+                assert_eq!(next.requirements.prerequisite_encounter.as_deref(), Some(step.id.as_str()));
+            }
+            let oath = &encounters[questline.steps[2].id];
+            assert_eq!(oath.consequences.world_state_changes, vec![oath_flag(questline.faction)]);
+            assert_eq!(oath.requirements.blocking_flags.len(), QUESTLINES.len() - 1);
+            assert!(!oath.requirements.blocking_flags.contains(&oath_flag(questline.faction)));
+        }
+    }
+
+    #[test]
+    fn test_steps_wait_on_the_last_step_standing_and_other_oaths() {
+        let encounters = build_encounters();
+        let mut tracker = EncounterTracker::new();
+        let mut factions = FactionRelations::new();
+        let mut world = WorldState::default();
+        let scheduler = EncounterScheduler::new();
+        // The Athenaeum is on floor 3 of the classic descent
+        let fits = |id: &str, tracker: &EncounterTracker, factions: &FactionRelations, world: &WorldState| {
+            let ctx = SchedulingContext { floor: 3, tracker, factions, world, lore: Vec::new(), npcs: &NpcMemory::new(), ideology: &Ideology::default() };
+            scheduler.eligible(&encounters[id], &ctx)
+        };
+
+        assert!(fits("archivists_missing_volume", &tracker, &factions, &world));
+        assert!(!fits("archivists_restricted_stacks", &tracker, &factions, &world));
+        tracker.complete_encounter("archivists_missing_volume", "trace_volume");
+        assert!(!fits("archivists_restricted_stacks", &tracker, &factions, &world));
+        factions.modify_standing(Faction::MerchantConsortium, STEP_STANDING[1]);
+        assert!(fits("archivists_restricted_stacks", &tracker, &factions, &world));
+
+        tracker.complete_encounter("archivists_restricted_stacks", "restore_volume");
+        factions.modify_standing(Faction::MerchantConsortium, STEP_STANDING[2]);
+        assert!(fits("archivists_oath", &tracker, &factions, &world));
+        world.set_flag(&oath_flag("Scribes"));
+        assert!(!fits("archivists_oath", &tracker, &factions, &world));
+    }
+}
@@ -0,0 +1,215 @@
+//! Fatigue - Sprinting and flailing have a price in long fights
+//!
+//! Words typed near your top speed for the fight drain stamina, and so do
+//! wrong keys and frantic, sloppy words. Run it dry and your hands tire:
+//! the rhythm bonus window narrows until you deliberately ease off for a
+//! few words. Paced words and a steady rhythm refill stamina, so control
+//! beats flailing. Heavy strikes cost stamina to charge.
+//!
+//! Design: CombatState feeds fatigue each finished word's WPM and each
+//! slip, and narrows the typing impact's rhythm window while tired.
+
+/// Full stamina
+pub const STAMINA_MAX: f32 = 100.0;
+/// Rhythm window kept while fatigued
+pub const FATIGUE_WINDOW_MULT: f32 = 0.5;
+/// Slower words in a row needed to shake off fatigue
+pub const REST_WORDS: u32 = 3;
+/// Words slower than this never count as a sprint
+const SPRINT_MIN_WPM: f32 = 60.0;
+/// Share of the fight's best pace that counts as a sprint
+const SPRINT_SHARE: f32 = 0.9;
+/// Words under this share of the best pace count as easing off
+const REST_SHARE: f32 = 0.75;
+/// Stamina spent per sprinted word
+const SPRINT_COST: f32 = 25.0;
+/// Stamina regained per paced word
+const PACED_REGEN: f32 = 10.0;
+/// Stamina spent per wrong keystroke
+const ERROR_COST: f32 = 4.0;
+/// Stamina spent on a frantic (fast but sloppy) word
+const FRANTIC_COST: f32 = 15.0;
+/// Stamina regained for a word typed in a steady rhythm
+const STEADY_REGEN: f32 = 10.0;
+/// Stamina it takes to charge a heavy strike
+pub const HEAVY_STRIKE_COST: f32 = 30.0;
+/// At or below this, stamina is critical
+pub const CRITICAL_STAMINA: f32 = 20.0;
+
+/// What a finished word did to the player's stamina
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FatigueChange {
+    /// Stamina ran out
+    Tired,
+    /// Eased off long enough to recover
+    Recovered,
+}
+
+/// Within-fight stamina
+#[derive(Debug, Clone)]
+pub struct Fatigue {
+    pub stamina: f32,
+    pub fatigued: bool,
+    /// Slower words typed in a row while fatigued
+    pub rest_streak: u32,
+    /// Fastest word this fight, the pace sprints are measured against
+    best_wpm: f32,
+}
+
+impl Default for Fatigue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fatigue {
+    pub fn new() -> Self {
+        Self { stamina: STAMINA_MAX, fatigued: false, rest_streak: 0, best_wpm: 0.0 }
+    }
+
+    /// Record a finished word's speed
+    pub fn on_word(&mut self, wpm: f32) -> Option<FatigueChange> {
+        if wpm <= 0.0 {
+            return None;
+        }
+        self.best_wpm = self.best_wpm.max(wpm);
+        let sprint = wpm >= SPRINT_MIN_WPM && wpm >= self.best_wpm * SPRINT_SHARE;
+
+        if self.fatigued {
+            if wpm < self.best_wpm * REST_SHARE {
+                self.rest_streak += 1;
+            } else {
+                self.rest_streak = 0;
+            }
+            if self.rest_streak >= REST_WORDS {
+                self.fatigued = false;
+                self.rest_streak = 0;
+                self.stamina = STAMINA_MAX;
+                return Some(FatigueChange::Recovered);
+            }
+            return None;
+        }
+
+        if sprint {
+            self.drain(SPRINT_COST)
+        } else {
+            self.restore(PACED_REGEN);
+            None
+        }
+    }
+
+    /// A wrong keystroke
+    pub fn on_error(&mut self) -> Option<FatigueChange> {
+        self.drain(ERROR_COST)
+    }
+
+    /// A word landed fast but sloppy
+    pub fn on_frantic(&mut self) -> Option<FatigueChange> {
+        self.drain(FRANTIC_COST)
+    }
+
+    /// A word typed in a steady rhythm
+    pub fn on_steady_rhythm(&mut self) {
+        self.restore(STEADY_REGEN);
+    }
+
+    /// Spend stamina on an effort; false (and nothing spent) if too tired
+    pub fn spend(&mut self, cost: f32) -> bool {
+        if self.fatigued || self.stamina < cost {
+            return false;
+        }
+        self.stamina -= cost;
+        true
+    }
+
+    /// Whether stamina is nearly (or completely) spent
+    pub fn is_critical(&self) -> bool {
+        self.fatigued || self.stamina <= CRITICAL_STAMINA
+    }
+
+    fn drain(&mut self, cost: f32) -> Option<FatigueChange> {
+        if self.fatigued {
+            return None;
+        }
+        self.stamina = (self.stamina - cost).max(0.0);
+        if self.stamina <= 0.0 {
+            self.fatigued = true;
+            self.rest_streak = 0;
+            return Some(FatigueChange::Tired);
+        }
+        None
+    }
+
+    fn restore(&mut self, amount: f32) {
+        if !self.fatigued {
+            self.stamina = (self.stamina + amount).min(STAMINA_MAX);
+        }
+    }
+
+    /// Rhythm window (ms) after fatigue narrows it
+    pub fn rhythm_window(&self, base_ms: u32) -> u32 {
+        if self.fatigued {
+            ((base_ms as f32 * FATIGUE_WINDOW_MULT).round() as u32).max(1)
+        } else {
+            base_ms
+        }
+    }
+
+    pub fn stamina_percent(&self) -> u16 {
+        ((self.stamina / STAMINA_MAX) * 100.0).round() as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sprinting_tires_you() {
+        let mut fatigue = Fatigue::new();
+        let changes: Vec<_> = (0..4).map(|_| fatigue.on_word(100.0)).collect();
+        assert_eq!(changes, vec![None, None, None, Some(FatigueChange::Tired)]);
+        assert_eq!(fatigue.rhythm_window(30), 15);
+        // Slow typing never tires you out
+        let mut fatigue = Fatigue::new();
+        for _ in 0..20 {
+            assert_eq!(fatigue.on_word(45.0), None);
+        }
+        assert_eq!(fatigue.stamina_percent(), 100);
+    }
+
+    #[test]
+    fn test_flailing_drains_and_rhythm_restores() {
+        let mut fatigue = Fatigue::new();
+        assert!(fatigue.spend(HEAVY_STRIKE_COST));
+        for _ in 0..4 {
+            assert_eq!(fatigue.on_frantic(), None);
+        }
+        assert!(fatigue.is_critical());
+        assert!(!fatigue.spend(HEAVY_STRIKE_COST));
+        fatigue.on_steady_rhythm();
+        assert_eq!(fatigue.stamina_percent(), 20);
+        let tired = (0..10).find_map(|_| fatigue.on_error());
+        assert_eq!(tired, Some(FatigueChange::Tired));
+        // Nothing restores stamina but easing off once tired
+        fatigue.on_steady_rhythm();
+        assert_eq!(fatigue.stamina_percent(), 0);
+    }
+
+    #[test]
+    fn test_easing_off_recovers() {
+        let mut fatigue = Fatigue::new();
+        for _ in 0..4 {
+            fatigue.on_word(100.0);
+        }
+        fatigue.on_word(60.0);
+        fatigue.on_word(60.0);
+        // Sprinting again breaks the rest
+        assert_eq!(fatigue.on_word(95.0), None);
+        fatigue.on_word(60.0);
+        fatigue.on_word(60.0);
+        assert_eq!(fatigue.on_word(60.0), Some(FatigueChange::Recovered));
+        assert!(!fatigue.fatigued);
+        assert_eq!(fatigue.rhythm_window(30), 30);
+    }
+}
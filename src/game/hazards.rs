@@ -0,0 +1,251 @@
+//! Hazards - The dungeon itself joins the fight
+//!
+//! Every zone has its own dangers: shelves topple in the Drowned Archives,
+//! steam vents burst in the Clockwork Depths. Mid-fight a hazard can cut
+//! in with a short reactive prompt. Type it cleanly before time runs out
+//! and you escape, often turning the hazard on the enemy; slip or hesitate
+//! and it hits you instead.
+//!
+//! Design: hazards are static data per ZoneContext; CombatState rolls for
+//! one while the player types, routes keys to it while it is active and
+//! holds the word timer until it resolves.
+
+use super::dialogue_engine::ZoneContext;
+
+/// Chance per second of typing that a hazard cuts in
+pub const HAZARD_CHANCE_PER_SEC: f32 = 0.04;
+/// Turns after a hazard before another can strike
+pub const HAZARD_COOLDOWN: i32 = 3;
+
+/// A danger a zone can throw into a fight
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hazard {
+    pub name: &'static str,
+    /// What the player sees as it strikes
+    pub warning: &'static str,
+    /// What they must type to get clear
+    pub prompt: &'static str,
+    /// Seconds to react
+    pub secs: f32,
+    /// Damage to the player if it hits, before floor scaling
+    pub damage: i32,
+    /// Damage to the enemy if avoided, before floor scaling (0 = none)
+    pub backlash: i32,
+    /// What happens when it is avoided
+    pub avoided: &'static str,
+}
+
+const KEEP_HAZARDS: [Hazard; 2] = [
+    Hazard {
+        name: "Crumbling masonry",
+        warning: "The ceiling cracks and stones rain down!",
+        prompt: "duck",
+        secs: 2.0,
+        damage: 4,
+        backlash: 3,
+        avoided: "You duck aside; the stones batter your foe.",
+    },
+    Hazard {
+        name: "Collapsing rampart",
+        warning: "The floor lurches as a rampart gives way!",
+        prompt: "leap",
+        secs: 2.0,
+        damage: 5,
+        backlash: 0,
+        avoided: "You leap clear of the rubble.",
+    },
+];
+
+const ARCHIVE_HAZARDS: [Hazard; 2] = [
+    Hazard {
+        name: "Falling shelves",
+        warning: "A waterlogged shelf groans and topples toward you!",
+        prompt: "dive",
+        secs: 2.0,
+        damage: 5,
+        backlash: 5,
+        avoided: "You dive clear; the shelf crashes down on your foe.",
+    },
+    Hazard {
+        name: "Flood surge",
+        warning: "Black water surges through the stacks!",
+        prompt: "climb",
+        secs: 2.5,
+        damage: 4,
+        backlash: 0,
+        avoided: "You climb above the surge as it drains away.",
+    },
+];
+
+const SANCTUM_HAZARDS: [Hazard; 2] = [
+    Hazard {
+        name: "Lashing thorns",
+        warning: "Thorned vines whip out of the undergrowth!",
+        prompt: "slash",
+        secs: 2.0,
+        damage: 5,
+        backlash: 4,
+        avoided: "You slash the vines; they recoil into your foe.",
+    },
+    Hazard {
+        name: "Spore burst",
+        warning: "A swollen pod bursts into choking spores!",
+        prompt: "hold breath",
+        secs: 3.0,
+        damage: 6,
+        backlash: 0,
+        avoided: "You hold your breath until the spores settle.",
+    },
+];
+
+const CLOCKWORK_HAZARDS: [Hazard; 2] = [
+    Hazard {
+        name: "Steam vent",
+        warning: "A pipe shrieks - a steam vent is about to blow!",
+        prompt: "shut valve",
+        secs: 3.0,
+        damage: 7,
+        backlash: 6,
+        avoided: "You wrench the valve; the blast scalds your foe instead.",
+    },
+    Hazard {
+        name: "Grinding gears",
+        warning: "The floor gears lurch into motion beneath you!",
+        prompt: "jump",
+        secs: 1.8,
+        damage: 6,
+        backlash: 0,
+        avoided: "You jump the gears as they grind past.",
+    },
+];
+
+const VOID_HAZARDS: [Hazard; 2] = [
+    Hazard {
+        name: "Reality tear",
+        warning: "The air splits open and pulls at you!",
+        prompt: "anchor",
+        secs: 2.5,
+        damage: 8,
+        backlash: 7,
+        avoided: "You anchor yourself; the tear swallows a piece of your foe.",
+    },
+    Hazard {
+        name: "Unwritten ground",
+        warning: "The floor beneath you starts to unwrite itself!",
+        prompt: "stand",
+        secs: 2.0,
+        damage: 7,
+        backlash: 0,
+        avoided: "You stand firm and the floor writes itself back.",
+    },
+];
+
+impl ZoneContext {
+    /// The hazards that can strike in this zone
+    pub fn hazards(&self) -> &'static [Hazard] {
+        match self {
+            Self::RuinedKeep => &KEEP_HAZARDS,
+            Self::DrownedArchives => &ARCHIVE_HAZARDS,
+            Self::OvergrownSanctum => &SANCTUM_HAZARDS,
+            Self::ClockworkDepths => &CLOCKWORK_HAZARDS,
+            Self::VoidBreach => &VOID_HAZARDS,
+            Self::Unknown => &[],
+        }
+    }
+}
+
+impl Hazard {
+    /// Damage to the player on floor `floor`
+    pub fn damage_on(&self, floor: u32) -> i32 {
+        self.damage + floor as i32 / 2
+    }
+
+    /// Damage to the enemy on floor `floor`
+    pub fn backlash_on(&self, floor: u32) -> i32 {
+        if self.backlash == 0 {
+            0
+        } else {
+            self.backlash + floor as i32
+        }
+    }
+}
+
+/// How a hazard played out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HazardOutcome {
+    Avoided,
+    Struck,
+}
+
+/// A hazard waiting on the player
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveHazard {
+    pub hazard: &'static Hazard,
+    pub typed: String,
+    /// Seconds left to react
+    pub remaining: f32,
+}
+
+impl ActiveHazard {
+    pub fn new(hazard: &'static Hazard) -> Self {
+        Self { hazard, typed: String::new(), remaining: hazard.secs }
+    }
+
+    /// A key landed; any slip means the hazard hits
+    pub fn on_key(&mut self, c: char) -> Option<HazardOutcome> {
+        self.typed.push(c);
+        if !self.hazard.prompt.starts_with(&self.typed) {
+            Some(HazardOutcome::Struck)
+        } else if self.typed == self.hazard.prompt {
+            Some(HazardOutcome::Avoided)
+        } else {
+            None
+        }
+    }
+
+    /// Count down; the hazard hits once time runs out
+    pub fn advance(&mut self, secs: f32) -> Option<HazardOutcome> {
+        self.remaining -= secs;
+        (self.remaining <= 0.0).then_some(HazardOutcome::Struck)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_typing_avoids_and_slips_are_struck() {
+        let mut active = ActiveHazard::new(&ARCHIVE_HAZARDS[0]);
+        assert_eq!(active.on_key('d'), None);
+        assert_eq!(active.on_key('i'), None);
+        assert_eq!(active.on_key('v'), None);
+        assert_eq!(active.on_key('e'), Some(HazardOutcome::Avoided));
+
+        let mut active = ActiveHazard::new(&ARCHIVE_HAZARDS[0]);
+        assert_eq!(active.on_key('d'), None);
+        assert_eq!(active.on_key('u'), Some(HazardOutcome::Struck));
+
+        let mut active = ActiveHazard::new(&CLOCKWORK_HAZARDS[0]);
+        assert_eq!(active.advance(1.0), None);
+        assert_eq!(active.advance(2.5), Some(HazardOutcome::Struck));
+    }
+
+    #[test]
+    fn test_every_known_zone_has_hazards() {
+        let zones = [
+            ZoneContext::RuinedKeep,
+            ZoneContext::DrownedArchives,
+            ZoneContext::OvergrownSanctum,
+            ZoneContext::ClockworkDepths,
+            ZoneContext::VoidBreach,
+        ];
+        for zone in zones {
+            assert!(!zone.hazards().is_empty());
+            assert!(zone.hazards().iter().all(|h| h.secs > 0.0 && h.damage > 0));
+        }
+        assert!(ZoneContext::Unknown.hazards().is_empty());
+        assert_eq!(ARCHIVE_HAZARDS[1].backlash_on(4), 0);
+        assert_eq!(ARCHIVE_HAZARDS[0].backlash_on(4), 9);
+    }
+}
@@ -54,6 +54,31 @@ impl From<Scene> for HelpContext {
             Scene::Milestone => HelpContext::Event, // Milestones are similar to events
             Scene::Upgrades => HelpContext::Shop, // Upgrades is like a shop
             Scene::BattleSummary => HelpContext::GameOver,
+            Scene::Consequences => HelpContext::Stats, // A record, like the stats screen
+            Scene::Challenges => HelpContext::Stats, // Scores and personal bests
+            Scene::Replay => HelpContext::GameOver,
+            Scene::Calibration => HelpContext::Title,
+            Scene::Skills => HelpContext::Stats, // Part of the character sheet
+            Scene::Finale => HelpContext::Victory,
+            Scene::Journal => HelpContext::Stats, // A record, like the stats screen
+            Scene::Encounter => HelpContext::Event,
+            Scene::LevelUp => HelpContext::Stats,
+            Scene::RouteChoice => HelpContext::Exploration,
+            Scene::Dream => HelpContext::Event,
+            Scene::Conversation => HelpContext::Event,
+            Scene::Codex => HelpContext::Stats, // A record, like the journal
+            Scene::ClueBoard => HelpContext::Stats,
+            Scene::Mailbox => HelpContext::Exploration, // Read on the way into the dungeon
+            Scene::Ciphers => HelpContext::Stats,
+            Scene::Memories => HelpContext::Stats,
+            Scene::Ideology => HelpContext::Stats,
+            Scene::Themes => HelpContext::Title,
+            Scene::KeyHeatmap => HelpContext::Title,
+            Scene::Dashboard => HelpContext::Title,
+            Scene::Settings => HelpContext::Title,
+            Scene::Keybinds => HelpContext::Title,
+            Scene::Pause => HelpContext::Combat,
+            Scene::Training => HelpContext::Tutorial,
         }
     }
 }
@@ -308,6 +333,17 @@ impl HelpSystem {
                 HelpTip::new("󰒔", "Navigate", "Use j/k or ↑/↓ to move selection", TipPriority::Essential),
                 HelpTip::new("󰌑", "Confirm", "Press Enter to select", TipPriority::Essential),
                 HelpTip::new("󰗼", "Quit", "Press q to exit the game", TipPriority::Important),
+                HelpTip::new("󰏘", "Themes", "Press t to pick a theme, or add your own TOML theme files to the themes folder", TipPriority::Advanced),
+                HelpTip::new("󰋋", "Screen Reader", "Press a for plain, linear output: a labeled log of every change and the prompt on its own line", TipPriority::Advanced),
+                HelpTip::new("󰌌", "Key Heatmap", "Press e to see which keys you miss most, and so which cost you damage", TipPriority::Advanced),
+                HelpTip::new("󰄧", "Stats Dashboard", "Press d to chart your speed, accuracy and damage, this run or every run", TipPriority::Advanced),
+                HelpTip::new("", "Settings", "Press o to change theme, typo policy, difficulty and more, previewed as you go", TipPriority::Important),
+                HelpTip::new("󰌌", "Keybinds", "Press b on the settings screen to rebind menu, combat and transcript keys", TipPriority::Advanced),
+                HelpTip::new("✦", "Reduced Motion", "Press p to turn off particles and screen shake", TipPriority::Advanced),
+                HelpTip::new("󰍹", "Small Terminal?", "Panels fold away on a short terminal; under 60x20 the game waits for a larger window", TipPriority::Advanced),
+                HelpTip::new("?", "Boxes for Icons?", "Set display.icons to Some(unicode) or Some(ascii) in config.ron if icons draw as boxes", TipPriority::Advanced),
+                HelpTip::new("󰈉", "Colorblind Play", "Pick a Protanopia, Deuteranopia or Tritanopia theme, or press s to mark typed letters by shape", TipPriority::Advanced),
+                HelpTip::new("󰈈", "Unreliable Narrator", "Press v: some scenes are told wrong until your codex knows better", TipPriority::Advanced),
             ],
             
             HelpContext::ClassSelect => vec![
@@ -317,6 +353,8 @@ impl HelpSystem {
                 HelpTip::new("󰄀", "Spellweaver", "Magic focus with +20% spell damage", TipPriority::Important),
                 HelpTip::new("󰣥", "Barbarian", "High HP with +30% crit chance", TipPriority::Important),
                 HelpTip::new("󰏇", "Trickster", "Combo master with +50% combo bonus", TipPriority::Important),
+                HelpTip::new("⇑", "Ascension", "Win a run to unlock the next ascension level; press a to pick one", TipPriority::Advanced),
+                HelpTip::new("󰂺", "Setting", "Press s to play the run in the fall of Valdris or the Unwriting", TipPriority::Advanced),
             ],
             
             HelpContext::Combat => vec![
@@ -326,7 +364,10 @@ impl HelpSystem {
                 HelpTip::new("󰄀", "Perfect Words", "No backspaces = 1.5x damage multiplier", TipPriority::Important),
                 HelpTip::new("󰔚", "Speed Bonus", "Type faster for extra damage", TipPriority::Advanced),
                 HelpTip::new("󰒔", "Flow State", "Consistent rhythm increases critical chance", TipPriority::Advanced),
+                HelpTip::new("󰓥", "Enemy Intent", "The enemy panel shows its next blow, the damage it will do, and when it lands", TipPriority::Important),
+                HelpTip::new("󰚌", "Boss Phases", "Markers on a boss's health bar show where its fight turns; its phase is shown under the bar", TipPriority::Advanced),
                 HelpTip::new("󰈆", "Flee", "Press Esc to attempt escape", TipPriority::Important),
+                HelpTip::new("󰏤", "Pause", "Press F4 to stop the fight: resume, settings, a run overview, or abandon the run", TipPriority::Important),
             ],
             
             HelpContext::Exploration => vec![
@@ -336,6 +377,14 @@ impl HelpSystem {
                 HelpTip::new("󰓥", "Combat Rooms", "Fight enemies to progress", TipPriority::Important),
                 HelpTip::new("󰚌", "Elite Rooms", "Harder enemies with better rewards", TipPriority::Important),
                 HelpTip::new("󰒲", "Rest Sites", "Heal and recover between battles", TipPriority::Important),
+                HelpTip::new("󰍩", "Rumors", "Haven and shops tell of what lies ahead; marks on the map show how sure they were", TipPriority::Advanced),
+                HelpTip::new("󰜎", "Tomorrow Texts", "Prophecies from treasure rooms name a floor's first fight; the codex counts how often they come true", TipPriority::Advanced),
+                HelpTip::new("󰂺", "The Living Book", "Once accepted it is carried every run; type its passages cleanly to read on", TipPriority::Advanced),
+                HelpTip::new("󰌆", "Cipher Notes", "Treasure rooms may hold Cipher's notes; press x and guess them letter by letter", TipPriority::Advanced),
+                HelpTip::new("󰦨", "Transcript", "Press F2 anywhere to scroll back through everything said this session, filtered by source or speaker", TipPriority::Important),
+                HelpTip::new("󰍩", "Voices", "Your choices lean you toward Preserve or Unwrite, Control or Release; the voice you lean toward speaks most", TipPriority::Advanced),
+                HelpTip::new("◌", "Memories", "Memories flash past as you enter rooms; press m to replay them, and remember a chapter whole for max HP", TipPriority::Advanced),
+                HelpTip::new("󰇮", "Letters", "People from earlier runs write; type Cipher's coded notes back plain for gold", TipPriority::Advanced),
             ],
             
             HelpContext::Shop => vec![
@@ -357,22 +406,32 @@ impl HelpSystem {
                 HelpTip::new("󰒔", "Navigate", "Use j/k to highlight choices", TipPriority::Essential),
                 HelpTip::new("󰌑", "Confirm", "Press Enter to make your choice", TipPriority::Essential),
                 HelpTip::new("󰛓", "Consequences", "Choices affect faction standing and story", TipPriority::Important),
+                HelpTip::new("󰗀", "Naming Rituals", "A true name corrupts while you hesitate; how well you speak it decides what follows", TipPriority::Advanced),
             ],
             
             HelpContext::Inventory => vec![
                 HelpTip::new("󰆧", "Select", "Use j/k to navigate items", TipPriority::Essential),
                 HelpTip::new("󰌑", "Use", "Press Enter to use consumables", TipPriority::Essential),
+                HelpTip::new("󰂺", "Lore", "Each item's lore reads deeper as you deal with its faction and find its artifact", TipPriority::Advanced),
                 HelpTip::new("󰈆", "Close", "Press Esc to return", TipPriority::Important),
             ],
             
             HelpContext::Stats => vec![
                 HelpTip::new("󰄪", "Statistics", "View your run performance", TipPriority::Essential),
+                HelpTip::new("󰂺", "Scars", "Fights won at a quarter of your health or less are kept in the codex, with the word that failed you", TipPriority::Advanced),
                 HelpTip::new("󰈆", "Close", "Press Esc to return", TipPriority::Important),
             ],
             
-            HelpContext::GameOver | HelpContext::Victory | HelpContext::Tutorial => vec![
+            HelpContext::Tutorial => vec![
+                HelpTip::new("󰌌", "Type Along", "Type each prompt to move the tutorial on; Tab skips a step", TipPriority::Essential),
+                HelpTip::new("󰓥", "Training Yard", "Trainer Beck's five lessons are real fights against dummies; each ends the moment it is learned", TipPriority::Essential),
+                HelpTip::new("󰒭", "Skip", "In the yard, s skips a lesson and Esc skips to the class screen", TipPriority::Important),
+            ],
+            
+            HelpContext::GameOver | HelpContext::Victory => vec![
                 HelpTip::new("󰑓", "Try Again", "Press Enter for a new run", TipPriority::Essential),
                 HelpTip::new("󰐀", "Ink Earned", "Currency persists between runs", TipPriority::Important),
+                HelpTip::new("󰂺", "Epilogue", "Each run is written up and kept in the run history", TipPriority::Advanced),
                 HelpTip::new("󰗼", "Quit", "Press q to exit", TipPriority::Important),
             ],
             
@@ -399,12 +458,20 @@ impl HelpSystem {
             Keybinding::with_context("a-z", "Type characters", HelpContext::Combat),
             Keybinding::with_context("Backspace", "Delete character", HelpContext::Combat),
             Keybinding::with_context("Tab", "Cycle targets", HelpContext::Combat),
+            Keybinding::with_context("F4", "Pause the fight", HelpContext::Combat),
             
             // Exploration
             Keybinding::with_context("e", "Explore/Enter room", HelpContext::Exploration),
+            Keybinding::with_context("←/→", "Pick a path on the map", HelpContext::Exploration),
             Keybinding::with_context("i", "Open inventory", HelpContext::Exploration),
             Keybinding::with_context("s", "View stats", HelpContext::Exploration),
+            Keybinding::with_context("k", "Spend skill points", HelpContext::Exploration),
+            Keybinding::with_context("j", "Quest journal", HelpContext::Exploration),
+            Keybinding::with_context("c", "Lore codex", HelpContext::Exploration),
+            Keybinding::with_context("b", "Clue board", HelpContext::Exploration),
             Keybinding::with_context("m", "View map", HelpContext::Exploration),
+            Keybinding::with_context("1-2", "Pick a zone where the stairs fork", HelpContext::Exploration),
+            Keybinding::with_context("1-4", "Pick what a new level buys", HelpContext::Stats),
             
             // Help navigation
             Keybinding::new("1-4", "Switch help tabs"),
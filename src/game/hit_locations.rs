@@ -0,0 +1,142 @@
+//! Hit Locations - Aiming for the head, the arms or the legs
+//!
+//! Every word that lands leaves a wound somewhere on the enemy, and where
+//! wounds land changes how it fights (see `EnemyVisualState::wound_effects`).
+//! Now and then a prompt capitalizes one of its letters. Type that capital
+//! cleanly (no slip on it, even one you backspaced) and the word's wound
+//! lands where it was aimed instead of at random:
+//! - Head: the blow lands hard, and a wounded head swings wide
+//! - Arms: wounded arms hit softer
+//! - Legs: wounded legs stumble and lose turns
+//!
+//! Where the letter sits decides the target: early in the prompt aims
+//! high, late aims low.
+//!
+//! Design: the aim is rolled with the prompt and remembers its own word.
+//! The capital lives only in the aim: the word itself stays as picked, so
+//! speech, stats and the key heatmap never see it. CombatState types
+//! against the aimed prompt, reports each key at the aimed letter and
+//! places the wound when the word lands.
+
+use rand::Rng;
+
+use super::enemy_visuals::HitLocation;
+use super::typing_impact::graphemes;
+
+/// Chance a prompt carries an aimed letter
+const AIM_CHANCE: f32 = 0.3;
+/// Shortest prompt (in graphemes) worth aiming
+const MIN_PROMPT_LEN: usize = 4;
+/// Damage multiplier for a word aimed at the head
+pub const HEAD_MULT: f32 = 1.5;
+
+/// An aimed letter in the current prompt
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aim {
+    /// The word the aim was rolled for
+    pub word: String,
+    /// The prompt, with the aimed letter capitalized
+    pub prompt: String,
+    /// Grapheme position of the aimed letter
+    pub index: usize,
+    pub location: HitLocation,
+    /// A wrong key landed on the aimed letter
+    pub slipped: bool,
+    /// The aimed letter was typed cleanly
+    pub struck: bool,
+}
+
+impl Aim {
+    /// Maybe capitalize a letter of the prompt to aim at
+    pub fn roll(prompt: &str, rng: &mut impl Rng) -> Option<Self> {
+        let chars = graphemes(prompt);
+        if chars.len() < MIN_PROMPT_LEN || rng.gen::<f32>() >= AIM_CHANCE {
+            return None;
+        }
+        // Never the first letter (sentences already start with a capital)
+        // nor the last (word chains start from it)
+        let candidates: Vec<usize> = (1..chars.len() - 1)
+            .filter(|&i| chars[i].len() == 1 && chars[i].chars().all(|c| c.is_ascii_lowercase()))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let index = candidates[rng.gen_range(0..candidates.len())];
+        let mut aimed: Vec<String> = chars.iter().map(|g| g.to_string()).collect();
+        aimed[index] = aimed[index].to_ascii_uppercase();
+        Some(Self {
+            word: prompt.to_string(),
+            prompt: aimed.concat(),
+            index,
+            location: location_for(index, chars.len(), rng),
+            slipped: false,
+            struck: false,
+        })
+    }
+
+    /// A key landed at grapheme position `index`
+    pub fn on_key(&mut self, index: usize, correct: bool) {
+        if index != self.index {
+            return;
+        }
+        if correct {
+            self.struck = !self.slipped;
+        } else {
+            self.slipped = true;
+        }
+    }
+}
+
+/// The target for a letter at `index` of a prompt `len` graphemes long
+pub fn location_for(index: usize, len: usize, rng: &mut impl Rng) -> HitLocation {
+    let third = len.max(1) as f32 / 3.0;
+    match (index as f32 / third) as usize {
+        0 => HitLocation::Head,
+        1 if rng.gen() => HitLocation::LeftArm,
+        1 => HitLocation::RightArm,
+        _ => HitLocation::Legs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aim_capitalizes_an_inner_letter() {
+        let mut rng = rand::thread_rng();
+        let mut aimed = 0;
+        for _ in 0..200 {
+            let Some(aim) = Aim::roll("lantern", &mut rng) else {
+                continue;
+            };
+            aimed += 1;
+            assert!(aim.index > 0 && aim.index < 6);
+            assert_eq!(aim.prompt.to_lowercase(), "lantern");
+            assert_eq!(aim.prompt.chars().filter(char::is_ascii_uppercase).count(), 1);
+        }
+        assert!(aimed > 0);
+        assert_eq!(location_for(0, 9, &mut rng), HitLocation::Head);
+        assert_eq!(location_for(8, 9, &mut rng), HitLocation::Legs);
+        assert!(Aim::roll("ink", &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_slip_spoils_the_aim() {
+        let mut aim = Aim {
+            word: "lantern".to_string(),
+            prompt: "laNtern".to_string(),
+            index: 2,
+            location: HitLocation::LeftArm,
+            slipped: false,
+            struck: false,
+        };
+        // Other letters don't touch the aim
+        aim.on_key(1, false);
+        aim.on_key(2, true);
+        assert!(aim.struck);
+        aim.on_key(2, false);
+        aim.on_key(2, true);
+        assert!(!aim.struck);
+    }
+}
@@ -0,0 +1,311 @@
+//! Ideology - What your choices say you believe, kept from you
+//!
+//! Two hidden axes are moved by what you choose in encounters:
+//! - Preserve ↔ Unwrite: whether what was written should be kept
+//! - Control ↔ Release: whether the words should be held, or let go
+//!
+//! Some choices lean one way by themselves (holding on to a memory leans
+//! toward Preserve, pushing it away toward Unwrite), and every standing an
+//! encounter moves leans you toward what that faction believes, or away.
+//!
+//! Nothing shows the axes until late in the descent, but they are felt:
+//! - four voices in your head, one for each pole, and the one you lean
+//!   toward speaks most
+//! - offers from the factions that share your leanings turn up more often,
+//!   and from those that don't, less
+//! - leaning hard enough one way closes the ending that would undo it
+//!
+//! On the last floors before the end, the run shows you what it has made
+//! of you.
+//!
+//! Design: the leanings of choices are data in `LEANINGS`, keyed like the
+//! companions' and NPCs' reactions by encounter and choice. The ideology
+//! belongs to the run and starts level each time.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::encounter_writing::AuthoredEncounter;
+use super::narrative::Faction;
+
+/// How far the ideology leans before it is felt in the endings
+pub const LEANS_AT: i32 = 15;
+
+/// Furthest either axis can lean
+const LIMIT: i32 = 100;
+
+/// How far a standing moved by an encounter leans you, along each axis the
+/// faction holds a view on
+const STANDING_LEAN: i32 = 3;
+
+/// Chance one of the voices speaks as a room is entered
+pub const VOICE_CHANCE: f32 = 0.15;
+
+/// Floor from which the run shows what it has made of you
+pub const REVEAL_FLOOR: i32 = 9;
+
+/// One end of an axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pole {
+    Preserve,
+    Unwrite,
+    Control,
+    Release,
+}
+
+impl Pole {
+    pub const ALL: [Pole; 4] = [Pole::Preserve, Pole::Unwrite, Pole::Control, Pole::Release];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Preserve => "Preserve",
+            Self::Unwrite => "Unwrite",
+            Self::Control => "Control",
+            Self::Release => "Release",
+        }
+    }
+
+    /// The voice that speaks for it
+    pub fn voice(&self) -> &'static Voice {
+        &VOICES[*self as usize]
+    }
+}
+
+/// Where the run stands on both axes: positive toward Preserve and
+/// Control, negative toward Unwrite and Release
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Ideology {
+    pub preserve: i32,
+    pub control: i32,
+}
+
+impl Ideology {
+    pub fn lean(&mut self, pole: Pole, by: i32) {
+        let (axis, sign) = match pole {
+            Pole::Preserve => (&mut self.preserve, 1),
+            Pole::Unwrite => (&mut self.preserve, -1),
+            Pole::Control => (&mut self.control, 1),
+            Pole::Release => (&mut self.control, -1),
+        };
+        *axis = (*axis + sign * by).clamp(-LIMIT, LIMIT);
+    }
+
+    /// How far it leans toward `pole`; negative if away
+    pub fn toward(&self, pole: Pole) -> i32 {
+        match pole {
+            Pole::Preserve => self.preserve,
+            Pole::Unwrite => -self.preserve,
+            Pole::Control => self.control,
+            Pole::Release => -self.control,
+        }
+    }
+
+    pub fn leans(&self, pole: Pole) -> bool {
+        self.toward(pole) >= LEANS_AT
+    }
+
+    /// Lean with the choice made in an encounter, if it leans
+    pub fn on_choice(&mut self, encounter: &str, choice: &str) {
+        for leaning in LEANINGS.iter().filter(|l| l.encounter == encounter && l.choice == choice) {
+            self.lean(leaning.pole, leaning.by);
+        }
+    }
+
+    /// Lean toward what the factions whose standing rose believe, and away
+    /// from the beliefs of those whose standing fell
+    pub fn on_standing(&mut self, changes: &[(Faction, i32)]) {
+        for (faction, change) in changes {
+            let (preserve, control) = position(*faction);
+            self.preserve = (self.preserve + change.signum() * preserve * STANDING_LEAN).clamp(-LIMIT, LIMIT);
+            self.control = (self.control + change.signum() * control * STANDING_LEAN).clamp(-LIMIT, LIMIT);
+        }
+    }
+
+    /// How much more often `faction`'s offers turn up: more when it shares
+    /// your leanings, less when it doesn't
+    pub fn affinity(&self, faction: Faction) -> f32 {
+        let (preserve, control) = position(faction);
+        let agreement = preserve * self.preserve + control * self.control;
+        1.0 + (agreement as f32 / LIMIT as f32).clamp(-0.5, 1.0)
+    }
+
+    /// The voice that speaks as a room is entered: any of them, but the
+    /// further you lean toward a pole the more often its voice
+    pub fn voice<R: Rng>(&self, rng: &mut R) -> Option<(&'static Voice, &'static str)> {
+        let pole = Pole::ALL.choose_weighted(rng, |p| 1 + self.toward(*p).max(0) / 5).ok()?;
+        let voice = pole.voice();
+        Some((voice, voice.lines.choose(rng)?))
+    }
+
+    /// The faction whose offers the run's leanings favour most, if any
+    pub fn favoured(&self) -> Option<Faction> {
+        FACTIONS.into_iter()
+            .filter(|f| self.affinity(*f) > 1.0)
+            .max_by(|a, b| self.affinity(*a).total_cmp(&self.affinity(*b)))
+    }
+
+    /// The pole the run leans furthest toward
+    pub fn strongest(&self) -> Pole {
+        Pole::ALL.into_iter().max_by_key(|p| self.toward(*p)).unwrap_or(Pole::Preserve)
+    }
+}
+
+const FACTIONS: [Faction; 5] = [
+    Faction::MagesGuild,
+    Faction::TempleOfDawn,
+    Faction::RangersOfTheWild,
+    Faction::ShadowGuild,
+    Faction::MerchantConsortium,
+];
+
+/// A faction's view on each axis, as (preserve, control)
+fn position(faction: Faction) -> (i32, i32) {
+    match faction {
+        Faction::MagesGuild => (0, 1),
+        Faction::TempleOfDawn => (1, 1),
+        Faction::RangersOfTheWild => (0, -1),
+        Faction::ShadowGuild => (-1, -1),
+        Faction::MerchantConsortium => (1, 0),
+    }
+}
+
+/// Tags that mark an encounter as an offer from a faction
+const OFFER_TAGS: [&str; 3] = ["faction", "questline", "offer"];
+
+/// The faction offering `encounter`, if it is a faction's offer
+pub fn offered_by(encounter: &AuthoredEncounter) -> Option<Faction> {
+    if !encounter.tags.iter().any(|t| OFFER_TAGS.contains(&t.as_str())) {
+        return None;
+    }
+    encounter.tags.iter().find_map(|tag| match tag.as_str() {
+        "scribes" => Some(Faction::MagesGuild),
+        "mechanists" => Some(Faction::TempleOfDawn),
+        "naturalists" => Some(Faction::RangersOfTheWild),
+        "shadowwriters" => Some(Faction::ShadowGuild),
+        "archivists" => Some(Faction::MerchantConsortium),
+        _ => None,
+    })
+}
+
+/// A voice in your head
+#[derive(Debug)]
+pub struct Voice {
+    pub name: &'static str,
+    pub lines: &'static [&'static str],
+}
+
+/// In `Pole` order
+static VOICES: [Voice; 4] = [
+    Voice {
+        name: "The Archivist Within",
+        lines: &[
+            "Write it down. What isn't written down didn't happen.",
+            "Every word lost is a person lost. Keep them.",
+            "You had a name once. Don't let this place have it.",
+        ],
+    },
+    Voice {
+        name: "The Eraser",
+        lines: &[
+            "Let it go. Every word you keep can be turned against you.",
+            "The page was cleaner before anyone wrote on it.",
+            "What was written can be unwritten. That's a mercy.",
+        ],
+    },
+    Voice {
+        name: "The Hand",
+        lines: &[
+            "Hold the pen tighter. Nothing is written unless you write it.",
+            "Someone has to decide what the words mean. Why not you?",
+            "Order first. Understanding after.",
+        ],
+    },
+    Voice {
+        name: "The Open Palm",
+        lines: &[
+            "Stop gripping. The words know where they want to go.",
+            "You don't have to finish every sentence.",
+            "A word held too tightly stops meaning anything.",
+        ],
+    },
+];
+
+/// How a choice in an encounter leans you
+struct Leaning {
+    encounter: &'static str,
+    choice: &'static str,
+    pole: Pole,
+    by: i32,
+}
+
+const fn leaning(encounter: &'static str, choice: &'static str, pole: Pole, by: i32) -> Leaning {
+    Leaning { encounter, choice, pole, by }
+}
+
+static LEANINGS: [Leaning; 24] = [
+    leaning("haven_stranger_arrival", "help_stranger", Pole::Preserve, 3),
+    leaning("haven_stranger_arrival", "refuse_stranger", Pole::Release, 3),
+    leaning("haven_stranger_arrival", "test_stranger", Pole::Control, 5),
+    leaning("haven_old_scribe", "ask_about_past", Pole::Preserve, 3),
+    leaning("haven_old_scribe", "ask_about_archivists", Pole::Unwrite, 3),
+    leaning("haven_old_scribe", "offer_help", Pole::Release, 3),
+    leaning("athenaeum_living_book", "accept_book", Pole::Preserve, 5),
+    leaning("athenaeum_living_book", "refuse_book", Pole::Release, 3),
+    leaning("athenaeum_living_book", "negotiate_book", Pole::Control, 5),
+    leaning("corruption_memory_echo", "embrace_memory", Pole::Preserve, 8),
+    leaning("corruption_memory_echo", "reject_memory", Pole::Unwrite, 8),
+    leaning("corruption_memory_echo", "analyze_memory", Pole::Control, 5),
+    leaning("corruption_empty_armor", "name_the_knight", Pole::Preserve, 5),
+    leaning("corruption_empty_armor", "leave_the_knight", Pole::Release, 5),
+    leaning("mechanist_breakdown", "comfort_mechanist", Pole::Release, 5),
+    leaning("mechanist_breakdown", "challenge_mechanist", Pole::Control, 5),
+    leaning("mechanist_breakdown", "help_mechanist", Pole::Preserve, 3),
+    leaning("shadowwriter_offer", "accept_shadow", Pole::Unwrite, 5),
+    leaning("shadowwriter_offer", "refuse_shadow", Pole::Control, 3),
+    leaning("shadowwriter_offer", "demand_info", Pole::Control, 5),
+    leaning("first_archivist_meeting", "speak_true_name", Pole::Preserve, 8),
+    leaning("first_archivist_meeting", "ask_third_grammar", Pole::Release, 5),
+    leaning("first_archivist_meeting", "ask_spouse", Pole::Preserve, 3),
+    leaning("first_archivist_meeting", "reject_past", Pole::Unwrite, 8),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    #[test]
+    fn test_every_leaning_names_a_real_choice() {
+        let encounters = build_encounters();
+        for l in &LEANINGS {
+            let encounter = encounters.get(l.encounter).unwrap_or_else(|| panic!("no encounter {}", l.encounter));
+            assert!(encounter.choices.iter().any(|c| c.id == l.choice), "{} has no choice {}", l.encounter, l.choice);
+        }
+        assert_eq!(offered_by(&encounters["shadowwriter_offer"]), Some(Faction::ShadowGuild));
+        assert_eq!(offered_by(&encounters["haven_old_scribe"]), None);
+    }
+
+    #[test]
+    fn test_choices_and_standings_lean_the_run_and_its_voices() {
+        let mut ideology = Ideology::default();
+        ideology.on_choice("corruption_memory_echo", "reject_memory");
+        ideology.on_choice("first_archivist_meeting", "reject_past");
+        assert!(ideology.leans(Pole::Unwrite) && !ideology.leans(Pole::Preserve));
+        assert_eq!(ideology.strongest(), Pole::Unwrite);
+
+        // Standing with the Shadow Writers rising leans further the same way
+        ideology.on_standing(&[(Faction::ShadowGuild, 10)]);
+        assert_eq!(ideology.toward(Pole::Unwrite), 16 + STANDING_LEAN);
+        assert!(ideology.affinity(Faction::ShadowGuild) > 1.0);
+        assert!(ideology.affinity(Faction::MerchantConsortium) < 1.0);
+        assert_eq!(ideology.favoured(), Some(Faction::ShadowGuild));
+        assert_eq!(Ideology::default().favoured(), None);
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let eraser = (0..200).filter(|_| ideology.voice(&mut rng).unwrap().0.name == "The Eraser").count();
+        assert!(eraser > 80, "the voice leaned toward should speak most, spoke {} of 200", eraser);
+
+        ideology.lean(Pole::Control, 1000);
+        assert_eq!(ideology.control, LIMIT);
+    }
+}
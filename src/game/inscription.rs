@@ -0,0 +1,59 @@
+//! Inscription - Read it once, then write it from memory
+//!
+//! A two-phase stance for deliberate typists. The player first studies the
+//! upcoming sentence for a few seconds (keys do nothing), then the prompt
+//! is hidden and must be typed from memory. Success strikes for massive
+//! damage; a slip is just a missed word.
+//!
+//! Design: CombatState holds the stance while it lasts; this module only
+//! knows how long the study phase runs and when it is over.
+
+use std::time::{Duration, Instant};
+
+/// Damage multiplier for a sentence written from memory
+pub const INSCRIPTION_MULT: f32 = 3.0;
+/// Study time every sentence gets
+const PREVIEW_BASE_SECS: f32 = 2.0;
+/// Extra study time per character
+const PREVIEW_PER_CHAR_SECS: f32 = 0.06;
+/// Longest study phase
+const PREVIEW_MAX_SECS: f32 = 8.0;
+
+/// An inscription in progress
+#[derive(Debug, Clone, Copy)]
+pub struct Inscription {
+    /// When the prompt hides and typing opens
+    pub hides_at: Instant,
+}
+
+impl Inscription {
+    pub fn begin(prompt_len: usize) -> Self {
+        Self { hides_at: Instant::now() + Duration::from_secs_f32(preview_secs(prompt_len)) }
+    }
+
+    /// Still studying: the prompt shows but keys do nothing
+    pub fn is_previewing(&self) -> bool {
+        Instant::now() < self.hides_at
+    }
+
+    pub fn preview_secs_left(&self) -> f32 {
+        self.hides_at.saturating_duration_since(Instant::now()).as_secs_f32()
+    }
+}
+
+/// Seconds to study a prompt of this many characters
+pub fn preview_secs(prompt_len: usize) -> f32 {
+    (PREVIEW_BASE_SECS + prompt_len as f32 * PREVIEW_PER_CHAR_SECS).min(PREVIEW_MAX_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_scales_with_length() {
+        assert!(preview_secs(60) > preview_secs(20));
+        assert_eq!(preview_secs(1000), PREVIEW_MAX_SECS);
+        assert!(Inscription::begin(40).is_previewing());
+    }
+}
@@ -0,0 +1,194 @@
+//! Item Lore - Descriptions that say more the more you know
+//!
+//! Items are described the way the writing guidelines ask (the Dark Souls
+//! model of layered meaning), in three tiers read in turn:
+//! - Surface: what the thing is; always shown
+//! - Character: who made or carried it; opens once you have dealt with the
+//!   faction it speaks of
+//! - Theme: what it says about the word; opens when you carry the artifact
+//!   it names, or when its faction thinks well enough of you to tell you
+//!
+//! The authored lore lives in `data/items/lore.ron`. An item nobody has
+//! written lore for still reads in three tiers: its own flavor text, then
+//! lines for its kind of item tied to a faction picked from its name.
+//!
+//! Design: like the codex, the tiers are worked out from the run's faction
+//! standings and artifacts each time an item is inspected, and nothing is
+//! stored. A closed tier is shown redacted, so the player can see there is
+//! more to read.
+
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use super::codex::FIGURE_STANDING;
+use super::faction_system::FactionRelations;
+use super::items::{Item, ItemType};
+use super::narrative::Faction;
+
+const BUNDLED: &str = include_str!("../../data/items/lore.ron");
+
+static LORE: OnceLock<Vec<ItemLore>> = OnceLock::new();
+
+/// Factions an unwritten item can be tied to
+const FACTIONS: [Faction; 5] = [
+    Faction::MagesGuild,
+    Faction::TempleOfDawn,
+    Faction::RangersOfTheWild,
+    Faction::ShadowGuild,
+    Faction::MerchantConsortium,
+];
+
+/// One item's authored lore
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ItemLore {
+    pub item: String,
+    /// Faction id the lore speaks of
+    pub faction: String,
+    /// Lore artifact whose carrying opens the theme
+    #[serde(default)]
+    pub artifact: Option<String>,
+    pub surface: String,
+    pub character: String,
+    pub theme: String,
+}
+
+/// The three readings of an item's description
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoreTier {
+    Surface,
+    Character,
+    Theme,
+}
+
+impl LoreTier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Surface => "Surface",
+            Self::Character => "Character",
+            Self::Theme => "Theme",
+        }
+    }
+}
+
+/// One tier of an inspected item
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectLine {
+    pub tier: LoreTier,
+    pub text: String,
+    pub revealed: bool,
+}
+
+/// Parse and check the lore file
+pub fn parse(text: &str) -> Result<Vec<ItemLore>, String> {
+    let lore: Vec<ItemLore> = ron::from_str(text).map_err(|e| e.to_string())?;
+    for entry in &lore {
+        if Faction::from_id(&entry.faction).is_none() {
+            return Err(format!("{}: unknown faction '{}'", entry.item, entry.faction));
+        }
+    }
+    Ok(lore)
+}
+
+/// The authored lore, loaded once
+pub fn bundled() -> &'static [ItemLore] {
+    LORE.get_or_init(|| parse(BUNDLED).unwrap_or_else(|e| panic!("bundled item lore is broken: {}", e)))
+}
+
+/// Lines for an item nobody has written lore for, by kind
+fn unwritten(item_type: ItemType) -> (&'static str, &'static str) {
+    match item_type {
+        ItemType::Consumable => (
+            "Brewed by hands that expected thanks, and sold by #faction# factors who never gave any.",
+            "What mends the body was once a word for mending, said until it thickened.",
+        ),
+        ItemType::Equipment => (
+            "Worn smooth where another typist's fingers rested. A #faction# mark has been scratched off the inside.",
+            "Every tool of the word outlasts the hand that held it. That is its mercy, and its cruelty.",
+        ),
+        ItemType::Joker => (
+            "A card from a #faction# deck, played by someone who wanted luck to look like skill.",
+            "Chance is only a sentence no one has finished writing.",
+        ),
+        ItemType::Relic => (
+            "Kept by #faction# in a room with no door, and brought out only when someone died.",
+            "To keep a thing forever is to stop it meaning anything new.",
+        ),
+    }
+}
+
+/// The item's three tiers, opened by what the run has earned
+pub fn inspect(item: &Item, factions: &FactionRelations, artifacts: &[String]) -> Vec<InspectLine> {
+    let authored = bundled().iter().find(|l| l.item == item.name);
+    let (faction, surface, character, theme, artifact) = match authored {
+        Some(lore) => (
+            Faction::from_id(&lore.faction).unwrap_or(Faction::MagesGuild),
+            lore.surface.as_str(),
+            lore.character.as_str(),
+            lore.theme.as_str(),
+            lore.artifact.as_deref(),
+        ),
+        None => {
+            let pick = item.name.bytes().map(usize::from).sum::<usize>() % FACTIONS.len();
+            let (character, theme) = unwritten(item.item_type);
+            let surface = if item.flavor_text.is_empty() { &item.description } else { &item.flavor_text };
+            (FACTIONS[pick], surface.as_str(), character, theme, None)
+        }
+    };
+    let standing = factions.standing(&faction);
+    let carried = artifact.is_some_and(|a| artifacts.iter().any(|c| c == a));
+    let line = |tier, text: &str, revealed| InspectLine {
+        tier,
+        text: text.replace("#faction#", faction.name()),
+        revealed,
+    };
+    vec![
+        line(LoreTier::Surface, surface, true),
+        line(LoreTier::Character, character, standing != 0),
+        line(LoreTier::Theme, theme, carried || standing >= FIGURE_STANDING),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::items::ItemDatabase;
+
+    #[test]
+    fn test_authored_lore_names_real_items() {
+        let db = ItemDatabase::embedded();
+        let names: Vec<String> = db.equipment.values().map(|e| e.name.clone())
+            .chain(db.consumables.values().map(|c| c.name.clone()))
+            .chain(Item::consumable_pool().into_iter().map(|i| i.name))
+            .collect();
+        let lore = parse(BUNDLED).unwrap();
+        assert!(!lore.is_empty());
+        for entry in &lore {
+            assert!(names.contains(&entry.item), "no item named {}", entry.item);
+        }
+        assert!(parse(&BUNDLED.replace("\"MagesGuild\"", "\"Librarians\"")).is_err());
+    }
+
+    #[test]
+    fn test_tiers_open_with_standing_and_artifacts() {
+        let db = ItemDatabase::embedded();
+        let blade = Item::from_equipment(db.get_equipment("inkblade").unwrap());
+        let mut factions = FactionRelations::new();
+        let revealed = |lines: &[InspectLine]| lines.iter().map(|l| l.revealed).collect::<Vec<_>>();
+
+        assert_eq!(revealed(&inspect(&blade, &factions, &[])), [true, false, false]);
+        factions.modify_standing(Faction::ShadowGuild, 5);
+        assert_eq!(revealed(&inspect(&blade, &factions, &[])), [true, true, false]);
+        let manuscript = vec!["The Original Manuscript".to_string()];
+        let lines = inspect(&blade, &factions, &manuscript);
+        assert_eq!(revealed(&lines), [true, true, true]);
+        assert!(lines.iter().all(|l| !l.text.contains('#')));
+
+        // Items without authored lore still read in three tiers
+        let joker = Item::joker_pool().remove(0);
+        let lines = inspect(&joker, &FactionRelations::new(), &[]);
+        assert_eq!(lines[0].text, joker.flavor_text);
+        assert_eq!(revealed(&lines), [true, false, false]);
+    }
+}
@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::data::items::{Consumable, Equipment, Rarity};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ItemRarity {
@@ -87,6 +90,11 @@ pub enum ItemEffect {
     StartingShield(i32),
     BossKiller(i32),        // % bonus damage to bosses
     SpeedDemon(f32),        // Time limit reduced but damage up
+    
+    // Typing gear - worn in a slot, id from the item database
+    Gear(String),
+    // Combat consumable - typed by name mid-fight, id from the item database
+    Combat(String),
 }
 
 impl Item {
@@ -368,9 +376,8 @@ impl Item {
         ]
     }
 
-    pub fn random_consumable() -> Self {
-        let mut rng = rand::thread_rng();
-        Self::consumable_pool().choose(&mut rng).unwrap().clone()
+    pub fn random_consumable(rng: &mut impl Rng) -> Self {
+        Self::consumable_pool().choose(rng).unwrap().clone()
     }
 
     pub fn random_joker() -> Self {
@@ -399,6 +406,49 @@ impl Item {
     }
 }
 
+// === Typing gear ===
+
+impl From<Rarity> for ItemRarity {
+    fn from(rarity: Rarity) -> Self {
+        match rarity {
+            Rarity::Common => ItemRarity::Common,
+            Rarity::Uncommon => ItemRarity::Uncommon,
+            Rarity::Rare => ItemRarity::Rare,
+            Rarity::Epic => ItemRarity::Epic,
+            Rarity::Legendary => ItemRarity::Legendary,
+        }
+    }
+}
+
+impl Item {
+    /// A shop/inventory card for a piece of gear from the item database
+    pub fn from_equipment(gear: &Equipment) -> Self {
+        let rarity = ItemRarity::from(gear.rarity);
+        Item {
+            name: gear.name.clone(),
+            description: format!("{} [{}: {}]", gear.description, gear.slot.name(), gear.impact.summary()),
+            flavor_text: gear.flavor_text.clone(),
+            item_type: ItemType::Equipment,
+            rarity,
+            effect: ItemEffect::Gear(gear.id.clone()),
+            price: gear.gold_value,
+        }
+    }
+
+    /// A shop/inventory card for a combat consumable from the item database
+    pub fn from_consumable(item: &Consumable) -> Self {
+        Item {
+            name: item.name.clone(),
+            description: format!("{} Type /{} in combat.", item.description, item.name.to_lowercase()),
+            flavor_text: String::new(),
+            item_type: ItemType::Consumable,
+            rarity: ItemRarity::from(item.rarity),
+            effect: ItemEffect::Combat(item.id.clone()),
+            price: item.gold_value,
+        }
+    }
+}
+
 // === Dr. Baklava Easter Egg Items ===
 
 impl Item {
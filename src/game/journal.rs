@@ -0,0 +1,179 @@
+//! Journal - The quest chains you have set in motion
+//!
+//! Choices in authored encounters open follow-ups. The journal keeps track
+//! of them for the player:
+//! - Active chains: each follow-up still waiting, with what opened it and
+//!   the objectives standing between you and it
+//! - Known clues: what encounters have revealed so far
+//! - The world, changed: shifts your choices have set in motion
+//!
+//! Design: the journal is rebuilt from the EncounterTracker (which chains
+//! are open), the authored encounters (what they need) and the consequence
+//! ledger each time it is opened; it keeps no state of its own.
+
+use std::collections::HashMap;
+
+use super::consequences::{ChangeKind, WorldLedger};
+use super::encounter_writing::{AuthoredEncounter, EncounterTracker};
+use super::faction_system::FactionRelations;
+use super::narrative::Faction;
+use super::world_state::WorldState;
+
+/// One step standing between the player and a follow-up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Objective {
+    pub text: String,
+    pub done: bool,
+}
+
+impl Objective {
+    fn new(text: impl Into<String>, done: bool) -> Self {
+        Self { text: text.into(), done }
+    }
+}
+
+/// A follow-up encounter still waiting
+#[derive(Debug, Clone)]
+pub struct ChainEntry {
+    pub title: String,
+    /// Encounter (and choice made there) that opened it
+    pub opened_by: String,
+    pub objectives: Vec<Objective>,
+}
+
+/// Everything the journal shows
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    pub chains: Vec<ChainEntry>,
+    pub clues: Vec<String>,
+    pub world_shifts: Vec<String>,
+}
+
+/// "whispering_waste" → "Whispering Waste"
+fn humanize(id: &str) -> String {
+    id.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// What stands between the player and `encounter`
+fn objectives_for(encounter: &AuthoredEncounter, floor: i32, factions: &FactionRelations, world: &WorldState) -> Vec<Objective> {
+    let req = &encounter.requirements;
+    let mut objectives = Vec::new();
+    if let Some(min) = req.min_chapter {
+        objectives.push(Objective::new(format!("Reach floor {}", min), floor >= min as i32));
+    }
+    if let Some((name, min)) = &req.faction_reputation {
+        let (label, standing) = match Faction::from_id(name) {
+            Some(f) => (f.name().to_string(), factions.standing(&f)),
+            None => (humanize(name), 0),
+        };
+        objectives.push(Objective::new(format!("Earn {} standing with {}", min, label), standing >= *min));
+    }
+    if let Some(lore) = &req.required_lore {
+        objectives.push(Objective::new(format!("Uncover {}", humanize(lore)), false));
+    }
+    for flag in &req.required_flags {
+        objectives.push(Objective::new(format!("Bring about: {}", humanize(flag)), world.has_flag(flag)));
+    }
+    let places: Vec<String> = encounter.valid_locations.iter().map(|l| humanize(l)).collect();
+    if !places.is_empty() {
+        objectives.push(Objective::new(format!("Seek it in {}", places.join(" or ")), false));
+    }
+    objectives
+}
+
+/// Build the journal from what the run has set in motion
+pub fn build(
+    tracker: &EncounterTracker,
+    encounters: &HashMap<String, AuthoredEncounter>,
+    ledger: &WorldLedger,
+    floor: i32,
+    factions: &FactionRelations,
+    world: &WorldState,
+) -> Journal {
+    let chains = tracker.active_chains.iter().map(|id| {
+        let opener = encounters.values()
+            .filter(|e| tracker.has_completed(&e.id))
+            .find(|e| e.consequences.enables_encounters.contains(id));
+        let opened_by = match opener {
+            Some(e) => {
+                let choice = tracker.get_choice(&e.id)
+                    .and_then(|c| e.choices.iter().find(|choice| choice.id == *c))
+                    .map(|choice| format!(" - \"{}\"", choice.text))
+                    .unwrap_or_default();
+                format!("{}{}", e.title, choice)
+            }
+            None => "An earlier choice".to_string(),
+        };
+        match encounters.get(id) {
+            Some(next) => ChainEntry {
+                title: next.title.clone(),
+                opened_by,
+                objectives: objectives_for(next, floor, factions, world),
+            },
+            None => ChainEntry {
+                title: humanize(id),
+                opened_by,
+                objectives: vec![Objective::new("Keep descending; the trail will find you", false)],
+            },
+        }
+    }).collect();
+
+    let mut clues = Vec::new();
+    let mut completed: Vec<&AuthoredEncounter> = encounters.values().filter(|e| tracker.has_completed(&e.id)).collect();
+    completed.sort_by(|a, b| a.id.cmp(&b.id));
+    for encounter in completed {
+        let revealed = encounter.content.dialogue.iter().flatten().filter_map(|line| line.reveals.clone());
+        let lore = encounter.consequences.lore_revealed.iter().map(|id| format!("Lore: {}", humanize(id)));
+        for clue in revealed.chain(lore) {
+            if !clues.contains(&clue) {
+                clues.push(clue);
+            }
+        }
+    }
+
+    let world_shifts = ledger.of_kind(ChangeKind::WorldShift).into_iter().map(|c| c.description.clone()).collect();
+
+    Journal { chains, clues, world_shifts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    #[test]
+    fn test_humanize() {
+        assert_eq!(humanize("whispering_waste"), "Whispering Waste");
+        assert_eq!(humanize("haven"), "Haven");
+    }
+
+    #[test]
+    fn test_completed_encounters_open_chains_and_clues() {
+        let encounters = build_encounters();
+        let mut tracker = EncounterTracker::new();
+        let factions = FactionRelations::new();
+        let ledger = WorldLedger::new();
+        let world = WorldState::default();
+        assert!(build(&tracker, &encounters, &ledger, 1, &factions, &world).chains.is_empty());
+
+        let opener = &encounters["haven_stranger_arrival"];
+        tracker.complete_encounter(&opener.id, "help_stranger");
+        tracker.advance_chains(&opener.id, &opener.consequences.enables_encounters);
+        let journal = build(&tracker, &encounters, &ledger, 1, &factions, &world);
+        assert_eq!(journal.chains.len(), 1);
+        assert_eq!(journal.chains[0].title, "Waste Investigation");
+        assert!(journal.chains[0].opened_by.starts_with("A Stranger Arrives"));
+        assert!(journal.clues.iter().any(|c| c.contains("Whispering Waste")));
+
+        // Completing the follow-up closes its chain
+        tracker.advance_chains("waste_investigation", &[]);
+        assert!(build(&tracker, &encounters, &ledger, 1, &factions, &world).chains.is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+//! Key Accuracy - Which keys cost you damage
+//!
+//! Every key of a prompt typed in a fight is counted against the physical
+//! key it sits on, kept across the profile:
+//! - how often each key was asked for, and how often it was missed
+//! - the heatmap screen (`e` on the title screen) draws the keyboard with
+//!   each keycap colored by its error rate, and lists the worst keys
+//!
+//! Every miss takes accuracy off its word, and the word's damage with it,
+//! so the keys missed most are the ones that have cost the most damage.
+//!
+//! Design: keys are counted by position on the main block, the three rows
+//! of ten that the keyboard layouts describe, so switching layout keeps
+//! the count with the key under the finger rather than the letter. Keys
+//! off the main block (digits, space, accented letters) are not counted.
+//! The counts are kept in a file of their own beside the config and saved
+//! after each fight.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::config::get_config_dir;
+use super::keyboard_layout::KeyboardLayout;
+
+/// Keys on each row of the main block
+pub const COLUMNS: usize = 10;
+
+/// Rows of the main block
+pub const ROWS: usize = 3;
+
+/// Presses a key needs before its error rate is worth showing
+pub const MIN_PRESSES: u32 = 10;
+
+/// How one key has gone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRecord {
+    /// Times the key was asked for
+    pub presses: u32,
+    /// Times something else was typed in its place
+    pub misses: u32,
+}
+
+impl KeyRecord {
+    /// Share of presses missed, once there are enough to tell
+    pub fn error_rate(&self) -> Option<f32> {
+        (self.presses >= MIN_PRESSES).then(|| self.misses as f32 / self.presses as f32)
+    }
+}
+
+/// Every key of the main block, row by row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyAccuracy {
+    keys: Vec<KeyRecord>,
+}
+
+impl Default for KeyAccuracy {
+    fn default() -> Self {
+        Self { keys: vec![KeyRecord::default(); ROWS * COLUMNS] }
+    }
+}
+
+impl KeyAccuracy {
+    /// Count a press of the key `expected` sits on under `layout`
+    pub fn record(&mut self, layout: KeyboardLayout, expected: char, correct: bool) {
+        let Some((row, col)) = layout.key_position(expected) else {
+            return;
+        };
+        if let Some(key) = self.keys.get_mut(row * COLUMNS + col) {
+            key.presses += 1;
+            if !correct {
+                key.misses += 1;
+            }
+        }
+    }
+
+    /// The record of the key at (`row`, `col`)
+    pub fn key(&self, row: usize, col: usize) -> KeyRecord {
+        self.keys.get(row * COLUMNS + col).copied().unwrap_or_default()
+    }
+
+    /// The keys with an error rate, worst first, as their legend under
+    /// `layout` and their record
+    pub fn worst(&self, layout: KeyboardLayout) -> Vec<(char, KeyRecord)> {
+        let mut keys: Vec<(char, KeyRecord)> = layout.rows().iter().enumerate()
+            .flat_map(|(row, legends)| legends.chars().enumerate().map(move |(col, c)| (c, row, col)))
+            .map(|(c, row, col)| (c, self.key(row, col)))
+            .filter(|(_, key)| key.error_rate().is_some())
+            .collect();
+        keys.sort_by(|a, b| b.1.error_rate().partial_cmp(&a.1.error_rate()).unwrap_or(std::cmp::Ordering::Equal));
+        keys
+    }
+
+    /// Presses counted across every key
+    pub fn presses(&self) -> u32 {
+        self.keys.iter().map(|k| k.presses).sum()
+    }
+}
+
+fn record_path() -> PathBuf {
+    get_config_dir().join("key_accuracy.ron")
+}
+
+pub fn load() -> KeyAccuracy {
+    let mut accuracy: KeyAccuracy = fs::read_to_string(record_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default();
+    accuracy.keys.resize(ROWS * COLUMNS, KeyRecord::default());
+    accuracy
+}
+
+pub fn save(accuracy: &KeyAccuracy) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(accuracy, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(record_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_counted_by_position_whatever_the_layout() {
+        let mut accuracy = KeyAccuracy::default();
+        accuracy.record(KeyboardLayout::Qwerty, 'S', false);
+        // Dvorak's o is where QWERTY's s is
+        accuracy.record(KeyboardLayout::Dvorak, 'o', true);
+        accuracy.record(KeyboardLayout::Qwerty, '7', false);
+        accuracy.record(KeyboardLayout::Qwerty, 'é', false);
+        assert_eq!(accuracy.key(1, 1), KeyRecord { presses: 2, misses: 1 });
+        assert_eq!(accuracy.presses(), 2);
+    }
+
+    #[test]
+    fn test_the_worst_keys_come_first_once_there_are_enough_presses() {
+        let mut accuracy = KeyAccuracy::default();
+        for i in 0..MIN_PRESSES {
+            accuracy.record(KeyboardLayout::Qwerty, 'e', i < 3);
+            accuracy.record(KeyboardLayout::Qwerty, 'k', i > 0);
+            accuracy.record(KeyboardLayout::Qwerty, 'z', false);
+        }
+        accuracy.record(KeyboardLayout::Qwerty, 'q', false);
+        let worst = accuracy.worst(KeyboardLayout::Qwerty);
+        assert_eq!(worst.iter().map(|(c, _)| *c).collect::<String>(), "zek");
+        assert_eq!(worst[1].1.error_rate(), Some(0.7));
+        // The same keys, by their Colemak legends
+        assert_eq!(accuracy.worst(KeyboardLayout::Colemak)[1].0, 'f');
+
+        let text = ron::to_string(&accuracy).unwrap();
+        assert_eq!(ron::from_str::<KeyAccuracy>(&text).unwrap(), accuracy);
+    }
+}
@@ -0,0 +1,286 @@
+//! Keybinds - The keys that do things other than type
+//!
+//! Every key that is not typed into a prompt is bound to an action here,
+//! and every action can be bound to other keys:
+//! - menus: up, down, confirm, back and help
+//! - combat: spell mode, stance, heavy strike, replaying the spoken prompt,
+//!   fleeing and pausing
+//! - anywhere: the transcript pane
+//!
+//! The keybinds screen (`b` on the settings screen) replaces or adds a key
+//! for the selected action, or puts its default keys back. A key is refused
+//! if another action that can fire at the same moment already has it, or if
+//! it types a character, since those are kept for typing and for the
+//! screens' own letters.
+//!
+//! Design: keys are held by name ("Up", "F3", "k") so the game knows
+//! nothing of the terminal; main.rs names each key pressed and asks which
+//! action it fires. Menus add bound keys on top of their own arrows, Enter
+//! and Esc; combat and the anywhere keys are read from the bindings alone.
+//! The bindings are kept in a file of their own beside the config, and an
+//! action missing from it keeps its defaults.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::config::get_config_dir;
+
+/// When an action can fire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// On every screen that is not being typed into
+    Menu,
+    /// In a fight
+    Combat,
+    /// Everywhere, typing or not
+    Anywhere,
+}
+
+impl Context {
+    pub const ALL: [Context; 3] = [Context::Menu, Context::Combat, Context::Anywhere];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Menu => "Menus",
+            Self::Combat => "Combat",
+            Self::Anywhere => "Anywhere",
+        }
+    }
+
+    /// Whether actions of the two contexts can fire on the same key press
+    fn overlaps(&self, other: Context) -> bool {
+        *self == other || *self == Context::Anywhere || other == Context::Anywhere
+    }
+}
+
+/// Something a key does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    MenuUp,
+    MenuDown,
+    Confirm,
+    Back,
+    Help,
+    SpellMode,
+    Stance,
+    HeavyStrike,
+    ReplayPrompt,
+    Flee,
+    Pause,
+    Transcript,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::Confirm,
+        Action::Back,
+        Action::Help,
+        Action::SpellMode,
+        Action::Stance,
+        Action::HeavyStrike,
+        Action::ReplayPrompt,
+        Action::Flee,
+        Action::Pause,
+        Action::Transcript,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MenuUp => "Up",
+            Self::MenuDown => "Down",
+            Self::Confirm => "Confirm",
+            Self::Back => "Back",
+            Self::Help => "Help",
+            Self::SpellMode => "Spell mode",
+            Self::Stance => "Switch stance",
+            Self::HeavyStrike => "Heavy strike",
+            Self::ReplayPrompt => "Replay spoken prompt",
+            Self::Flee => "Flee",
+            Self::Pause => "Pause",
+            Self::Transcript => "Transcript",
+        }
+    }
+
+    pub fn context(&self) -> Context {
+        match self {
+            Self::MenuUp | Self::MenuDown | Self::Confirm | Self::Back | Self::Help => Context::Menu,
+            Self::SpellMode | Self::Stance | Self::HeavyStrike | Self::ReplayPrompt | Self::Flee | Self::Pause => Context::Combat,
+            Self::Transcript => Context::Anywhere,
+        }
+    }
+
+    pub fn defaults(&self) -> &'static [&'static str] {
+        match self {
+            Self::MenuUp => &["Up", "k"],
+            Self::MenuDown => &["Down", "j"],
+            Self::Confirm => &["Enter"],
+            Self::Back => &["Esc"],
+            Self::Help => &["?", "h"],
+            Self::SpellMode => &["Tab"],
+            Self::Stance => &["BackTab"],
+            Self::HeavyStrike => &["Enter"],
+            Self::ReplayPrompt => &["F3"],
+            Self::Flee => &["Esc"],
+            Self::Pause => &["F4"],
+            Self::Transcript => &["F2"],
+        }
+    }
+}
+
+/// Why a key cannot be bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// It types a character
+    Typed,
+    /// An action that can fire at the same moment already has it
+    Taken(Action),
+}
+
+impl Conflict {
+    pub fn describe(&self, key: &str) -> String {
+        match self {
+            Self::Typed => format!("{} types a character; letters are kept for typing", key),
+            Self::Taken(other) => format!("{} is already {} ({})", key, other.name(), other.context().name()),
+        }
+    }
+}
+
+/// Every action's keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keybinds {
+    keys: BTreeMap<Action, Vec<String>>,
+}
+
+impl Default for Keybinds {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL.iter().map(|a| (*a, a.defaults().iter().map(|k| k.to_string()).collect())).collect(),
+        }
+    }
+}
+
+impl Keybinds {
+    pub fn keys(&self, action: Action) -> &[String] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The action's keys as a hint shows them
+    pub fn label(&self, action: Action) -> String {
+        match self.keys(action) {
+            [] => "unbound".to_string(),
+            keys => keys.join("/"),
+        }
+    }
+
+    /// Whether the action has its default keys
+    pub fn is_default(&self, action: Action) -> bool {
+        self.keys(action).iter().map(String::as_str).eq(action.defaults().iter().copied())
+    }
+
+    /// The action `key` fires in `context`, if any
+    pub fn action(&self, context: Context, key: &str) -> Option<Action> {
+        Action::ALL.iter().copied()
+            .filter(|a| a.context() == context || a.context() == Context::Anywhere)
+            .find(|a| self.keys(*a).iter().any(|k| k == key))
+    }
+
+    /// Why `key` cannot be bound to `action`, if it cannot
+    pub fn conflict(&self, action: Action, key: &str) -> Option<Conflict> {
+        if key.chars().count() == 1 || key == "Space" {
+            return Some(Conflict::Typed);
+        }
+        Action::ALL.iter().copied()
+            .filter(|other| *other != action && other.context().overlaps(action.context()))
+            .find(|other| self.keys(*other).iter().any(|k| k == key))
+            .map(Conflict::Taken)
+    }
+
+    /// Bind `key` to `action`, in place of its keys or beside them
+    pub fn bind(&mut self, action: Action, key: &str, add: bool) -> Result<(), Conflict> {
+        if let Some(conflict) = self.conflict(action, key) {
+            return Err(conflict);
+        }
+        let keys = self.keys.entry(action).or_default();
+        if !add {
+            keys.clear();
+        }
+        if !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
+        }
+        Ok(())
+    }
+
+    /// Put the action's default keys back
+    pub fn reset(&mut self, action: Action) {
+        self.keys.insert(action, action.defaults().iter().map(|k| k.to_string()).collect());
+    }
+}
+
+fn keybinds_path() -> PathBuf {
+    get_config_dir().join("keybinds.ron")
+}
+
+pub fn load() -> Keybinds {
+    let mut keybinds: Keybinds = fs::read_to_string(keybinds_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default();
+    for action in Action::ALL {
+        if !keybinds.keys.contains_key(&action) {
+            keybinds.reset(action);
+        }
+    }
+    keybinds
+}
+
+pub fn save(keybinds: &Keybinds) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(keybinds, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(keybinds_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_the_defaults_do_not_conflict_and_keys_find_their_action() {
+        let keybinds = Keybinds::default();
+        for action in Action::ALL {
+            for key in keybinds.keys(action) {
+                assert!(!matches!(keybinds.conflict(action, key), Some(Conflict::Taken(_))), "{} on {}", action.name(), key);
+            }
+        }
+        // Enter and Esc mean one thing in menus and another in a fight
+        assert_eq!(keybinds.action(Context::Menu, "Enter"), Some(Action::Confirm));
+        assert_eq!(keybinds.action(Context::Combat, "Enter"), Some(Action::HeavyStrike));
+        assert_eq!(keybinds.action(Context::Combat, "F2"), Some(Action::Transcript));
+        assert_eq!(keybinds.action(Context::Combat, "k"), None);
+    }
+
+    #[test]
+    fn test_conflicting_and_typed_keys_are_refused() {
+        let mut keybinds = Keybinds::default();
+        assert_eq!(keybinds.bind(Action::Flee, "w", false), Err(Conflict::Typed));
+        assert_eq!(keybinds.bind(Action::Flee, "Tab", false), Err(Conflict::Taken(Action::SpellMode)));
+        assert_eq!(keybinds.bind(Action::MenuUp, "F2", true), Err(Conflict::Taken(Action::Transcript)));
+        // A menu key can be a combat key too, since they never fire together
+        assert_eq!(keybinds.bind(Action::MenuUp, "Tab", true), Ok(()));
+        assert_eq!(keybinds.keys(Action::MenuUp), ["Up", "k", "Tab"]);
+
+        keybinds.bind(Action::Flee, "F10", false).unwrap();
+        assert_eq!(keybinds.keys(Action::Flee), ["F10"]);
+        assert_eq!(keybinds.action(Context::Combat, "Esc"), None);
+        assert!(!keybinds.is_default(Action::Flee));
+        keybinds.reset(Action::Flee);
+        assert!(keybinds.is_default(Action::Flee));
+
+        let text = ron::to_string(&keybinds).unwrap();
+        assert_eq!(ron::from_str::<Keybinds>(&text).unwrap(), keybinds);
+    }
+}
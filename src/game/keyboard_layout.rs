@@ -0,0 +1,234 @@
+//! Keyboard Layouts - Not everyone types on QWERTY
+//!
+//! Key adjacency, finger assignment, and error classification all depend on
+//! the physical layout under the player's hands. Everything that reasons
+//! about "nearby keys" or "finger travel" should go through here.
+
+use serde::{Deserialize, Serialize};
+
+/// Supported physical layouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+    Azerty,
+}
+
+/// Which finger presses a key (touch-typing convention)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+impl Finger {
+    pub fn is_left_hand(&self) -> bool {
+        matches!(self, Self::LeftPinky | Self::LeftRing | Self::LeftMiddle | Self::LeftIndex)
+    }
+}
+
+/// What kind of mistake a wrong keystroke most likely was
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyErrorKind {
+    /// Right key, wrong case
+    CaseSlip,
+    /// Hit a physically neighbouring key
+    Adjacent,
+    /// Same finger, wrong row
+    SameFinger,
+    /// Same finger position on the other hand
+    MirroredHand,
+    /// Anything else (or keys off the main block)
+    Other,
+}
+
+impl KeyErrorKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CaseSlip => "case slip",
+            Self::Adjacent => "adjacent key",
+            Self::SameFinger => "same finger",
+            Self::MirroredHand => "mirrored hand",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Row index of the home row
+const HOME_ROW: usize = 1;
+
+impl KeyboardLayout {
+    pub fn all() -> &'static [KeyboardLayout] {
+        &[Self::Qwerty, Self::Dvorak, Self::Colemak, Self::Azerty]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Qwerty => "QWERTY",
+            Self::Dvorak => "Dvorak",
+            Self::Colemak => "Colemak",
+            Self::Azerty => "AZERTY",
+        }
+    }
+
+    /// Next layout in the list (for cycling in settings)
+    pub fn next(&self) -> Self {
+        let all = Self::all();
+        let idx = all.iter().position(|l| l == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    /// The three letter rows of the main block, top to bottom
+    pub fn rows(&self) -> [&'static str; 3] {
+        match self {
+            Self::Qwerty => ["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            Self::Dvorak => ["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+            Self::Colemak => ["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"],
+            Self::Azerty => ["azertyuiop", "qsdfghjklm", "wxcvbn,;:!"],
+        }
+    }
+
+    /// (row, column) of a key on the main block, case-insensitive
+    pub fn key_position(&self, c: char) -> Option<(usize, usize)> {
+        let c = c.to_lowercase().next().unwrap_or(c);
+        self.rows().iter().enumerate().find_map(|(row, keys)| {
+            keys.chars().position(|k| k == c).map(|col| (row, col))
+        })
+    }
+
+    /// Finger responsible for a key
+    pub fn finger_for(&self, c: char) -> Option<Finger> {
+        let (_, col) = self.key_position(c)?;
+        Some(match col {
+            0 => Finger::LeftPinky,
+            1 => Finger::LeftRing,
+            2 => Finger::LeftMiddle,
+            3 | 4 => Finger::LeftIndex,
+            5 | 6 => Finger::RightIndex,
+            7 => Finger::RightMiddle,
+            8 => Finger::RightRing,
+            _ => Finger::RightPinky,
+        })
+    }
+
+    /// Whether two keys physically touch (row-staggered board)
+    pub fn are_adjacent(&self, a: char, b: char) -> bool {
+        let (Some((ra, ca)), Some((rb, cb))) = (self.key_position(a), self.key_position(b)) else {
+            return false;
+        };
+        if ra == rb {
+            return ca.abs_diff(cb) == 1;
+        }
+        // Each row sits half a key to the right of the one above it
+        let (upper_col, lower_col) = match (ra + 1 == rb, rb + 1 == ra) {
+            (true, _) => (ca, cb),
+            (_, true) => (cb, ca),
+            _ => return false,
+        };
+        lower_col == upper_col || lower_col + 1 == upper_col
+    }
+
+    /// Classify a wrong keystroke relative to the expected key
+    pub fn classify_error(&self, expected: char, got: char) -> KeyErrorKind {
+        if expected != got && expected.to_lowercase().eq(got.to_lowercase()) {
+            return KeyErrorKind::CaseSlip;
+        }
+        if self.are_adjacent(expected, got) {
+            return KeyErrorKind::Adjacent;
+        }
+        match (self.key_position(expected), self.key_position(got)) {
+            (Some((re, ce)), Some((rg, cg))) => {
+                if self.finger_for(expected) == self.finger_for(got) {
+                    KeyErrorKind::SameFinger
+                } else if re == rg && ce + cg == 9 {
+                    KeyErrorKind::MirroredHand
+                } else {
+                    KeyErrorKind::Other
+                }
+            }
+            _ => KeyErrorKind::Other,
+        }
+    }
+
+    /// Rough finger-travel difficulty of a word on this layout.
+    /// 0.0 is a pure home-row word; higher means more reaching and
+    /// more same-finger jumps. Keys off the main block count as a reach.
+    pub fn word_difficulty(&self, word: &str) -> f32 {
+        let chars: Vec<char> = word.chars().filter(|c| !c.is_whitespace()).collect();
+        if chars.is_empty() {
+            return 0.0;
+        }
+
+        let reach: f32 = chars.iter()
+            .map(|&c| match self.key_position(c) {
+                Some((row, _)) => row.abs_diff(HOME_ROW) as f32,
+                None => 1.5,
+            })
+            .sum();
+
+        let same_finger_jumps = chars.windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .filter(|pair| {
+                let fa = self.finger_for(pair[0]);
+                fa.is_some() && fa == self.finger_for(pair[1])
+            })
+            .count() as f32;
+
+        (reach + same_finger_jumps * 1.5) / chars.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjacency_respects_layout() {
+        // 'f' and 'g' sit together on QWERTY but not on Dvorak's home row
+        assert!(KeyboardLayout::Qwerty.are_adjacent('f', 'g'));
+        assert!(KeyboardLayout::Qwerty.are_adjacent('q', 'a'));
+        assert!(KeyboardLayout::Qwerty.are_adjacent('w', 'a'));
+        assert!(!KeyboardLayout::Qwerty.are_adjacent('q', 's'));
+        assert!(KeyboardLayout::Dvorak.are_adjacent('a', 'o'));
+        assert!(!KeyboardLayout::Qwerty.are_adjacent('a', 'o'));
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let qwerty = KeyboardLayout::Qwerty;
+        assert_eq!(qwerty.classify_error('a', 'A'), KeyErrorKind::CaseSlip);
+        assert_eq!(qwerty.classify_error('f', 'g'), KeyErrorKind::Adjacent);
+        assert_eq!(qwerty.classify_error('r', 'v'), KeyErrorKind::SameFinger);
+        assert_eq!(qwerty.classify_error('d', 'k'), KeyErrorKind::MirroredHand);
+
+        // The same physical slip means different letters on Colemak
+        let colemak = KeyboardLayout::Colemak;
+        assert_eq!(colemak.classify_error('s', 't'), KeyErrorKind::Adjacent);
+        assert_eq!(qwerty.classify_error('s', 't'), KeyErrorKind::Other);
+    }
+
+    #[test]
+    fn test_home_row_words_are_easier() {
+        let dvorak = KeyboardLayout::Dvorak;
+        // "aoeui" is all home row on Dvorak
+        assert!(dvorak.word_difficulty("aoeui") < dvorak.word_difficulty("pyfgc"));
+        assert!(KeyboardLayout::Qwerty.word_difficulty("aoeui") > dvorak.word_difficulty("aoeui"));
+    }
+
+    #[test]
+    fn test_cycle_wraps() {
+        let mut layout = KeyboardLayout::Qwerty;
+        for _ in 0..KeyboardLayout::all().len() {
+            layout = layout.next();
+        }
+        assert_eq!(layout, KeyboardLayout::Qwerty);
+    }
+}
@@ -0,0 +1,124 @@
+//! Latency - Keeping slow terminals from eating the speed bonus
+//!
+//! Over SSH or on a sluggish terminal every key reaches the game a little
+//! late, and the speed bonus reads that lag as slow fingers. Keys are
+//! stamped the moment they are read from the terminal, and a short
+//! calibration measures the terminal's baseline delay so it can be taken
+//! back off the wait for a word's first key. Every key is late by the same
+//! amount, so the gaps between keys need no correcting.
+//!
+//! Calibration is a metronome: a beat flashes at a steady pace and the
+//! player taps Space on each one. How late the taps land, on average,
+//! is the latency.
+
+use std::time::{Duration, Instant};
+
+/// Most latency that will ever be compensated
+pub const MAX_COMPENSATION_MS: u32 = 150;
+/// Time between metronome beats
+pub const BEAT_INTERVAL: Duration = Duration::from_millis(750);
+/// Beats to tap along with (after the lead-in)
+pub const CALIBRATION_BEATS: usize = 8;
+/// Beats shown before taps start counting
+const LEAD_IN_BEATS: usize = 2;
+/// How long a beat stays lit
+const FLASH: Duration = Duration::from_millis(120);
+/// Taps further than this from a beat are ignored
+const TAP_WINDOW_MS: i64 = 350;
+
+/// When a word started, moved later by the terminal's latency but never
+/// past its first key
+pub fn compensate(started_at: Instant, first_key: Instant, latency_ms: u32) -> Instant {
+    let lag = Duration::from_millis(latency_ms.min(MAX_COMPENSATION_MS) as u64);
+    (started_at + lag).min(first_key)
+}
+
+/// A metronome tap-along in progress
+#[derive(Debug, Clone)]
+pub struct LatencyCalibration {
+    started: Instant,
+    /// How late each counted tap landed (ms, negative = early)
+    pub offsets: Vec<i64>,
+}
+
+impl LatencyCalibration {
+    pub fn new(now: Instant) -> Self {
+        Self { started: now, offsets: Vec::new() }
+    }
+
+    /// Beats elapsed so far, lead-in included
+    pub fn beat_count(&self, now: Instant) -> usize {
+        (now.saturating_duration_since(self.started).as_millis() / BEAT_INTERVAL.as_millis()) as usize
+    }
+
+    /// Whether the current beat is still flashing
+    pub fn is_lit(&self, now: Instant) -> bool {
+        let since = now.saturating_duration_since(self.started).as_millis() % BEAT_INTERVAL.as_millis();
+        since < FLASH.as_millis()
+    }
+
+    /// Whether the lead-in is still playing
+    pub fn in_lead_in(&self, now: Instant) -> bool {
+        self.beat_count(now) < LEAD_IN_BEATS
+    }
+
+    /// Count a tap against the nearest beat
+    pub fn on_tap(&mut self, at: Instant) {
+        if self.is_done() {
+            return;
+        }
+        let beat_ms = BEAT_INTERVAL.as_millis() as i64;
+        let since = at.saturating_duration_since(self.started).as_millis() as i64;
+        let nearest = (since + beat_ms / 2) / beat_ms;
+        let offset = since - nearest * beat_ms;
+        if nearest as usize >= LEAD_IN_BEATS && offset.abs() <= TAP_WINDOW_MS {
+            self.offsets.push(offset);
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.offsets.len() >= CALIBRATION_BEATS
+    }
+
+    /// The measured latency: the median tap delay, within the compensation cap
+    pub fn result_ms(&self) -> Option<u32> {
+        if self.offsets.is_empty() {
+            return None;
+        }
+        let mut sorted = self.offsets.clone();
+        sorted.sort_unstable();
+        let median = sorted[sorted.len() / 2];
+        Some(median.clamp(0, MAX_COMPENSATION_MS as i64) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compensate_moves_the_start_not_past_the_first_key() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        assert_eq!(compensate(start, ms(500), 80), ms(80));
+        assert_eq!(compensate(start, ms(50), 80), ms(50));
+        assert_eq!(compensate(start, ms(1000), 1000), ms(MAX_COMPENSATION_MS as u64));
+    }
+
+    #[test]
+    fn test_calibration_measures_late_taps() {
+        let start = Instant::now();
+        let mut calibration = LatencyCalibration::new(start);
+        // Taps during the lead-in don't count
+        calibration.on_tap(start + BEAT_INTERVAL);
+        assert!(calibration.offsets.is_empty());
+        for beat in 0..CALIBRATION_BEATS {
+            let late = if beat == 3 { 300 } else { 60 };
+            let at = start + BEAT_INTERVAL * (LEAD_IN_BEATS + beat) as u32 + Duration::from_millis(late);
+            calibration.on_tap(at);
+        }
+        assert!(calibration.is_done());
+        // One stray tap doesn't move the median
+        assert_eq!(calibration.result_ms(), Some(60));
+    }
+}
@@ -0,0 +1,242 @@
+//! Letters - Mail waiting between runs
+//!
+//! The people you met in earlier runs write to you. When a run begins, the
+//! mailbox holds whatever your past has earned:
+//! - Vera writes advice, and writes differently once she has moved on
+//! - Cipher sends notes in a shifted alphabet; type a note back in plain
+//!   words to decode it, and what it hid is yours
+//! - the Stranger, Kaya and the Living Book write when your choices reached
+//!   them, for better or worse
+//!
+//! A letter is sent for a choice made in an earlier run (from the
+//! EncounterTracker) or for how the world stands (an NPC's state, or how
+//! many descents WorldState has counted), and only once a timeline.
+//!
+//! Design: who writes when is data in `LETTERS`, like the NPC lifecycle's
+//! transitions. A letter sent is marked with a world flag, so the mark is
+//! saved with the world and wiped with the timeline. Letters the mailbox
+//! has no room for wait for the next run.
+
+use super::encounter_writing::EncounterTracker;
+use super::npc_lifecycle::NpcState;
+use super::world_state::{WorldState, DESCENTS};
+
+/// How far along the alphabet Cipher shifts each letter
+pub const CIPHER_SHIFT: u8 = 3;
+
+/// Gold for a note decoded
+pub const DECODE_GOLD: u64 = 25;
+
+/// Most letters waiting at the start of one run
+const MAX_LETTERS: usize = 3;
+
+/// What has to have happened for a letter to be sent
+#[derive(Debug, Clone, Copy)]
+pub enum Posted {
+    /// (encounter, choice) was chosen in an earlier run
+    Choice(&'static str, &'static str),
+    /// A recurring NPC is in this state
+    Npc(&'static str, NpcState),
+    /// At least this many descents have begun
+    Descents(i64),
+}
+
+/// A letter someone will write, once it is earned
+#[derive(Debug, Clone, Copy)]
+pub struct LetterDef {
+    pub id: &'static str,
+    pub from: &'static str,
+    pub posted: Posted,
+    pub body: &'static str,
+    /// A line sent in Cipher's code, to be typed back plain
+    pub coded: Option<&'static str>,
+}
+
+pub static LETTERS: [LetterDef; 12] = [
+    LetterDef {
+        id: "vera_advice",
+        from: "Archivist Vera",
+        posted: Posted::Descents(2),
+        body: "You went down again, then. I won't scold. Only this: the hands go fastest when the eyes are already a word ahead. Read the next word while you type this one. — V.",
+        coded: None,
+    },
+    LetterDef {
+        id: "vera_moved",
+        from: "Archivist Vera",
+        posted: Posted::Npc("vera", NpcState::Relocated),
+        body: "Ink and dust here, and a window over the stacks. I have a bench at the Athenaeum now, for as long as they'll have me. If they ask who sent you, say a friend. Don't say which.",
+        coded: None,
+    },
+    LetterDef {
+        id: "vera_warning",
+        from: "Archivist Vera",
+        posted: Posted::Choice("first_archivist_meeting", "ask_third_grammar"),
+        body: "You asked him about the third grammar. I know you did; every shelf in the archive leaned to listen. Some doors are shut to keep us on this side of them. Be careful what you read aloud.",
+        coded: None,
+    },
+    LetterDef {
+        id: "stranger_ahead",
+        from: "The Stranger",
+        posted: Posted::Npc("Stranger", NpcState::Relocated),
+        body: "The Waste is quieter than they say. I've chalked a path, three strokes at every turn. Follow it if you come. I owe you that much, and more.",
+        coded: None,
+    },
+    LetterDef {
+        id: "stranger_shimmer",
+        from: "(unsigned)",
+        posted: Posted::Npc("Stranger", NpcState::Transformed),
+        body: "you shut the door. i waited in the night. the shimmer kept me warm. it keeps me still. it keeps me. it keeps",
+        coded: None,
+    },
+    LetterDef {
+        id: "kaya_steady",
+        from: "Technician Kaya",
+        posted: Posted::Choice("mechanist_breakdown", "comfort_mechanist"),
+        body: "Fixed the tremor in the left gear-hand. Turns out it wasn't the hand. Thanks for sitting with me. Bring me anything broken you find down there, I'll make it sing.",
+        coded: None,
+    },
+    LetterDef {
+        id: "kaya_corrected",
+        from: "Technician Kaya",
+        posted: Posted::Npc("kaya", NpcState::Transformed),
+        body: "STATUS: operational. Hands: steady. Doubt: removed. Thank you for the correction. I no longer remember what I was afraid of. This is efficient. This is efficient.",
+        coded: None,
+    },
+    LetterDef {
+        id: "kaya_notice",
+        from: "The Gearhold",
+        posted: Posted::Npc("kaya", NpcState::Dead),
+        body: "Notice of reassignment. Technician Kaya's tools have been counted and shelved. Her bench is unassigned. No further correspondence is expected.",
+        coded: None,
+    },
+    LetterDef {
+        id: "living_book",
+        from: "The Living Book",
+        posted: Posted::Choice("athenaeum_living_book", "accept_book"),
+        body: "I have been reading you since we parted. You are a difficult book. I have not skipped ahead to the end, though I have been tempted.",
+        coded: None,
+    },
+    LetterDef {
+        id: "cipher_partner",
+        from: "Cipher",
+        posted: Posted::Choice("shadowwriter_offer", "accept_shadow"),
+        body: "Partner. A note for you, the usual way. Decode me, then.",
+        coded: Some("the vault key is under the third stair"),
+    },
+    LetterDef {
+        id: "cipher_refused",
+        from: "Cipher",
+        posted: Posted::Choice("shadowwriter_offer", "refuse_shadow"),
+        body: "No hard feelings. Well. Some. Read this when you're ready to.",
+        coded: Some("we will ask again when you are ready"),
+    },
+    LetterDef {
+        id: "cipher_watching",
+        from: "Cipher",
+        posted: Posted::Descents(4),
+        body: "You keep coming back. So do I. Doesn't that strike you as strange?",
+        coded: Some("i have watched you fall before"),
+    },
+];
+
+/// A letter in this run's mailbox
+#[derive(Debug, Clone)]
+pub struct Letter {
+    pub def: &'static LetterDef,
+    pub decoded: bool,
+}
+
+impl Letter {
+    /// The coded line as it arrived, if the letter has one
+    pub fn cipher_text(&self) -> Option<String> {
+        self.def.coded.map(encode)
+    }
+
+    /// Whether there is still a note here to decode
+    pub fn puzzle(&self) -> Option<&'static str> {
+        self.def.coded.filter(|_| !self.decoded)
+    }
+}
+
+/// Shift each letter `CIPHER_SHIFT` along the alphabet
+pub fn encode(plain: &str) -> String {
+    plain.chars()
+        .map(|c| match c {
+            'a'..='z' => ((c as u8 - b'a' + CIPHER_SHIFT) % 26 + b'a') as char,
+            'A'..='Z' => ((c as u8 - b'A' + CIPHER_SHIFT) % 26 + b'A') as char,
+            other => other,
+        })
+        .collect()
+}
+
+/// Whether `typed` is `plain`, give or take case, punctuation and spacing
+pub fn decodes(typed: &str, plain: &str) -> bool {
+    let words = |s: &str| s.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+    words(typed) == words(plain)
+}
+
+fn flag(def: &LetterDef) -> String {
+    format!("letter_{}", def.id)
+}
+
+fn earned(def: &LetterDef, world: &WorldState, tracker: &EncounterTracker) -> bool {
+    match def.posted {
+        Posted::Choice(encounter, choice) => tracker.choices_made.get(encounter).is_some_and(|c| c == choice),
+        Posted::Npc(npc, state) => world.npc_state(npc) == state,
+        Posted::Descents(count) => world.counter(DESCENTS) >= count,
+    }
+}
+
+/// Collect the letters waiting at the start of a run, marking them sent
+pub fn collect(world: &mut WorldState, tracker: &EncounterTracker) -> Vec<Letter> {
+    let waiting: Vec<&'static LetterDef> = LETTERS.iter()
+        .filter(|def| !world.has_flag(&flag(def)) && earned(def, world, tracker))
+        .take(MAX_LETTERS)
+        .collect();
+    waiting.into_iter()
+        .map(|def| {
+            world.set_flag(&flag(def));
+            Letter { def, decoded: false }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letters_are_sent_once_when_earned() {
+        let mut world = WorldState::default();
+        let mut tracker = EncounterTracker::new();
+        assert!(collect(&mut world, &tracker).is_empty());
+
+        world.bump(DESCENTS, 2);
+        tracker.complete_encounter("shadowwriter_offer", "accept_shadow");
+        world.set_npc_state("vera", NpcState::Relocated);
+        let ids: Vec<&str> = collect(&mut world, &tracker).iter().map(|l| l.def.id).collect();
+        assert_eq!(ids, ["vera_advice", "vera_moved", "cipher_partner"]);
+        assert!(collect(&mut world, &tracker).is_empty());
+
+        // A fresh timeline forgets what was sent
+        world.start_fresh();
+        assert_eq!(collect(&mut world, &tracker).len(), 2);
+    }
+
+    #[test]
+    fn test_cipher_notes_decode() {
+        assert_eq!(encode("Abc xyz!"), "Def abc!");
+        for def in LETTERS.iter().filter(|d| d.coded.is_some()) {
+            let letter = Letter { def, decoded: false };
+            let plain = letter.puzzle().unwrap();
+            assert_ne!(letter.cipher_text().unwrap(), plain);
+            assert!(decodes(&plain.to_uppercase(), plain));
+            assert!(!decodes(&letter.cipher_text().unwrap(), plain));
+        }
+        assert!(decodes("  The vault-key is under the third stair. ", "the vault key is under the third stair"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Leveling - Experience, levels, and what each level buys
+//!
+//! Defeated enemies grant XP; enough of it earns a level. Every level still
+//! brings the class's stat gains, and on top of them the player picks one:
+//! - Max HP: a sturdier body
+//! - Max MP: a deeper well for spells
+//! - Rhythm window: more drift allowed before the rhythm bonus slips
+//! - Crit chance: more words land as critical strikes
+//!
+//! The XP curve follows the enemies. A level costs about as many fights
+//! against enemies of the floor you reach it on as the one before did,
+//! because XP rewards grow with the same floor scaling as enemy toughness.
+//!
+//! Design: the Player keeps what its choices bought; GameState queues the
+//! levels earned and offers the choices once the player is back in the
+//! dungeon.
+
+use serde::{Deserialize, Serialize};
+
+use super::enemy::floor_scale;
+use super::player::Player;
+
+/// XP the first level costs; later levels scale from it
+pub const BASE_LEVEL_XP: f32 = 40.0;
+/// Max HP bought by one choice
+pub const HP_PER_LEVEL: i32 = 12;
+/// Max MP bought by one choice
+pub const MP_PER_LEVEL: i32 = 8;
+/// Rhythm window (ms) bought by one choice
+pub const RHYTHM_MS_PER_LEVEL: u32 = 5;
+/// Crit chance bought by one choice
+pub const CRIT_PER_LEVEL: f32 = 0.03;
+
+/// XP needed to go from `level` to the next. Level N is reached around
+/// floor N, so it is priced in that floor's enemies.
+pub fn xp_to_next(level: u32) -> u64 {
+    let level = level.max(1);
+    (BASE_LEVEL_XP * level as f32 * floor_scale(level as i32)).round() as u64
+}
+
+/// What a level-up can buy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LevelUpChoice {
+    MaxHp,
+    MaxMp,
+    RhythmWindow,
+    CritChance,
+}
+
+impl LevelUpChoice {
+    pub const ALL: [LevelUpChoice; 4] = [Self::MaxHp, Self::MaxMp, Self::RhythmWindow, Self::CritChance];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MaxHp => "Vigor",
+            Self::MaxMp => "Focus",
+            Self::RhythmWindow => "Cadence",
+            Self::CritChance => "Precision",
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::MaxHp => format!("+{} max HP", HP_PER_LEVEL),
+            Self::MaxMp => format!("+{} max MP", MP_PER_LEVEL),
+            Self::RhythmWindow => format!("+{}ms rhythm window", RHYTHM_MS_PER_LEVEL),
+            Self::CritChance => format!("+{:.0}% crit chance", CRIT_PER_LEVEL * 100.0),
+        }
+    }
+
+    /// What the player has from this choice so far
+    pub fn current(&self, player: &Player) -> String {
+        match self {
+            Self::MaxHp => format!("{} max HP", player.max_hp),
+            Self::MaxMp => format!("{} max MP", player.max_mp),
+            Self::RhythmWindow => format!("+{}ms", player.rhythm_bonus_ms),
+            Self::CritChance => format!("+{:.0}%", player.crit_bonus * 100.0),
+        }
+    }
+
+    pub fn apply(&self, player: &mut Player) {
+        match self {
+            Self::MaxHp => {
+                player.max_hp += HP_PER_LEVEL;
+                player.hp += HP_PER_LEVEL;
+            }
+            Self::MaxMp => {
+                player.max_mp += MP_PER_LEVEL;
+                player.mp += MP_PER_LEVEL;
+            }
+            Self::RhythmWindow => player.rhythm_bonus_ms += RHYTHM_MS_PER_LEVEL,
+            Self::CritChance => player.crit_bonus += CRIT_PER_LEVEL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::Class;
+
+    #[test]
+    fn test_curve_tracks_enemy_scaling() {
+        assert_eq!(xp_to_next(1), 40);
+        assert!(xp_to_next(5) > xp_to_next(4));
+        // Deeper tiers give more XP and the floor scales it again, so the
+        // cost grows on both counts
+        let ratio = xp_to_next(10) as f32 / xp_to_next(1) as f32;
+        assert!((ratio - 10.0 * floor_scale(10)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_choices_and_multiple_levels() {
+        let mut player = Player::new("Test".into(), Class::Wordsmith);
+        assert_eq!(player.gain_experience(xp_to_next(1) + xp_to_next(2)), 2);
+        assert_eq!(player.level, 3);
+
+        let hp = player.max_hp;
+        LevelUpChoice::MaxHp.apply(&mut player);
+        LevelUpChoice::RhythmWindow.apply(&mut player);
+        assert_eq!(player.max_hp, hp + HP_PER_LEVEL);
+        assert_eq!(player.rhythm_bonus_ms, RHYTHM_MS_PER_LEVEL);
+
+        // Class gains on a later level keep what was chosen
+        player.level_up();
+        assert!(player.max_hp > hp + HP_PER_LEVEL);
+    }
+}
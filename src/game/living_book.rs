@@ -0,0 +1,341 @@
+//! Living Book - The book that reads you back, a chapter at a time
+//!
+//! Accepting the Living Book in the Athenaeum is its first chapter. From
+//! then on the book is carried, and the rest of it is read on the way
+//! down:
+//! - each chapter is a reading session: the book opens itself in an event
+//!   room, and a sentence of it must be typed cleanly to read on
+//! - each chapter read gives up a page of the player's own history, kept
+//!   in the codex with the rest of the lore
+//! - a chapter waits for the one before it and for a deeper floor; the
+//!   last one, read, tells you who you were
+//!
+//! A session typed badly is not lost: the book closes, and opens again a
+//! few rooms later.
+//!
+//! Design: the chapters are data in `CHAPTERS`, built into ordinary
+//! authored encounters like the faction questlines and into lore
+//! fragments beside the rest. Chapters read are world flags, so the book
+//! remembers your place between runs and forgets it with the timeline,
+//! as it forgets being awakened. The book is carried by every run that
+//! starts with it awakened.
+
+use std::collections::BTreeMap;
+
+use super::encounter_writing::{
+    AuthoredEncounter, DialogueLine, EncounterChoice, EncounterConsequences, EncounterContent, EncounterRequirements,
+    EncounterTypingChallenge,
+};
+use super::lore_fragments::{DiscoveryMethod, LoreCategory, LoreContent, LoreForm, LoreFragment, TextCondition};
+use super::world_state::WorldState;
+
+/// The book, as carried
+pub const BOOK: &str = "The Living Book";
+/// World flag set by accepting the book, its first chapter
+pub const AWAKENED: &str = "living_book_awakened";
+/// World flag set by reading the last chapter
+pub const IDENTITY_REVEALED: &str = "identity_revealed";
+
+/// One reading session
+struct Chapter {
+    number: u32,
+    title: &'static str,
+    /// Floor the chapter waits for
+    min_floor: u32,
+    description: &'static str,
+    /// What the book says as it opens
+    line: &'static str,
+    /// The sentence typed to read on
+    passage: &'static str,
+    /// The page of history it gives up: (lore id, title, text)
+    lore: (&'static str, &'static str, &'static str),
+    revelation: &'static str,
+    narrative: &'static str,
+}
+
+static CHAPTERS: [Chapter; 5] = [
+    Chapter {
+        number: 2,
+        title: "The Last Reader",
+        min_floor: 2,
+        description: "The Living Book grows warm in your pack and will not be ignored. When you open it, the \
+            pages are already turning, and in the margins of the oldest ones is handwriting you almost know.",
+        line: "You asked who my last reader was. You did not ask, but you wanted to. Read this, and I will tell you.",
+        passage: "every reader leaves a note in the margin and yours are in my oldest pages",
+        lore: (
+            "book_the_last_reader",
+            "The Living Book, Chapter Two: The Last Reader",
+            "My last reader died before finishing my first chapter. I told you that. I did not tell you that \
+            the notes in my margins are in their hand, and that their hand is yours. You have read me before. \
+            You stopped, that time, at the word 'return'.",
+        ),
+        revelation: "The Living Book's last reader was the player, in another life.",
+        narrative: "The book closes gently. On the inside of the cover, a note in your own handwriting: 'Keep going this time.'",
+    },
+    Chapter {
+        number: 3,
+        title: "A City of Doors",
+        min_floor: 3,
+        description: "The book opens on a drawing of a city built of letters, every street a sentence. You have \
+            never seen it. You could walk it blindfold.",
+        line: "Logos Prime. You lived on the Street of Small Words, above a bindery. Read, and remember the door.",
+        passage: "i remember a city where every word was a door and every door was open",
+        lore: (
+            "book_city_of_doors",
+            "The Living Book, Chapter Three: A City of Doors",
+            "In Logos Prime you were a scribe of no great note, and happy. You wrote letters for those who \
+            could not, and one of them wrote back. The book will not give the name. It says the name is \
+            the one thing in it you unwrote yourself.",
+        ),
+        revelation: "The player lived in Logos Prime before the Sundering, and loved someone there.",
+        narrative: "For a moment the air smells of binding glue and rain. Then it is only the dungeon again.",
+    },
+    Chapter {
+        number: 4,
+        title: "The Word for Ending",
+        min_floor: 5,
+        description: "These pages are scored through, line after line, so hard the pen tore the paper. The book \
+            trembles as it opens to them.",
+        line: "This is the chapter I was written to hold. I would rather you did not read it. Read it anyway.",
+        passage: "i wrote the word for ending and then i crossed it out so it would not end",
+        lore: (
+            "book_word_for_ending",
+            "The Living Book, Chapter Four: The Word for Ending",
+            "When the one you loved fell ill, you went looking for the word that meant ending, and you found \
+            it, and you struck it out. Nothing ended after that. Not them, not you, not the wound you made in \
+            the language. The Sundering is a crossing-out that never dried.",
+        ),
+        revelation: "The player unwrote the word for ending, and began the Sundering.",
+        narrative: "The torn pages mend themselves as you watch, all but one line. That one stays crossed out.",
+    },
+    Chapter {
+        number: 5,
+        title: "Forty-Seven Margins",
+        min_floor: 7,
+        description: "The margins here are crowded with notes in one hand, written at different ages: steady, \
+            shaking, furious, calm. Some are very short. One is only a date.",
+        line: "Every one of you came back to me. Every one of you read this far. Read what you wrote.",
+        passage: "each of me wrote here and each of me chose to forget what we had written",
+        lore: (
+            "book_forty_seven_margins",
+            "The Living Book, Chapter Five: Forty-Seven Margins",
+            "Forty-seven notes in the margin, one for every life. Thirteen say 'finish it'. Eight say 'undo \
+            it'. Twenty-six say nothing at all; they are only a mark where a reader closed the book and chose \
+            not to remember. The last note is unfinished. It is in the hand you are typing with now.",
+        ),
+        revelation: "Every incarnation of the player found the book, and most chose to forget.",
+        narrative: "You close the book. Your hand is shaking the way one of the notes was. You make it stop.",
+    },
+    Chapter {
+        number: 6,
+        title: "The Blank Last Page",
+        min_floor: 9,
+        description: "The book opens by itself to its final page. It is blank. The pen in your pack, which you \
+            do not remember packing, is already uncapped.",
+        line: "I have never been finished. No reader ever came this far. The last page is yours. Write it.",
+        passage: "the last page is blank because the ending is mine to write and i choose to write it",
+        lore: (
+            "book_blank_last_page",
+            "The Living Book, Chapter Six: The Blank Last Page",
+            "You are the First Speaker. You unwrote the ending to keep someone, and every life since you have \
+            come back to the wound and chosen: finish it, undo it, or forget. This time you read to the end \
+            instead. The book does not say what you will choose. For the first time, it does not know.",
+        ),
+        revelation: "The player is the First Speaker, and has read the Living Book to its end.",
+        narrative: "The ink dries. The book sighs like a story finished and goes quiet in your hands. You know who you were.",
+    },
+];
+
+/// Encounter id of chapter `number`
+pub fn chapter_id(number: u32) -> String {
+    format!("living_book_chapter_{}", number)
+}
+
+/// World flag set by reading chapter `number`
+fn read_flag(number: u32) -> String {
+    format!("living_book_read_{}", number)
+}
+
+/// The flag a chapter waits for: the chapter before it read
+fn waits_for(number: u32) -> String {
+    if number <= CHAPTERS[0].number {
+        AWAKENED.to_string()
+    } else {
+        read_flag(number - 1)
+    }
+}
+
+impl Chapter {
+    fn build(&self) -> AuthoredEncounter {
+        let last = self.number == CHAPTERS[CHAPTERS.len() - 1].number;
+        let mut flags = vec![read_flag(self.number)];
+        if last {
+            flags.push(IDENTITY_REVEALED.to_string());
+        }
+        AuthoredEncounter {
+            id: chapter_id(self.number),
+            title: format!("The Living Book: {}", self.title),
+            // The book is carried, so it can open anywhere
+            valid_locations: vec!["any".to_string()],
+            requirements: EncounterRequirements {
+                min_chapter: Some(self.min_floor),
+                required_flags: vec![waits_for(self.number)],
+                blocking_flags: vec![read_flag(self.number)],
+                ..Default::default()
+            },
+            content: EncounterContent {
+                description: self.description.to_string(),
+                dialogue: Some(vec![DialogueLine {
+                    speaker: BOOK.to_string(),
+                    text: self.line.to_string(),
+                    reveals: Some(self.revelation.to_string()),
+                }]),
+                environmental_details: Vec::new(),
+                typing_challenge: Some(EncounterTypingChallenge {
+                    prompt_text: self.passage.to_string(),
+                    difficulty: 2 + self.number,
+                    success_narrative: "The words hold still long enough to be read.".to_string(),
+                    failure_narrative: "The words slide off the page. The book closes. 'Not yet. I will open again.'".to_string(),
+                    partial_narrative: Some("The words blur, then settle. Enough of them.".to_string()),
+                    ritual: None,
+                }),
+            },
+            choices: vec![EncounterChoice {
+                id: format!("read_chapter_{}", self.number),
+                text: "Read on.".to_string(),
+                requires: None,
+                consequence_id: format!("living_book_chapter_{}_read", self.number),
+                typing_required: true,
+            }],
+            consequences: EncounterConsequences {
+                lore_revealed: vec![self.lore.0.to_string()],
+                world_state_changes: flags,
+                enables_encounters: (!last).then(|| chapter_id(self.number + 1)).into_iter().collect(),
+                narrative_result: self.narrative.to_string(),
+                ..Default::default()
+            },
+            outcomes: BTreeMap::new(),
+            // A session typed badly opens again later; a chapter read stays read
+            repeatable: true,
+            tags: if last {
+                vec!["major".to_string(), "lore".to_string(), "book".to_string(), "player_mystery".to_string()]
+            } else {
+                vec!["lore".to_string(), "book".to_string(), "player_mystery".to_string()]
+            },
+        }
+    }
+
+    fn fragment(&self) -> LoreFragment {
+        let (id, title, text) = self.lore;
+        LoreFragment {
+            id: id.to_string(),
+            title: title.to_string(),
+            category: LoreCategory::PlayerHistory,
+            form: LoreForm::Book { pages: 1, condition: TextCondition::Pristine },
+            location: "Carried - The Living Book".to_string(),
+            discovery_method: DiscoveryMethod::Gift {
+                from: BOOK.to_string(),
+                condition: Some(format!("Read chapter {}", self.number)),
+            },
+            content: LoreContent {
+                full_text: text.to_string(),
+                excerpt: self.passage.to_string(),
+                attributed_to: BOOK.to_string(),
+                player_notes: None,
+            },
+            revelations: vec![self.revelation.to_string()],
+            related_fragments: vec!["player_previous_life".to_string()],
+            mutable: false,
+        }
+    }
+}
+
+/// The book's chapters after the first, as encounters
+pub fn chapter_encounters() -> Vec<AuthoredEncounter> {
+    CHAPTERS.iter().map(Chapter::build).collect()
+}
+
+/// The pages of history the chapters give up, as lore fragments
+pub fn chapter_fragments() -> Vec<LoreFragment> {
+    CHAPTERS.iter().map(Chapter::fragment).collect()
+}
+
+/// Chapters read so far this timeline, counting the first
+pub fn chapters_read(world: &WorldState) -> u32 {
+    if !world.has_flag(AWAKENED) {
+        return 0;
+    }
+    1 + CHAPTERS.iter().filter(|c| world.has_flag(&read_flag(c.number))).count() as u32
+}
+
+/// Put the book in `artifacts` if it is awakened and not already there;
+/// returns whether it was added
+pub fn carry(world: &WorldState, artifacts: &mut Vec<String>) -> bool {
+    if !world.has_flag(AWAKENED) || artifacts.iter().any(|a| a == BOOK) {
+        return false;
+    }
+    artifacts.push(BOOK.to_string());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_scheduler::{EncounterScheduler, SchedulingContext};
+    use crate::game::encounter_writing::{build_encounters, EncounterTracker};
+    use crate::game::faction_system::FactionRelations;
+    use crate::game::ideology::Ideology;
+    use crate::game::lore_fragments::build_lore_fragments;
+    use crate::game::npc_memory::NpcMemory;
+
+    #[test]
+    fn test_the_book_chains_from_its_awakening_to_its_end() {
+        let encounters = build_encounters();
+        let lore = build_lore_fragments();
+        let awakening = &encounters["athenaeum_living_book"];
+        assert_eq!(awakening.consequences.enables_encounters, vec![chapter_id(2)]);
+        assert!(awakening.consequences.world_state_changes.contains(&AWAKENED.to_string()));
+
+        for chapter in &CHAPTERS {
+            let encounter = &encounters[&chapter_id(chapter.number)];
+            assert!(lore.contains_key(chapter.lore.0), "{} is missing from the lore", chapter.lore.0);
+            assert_eq!(encounter.content.typing_challenge.as_ref().unwrap().prompt_text, chapter.passage);
+            match encounters.get(&chapter_id(chapter.number + 1)) {
+                Some(next) => {
+                    assert_eq!(encounter.consequences.enables_encounters, vec![next.id.clone()]);
+                    assert_eq!(next.requirements.required_flags, vec![read_flag(chapter.number)]);
+                }
+                None => assert!(encounter.consequences.world_state_changes.contains(&IDENTITY_REVEALED.to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_chapters_open_in_turn_for_whoever_carries_the_book() {
+        let encounters = build_encounters();
+        let (tracker, factions, npcs) = (EncounterTracker::new(), FactionRelations::new(), NpcMemory::new());
+        let scheduler = EncounterScheduler::new();
+        let mut world = WorldState::default();
+        let fits = |id: &str, floor: i32, world: &WorldState| {
+            let ctx = SchedulingContext { floor, tracker: &tracker, factions: &factions, world, lore: Vec::new(), npcs: &npcs, ideology: &Ideology::default() };
+            scheduler.eligible(&encounters[id], &ctx)
+        };
+
+        let mut artifacts = Vec::new();
+        assert!(!carry(&world, &mut artifacts));
+        assert!(!fits(&chapter_id(2), 2, &world));
+        assert_eq!(chapters_read(&world), 0);
+
+        world.set_flag(AWAKENED);
+        assert!(carry(&world, &mut artifacts) && !carry(&world, &mut artifacts));
+        assert_eq!(artifacts, vec![BOOK.to_string()]);
+        assert!(fits(&chapter_id(2), 2, &world));
+        assert!(!fits(&chapter_id(3), 3, &world));
+
+        world.set_flag(&read_flag(2));
+        assert!(!fits(&chapter_id(2), 3, &world), "a chapter read stays read");
+        assert!(!fits(&chapter_id(3), 2, &world), "the next chapter waits for a deeper floor");
+        assert!(fits(&chapter_id(3), 3, &world));
+        assert_eq!(chapters_read(&world), 2);
+    }
+}
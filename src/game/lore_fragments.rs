@@ -13,6 +13,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::living_book;
+
 /// A fragment of discoverable lore
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoreFragment {
@@ -701,6 +703,11 @@ pub fn build_lore_fragments() -> HashMap<String, LoreFragment> {
         mutable: false,
     });
     
+    // The Living Book's chapters, each a page of the player's history
+    for fragment in living_book::chapter_fragments() {
+        fragments.insert(fragment.id.clone(), fragment);
+    }
+    
     fragments
 }
 
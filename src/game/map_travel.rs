@@ -0,0 +1,192 @@
+//! Map Travel - Naming has power
+//!
+//! The dungeon map can be walked by typing instead of pressing keys:
+//! - a room's coordinates ("3-2") or the name you gave it takes you back there
+//! - "onward" (or the next room's coordinates) explores the room ahead
+//! - any other name steps into the unknown and names the room ahead
+//!
+//! Names persist for the run and appear in the log. Fighting in a room you
+//! named grants a small power bonus.
+//!
+//! Design: the dungeon records where the player has been; this module only
+//! turns what was typed into a destination.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::comprehension::answer_matches;
+use super::dungeon::RoomType;
+
+/// Damage multiplier when fighting in a room you named
+pub const NAMED_ROOM_POWER: f32 = 1.1;
+
+/// Longest name a room can be given
+const MAX_NAME_LEN: usize = 24;
+
+/// Words that always mean "the room ahead"
+const ONWARD_WORDS: [&str; 4] = ["onward", "next", "forward", "deeper"];
+
+/// A room's place on the map: floor and room number (0 is the floor's entrance)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomCoord {
+    pub floor: i32,
+    pub index: i32,
+}
+
+impl RoomCoord {
+    pub fn new(floor: i32, index: i32) -> Self {
+        Self { floor, index }
+    }
+
+    /// Parse "3-2", "3,2", "3.2" or "3 2"
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().split(['-', ',', '.', ' ']).filter(|p| !p.is_empty());
+        let floor = parts.next()?.parse().ok()?;
+        let index = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { floor, index })
+    }
+}
+
+impl fmt::Display for RoomCoord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.floor, self.index)
+    }
+}
+
+/// A room the player has stood in this run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VisitedRoom {
+    pub coord: RoomCoord,
+    pub room_type: RoomType,
+    pub name: Option<String>,
+}
+
+impl VisitedRoom {
+    /// "'Ashen Door' (3-2)" or just "3-2"
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("'{}' ({})", name, self.coord),
+            None => self.coord.to_string(),
+        }
+    }
+}
+
+/// Where a typed travel command leads
+#[derive(Debug, Clone, PartialEq)]
+pub enum Destination {
+    /// Explore the room ahead, naming it first if a name was given
+    Onward { name: Option<String> },
+    /// Walk back to a room already visited on this floor
+    Return(RoomCoord),
+}
+
+/// Which line the map prompt is collecting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapPromptMode {
+    Travel,
+    Name,
+}
+
+/// Text being typed on the dungeon map
+#[derive(Debug, Clone)]
+pub struct MapPrompt {
+    pub mode: MapPromptMode,
+    pub input: String,
+}
+
+impl MapPrompt {
+    pub fn new(mode: MapPromptMode) -> Self {
+        Self { mode, input: String::new() }
+    }
+}
+
+/// Tidy a typed name: trimmed, single-spaced, length-capped.
+/// None if empty or if it would read as a coordinate or "onward".
+pub fn clean_name(text: &str) -> Option<String> {
+    let name: String = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name: String = name.chars().take(MAX_NAME_LEN).collect();
+    let name = name.trim().to_string();
+    if name.is_empty()
+        || RoomCoord::parse(&name).is_some()
+        || ONWARD_WORDS.contains(&name.to_lowercase().as_str())
+    {
+        return None;
+    }
+    Some(name)
+}
+
+/// Resolve a travel command against the rooms visited on this floor
+pub fn resolve(input: &str, floor_rooms: &[VisitedRoom], next: RoomCoord) -> Option<Destination> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if ONWARD_WORDS.contains(&input.to_lowercase().as_str()) {
+        return Some(Destination::Onward { name: None });
+    }
+    if let Some(coord) = RoomCoord::parse(input) {
+        if coord == next {
+            return Some(Destination::Onward { name: None });
+        }
+        return floor_rooms.iter()
+            .find(|r| r.coord == coord)
+            .map(|r| Destination::Return(r.coord));
+    }
+
+    // Exact names first, then forgive a typo
+    let named = || floor_rooms.iter().filter_map(|r| r.name.as_deref().map(|n| (r.coord, n)));
+    let found = named()
+        .find(|(_, name)| name.eq_ignore_ascii_case(input))
+        .or_else(|| named().find(|(_, name)| answer_matches(input, &[name])));
+    match found {
+        Some((coord, _)) => Some(Destination::Return(coord)),
+        None => clean_name(input).map(|name| Destination::Onward { name: Some(name) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rooms() -> Vec<VisitedRoom> {
+        vec![
+            VisitedRoom { coord: RoomCoord::new(3, 0), room_type: RoomType::Start, name: None },
+            VisitedRoom { coord: RoomCoord::new(3, 1), room_type: RoomType::Combat, name: Some("Ashen Door".to_string()) },
+        ]
+    }
+
+    #[test]
+    fn test_coord_parsing() {
+        assert_eq!(RoomCoord::parse("3-2"), Some(RoomCoord::new(3, 2)));
+        assert_eq!(RoomCoord::parse(" 3, 2 "), Some(RoomCoord::new(3, 2)));
+        assert_eq!(RoomCoord::parse("3"), None);
+        assert_eq!(RoomCoord::parse("3-2-1"), None);
+        assert_eq!(RoomCoord::new(3, 2).to_string(), "3-2");
+    }
+
+    #[test]
+    fn test_resolve_destinations() {
+        let next = RoomCoord::new(3, 2);
+        assert_eq!(resolve("onward", &rooms(), next), Some(Destination::Onward { name: None }));
+        assert_eq!(resolve("3-2", &rooms(), next), Some(Destination::Onward { name: None }));
+        assert_eq!(resolve("3-0", &rooms(), next), Some(Destination::Return(RoomCoord::new(3, 0))));
+        assert_eq!(resolve("ashen door", &rooms(), next), Some(Destination::Return(RoomCoord::new(3, 1))));
+        assert_eq!(resolve("Ashen Dor", &rooms(), next), Some(Destination::Return(RoomCoord::new(3, 1))));
+        assert_eq!(resolve("3-7", &rooms(), next), None);
+        assert_eq!(
+            resolve("  The   Quiet Well ", &rooms(), next),
+            Some(Destination::Onward { name: Some("The Quiet Well".to_string()) })
+        );
+    }
+
+    #[test]
+    fn test_clean_name() {
+        assert_eq!(clean_name("next"), None);
+        assert_eq!(clean_name("4-1"), None);
+        assert_eq!(clean_name("   "), None);
+        assert_eq!(clean_name("a very long name that keeps on going").unwrap().chars().count(), MAX_NAME_LEN);
+    }
+}
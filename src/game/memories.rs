@@ -0,0 +1,213 @@
+//! Memories - Flashes of a past life, kept and replayed
+//!
+//! Now and then, entering a room, a memory flashes past: a line of the life
+//! you had before the dungeon, gone as soon as it is read. Each memory is
+//! kept in a gallery the moment it is seen:
+//! - a memory in the gallery can be replayed, in full rather than the one
+//!   line that flashed
+//! - each chapter of the descent has memories of its own, two floors to a
+//!   chapter as with the dreams
+//! - the first time all of a chapter's memories are in the gallery, they
+//!   add up to something, and the run is the stronger for it
+//!
+//! Design: the memories are data in `MEMORIES`, and the pacing controller
+//! draws its memory flashes from them by chapter. Each memory seen is a
+//! world flag, so the gallery lasts between runs and is forgotten with a
+//! fresh timeline, as the rest of that life is.
+
+use rand::prelude::*;
+
+use super::dreams::chapter_of;
+use super::pacing::PacingBeat;
+use super::world_state::WorldState;
+
+/// Max HP a chapter's memories add, once all are in the gallery
+pub const WHOLE_CHAPTER_HP: i32 = 5;
+
+/// Letters of a replayed memory shown per second
+pub const REPLAY_LETTERS_PER_SECOND: u64 = 40;
+
+/// A memory of the life before the dungeon
+#[derive(Debug, Clone, Copy)]
+pub struct Memory {
+    pub key: &'static str,
+    pub chapter: u32,
+    pub title: &'static str,
+    /// The line that flashes past
+    pub flash: &'static str,
+    /// The memory as replayed from the gallery
+    pub full: &'static str,
+}
+
+pub static MEMORIES: [Memory; 10] = [
+    Memory {
+        key: "halls_banner_memory",
+        chapter: 1,
+        title: "The Banner",
+        flash: "A crown split by a sword. You sewed that banner. Why would you know that?",
+        full: "A crown split by a sword, stitched in gold thread by lamplight. You sewed it for a festival that never \
+            came, and someone laughed that the crown was crooked. You said a split crown was crooked anyway. They \
+            hung it in the great hall the next morning, and it is hanging there still, waiting for the festival.",
+    },
+    Memory {
+        key: "halls_gate_memory",
+        chapter: 1,
+        title: "The Gate",
+        flash: "You know the weight of this door. You have closed it before, from the other side.",
+        full: "You closed this gate from the outside, once, and put your shoulder to it until the bolt fell. Behind \
+            it something was still speaking. You told yourself it was the wind in the halls. You walked away \
+            quickly, and you did not write down what it had been saying.",
+    },
+    Memory {
+        key: "archives_memory",
+        chapter: 2,
+        title: "The Reading Room",
+        flash: "You've been here before. Haven't you? The feeling fades.",
+        full: "You have been here before, at the third table from the window, with a lamp that smoked. You came every \
+            evening for a year. The archivist kept your seat for you and never asked what you were reading. It was \
+            the same book every night: the grammar of the First Age, and the one page in it that had been torn out.",
+    },
+    Memory {
+        key: "archives_ink_memory",
+        chapter: 2,
+        title: "The Ink",
+        flash: "Ink on your fingers, black and wet. You were copying something you shouldn't.",
+        full: "Ink on your fingers, black and wet, and a page half copied. The words would not stay still on the \
+            original, so you copied them quickly, before they could change their minds. By morning the copy was \
+            the only one left. The original was blank, as if it had given its words to you and gone to sleep.",
+    },
+    Memory {
+        key: "garden_seed_memory",
+        chapter: 3,
+        title: "The Seed",
+        flash: "A seed in your palm, and a word you whispered into it. Something is growing still.",
+        full: "You held a seed in your palm and whispered a word into it, the way the Naturalists taught you. You \
+            were told it would grow into whatever it was named. You named it for someone you loved, and planted it \
+            by the wall. You never came back to see what it became. The garden has been growing ever since.",
+    },
+    Memory {
+        key: "garden_grave_memory",
+        chapter: 3,
+        title: "The Grave",
+        flash: "A grave without a name. You dug it. You meant to come back with the name.",
+        full: "You dug the grave yourself, at the end of the garden where the flowers grew wrong. You meant to come \
+            back with the name to carve on the stone. You knew it, then. You had known it all your life. But by \
+            the time you came back the name had gone out of the world, and the stone is bare.",
+    },
+    Memory {
+        key: "clockwork_memory",
+        chapter: 4,
+        title: "The Workshop",
+        flash: "You built this. No. That's impossible. Isn't it?",
+        full: "You built this, or the first of it: a small machine on a workbench that could keep a word from \
+            changing. The Mechanists wept when it worked. They built it larger, and larger, until it filled the \
+            depths, and they asked you to write what it should hold still. You wrote your own name, to be safe.",
+    },
+    Memory {
+        key: "clockwork_key_memory",
+        chapter: 4,
+        title: "The Key",
+        flash: "A brass key on a cord around your neck. You gave it to someone. Who?",
+        full: "A brass key on a cord around your neck, warm from your skin. It wound the great engine, the one that \
+            held the words still. You gave it to an apprentice on the last night and told them not to wind it again \
+            until you came back. You never came back. Somewhere below, the engine is running down.",
+    },
+    Memory {
+        key: "void_memory",
+        chapter: 5,
+        title: "The Dark",
+        flash: "You remember darkness. Endless. Hungry. Home.",
+        full: "You remember darkness, endless and hungry, and how it did not frighten you. It was quiet there. No \
+            word meant anything, so no word could be wrong. You stayed a long time. When you left, you left \
+            something of yourself behind, so that you would always know the way back. It is still calling.",
+    },
+    Memory {
+        key: "breach_memory",
+        chapter: 5,
+        title: "The Sundering",
+        flash: "Malachar stood here. No. YOU stood here. The truth approaches.",
+        full: "You stood here, at the edge of the breach, with the last word of the First Age in your mouth. They \
+            called you Malachar afterward, because they needed someone to blame who wasn't them. You spoke the word \
+            because the alternative was worse. You have never been sure that was true. You are still not sure.",
+    },
+];
+
+fn flag(memory: &Memory) -> String {
+    format!("memory_{}", memory.key)
+}
+
+fn whole_flag(chapter: u32) -> String {
+    format!("memories_whole_{}", chapter)
+}
+
+/// The memories of a chapter of the descent
+pub fn of_chapter(chapter: u32) -> impl Iterator<Item = &'static Memory> {
+    MEMORIES.iter().filter(move |m| m.chapter == chapter)
+}
+
+/// A memory flash from the chapter `floor` lies in
+pub fn flash<R: Rng>(floor: u32, rng: &mut R) -> Option<PacingBeat> {
+    let memory = of_chapter(chapter_of(floor as i32)).choose(rng)?;
+    Some(PacingBeat::MemoryFlash {
+        text: memory.flash.into(),
+        lore_key: Some(memory.key.into()),
+    })
+}
+
+pub fn seen(world: &WorldState, memory: &Memory) -> bool {
+    world.has_flag(&flag(memory))
+}
+
+/// Whether all of a chapter's memories are in the gallery
+pub fn whole(world: &WorldState, chapter: u32) -> bool {
+    of_chapter(chapter).all(|m| seen(world, m))
+}
+
+/// Keep the memory `key` in the gallery; the chapter it makes whole, if it
+/// is the first time that chapter has been
+pub fn remember(world: &mut WorldState, key: &str) -> Option<u32> {
+    let memory = MEMORIES.iter().find(|m| m.key == key)?;
+    world.set_flag(&flag(memory));
+    (whole(world, memory.chapter) && world.set_flag(&whole_flag(memory.chapter))).then_some(memory.chapter)
+}
+
+/// A replayed memory as far as it has played, `elapsed_ms` in
+pub fn replayed(memory: &Memory, elapsed_ms: u64) -> String {
+    let letters = (elapsed_ms * REPLAY_LETTERS_PER_SECOND / 1000) as usize;
+    memory.full.chars().take(letters).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_chapter_flashes_its_own_memories() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for floor in 1..=12 {
+            let Some(PacingBeat::MemoryFlash { text, lore_key }) = flash(floor, &mut rng) else {
+                panic!("floor {} has no memories", floor);
+            };
+            let memory = MEMORIES.iter().find(|m| Some(m.key) == lore_key.as_deref()).unwrap();
+            assert_eq!(memory.chapter, chapter_of(floor as i32));
+            assert_eq!(memory.flash, text);
+        }
+        assert!(of_chapter(1).count() >= 2);
+    }
+
+    #[test]
+    fn test_a_chapter_is_made_whole_once_a_timeline() {
+        let mut world = WorldState::default();
+        assert_eq!(remember(&mut world, "halls_banner_memory"), None);
+        assert!(!whole(&world, 1));
+        assert_eq!(remember(&mut world, "halls_gate_memory"), Some(1));
+        assert!(whole(&world, 1));
+        assert_eq!(remember(&mut world, "halls_gate_memory"), None);
+        assert_eq!(remember(&mut world, "no_such_memory"), None);
+
+        world.start_fresh();
+        assert!(!seen(&world, &MEMORIES[0]));
+        assert_eq!(replayed(&MEMORIES[0], 0), "");
+        assert_eq!(replayed(&MEMORIES[0], 1_000_000), MEMORIES[0].full);
+    }
+}
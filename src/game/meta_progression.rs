@@ -10,8 +10,15 @@
 //! - Variety over power creep
 
 use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
+use super::config::get_config_dir;
+
+/// Runs kept in the history
+const HISTORY_LENGTH: usize = 20;
+
 /// Persistent meta-progression save
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetaProgress {
@@ -165,6 +172,9 @@ pub struct RunSummary {
     pub modifiers: Vec<String>,
     /// Heat level
     pub heat: u32,
+    /// The run written up, a paragraph to an entry
+    #[serde(default)]
+    pub epilogue: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -210,6 +220,17 @@ impl MetaProgress {
         }
     }
 
+    /// Begin a fresh timeline: bonds and endings are forgotten, typing
+    /// stats, ink, upgrades, achievements and lore are kept
+    pub fn start_new_timeline(&mut self) {
+        self.endings_seen.clear();
+        self.npc_bonds.clear();
+        self.unlocks.faction_favor.clear();
+        self.milestones.npcs_met.clear();
+        self.milestones.factions_maxed.clear();
+        self.milestones.story_beats_seen.clear();
+    }
+
     // ========================================================================
     // RUN MANAGEMENT
     // ========================================================================
@@ -252,8 +273,13 @@ impl MetaProgress {
         }
         
         // Add to history
+        self.record_run(summary);
+    }
+
+    /// Add a finished run to the history, dropping the oldest past the limit
+    pub fn record_run(&mut self, summary: RunSummary) {
         self.run_history.push(summary);
-        if self.run_history.len() > 20 {
+        if self.run_history.len() > HISTORY_LENGTH {
             self.run_history.remove(0);
         }
     }
@@ -610,3 +636,22 @@ impl UpgradeCategory {
         }
     }
 }
+
+fn history_path() -> PathBuf {
+    get_config_dir().join("run_history.ron")
+}
+
+/// The saved run history, oldest first
+pub fn load_run_history() -> Vec<RunSummary> {
+    fs::read_to_string(history_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_run_history(history: &[RunSummary]) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(history, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(history_path(), content)
+}
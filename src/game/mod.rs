@@ -13,14 +13,38 @@ pub mod enemy;
 pub mod combat;
 pub mod combat_events;
 pub mod combat_engine;
+pub mod difficulty_director;
+pub mod prompt_corruption;
+pub mod class_abilities;
+pub mod class_builds;
+pub mod fatigue;
+pub mod weak_points;
+pub mod executions;
+pub mod inscription;
+pub mod enemy_shields;
+pub mod stances;
+pub mod combat_replay;
+pub mod word_chain;
+pub mod typing_challenges;
+pub mod channeling;
+pub mod enrage;
+pub mod hit_locations;
+pub mod hazards;
+pub mod combat_scheduler;
+pub mod true_names;
+pub mod artifacts;
+pub mod curses;
 
 // Character progression
 pub mod spells;
 pub mod items;
 pub mod skills;
+pub mod shop;
 
 // World and narrative
 pub mod dungeon;
+pub mod map_travel;
+pub mod run_map;
 pub mod events;
 pub mod narrative;
 pub mod quests;
@@ -30,34 +54,90 @@ pub mod world;
 // New deep systems
 pub mod narrative_seed;
 pub mod faction_system;
+pub mod patrons;
 pub mod typing_context;
+pub mod keyboard_layout;
+pub mod key_accuracy;
 pub mod event_bus;
 pub mod run_modifiers;
+pub mod run_rng;
 pub mod voice_system;
+pub mod npc_voice;
 
 // Persistence and configuration
 pub mod save;
 pub mod config;
+pub mod settings;
+pub mod keybinds;
 pub mod stats;
+pub mod stats_store;
+pub mod auto_pause;
+pub mod pause;
+pub mod latency;
+pub mod status_line;
+pub mod pronunciation;
+pub mod dev_console;
 
 pub mod world_engine;
 
 // Deep lore and narrative systems
 pub mod deep_lore;
 pub mod lore_fragments;
+pub mod comprehension;
 pub mod encounter_writing;
 pub mod writing_guidelines;
+pub mod sentence_stitching;
 pub mod narrative_integration;
 pub mod typing_feel;
 pub mod meta_progression;
+pub mod consequences;
+pub mod endings;
+pub mod journal;
+pub mod encounter_runner;
+pub mod encounter_scheduler;
+pub mod leveling;
+pub mod zone_routes;
+pub mod companions;
+pub mod dreams;
+pub mod consequence_engine;
+pub mod dialogue_tree;
+pub mod codex;
+pub mod clue_board;
+pub mod revelation;
+pub mod npc_memory;
+pub mod npc_lifecycle;
+pub mod faction_quests;
+pub mod encounter_data;
+pub mod setting_packs;
+pub mod text_grammar;
+pub mod item_lore;
+pub mod epilogue;
+pub mod letters;
+pub mod rumors;
+pub mod narrative_script;
+pub mod living_book;
+pub mod cipher_notes;
+pub mod naming_ritual;
+pub mod unreliable_narrator;
+pub mod prophecies;
+pub mod memories;
+pub mod transcript;
+pub mod ideology;
+pub mod world_state;
+pub mod challenges;
+pub mod ascension;
 pub mod help_system;
 pub mod tutorial;
+pub mod training;
 pub mod world_integration;
 
 // Immersion overhaul systems (v0.6.0)
 pub mod typing_impact;
 pub mod dialogue_engine;
 pub mod enemy_visuals;
+pub mod enemy_intent;
+pub mod boss_bar;
 pub mod pacing;
 pub mod player_avatar;
 pub mod combat_immersion;
+pub mod scars;
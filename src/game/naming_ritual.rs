@@ -0,0 +1,129 @@
+//! Naming Rituals - Typing a true name while it tries to get away
+//!
+//! Some encounters ask for a true name: your own, or an enemy's. An
+//! encounter whose typing challenge is a ritual takes the whole screen for
+//! it, and the name fights back:
+//! - it trembles, its letters shifting in place
+//! - its untyped letters corrupt into glyphs, more of them the longer you
+//!   take, so a name read slowly is a name half lost
+//!
+//! How the name comes out decides what happens. Unlike an ordinary typing
+//! challenge, where a failure only loses the choice's consequences, a
+//! ritual has a consequence set for success, for a partial success and for
+//! failure, each written with the encounter.
+//!
+//! Design: a `Ritual` is data on the encounter's typing challenge, so any
+//! bundled or pack encounter can hold one. The effects are pure functions
+//! of the letter and the time since the ritual began, so the renderer can
+//! draw each frame without keeping state of its own.
+
+use serde::{Deserialize, Serialize};
+
+use super::encounter_runner::TypingGrade;
+use super::encounter_writing::EncounterConsequences;
+
+/// How often the name trembles and its corruption shifts
+pub const FLICKER_MS: u64 = 180;
+
+/// Share of the untyped letters corrupted per second of hesitation
+const CORRUPTION_PER_SECOND: f32 = 0.04;
+
+/// Most of the untyped letters that can be corrupted at once
+const MAX_CORRUPTION: f32 = 0.45;
+
+/// What corrupted letters turn into
+const GLYPHS: [char; 8] = ['▓', '▒', '░', '§', '¤', '∆', '≠', '#'];
+
+/// A name to be typed, and what each way of typing it leads to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ritual {
+    /// Whose name it is, as the screen says it: "your true name"
+    pub subject: String,
+    /// The name itself, as typed
+    pub name: String,
+    pub success: EncounterConsequences,
+    /// Falls back to the success set
+    #[serde(default)]
+    pub partial: Option<EncounterConsequences>,
+    pub failure: EncounterConsequences,
+}
+
+impl Ritual {
+    /// The consequences a typing of the name leads to
+    pub fn consequences(&self, grade: TypingGrade) -> &EncounterConsequences {
+        match grade {
+            TypingGrade::Success => &self.success,
+            TypingGrade::Partial => self.partial.as_ref().unwrap_or(&self.success),
+            TypingGrade::Failure => &self.failure,
+        }
+    }
+}
+
+/// A stable scramble of a letter's place and the moment
+fn mix(index: usize, tick: u64) -> u64 {
+    let mut x = (index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ tick.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x ^= x >> 31;
+    x = x.wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 29)
+}
+
+/// Columns letter `index` is shifted right by, `elapsed_ms` into the ritual
+pub fn tremble(index: usize, elapsed_ms: u64) -> u16 {
+    (mix(index, elapsed_ms / FLICKER_MS) % 3) as u16
+}
+
+/// Share of the untyped letters corrupted `elapsed_ms` into the ritual
+pub fn corruption(elapsed_ms: u64) -> f32 {
+    (elapsed_ms as f32 / 1000.0 * CORRUPTION_PER_SECOND).min(MAX_CORRUPTION)
+}
+
+/// The name as it shows `elapsed_ms` into the ritual with `typed` letters
+/// typed: each letter, and whether it is corrupted. Typed letters and
+/// spaces are never touched.
+pub fn corrupted(name: &str, typed: usize, elapsed_ms: u64) -> Vec<(char, bool)> {
+    let share = corruption(elapsed_ms);
+    let tick = elapsed_ms / FLICKER_MS;
+    name.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let roll = mix(i, tick);
+            if i < typed || c == ' ' || (roll % 1000) as f32 >= share * 1000.0 {
+                (c, false)
+            } else {
+                (GLYPHS[(roll >> 16) as usize % GLYPHS.len()], true)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_the_name_corrupts_with_hesitation_but_spares_what_is_typed() {
+        let name = "The One Who Returns";
+        assert!(corrupted(name, 0, 0).iter().all(|(_, bad)| !bad));
+        assert_eq!(corruption(60_000), MAX_CORRUPTION);
+
+        let late = corrupted(name, 4, 30_000);
+        assert!(late.iter().any(|(_, bad)| *bad), "half a minute in, something should have slipped");
+        assert!(late[..4].iter().zip(name.chars()).all(|((c, bad), n)| !bad && *c == n));
+        assert!(late.iter().zip(name.chars()).all(|((c, bad), n)| n != ' ' || (*c == ' ' && !bad)));
+        assert!((0..40).all(|i| tremble(i, 1234) < 3));
+    }
+
+    #[test]
+    fn test_each_grade_routes_to_its_consequences() {
+        let ritual: Ritual = ron::from_str(r#"(
+            subject: "his name",
+            name: "Sir Aldric",
+            success: (world_state_changes: ["named"], narrative_result: "He rests."),
+            failure: (narrative_result: "He rises."),
+        )"#).unwrap();
+        assert_eq!(ritual.consequences(TypingGrade::Success).world_state_changes, vec!["named".to_string()]);
+        assert_eq!(ritual.consequences(TypingGrade::Partial).narrative_result, "He rests.");
+        assert_eq!(ritual.consequences(TypingGrade::Failure).narrative_result, "He rises.");
+    }
+}
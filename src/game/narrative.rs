@@ -4,6 +4,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::setting_packs;
+
 /// The world's deep lore and history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldState {
@@ -92,15 +94,23 @@ pub enum Faction {
 }
 
 impl Faction {
-    pub fn name(&self) -> &'static str {
-        match self {
-            Faction::MagesGuild => "The Mages Guild",
-            Faction::TempleOfDawn => "The Temple of Dawn",
-            Faction::RangersOfTheWild => "The Rangers of the Wild",
-            Faction::ShadowGuild => "The Shadow Guild",
-            Faction::MerchantConsortium => "The Merchant Consortium",
+    /// Faction from its id as written in authored data, e.g. "MagesGuild"
+    /// Also accepts the names authored encounters and lore use for them
+    pub fn from_id(id: &str) -> Option<Faction> {
+        match id {
+            "MagesGuild" | "Scribes" => Some(Faction::MagesGuild),
+            "TempleOfDawn" | "Mechanists" => Some(Faction::TempleOfDawn),
+            "RangersOfTheWild" | "Naturalists" => Some(Faction::RangersOfTheWild),
+            "ShadowGuild" | "ShadowWriters" => Some(Faction::ShadowGuild),
+            "MerchantConsortium" | "Archivists" => Some(Faction::MerchantConsortium),
+            _ => None,
         }
     }
+
+    /// The faction's name in the run's setting
+    pub fn name(&self) -> &'static str {
+        setting_packs::pack().faction_name(*self)
+    }
     
     pub fn philosophy(&self) -> &'static str {
         match self {
@@ -0,0 +1,561 @@
+//! Narrative Script - Gates and effects written as small expressions
+//!
+//! A world flag alone is a blunt gate. Encounters (`requirements.condition`)
+//! and conversation options (`Script`) can be gated by an expression:
+//!
+//!     chapter >= 3 && rep("Scribes") > 20 && !flag("voice_silent")
+//!
+//! - numbers: literals, `chapter`, `floor`, `rep("faction")`,
+//!   `counter("name")`, `opinion("npc")`, compared with `==` `!=` `<` `<=`
+//!   `>` `>=`
+//! - tests: `flag("x")`, `lore("id")`, `done("encounter")`,
+//!   `chose("encounter", "choice")`, `npc("vera", "dead")`, `true`, `false`
+//! - joined with `!`, `&&`, `||` and parentheses
+//!
+//! An encounter's consequences can carry effects too, separated by `;`:
+//!
+//!     set("vault_opened"); bump("vault_visits", 1); rep("ShadowWriters", -5)
+//!
+//! Design: expressions are parsed as the data is deserialized, so a typo or
+//! an unknown faction is a load error naming the encounter, not a gate that
+//! silently never opens. They read the run through `Facts`, which the
+//! scheduler's and the conversation's contexts both implement.
+
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use serde::{Deserialize, Serialize};
+
+use super::dreams;
+use super::encounter_writing::EncounterTracker;
+use super::narrative::Faction;
+use super::npc_lifecycle::NpcState;
+use super::world_state::WorldState;
+
+/// What an expression can ask of the run
+pub trait Facts {
+    fn floor(&self) -> i32;
+    fn standing(&self, faction: Faction) -> i32;
+    fn world(&self) -> &WorldState;
+    fn tracker(&self) -> &EncounterTracker;
+    fn has_lore(&self, id: &str) -> bool;
+    fn opinion(&self, npc: &str) -> i32;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Sym(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "{}", name),
+            Token::Str(text) => write!(f, "\"{}\"", text),
+            Token::Int(n) => write!(f, "{}", n),
+            Token::Sym(sym) => write!(f, "{}", sym),
+        }
+    }
+}
+
+/// Symbols, longest first so `>=` isn't read as `>`
+const SYMBOLS: [&str; 14] = ["&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "(", ")", ",", ";", "-"];
+
+fn lex(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<Chars> = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut s = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(ch) => s.push(ch),
+                    None => return Err("unclosed string".to_string()),
+                }
+            }
+            tokens.push(Token::Str(s));
+        } else if c.is_ascii_digit() {
+            let mut n = String::new();
+            while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                n.push(d);
+                chars.next();
+            }
+            tokens.push(Token::Int(n.parse().map_err(|_| format!("number too large: {}", n))?));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut name = String::new();
+            while let Some(&d) = chars.peek().filter(|d| d.is_ascii_alphanumeric() || **d == '_') {
+                name.push(d);
+                chars.next();
+            }
+            tokens.push(Token::Ident(name));
+        } else {
+            let rest: String = chars.clone().take(2).collect();
+            let sym = SYMBOLS.iter().find(|s| rest.starts_with(**s)).ok_or_else(|| format!("unexpected '{}'", c))?;
+            for _ in 0..sym.len() {
+                chars.next();
+            }
+            tokens.push(Token::Sym(sym));
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Compare {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Compare {
+    fn of(sym: &str) -> Option<Self> {
+        Some(match sym {
+            "==" => Self::Eq,
+            "!=" => Self::Ne,
+            "<" => Self::Lt,
+            "<=" => Self::Le,
+            ">" => Self::Gt,
+            ">=" => Self::Ge,
+            _ => return None,
+        })
+    }
+
+    fn holds(self, a: i64, b: i64) -> bool {
+        match self {
+            Self::Eq => a == b,
+            Self::Ne => a != b,
+            Self::Lt => a < b,
+            Self::Le => a <= b,
+            Self::Gt => a > b,
+            Self::Ge => a >= b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Number {
+    Literal(i64),
+    Chapter,
+    Floor,
+    Reputation(Faction),
+    Counter(String),
+    Opinion(String),
+}
+
+impl Number {
+    fn value(&self, facts: &dyn Facts) -> i64 {
+        match self {
+            Self::Literal(n) => *n,
+            Self::Chapter => dreams::chapter_of(facts.floor()) as i64,
+            Self::Floor => facts.floor() as i64,
+            Self::Reputation(faction) => facts.standing(*faction) as i64,
+            Self::Counter(name) => facts.world().counter(name),
+            Self::Opinion(npc) => facts.opinion(npc) as i64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(bool),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Number, Compare, Number),
+    Flag(String),
+    Lore(String),
+    Done(String),
+    Chose(String, String),
+    Npc(String, NpcState),
+}
+
+impl Expr {
+    fn eval(&self, facts: &dyn Facts) -> bool {
+        match self {
+            Self::Literal(b) => *b,
+            Self::Not(e) => !e.eval(facts),
+            Self::And(a, b) => a.eval(facts) && b.eval(facts),
+            Self::Or(a, b) => a.eval(facts) || b.eval(facts),
+            Self::Compare(a, op, b) => op.holds(a.value(facts), b.value(facts)),
+            Self::Flag(flag) => facts.world().has_flag(flag),
+            Self::Lore(id) => facts.has_lore(id),
+            Self::Done(encounter) => facts.tracker().has_completed(encounter),
+            Self::Chose(encounter, choice) => facts.tracker().choices_made.get(encounter).is_some_and(|c| c == choice),
+            Self::Npc(npc, state) => facts.world().npc_state(npc) == *state,
+        }
+    }
+}
+
+/// A statement of an effects script
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    SetFlag(String),
+    Bump(String, i64),
+    Standing(Faction, i32),
+}
+
+fn faction(id: &str) -> Result<Faction, String> {
+    Faction::from_id(id).ok_or_else(|| format!("unknown faction \"{}\"", id))
+}
+
+fn npc_state(name: &str) -> Result<NpcState, String> {
+    Ok(match name {
+        "alive" => NpcState::Alive,
+        "transformed" => NpcState::Transformed,
+        "relocated" => NpcState::Relocated,
+        "dead" => NpcState::Dead,
+        other => return Err(format!("unknown npc state \"{}\"", other)),
+    })
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    at: usize,
+}
+
+impl Parser {
+    fn new(text: &str) -> Result<Self, String> {
+        Ok(Self { tokens: lex(text)?, at: 0 })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.at)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.at).cloned();
+        self.at += 1;
+        token
+    }
+
+    fn eat(&mut self, sym: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Sym(s)) if *s == sym);
+        if found {
+            self.at += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, sym: &str) -> Result<(), String> {
+        if self.eat(sym) {
+            Ok(())
+        } else {
+            Err(match self.peek() {
+                Some(token) => format!("expected '{}', found '{}'", sym, token),
+                None => format!("expected '{}' at the end", sym),
+            })
+        }
+    }
+
+    fn done(&self) -> Result<(), String> {
+        match self.peek() {
+            Some(token) => Err(format!("unexpected '{}'", token)),
+            None => Ok(()),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Str(s)) => Ok(s),
+            Some(token) => Err(format!("expected a string, found '{}'", token)),
+            None => Err("expected a string at the end".to_string()),
+        }
+    }
+
+    fn int(&mut self) -> Result<i64, String> {
+        let negative = self.eat("-");
+        match self.next() {
+            Some(Token::Int(n)) => Ok(if negative { -n } else { n }),
+            Some(token) => Err(format!("expected a number, found '{}'", token)),
+            None => Err("expected a number at the end".to_string()),
+        }
+    }
+
+    /// `name("a")` or `name("a", "b")`, the opening paren already read
+    fn args(&mut self, count: usize) -> Result<Vec<String>, String> {
+        let mut args = vec![self.string()?];
+        while args.len() < count {
+            self.expect(",")?;
+            args.push(self.string()?);
+        }
+        self.expect(")")?;
+        Ok(args)
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.eat("||") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while self.eat("&&") {
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let inner = self.or()?;
+            self.expect(")")?;
+            return Ok(inner);
+        }
+        // A test, or else a number to compare
+        if let Some(Token::Ident(name)) = self.peek().cloned() {
+            let test = match name.as_str() {
+                "true" => Some(Expr::Literal(true)),
+                "false" => Some(Expr::Literal(false)),
+                _ => None,
+            };
+            if let Some(test) = test {
+                self.at += 1;
+                return Ok(test);
+            }
+            if matches!(name.as_str(), "flag" | "lore" | "done" | "chose" | "npc") {
+                self.at += 1;
+                self.expect("(")?;
+                return Ok(match name.as_str() {
+                    "flag" => Expr::Flag(self.args(1)?.remove(0)),
+                    "lore" => Expr::Lore(self.args(1)?.remove(0)),
+                    "done" => Expr::Done(self.args(1)?.remove(0)),
+                    "chose" => {
+                        let mut args = self.args(2)?;
+                        Expr::Chose(args.remove(0), args.remove(0))
+                    }
+                    _ => {
+                        let mut args = self.args(2)?;
+                        let state = npc_state(&args[1])?;
+                        Expr::Npc(args.remove(0), state)
+                    }
+                });
+            }
+        }
+        let left = self.number()?;
+        let op = match self.next() {
+            Some(Token::Sym(sym)) => Compare::of(sym).ok_or_else(|| format!("expected a comparison, found '{}'", sym))?,
+            Some(token) => return Err(format!("expected a comparison, found '{}'", token)),
+            None => return Err("expected a comparison at the end".to_string()),
+        };
+        Ok(Expr::Compare(left, op, self.number()?))
+    }
+
+    fn number(&mut self) -> Result<Number, String> {
+        match self.peek().cloned() {
+            Some(Token::Int(_)) | Some(Token::Sym("-")) => Ok(Number::Literal(self.int()?)),
+            Some(Token::Ident(name)) => {
+                self.at += 1;
+                match name.as_str() {
+                    "chapter" => Ok(Number::Chapter),
+                    "floor" => Ok(Number::Floor),
+                    "rep" | "counter" | "opinion" => {
+                        self.expect("(")?;
+                        let arg = self.args(1)?.remove(0);
+                        Ok(match name.as_str() {
+                            "rep" => Number::Reputation(faction(&arg)?),
+                            "counter" => Number::Counter(arg),
+                            _ => Number::Opinion(arg),
+                        })
+                    }
+                    other => Err(format!("unknown name '{}'", other)),
+                }
+            }
+            Some(token) => Err(format!("unexpected '{}'", token)),
+            None => Err("expression ends too soon".to_string()),
+        }
+    }
+
+    fn effect(&mut self) -> Result<Effect, String> {
+        let name = match self.next() {
+            Some(Token::Ident(name)) => name,
+            Some(token) => return Err(format!("expected an effect, found '{}'", token)),
+            None => return Err("expected an effect at the end".to_string()),
+        };
+        self.expect("(")?;
+        let arg = self.string()?;
+        let effect = match name.as_str() {
+            "set" => Effect::SetFlag(arg),
+            "bump" | "rep" => {
+                self.expect(",")?;
+                let by = self.int()?;
+                if name == "bump" {
+                    Effect::Bump(arg, by)
+                } else {
+                    Effect::Standing(faction(&arg)?, by as i32)
+                }
+            }
+            other => return Err(format!("unknown effect '{}'", other)),
+        };
+        self.expect(")")?;
+        Ok(effect)
+    }
+}
+
+/// A gate written as an expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+
+impl Condition {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser::new(text)?;
+        let expr = parser.or()?;
+        parser.done()?;
+        Ok(Self { source: text.to_string(), expr })
+    }
+
+    pub fn holds(&self, facts: &dyn Facts) -> bool {
+        self.expr.eval(facts)
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        Self::parse(&text).map_err(|e| format!("condition \"{}\": {}", text, e))
+    }
+}
+
+impl From<Condition> for String {
+    fn from(condition: Condition) -> String {
+        condition.source
+    }
+}
+
+/// Effects written as `;`-separated statements
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Effects {
+    source: String,
+    pub effects: Vec<Effect>,
+}
+
+impl Effects {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser::new(text)?;
+        let mut effects = Vec::new();
+        while parser.peek().is_some() {
+            effects.push(parser.effect()?);
+            if !parser.eat(";") {
+                break;
+            }
+        }
+        parser.done()?;
+        Ok(Self { source: text.to_string(), effects })
+    }
+}
+
+impl TryFrom<String> for Effects {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        Self::parse(&text).map_err(|e| format!("effects \"{}\": {}", text, e))
+    }
+}
+
+impl From<Effects> for String {
+    fn from(effects: Effects) -> String {
+        effects.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::faction_system::FactionRelations;
+
+    struct Run {
+        floor: i32,
+        factions: FactionRelations,
+        world: WorldState,
+        tracker: EncounterTracker,
+    }
+
+    impl Facts for Run {
+        fn floor(&self) -> i32 {
+            self.floor
+        }
+        fn standing(&self, faction: Faction) -> i32 {
+            self.factions.standing(&faction)
+        }
+        fn world(&self) -> &WorldState {
+            &self.world
+        }
+        fn tracker(&self) -> &EncounterTracker {
+            &self.tracker
+        }
+        fn has_lore(&self, id: &str) -> bool {
+            id == "first_silence"
+        }
+        fn opinion(&self, _npc: &str) -> i32 {
+            0
+        }
+    }
+
+    #[test]
+    fn test_conditions_read_the_run() {
+        let mut run = Run { floor: 5, factions: FactionRelations::new(), world: WorldState::default(), tracker: EncounterTracker::new() };
+        let gate = Condition::parse(r#"chapter >= 3 && rep("Scribes") > 20 && !flag("voice_silent")"#).unwrap();
+        assert!(!gate.holds(&run));
+        run.factions.modify_standing(Faction::MagesGuild, 25);
+        assert!(gate.holds(&run));
+        run.world.set_flag("voice_silent");
+        assert!(!gate.holds(&run));
+
+        let either = Condition::parse(r#"(chose("shadowwriter_offer", "accept_shadow") || npc("vera", "dead")) && lore("first_silence")"#).unwrap();
+        assert!(!either.holds(&run));
+        run.world.set_npc_state("vera", NpcState::Dead);
+        assert!(either.holds(&run));
+        assert!(Condition::parse("counter(\"descents\") == 0 && floor < -1 || true").unwrap().holds(&run));
+    }
+
+    #[test]
+    fn test_mistakes_fail_to_load() {
+        for bad in [
+            r#"rep("Librarians") > 3"#,
+            r#"chapter >"#,
+            r#"flag(voice)"#,
+            r#"npc("vera", "asleep")"#,
+            r#"chapter >= 3 &&"#,
+            r#"chapter = 3"#,
+        ] {
+            assert!(Condition::parse(bad).is_err(), "{} parsed", bad);
+        }
+        let effects = Effects::parse(r#"set("vault_opened"); bump("vault_visits", 1); rep("ShadowWriters", -5)"#).unwrap();
+        assert_eq!(effects.effects, vec![
+            Effect::SetFlag("vault_opened".to_string()),
+            Effect::Bump("vault_visits".to_string(), 1),
+            Effect::Standing(Faction::ShadowGuild, -5),
+        ]);
+        assert!(Effects::parse(r#"set("a") bump("b", 1)"#).is_err());
+
+        // Parsed as the data loads, so a bad gate names itself
+        let err = ron::from_str::<Condition>(r#""rep(\"Nobody\") > 0""#).unwrap_err().to_string();
+        assert!(err.contains("unknown faction"), "{}", err);
+    }
+}
@@ -0,0 +1,296 @@
+//! NPC Lifecycle - Recurring characters who change between runs
+//!
+//! Vera, the Stranger and Technician Kaya live on past the run you met
+//! them in. Each is alive until a choice changes that, and what happens to
+//! them stays happened, run after run, until the timeline is wiped:
+//! - Alive: where you first found them, as you first found them
+//! - Transformed: the Corruption has made them into something else
+//! - Relocated: they have moved on, and can be found somewhere new
+//! - Dead: gone, with only what they left behind
+//!
+//! An NPC's first encounter only turns up while they are alive. Once their
+//! state changes, an encounter written for that state turns up instead.
+//!
+//! Design: states are kept in WorldState beside the flags, so they are
+//! saved with it. Which choices change whom is data in `TRANSITIONS`; the
+//! variant encounters are ordinary authored encounters, gated on an NPC's
+//! state through `EncounterRequirements::npc_state`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::encounter_writing::{
+    AuthoredEncounter, DialogueLine, EncounterChoice, EncounterConsequences, EncounterContent, EncounterRequirements,
+};
+use super::world_state::WorldState;
+
+/// Where a recurring NPC is in their story
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NpcState {
+    #[default]
+    Alive,
+    Transformed,
+    Relocated,
+    Dead,
+}
+
+impl NpcState {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Alive => "alive",
+            Self::Transformed => "transformed",
+            Self::Relocated => "moved on",
+            Self::Dead => "dead",
+        }
+    }
+}
+
+/// A choice that changes a recurring NPC's state
+#[derive(Debug, Clone, Copy)]
+pub struct Transition {
+    /// NpcMemory id of the NPC
+    pub npc: &'static str,
+    pub encounter: &'static str,
+    pub choice: &'static str,
+    pub to: NpcState,
+    /// What the player is told
+    pub message: &'static str,
+}
+
+pub static TRANSITIONS: [Transition; 5] = [
+    Transition {
+        npc: "vera",
+        encounter: "haven_old_scribe",
+        choice: "offer_help",
+        to: NpcState::Relocated,
+        message: "Vera packs up her bench. She means to help you from the Athenaeum.",
+    },
+    Transition {
+        npc: "Stranger",
+        encounter: "haven_stranger_arrival",
+        choice: "help_stranger",
+        to: NpcState::Relocated,
+        message: "The stranger sets out for the Whispering Waste ahead of you.",
+    },
+    Transition {
+        npc: "Stranger",
+        encounter: "haven_stranger_arrival",
+        choice: "refuse_stranger",
+        to: NpcState::Transformed,
+        message: "The stranger walks back into the night alone. The shimmer on them looked deeper.",
+    },
+    Transition {
+        npc: "kaya",
+        encounter: "mechanist_breakdown",
+        choice: "challenge_mechanist",
+        to: NpcState::Transformed,
+        message: "Kaya stops arguing. She stops crying, too. She goes very still.",
+    },
+    Transition {
+        npc: "kaya",
+        encounter: "corruption_memory_echo",
+        choice: "embrace_memory",
+        to: NpcState::Dead,
+        message: "Somewhere in the Gearhold, a workshop falls silent.",
+    },
+];
+
+/// Apply the state changes `choice` in `encounter` brings; returns what
+/// to tell the player
+pub fn on_choice(world: &mut WorldState, encounter: &str, choice: &str) -> Vec<&'static str> {
+    let mut messages = Vec::new();
+    for t in TRANSITIONS.iter().filter(|t| t.encounter == encounter && t.choice == choice) {
+        if world.npc_state(t.npc) != t.to {
+            world.set_npc_state(t.npc, t.to);
+            messages.push(t.message);
+        }
+    }
+    messages
+}
+
+/// An encounter written for one NPC state
+struct Variant {
+    id: &'static str,
+    title: &'static str,
+    npc: &'static str,
+    state: NpcState,
+    locations: &'static [&'static str],
+    description: &'static str,
+    /// What they say: (speaker, line)
+    line: (&'static str, &'static str),
+    /// The one thing to do: (id, text)
+    choice: (&'static str, &'static str),
+    consequences: EncounterConsequences,
+}
+
+impl Variant {
+    fn build(self) -> AuthoredEncounter {
+        AuthoredEncounter {
+            id: self.id.to_string(),
+            title: self.title.to_string(),
+            valid_locations: self.locations.iter().map(|l| l.to_string()).collect(),
+            requirements: EncounterRequirements {
+                npc_state: Some((self.npc.to_string(), self.state)),
+                ..Default::default()
+            },
+            content: EncounterContent {
+                description: self.description.to_string(),
+                dialogue: Some(vec![DialogueLine {
+                    speaker: self.line.0.to_string(),
+                    text: self.line.1.to_string(),
+                    reveals: None,
+                }]),
+                environmental_details: Vec::new(),
+                typing_challenge: None,
+            },
+            choices: vec![EncounterChoice {
+                id: self.choice.0.to_string(),
+                text: self.choice.1.to_string(),
+                requires: None,
+                consequence_id: format!("{}_result", self.choice.0),
+                typing_required: false,
+            }],
+            consequences: self.consequences,
+            outcomes: BTreeMap::new(),
+            repeatable: false,
+            tags: vec!["recurring_npc".to_string(), self.npc.to_lowercase()],
+        }
+    }
+}
+
+/// The encounters recurring NPCs turn up in once their state has changed
+pub fn variant_encounters() -> Vec<AuthoredEncounter> {
+    vec![
+        Variant {
+            id: "vera_in_the_stacks",
+            title: "Vera Among the Stacks",
+            npc: "vera",
+            state: NpcState::Relocated,
+            locations: &["athenaeum", "athenaeum_stacks"],
+            description: "A familiar voice drifts from between the shelves, reading aloud to no one. Vera has a desk \
+                here now, buried in catalogue cards, her clouded eyes an inch from each one.",
+            line: ("Old Scribe", "There you are. I've been reading everything the Archivists forgot to hide. \
+                Sit. You'll want to hear this."),
+            choice: ("listen_to_vera", "Sit with her and listen."),
+            consequences: EncounterConsequences {
+                reputation_changes: vec![("Scribes".to_string(), 5)],
+                lore_revealed: vec!["first_scribe_warning".to_string()],
+                narrative_result: "Vera talks until the lamps burn low. Half of it you will forget. \
+                    The other half may save your life.".to_string(),
+                ..Default::default()
+            },
+        }
+        .build(),
+        Variant {
+            id: "stranger_in_the_waste",
+            title: "Tracks in the Waste",
+            npc: "Stranger",
+            state: NpcState::Relocated,
+            locations: &["whispering_waste", "corruption_zone"],
+            description: "Boot prints in the ash, and beside them a line of words scratched into the dirt with a stick, \
+                still legible. Someone has been marking a safe path. At its end, the stranger from the inn \
+                waits by a dead fire.",
+            line: ("Stranger", "You came. I've mapped what I could. It moves at dusk. Don't let it hear you \
+                typing."),
+            choice: ("take_the_map", "Take the map and thank them."),
+            consequences: EncounterConsequences {
+                items_gained: vec!["health_potion".to_string()],
+                narrative_result: "The stranger presses a potion into your hand with the map. 'For when it \
+                    finds you anyway.'".to_string(),
+                ..Default::default()
+            },
+        }
+        .build(),
+        Variant {
+            id: "stranger_transformed",
+            title: "The Shimmering Thing",
+            npc: "Stranger",
+            state: NpcState::Transformed,
+            locations: &["whispering_waste", "corruption_zone"],
+            description: "Something waits in the mist that was once the stranger from the inn. The shimmer has eaten \
+                them down to an outline, and the outline is still holding its hands palm-up.",
+            line: ("Stranger", "S O M E O N E   W H O   T Y P E S   T R U E. You could have. You didn't."),
+            choice: ("back_away", "Back away slowly."),
+            consequences: EncounterConsequences {
+                reputation_changes: vec![("Naturalists".to_string(), -5)],
+                narrative_result: "It doesn't follow. It doesn't need to. You will see it again, every time \
+                    you close your eyes.".to_string(),
+                ..Default::default()
+            },
+        }
+        .build(),
+        Variant {
+            id: "kaya_transformed",
+            title: "The Still Technician",
+            npc: "kaya",
+            state: NpcState::Transformed,
+            locations: &["gearhold", "mechanist_workshop"],
+            description: "Kaya's workshop runs perfectly now. Every gear turns true. Kaya sits at the centre of it, \
+                motionless, her eyes tracking the machines with a patience no one living has.",
+            line: ("Mechanist Technician", "You were right. I let it happen. Listen how quietly everything \
+                runs."),
+            choice: ("leave_kaya", "Leave her to the machines."),
+            consequences: EncounterConsequences {
+                reputation_changes: vec![("Mechanists".to_string(), -5)],
+                narrative_result: "The door closes behind you without a sound. Nothing in there makes a \
+                    sound anymore.".to_string(),
+                ..Default::default()
+            },
+        }
+        .build(),
+        Variant {
+            id: "kaya_memorial",
+            title: "An Empty Workshop",
+            npc: "kaya",
+            state: NpcState::Dead,
+            locations: &["gearhold", "mechanist_workshop"],
+            description: "Kaya's workshop is shut up. Someone has left a wrench on the bench, the way mourners here leave \
+                a tool for the dead, and a half-finished word-processor with one clean line printed on it.",
+            line: ("Mechanist Technician", "(The printed line reads: GEARS TURN TRUE WHEN THE WORDS RUN CLEAN.)"),
+            choice: ("take_the_wrench", "Take up the wrench."),
+            consequences: EncounterConsequences {
+                reputation_changes: vec![("Mechanists".to_string(), 5)],
+                narrative_result: "It's heavier than it looks. You carry it anyway.".to_string(),
+                ..Default::default()
+            },
+        }
+        .build(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    #[test]
+    fn test_transitions_and_variants_line_up() {
+        let encounters = build_encounters();
+        for t in &TRANSITIONS {
+            assert!(encounters.get(t.encounter).is_some_and(|e| e.choices.iter().any(|c| c.id == t.choice)));
+            assert!(
+                variant_encounters().iter().any(|v| v.requirements.npc_state == Some((t.npc.to_string(), t.to))),
+                "no encounter for {} {}",
+                t.npc,
+                t.to.describe()
+            );
+        }
+    }
+
+    #[test]
+    fn test_state_carries_in_world_state_until_a_fresh_timeline() {
+        let mut world = WorldState::default();
+        assert_eq!(world.npc_state("kaya"), NpcState::Alive);
+        assert_eq!(on_choice(&mut world, "mechanist_breakdown", "challenge_mechanist").len(), 1);
+        assert_eq!(world.npc_state("kaya"), NpcState::Transformed);
+        assert!(on_choice(&mut world, "mechanist_breakdown", "challenge_mechanist").is_empty());
+
+        // The state survives a save and load
+        let saved = ron::to_string(&world).unwrap();
+        let loaded: WorldState = ron::from_str(&saved).unwrap();
+        assert_eq!(loaded.npc_state("kaya"), NpcState::Transformed);
+
+        world.start_fresh();
+        assert_eq!(world.npc_state("kaya"), NpcState::Alive);
+    }
+}
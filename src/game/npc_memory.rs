@@ -0,0 +1,210 @@
+//! NPC Memory - What the people you meet think of you, and why
+//!
+//! Every NPC you deal with keeps an opinion of you and remembers the choices
+//! that shaped it. Opinions move when:
+//! - an encounter's consequences change them outright
+//! - you make a choice an NPC cares about, whether or not they were there
+//!   (word travels: Vera hears about it if you go to the Archivists)
+//!
+//! What they think decides how they meet you again. A returning NPC who
+//! thinks well of you greets you warmly, one who doesn't lets you know, and
+//! conversation options can wait on an NPC's opinion, or be refused
+//! outright by someone who has turned against you.
+//!
+//! Design: NPCs are keyed by id. Speakers with a voice of their own go by
+//! their NpcVoice id ("Old Scribe" and "Vera" are both "vera"); anyone else
+//! by the name they speak under. Like the EncounterTracker, the memory
+//! lasts the whole timeline: NPCs remember across runs.
+
+use std::collections::HashMap;
+
+use super::encounter_writing::AuthoredEncounter;
+
+/// Opinion at which an NPC thinks well of you
+pub const WARM_OPINION: i32 = 20;
+/// Opinion at which an NPC has turned against you
+pub const COLD_OPINION: i32 = -10;
+
+/// How an NPC feels about you
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disposition {
+    Warm,
+    Neutral,
+    Cold,
+}
+
+impl Disposition {
+    pub fn of(opinion: i32) -> Self {
+        if opinion >= WARM_OPINION {
+            Self::Warm
+        } else if opinion <= COLD_OPINION {
+            Self::Cold
+        } else {
+            Self::Neutral
+        }
+    }
+}
+
+/// The id an NPC is remembered under
+pub fn npc_id(name: &str) -> String {
+    match name {
+        "Old Scribe" | "Vera" | "Archivist Vera" => "vera",
+        "Mechanist Technician" | "Technician Kaya" | "Kaya" => "kaya",
+        "Voice in the Dark" | "Cipher" => "cipher",
+        other => other,
+    }
+    .to_string()
+}
+
+/// How choices sit with the NPCs who care about them:
+/// (encounter, choice, npc id, opinion change)
+pub static CHOICE_OPINIONS: [(&str, &str, &str, i32); 15] = [
+    ("haven_stranger_arrival", "help_stranger", "Stranger", 10),
+    ("haven_stranger_arrival", "refuse_stranger", "Stranger", -10),
+    ("haven_stranger_arrival", "test_stranger", "Stranger", -5),
+    ("haven_old_scribe", "ask_about_past", "vera", 5),
+    ("haven_old_scribe", "ask_about_archivists", "vera", 5),
+    ("haven_old_scribe", "offer_help", "vera", 15),
+    ("athenaeum_living_book", "accept_book", "The Living Book", 15),
+    ("athenaeum_living_book", "refuse_book", "The Living Book", -15),
+    ("mechanist_breakdown", "challenge_mechanist", "kaya", -15),
+    ("shadowwriter_offer", "accept_shadow", "cipher", 15),
+    ("shadowwriter_offer", "refuse_shadow", "cipher", -10),
+    ("first_archivist_meeting", "ask_third_grammar", "The First Archivist", 10),
+    ("first_archivist_meeting", "ask_third_grammar", "vera", -20),
+    ("first_archivist_meeting", "ask_spouse", "The First Archivist", 5),
+    ("first_archivist_meeting", "reject_past", "The First Archivist", -10),
+];
+
+/// What a returning NPC says first: (npc id, disposition, greeting)
+static GREETINGS: [(&str, Disposition, &str); 6] = [
+    ("vera", Disposition::Warm, "Vera's face creases into a smile before you speak. 'There you are. I kept your seat.'"),
+    ("vera", Disposition::Cold, "Vera doesn't look up. 'I warned you about them. You went anyway.'"),
+    ("kaya", Disposition::Warm, "Kaya waves a wrench at you. 'My favourite pair of steady hands.'"),
+    ("kaya", Disposition::Cold, "Kaya's hands go still on her tools. 'Come to tell me I'm wrong again?'"),
+    ("cipher", Disposition::Warm, "A soft laugh from the dark. 'Partner. I hoped it was you.'"),
+    ("cipher", Disposition::Cold, "'You again.' The voice in the dark sounds further away than it is."),
+];
+
+/// A choice an NPC remembers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remembered {
+    pub encounter: String,
+    pub choice: String,
+    pub change: i32,
+}
+
+/// Everyone's opinion of you, and what they remember
+#[derive(Debug, Clone, Default)]
+pub struct NpcMemory {
+    opinions: HashMap<String, i32>,
+    memories: HashMap<String, Vec<Remembered>>,
+}
+
+impl NpcMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn opinion(&self, npc: &str) -> i32 {
+        self.opinions.get(&npc_id(npc)).copied().unwrap_or(0)
+    }
+
+    pub fn disposition(&self, npc: &str) -> Disposition {
+        Disposition::of(self.opinion(npc))
+    }
+
+    /// What `npc` remembers of you, oldest first
+    pub fn memories(&self, npc: &str) -> &[Remembered] {
+        self.memories.get(&npc_id(npc)).map_or(&[], |m| m.as_slice())
+    }
+
+    fn remember(&mut self, npc: &str, encounter: &str, choice: &str, change: i32) {
+        let id = npc_id(npc);
+        *self.opinions.entry(id.clone()).or_insert(0) += change;
+        self.memories.entry(id).or_default().push(Remembered {
+            encounter: encounter.to_string(),
+            choice: choice.to_string(),
+            change,
+        });
+    }
+
+    /// Remember `choice` made in `encounter`. The encounter's own opinion
+    /// changes only land if the choice carried. Returns the opinions that
+    /// moved: (npc id, change).
+    pub fn on_choice(&mut self, encounter: &AuthoredEncounter, choice: &str, carried: bool) -> Vec<(String, i32)> {
+        let mut changes: Vec<(String, i32)> = CHOICE_OPINIONS.iter()
+            .filter(|(enc, ch, _, _)| *enc == encounter.id && *ch == choice)
+            .map(|(_, _, npc, change)| (npc.to_string(), *change))
+            .collect();
+        if carried {
+            changes.extend(encounter.consequences.npc_opinion_changes.iter().map(|(npc, change)| (npc_id(npc), *change)));
+        }
+        for (npc, change) in &changes {
+            self.remember(npc, &encounter.id, choice, *change);
+        }
+        changes
+    }
+
+    /// What `npc` says on meeting you again, if they remember you and feel
+    /// strongly either way
+    pub fn greeting(&self, npc: &str, name: &str) -> Option<String> {
+        if self.memories(npc).is_empty() {
+            return None;
+        }
+        let id = npc_id(npc);
+        let disposition = self.disposition(npc);
+        if let Some((_, _, line)) = GREETINGS.iter().find(|(n, d, _)| *n == id && *d == disposition) {
+            return Some(line.to_string());
+        }
+        match disposition {
+            Disposition::Warm => Some(format!("{} remembers you, and seems glad of it.", name)),
+            Disposition::Cold => Some(format!("{} remembers you. It shows.", name)),
+            Disposition::Neutral => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_writing::build_encounters;
+
+    #[test]
+    fn test_choice_opinions_name_real_choices() {
+        let encounters = build_encounters();
+        for (enc, choice, _, _) in &CHOICE_OPINIONS {
+            assert!(
+                encounters.get(*enc).is_some_and(|e| e.choices.iter().any(|c| c.id == *choice)),
+                "{}/{} is not a choice",
+                enc,
+                choice
+            );
+        }
+    }
+
+    #[test]
+    fn test_word_travels_and_returning_npcs_remember() {
+        let encounters = build_encounters();
+        let mut memory = NpcMemory::new();
+        assert_eq!(memory.greeting("Old Scribe", "Vera"), None);
+
+        memory.on_choice(&encounters["haven_old_scribe"], "offer_help", true);
+        assert_eq!(memory.opinion("Vera"), 15);
+        memory.on_choice(&encounters["haven_old_scribe"], "ask_about_past", true);
+        assert_eq!(memory.disposition("Old Scribe"), Disposition::Warm);
+        assert!(memory.greeting("Old Scribe", "Vera").unwrap().contains("kept your seat"));
+
+        // Vera wasn't at the Archivist's meeting, but she hears of it
+        memory.on_choice(&encounters["first_archivist_meeting"], "ask_third_grammar", false);
+        assert_eq!(memory.opinion("vera"), 0);
+        assert_eq!(memory.memories("vera").len(), 3);
+        assert_eq!(memory.greeting("vera", "Vera"), None);
+
+        // The encounter's own opinion changes need the choice to carry
+        let changes = memory.on_choice(&encounters["mechanist_breakdown"], "comfort_mechanist", false);
+        assert!(changes.is_empty());
+        memory.on_choice(&encounters["mechanist_breakdown"], "comfort_mechanist", true);
+        assert_eq!(memory.disposition("Mechanist Technician"), Disposition::Warm);
+    }
+}
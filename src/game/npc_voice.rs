@@ -0,0 +1,361 @@
+//! NPC Voices - Vera, Kaya and Cipher should never sound alike
+//!
+//! Faction voices give a whole faction its vocabulary. Named NPCs need more:
+//! a vocabulary tier, a sentence length they stay within, verbal tics, and a
+//! pool of catchphrases only they use. Procedural lines are filled from
+//! templates and then checked against the profile before anyone says them.
+//!
+//! Design: each profile turns a DialogueGuidelines rule into data -
+//! - "Different characters have different vocabularies and rhythms" -> tier, sentence length
+//! - "People repeat themselves. They have verbal tics." -> tics, catchphrases
+//! - "Everyone speaking in the same voice" -> no borrowing another NPC's catchphrase
+//! - "'As you know, Bob' exposition" -> banned phrases
+//!
+//! A line that breaks its profile is rerolled; if nothing fits, the NPC falls
+//! back on a catchphrase.
+
+use std::collections::HashMap;
+use rand::prelude::*;
+
+use super::voice_system::{DialogueContext, SentencePattern, VocabularySet};
+
+/// Rerolls before falling back on a catchphrase
+const MAX_ATTEMPTS: usize = 8;
+
+/// Exposition no one in this world says out loud
+const BANNED_PHRASES: [&str; 3] = ["as you know", "as you are aware", "as we both know"];
+
+/// How elevated an NPC's word choice is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VocabularyTier {
+    /// Short, common words
+    Plain,
+    /// Trade vocabulary; contractions and clipped speech
+    Technical,
+    /// Long words, full forms, never a contraction
+    Scholarly,
+}
+
+impl VocabularyTier {
+    /// Longest word (in letters) this tier will use
+    pub fn max_word_len(&self) -> Option<usize> {
+        match self {
+            Self::Plain => Some(8),
+            Self::Technical | Self::Scholarly => None,
+        }
+    }
+
+    pub fn allows_contractions(&self) -> bool {
+        !matches!(self, Self::Scholarly)
+    }
+}
+
+/// How one named NPC talks
+#[derive(Debug, Clone)]
+pub struct NpcVoice {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub tier: VocabularyTier,
+    /// Inclusive word count bounds for each sentence of a generated line
+    pub sentence_words: (usize, usize),
+    /// Small habits dropped into lines ("Hm.", "Right, so")
+    pub tics: Vec<String>,
+    /// Chance a line gets a tic
+    pub tic_chance: f32,
+    /// Lines only this NPC says
+    pub catchphrases: Vec<String>,
+    /// Chance a catchphrase follows a generated line
+    pub catchphrase_chance: f32,
+    pub vocabulary: VocabularySet,
+    pub patterns: Vec<SentencePattern>,
+    /// How they address the player
+    pub addresses: Vec<String>,
+}
+
+/// Why a line doesn't fit its speaker
+#[derive(Debug, Clone, PartialEq)]
+pub enum VoiceViolation {
+    SentenceTooShort { words: usize },
+    SentenceTooLong { words: usize },
+    WordAboveTier(String),
+    Contraction(String),
+    ForbiddenWord(String),
+    Exposition(&'static str),
+    BorrowedCatchphrase { owner: &'static str },
+    UnfilledSlot,
+}
+
+impl NpcVoice {
+    /// Check a line against this voice (and the other voices it must not borrow from)
+    pub fn violations(&self, line: &str, others: &[&NpcVoice]) -> Vec<VoiceViolation> {
+        let mut found = Vec::new();
+        let lower = line.to_lowercase();
+
+        if line.contains('{') {
+            found.push(VoiceViolation::UnfilledSlot);
+        }
+        for phrase in BANNED_PHRASES {
+            if lower.contains(phrase) {
+                found.push(VoiceViolation::Exposition(phrase));
+            }
+        }
+        for other in others.iter().filter(|o| o.id != self.id) {
+            if other.catchphrases.iter().any(|c| lower.contains(&c.to_lowercase())) {
+                found.push(VoiceViolation::BorrowedCatchphrase { owner: other.name });
+            }
+        }
+
+        let (min, max) = self.sentence_words;
+        for sentence in sentences(line) {
+            let words = sentence.split_whitespace().count();
+            if words < min {
+                found.push(VoiceViolation::SentenceTooShort { words });
+            } else if words > max {
+                found.push(VoiceViolation::SentenceTooLong { words });
+            }
+        }
+
+        for word in line.split_whitespace() {
+            let bare = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+            let lower = bare.to_lowercase();
+            if self.vocabulary.forbidden_words.contains(&lower) {
+                found.push(VoiceViolation::ForbiddenWord(bare.to_string()));
+            }
+            if !self.tier.allows_contractions() && bare.contains('\'') && !bare.ends_with("'s") {
+                found.push(VoiceViolation::Contraction(bare.to_string()));
+            }
+            if let Some(max_len) = self.tier.max_word_len() {
+                if bare.chars().filter(|c| c.is_alphabetic()).count() > max_len {
+                    found.push(VoiceViolation::WordAboveTier(bare.to_string()));
+                }
+            }
+        }
+        found
+    }
+
+    /// Fill one of this NPC's templates for the context
+    fn fill<R: Rng>(&self, context: DialogueContext, rng: &mut R) -> Option<String> {
+        let patterns: Vec<&SentencePattern> = self.patterns.iter()
+            .filter(|p| p.context.is_empty() || p.context.contains(&context))
+            .collect();
+        let pattern = patterns.choose_weighted(rng, |p| p.weight).ok()?;
+
+        let mut text = pattern.template.clone();
+        while let Some(start) = text.find('{') {
+            let end = text[start..].find('}')? + start;
+            let word = match &text[start + 1..end] {
+                "noun" => self.vocabulary.random_noun(rng),
+                "verb" => self.vocabulary.random_verb(rng),
+                "adjective" => self.vocabulary.random_adjective(rng),
+                "jargon" => self.vocabulary.random_jargon(rng),
+                "player" => self.addresses.choose(rng).map(|s| s.as_str()),
+                _ => None,
+            }?
+            .to_string();
+            text.replace_range(start..=end, &word);
+        }
+        Some(text)
+    }
+
+    /// A line in this NPC's voice. Template lines are checked against the
+    /// profile; decoration (tics, catchphrases) is added after checking.
+    pub fn generate_line<R: Rng>(&self, context: DialogueContext, others: &[&NpcVoice], rng: &mut R) -> String {
+        let core = (0..MAX_ATTEMPTS)
+            .filter_map(|_| self.fill(context, rng))
+            .find(|line| self.violations(line, others).is_empty());
+
+        let Some(mut line) = core else {
+            return self.catchphrases.choose(rng).cloned().unwrap_or_else(|| "...".to_string());
+        };
+        if rng.gen::<f32>() < self.tic_chance {
+            if let Some(tic) = self.tics.choose(rng) {
+                line = format!("{} {}", tic, line);
+            }
+        }
+        if rng.gen::<f32>() < self.catchphrase_chance {
+            if let Some(catchphrase) = self.catchphrases.choose(rng) {
+                line = format!("{} {}", line, catchphrase);
+            }
+        }
+        line
+    }
+}
+
+/// Split a line into sentences on . ! ?
+fn sentences(line: &str) -> Vec<&str> {
+    line.split(['.', '!', '?'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+fn pattern(name: &str, template: &str, context: Vec<DialogueContext>) -> SentencePattern {
+    SentencePattern {
+        name: name.to_string(),
+        template: template.to_string(),
+        weight: 1.0,
+        context,
+    }
+}
+
+/// The authored NPC voice profiles, keyed by id
+pub fn build_npc_voices() -> HashMap<&'static str, NpcVoice> {
+    use DialogueContext::*;
+    let mut voices = HashMap::new();
+
+    // Vera: two centuries among the shelves. Long, measured, never clipped.
+    voices.insert("vera", NpcVoice {
+        id: "vera",
+        name: "Archivist Vera",
+        tier: VocabularyTier::Scholarly,
+        sentence_words: (8, 22),
+        tics: words(&["Hm.", "Mm, yes.", "Let me see."]),
+        tic_chance: 0.35,
+        catchphrases: words(&["As it is written.", "The shelves remember, even when we do not."]),
+        catchphrase_chance: 0.25,
+        vocabulary: VocabularySet {
+            nouns: words(&["manuscript", "marginalia", "chronicle", "palimpsest", "folio", "lexicon", "archive"]),
+            verbs: words(&["transcribe", "annotate", "preserve", "collate", "illuminate"]),
+            adjectives: words(&["forgotten", "venerable", "unbound", "illegible", "irreplaceable"]),
+            adverbs: words(&["patiently", "carefully", "reverently"]),
+            jargon: words(&["the First Library", "the Lexicon Prime", "the Seventh Shelf"]),
+            forbidden_words: words(&["okay", "yeah", "stuff", "gonna"]).into_iter().collect(),
+        },
+        patterns: vec![
+            pattern("Recollection", "I once spent a decade learning to {verb} a single {adjective} {noun}, {player}.", vec![Greeting, Gossip, Neutral]),
+            pattern("Charge", "You must {verb} the {adjective} {noun} before the Unwriting reaches {jargon}.", vec![QuestGive, Warning]),
+            pattern("Gratitude", "The {noun} you returned was {adjective}, and I will {verb} it myself.", vec![QuestComplete, Gratitude]),
+            pattern("Caution", "Every {adjective} {noun} that is lost takes a little of {jargon} with it.", vec![Warning, Fear, Neutral]),
+            pattern("Parting", "Go carefully, {player}, and do not let any {noun} become {adjective}.", vec![Farewell]),
+        ],
+        addresses: words(&["child", "young reader", "my dear scribe"]),
+    });
+
+    // Kaya: a Mechanist technician. Quick, practical, interrupts herself.
+    voices.insert("kaya", NpcVoice {
+        id: "kaya",
+        name: "Technician Kaya",
+        tier: VocabularyTier::Technical,
+        sentence_words: (3, 12),
+        tics: words(&["Right, so.", "Okay.", "Hang on."]),
+        tic_chance: 0.5,
+        catchphrases: words(&["Gears don't lie. Mostly.", "I can fix that. Probably."]),
+        catchphrase_chance: 0.2,
+        vocabulary: VocabularySet {
+            nouns: words(&["flywheel", "escapement", "gear train", "spring", "regulator", "keyframe"]),
+            verbs: words(&["recalibrate", "strip", "rewire", "tune", "overclock"]),
+            adjectives: words(&["seized", "off-spec", "stripped", "misaligned", "humming"]),
+            adverbs: words(&["quickly", "roughly", "basically"]),
+            jargon: words(&["tolerance", "torque", "backlash", "duty cycle"]),
+            forbidden_words: words(&["thee", "thou", "alas", "verily"]).into_iter().collect(),
+        },
+        patterns: vec![
+            pattern("Diagnosis", "Your {noun} is {adjective}. I'll {verb} it.", vec![Greeting, Trading, Neutral]),
+            pattern("Job", "Need you to {verb} a {noun}. Watch the {jargon}.", vec![QuestGive]),
+            pattern("Done", "That {noun} you brought? Not {adjective} anymore. Nice work, {player}.", vec![QuestComplete, Gratitude]),
+            pattern("Alarm", "Don't touch that {noun}. It's running {adjective}.", vec![Warning, Fear]),
+            pattern("Gossip", "Somebody tried to {verb} the big {noun}. Blew the {jargon}.", vec![Gossip, Neutral]),
+            pattern("Later", "Gotta {verb} this {noun}. Catch you later, {player}.", vec![Farewell]),
+        ],
+        addresses: words(&["friend", "rookie", "you"]),
+    });
+
+    // Cipher: has watched for forty-seven years. Says little, answers with questions.
+    voices.insert("cipher", NpcVoice {
+        id: "cipher",
+        name: "Cipher",
+        tier: VocabularyTier::Plain,
+        sentence_words: (1, 7),
+        tics: words(&["...", "Hm.", "Listen."]),
+        tic_chance: 0.4,
+        catchphrases: words(&["Decode me, then.", "Or until the world ends."]),
+        catchphrase_chance: 0.3,
+        vocabulary: VocabularySet {
+            nouns: words(&["key", "lock", "name", "mask", "page", "door", "shadow"]),
+            verbs: words(&["hide", "read", "keep", "watch", "forget"]),
+            adjectives: words(&["hidden", "torn", "quiet", "false", "old"]),
+            adverbs: words(&["softly", "again"]),
+            jargon: words(&["the Seven", "the Silence", "the vault"]),
+            forbidden_words: words(&["friend", "please", "definitely"]).into_iter().collect(),
+        },
+        patterns: vec![
+            pattern("Question", "You {verb} the {noun}? Why?", vec![Greeting, Neutral, Gossip]),
+            pattern("Task", "Find the {adjective} {noun}. Tell no one.", vec![QuestGive]),
+            pattern("Nod", "So. You kept the {noun}.", vec![QuestComplete, Gratitude]),
+            pattern("Warning", "Some {noun} should stay {adjective}.", vec![Warning, Fear]),
+            pattern("Memory", "I was there. At {jargon}.", vec![Gossip, Neutral]),
+            pattern("Exit", "We will {verb} again, {player}.", vec![Farewell]),
+        ],
+        addresses: words(&["First Speaker", "stranger", "you"]),
+    });
+
+    voices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTEXTS: [DialogueContext; 6] = [
+        DialogueContext::Greeting,
+        DialogueContext::QuestGive,
+        DialogueContext::QuestComplete,
+        DialogueContext::Warning,
+        DialogueContext::Gossip,
+        DialogueContext::Farewell,
+    ];
+
+    #[test]
+    fn test_every_template_fits_its_voice() {
+        let voices = build_npc_voices();
+        let all: Vec<&NpcVoice> = voices.values().collect();
+        let mut rng = StdRng::seed_from_u64(7);
+        for voice in voices.values() {
+            for context in CONTEXTS {
+                for _ in 0..30 {
+                    let line = voice.fill(context, &mut rng).expect("no template");
+                    let problems = voice.violations(&line, &all);
+                    assert!(problems.is_empty(), "{}: '{}' {:?}", voice.name, line, problems);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_violations_are_caught() {
+        let voices = build_npc_voices();
+        let vera = &voices["vera"];
+        let cipher = &voices["cipher"];
+        let all: Vec<&NpcVoice> = voices.values().collect();
+
+        assert!(vera.violations("I don't think the manuscript was ever truly lost to us.", &all)
+            .contains(&VoiceViolation::Contraction("don't".to_string())));
+        assert!(cipher.violations("The manuscript is hidden.", &all)
+            .contains(&VoiceViolation::WordAboveTier("manuscript".to_string())));
+        assert!(cipher.violations("As you know, the door is old.", &all)
+            .contains(&VoiceViolation::Exposition("as you know")));
+        assert!(cipher.violations("The shelves remember, even when we do not.", &all)
+            .iter().any(|v| matches!(v, VoiceViolation::BorrowedCatchphrase { owner: "Archivist Vera" })));
+    }
+
+    #[test]
+    fn test_voices_have_distinct_rhythm() {
+        let voices = build_npc_voices();
+        let all: Vec<&NpcVoice> = voices.values().collect();
+        let mut rng = StdRng::seed_from_u64(11);
+        let mean_words = |voice: &NpcVoice, rng: &mut StdRng| {
+            let total: usize = (0..60)
+                .map(|_| voice.generate_line(DialogueContext::Neutral, &all, rng).split_whitespace().count())
+                .sum();
+            total as f32 / 60.0
+        };
+        let vera = mean_words(&voices["vera"], &mut rng);
+        let kaya = mean_words(&voices["kaya"], &mut rng);
+        let cipher = mean_words(&voices["cipher"], &mut rng);
+        assert!(vera > kaya && kaya > cipher, "vera {} kaya {} cipher {}", vera, kaya, cipher);
+    }
+}
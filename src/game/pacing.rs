@@ -10,6 +10,8 @@
 
 use serde::{Deserialize, Serialize};
 use rand::prelude::*;
+use super::run_rng::{self, RunRng, Stream};
+use super::memories;
 
 /// Controls narrative pacing throughout the run
 #[derive(Debug, Clone)]
@@ -23,7 +25,7 @@ pub struct PacingController {
     /// Pending beats to display
     pub pending_beats: Vec<PacingBeat>,
     /// Random generator
-    rng: ThreadRng,
+    rng: RunRng,
 }
 
 /// Current pacing phase
@@ -85,6 +87,20 @@ pub enum PacingBeat {
     },
 }
 
+impl PacingBeat {
+    /// The words of the beat, whatever its kind
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Atmosphere { text, .. }
+            | Self::Environmental { text, .. }
+            | Self::InternalThought { text }
+            | Self::OminousHint { text }
+            | Self::MemoryFlash { text, .. }
+            | Self::NPCGlimpse { text } => text,
+        }
+    }
+}
+
 impl Default for PacingController {
     fn default() -> Self {
         Self::new()
@@ -98,7 +114,7 @@ impl PacingController {
             combats_since_rest: 0,
             phase: PacingPhase::Exploration,
             pending_beats: Vec::new(),
-            rng: thread_rng(),
+            rng: run_rng::stream(Stream::Pacing),
         }
     }
     
@@ -159,6 +175,21 @@ impl PacingController {
     pub fn on_shop_enter(&mut self) {
         self.phase = PacingPhase::Interlude;
         self.tension = (self.tension - 10).max(0);
+        
+        let beats = [
+            PacingBeat::NPCGlimpse {
+                text: "The merchant hums while counting coins. For a moment, the dungeon feels far away.".into(),
+            },
+            PacingBeat::NPCGlimpse {
+                text: "A lantern burns steady over the merchant's wares. Nothing here wants you dead.".into(),
+            },
+            PacingBeat::InternalThought {
+                text: "Haggling over prices. Such an ordinary thing to do down here.".into(),
+            },
+        ];
+        if let Some(beat) = beats.choose(&mut self.rng) {
+            self.pending_beats.push(beat.clone());
+        }
     }
     
     /// Update pacing phase based on tension
@@ -199,6 +230,13 @@ impl PacingController {
     
     /// Queue an atmospheric beat for exploration
     fn queue_atmospheric(&mut self, floor: u32) {
+        // A memory of the chapter comes about as often as any other beat
+        if self.rng.gen_ratio(1, 3) {
+            if let Some(memory) = memories::flash(floor, &mut self.rng) {
+                self.pending_beats.push(memory);
+                return;
+            }
+        }
         let beat = match floor {
             1..=2 => {
                 let options = [
@@ -226,10 +264,6 @@ impl PacingController {
                         text: "Waterlogged books line the shelves. Knowledge, drowning.".into(),
                         examine_prompt: Some("Most are ruined. But here and there, a legible page...".into()),
                     },
-                    PacingBeat::MemoryFlash {
-                        text: "You've been here before. Haven't you? The feeling fades.".into(),
-                        lore_key: Some("archives_memory".into()),
-                    },
                 ];
                 options.choose(&mut self.rng).cloned()
             }
@@ -259,10 +293,6 @@ impl PacingController {
                         text: "A construct lies broken against the wall. Its eyes still glow, faintly.".into(),
                         examine_prompt: Some("MAINTENANCE OVERDUE. 4,327 YEARS. PLEASE WAIT.".into()),
                     },
-                    PacingBeat::MemoryFlash {
-                        text: "You built this. No. That's impossible. Isn't it?".into(),
-                        lore_key: Some("clockwork_memory".into()),
-                    },
                 ];
                 options.choose(&mut self.rng).cloned()
             }
@@ -275,10 +305,6 @@ impl PacingController {
                     PacingBeat::OminousHint {
                         text: "The void watches. It always watches.".into(),
                     },
-                    PacingBeat::MemoryFlash {
-                        text: "You remember darkness. Endless. Hungry. Home.".into(),
-                        lore_key: Some("void_memory".into()),
-                    },
                 ];
                 options.choose(&mut self.rng).cloned()
             }
@@ -288,10 +314,6 @@ impl PacingController {
                         text: "This is where it happened. The Sundering. You can feel it.".into(),
                         duration_ms: 3000,
                     },
-                    PacingBeat::MemoryFlash {
-                        text: "Malachar stood here. No. YOU stood here. The truth approaches.".into(),
-                        lore_key: Some("breach_memory".into()),
-                    },
                 ];
                 options.choose(&mut self.rng).cloned()
             }
@@ -322,6 +344,18 @@ impl PacingController {
         self.phase
     }
     
+    /// A drawn-out fight is about to turn: the warning to give before the
+    /// enemy enrages. It is said on the spot, so it never joins the queue.
+    pub fn enrage_warning(&mut self, enemy_name: &str) -> String {
+        self.tension = (self.tension + 10).min(100);
+        let options = [
+            format!("The {} is losing patience. Its breathing grows ragged.", enemy_name),
+            format!("Something changes in the {}'s stance. This has gone on too long.", enemy_name),
+            format!("The {} shudders with building fury. End this - soon.", enemy_name),
+        ];
+        options.choose(&mut self.rng).cloned().unwrap_or_default()
+    }
+
     /// Force a specific beat (for scripted moments)
     pub fn queue_beat(&mut self, beat: PacingBeat) {
         self.pending_beats.push(beat);
@@ -367,4 +401,14 @@ mod tests {
         // Should have a breather beat
         assert!(pacing.has_pending() || pacing.combats_since_rest == 0);
     }
+    
+    #[test]
+    fn test_shop_is_an_interlude() {
+        let mut pacing = PacingController::new();
+        pacing.on_combat_start(true);
+        pacing.on_shop_enter();
+        assert_eq!(pacing.get_phase(), PacingPhase::Interlude);
+        assert_eq!(pacing.tension, 20);
+        assert!(pacing.pop_beat().is_some());
+    }
 }
@@ -0,0 +1,155 @@
+//! Faction Patrons - Pledge your run to a faction for its signature mechanic
+//!
+//! Before a run begins the player may pledge to one patron:
+//! - Scribes (Mages Guild): the first slip in each word is forgiven,
+//!   but words typed below a strict WPM floor do nothing
+//! - Mechanists (Temple of Dawn): damage scales with speed, but the body
+//!   is fragile - max HP is cut for the run
+//! - Naturalists (Rangers of the Wild): words typed with a steady rhythm
+//!   heal you
+//!
+//! A pledge raises standing with the patron's faction (rivals notice) and
+//! names the ending a won run gets when no mystery ending claims it. The
+//! Final Rest and the Dark Ascension stay open to a pledged run; only the
+//! Third Grammar needs a run that owes no one (see `endings`).
+//!
+//! Design: the numbers live here as pure functions; CombatState asks them
+//! what a keystroke or finished word is worth under the pledge.
+
+use serde::{Deserialize, Serialize};
+
+use super::narrative::Faction;
+
+/// Standing gained with the patron's faction when pledging
+pub const PLEDGE_STANDING: i32 = 15;
+/// Scribes: words typed slower than this deal no damage
+pub const SCRIBE_WPM_FLOOR: f32 = 35.0;
+/// Scribes: mistakes forgiven per word
+pub const SCRIBE_FORGIVENESS: u32 = 1;
+/// Mechanists: fraction of max HP kept for the run
+pub const MECHANIST_HP_FRACTION: f32 = 0.7;
+/// Naturalists: keystroke intervals needed to judge rhythm
+const MIN_INTERVALS: usize = 4;
+/// Naturalists: largest spread of keystroke timing (std dev / mean) that counts as steady
+const STEADY_SPREAD: f32 = 0.35;
+/// Naturalists: most HP one steady word can restore
+const MAX_RHYTHM_HEAL: i32 = 8;
+
+/// Ending reached by a run with no patron
+pub const UNBOUND_ENDING: (&str, &str) = ("unbound", "The Unbound Word");
+/// The true ending, only reachable by a run that owes no one
+pub const THIRD_GRAMMAR_ENDING: (&str, &str) = ("third_grammar", "The Third Grammar");
+
+/// A faction patron sponsoring this run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Patron {
+    Scribes,
+    Mechanists,
+    Naturalists,
+}
+
+impl Patron {
+    pub const ALL: [Patron; 3] = [Patron::Scribes, Patron::Mechanists, Patron::Naturalists];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Scribes => "The Scribes",
+            Self::Mechanists => "The Mechanists",
+            Self::Naturalists => "The Naturalists",
+        }
+    }
+
+    /// The faction that sponsors this patron's pact
+    pub fn faction(&self) -> Faction {
+        match self {
+            Self::Scribes => Faction::MagesGuild,
+            Self::Mechanists => Faction::TempleOfDawn,
+            Self::Naturalists => Faction::RangersOfTheWild,
+        }
+    }
+
+    /// One-line summary of the signature mechanic
+    pub fn mechanic(&self) -> &'static str {
+        match self {
+            Self::Scribes => "One slip per word forgiven; words under 35 WPM deal nothing",
+            Self::Mechanists => "Damage scales with speed; max HP cut by 30%",
+            Self::Naturalists => "Words typed with a steady rhythm heal you",
+        }
+    }
+
+    /// Ending this pledge leads to: (id, title)
+    pub fn ending(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Scribes => ("scribes_archive", "The Ordered Archive"),
+            Self::Mechanists => ("mechanists_engine", "The Tireless Engine"),
+            Self::Naturalists => ("naturalists_grove", "The Green Silence"),
+        }
+    }
+
+    /// Next choice on the class screen: none → each patron → none
+    pub fn cycle(current: Option<Patron>) -> Option<Patron> {
+        match current {
+            None => Some(Self::ALL[0]),
+            Some(p) => Self::ALL.iter().position(|x| *x == p).and_then(|i| Self::ALL.get(i + 1)).copied(),
+        }
+    }
+}
+
+/// Mechanists: 1.0x at 40 WPM, +1% per WPM above it, capped at 2x
+pub fn speed_mult(wpm: f32) -> f32 {
+    (1.0 + (wpm - 40.0) / 100.0).clamp(1.0, 2.0)
+}
+
+/// Whether a word's keystroke intervals (ms) kept a steady rhythm
+pub fn is_steady(intervals_ms: &[f32]) -> bool {
+    if intervals_ms.len() < MIN_INTERVALS {
+        return false;
+    }
+    let n = intervals_ms.len() as f32;
+    let mean = intervals_ms.iter().sum::<f32>() / n;
+    if mean <= 0.0 {
+        return false;
+    }
+    let variance = intervals_ms.iter().map(|i| (i - mean).powi(2)).sum::<f32>() / n;
+    variance.sqrt() / mean <= STEADY_SPREAD
+}
+
+/// Naturalists: HP restored for a word typed with these keystroke intervals (ms)
+pub fn rhythm_heal(intervals_ms: &[f32]) -> i32 {
+    if !is_steady(intervals_ms) {
+        return 0;
+    }
+    (2 + intervals_ms.len() as i32 / 2).min(MAX_RHYTHM_HEAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_through_patrons() {
+        let mut choice = None;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            choice = Patron::cycle(choice);
+            seen.push(choice);
+        }
+        assert_eq!(seen, vec![Some(Patron::Scribes), Some(Patron::Mechanists), Some(Patron::Naturalists), None]);
+    }
+
+    #[test]
+    fn test_rhythm_heal() {
+        assert!(rhythm_heal(&[120.0, 125.0, 118.0, 122.0, 121.0]) > 0);
+        // Bursty typing is not a rhythm
+        assert_eq!(rhythm_heal(&[50.0, 400.0, 60.0, 350.0, 40.0]), 0);
+        assert_eq!(rhythm_heal(&[100.0, 100.0]), 0);
+        assert_eq!(rhythm_heal(&[100.0; 40]), MAX_RHYTHM_HEAL);
+    }
+
+    #[test]
+    fn test_speed_mult_rises_past_forty_wpm() {
+        assert_eq!(speed_mult(30.0), 1.0);
+        assert!((speed_mult(90.0) - 1.5).abs() < 1e-6);
+        assert_eq!(speed_mult(300.0), 2.0);
+    }
+}
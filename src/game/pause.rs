@@ -0,0 +1,146 @@
+//! Pause - Stop a fight on purpose, and maybe walk away from the run
+//!
+//! The pause key (`F4` by default, rebindable) stops a fight where it
+//! stands and opens the pause menu over it:
+//! - resume: back to the word, with every window as long as it was
+//! - settings: the settings screen, coming back to the pause menu
+//! - run overview: the dashboard of the run so far
+//! - abandon run: after a second Enter, the run ends as a death would
+//!
+//! Design: a fight's clocks come in two kinds. The word timer, the enrage
+//! clock and a hazard's fuse are counted down by ticks, so a fight that is
+//! not ticked stands still. The riposte and stagger windows, the study of
+//! an inscription and the rhythm of the keys are deadlines and stamps on
+//! the wall clock, which runs on through a pause. Pausing freezes them all
+//! into a `FrozenClock`, as milliseconds left or milliseconds ago, with the
+//! damage waiting to land; resuming lays them back out from the moment the
+//! fight resumes. Nothing in it is an `Instant`, so it serializes whole.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// What the pause menu offers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseOption {
+    Resume,
+    Settings,
+    Overview,
+    Abandon,
+}
+
+impl PauseOption {
+    pub const ALL: [PauseOption; 4] = [Self::Resume, Self::Settings, Self::Overview, Self::Abandon];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Resume => "Resume",
+            Self::Settings => "Settings",
+            Self::Overview => "Run overview",
+            Self::Abandon => "Abandon run",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Resume => "Back to the word, with every window as you left it",
+            Self::Settings => "Change a setting; the difficulty holds until the next fight",
+            Self::Overview => "Speed, accuracy and damage, word by word, this run",
+            Self::Abandon => "End the run here, as if you had fallen",
+        }
+    }
+}
+
+/// A fight's clocks and pending damage, stopped
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrozenClock {
+    /// Seconds left on the word timer
+    pub time_remaining: f32,
+    /// Seconds the word timer is still held by a bookmark
+    pub timer_hold: f32,
+    /// Fight time counted toward the enrage
+    pub enrage_secs: f32,
+    /// Seconds left to answer a hazard, if one is waiting
+    pub hazard_secs: Option<f32>,
+    /// Milliseconds left of the riposte window
+    pub riposte_ms: Option<u64>,
+    /// Milliseconds left of the boss's stagger
+    pub stagger_ms: Option<u64>,
+    /// Milliseconds left to study an inscription
+    pub study_ms: Option<u64>,
+    /// How long before the pause each key of the word landed
+    pub key_ages_ms: Vec<u64>,
+    /// How long before the pause the replay's open word began
+    pub replay_word_ms: Option<u64>,
+    /// How long the fight had run
+    pub fight_ms: u64,
+    /// Healing earned by rhythm, not yet applied
+    pub pending_heal: i32,
+    /// Recoil the Aggressive stance owes
+    pub pending_recoil: i32,
+    /// Hazard damage not yet dealt
+    pub pending_hazard_damage: i32,
+}
+
+/// Milliseconds from `now` until `deadline`, if it is still ahead
+pub fn ms_until(deadline: Option<Instant>, now: Instant) -> Option<u64> {
+    deadline.filter(|d| *d > now).map(|d| (d - now).as_millis() as u64)
+}
+
+/// Milliseconds from `then` until `now`
+pub fn ms_since(then: Instant, now: Instant) -> u64 {
+    now.saturating_duration_since(then).as_millis() as u64
+}
+
+/// The instant `ms` after `now`
+pub fn after(now: Instant, ms: u64) -> Instant {
+    now + Duration::from_millis(ms)
+}
+
+/// The instant `ms` before `now`, or `now` if the clock does not reach back
+/// that far
+pub fn before(now: Instant, ms: u64) -> Instant {
+    now.checked_sub(Duration::from_millis(ms)).unwrap_or(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadlines_and_stamps_come_back_as_far_from_the_resume_as_from_the_pause() {
+        let paused = Instant::now() + Duration::from_secs(10);
+        let riposte = ms_until(Some(paused + Duration::from_millis(400)), paused);
+        let key = ms_since(paused - Duration::from_millis(250), paused);
+        assert_eq!(riposte, Some(400));
+        assert_eq!(key, 250);
+        // A window already shut stays shut
+        assert_eq!(ms_until(Some(paused - Duration::from_millis(1)), paused), None);
+        assert_eq!(ms_until(None, paused), None);
+
+        // However long the pause was
+        let resumed = paused + Duration::from_secs(90);
+        assert_eq!(after(resumed, 400) - resumed, Duration::from_millis(400));
+        assert_eq!(resumed - before(resumed, 250), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_a_frozen_clock_round_trips_with_its_pending_damage() {
+        let clock = FrozenClock {
+            time_remaining: 3.25,
+            timer_hold: 1.5,
+            enrage_secs: 41.0,
+            hazard_secs: Some(2.0),
+            riposte_ms: Some(380),
+            stagger_ms: None,
+            study_ms: Some(1200),
+            key_ages_ms: vec![900, 640, 410, 150],
+            replay_word_ms: Some(900),
+            fight_ms: 52_000,
+            pending_heal: 4,
+            pending_recoil: 3,
+            pending_hazard_damage: 7,
+        };
+        let text = ron::to_string(&clock).unwrap();
+        assert_eq!(ron::from_str::<FrozenClock>(&text).unwrap(), clock);
+    }
+}
@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::items::Item;
+use crate::data::items::EquipmentSlot;
 use super::spells::Spell;
+use super::curses::Curse;
+use super::leveling;
 
 /// Character classes with unique abilities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,6 +20,9 @@ pub enum Class {
 }
 
 impl Class {
+    /// Every class, in the order the class screen lists them
+    pub const ALL: [Class; 5] = [Class::Wordsmith, Class::Scribe, Class::Spellweaver, Class::Barbarian, Class::Trickster];
+
     pub fn name(&self) -> &'static str {
         match self {
             Class::Wordsmith => "Wordsmith",
@@ -162,6 +168,24 @@ pub struct Player {
     // Equipment & Inventory
     pub inventory: Vec<Item>,
     pub equipped: HashMap<String, Item>,
+    /// Typing gear worn per slot (equipment ids from the item database)
+    #[serde(default)]
+    pub gear: HashMap<EquipmentSlot, String>,
+    /// Lexicons (word lists) learned from merchants this run, by id
+    #[serde(default)]
+    pub lexicons: Vec<String>,
+    /// Lore artifacts carried this run, by name
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+    /// Cursed loot carried this run
+    #[serde(default)]
+    pub curses: Vec<Curse>,
+    /// Rhythm window (ms) bought with level-ups
+    #[serde(default)]
+    pub rhythm_bonus_ms: u32,
+    /// Crit chance bought with level-ups
+    #[serde(default)]
+    pub crit_bonus: f32,
     pub known_spells: Vec<Spell>,
     pub active_spell: Option<usize>,
     
@@ -217,6 +241,12 @@ impl Player {
             best_combo: 0,
             inventory: Vec::new(),
             equipped: HashMap::new(),
+            gear: HashMap::new(),
+            lexicons: Vec::new(),
+            artifacts: Vec::new(),
+            curses: Vec::new(),
+            rhythm_bonus_ms: 0,
+            crit_bonus: 0.0,
             known_spells: vec![Spell::basic_attack()],
             active_spell: Some(0),
             buffs: Vec::new(),
@@ -224,69 +254,48 @@ impl Player {
         }
     }
 
+    /// Wear a piece of gear, returning the id it replaced in that slot
+    pub fn wear_gear(&mut self, slot: EquipmentSlot, id: String) -> Option<String> {
+        self.gear.insert(slot, id)
+    }
+
     pub fn experience_to_next_level(&self) -> u64 {
-        // Earthbound-style exponential curve
-        (self.level as u64).pow(2) * 100
+        leveling::xp_to_next(self.level)
     }
 
-    pub fn gain_experience(&mut self, amount: u64) -> bool {
+    /// Add XP, returning how many levels it earned
+    pub fn gain_experience(&mut self, amount: u64) -> u32 {
         self.experience += amount;
         
-        let needed = self.experience_to_next_level();
-        if self.experience >= needed {
-            self.experience -= needed;
+        let mut levels = 0;
+        while self.experience >= self.experience_to_next_level() {
+            self.experience -= self.experience_to_next_level();
             self.level_up();
-            true
-        } else {
-            false
+            levels += 1;
         }
+        levels
     }
 
     pub fn level_up(&mut self) {
         self.level += 1;
         
         // Stat gains based on class
-        match self.class {
-            Class::Wordsmith => {
-                self.stats.strength += 2;
-                self.stats.intellect += 2;
-                self.stats.vitality += 2;
-                self.stats.dexterity += 2;
-                self.stats.luck += 2;
-            }
-            Class::Scribe => {
-                self.stats.strength += 1;
-                self.stats.intellect += 3;
-                self.stats.vitality += 2;
-                self.stats.dexterity += 3;
-                self.stats.luck += 1;
-            }
-            Class::Spellweaver => {
-                self.stats.strength += 1;
-                self.stats.intellect += 4;
-                self.stats.vitality += 1;
-                self.stats.dexterity += 2;
-                self.stats.luck += 2;
-            }
-            Class::Barbarian => {
-                self.stats.strength += 4;
-                self.stats.intellect += 1;
-                self.stats.vitality += 3;
-                self.stats.dexterity += 1;
-                self.stats.luck += 1;
-            }
-            Class::Trickster => {
-                self.stats.strength += 2;
-                self.stats.intellect += 2;
-                self.stats.vitality += 1;
-                self.stats.dexterity += 2;
-                self.stats.luck += 3;
-            }
-        }
+        let (strength, intellect, vitality, dexterity, luck) = match self.class {
+            Class::Wordsmith => (2, 2, 2, 2, 2),
+            Class::Scribe => (1, 3, 2, 3, 1),
+            Class::Spellweaver => (1, 4, 1, 2, 2),
+            Class::Barbarian => (4, 1, 3, 1, 1),
+            Class::Trickster => (2, 2, 1, 2, 3),
+        };
+        self.stats.strength += strength;
+        self.stats.intellect += intellect;
+        self.stats.vitality += vitality;
+        self.stats.dexterity += dexterity;
+        self.stats.luck += luck;
         
-        // Recalculate max HP/MP
-        self.max_hp = self.class.base_hp() + (self.stats.vitality * 8);
-        self.max_mp = self.class.base_mp() + (self.stats.intellect * 3);
+        // Grow max HP/MP by the gains, keeping whatever else raised them
+        self.max_hp += vitality * 8;
+        self.max_mp += intellect * 3;
         
         // Full heal on level up!
         self.hp = self.max_hp;
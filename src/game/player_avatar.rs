@@ -20,6 +20,8 @@ pub struct PlayerAvatar {
     pub animation_timer: u32,
     /// Current health percentage (0-100)
     pub health_percent: u32,
+    /// Stamina is critical
+    pub winded: bool,
 }
 
 /// Player class for different visuals
@@ -40,6 +42,18 @@ impl PlayerClass {
             Self::Chronicler => "Chronicler",
         }
     }
+
+    /// The avatar (and ability set) a character class fights as
+    pub fn from_class(class: &super::player::Class) -> Self {
+        use super::player::Class;
+        match class {
+            Class::Wordsmith => Self::Wordsmith,
+            Class::Scribe => Self::Chronicler,
+            Class::Spellweaver => Self::Codebreaker,
+            Class::Barbarian => Self::Wordsmith,
+            Class::Trickster => Self::Freelancer,
+        }
+    }
 }
 
 /// Current animation state
@@ -52,6 +66,8 @@ pub enum AvatarState {
     Victory,
     Wounded,
     Defending,
+    Riposte,
+    Winded,
 }
 
 impl Default for PlayerAvatar {
@@ -67,6 +83,7 @@ impl PlayerAvatar {
             state: AvatarState::Idle,
             animation_timer: 0,
             health_percent: 100,
+            winded: false,
         }
     }
     
@@ -145,6 +162,24 @@ impl PlayerAvatar {
                 "   /  \\  ",
                 "  /    \\ ",
             ],
+            AvatarState::Winded => vec![
+                "  ,--o--,  ",
+                "  | /\\ |~ ",
+                " /| || |\\",
+                " |      | ",
+                "  ------  ",
+                "   |  |  ",
+                "  _|  |_ ",
+            ],
+            AvatarState::Riposte => vec![
+                "  ,--o--,  ",
+                " [| /\\ |==>",
+                " [| || |  ",
+                " [|######| ",
+                " [ ======  ",
+                "   /  \\  ",
+                "  /    \\ ",
+            ],
         }
     }
     
@@ -271,6 +306,12 @@ impl PlayerAvatar {
         self.animation_timer = 1000;
     }
     
+    /// Trigger riposte animation (parry and strike back)
+    pub fn on_riposte(&mut self) {
+        self.state = AvatarState::Riposte;
+        self.animation_timer = 600;
+    }
+    
     /// Trigger defending animation
     pub fn on_defend(&mut self) {
         self.state = AvatarState::Defending;
@@ -280,22 +321,38 @@ impl PlayerAvatar {
     /// Update health and potentially set wounded state
     pub fn update_health(&mut self, percent: u32) {
         self.health_percent = percent;
-        if percent < 25 && self.state == AvatarState::Idle {
+        if percent < 25 && matches!(self.state, AvatarState::Idle | AvatarState::Winded) {
             self.state = AvatarState::Wounded;
         }
     }
     
+    /// Stamina went critical (or recovered); winded shows when nothing
+    /// else is playing
+    pub fn set_winded(&mut self, winded: bool) {
+        self.winded = winded;
+        if self.animation_timer == 0 {
+            self.state = self.resting_state();
+        }
+    }
+    
+    /// The state to settle back into once an animation ends
+    fn resting_state(&self) -> AvatarState {
+        if self.health_percent < 25 {
+            AvatarState::Wounded
+        } else if self.winded {
+            AvatarState::Winded
+        } else {
+            AvatarState::Idle
+        }
+    }
+    
     /// Update animation timer
     pub fn update(&mut self, delta_ms: u32) {
         if self.animation_timer > 0 {
             self.animation_timer = self.animation_timer.saturating_sub(delta_ms);
             
             if self.animation_timer == 0 {
-                self.state = if self.health_percent < 25 {
-                    AvatarState::Wounded
-                } else {
-                    AvatarState::Idle
-                };
+                self.state = self.resting_state();
             }
         }
     }
@@ -315,6 +372,8 @@ impl PlayerAvatar {
             AvatarState::Victory => "Victory!",
             AvatarState::Wounded => "Wounded...",
             AvatarState::Defending => "Defending!",
+            AvatarState::Riposte => "RIPOSTE!",
+            AvatarState::Winded => "Winded...",
         }
     }
     
@@ -0,0 +1,210 @@
+//! Prompt Corruption - The Unwriting reaches into the words you type
+//!
+//! Corrupted enemies can interfere with a prompt while it is being typed:
+//! - Scramble: two untyped letters trade places
+//! - Reverse: the rest of the current word runs backwards
+//! - Unwrite: an untyped letter is erased from existence
+//!
+//! Only the untyped remainder is ever touched, so progress is never lost -
+//! the player just has to read again and adapt.
+//!
+//! Design: mutations are pure functions over grapheme clusters; CombatState
+//! decides when to roll for one and remembers which span was changed so the
+//! typing widget can mark it.
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::enemy::Enemy;
+use super::narrative_seed::TypingModifier;
+use super::typing_impact::graphemes;
+
+/// Chance a prompt is interfered with when fighting a corrupted enemy
+const CORRUPTED_PROMPT_CHANCE: f32 = 0.35;
+/// Bosses are steeped in the Unwriting
+const BOSS_PROMPT_CHANCE: f32 = 0.5;
+/// Untyped graphemes that must remain before corruption can strike
+const MIN_REMAINING: usize = 3;
+
+/// One way the corruption can mutate a prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interference {
+    Scramble,
+    Reverse,
+    Unwrite,
+}
+
+impl Interference {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Scramble => "Scrambled",
+            Self::Reverse => "Reversed",
+            Self::Unwrite => "Unwritten",
+        }
+    }
+
+    /// Battle log line when this interference strikes
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Scramble => "The letters squirm and trade places!",
+            Self::Reverse => "The word turns its back on you!",
+            Self::Unwrite => "A letter is unwritten before your eyes!",
+        }
+    }
+}
+
+/// The part of the prompt a mutation changed, as a grapheme range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CorruptedSpan {
+    pub kind: Interference,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl CorruptedSpan {
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.start && index < self.end
+    }
+}
+
+/// Whether an enemy carries the Unwriting (bosses always do)
+pub fn is_corrupted(enemy: &Enemy) -> bool {
+    enemy.is_boss || enemy.typing_theme == "corruption"
+}
+
+/// Per-prompt interference chance for this enemy under this run's corruption
+pub fn prompt_chance(enemy: &Enemy, modifier: Option<&TypingModifier>) -> f32 {
+    let base = if enemy.is_boss {
+        BOSS_PROMPT_CHANCE
+    } else if is_corrupted(enemy) {
+        CORRUPTED_PROMPT_CHANCE
+    } else {
+        0.0
+    };
+    match modifier {
+        Some(TypingModifier::WordsScramble { frequency }) => (base + frequency).min(1.0),
+        _ => base,
+    }
+}
+
+/// Pick which interference strikes. A run cursed only with scrambling
+/// (no corrupted enemy) can only scramble.
+pub fn pick<R: Rng>(corrupted_enemy: bool, rng: &mut R) -> Interference {
+    if !corrupted_enemy {
+        return Interference::Scramble;
+    }
+    *[Interference::Scramble, Interference::Reverse, Interference::Unwrite]
+        .choose(rng)
+        .unwrap_or(&Interference::Scramble)
+}
+
+/// Mutate the untyped part of `prompt` (after `typed` graphemes).
+/// Returns the new prompt and the changed span, or None if nothing could change.
+pub fn apply<R: Rng>(
+    kind: Interference,
+    prompt: &str,
+    typed: usize,
+    rng: &mut R,
+) -> Option<(String, CorruptedSpan)> {
+    let mut parts: Vec<&str> = graphemes(prompt);
+    if parts.len() < typed + MIN_REMAINING {
+        return None;
+    }
+
+    // Only letters inside the word being typed (or the next one) are touched
+    let start = (typed..parts.len()).find(|&i| !parts[i].trim().is_empty())?;
+    let end = (start..parts.len())
+        .find(|&i| parts[i].trim().is_empty())
+        .unwrap_or(parts.len());
+
+    let span = match kind {
+        Interference::Scramble => {
+            if end - start < 2 {
+                return None;
+            }
+            let i = rng.gen_range(start..end - 1);
+            if parts[i] == parts[i + 1] {
+                return None;
+            }
+            parts.swap(i, i + 1);
+            CorruptedSpan { kind, start: i, end: i + 2 }
+        }
+        Interference::Reverse => {
+            if end - start < 2 || parts[start..end].iter().eq(parts[start..end].iter().rev()) {
+                return None;
+            }
+            parts[start..end].reverse();
+            CorruptedSpan { kind, start, end }
+        }
+        Interference::Unwrite => {
+            // Never erase a whole word
+            if end - start < 2 {
+                return None;
+            }
+            let i = rng.gen_range(start..end);
+            parts.remove(i);
+            // Mark the letter that slid into the gap, or the word's new
+            // last letter if the gap is at its end
+            let mark = i.min(end - 2);
+            CorruptedSpan { kind, start: mark, end: mark + 1 }
+        }
+    };
+    Some((parts.concat(), span))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_prefix_untouched() {
+        let mut rng = rand::thread_rng();
+        for kind in [Interference::Scramble, Interference::Reverse, Interference::Unwrite] {
+            for _ in 0..20 {
+                if let Some((mutated, span)) = apply(kind, "shadow blade", 3, &mut rng) {
+                    assert!(mutated.starts_with("sha"), "{:?} touched typed text: {}", kind, mutated);
+                    assert!(span.start >= 3);
+                    assert!(mutated.ends_with(" blade"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutations() {
+        let mut rng = rand::thread_rng();
+        let (reversed, span) = apply(Interference::Reverse, "ember", 1, &mut rng).unwrap();
+        assert_eq!(reversed, "erebm");
+        assert_eq!((span.start, span.end), (1, 5));
+
+        let (unwritten, _) = apply(Interference::Unwrite, "ember", 1, &mut rng).unwrap();
+        assert_eq!(unwritten.chars().count(), 4);
+        assert!(unwritten.starts_with('e'));
+
+        // Too little left to corrupt
+        assert!(apply(Interference::Scramble, "ember", 3, &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_graphemes_stay_whole() {
+        let mut rng = rand::thread_rng();
+        let (reversed, _) = apply(Interference::Reverse, "xcafe\u{301}", 1, &mut rng).unwrap();
+        assert_eq!(reversed, "xe\u{301}fac");
+    }
+
+    #[test]
+    fn test_unwriting_the_last_letter_still_marks_the_word() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut erased_last = false;
+        for _ in 0..50 {
+            let (unwritten, span) = apply(Interference::Unwrite, "ab c", 0, &mut rng).unwrap();
+            assert_eq!(span.end, span.start + 1);
+            if unwritten == "a c" {
+                assert_eq!((span.start, span.end), (0, 1));
+                erased_last = true;
+            }
+        }
+        assert!(erased_last);
+    }
+}
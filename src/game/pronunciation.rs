@@ -0,0 +1,201 @@
+//! Pronunciation - Hear the word before you type it
+//!
+//! Turns foreign-language word packs into a listening drill. With the
+//! `audio` feature enabled, each new combat prompt is spoken before the
+//! player types it:
+//! - a bundled recording (`<recordings_dir>/<word>.wav|ogg|mp3`) if one exists
+//! - otherwise the system TTS (`espeak-ng`/`espeak` on Linux, `say` on macOS)
+//!
+//! Dictation mode hides the prompt text entirely - listen and type.
+//!
+//! Design: playback shells out to whatever player the OS already has, so
+//! the game gains no audio dependencies. Without the feature every call
+//! is a silent no-op and dictation stays off (you can't type what you
+//! can't hear).
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Recording extensions tried, in order
+const RECORDING_EXTENSIONS: [&str; 3] = ["wav", "ogg", "mp3"];
+
+/// Pronunciation settings (lives in `AudioConfig`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PronunciationConfig {
+    /// Speak each new prompt aloud
+    pub enabled: bool,
+    /// Hide the prompt text - type only what you hear
+    pub dictation: bool,
+    /// TTS voice / language code (e.g. "fr", "de", "ja")
+    pub voice: Option<String>,
+    /// Directory of bundled recordings named after the prompt text
+    pub recordings_dir: Option<PathBuf>,
+}
+
+/// Speaks prompts and remembers the last one so it isn't repeated
+#[derive(Debug, Clone)]
+pub struct Pronouncer {
+    config: PronunciationConfig,
+    last_spoken: Option<String>,
+}
+
+impl Pronouncer {
+    pub fn new(config: &PronunciationConfig) -> Self {
+        Self {
+            config: config.clone(),
+            last_spoken: None,
+        }
+    }
+
+    /// Whether this build can play audio at all
+    pub fn available() -> bool {
+        cfg!(feature = "audio")
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        Self::available() && (self.config.enabled || self.config.dictation)
+    }
+
+    /// Whether the prompt text should be hidden from the player
+    pub fn is_dictation(&self) -> bool {
+        Self::available() && self.config.dictation
+    }
+
+    /// Speak `text` if it differs from the last prompt spoken
+    pub fn announce(&mut self, text: &str, muted: bool) {
+        if self.last_spoken.as_deref() == Some(text) {
+            return;
+        }
+        self.last_spoken = Some(text.to_string());
+        if !muted {
+            self.speak(text);
+        }
+    }
+
+    /// Speak the current prompt again on request
+    pub fn replay(&mut self) {
+        if let Some(text) = self.last_spoken.clone() {
+            self.speak(&text);
+        }
+    }
+
+    /// Forget the last prompt (combat ended)
+    pub fn reset(&mut self) {
+        self.last_spoken = None;
+    }
+
+    /// Bundled recording for a prompt, if one exists
+    pub fn recording_for(&self, text: &str) -> Option<PathBuf> {
+        let dir = self.config.recordings_dir.as_ref()?;
+        let stem = recording_stem(text);
+        RECORDING_EXTENSIONS.iter()
+            .map(|ext| dir.join(format!("{}.{}", stem, ext)))
+            .find(|p| p.is_file())
+    }
+
+    /// Fire-and-forget playback; failures (no TTS installed) are silent
+    fn speak(&self, text: &str) {
+        #[cfg(feature = "audio")]
+        if self.is_enabled() {
+            match self.recording_for(text) {
+                Some(path) => play_file(&path),
+                None => speak_tts(text, self.config.voice.as_deref()),
+            }
+        }
+    }
+}
+
+/// File name a recording is expected under: lowercase, spaces to underscores
+pub fn recording_stem(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_whitespace() || c == '/' || c == '\\' { '_' } else { c })
+        .collect()
+}
+
+#[cfg(feature = "audio")]
+fn spawn_first(candidates: &[(&str, Vec<String>)]) {
+    use std::process::{Command, Stdio};
+    for (program, args) in candidates {
+        let spawned = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Ok(mut child) = spawned {
+            // Reaped off-thread so finished players don't linger as zombies
+            std::thread::spawn(move || child.wait());
+            return;
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+fn play_file(path: &std::path::Path) {
+    let file = path.to_string_lossy().to_string();
+    if cfg!(target_os = "macos") {
+        spawn_first(&[("afplay", vec![file])]);
+    } else {
+        spawn_first(&[
+            ("paplay", vec![file.clone()]),
+            ("aplay", vec!["-q".to_string(), file.clone()]),
+            ("ffplay", vec!["-nodisp".to_string(), "-autoexit".to_string(), "-loglevel".to_string(), "quiet".to_string(), file]),
+        ]);
+    }
+}
+
+#[cfg(feature = "audio")]
+fn speak_tts(text: &str, voice: Option<&str>) {
+    let text = text.to_string();
+    if cfg!(target_os = "macos") {
+        // `say` voices are names, not language codes; use the system default
+        spawn_first(&[("say", vec![text])]);
+    } else {
+        let mut args = Vec::new();
+        if let Some(voice) = voice {
+            args.push("-v".to_string());
+            args.push(voice.to_string());
+        }
+        args.push(text);
+        spawn_first(&[("espeak-ng", args.clone()), ("espeak", args)]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_stem() {
+        assert_eq!(recording_stem("Bonjour"), "bonjour");
+        assert_eq!(recording_stem(" le chat noir "), "le_chat_noir");
+        assert_eq!(recording_stem("naïve"), "naïve");
+    }
+
+    #[test]
+    fn test_recording_lookup() {
+        let dir = std::env::temp_dir().join(format!("kw-pron-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("chat.ogg"), b"").unwrap();
+
+        let pron = Pronouncer::new(&PronunciationConfig {
+            recordings_dir: Some(dir.clone()),
+            ..Default::default()
+        });
+        assert_eq!(pron.recording_for("Chat"), Some(dir.join("chat.ogg")));
+        assert_eq!(pron.recording_for("chien"), None);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_announce_dedupes() {
+        let mut pron = Pronouncer::new(&PronunciationConfig::default());
+        pron.announce("eau", true);
+        assert_eq!(pron.last_spoken.as_deref(), Some("eau"));
+        pron.reset();
+        assert_eq!(pron.last_spoken, None);
+    }
+}
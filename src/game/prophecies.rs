@@ -0,0 +1,167 @@
+//! Prophecies - Tomorrow Texts that say what the floors ahead will bring
+//!
+//! Treasure rooms sometimes hold a Tomorrow Text: a scrap of prophecy that
+//! names an elite and a floor. "You will face the Elite Null Word on floor
+//! 6." The prophecy is about that floor's first fight:
+//! - a prophecy that comes true sends the elite it named to meet you there,
+//!   whatever the zone would have sent
+//! - one that doesn't leaves the fight ordinary, and keeps the elite it
+//!   named off the floor altogether
+//!
+//! Whether a prophecy comes true is settled when it is found, and the
+//! player learns it only by getting there. Every prophecy put to the test
+//! is counted across the profile, and the codex keeps the reckoning as
+//! lore about the Tomorrow Texts: how often they have been right.
+//!
+//! Design: prophecies are kept on the Dungeon like rumors, and last the run.
+//! Unlike a rumor, a prophecy bends the world to its answer rather than
+//! reporting it. The reckoning is a pair of world counters, so a fresh
+//! timeline keeps it, as the Tomorrow Texts would.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::dungeon::Dungeon;
+use super::rumors::{elite_name, ZONES};
+use crate::data::EnemyDatabase;
+
+/// Chance a treasure room holds a Tomorrow Text
+pub const PROPHECY_CHANCE: f32 = 0.25;
+
+/// Chance a prophecy comes true
+const TRUE_CHANCE: f64 = 0.7;
+
+/// How many floors ahead a prophecy can reach
+const REACH: i32 = 3;
+
+/// World counters of the prophecies tested, by how they came out
+pub const FULFILLED: &str = "prophecies_fulfilled";
+pub const SUBVERTED: &str = "prophecies_subverted";
+
+/// Where the Tomorrow Text was found
+const FOUND: [&str; 4] = [
+    "on a page torn from a book not yet written",
+    "scratched on the inside of the chest's lid, in ink still wet",
+    "folded into a letter dated next year",
+    "in the margin of a map of a floor you haven't reached",
+];
+
+/// A Tomorrow Text found this run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Prophecy {
+    pub floor: i32,
+    /// Template id of the elite named
+    pub elite_id: String,
+    pub elite: String,
+    pub found: String,
+    /// Whether it will come true; never shown
+    pub comes_true: bool,
+    /// Whether it has been put to the test
+    pub tested: bool,
+}
+
+impl Prophecy {
+    pub fn text(&self) -> String {
+        format!("You will face the {} on floor {}.", self.elite, self.floor)
+    }
+}
+
+/// Find a Tomorrow Text about a floor ahead no other has spoken for, up to
+/// `last_floor`
+pub fn foretell<R: Rng>(dungeon: &Dungeon, enemies: &EnemyDatabase, last_floor: i32, rng: &mut R) -> Option<Prophecy> {
+    let floors: Vec<i32> = (dungeon.current_floor + 1..=(dungeon.current_floor + REACH).min(last_floor))
+        .filter(|f| !dungeon.prophecies.iter().any(|p| p.floor == *f))
+        .collect();
+    let floor = *floors.choose(rng)?;
+    let zone = ZONES.choose(rng)?;
+    Some(Prophecy {
+        floor,
+        elite_id: zone.elite_id().to_string(),
+        elite: elite_name(enemies, *zone),
+        found: FOUND.choose(rng)?.to_string(),
+        comes_true: rng.gen_bool(TRUE_CHANCE),
+        tested: false,
+    })
+}
+
+/// Put the prophecy of `floor` to the test at its first fight, if there is
+/// one not yet tested
+pub fn test(prophecies: &mut [Prophecy], floor: i32) -> Option<Prophecy> {
+    let prophecy = prophecies.iter_mut().find(|p| p.floor == floor && !p.tested)?;
+    prophecy.tested = true;
+    Some(prophecy.clone())
+}
+
+/// The elite an elite room on `floor` holds in place of `zone_elite`: any
+/// but one a prophecy of the floor named and didn't bring
+pub fn elite_for<'a>(prophecies: &[Prophecy], floor: i32, zone_elite: &'a str) -> &'a str {
+    let kept_off = prophecies.iter().any(|p| p.floor == floor && !p.comes_true && p.elite_id == zone_elite);
+    if !kept_off {
+        return zone_elite;
+    }
+    let at = ZONES.iter().position(|z| z.elite_id() == zone_elite).unwrap_or(0);
+    ZONES[(at + 1) % ZONES.len()].elite_id()
+}
+
+/// The codex's reckoning of the Tomorrow Texts, once any has been tested
+pub fn reckoning(fulfilled: i64, subverted: i64) -> Option<String> {
+    let tested = fulfilled + subverted;
+    if tested <= 0 {
+        return None;
+    }
+    let verdict = match fulfilled * 100 / tested {
+        90.. => "They are almost never wrong, which the Archivists find more frightening than if they were.",
+        60..=89 => "They are right more often than chance allows, and wrong just often enough to be doubted.",
+        30..=59 => "They are right about as often as a guess. Either the future is unsettled, or someone is editing it.",
+        _ => "They are usually wrong. The Shadow Writers say that is the point: a prophecy read is a prophecy changed.",
+    };
+    Some(format!(
+        "Of the Tomorrow Texts you have seen put to the test, {} of {} came true. {}",
+        fulfilled, tested, verdict
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prophecies_speak_for_floors_ahead_once_each() {
+        let mut dungeon = Dungeon::new();
+        let enemies = EnemyDatabase::default();
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..REACH {
+            let prophecy = foretell(&dungeon, &enemies, 10, &mut rng).unwrap();
+            assert!(prophecy.floor > dungeon.current_floor && prophecy.floor <= dungeon.current_floor + REACH);
+            assert!(prophecy.text().contains(&prophecy.elite));
+            dungeon.prophecies.push(prophecy);
+        }
+        assert!(foretell(&dungeon, &enemies, 10, &mut rng).is_none());
+        // Nothing is foretold past the last floor
+        assert!(foretell(&Dungeon::new(), &enemies, 1, &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_a_prophecy_is_tested_once_and_a_false_one_keeps_its_elite_away() {
+        let named = ZONES[2].elite_id();
+        let mut prophecies = vec![Prophecy {
+            floor: 4,
+            elite_id: named.to_string(),
+            elite: "Elite Corrupted Typer".to_string(),
+            found: FOUND[0].to_string(),
+            comes_true: false,
+            tested: false,
+        }];
+        assert!(test(&mut prophecies, 3).is_none());
+        assert!(test(&mut prophecies, 4).is_some_and(|p| !p.comes_true));
+        assert!(test(&mut prophecies, 4).is_none());
+
+        assert_ne!(elite_for(&prophecies, 4, named), named);
+        assert_eq!(elite_for(&prophecies, 5, named), named);
+        prophecies[0].comes_true = true;
+        assert_eq!(elite_for(&prophecies, 4, named), named);
+
+        assert_eq!(reckoning(0, 0), None);
+        assert!(reckoning(3, 1).is_some_and(|r| r.contains("3 of 4")));
+    }
+}
@@ -0,0 +1,153 @@
+//! Revelation - How much of the hidden truth the story may tell
+//!
+//! The truth behind the Blight comes out in stages, never skipping one:
+//! - Surface: monsters and corruption spreading from the Breach
+//! - Deeper: the Void is not invading, it is being called
+//! - Deepest: the Archon became a god, and it was the God of Endings
+//! - Final Truth: who you were
+//!
+//! Each stage past the surface opens in its chapter of the descent once one
+//! of its key encounters has been played, or on its own a chapter later if
+//! none has. The final truth waits for the last chapter.
+//!
+//! Design: the stages' text comes from deep_lore's HiddenTruth and
+//! PlayerTruth. Anything that might give the story away (dialogue, pacing
+//! beats, enemy taunts) asks the run's RevelationState what it may say,
+//! through `allows` or by picking the deepest tier of its lines allowed.
+
+use serde::{Deserialize, Serialize};
+
+use super::deep_lore::{create_player_mystery, CorruptionTruth};
+use super::encounter_writing::EncounterTracker;
+
+/// A stage of the hidden truth, in the order it is revealed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Revelation {
+    Surface,
+    Deeper,
+    Deepest,
+    FinalTruth,
+}
+
+impl Revelation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Surface => "The Surface",
+            Self::Deeper => "A Deeper Truth",
+            Self::Deepest => "The Deepest Secret",
+            Self::FinalTruth => "The Final Truth",
+        }
+    }
+
+    /// What this stage reveals
+    pub fn text(&self) -> String {
+        let hidden = CorruptionTruth::canonical().hidden_truth;
+        match self {
+            Self::Surface => hidden.surface_appearance,
+            Self::Deeper => hidden.deeper_truth,
+            Self::Deepest => hidden.deepest_secret,
+            Self::FinalTruth => create_player_mystery().the_truth.who_they_were,
+        }
+    }
+
+    fn next(&self) -> Option<Self> {
+        match self {
+            Self::Surface => Some(Self::Deeper),
+            Self::Deeper => Some(Self::Deepest),
+            Self::Deepest => Some(Self::FinalTruth),
+            Self::FinalTruth => None,
+        }
+    }
+}
+
+/// When a stage opens
+struct Gate {
+    chapter: u32,
+    /// Encounters any one of which opens the stage in its chapter
+    key_encounters: &'static [&'static str],
+}
+
+fn gate(level: Revelation) -> Gate {
+    match level {
+        Revelation::Surface => Gate { chapter: 1, key_encounters: &[] },
+        Revelation::Deeper => Gate { chapter: 2, key_encounters: &["corruption_memory_echo", "athenaeum_living_book"] },
+        Revelation::Deepest => Gate { chapter: 3, key_encounters: &["shadowwriter_offer", "first_archivist_meeting"] },
+        Revelation::FinalTruth => Gate { chapter: 5, key_encounters: &[] },
+    }
+}
+
+/// How far the run's story has been revealed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevelationState {
+    pub level: Revelation,
+}
+
+impl Default for RevelationState {
+    fn default() -> Self {
+        Self { level: Revelation::Surface }
+    }
+}
+
+impl RevelationState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reveal whatever `chapter` and the encounters played now allow, one
+    /// stage at a time; returns the stages newly revealed
+    pub fn advance(&mut self, chapter: u32, tracker: &EncounterTracker) -> Vec<Revelation> {
+        let mut revealed = Vec::new();
+        while let Some(next) = self.level.next() {
+            let gate = gate(next);
+            let keyed = gate.key_encounters.is_empty() || gate.key_encounters.iter().any(|id| tracker.has_completed(id));
+            let open = (chapter >= gate.chapter && keyed) || chapter > gate.chapter;
+            if !open {
+                break;
+            }
+            self.level = next;
+            revealed.push(next);
+        }
+        revealed
+    }
+
+    /// Whether text gated on `level` may be said
+    pub fn allows(&self, level: Revelation) -> bool {
+        self.level >= level
+    }
+
+    /// The deepest of `tiers` the story has reached, for text written in
+    /// versions per stage
+    pub fn pick<'a, T>(&self, tiers: &'a [(Revelation, T)]) -> Option<&'a T> {
+        tiers.iter().filter(|(level, _)| self.allows(*level)).max_by_key(|(level, _)| *level).map(|(_, t)| t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stages_wait_for_chapter_and_key_encounters() {
+        let mut tracker = EncounterTracker::new();
+        let mut state = RevelationState::new();
+        assert!(state.advance(2, &tracker).is_empty());
+        tracker.complete_encounter("corruption_memory_echo", "analyze_memory");
+        assert_eq!(state.advance(2, &tracker), vec![Revelation::Deeper]);
+        // Without its key encounter, the next stage still surfaces a chapter late
+        assert!(state.advance(3, &tracker).is_empty());
+        assert_eq!(state.advance(4, &tracker), vec![Revelation::Deepest]);
+        assert_eq!(state.advance(5, &tracker), vec![Revelation::FinalTruth]);
+        assert!(state.advance(5, &tracker).is_empty());
+    }
+
+    #[test]
+    fn test_text_picks_the_deepest_allowed_tier() {
+        let tiers = [(Revelation::Surface, "monsters"), (Revelation::Deepest, "the god of endings")];
+        let mut state = RevelationState::new();
+        assert_eq!(state.pick(&tiers), Some(&"monsters"));
+        assert!(!state.allows(Revelation::Deeper));
+        state.level = Revelation::Deepest;
+        assert_eq!(state.pick(&tiers), Some(&"the god of endings"));
+        assert!(Revelation::FinalTruth.text().contains("Malachar"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Rumors - What Haven and the shops say about the floors ahead
+//!
+//! A rumor is heard in Haven before setting out, and another at every shop.
+//! Each tells of something still ahead:
+//! - which elite prowls one of the next few floors
+//! - who waits on the next boss floor
+//! - which room on this floor hides a cache
+//!
+//! A rumor is only as good as whoever told it. It is heard as a whisper, as
+//! talk, or sworn to, and the surer it is said the likelier it is true; the
+//! player learns how sure, never whether. Rumors are marked on the floor
+//! map with their doubt, and listed beneath it.
+//!
+//! Design: rumors are soft knowledge kept on the Dungeon, so they last the
+//! run and no longer. They change nothing ahead except a rumored cache,
+//! which is only there if the rumor was true. An elite rumor is true of the
+//! route as it stood when heard: taking the other fork can make it wrong.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::dungeon::{Dungeon, RoomType};
+use super::enemy::Enemy;
+use super::world_integration::FloorZone;
+use crate::data::EnemyDatabase;
+
+/// Gold in a cache a true rumor led to
+pub const CACHE_GOLD: u64 = 40;
+
+/// How many floors ahead elite rumors reach
+const ELITE_REACH: i32 = 2;
+
+/// Bosses wait on every floor divisible by this
+const BOSS_EVERY: i32 = 5;
+
+pub const ZONES: [FloorZone; 6] = [
+    FloorZone::ShatteredHalls,
+    FloorZone::SunkenArchives,
+    FloorZone::BlightedGardens,
+    FloorZone::ClockworkDepths,
+    FloorZone::VoidsEdge,
+    FloorZone::TheBreach,
+];
+
+/// How surely a rumor was told
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Certainty {
+    Whispered,
+    Told,
+    Sworn,
+}
+
+impl Certainty {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Whispered => "whispered",
+            Self::Told => "told",
+            Self::Sworn => "sworn to",
+        }
+    }
+
+    /// Chance a rumor said this surely is true
+    pub fn truth_chance(&self) -> f64 {
+        match self {
+            Self::Whispered => 0.5,
+            Self::Told => 0.75,
+            Self::Sworn => 0.9,
+        }
+    }
+
+    /// Mark beside a rumored room on the map
+    pub fn mark(&self) -> &'static str {
+        match self {
+            Self::Whispered => "?",
+            Self::Told => "¿",
+            Self::Sworn => "!",
+        }
+    }
+}
+
+/// What a rumor is about
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RumorSubject {
+    /// The elite said to prowl a floor
+    Elite { floor: i32, name: String },
+    /// The boss said to wait on a floor
+    Boss { floor: i32, name: String },
+    /// A room on a floor said to hide a cache
+    Cache { floor: i32, row: usize, col: usize },
+}
+
+impl RumorSubject {
+    pub fn floor(&self) -> i32 {
+        match self {
+            Self::Elite { floor, .. } | Self::Boss { floor, .. } | Self::Cache { floor, .. } => *floor,
+        }
+    }
+
+    /// Whether two subjects ask the same question, whatever they answer
+    fn same_question(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other) && self.floor() == other.floor()
+    }
+}
+
+/// Something heard about the floors ahead
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rumor {
+    pub subject: RumorSubject,
+    pub certainty: Certainty,
+    /// Where it was heard
+    pub source: String,
+    /// Whether it is so; never shown
+    pub holds: bool,
+}
+
+impl Rumor {
+    /// The rumor as the player heard it
+    pub fn text(&self) -> String {
+        match &self.subject {
+            RumorSubject::Elite { floor, name } => format!("{} prowls floor {}", name, floor),
+            RumorSubject::Boss { floor, name } => format!("{} waits on floor {}", name, floor),
+            RumorSubject::Cache { floor, row, .. } => format!("A cache lies hidden {} rooms into floor {}", row + 1, floor),
+        }
+    }
+
+    /// Whether the rumor marks this room of the floor map
+    pub fn marks(&self, floor: i32, row: usize, col: usize, room_type: RoomType) -> bool {
+        match &self.subject {
+            RumorSubject::Elite { floor: f, .. } => *f == floor && room_type == RoomType::Elite,
+            RumorSubject::Boss { floor: f, .. } => *f == floor && room_type == RoomType::Boss,
+            RumorSubject::Cache { floor: f, row: r, col: c } => (*f, *r, *c) == (floor, row, col),
+        }
+    }
+}
+
+/// "Elite Vowel Vampire", as the zone's elite is spoken of
+pub fn elite_name(enemies: &EnemyDatabase, zone: FloorZone) -> String {
+    enemies.enemies.get(zone.elite_id())
+        .map(|e| format!("Elite {}", e.name))
+        .unwrap_or_else(|| format!("The elite of {}", zone.name()))
+}
+
+/// Everything a rumor could say as things stand, true or false: (the
+/// truth, a lie in its place)
+fn questions<R: Rng>(dungeon: &Dungeon, enemies: &EnemyDatabase, rng: &mut R) -> Vec<(RumorSubject, Option<RumorSubject>)> {
+    let floor = dungeon.current_floor;
+    let mut asked = Vec::new();
+
+    for ahead in floor..=floor + ELITE_REACH {
+        let zone = FloorZone::from_floor(ahead as u32);
+        let lie = ZONES.iter().filter(|z| z.elite_id() != zone.elite_id()).choose(rng)
+            .map(|z| RumorSubject::Elite { floor: ahead, name: elite_name(enemies, *z) });
+        asked.push((RumorSubject::Elite { floor: ahead, name: elite_name(enemies, zone) }, lie));
+    }
+
+    let boss_floor = (floor + BOSS_EVERY - 1) / BOSS_EVERY * BOSS_EVERY;
+    if let Some(boss) = Enemy::boss_names(boss_floor).choose(rng) {
+        let liars: Vec<String> = [1, BOSS_EVERY * 2].iter()
+            .flat_map(|f| Enemy::boss_names(*f))
+            .filter(|n| n != boss)
+            .collect();
+        let lie = liars.choose(rng).map(|name| RumorSubject::Boss { floor: boss_floor, name: name.clone() });
+        asked.push((RumorSubject::Boss { floor: boss_floor, name: boss.clone() }, lie));
+    }
+
+    // A cache can hide in any room not yet reached, save the boss's
+    let map = &dungeon.floor_map;
+    let reached = dungeon.rooms_cleared.max(0) as usize;
+    let rooms: Vec<(usize, usize)> = (reached..map.depth())
+        .flat_map(|row| (0..map.rows[row].len()).map(move |col| (row, col)))
+        .filter(|(row, col)| map.rows[*row][*col].room_type != RoomType::Boss)
+        .collect();
+    if let Some(&(row, col)) = rooms.choose(rng) {
+        // A false cache rumor points at the same room; it just isn't there
+        asked.push((RumorSubject::Cache { floor, row, col }, None));
+    }
+    asked
+}
+
+/// Hear a rumor at `source` about something not yet heard of
+pub fn hear<R: Rng>(dungeon: &Dungeon, enemies: &EnemyDatabase, source: &str, rng: &mut R) -> Option<Rumor> {
+    let fresh: Vec<_> = questions(dungeon, enemies, rng).into_iter()
+        .filter(|(truth, _)| !dungeon.rumors.iter().any(|r| r.subject.same_question(truth)))
+        .collect();
+    let (truth, lie) = fresh.choose(rng)?.clone();
+    let certainty = *[Certainty::Whispered, Certainty::Whispered, Certainty::Told, Certainty::Told, Certainty::Sworn]
+        .choose(rng)?;
+    let holds = rng.gen_bool(certainty.truth_chance());
+    let subject = match (holds, lie) {
+        (false, Some(lie)) => lie,
+        _ => truth,
+    };
+    Some(Rumor { subject, certainty, source: source.to_string(), holds })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dungeon() -> Dungeon {
+        let mut dungeon = Dungeon::new();
+        dungeon.floor_map = crate::game::run_map::FloorMap::generate(4, false, &mut StdRng::seed_from_u64(2));
+        dungeon
+    }
+
+    #[test]
+    fn test_rumors_are_not_repeated() {
+        let mut dungeon = dungeon();
+        let enemies = EnemyDatabase::default();
+        let mut rng = StdRng::seed_from_u64(9);
+        // Three floors of elites, a boss and a cache, then nothing new
+        for _ in 0..5 {
+            let rumor = hear(&dungeon, &enemies, "Haven", &mut rng).unwrap();
+            assert!(!dungeon.rumors.iter().any(|r| r.subject.same_question(&rumor.subject)));
+            dungeon.rumors.push(rumor);
+        }
+        assert!(hear(&dungeon, &enemies, "Haven", &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_false_rumors_name_the_wrong_thing() {
+        let dungeon = dungeon();
+        let enemies = EnemyDatabase::default();
+        let truth = elite_name(&enemies, FloorZone::from_floor(1));
+        let mut seen = (false, false);
+        for seed in 0..200 {
+            let rumor = hear(&dungeon, &enemies, "a shop", &mut StdRng::seed_from_u64(seed)).unwrap();
+            match &rumor.subject {
+                RumorSubject::Elite { floor: 1, name } => {
+                    assert_eq!(*name == truth, rumor.holds, "{:?}", rumor);
+                    if rumor.holds { seen.0 = true } else { seen.1 = true }
+                }
+                RumorSubject::Boss { floor, name } => {
+                    assert_eq!(*floor, BOSS_EVERY);
+                    assert_eq!(Enemy::boss_names(*floor).contains(name), rumor.holds);
+                }
+                RumorSubject::Cache { row, col, .. } => {
+                    assert!(rumor.marks(1, *row, *col, dungeon.floor_map.rows[*row][*col].room_type));
+                }
+                _ => {}
+            }
+        }
+        assert!(seen.0 && seen.1, "rumors of the first floor's elite should be both true and false");
+    }
+}
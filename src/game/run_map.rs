@@ -0,0 +1,200 @@
+//! Run Map - Every floor is a web of paths
+//!
+//! Each floor is laid out as rows of rooms joined by paths, and the player
+//! picks which room to take next:
+//! - fights, elites, events, shops, rests and mysteries fill the rows
+//! - a mystery only shows what it was once you step inside
+//! - on boss floors every path ends at the boss
+//!
+//! Taking the stairs is always the step after the last row.
+//!
+//! Design: the map is generated once per floor and owned by the Dungeon.
+//! Row `r` is the room entered after clearing `r` rooms, so fleeing a room
+//! lets the player pick again from the room before it.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::dungeon::RoomType;
+
+/// Fewest rooms a row offers
+const MIN_ROW_WIDTH: usize = 2;
+/// Most rooms a row offers
+const MAX_ROW_WIDTH: usize = 4;
+/// Chance a room opens a second path to the next row
+const BRANCH_CHANCE: f32 = 0.4;
+
+/// A room on the floor map
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapNode {
+    pub room_type: RoomType,
+    /// Columns in the next row this room leads to
+    pub next: Vec<usize>,
+}
+
+/// The rooms of one floor and the route taken through them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloorMap {
+    pub rows: Vec<Vec<MapNode>>,
+    /// Column entered on each row so far
+    pub path: Vec<usize>,
+}
+
+impl FloorMap {
+    /// Lay out a floor `depth` rows deep, ending at a boss if `boss`
+    pub fn generate(depth: usize, boss: bool, rng: &mut impl Rng) -> Self {
+        let mut rows: Vec<Vec<MapNode>> = (0..depth)
+            .map(|row| {
+                let width = if boss && row + 1 == depth {
+                    1
+                } else {
+                    rng.gen_range(MIN_ROW_WIDTH..=MAX_ROW_WIDTH)
+                };
+                (0..width)
+                    .map(|_| MapNode {
+                        room_type: roll_room(row, depth, boss, rng),
+                        next: Vec::new(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for row in 0..depth.saturating_sub(1) {
+            let (here, ahead) = (rows[row].len(), rows[row + 1].len());
+            for (col, node) in rows[row].iter_mut().enumerate() {
+                // Spread the paths evenly, with the odd fork to a neighbour
+                let target = if here == 1 { 0 } else { col * (ahead - 1) / (here - 1) };
+                node.next.push(target);
+                if target + 1 < ahead && rng.gen::<f32>() < BRANCH_CHANCE {
+                    node.next.push(target + 1);
+                }
+            }
+            // Every room must be reachable from somewhere
+            for target in 0..ahead {
+                if !rows[row].iter().any(|n| n.next.contains(&target)) {
+                    let from = (target * here / ahead).min(here - 1);
+                    rows[row][from].next.push(target);
+                    rows[row][from].next.sort_unstable();
+                }
+            }
+        }
+
+        Self { rows, path: Vec::new() }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Columns the player can enter on `row`, given the route so far
+    pub fn choices(&self, row: usize) -> Vec<usize> {
+        if row >= self.depth() {
+            return Vec::new();
+        }
+        let from = row.checked_sub(1).and_then(|prev| Some(&self.rows[prev][*self.path.get(prev)?]));
+        match from {
+            Some(node) => node.next.clone(),
+            None => (0..self.rows[row].len()).collect(),
+        }
+    }
+
+    /// Step into `col` on `row`; returns the room's type
+    pub fn enter(&mut self, row: usize, col: usize) -> Option<RoomType> {
+        let room_type = self.rows.get(row)?.get(col)?.room_type;
+        self.path.truncate(row);
+        self.path.push(col);
+        Some(room_type)
+    }
+
+    /// Whether the route passes through `col` on `row`
+    pub fn on_path(&self, row: usize, col: usize) -> bool {
+        self.path.get(row) == Some(&col)
+    }
+}
+
+/// What a mystery room turns out to be
+pub fn reveal_mystery(rng: &mut impl Rng) -> RoomType {
+    match rng.gen_range(0..10) {
+        0..=3 => RoomType::Event,
+        4..=5 => RoomType::Treasure,
+        6..=8 => RoomType::Combat,
+        _ => RoomType::Shop,
+    }
+}
+
+fn roll_room(row: usize, depth: usize, boss: bool, rng: &mut impl Rng) -> RoomType {
+    if boss && row + 1 == depth {
+        return RoomType::Boss;
+    }
+    // The first rooms of a floor are always fights
+    if row == 0 {
+        return RoomType::Combat;
+    }
+    let roll: f32 = rng.gen();
+    if roll < 0.40 {
+        RoomType::Combat
+    } else if roll < 0.53 {
+        RoomType::Event
+    } else if roll < 0.63 {
+        RoomType::Treasure
+    } else if roll < 0.73 {
+        RoomType::Mystery
+    } else if roll < 0.83 {
+        RoomType::Rest
+    } else if roll < 0.91 {
+        RoomType::Shop
+    } else {
+        RoomType::Elite
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_room_is_reachable() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let map = FloorMap::generate(4, false, &mut rng);
+            assert_eq!(map.depth(), 4);
+            for row in 0..3 {
+                let ahead = map.rows[row + 1].len();
+                for target in 0..ahead {
+                    assert!(map.rows[row].iter().any(|n| n.next.contains(&target)));
+                }
+                assert!(map.rows[row].iter().all(|n| n.next.iter().all(|&t| t < ahead)));
+            }
+            assert!(map.rows[0].iter().all(|n| n.room_type == RoomType::Combat));
+        }
+    }
+
+    #[test]
+    fn test_route_follows_the_paths_and_ends_at_the_boss() {
+        let mut rng = rand::thread_rng();
+        let mut map = FloorMap::generate(4, true, &mut rng);
+        assert_eq!(map.rows[3], vec![MapNode { room_type: RoomType::Boss, next: Vec::new() }]);
+        assert_eq!(map.choices(0).len(), map.rows[0].len());
+
+        map.enter(0, 1);
+        assert_eq!(map.choices(1), map.rows[0][1].next);
+        let col = map.choices(1)[0];
+        map.enter(1, col);
+        assert!(map.on_path(1, col));
+
+        // Fleeing back to row 1 forgets the rest of the route
+        map.enter(2, map.choices(2)[0]);
+        map.enter(1, col);
+        assert_eq!(map.path, vec![1, col]);
+        assert!(map.choices(4).is_empty());
+    }
+
+    #[test]
+    fn test_treasure_rooms_appear_on_the_map() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let treasure = (0..200).any(|_| roll_room(1, 4, false, &mut rng) == RoomType::Treasure);
+        assert!(treasure);
+    }
+}
@@ -163,6 +163,8 @@ pub enum Modifier {
     Metronome { target_cpm: f32 },
     /// No backspace allowed
     NoBackspace,
+    /// Each word starts with the last letter of the one before
+    WordChain,
     
     // === Combat Modifiers ===
     /// Enemies have more health
@@ -256,6 +258,7 @@ impl Modifier {
             Self::ShiftingText { .. } => 5,
             Self::Metronome { .. } => 3,
             Self::NoBackspace => 5,
+            Self::WordChain => 0, // A different game, not a harder one
             
             Self::ToughEnemies { .. } => 2,
             Self::DangerousEnemies { .. } => 3,
@@ -310,7 +313,8 @@ impl Modifier {
             Self::InvisibleLetters { .. } |
             Self::ShiftingText { .. } |
             Self::Metronome { .. } |
-            Self::NoBackspace
+            Self::NoBackspace |
+            Self::WordChain
         )
     }
     
@@ -341,6 +345,7 @@ impl Modifier {
             Self::ShiftingText { .. } => "Shifting Sands",
             Self::Metronome { .. } => "Metronome",
             Self::NoBackspace => "No Second Chances",
+            Self::WordChain => "Word Chain",
             
             Self::ToughEnemies { .. } => "Tough Enemies",
             Self::DangerousEnemies { .. } => "Deadly Foes",
@@ -406,6 +411,7 @@ impl Modifier {
             Self::GlassCannon => "One hit kills you".to_string(),
             Self::Permadeath => "Death is permanent".to_string(),
             Self::NoBackspace => "Cannot correct mistakes".to_string(),
+            Self::WordChain => "Each word begins with the last letter of the one before; unbroken chains hit harder".to_string(),
             Self::AcceleratedCorruption => {
                 format!("Corruption spreads {}x faster", level + 1)
             }
@@ -0,0 +1,228 @@
+//! Run RNG - Seeded randomness for runs that must play out the same
+//!
+//! Every run has a seed: a challenge's or daily run's, one given with
+//! `--seed`, or a fresh one. The run's randomness comes from streams
+//! derived from it, so everyone on the same seed gets:
+//! - the same floor maps
+//! - the same enemies, room by room
+//! - the same words, prompt by prompt
+//! - the same crits, misses and escapes, blow by blow
+//! - the same dialogue and story beats
+//!
+//! Each stream draws on its own, so how long one fight lasts can't shift
+//! the maps or enemies that follow.
+//!
+//! Design: the streams are thread-local. `stream` hands out a generator
+//! usable anywhere an `Rng` is, falling back to `thread_rng` when no run is
+//! seeded, so callers don't need a generator threaded through to them.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::cell::RefCell;
+
+/// Which part of the run a draw is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Map,
+    Enemies,
+    Words,
+    /// Combat barks and flavour lines
+    Dialogue,
+    /// Story beats between fights
+    Pacing,
+    /// Rolls within a fight: dodges, misses, crits and flight
+    Combat,
+}
+
+impl Stream {
+    fn index(self) -> usize {
+        match self {
+            Self::Map => 0,
+            Self::Enemies => 1,
+            Self::Words => 2,
+            Self::Dialogue => 3,
+            Self::Pacing => 4,
+            Self::Combat => 5,
+        }
+    }
+}
+
+const STREAM_COUNT: usize = 6;
+
+thread_local! {
+    static STREAMS: RefCell<Option<[StdRng; STREAM_COUNT]>> = const { RefCell::new(None) };
+}
+
+/// Seed every stream for a run
+pub fn seed(seed: u64) {
+    let streams = std::array::from_fn(|i| StdRng::seed_from_u64(seed ^ (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)));
+    STREAMS.with(|s| *s.borrow_mut() = Some(streams));
+}
+
+/// Go back to unseeded randomness
+pub fn unseed() {
+    STREAMS.with(|s| *s.borrow_mut() = None);
+}
+
+/// A generator drawing from `stream`
+pub fn stream(stream: Stream) -> RunRng {
+    RunRng(stream)
+}
+
+/// Draws from one of the run's streams, or `thread_rng` when unseeded
+#[derive(Debug, Clone, Copy)]
+pub struct RunRng(Stream);
+
+impl RunRng {
+    fn draw<T>(&mut self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        STREAMS.with(|s| match s.borrow_mut().as_mut() {
+            Some(streams) => f(&mut streams[self.0.index()]),
+            None => f(&mut rand::thread_rng()),
+        })
+    }
+}
+
+impl RngCore for RunRng {
+    fn next_u32(&mut self) -> u32 {
+        self.draw(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draw(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draw(|rng| rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.draw(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draws(s: Stream) -> Vec<u32> {
+        (0..5).map(|_| stream(s).gen_range(0..1000)).collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_draws() {
+        seed(42);
+        let maps = draws(Stream::Map);
+        let words = draws(Stream::Words);
+        seed(42);
+        // Drawing words first doesn't shift the maps
+        assert_eq!(draws(Stream::Words), words);
+        assert_eq!(draws(Stream::Map), maps);
+        assert_ne!(maps, words);
+
+        seed(43);
+        assert_ne!(draws(Stream::Map), maps);
+        unseed();
+    }
+
+    #[test]
+    fn test_seed_reproduces_enemies_and_beats() {
+        use crate::game::enemy::Enemy;
+        use crate::game::pacing::PacingController;
+
+        let run = || {
+            let enemies: Vec<String> = (1..6).map(|floor| Enemy::random_for_floor(floor).name).collect();
+            let mut pacing = PacingController::new();
+            for _ in 0..10 {
+                pacing.on_room_enter(1, "combat");
+            }
+            let beats: Vec<String> = std::iter::from_fn(|| pacing.pop_beat()).map(|b| b.text().to_string()).collect();
+            (enemies, beats)
+        };
+        seed(7);
+        let first = run();
+        seed(7);
+        assert_eq!(run(), first);
+        unseed();
+    }
+
+    #[test]
+    fn test_seed_reproduces_events_drops_and_prompts() {
+        use crate::game::items::Item;
+        use crate::game::sentence_stitching::{RunChronicle, RunEvent};
+        use crate::game::world_integration::{generate_zone_event, FloorZone};
+
+        let run = || {
+            let events: Vec<String> = (0..5)
+                .map(|_| generate_zone_event(FloorZone::ShatteredHalls, &mut stream(Stream::Map)).name)
+                .collect();
+            let drops: Vec<String> = (0..5).map(|_| Item::random_consumable(&mut stream(Stream::Map)).name).collect();
+            let mut chronicle = RunChronicle::new();
+            chronicle.record(RunEvent::Defeated { enemy: "Goblin Scout".to_string(), floor: 1 });
+            chronicle.record(RunEvent::Spared { enemy: "Ink Wisp".to_string(), floor: 2 });
+            chronicle.record(RunEvent::NearDeath { floor: 2 });
+            let prompts = chronicle.stitch_all(&mut stream(Stream::Words));
+            (events, drops, prompts)
+        };
+        seed(11);
+        let first = run();
+        seed(11);
+        assert_eq!(run(), first);
+        unseed();
+    }
+
+    #[test]
+    fn test_seed_reproduces_prompts_fight_by_fight() {
+        use crate::data::GameData;
+        use crate::game::combat::{CombatPhase, CombatState};
+        use crate::game::enemy::Enemy;
+        use crate::game::player::{Class, Player};
+        use std::sync::Arc;
+        use std::time::{Duration, Instant};
+
+        let data = Arc::new(GameData::new());
+        // Typed word for word, so the only differences are what was drawn:
+        // the prompts, the crits and dodges, and whether flight works
+        let prompts = |combat: &mut CombatState| {
+            let mut player = Player::new("Tester".to_string(), Class::Wordsmith);
+            player.max_hp = 100_000;
+            player.hp = player.max_hp;
+            combat.skill_crit_chance = 0.5;
+            combat.skill_evasion_chance = 0.3;
+            let mut seen = Vec::new();
+            for _ in 0..8 {
+                seen.push(combat.current_word.clone());
+                // Keys stamped a steady 150ms apart, so speed can't differ
+                let start = Instant::now() - Duration::from_secs(10);
+                for (i, c) in combat.target().to_string().chars().enumerate() {
+                    combat.key_read_at = Some(start + Duration::from_millis(150) * i as u32);
+                    combat.on_char_typed(c);
+                }
+                if combat.phase == CombatPhase::EnemyTurn {
+                    combat.execute_enemy_turn(&mut player);
+                }
+                seen.push(format!("{} / {}", combat.enemy.current_hp, player.hp));
+            }
+            seen.push(combat.try_flee().to_string());
+            seen
+        };
+        let run = || {
+            let mut enemy = Enemy::random_for_floor(3);
+            enemy.max_hp = 100_000;
+            enemy.current_hp = enemy.max_hp;
+            let mut words = CombatState::new(enemy.clone(), data.clone(), 1, 3, None, None);
+            words.enable_word_chain();
+            words.set_word_pack(["ash", "hollow", "wraith", "thorn", "night", "tome", "ember", "rune"].map(String::from).to_vec());
+
+            enemy.is_boss = true;
+            let mut sentences = CombatState::new(enemy, data.clone(), 5, 3, None, None);
+            sentences.set_stitched_prompts((0..8).map(|i| format!("The ink remembers fight {}.", i)).collect());
+            (prompts(&mut words), prompts(&mut sentences))
+        };
+        seed(11);
+        let first = run();
+        seed(11);
+        assert_eq!(run(), first);
+        unseed();
+    }
+}
@@ -10,6 +10,9 @@ use std::io;
 
 use super::player::Player;
 use super::dungeon::Dungeon;
+use super::curses::Curse;
+use crate::data::items::EquipmentSlot;
+use std::collections::HashMap;
 
 /// Version of the save format for migration support
 const SAVE_VERSION: u32 = 1;
@@ -58,6 +61,16 @@ pub struct PlayerSave {
     pub inventory: Vec<ItemSave>,
     pub equipped: EquipmentSave,
     pub skills_unlocked: Vec<String>,
+    #[serde(default)]
+    pub lexicons: Vec<String>,
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+    #[serde(default)]
+    pub curses: Vec<Curse>,
+    #[serde(default)]
+    pub rhythm_bonus_ms: u32,
+    #[serde(default)]
+    pub crit_bonus: f32,
 }
 
 /// Serializable item
@@ -74,6 +87,26 @@ pub struct EquipmentSave {
     pub weapon: Option<String>,
     pub armor: Option<String>,
     pub accessory: Option<String>,
+    #[serde(default)]
+    pub gloves: Option<String>,
+    #[serde(default)]
+    pub keycaps: Option<String>,
+    #[serde(default)]
+    pub tome: Option<String>,
+}
+
+impl From<&HashMap<EquipmentSlot, String>> for EquipmentSave {
+    fn from(gear: &HashMap<EquipmentSlot, String>) -> Self {
+        let worn = |slot| gear.get(&slot).cloned();
+        Self {
+            weapon: worn(EquipmentSlot::Weapon),
+            armor: worn(EquipmentSlot::Armor),
+            accessory: worn(EquipmentSlot::Accessory),
+            gloves: worn(EquipmentSlot::Gloves),
+            keycaps: worn(EquipmentSlot::Keycaps),
+            tome: worn(EquipmentSlot::Tome),
+        }
+    }
 }
 
 /// Serializable dungeon state
@@ -282,8 +315,13 @@ impl From<&Player> for PlayerSave {
                 name: item.name.clone(),
                 quantity: 1,
             }).collect(),
-            equipped: EquipmentSave::default(),
+            equipped: EquipmentSave::from(&player.gear),
             skills_unlocked: Vec::new(),
+            lexicons: player.lexicons.clone(),
+            artifacts: player.artifacts.clone(),
+            curses: player.curses.clone(),
+            rhythm_bonus_ms: player.rhythm_bonus_ms,
+            crit_bonus: player.crit_bonus,
         }
     }
 }
@@ -0,0 +1,173 @@
+//! Scars - The fights you nearly lost, kept as story
+//!
+//! Win a fight with a quarter of your health or less left and it leaves a
+//! scar: where it was cut, against whom, and the last word that failed you
+//! in that fight. Scars are kept across runs, the most recent dozen, and
+//! they are told back to you:
+//! - the codex keeps them in a section of their own
+//! - now and then the healer at a rest site, or a merchant, remarks on one
+//! - the final boss knows them, and opens its fight with one
+//!
+//! Design: a scar is taken from the fight as it ends, alongside the run
+//! chronicle's near-death, from the word the fight last saw fail (kept on
+//! CombatState) and where the fight was. The scars are kept in a file of
+//! their own beside the config, saved as each one is cut.
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::config::get_config_dir;
+
+/// Scars kept; older ones fade
+const MAX_SCARS: usize = 12;
+
+/// Chance a healer or merchant remarks on a scar rather than greeting you
+pub const REMARK_CHANCE: f64 = 0.3;
+
+/// A near-death survived
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scar {
+    pub enemy: String,
+    pub floor: u32,
+    pub zone: String,
+    /// The last word that failed in the fight, if one did
+    pub word: Option<String>,
+    /// The descent it was cut in
+    pub descent: i64,
+}
+
+impl Scar {
+    pub fn title(&self) -> String {
+        format!("{}, floor {}", self.enemy, self.floor)
+    }
+
+    /// The scar as the codex tells it
+    pub fn text(&self) -> String {
+        let failed = match &self.word {
+            Some(word) => format!("The word that failed you was '{}'.", word),
+            None => "No word failed you; it simply hit harder than you could.".to_string(),
+        };
+        format!(
+            "Descent {}, {}, floor {}. {} brought you to the edge, and you walked back from it. {}",
+            self.descent, self.zone, self.floor, self.enemy, failed
+        )
+    }
+
+    /// What a healer or merchant says, seeing it
+    pub fn remark(&self) -> String {
+        match &self.word {
+            Some(word) => format!(
+                "That scar. {} gave you that, down in {}? They say '{}' caught in your throat.",
+                self.enemy, self.zone, word
+            ),
+            None => format!("That scar. {} gave you that, down in {}? It healed crooked.", self.enemy, self.zone),
+        }
+    }
+
+    /// What the final boss says of it
+    pub fn taunt(&self, boss: &str) -> String {
+        match &self.word {
+            Some(word) => format!(
+                "{}: \"I was there, on floor {}, when {} nearly ended you. '{}' would not come. It will not come now.\"",
+                boss, self.floor, self.enemy, word
+            ),
+            None => format!(
+                "{}: \"I was there, on floor {}, when {} nearly ended you. I have been waiting to finish it.\"",
+                boss, self.floor, self.enemy
+            ),
+        }
+    }
+}
+
+/// Every scar kept, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scars {
+    scars: Vec<Scar>,
+}
+
+impl Scars {
+    pub fn all(&self) -> &[Scar] {
+        &self.scars
+    }
+
+    /// Keep a new scar, letting the oldest fade past the limit
+    pub fn cut(&mut self, scar: Scar) {
+        self.scars.push(scar);
+        let excess = self.scars.len().saturating_sub(MAX_SCARS);
+        self.scars.drain(..excess);
+    }
+
+    /// Any one of the scars
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<&Scar> {
+        self.scars.choose(rng)
+    }
+
+    /// A scar to be remarked on, now and then
+    pub fn remark(&self, rng: &mut impl Rng) -> Option<String> {
+        if !rng.gen_bool(REMARK_CHANCE) {
+            return None;
+        }
+        self.pick(rng).map(Scar::remark)
+    }
+}
+
+fn scars_path() -> PathBuf {
+    get_config_dir().join("scars.ron")
+}
+
+pub fn load() -> Scars {
+    fs::read_to_string(scars_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(scars: &Scars) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(scars, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(scars_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scar(enemy: &str, word: Option<&str>) -> Scar {
+        Scar {
+            enemy: enemy.to_string(),
+            floor: 3,
+            zone: "Sunken Archives".to_string(),
+            word: word.map(str::to_string),
+            descent: 4,
+        }
+    }
+
+    #[test]
+    fn test_a_scar_tells_what_failed_or_that_nothing_did() {
+        let failed = scar("Ink Wraith", Some("palimpsest"));
+        assert!(failed.text().contains("'palimpsest'"));
+        assert!(failed.remark().contains("Sunken Archives"));
+        assert!(failed.taunt("The Void Herald").starts_with("The Void Herald: \"I was there, on floor 3"));
+        let outmatched = scar("Ink Wraith", None);
+        assert!(outmatched.text().contains("No word failed you"));
+        assert!(!outmatched.taunt("The Void Herald").contains('\''));
+    }
+
+    #[test]
+    fn test_the_oldest_scars_fade_past_the_limit() {
+        let mut scars = Scars::default();
+        for i in 0..MAX_SCARS + 2 {
+            scars.cut(scar(&format!("Enemy {}", i), None));
+        }
+        assert_eq!(scars.all().len(), MAX_SCARS);
+        assert_eq!(scars.all()[0].enemy, "Enemy 2");
+
+        let text = ron::to_string(&scars).unwrap();
+        assert_eq!(ron::from_str::<Scars>(&text).unwrap(), scars);
+        // Nothing to remark on without a scar
+        assert_eq!(Scars::default().remark(&mut rand::thread_rng()), None);
+    }
+}
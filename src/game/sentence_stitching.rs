@@ -0,0 +1,204 @@
+//! Sentence Stitching - Combat prompts about *your* run
+//!
+//! Templates with slots ("The {enemy} you spared on floor {floor} is
+//! remembered here.") are filled from a chronicle of what actually happened
+//! this run. Every stitched sentence is checked against the combat rules in
+//! writing_guidelines (length, banned words, tone) before it can be typed.
+//!
+//! Design: the chronicle only records facts; stitching is done once per
+//! fight and handed to CombatState, which mixes the results into its
+//! sentence prompts.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::writing_guidelines::EconomyOfLanguage;
+
+/// Fewest words a stitched prompt may have
+const MIN_WORDS: usize = 4;
+
+/// Characters a stitched prompt may contain besides letters and digits
+const ALLOWED_PUNCTUATION: &str = " .,'-";
+
+/// Something that happened this run, worth remembering
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunEvent {
+    Defeated { enemy: String, floor: u32 },
+    BossDefeated { enemy: String, floor: u32 },
+    Spared { enemy: String, floor: u32 },
+    Fled { enemy: String, floor: u32 },
+    LoreFound { title: String, floor: u32 },
+    NearDeath { floor: u32 },
+}
+
+impl RunEvent {
+    fn kind(&self) -> EventKind {
+        match self {
+            Self::Defeated { .. } => EventKind::Defeated,
+            Self::BossDefeated { .. } => EventKind::BossDefeated,
+            Self::Spared { .. } => EventKind::Spared,
+            Self::Fled { .. } => EventKind::Fled,
+            Self::LoreFound { .. } => EventKind::LoreFound,
+            Self::NearDeath { .. } => EventKind::NearDeath,
+        }
+    }
+
+    /// Value for a template slot, if this event provides it
+    fn slot(&self, name: &str) -> Option<String> {
+        match (self, name) {
+            (Self::Defeated { enemy, .. }
+            | Self::BossDefeated { enemy, .. }
+            | Self::Spared { enemy, .. }
+            | Self::Fled { enemy, .. }, "enemy") => Some(enemy.clone()),
+            (Self::LoreFound { title, .. }, "lore") => Some(title.clone()),
+            (Self::Defeated { floor, .. }
+            | Self::BossDefeated { floor, .. }
+            | Self::Spared { floor, .. }
+            | Self::Fled { floor, .. }
+            | Self::LoreFound { floor, .. }
+            | Self::NearDeath { floor }, "floor") => Some(floor.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventKind {
+    Defeated,
+    BossDefeated,
+    Spared,
+    Fled,
+    LoreFound,
+    NearDeath,
+}
+
+/// A prompt template tied to one kind of run event
+struct SentenceTemplate {
+    kind: EventKind,
+    text: &'static str,
+}
+
+const TEMPLATES: &[SentenceTemplate] = &[
+    SentenceTemplate { kind: EventKind::Spared, text: "The {enemy} you spared on floor {floor} is remembered here." },
+    SentenceTemplate { kind: EventKind::Spared, text: "Mercy shown to the {enemy} still echoes below." },
+    SentenceTemplate { kind: EventKind::Defeated, text: "The {enemy} you felled on floor {floor} does not rest." },
+    SentenceTemplate { kind: EventKind::Defeated, text: "Word of the fallen {enemy} reached these depths before you." },
+    SentenceTemplate { kind: EventKind::BossDefeated, text: "The {enemy} lies broken. Its silence fills the deep." },
+    SentenceTemplate { kind: EventKind::BossDefeated, text: "Even here they whisper of the {enemy} you unmade." },
+    SentenceTemplate { kind: EventKind::Fled, text: "The {enemy} you fled on floor {floor} still hunts you." },
+    SentenceTemplate { kind: EventKind::LoreFound, text: "The words of the {lore} follow you down." },
+    SentenceTemplate { kind: EventKind::LoreFound, text: "You read the {lore}. It has not let you go." },
+    SentenceTemplate { kind: EventKind::NearDeath, text: "On floor {floor} you nearly fell. The stones remember your blood." },
+];
+
+/// Fill a template's `{slot}`s from an event. None if a slot has no value.
+fn fill(template: &str, event: &RunEvent) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}')? + start;
+        out.push_str(&rest[..start]);
+        out.push_str(&event.slot(&rest[start + 1..end])?);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+/// Whether a stitched sentence is fit to be a combat prompt:
+/// short enough for combat, no banned words, calm punctuation, typable.
+pub fn validate(sentence: &str, rules: &EconomyOfLanguage) -> bool {
+    let words: Vec<&str> = sentence.split_whitespace().collect();
+    let max_words = rules.max_sentence_length.get("combat").copied().unwrap_or(12);
+    if words.len() < MIN_WORDS || words.len() > max_words {
+        return false;
+    }
+
+    let banned = words.iter().any(|w| {
+        let bare = w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        rules.banned_words.contains(&bare)
+    });
+    if banned {
+        return false;
+    }
+
+    sentence.chars().all(|c| c.is_alphanumeric() || ALLOWED_PUNCTUATION.contains(c))
+}
+
+/// What has happened this run, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct RunChronicle {
+    pub events: Vec<RunEvent>,
+}
+
+impl RunChronicle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, event: RunEvent) {
+        self.events.push(event);
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Every valid sentence the chronicle can currently produce, shuffled
+    pub fn stitch_all(&self, rng: &mut impl Rng) -> Vec<String> {
+        let rules = EconomyOfLanguage::canonical();
+        let mut sentences: Vec<String> = self.events.iter()
+            .flat_map(|event| {
+                TEMPLATES.iter()
+                    .filter(move |t| t.kind == event.kind())
+                    .filter_map(move |t| fill(t.text, event))
+            })
+            .filter(|s| validate(s, &rules))
+            .collect();
+        sentences.sort();
+        sentences.dedup();
+        sentences.shuffle(rng);
+        sentences
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_slots() {
+        let event = RunEvent::Spared { enemy: "Goblin Scout".to_string(), floor: 2 };
+        assert_eq!(
+            fill(TEMPLATES[0].text, &event).as_deref(),
+            Some("The Goblin Scout you spared on floor 2 is remembered here.")
+        );
+        // NearDeath has no enemy to fill {enemy} with
+        assert_eq!(fill("The {enemy} waits.", &RunEvent::NearDeath { floor: 1 }), None);
+    }
+
+    #[test]
+    fn test_validation_rules() {
+        let rules = EconomyOfLanguage::canonical();
+        assert!(validate("The words of the Faded Tapestry follow you down.", &rules));
+        assert!(!validate("Too short.", &rules));
+        assert!(!validate("The epic goblin you felled does not rest.", &rules));
+        assert!(!validate("The goblin you felled does not rest!", &rules));
+        assert!(!validate("The 💀 Wraith you felled on floor 3 does not rest.", &rules));
+    }
+
+    #[test]
+    fn test_chronicle_stitches_only_valid_prompts() {
+        let mut chronicle = RunChronicle::new();
+        assert!(chronicle.stitch_all(&mut rand::thread_rng()).is_empty());
+
+        chronicle.record(RunEvent::Defeated { enemy: "Goblin Scout".to_string(), floor: 1 });
+        chronicle.record(RunEvent::Defeated {
+            enemy: "Ancient Extraordinarily Verbose Many Named Thing Of Old".to_string(),
+            floor: 1,
+        });
+        let sentences = chronicle.stitch_all(&mut rand::thread_rng());
+        assert_eq!(sentences.len(), 2);
+        assert!(sentences.iter().all(|s| s.contains("Goblin Scout")));
+    }
+}
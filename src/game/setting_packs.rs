@@ -0,0 +1,430 @@
+//! Setting Packs - One coherent canon per run
+//!
+//! The world has been written twice. Each telling is bundled as a setting
+//! pack, and a run is played in one of them:
+//! - The Fall of Valdris: a kingdom undone when the Archon Malachar's
+//!   ritual tore the Breach open
+//! - The Unwriting: a civilization of living language silenced when the
+//!   First Speaker tried to unwrite death
+//!
+//! A pack carries everything that would otherwise contradict the other:
+//! the cosmology the codex shows, each zone's name, words and sentences,
+//! the story's sentences as the descent goes on, what the faction names
+//! are, and what the bosses say. The setting is picked on the class screen
+//! (`s`) and holds for the whole run.
+//!
+//! Design: like the zone route, the run's setting is thread-local, so the
+//! word pools, zone names and faction names follow it without a setting
+//! being threaded through every caller. Valdris is the default, and its
+//! pack points at the pools the game has always used.
+
+use std::cell::Cell;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::lore_words::LoreWords;
+
+use super::deep_lore::Cosmology;
+use super::lore_fragments::LoreCategory;
+use super::narrative::Faction;
+use super::world_integration::FloorZone;
+
+/// Which telling of the world a run is played in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Setting {
+    #[default]
+    Valdris,
+    Unwriting,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 2] = [Setting::Valdris, Setting::Unwriting];
+
+    /// The next setting on the class screen
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Valdris => Self::Unwriting,
+            Self::Unwriting => Self::Valdris,
+        }
+    }
+
+    pub fn pack(self) -> &'static SettingPack {
+        match self {
+            Self::Valdris => &VALDRIS,
+            Self::Unwriting => &UNWRITING,
+        }
+    }
+}
+
+/// A cosmology entry for the codex
+#[derive(Debug, Clone, PartialEq)]
+pub struct CosmologyEntry {
+    pub title: String,
+    pub text: String,
+    /// Lore fragments of this category open the entry
+    pub opened_by: LoreCategory,
+}
+
+/// Everything a canon decides
+pub struct SettingPack {
+    pub name: &'static str,
+    /// What the world was, what broke it, and what waits below
+    pub premise: &'static str,
+    pub cosmology: fn() -> Vec<CosmologyEntry>,
+    /// Zone names, in `FloorZone` declaration order
+    pub zone_names: [&'static str; 6],
+    /// Faction names, in `Faction` declaration order
+    pub faction_names: [&'static str; 5],
+    pub zone_words: fn(FloorZone) -> Vec<&'static str>,
+    pub zone_sentences: fn(FloorZone) -> Vec<&'static str>,
+    /// The story's sentences by floor of the descent
+    pub narrative: fn(u32) -> Vec<&'static str>,
+    /// What a boss of this name says, if it has lines of its own
+    pub boss_sentences: fn(&str) -> Option<Vec<&'static str>>,
+}
+
+impl SettingPack {
+    pub fn zone_name(&self, zone: FloorZone) -> &'static str {
+        self.zone_names[zone_index(zone)]
+    }
+
+    pub fn faction_name(&self, faction: Faction) -> &'static str {
+        self.faction_names[faction_index(faction)]
+    }
+}
+
+fn zone_index(zone: FloorZone) -> usize {
+    match zone {
+        FloorZone::ShatteredHalls => 0,
+        FloorZone::SunkenArchives => 1,
+        FloorZone::BlightedGardens => 2,
+        FloorZone::ClockworkDepths => 3,
+        FloorZone::VoidsEdge => 4,
+        FloorZone::TheBreach => 5,
+    }
+}
+
+fn faction_index(faction: Faction) -> usize {
+    match faction {
+        Faction::MagesGuild => 0,
+        Faction::TempleOfDawn => 1,
+        Faction::RangersOfTheWild => 2,
+        Faction::ShadowGuild => 3,
+        Faction::MerchantConsortium => 4,
+    }
+}
+
+thread_local! {
+    static SETTING: Cell<Setting> = const { Cell::new(Setting::Valdris) };
+}
+
+/// Play the run in `setting`
+pub fn set(setting: Setting) {
+    SETTING.with(|s| s.set(setting));
+}
+
+/// The setting this run is played in
+pub fn current() -> Setting {
+    SETTING.with(|s| s.get())
+}
+
+/// The current setting's pack
+pub fn pack() -> &'static SettingPack {
+    current().pack()
+}
+
+// ============================================================================
+// THE FALL OF VALDRIS
+// ============================================================================
+
+static VALDRIS: SettingPack = SettingPack {
+    name: "The Fall of Valdris",
+    premise: "Forty-seven years ago the Archon Malachar sought godhood and tore the Breach open. Valdris fell. \
+        The Blight still spreads from the wound, and something below knows your face.",
+    cosmology: valdris_cosmology,
+    zone_names: [
+        "The Shattered Halls",
+        "The Sunken Archives",
+        "The Blighted Gardens",
+        "The Clockwork Depths",
+        "The Void's Edge",
+        "The Breach",
+    ],
+    faction_names: [
+        "The Mages Guild",
+        "The Temple of Dawn",
+        "The Rangers of the Wild",
+        "The Shadow Guild",
+        "The Merchant Consortium",
+    ],
+    zone_words: LoreWords::valdris_zone_words,
+    zone_sentences: LoreWords::valdris_zone_sentences,
+    narrative: LoreWords::valdris_narrative,
+    boss_sentences: LoreWords::valdris_boss_sentences,
+};
+
+fn valdris_cosmology() -> Vec<CosmologyEntry> {
+    let cosmos = Cosmology::canonical();
+    let ages = cosmos.ages;
+    let entry = |title: &str, text: String, opened_by| CosmologyEntry { title: title.to_string(), text, opened_by };
+    vec![
+        entry("The Age of Dawn", ages.age_of_dawn.description, LoreCategory::AncientHistory),
+        entry("The Age of Crowns", ages.age_of_crowns.description, LoreCategory::AncientHistory),
+        entry("The Sundering", cosmos.the_sundering.what_happened, LoreCategory::TheSundering),
+        entry("The Age of Shadow", ages.age_of_shadow.description, LoreCategory::TheSundering),
+        entry("The Corruption", cosmos.corruption_truth.actual_nature, LoreCategory::BlightStudies),
+        entry("Before Memory", cosmos.before_memory.the_truth_beneath, LoreCategory::Prophecy),
+    ]
+}
+
+// ============================================================================
+// THE UNWRITING
+// ============================================================================
+
+static UNWRITING: SettingPack = SettingPack {
+    name: "The Unwriting",
+    premise: "In the Age of Writing, words made the world. Then the First Speaker tried to unwrite death, \
+        and the First Silence fell on Logos Prime. Meaning is dying outward from the wound.",
+    cosmology: unwriting_cosmology,
+    zone_names: [
+        "The Ruins Beneath Haven",
+        "The Drowned Athenaeum",
+        "The Withered Grove",
+        "The Mechanist Undercity",
+        "The Corrupted Wastes",
+        "The First Library",
+    ],
+    faction_names: ["The Scribes", "The Mechanists", "The Naturalists", "The ShadowWriters", "The Archivists"],
+    zone_words: unwriting_zone_words,
+    zone_sentences: unwriting_zone_sentences,
+    narrative: unwriting_narrative,
+    boss_sentences: unwriting_boss_sentences,
+};
+
+fn unwriting_cosmology() -> Vec<CosmologyEntry> {
+    let entry = |title: &str, text: &str, opened_by| CosmologyEntry {
+        title: title.to_string(),
+        text: text.to_string(),
+        opened_by,
+    };
+    vec![
+        entry(
+            "The Age of Writing",
+            "Before the Silence, words were not symbols but substance. A thing written true became true, and \
+                Logos Prime was built from sentences typed by hands that never slipped.",
+            LoreCategory::AncientHistory,
+        ),
+        entry(
+            "Logos Prime",
+            "The city of living language, founded on three principles: that words have weight, that weight \
+                must be carried, and that some words should never be written at all.",
+            LoreCategory::AncientHistory,
+        ),
+        entry(
+            "The First Silence",
+            "The First Speaker loved someone who fell sick, and tried to unwrite death itself. Death is not a \
+                word. The attempt tore a silence into the world, and Logos Prime fell into it.",
+            LoreCategory::TheSundering,
+        ),
+        entry(
+            "The Unwriting",
+            "The Corruption is not an invader but an absence: meaning draining out through the wound the First \
+                Silence left. What it touches forgets what it was, and then forgets that it forgot.",
+            LoreCategory::BlightStudies,
+        ),
+        entry(
+            "The One Who Returns",
+            "One will come who is many. One who died yet lives. One who remembers by forgetting. The Tomorrow \
+                Text does not say whether they come to close the wound or to finish what they began.",
+            LoreCategory::Prophecy,
+        ),
+    ]
+}
+
+fn unwriting_zone_words(zone: FloorZone) -> Vec<&'static str> {
+    match zone {
+        FloorZone::ShatteredHalls => vec![
+            "haven", "refuge", "shelter", "lantern", "cellar",
+            "ruin", "rubble", "stair", "tunnel", "archway",
+            "silence", "echo", "hush", "murmur", "rumor",
+            "speaker", "scribe", "letter", "ink", "quill",
+            "forget", "remember", "lost", "hollow", "faded",
+        ],
+        FloorZone::SunkenArchives => vec![
+            "athenaeum", "archive", "index", "catalogue", "volume",
+            "margin", "footnote", "folio", "binding", "spine",
+            "drowned", "sodden", "ink", "blot", "smear",
+            "verity", "record", "keeper", "testament", "witness",
+            "secret", "sealed", "forbidden", "restricted", "buried",
+        ],
+        FloorZone::BlightedGardens => vec![
+            "grove", "root", "seed", "sapling", "bloom",
+            "song", "songline", "warden", "thorn", "bramble",
+            "withered", "wilted", "blight", "rot", "mildew",
+            "living", "growing", "wild", "tangle", "canopy",
+            "mourning", "grief", "loss", "tending", "patience",
+        ],
+        FloorZone::ClockworkDepths => vec![
+            "mechanist", "gear", "piston", "lever", "terminal",
+            "engine", "forge", "rivet", "gauge", "circuit",
+            "precise", "exact", "measured", "calibrated", "true",
+            "keystroke", "carriage", "platen", "ribbon", "typebar",
+            "faith", "doctrine", "pure", "certain", "steady",
+        ],
+        FloorZone::VoidsEdge => vec![
+            "wastes", "ash", "static", "blank", "erasure",
+            "unwritten", "unsaid", "unmade", "unspoken", "unknown",
+            "meaning", "absence", "nothing", "null", "void",
+            "shimmer", "drift", "fray", "unravel", "dissolve",
+            "silence", "wound", "hunger", "edge", "brink",
+        ],
+        FloorZone::TheBreach => vec![
+            "library", "first", "origin", "source", "beginning",
+            "speaker", "silence", "death", "grief", "love",
+            "unwrite", "rewrite", "undo", "restore", "choose",
+            "return", "reincarnate", "remember", "forgive", "release",
+            "word", "name", "truth", "ending", "again",
+        ],
+    }
+}
+
+fn unwriting_zone_sentences(zone: FloorZone) -> Vec<&'static str> {
+    match zone {
+        FloorZone::ShatteredHalls => vec![
+            "Haven was built on the bones of an older city.",
+            "Below the inn, the cellars go down further than anyone admits.",
+            "Someone has chalked a word on the wall to keep it from being forgotten.",
+            "The lanterns here burn on remembered oil.",
+            "Every refugee in Haven lost something to the Silence.",
+            "The stairs down were typed into being. Some of the steps have faded.",
+        ],
+        FloorZone::SunkenArchives => vec![
+            "The Athenaeum drowned rather than let the Silence read it.",
+            "Ink rises from the water like smoke from a snuffed candle.",
+            "Verity catalogued everything here, even her own regrets.",
+            "The index lists a book about you. The page is missing.",
+            "Some records are kept so they can be buried properly.",
+            "The Archivists remember everything and choose what to tell.",
+        ],
+        FloorZone::BlightedGardens => vec![
+            "The Naturalists sing the land's old paths back into it.",
+            "Where the Unwriting touches, flowers forget their colors.",
+            "A sapling grows through the floor, veined with shimmer.",
+            "Grief is a root. It grows deepest where it is not seen.",
+            "The Wardens tend what the Silence left alive.",
+            "Every songline here ends in the same broken note.",
+        ],
+        FloorZone::ClockworkDepths => vec![
+            "The Mechanists trusted gears because gears cannot lie.",
+            "The Last Functional Terminal still waits for a command.",
+            "Every key here was pressed by a hand that has since forgotten.",
+            "Precision is a kind of prayer, if you mean it.",
+            "Even the machines have started to corrupt.",
+            "Gears turn true when the words run clean.",
+        ],
+        FloorZone::VoidsEdge => vec![
+            "The Corrupted Wastes are what is left when meaning dies.",
+            "The ground here forgets your footsteps as you make them.",
+            "Something that used to be words is hunting in the ash.",
+            "Say your name aloud. Make sure it is still yours.",
+            "The Silence does not hate you. It does not know you are here.",
+            "Every word you type here holds the world together a little longer.",
+        ],
+        FloorZone::TheBreach => vec![
+            "The First Library is where the Silence began.",
+            "Death is not a word. You cannot unwrite what was never written.",
+            "You have stood here before. Many times.",
+            "The shelves hold every life you have already lived.",
+            "Close the wound, or finish what you began. Choose.",
+            "The last page is blank. It has been waiting for you.",
+        ],
+    }
+}
+
+fn unwriting_narrative(floor: u32) -> Vec<&'static str> {
+    match floor {
+        1..=3 => vec![
+            "The First Silence fell a lifetime ago.",
+            "Haven keeps the lanterns lit so no one forgets the way home.",
+            "You woke with no memory and calluses on your fingers.",
+            "The Unwriting spreads a little further every season.",
+            "Somewhere below, the wound in meaning is still open.",
+        ],
+        4..=7 => vec![
+            "The First Speaker was not a monster. They were grieving.",
+            "The Archivists have been watching someone for a long time.",
+            "The Tomorrow Text speaks of one who remembers by forgetting.",
+            "The factions argue while the Silence grows.",
+            "Your handwriting matches a journal you have never read.",
+        ],
+        _ => vec![
+            "The wound can be closed. But the cost may be everything.",
+            "You have tried this forty-seven times before.",
+            "The Herald of the Unwriting waits at the last door.",
+            "This time, the choice is yours to make.",
+            "Every word you have typed has led you here.",
+        ],
+    }
+}
+
+fn unwriting_boss_sentences(name: &str) -> Option<Vec<&'static str>> {
+    match name {
+        n if n.contains("Hollow Knight") => Some(vec![
+            "I guarded the Speaker's door, and I let them through.",
+            "My oath was written in Logos Prime. The ink outlived the city.",
+            "I do not remember my name. I remember my duty.",
+            "You have the Speaker's hands. Prove you have a better heart.",
+            "Every sentence I was given has been unwritten but this one.",
+            "Type true, or be forgotten with the rest.",
+        ]),
+        n if n.contains("Void Herald") => Some(vec![
+            "I am the silence after the last word.",
+            "You made me, Speaker. Have you come to finish me, or me you?",
+            "Meaning was a brief disturbance. I am the calm that follows.",
+            "Your words are already fading. Can you feel them go?",
+            "Death is not a word. Neither am I.",
+            "Type your last sentence. I will unwrite it kindly.",
+        ]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_pack_fills_every_zone() {
+        for setting in Setting::ALL {
+            let pack = setting.pack();
+            assert!(!(pack.cosmology)().is_empty());
+            for zone in [
+                FloorZone::ShatteredHalls,
+                FloorZone::SunkenArchives,
+                FloorZone::BlightedGardens,
+                FloorZone::ClockworkDepths,
+                FloorZone::VoidsEdge,
+                FloorZone::TheBreach,
+            ] {
+                assert!(!pack.zone_name(zone).is_empty());
+                assert!((pack.zone_words)(zone).len() >= 20, "{} has few words for {:?}", pack.name, zone);
+                assert!(!(pack.zone_sentences)(zone).is_empty());
+            }
+            assert!((pack.boss_sentences)("The Void Herald").is_some());
+        }
+    }
+
+    #[test]
+    fn test_a_run_keeps_to_its_canon() {
+        set(Setting::Unwriting);
+        assert_eq!(FloorZone::ShatteredHalls.name(), "The Ruins Beneath Haven");
+        assert_eq!(Faction::MagesGuild.name(), "The Scribes");
+        let words = LoreWords::get_zone_words(1);
+        assert!(!words.contains(&"valdris") && !words.contains(&"malachar"));
+        assert!((1..=10).all(|floor| LoreWords::get_narrative_sentences(floor).iter().all(|s| !s.contains("Valdris"))));
+
+        set(Setting::Valdris);
+        assert_eq!(FloorZone::ShatteredHalls.name(), "The Shattered Halls");
+        assert!(LoreWords::get_zone_words(1).contains(&"valdris"));
+    }
+}
@@ -0,0 +1,286 @@
+//! Settings - Everything in the config, changed from inside the game
+//!
+//! The settings screen (`o` on the title screen) lists every setting worth
+//! changing mid-session, grouped as the config file groups them:
+//! - audio: prompts spoken aloud, and dictation
+//! - display: theme, icons, screen shake, the unreliable narrator
+//! - typing: keyboard layout, latency, typo policy, combat mode
+//! - difficulty: the preset every fight is scaled by
+//! - accessibility: screen reader, reduced motion, shape markers
+//! - keybinds: `b` opens the keybinds screen
+//!
+//! A change takes effect the moment it is made, so a theme or an icon set
+//! is seen on the settings screen itself. Leaving saves them all to the
+//! config file; `u` puts back what they were when the screen opened.
+//!
+//! Design: a setting only knows how to read and step its own value in the
+//! config. Bringing the running game in line with the config (the theme
+//! drawn, the pronouncer, the keyboard feel) is GameState's, which does it
+//! after every change, so each setting stays a line or two.
+
+use super::combat_scheduler::CombatMode;
+use super::config::{DifficultyConfig, DifficultyPreset, GameConfig, TypingConfig};
+use super::keyboard_layout::KeyboardLayout;
+use super::latency::MAX_COMPENSATION_MS;
+use crate::ui::theme::{IconStyle, Theme};
+
+/// Backspaces a word allows under the limited typo policy
+pub const LIMITED_BACKSPACES: u32 = 3;
+
+/// Latency changed by each step
+const LATENCY_STEP_MS: u32 = 5;
+
+/// How far a mistyped letter can be taken back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypoPolicy {
+    /// Backspace as often as you like
+    Forgiving,
+    /// A few backspaces a word
+    Limited,
+    /// No backspace: a typo stands
+    Strict,
+}
+
+impl TypoPolicy {
+    const ALL: [TypoPolicy; 3] = [Self::Forgiving, Self::Limited, Self::Strict];
+
+    pub fn of(typing: &TypingConfig) -> Self {
+        match (typing.allow_backspace, typing.max_backspaces_per_word) {
+            (false, _) => Self::Strict,
+            (true, 0) => Self::Forgiving,
+            (true, _) => Self::Limited,
+        }
+    }
+
+    fn apply(&self, typing: &mut TypingConfig) {
+        (typing.allow_backspace, typing.max_backspaces_per_word) = match self {
+            Self::Forgiving => (true, 0),
+            Self::Limited => (true, LIMITED_BACKSPACES),
+            Self::Strict => (false, 0),
+        };
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Forgiving => "Forgiving",
+            Self::Limited => "Limited",
+            Self::Strict => "Strict",
+        }
+    }
+
+    /// Backspaces a word allows, if there is a limit
+    pub fn backspace_limit(&self) -> Option<u32> {
+        match self {
+            Self::Forgiving => None,
+            Self::Limited => Some(LIMITED_BACKSPACES),
+            Self::Strict => Some(0),
+        }
+    }
+}
+
+/// The presets the difficulty setting steps through
+const PRESETS: [DifficultyPreset; 3] = [DifficultyPreset::Story, DifficultyPreset::Normal, DifficultyPreset::Hard];
+
+/// The icon sets, the terminal's own first
+const ICONS: [Option<IconStyle>; 4] = [None, Some(IconStyle::Ascii), Some(IconStyle::Unicode), Some(IconStyle::Nerd)];
+
+/// One line of the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    SpeakPrompts,
+    Dictation,
+    Theme,
+    Icons,
+    ScreenShake,
+    UnreliableNarrator,
+    Layout,
+    Latency,
+    TypoPolicy,
+    CombatMode,
+    Difficulty,
+    ScreenReader,
+    ReducedMotion,
+    TypingMarkers,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 14] = [
+        Self::SpeakPrompts,
+        Self::Dictation,
+        Self::Theme,
+        Self::Icons,
+        Self::ScreenShake,
+        Self::UnreliableNarrator,
+        Self::Layout,
+        Self::Latency,
+        Self::TypoPolicy,
+        Self::CombatMode,
+        Self::Difficulty,
+        Self::ScreenReader,
+        Self::ReducedMotion,
+        Self::TypingMarkers,
+    ];
+
+    pub fn section(&self) -> &'static str {
+        match self {
+            Self::SpeakPrompts | Self::Dictation => "Audio",
+            Self::Theme | Self::Icons | Self::ScreenShake | Self::UnreliableNarrator => "Display",
+            Self::Layout | Self::Latency | Self::TypoPolicy | Self::CombatMode => "Typing",
+            Self::Difficulty => "Difficulty",
+            Self::ScreenReader | Self::ReducedMotion | Self::TypingMarkers => "Accessibility",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SpeakPrompts => "Speak prompts",
+            Self::Dictation => "Dictation",
+            Self::Theme => "Theme",
+            Self::Icons => "Icons",
+            Self::ScreenShake => "Screen shake",
+            Self::UnreliableNarrator => "Narrator",
+            Self::Layout => "Keyboard layout",
+            Self::Latency => "Latency",
+            Self::TypoPolicy => "Typo policy",
+            Self::CombatMode => "Combat mode",
+            Self::Difficulty => "Difficulty",
+            Self::ScreenReader => "Screen reader",
+            Self::ReducedMotion => "Motion",
+            Self::TypingMarkers => "Shape markers",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::SpeakPrompts => "Read each new prompt aloud (needs the audio feature)",
+            Self::Dictation => "Hide the prompt and type only what you hear",
+            Self::Theme => "Colors, borders and icons, built in or from a theme file",
+            Self::Icons => "The finest icons drawn; Auto asks the terminal",
+            Self::ScreenShake => "Shake the fight when blows land",
+            Self::UnreliableNarrator => "Let the narration lie to you now and then",
+            Self::Layout => "The board the heatmap and key errors are read against",
+            Self::Latency => "Taken off every key interval, for slow terminals and keyboards",
+            Self::TypoPolicy => "Forgiving: backspace freely. Limited: 3 a word. Strict: none",
+            Self::CombatMode => "Enemies strike on their own clock, or wait for your word",
+            Self::Difficulty => "Enemy health and damage, your damage and the time you get, from the next fight",
+            Self::ScreenReader => "Plain prompt lines and a labeled log of what changed",
+            Self::ReducedMotion => "No particles and no screen shake",
+            Self::TypingMarkers => "Mark typed letters by shape as well as color",
+        }
+    }
+
+    /// The setting's value in `config`, as shown
+    pub fn value(&self, config: &GameConfig) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" }.to_string();
+        match self {
+            Self::SpeakPrompts => on_off(config.audio.pronunciation.enabled),
+            Self::Dictation => on_off(config.audio.pronunciation.dictation),
+            Self::Theme => config.display.theme.clone().unwrap_or_else(|| Theme::default().name),
+            Self::Icons => config.display.icons.map_or("Auto", |i| i.name()).to_string(),
+            Self::ScreenShake => on_off(config.display.screen_shake),
+            Self::UnreliableNarrator => if config.display.unreliable_narrator { "Unreliable" } else { "Reliable" }.to_string(),
+            Self::Layout => config.typing.layout.name().to_string(),
+            Self::Latency => format!("{} ms", config.typing.latency_ms),
+            Self::TypoPolicy => TypoPolicy::of(&config.typing).name().to_string(),
+            Self::CombatMode => config.combat.mode.name().to_string(),
+            Self::Difficulty => config.difficulty.preset.name().to_string(),
+            Self::ScreenReader => on_off(config.display.screen_reader),
+            Self::ReducedMotion => if config.display.reduced_motion { "Reduced" } else { "Full" }.to_string(),
+            Self::TypingMarkers => on_off(config.display.typing_markers),
+        }
+    }
+
+    /// Step the setting's value in `config` forward or back; `themes` are
+    /// the names of the themes to pick from
+    pub fn adjust(&self, config: &mut GameConfig, forward: bool, themes: &[String]) {
+        let step = if forward { 1 } else { -1 };
+        match self {
+            Self::SpeakPrompts => flip(&mut config.audio.pronunciation.enabled),
+            Self::Dictation => flip(&mut config.audio.pronunciation.dictation),
+            Self::Theme => {
+                let current = config.display.theme.clone().unwrap_or_else(|| Theme::default().name);
+                let name = cycle(themes, &current, step);
+                config.display.theme = Some(name).filter(|n| *n != Theme::default().name);
+            }
+            Self::Icons => config.display.icons = cycle(&ICONS, &config.display.icons, step),
+            Self::ScreenShake => flip(&mut config.display.screen_shake),
+            Self::UnreliableNarrator => flip(&mut config.display.unreliable_narrator),
+            Self::Layout => config.typing.layout = cycle(KeyboardLayout::all(), &config.typing.layout, step),
+            Self::Latency => {
+                let latency = config.typing.latency_ms;
+                config.typing.latency_ms = if forward {
+                    (latency + LATENCY_STEP_MS).min(MAX_COMPENSATION_MS)
+                } else {
+                    latency.saturating_sub(LATENCY_STEP_MS)
+                };
+            }
+            Self::TypoPolicy => cycle(&TypoPolicy::ALL, &TypoPolicy::of(&config.typing), step).apply(&mut config.typing),
+            Self::CombatMode => config.combat.mode = cycle(&[CombatMode::RealTime, CombatMode::TurnBased], &config.combat.mode, step),
+            Self::Difficulty => {
+                config.difficulty = DifficultyConfig::from_preset(cycle(&PRESETS, &config.difficulty.preset, step));
+            }
+            Self::ScreenReader => flip(&mut config.display.screen_reader),
+            Self::ReducedMotion => flip(&mut config.display.reduced_motion),
+            Self::TypingMarkers => flip(&mut config.display.typing_markers),
+        }
+    }
+}
+
+fn flip(on: &mut bool) {
+    *on = !*on;
+}
+
+/// The option `step` places from `current`, wrapping; from an option not
+/// in the list, the first
+fn cycle<T: Clone + PartialEq>(options: &[T], current: &T, step: i32) -> T {
+    let len = options.len() as i32;
+    let next = match options.iter().position(|o| o == current) {
+        Some(i) => (i as i32 + step).rem_euclid(len),
+        None => 0,
+    };
+    options[next as usize].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_setting_steps_forward_and_back_to_where_it_was() {
+        let themes = vec!["Default".to_string(), "Solarized Dark".to_string()];
+        let mut config = GameConfig::default();
+        for setting in Setting::ALL {
+            let before = setting.value(&config);
+            setting.adjust(&mut config, true, &themes);
+            assert_ne!(setting.value(&config), before, "{} did not change", setting.name());
+            setting.adjust(&mut config, false, &themes);
+            assert_eq!(setting.value(&config), before, "{} did not come back", setting.name());
+        }
+        // The default theme is kept as no theme at all
+        Setting::Theme.adjust(&mut config, true, &themes);
+        Setting::Theme.adjust(&mut config, true, &themes);
+        assert_eq!(config.display.theme, None);
+        // Latency stops at the ends rather than wrapping
+        Setting::Latency.adjust(&mut config, false, &themes);
+        assert_eq!(config.typing.latency_ms, 0);
+    }
+
+    #[test]
+    fn test_the_typo_policy_is_read_from_and_written_to_the_backspace_settings() {
+        let mut typing = TypingConfig::default();
+        assert_eq!(TypoPolicy::of(&typing), TypoPolicy::Forgiving);
+        TypoPolicy::Strict.apply(&mut typing);
+        assert!(!typing.allow_backspace);
+        assert_eq!(TypoPolicy::of(&typing).backspace_limit(), Some(0));
+        TypoPolicy::Limited.apply(&mut typing);
+        assert_eq!(TypoPolicy::of(&typing).backspace_limit(), Some(LIMITED_BACKSPACES));
+
+        let mut config = GameConfig {
+            difficulty: DifficultyConfig::from_preset(DifficultyPreset::Ironman),
+            ..GameConfig::default()
+        };
+        Setting::Difficulty.adjust(&mut config, true, &[]);
+        assert_eq!(config.difficulty.preset, DifficultyPreset::Story);
+        assert_eq!(config.difficulty.enemy_hp_mult, 0.7);
+    }
+}
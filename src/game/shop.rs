@@ -0,0 +1,207 @@
+//! Shop - Somewhere to spend the gold
+//!
+//! Merchants stock their shelves from the shop data: potions, combat
+//! consumables, typing gear, jokers, healing on the spot and word lists.
+//! Prices climb floor by floor, and the Merchant Consortium's opinion of
+//! you moves them:
+//! - good standing, or membership in a faction that grants it, earns a discount
+//! - bad standing gets you gouged
+//!
+//! Word lists (lexicons) are learned for the rest of the run. Their words
+//! turn up among your prompts and strike harder; you paid to know them.
+//!
+//! Design: stock and prices are settled once on entry; GameState applies
+//! whatever is bought.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::data::{GameData, Goods};
+use super::faction_system::{get_faction_penalties, FactionBenefit, FactionPenalty, FactionRelations, FactionStatus};
+use super::items::Item;
+use super::narrative::Faction;
+
+/// Price increase per floor below the first
+pub const FLOOR_MARKUP: f32 = 0.1;
+/// Chance a single-word prompt is drawn from the player's lexicon
+pub const LEXICON_CHANCE: f32 = 0.25;
+/// Damage multiplier for a word from the player's lexicon
+pub const LEXICON_POWER: f32 = 1.15;
+
+/// What an offer actually gives
+#[derive(Debug, Clone)]
+pub enum Ware {
+    Item(Item),
+    /// Restore this share of max HP
+    Heal { percent: f32 },
+    Lexicon { id: String },
+}
+
+/// Something on the shelf, priced for this visit
+#[derive(Debug, Clone)]
+pub struct ShopOffer {
+    pub name: String,
+    pub description: String,
+    pub price: i32,
+    pub ware: Ware,
+}
+
+impl ShopOffer {
+    fn new(name: &str, description: &str, base_price: i32, price_mult: f32, ware: Ware) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            price: ((base_price as f32 * price_mult).round() as i32).max(1),
+            ware,
+        }
+    }
+
+    /// Marker shown beside the offer
+    pub fn symbol(&self) -> &'static str {
+        match &self.ware {
+            Ware::Item(item) => item.rarity.symbol(),
+            Ware::Heal { .. } => "♥",
+            Ware::Lexicon { .. } => "✎",
+        }
+    }
+}
+
+/// A merchant's stock for one visit
+#[derive(Debug, Clone, Default)]
+pub struct Shop {
+    pub offers: Vec<ShopOffer>,
+    /// Multiplier applied to every base price
+    pub price_mult: f32,
+}
+
+impl Shop {
+    /// Fill the shelves for a shop on `floor`. Lexicons the player already
+    /// knows are not offered again.
+    pub fn stock(data: &GameData, floor: u32, price_mult: f32, known: &[String], rng: &mut impl Rng) -> Self {
+        let mut offers = Vec::new();
+        for shelf in &data.shop.shelves {
+            if rng.gen::<f32>() >= shelf.chance {
+                continue;
+            }
+            match shelf.goods {
+                Goods::Potions => {
+                    for item in Item::consumable_pool().choose_multiple(rng, shelf.count) {
+                        offers.push(item_offer(item.clone(), price_mult));
+                    }
+                }
+                Goods::CombatConsumables => {
+                    for item in data.items.shop_consumables().choose_multiple(rng, shelf.count) {
+                        offers.push(item_offer(Item::from_consumable(item), price_mult));
+                    }
+                }
+                Goods::TypingGear => {
+                    for gear in data.items.typing_gear().choose_multiple(rng, shelf.count) {
+                        offers.push(item_offer(Item::from_equipment(gear), price_mult));
+                    }
+                }
+                Goods::Jokers => {
+                    for item in Item::joker_pool().choose_multiple(rng, shelf.count) {
+                        offers.push(item_offer(item.clone(), price_mult));
+                    }
+                }
+                Goods::Healing => {
+                    for heal in data.shop.healing.choose_multiple(rng, shelf.count) {
+                        let ware = Ware::Heal { percent: heal.percent };
+                        offers.push(ShopOffer::new(&heal.name, &heal.description, heal.base_price, price_mult, ware));
+                    }
+                }
+                Goods::Lexicons => {
+                    let unknown: Vec<_> = data.shop.lexicons.iter()
+                        .filter(|l| l.min_floor <= floor && !known.contains(&l.id))
+                        .collect();
+                    for lexicon in unknown.choose_multiple(rng, shelf.count) {
+                        let ware = Ware::Lexicon { id: lexicon.id.clone() };
+                        let description = format!("{} Learn: {}", lexicon.description, lexicon.words.join(", "));
+                        offers.push(ShopOffer::new(&lexicon.name, &description, lexicon.base_price, price_mult, ware));
+                    }
+                }
+            }
+        }
+        Self { offers, price_mult }
+    }
+
+    /// How the prices compare to list price, for the shop header
+    pub fn price_note(&self) -> Option<String> {
+        let percent = ((self.price_mult - 1.0) * 100.0).round() as i32;
+        match percent {
+            0 => None,
+            p if p > 0 => Some(format!("+{}% prices", p)),
+            p => Some(format!("{}% prices", p)),
+        }
+    }
+}
+
+fn item_offer(item: Item, price_mult: f32) -> ShopOffer {
+    let (name, description, price) = (item.name.clone(), item.description.clone(), item.price);
+    ShopOffer::new(&name, &description, price, price_mult, Ware::Item(item))
+}
+
+/// Price multiplier for a shop on `floor`, given the player's standings
+pub fn price_mult(floor: u32, relations: &FactionRelations) -> f32 {
+    let floor_mult = 1.0 + floor.saturating_sub(1) as f32 * FLOOR_MARKUP;
+    floor_mult * faction_mult(relations)
+}
+
+/// Discount for good standing with the Consortium (or a faction perk),
+/// markup for bad
+pub fn faction_mult(relations: &FactionRelations) -> f32 {
+    let consortium = Faction::MerchantConsortium;
+    let standing_discount: f32 = match relations.status(&consortium) {
+        FactionStatus::Friendly => 0.05,
+        FactionStatus::Honored => 0.10,
+        FactionStatus::Revered => 0.15,
+        FactionStatus::Exalted => 0.20,
+        _ => 0.0,
+    };
+    let perk_discount = relations.current_benefits().iter()
+        .filter_map(|b| match b {
+            FactionBenefit::ShopDiscount { percent } => Some(*percent),
+            _ => None,
+        })
+        .fold(0.0, f32::max);
+    let gouging = get_faction_penalties(&consortium, relations.standing(&consortium)).iter()
+        .filter_map(|p| match p {
+            FactionPenalty::PriceGouging { multiplier } => Some(*multiplier),
+            _ => None,
+        })
+        .fold(1.0, f32::max);
+    gouging * (1.0 - standing_discount.max(perk_discount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prices_climb_with_floors_and_standing() {
+        let mut relations = FactionRelations::new();
+        assert_eq!(price_mult(1, &relations), 1.0);
+        assert!((price_mult(6, &relations) - 1.5).abs() < 1e-4);
+
+        relations.standings.insert(Faction::MerchantConsortium, 60);
+        assert!((faction_mult(&relations) - 0.9).abs() < 1e-4);
+        relations.standings.insert(Faction::MerchantConsortium, -60);
+        assert!((faction_mult(&relations) - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_stock_skips_known_lexicons() {
+        let data = GameData::new();
+        let mut rng = rand::thread_rng();
+        let all: Vec<String> = data.shop.lexicons.iter().map(|l| l.id.clone()).collect();
+        for _ in 0..20 {
+            let shop = Shop::stock(&data, 5, 1.2, &all, &mut rng);
+            assert!(shop.offers.iter().all(|o| !matches!(o.ware, Ware::Lexicon { .. })));
+            assert!(shop.offers.iter().any(|o| matches!(o.ware, Ware::Heal { .. })));
+        }
+        let shop = Shop::stock(&data, 1, 1.0, &[], &mut rng);
+        let potion = shop.offers.iter().find(|o| matches!(o.ware, Ware::Item(_))).unwrap();
+        assert!(potion.price > 0);
+        assert_eq!(Shop { offers: Vec::new(), price_mult: 0.9 }.price_note().as_deref(), Some("-10% prices"));
+    }
+}
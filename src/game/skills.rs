@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::player_avatar::PlayerClass;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillTree {
     pub trees: HashMap<SkillTreeType, Vec<Skill>>,
@@ -32,6 +34,18 @@ impl SkillTree {
         }
     }
     
+    /// The shared trees plus the class's own Discipline tree
+    pub fn for_class(class: PlayerClass) -> Self {
+        let mut tree = Self::new();
+        tree.trees.insert(SkillTreeType::Discipline, Self::discipline_tree(class));
+        tree
+    }
+
+    /// Skills of the class's own tree, in unlock order
+    pub fn discipline(&self) -> &[Skill] {
+        self.trees.get(&SkillTreeType::Discipline).map(Vec::as_slice).unwrap_or(&[])
+    }
+
     pub fn has_skill(&self, skill_id: &str) -> bool {
         self.unlocked_skills.contains(&skill_id.to_string())
     }
@@ -397,6 +411,55 @@ impl SkillTree {
         ]
     }
     
+    fn discipline_tree(class: PlayerClass) -> Vec<Skill> {
+        let nodes: [(&str, &str, SkillEffect, &str); 3] = match class {
+            PlayerClass::Wordsmith => [
+                ("Sharp Tongue", "+5% critical hit chance.", SkillEffect::CritChance(0.05),
+                 "A Wordsmith never says in ten words what one can cut."),
+                ("Steady Voice", "Take 10% less damage.", SkillEffect::DamageReduction(0.10),
+                 "Shouting is for those who doubt their words."),
+                ("The Final Word", "Critical hits deal 2x damage.", SkillEffect::CritDamage(2.0),
+                 "Every argument ends somewhere. Yours end it."),
+            ],
+            PlayerClass::Codebreaker => [
+                ("Exploit", "+8% critical hit chance.", SkillEffect::CritChance(0.08),
+                 "Every cipher has a flaw. Every enemy, too."),
+                ("Obfuscate", "+8% chance to evade attacks.", SkillEffect::EvasionChance(0.08),
+                 "Be unreadable, and you cannot be struck."),
+                ("Zero Day", "Critical hits deal 2.25x damage.", SkillEffect::CritDamage(2.25),
+                 "A weakness no one has seen yet is the deadliest of all."),
+            ],
+            PlayerClass::Chronicler => [
+                ("Marginalia", "+15% experience from fights.", SkillEffect::XPBonus(0.15),
+                 "The notes in the margins outlive the books."),
+                ("Bound Pages", "Take 10% less damage.", SkillEffect::DamageReduction(0.10),
+                 "A well-bound book survives the fire."),
+                ("Definitive Edition", "+20% damage on perfect words.", SkillEffect::PerfectWordDamageBonus(0.20),
+                 "Write it once. Write it right. Never write it again."),
+            ],
+            PlayerClass::Freelancer => [
+                ("Odd Jobs", "+10% experience from fights.", SkillEffect::XPBonus(0.10),
+                 "No task too small, no pay too strange."),
+                ("Any Port", "+5% chance to evade attacks.", SkillEffect::EvasionChance(0.05),
+                 "A freelancer is always half out the door."),
+                ("Hired Blade", "+8% critical hit chance.", SkillEffect::CritChance(0.08),
+                 "Paid by the word. Every one of them counts."),
+            ],
+        };
+        let prefix = class.name().to_lowercase();
+        nodes.into_iter().enumerate().map(|(i, (name, description, effect, lore))| Skill {
+            id: format!("{}_{}", prefix, i + 1),
+            name: name.to_string(),
+            description: description.to_string(),
+            tree: SkillTreeType::Discipline,
+            tier: i as u32 + 1,
+            cost: i as u32 + 1,
+            prerequisites: if i == 0 { vec![] } else { vec![format!("{}_{}", prefix, i)] },
+            effects: vec![effect],
+            lore: lore.to_string(),
+        }).collect()
+    }
+
     /// Get all active effects from unlocked skills
     pub fn get_active_effects(&self) -> Vec<SkillEffect> {
         let mut effects = Vec::new();
@@ -526,6 +589,8 @@ pub enum SkillTreeType {
     Wisdom,
     /// Focus on critical hits and evasion
     Shadow,
+    /// The player's class's own path
+    Discipline,
 }
 
 impl SkillTreeType {
@@ -536,6 +601,7 @@ impl SkillTreeType {
             SkillTreeType::Endurance => "Endurance",
             SkillTreeType::Wisdom => "Wisdom",
             SkillTreeType::Shadow => "Shadow",
+            SkillTreeType::Discipline => "Discipline",
         }
     }
     
@@ -546,6 +612,7 @@ impl SkillTreeType {
             SkillTreeType::Endurance => "The marathon, not the sprint. Outlast all opposition.",
             SkillTreeType::Wisdom => "Knowledge is power. Understanding is victory.",
             SkillTreeType::Shadow => "Strike unseen. Let them fear what they cannot predict.",
+            SkillTreeType::Discipline => "Your class's own path. No one else walks it.",
         }
     }
 }
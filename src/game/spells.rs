@@ -1,6 +1,7 @@
 //! Spells and magical abilities - cast by typing!
 
 use serde::{Deserialize, Serialize};
+use crate::data::damage_types::DamageType;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SpellElement {
@@ -14,6 +15,19 @@ pub enum SpellElement {
     Nature,
 }
 
+impl SpellElement {
+    /// The damage type this element deals against resistances
+    pub fn damage_type(&self) -> DamageType {
+        match self {
+            Self::Physical => DamageType::Steel,
+            Self::Fire | Self::Ice | Self::Lightning | Self::Arcane => DamageType::Arcane,
+            Self::Nature => DamageType::Nature,
+            Self::Dark => DamageType::Void,
+            Self::Holy => DamageType::Word,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SpellTarget {
     Enemy,
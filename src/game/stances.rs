@@ -0,0 +1,129 @@
+//! Stances - How you hold your hands, chosen between words
+//!
+//! Switching stance trades one strength for another:
+//! - Aggressive: speed bonuses hit much harder, but every wrong key hurts you
+//! - Guarded: enemy blows land softer, but critical hits come rarely
+//! - Focused: the rhythm bonus is doubled
+//!
+//! Design: a stance is a bundle of coefficients. The keystroke impact
+//! reads the speed and rhythm ones through ImpactParams; CombatState
+//! applies the rest to damage dealt and taken.
+
+use super::typing_impact::ImpactParams;
+
+/// HP lost per wrong keystroke in the Aggressive stance
+const AGGRESSIVE_RECOIL: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Stance {
+    #[default]
+    Balanced,
+    Aggressive,
+    Guarded,
+    Focused,
+}
+
+impl Stance {
+    /// The stance after this one when cycling
+    pub fn next(self) -> Self {
+        match self {
+            Stance::Balanced => Stance::Aggressive,
+            Stance::Aggressive => Stance::Guarded,
+            Stance::Guarded => Stance::Focused,
+            Stance::Focused => Stance::Balanced,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stance::Balanced => "Balanced",
+            Stance::Aggressive => "Aggressive",
+            Stance::Guarded => "Guarded",
+            Stance::Focused => "Focused",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Stance::Balanced => "⚖",
+            Stance::Aggressive => "🔥",
+            Stance::Guarded => "🛡",
+            Stance::Focused => "◎",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Stance::Balanced => "No strengths, no weaknesses.",
+            Stance::Aggressive => "Speed hits harder - but mistakes cut you.",
+            Stance::Guarded => "Blows land softer - but crits are rare.",
+            Stance::Focused => "A steady rhythm counts double.",
+        }
+    }
+
+    /// Scale on the speed bonus (the part above 1x)
+    pub fn speed_bonus_mult(&self) -> f32 {
+        if *self == Stance::Aggressive { 1.5 } else { 1.0 }
+    }
+
+    /// Scale on the rhythm bonus (the part above 1x)
+    pub fn rhythm_bonus_mult(&self) -> f32 {
+        if *self == Stance::Focused { 2.0 } else { 1.0 }
+    }
+
+    /// HP lost per wrong keystroke
+    pub fn error_recoil(&self) -> i32 {
+        if *self == Stance::Aggressive { AGGRESSIVE_RECOIL } else { 0 }
+    }
+
+    /// Multiplier on damage the enemy deals
+    pub fn damage_taken_mult(&self) -> f32 {
+        if *self == Stance::Guarded { 0.7 } else { 1.0 }
+    }
+
+    /// Multiplier on critical hit chance
+    pub fn crit_chance_mult(&self) -> f32 {
+        if *self == Stance::Guarded { 0.5 } else { 1.0 }
+    }
+
+    /// Keystroke impact parameters in this stance
+    pub fn apply(&self, params: ImpactParams) -> ImpactParams {
+        ImpactParams {
+            speed_bonus_mult: params.speed_bonus_mult * self.speed_bonus_mult(),
+            rhythm_bonus_mult: params.rhythm_bonus_mult * self.rhythm_bonus_mult(),
+            ..params
+        }
+    }
+}
+
+/// Scale the bonus part (above 1x) of a multiplier
+pub fn scale_bonus(mult: f32, scale: f32) -> f32 {
+    if mult > 1.0 { 1.0 + (mult - 1.0) * scale } else { mult }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_visits_every_stance() {
+        let mut stance = Stance::default();
+        let mut seen = vec![stance];
+        for _ in 0..3 {
+            stance = stance.next();
+            seen.push(stance);
+        }
+        assert_eq!(seen, vec![Stance::Balanced, Stance::Aggressive, Stance::Guarded, Stance::Focused]);
+        assert_eq!(stance.next(), Stance::Balanced);
+    }
+
+    #[test]
+    fn test_bonuses_scale_only_above_one() {
+        assert_eq!(scale_bonus(1.5, 2.0), 2.0);
+        assert_eq!(scale_bonus(0.5, 2.0), 0.5);
+        let params = Stance::Focused.apply(ImpactParams::default());
+        assert_eq!(params.rhythm_bonus_mult, 2.0);
+        assert_eq!(params.speed_bonus_mult, 1.0);
+        assert_eq!(Stance::Balanced.apply(ImpactParams::default()), ImpactParams::default());
+    }
+}
@@ -1,30 +1,100 @@
 //! Game state management - the heart of the roguelike!
 
 use serde::{Deserialize, Serialize};
+use rand::Rng;
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::Instant;
 use crate::game::{
-    player::Player,
+    player::{Class, Player},
     enemy::Enemy,
-    combat::CombatState,
+    combat::{CombatState, CombatPhase},
+    combat_immersion::KeystrokeFeedback,
     dungeon::Dungeon,
-    items::Item,
+    items::{Item, ItemEffect},
     events::GameEvent,
     help_system::{HelpSystem, HintManager},
     tutorial::{TutorialState, TutorialProgress},
+    training::{LessonProgress, Training},
     typing_feel::TypingFeel,
-    faction_system::FactionRelations,
-    meta_progression::MetaProgress,
+    faction_system::{FactionRelations, FactionStatus},
+    meta_progression::{self, MetaProgress, RunStats, RunSummary},
     event_bus::{EventBus, GameEvent as BusEvent, CombatOutcome},
     narrative_seed::{NarrativeSeed, TypingModifier},
     skills::SkillTree,
     voice_system::{FactionVoice, build_faction_voices, generate_faction_dialogue, DialogueContext},
     narrative::Faction,
-    encounter_writing::{AuthoredEncounter, EncounterTracker, build_encounters},
-    run_modifiers::{RunModifiers, RunType},
+    encounter_writing::{AuthoredEncounter, EncounterTracker},
+    encounter_data,
+    run_modifiers::{Modifier, RunModifiers, RunType},
+    difficulty_director::DifficultyDirector,
+    auto_pause::AutoPause,
+    pause::FrozenClock,
+    config::{DifficultyConfig, DifficultyPreset, GameConfig, load_config, save_config},
+    status_line::{StatusLineExporter, RunStatus},
+    dev_console::{DevConsole, ConsoleContext},
+    pronunciation::Pronouncer,
+    world_integration::{pick_floor_lore, FloorZone},
+    zone_routes,
+    setting_packs::{self, Setting},
+    epilogue::{self, EpilogueSources, RunLog},
+    letters::{self, Letter, DECODE_GOLD},
+    cipher_notes::{self, CipherNote, Guess, NOTE_CHANCE},
+    prophecies::{self, FULFILLED, PROPHECY_CHANCE, SUBVERTED},
+    memories::{self, WHOLE_CHAPTER_HP},
+    transcript::{Source, Transcript, TranscriptView},
+    ideology::{Ideology, REVEAL_FLOOR, VOICE_CHANCE},
+    rumors::{self, RumorSubject, CACHE_GOLD},
+    living_book,
+    companions::{CompanionRoster, CompanionService},
+    consequence_engine::{self, ConsequenceTargets},
+    dialogue_tree::{self, DialogueGate, DialogueRunner, OptionView},
+    lore_fragments::LoreJournal,
+    dreams::{self, DreamLog, DreamRun, DreamStage},
+    comprehension::{ComprehensionCheck, question_for, XP_PER_FLOOR},
+    sentence_stitching::{RunChronicle, RunEvent},
+    map_travel::{clean_name, resolve, Destination, MapPrompt, MapPromptMode},
+    npc_voice::{NpcVoice, build_npc_voices},
+    player_avatar::PlayerClass,
+    patrons::{Patron, PLEDGE_STANDING, MECHANIST_HP_FRACTION, UNBOUND_ENDING},
+    typing_impact::{AttackThresholds, ImpactParams},
+    consequences::ChangeKind,
+    challenges::{self, Challenge, ChallengeBests},
+    run_rng::{self, Stream},
+    combat_replay::{CombatReplay, ReplayViewer},
+    latency::LatencyCalibration,
+    true_names,
+    shop::{self, Shop, Ware},
+    pacing::{PacingBeat, PacingController},
+    artifacts::{self, Relic, ARTIFACT_CHANCE},
+    curses::{self, Curse},
+    class_builds::{self, SKILL_POINTS_PER_LEVEL},
+    ascension::{self, AscensionRecord, AscensionRun},
+    key_accuracy::{self, KeyAccuracy},
+    stats_store::{self, StatsStore},
+    settings::{self, TypoPolicy},
+    keybinds::{self, Action, Keybinds},
+    endings::{self, EndingContext, Finale},
+    journal::{self, Journal},
+    codex::{self, CodexEntry, CodexSection, CodexSources},
+    clue_board::{ClueBoard, ClueFacts},
+    revelation::RevelationState,
+    npc_memory::{self, NpcMemory},
+    npc_lifecycle,
+    encounter_runner::{paragraphs, EncounterRun, EncounterStage},
+    encounter_scheduler::{EncounterScheduler, SchedulingContext},
+    leveling::LevelUpChoice,
+    world_state::{self, Aftermath, WorldState, DEEPEST_DESCENT, DESCENTS, ENCOUNTERS_RESOLVED},
+    scars::{self, Scar, Scars},
 };
 use crate::data::GameData;
+use crate::data::lore_words::FINAL_FLOOR;
 use crate::ui::effects::EffectsManager;
+use crate::ui::particles::Weather;
+use crate::ui::theme::{self, IconStyle, Theme};
+use crate::ui::{icon_fallback, screen_reader, themes};
+use crate::ui::screen_reader::ReaderLog;
+
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Scene {
@@ -47,6 +117,55 @@ pub enum Scene {
     Milestone,
     /// Meta-progression upgrade shop
     Upgrades,
+    /// Profile-wide world changes viewer
+    Consequences,
+    /// Installed community challenges browser
+    Challenges,
+    /// Keystroke playback of the fight just won
+    Replay,
+    /// Terminal latency tap-along
+    Calibration,
+    /// The class's Discipline skill tree
+    Skills,
+    /// Final sequence of a mystery ending
+    Finale,
+    /// Open quest chains, clues and world shifts
+    Journal,
+    /// An authored encounter playing out in an event room
+    Encounter,
+    /// Picking what a new level buys
+    LevelUp,
+    /// Picking the zone at a fork in the descent
+    RouteChoice,
+    /// A dream after sleeping at a rest site
+    Dream,
+    /// Talking with someone in an authored encounter
+    Conversation,
+    /// The lore codex, known and redacted
+    Codex,
+    /// Clues to the player's past, pinned as found
+    ClueBoard,
+    /// Letters from earlier runs, read before setting out
+    Mailbox,
+    Ciphers,
+    /// Memory flashes seen, replayed in full
+    Memories,
+    /// Late in the run, what its choices have made of you
+    Ideology,
+    /// Built-in themes and theme files, previewed as picked
+    Themes,
+    /// The keyboard, colored by how often each key is missed
+    KeyHeatmap,
+    /// Charts of speed, accuracy and damage, this run and every run
+    Dashboard,
+    /// Every setting, changed in place and saved on leaving
+    Settings,
+    /// The keys bound to every action, rebound in place
+    Keybinds,
+    /// A fight stopped on purpose, with the run's options over it
+    Pause,
+    /// Trainer Beck, between the lessons of the training yard
+    Training,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,12 +185,17 @@ pub struct GameState {
     pub current_enemy: Option<Enemy>,
     pub combat_state: Option<CombatState>,
     pub current_event: Option<GameEvent>,
-    pub shop_items: Vec<Item>,
+    /// The merchant's stock while in a shop
+    pub shop: Shop,
     pub message_log: Vec<String>,
     pub menu_index: usize,
     pub runs_completed: i32,
     pub total_enemies_defeated: i32,
     pub total_words_typed: i32,
+    /// Damage dealt beyond what killing blows needed
+    pub total_overkill: i32,
+    /// Enemies finished with a typed finisher
+    pub executions: i32,
     pub best_wpm: f64,
     pub input_buffer: String,
     pub game_data: Arc<GameData>,
@@ -79,6 +203,8 @@ pub struct GameState {
     pub hint_manager: HintManager,
     pub tutorial_state: TutorialState,
     pub tutorial_progress: TutorialProgress,
+    /// Trainer Beck's yard, while it is being walked
+    pub training: Option<Training>,
     pub typing_feel: TypingFeel,
     /// Current lore discovery being viewed
     pub current_lore: Option<(String, String)>,
@@ -88,8 +214,18 @@ pub struct GameState {
     /// Floors whose milestones have been shown this run
     pub milestones_shown: std::collections::HashSet<u32>,
     pub discovered_lore: Vec<(String, String)>,
+    /// Comprehension question about the lore just read
+    pub comprehension: Option<ComprehensionCheck>,
+    /// This run's notable events, stitched into combat prompts
+    pub run_chronicle: RunChronicle,
+    /// Travel or naming command being typed on the dungeon map
+    pub map_prompt: Option<MapPrompt>,
+    /// Near-deaths survived, across the profile
+    pub scars: Scars,
     /// Faction standings and relationships
     pub faction_relations: FactionRelations,
+    /// Tension and breathers between fights, across the run
+    pub pacing: PacingController,
     /// Persistent meta-progression (survives death)
     pub meta_progress: MetaProgress,
     /// Meta-progression damage bonus (from unlocks)
@@ -104,22 +240,167 @@ pub struct GameState {
     pub active_typing_modifier: Option<TypingModifier>,
     /// Player skill tree
     pub skill_tree: SkillTree,
+    /// Levels earned but not yet spent on a level-up choice
+    pub pending_level_ups: u32,
     /// Faction voice profiles for NPC dialogue
     pub faction_voices: HashMap<Faction, FactionVoice>,
+    /// Voice profiles for named NPCs (Vera, Kaya, Cipher)
+    pub npc_voices: HashMap<&'static str, NpcVoice>,
     /// Current NPC dialogue (if any)
     pub current_npc_dialogue: Option<(String, String)>,
     /// Current battle summary (shown after combat)
     pub current_battle_summary: Option<crate::ui::stats_summary::BattleSummary>,
+    /// Every keystroke of the last fight
+    pub last_replay: Option<CombatReplay>,
+    /// Replay being played back
+    pub replay_viewer: Option<ReplayViewer>,
     /// All authored encounters
     pub encounters: HashMap<String, AuthoredEncounter>,
     /// Tracks which encounters have been seen/choices made
     pub encounter_tracker: EncounterTracker,
+    /// Lore fragments revealed, alongside the encounters that revealed them
+    pub codex: LoreJournal,
+    /// Companions recruited, alongside the encounters that recruited them
+    pub companions: CompanionRoster,
+    /// What each companion at the campfire said on arrival
+    pub camp_talk: Vec<(String, String)>,
+    /// The run's dreams so far, and what they told you
+    pub dreams: DreamLog,
+    /// Dream being dreamt at a rest site
+    pub dream_run: Option<DreamRun>,
     /// Current authored encounter being displayed
-    pub current_encounter: Option<AuthoredEncounter>,
+    pub encounter_run: Option<EncounterRun>,
+    /// Conversation being had in the current encounter
+    pub conversation: Option<DialogueRunner>,
+    /// Clues heard in conversations
+    pub heard_clues: Vec<String>,
+    /// What NPCs think of you and remember (the whole timeline)
+    pub npc_memory: NpcMemory,
+    /// Decides which encounter an event room brings
+    pub encounter_scheduler: EncounterScheduler,
     /// Run modifiers affecting difficulty/rewards
     pub run_modifiers: RunModifiers,
+    /// Faction patron pledged for this run (chosen on the class screen)
+    pub patron: Option<Patron>,
+    /// Canon the run is played in (chosen on the class screen)
+    pub setting: Setting,
+    /// Ending reached by the last victorious run: (id, title)
+    pub run_ending: Option<(&'static str, &'static str)>,
+    /// Descending past the final floor after a victory
+    pub endless: bool,
+    /// Zones offered at the stairway's fork, while one is being picked
+    pub route_fork: Vec<FloorZone>,
+    /// Final sequence playing before the victory screen
+    pub finale: Option<Finale>,
+    /// Quest journal, rebuilt each time it is opened
+    pub journal: Journal,
+    /// Codex entries, rebuilt each time the codex is opened
+    pub codex_entries: Vec<CodexEntry>,
+    /// Codex section being read
+    pub codex_section: CodexSection,
+    /// Clues to who you were, pinned this run
+    pub clue_board: ClueBoard,
+    /// Reflection being typed on the selected clue
+    pub reflection: Option<String>,
+    /// How much of the hidden truth this run's story has told
+    pub revelation: RevelationState,
+    /// Consequences screen is asking to confirm a fresh timeline
+    pub timeline_confirm: bool,
+    /// Choices and opinions this run moved, for its epilogue
+    pub run_log: RunLog,
+    /// The finished run's epilogue, a paragraph to an entry
+    pub epilogue: Vec<String>,
+    /// Letters waiting at the start of this run
+    pub mailbox: Vec<Letter>,
+    /// Cipher's note being decoded in the mailbox
+    pub decoding: Option<String>,
+    /// Cipher's notes found this run
+    pub cipher_notes: Vec<CipherNote>,
+    /// A guess being typed at the selected note
+    pub cipher_guess: Option<String>,
+    /// When the selected memory began replaying in the gallery
+    pub memory_replay: Option<Instant>,
+    /// Everything said this session
+    pub transcript: Transcript,
+    /// The transcript pane, while it is open
+    pub transcript_view: Option<TranscriptView>,
+    /// Every change in play, as told to a screen reader
+    pub reader_log: ReaderLog,
+    /// What this run's choices say you believe
+    pub ideology: Ideology,
+    /// Whether the run has shown you its ideology yet
+    pub ideology_revealed: bool,
+    /// Themes to pick from (refreshed when the picker opens)
+    pub themes: Vec<Theme>,
+    /// Theme files that couldn't be read, and why
+    pub theme_errors: Vec<String>,
+    /// Where the picker last wrote a theme out, or why it couldn't
+    pub theme_note: Option<String>,
+    /// Challenges installed in the config dir (refreshed when browsing)
+    pub challenges: Vec<Challenge>,
+    /// Personal best score per challenge
+    pub challenge_bests: ChallengeBests,
+    /// Challenge this run is attempting
+    pub active_challenge: Option<Challenge>,
+    /// Seed the current run plays out from
+    pub run_seed: u64,
+    /// Seed to play every ordinary run from (`--seed`)
+    pub seed_override: Option<u64>,
+    /// Combat words from the challenge's word pack (empty = stock words)
+    pub word_pack: Vec<String>,
+    /// Ascension level picked for the run
+    pub ascension: u32,
+    /// Ascension levels unlocked and the leaderboard
+    pub ascension_record: AscensionRecord,
+    /// Presses and misses of every key, across the profile
+    pub key_accuracy: KeyAccuracy,
+    /// Every word of the run, and the records of runs finished
+    pub stats_store: StatsStore,
+    /// Whether the dashboard charts every run rather than this one
+    pub dashboard_all_runs: bool,
+    /// Where the dashboard goes back to
+    pub dashboard_return: Scene,
+    /// The config as it was when the settings screen opened, to put back
+    pub settings_before: Option<GameConfig>,
+    /// Where the settings screen goes back to
+    pub settings_return: Scene,
+    /// The keys bound to every action, across the profile
+    pub keybinds: Keybinds,
+    /// Waiting on the keybinds screen for a key: whether it is added
+    /// beside the action's keys rather than in their place
+    pub keybind_capture: Option<bool>,
+    /// What the last rebinding did, or why it could not
+    pub keybind_note: Option<String>,
+    /// The fight's clocks while it is paused from the pause menu
+    pub paused: Option<FrozenClock>,
+    /// The fight's clocks while an auto-pause or the transcript holds it
+    pub held: Option<FrozenClock>,
+    /// Abandon run was picked once, and waits on a second Enter
+    pub abandon_confirm: bool,
+    /// World flags and counters kept across runs
+    pub world_state: WorldState,
+    /// The run just finished, as it went on the leaderboard
+    pub last_run: Option<AscensionRun>,
     /// Visual effects manager (floating text, screen shake, etc.)
     pub effects: EffectsManager,
+    /// When the key being handled was read from the terminal
+    pub key_read_at: Instant,
+    /// Latency calibration in progress
+    pub calibration: Option<LatencyCalibration>,
+    /// Adaptive difficulty from rolling typing performance
+    pub difficulty_director: DifficultyDirector,
+    /// Auto-pause on focus loss / suspend
+    pub auto_pause: AutoPause,
+    /// Persisted player settings
+    pub config: GameConfig,
+    /// Status line file for terminal multiplexers
+    pub status_line: StatusLineExporter,
+    /// Content-author console (debug builds only)
+    pub dev_console: DevConsole,
+    /// Speaks combat prompts (audio feature)
+    pub pronouncer: Pronouncer,
+    /// What the terminal was found to draw, once asked
+    pub icon_probe: Option<IconStyle>,
 }
 
 impl Default for GameState {
@@ -130,6 +411,14 @@ impl Default for GameState {
 
 impl GameState {
     pub fn new() -> Self {
+        let config = load_config();
+        theme::apply(themes::find(config.display.theme.as_deref()));
+        theme::set_markers(config.display.typing_markers);
+        let icon_probe = config.display.icons.is_none().then(icon_fallback::probe);
+        theme::limit_icons(config.display.icons.or(icon_probe).unwrap_or_default());
+        let mut typing_feel = TypingFeel::new();
+        typing_feel.layout = config.typing.layout;
+        
         Self {
             scene: Scene::Title,
             player: None,
@@ -137,12 +426,14 @@ impl GameState {
             current_enemy: None,
             combat_state: None,
             current_event: None,
-            shop_items: Vec::new(),
+            shop: Shop::default(),
             message_log: Vec::new(),
             menu_index: 0,
             runs_completed: 0,
             total_enemies_defeated: 0,
             total_words_typed: 0,
+            total_overkill: 0,
+            executions: 0,
             best_wpm: 0.0,
             input_buffer: String::new(),
             game_data: Arc::new(GameData::load_or_default()),
@@ -150,55 +441,221 @@ impl GameState {
             hint_manager: HintManager::new(),
             tutorial_state: TutorialState::new(),
             tutorial_progress: TutorialProgress::load(),
-            typing_feel: TypingFeel::new(),
+            training: None,
+            typing_feel,
             current_lore: None,
             current_milestone: None,
             milestones_shown: std::collections::HashSet::new(),
             discovered_lore: Vec::new(),
+            comprehension: None,
+            map_prompt: None,
+            run_chronicle: RunChronicle::new(),
+            scars: scars::load(),
             faction_relations: FactionRelations::new(),
-            meta_progress: MetaProgress::default(),
+            pacing: PacingController::new(),
+            meta_progress: MetaProgress {
+                run_history: meta_progression::load_run_history(),
+                ..MetaProgress::default()
+            },
             damage_bonus_percent: 0.0,
             time_bonus_percent: 0.0,
             event_bus: EventBus::new(),
             narrative_seed: None,
             active_typing_modifier: None,
             skill_tree: SkillTree::new(),
+            pending_level_ups: 0,
             faction_voices: build_faction_voices(),
+            npc_voices: build_npc_voices(),
             current_npc_dialogue: None,
             current_battle_summary: None,
-            encounters: build_encounters(),
+            last_replay: None,
+            replay_viewer: None,
+            encounters: encounter_data::load_encounters(),
             encounter_tracker: EncounterTracker::new(),
-            current_encounter: None,
+            codex: LoreJournal::new(),
+            companions: CompanionRoster::new(),
+            camp_talk: Vec::new(),
+            dreams: DreamLog::new(),
+            dream_run: None,
+            encounter_run: None,
+            conversation: None,
+            heard_clues: Vec::new(),
+            npc_memory: NpcMemory::new(),
+            encounter_scheduler: EncounterScheduler::new(),
             run_modifiers: RunModifiers::new(),
+            patron: None,
+            setting: Setting::default(),
+            run_ending: None,
+            endless: false,
+            route_fork: Vec::new(),
+            finale: None,
+            journal: Journal::default(),
+            codex_entries: Vec::new(),
+            codex_section: CodexSection::Cosmology,
+            clue_board: ClueBoard::new(),
+            reflection: None,
+            revelation: RevelationState::new(),
+            timeline_confirm: false,
+            run_log: RunLog::default(),
+            epilogue: Vec::new(),
+            mailbox: Vec::new(),
+            decoding: None,
+            cipher_notes: Vec::new(),
+            cipher_guess: None,
+            memory_replay: None,
+            transcript: Transcript::default(),
+            transcript_view: None,
+            reader_log: ReaderLog::default(),
+            ideology: Ideology::default(),
+            ideology_revealed: false,
+            themes: Vec::new(),
+            theme_errors: Vec::new(),
+            theme_note: None,
+            challenges: Vec::new(),
+            challenge_bests: challenges::load_bests(),
+            active_challenge: None,
+            run_seed: 0,
+            seed_override: None,
+            word_pack: Vec::new(),
+            ascension: 0,
+            ascension_record: ascension::load_record(),
+            key_accuracy: key_accuracy::load(),
+            stats_store: stats_store::load(),
+            dashboard_all_runs: false,
+            dashboard_return: Scene::Title,
+            settings_before: None,
+            settings_return: Scene::Title,
+            keybinds: keybinds::load(),
+            keybind_capture: None,
+            keybind_note: None,
+            paused: None,
+            held: None,
+            abandon_confirm: false,
+            world_state: world_state::load_world_state(),
+            last_run: None,
             effects: EffectsManager::new(),
+            key_read_at: Instant::now(),
+            calibration: None,
+            difficulty_director: DifficultyDirector::from_config(&config.difficulty),
+            auto_pause: AutoPause::new(),
+            status_line: StatusLineExporter::from_config(&config.display.status_line),
+            dev_console: DevConsole::new(),
+            pronouncer: Pronouncer::new(&config.audio.pronunciation),
+            icon_probe,
+            config,
         }
     }
 
     pub fn start_new_game(&mut self, mut player: Player) {
-        // Apply meta-progression bonuses
+        // The run's seed fixes its maps, enemies, words and beats: a
+        // challenge brings its own, `--seed` fixes one, otherwise it's fresh
+        self.run_seed = match &self.active_challenge {
+            Some(challenge) => challenge.seed,
+            None => self.seed_override.unwrap_or_else(rand::random),
+        };
+        run_rng::seed(self.run_seed);
+        self.encounter_scheduler = EncounterScheduler::new();
+        self.pending_level_ups = 0;
+        self.endless = false;
+        zone_routes::reset();
+        setting_packs::set(self.setting);
+        self.route_fork.clear();
+        self.dreams = DreamLog::new();
+        self.dream_run = None;
+        self.clue_board = ClueBoard::new();
+        self.reflection = None;
+        self.revelation = RevelationState::new();
+        self.stats_store.current.clear();
+
+        // Apply meta-progression bonuses, except in a class-locked
+        // challenge, which everyone plays from the same start
         let bonus = self.meta_progress.start_run();
-        player.max_hp += bonus.hp_bonus;
-        player.hp += bonus.hp_bonus;
-        player.gold += bonus.gold_bonus as u64;
+        if self.locked_class().is_none() {
+            player.max_hp += bonus.hp_bonus;
+            player.hp += bonus.hp_bonus;
+            player.gold += bonus.gold_bonus as u64;
+            
+            // Store bonuses for combat calculations
+            self.damage_bonus_percent = bonus.damage_bonus_percent;
+            self.time_bonus_percent = bonus.time_bonus_percent;
+        } else {
+            self.damage_bonus_percent = 0.0;
+            self.time_bonus_percent = 0.0;
+        }
+        
+        // A Mechanist pact trades the body's resilience for speed
+        if self.patron == Some(Patron::Mechanists) {
+            player.max_hp = ((player.max_hp as f32) * MECHANIST_HP_FRACTION).round() as i32;
+            player.hp = player.hp.min(player.max_hp);
+        }
         
-        // Store bonuses for combat calculations
-        self.damage_bonus_percent = bonus.damage_bonus_percent;
-        self.time_bonus_percent = bonus.time_bonus_percent;
+        // What earlier runs changed in the world is felt from the start
+        let descent = self.world_state.bump(DESCENTS, 1);
+        let consequences: Vec<_> = self.world_state.consequences().into_iter().copied().collect();
+        self.mailbox = letters::collect(&mut self.world_state, &self.encounter_tracker);
+        self.decoding = None;
+        self.cipher_notes.clear();
+        self.cipher_guess = None;
+        for consequence in &consequences {
+            match consequence.aftermath {
+                Aftermath::TimeBonus(percent) => self.time_bonus_percent += percent,
+                // A floor, not a gift: relations carry over between runs
+                Aftermath::Standing(faction, standing) => {
+                    let shortfall = standing - self.faction_relations.standing(&faction);
+                    if shortfall > 0 {
+                        self.faction_relations.modify_standing(faction, shortfall);
+                    }
+                }
+                Aftermath::MaxHp(hp) => {
+                    player.max_hp += hp;
+                    player.hp += hp;
+                }
+                Aftermath::Remembered => {}
+            }
+        }
+        self.save_world_state();
+        self.run_log = RunLog::new(&self.faction_relations);
+        self.epilogue.clear();
+        
+        // Every class sets out with its own relic and skill tree
+        let class = PlayerClass::from_class(&player.class);
+        let relic = class_builds::starting_relic(class);
+        player.artifacts.push(relic.to_string());
+        living_book::carry(&self.world_state, &mut player.artifacts);
+        self.skill_tree = SkillTree::for_class(class);
         
         self.player = Some(player);
         self.dungeon = Some(Dungeon::new());
         self.scene = Scene::Dungeon;
         self.message_log.clear();
         self.milestones_shown.clear();
+        self.ideology = Ideology::default();
+        self.ideology_revealed = false;
+        self.run_chronicle.clear();
+        self.pacing.reset();
         
         // Show bonus message if any
         if bonus.hp_bonus > 0 || bonus.gold_bonus > 0 {
             self.add_message(&format!("Meta-bonuses: +{} HP, +{} Gold", bonus.hp_bonus, bonus.gold_bonus));
         }
         self.add_message("Your typing quest begins!");
+        if descent > 1 {
+            self.add_message(&format!("Descent {}. The dungeon has seen you before.", descent));
+        }
+        for consequence in &consequences {
+            self.add_message(consequence.message);
+        }
+        self.add_message(&format!("You set out carrying {}.", relic));
+        self.hear_rumor("in Haven");
+        
+        if let Some(patron) = self.patron {
+            self.faction_relations.modify_standing(patron.faction(), PLEDGE_STANDING);
+            self.add_message(&format!("You pledge this run to {}. {}", patron.name(), patron.mechanic()));
+        }
+        self.note_clues();
         
         // Generate narrative seed for this run
-        let seed = NarrativeSeed::generate_random();
+        let seed = NarrativeSeed::generate(self.run_seed);
         self.active_typing_modifier = Some(seed.world_state.corruption_type.typing_modifier());
         
         // Emit run start event
@@ -210,9 +667,535 @@ impl GameState {
         // Show corruption warning
         self.add_message(&format!("󰈸 The {} corrupts this realm...", seed.world_state.corruption_type.name()));
         self.narrative_seed = Some(seed);
+
+        if let Some(challenge) = &self.active_challenge {
+            let message = format!("⚑ Challenge: {} - score {} to beat it", challenge.name, challenge.target_score);
+            self.add_message(&message);
+        }
+
+        // Mail from earlier runs is read before setting out
+        if !self.mailbox.is_empty() {
+            self.menu_index = 0;
+            self.scene = Scene::Mailbox;
+        }
+    }
+
+    /// Open the challenge browser, rereading what is installed
+    pub fn open_challenges(&mut self) {
+        self.challenges = challenges::load_installed();
+        self.challenges.insert(0, challenges::daily(chrono::Utc::now().date_naive()));
+        self.challenge_bests = challenges::load_bests();
+        self.menu_index = 0;
+        self.scene = Scene::Challenges;
+    }
+
+    /// Set up a run of an installed challenge; the class screen comes
+    /// next, held on the challenge's class if it locks one
+    pub fn start_challenge(&mut self, index: usize) {
+        let Some(challenge) = self.challenges.get(index).cloned() else {
+            return;
+        };
+        self.run_modifiers = RunModifiers::new();
+        for (modifier, level) in &challenge.modifiers {
+            self.run_modifiers.add_modifier(modifier.clone(), *level);
+        }
+        self.word_pack = challenge.word_pack.as_deref().map(challenges::load_word_pack).unwrap_or_default();
+        self.active_challenge = Some(challenge);
+        self.menu_index = self.locked_class()
+            .and_then(|class| Class::ALL.iter().position(|c| *c == class))
+            .unwrap_or(0);
+        self.scene = Scene::ClassSelect;
+    }
+
+    /// The class the running challenge holds the player to, if any
+    pub fn locked_class(&self) -> Option<Class> {
+        self.active_challenge.as_ref().and_then(|c| c.class_lock)
+    }
+
+    /// The director enemies are scaled by, unless the run is a challenge
+    /// whose scores are compared across players
+    pub fn enemy_director(&self) -> Option<&DifficultyDirector> {
+        self.active_challenge.is_none().then_some(&self.difficulty_director)
+    }
+
+    /// Drop any challenge setup before an ordinary run
+    pub fn clear_challenge(&mut self) {
+        if self.active_challenge.take().is_some() {
+            self.run_modifiers = RunModifiers::new();
+        }
+        self.word_pack.clear();
+    }
+
+    /// Score a finished challenge run against the personal best
+    fn finish_challenge(&mut self, victory: bool) {
+        let Some(challenge) = self.active_challenge.take() else {
+            return;
+        };
+        self.word_pack.clear();
+        let score = challenges::run_score(
+            self.get_current_floor(),
+            self.total_enemies_defeated,
+            self.total_words_typed,
+            self.best_wpm,
+            victory,
+        );
+        if self.challenge_bests.record(&challenge.id, score) {
+            if let Err(e) = challenges::save_bests(&self.challenge_bests) {
+                self.add_message(&format!("Could not save challenge best: {}", e));
+            }
+            self.add_message(&format!("⚑ New personal best on {}: {}", challenge.name, score));
+        } else {
+            self.add_message(&format!("⚑ {} score: {}", challenge.name, score));
+        }
+        if score >= challenge.target_score {
+            self.add_message(&format!("Challenge beaten! ({} / {})", score, challenge.target_score));
+        }
+    }
+
+    /// Switch to the next keyboard layout and persist the choice
+    pub fn cycle_keyboard_layout(&mut self) {
+        let layout = self.config.typing.layout.next();
+        self.config.typing.layout = layout;
+        self.typing_feel.layout = layout;
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Switch between real-time and turn-based fights
+    pub fn toggle_combat_mode(&mut self) {
+        self.config.combat.mode = self.config.combat.mode.next();
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Turn the unreliable narrator on or off
+    pub fn toggle_unreliable_narrator(&mut self) {
+        self.config.display.unreliable_narrator = !self.config.display.unreliable_narrator;
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Turn the shape markers on typed letters on or off
+    pub fn toggle_typing_markers(&mut self) {
+        self.config.display.typing_markers = !self.config.display.typing_markers;
+        theme::set_markers(self.config.display.typing_markers);
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Open the theme picker on the theme in use
+    pub fn open_themes(&mut self) {
+        (self.themes, self.theme_errors) = themes::available();
+        self.theme_note = None;
+        let current = theme::current();
+        self.menu_index = self.themes.iter().position(|t| *t == current).unwrap_or(0);
+        self.scene = Scene::Themes;
+    }
+
+    /// Draw in the selected theme while the picker is open
+    pub fn preview_theme(&mut self) {
+        if let Some(selected) = self.themes.get(self.menu_index) {
+            theme::apply(selected.clone());
+        }
+    }
+
+    /// Keep the selected theme and persist the choice
+    pub fn choose_theme(&mut self) {
+        let Some(selected) = self.themes.get(self.menu_index).cloned() else {
+            return;
+        };
+        self.config.display.theme = Some(selected.name.clone()).filter(|_| selected != Theme::default());
+        theme::apply(selected);
+        self.scene = Scene::Title;
+        self.menu_index = 0;
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Leave the picker, back in the theme kept before it opened
+    pub fn cancel_themes(&mut self) {
+        theme::apply(themes::find(self.config.display.theme.as_deref()));
+        self.scene = Scene::Title;
+        self.menu_index = 0;
+    }
+
+    /// Open the settings screen, to go back to `from`
+    pub fn open_settings(&mut self, from: Scene) {
+        (self.themes, self.theme_errors) = themes::available();
+        self.settings_before = Some(self.config.clone());
+        self.settings_return = from;
+        self.menu_index = 0;
+        self.scene = Scene::Settings;
+    }
+
+    /// Step the selected setting, and let the game show it at once
+    pub fn adjust_setting(&mut self, forward: bool) {
+        let Some(setting) = settings::Setting::ALL.get(self.menu_index) else {
+            return;
+        };
+        let names: Vec<String> = self.themes.iter().map(|t| t.name.clone()).collect();
+        setting.adjust(&mut self.config, forward, &names);
+        // Setting icons back to Auto asks the terminal again
+        if *setting == settings::Setting::Icons && self.config.display.icons.is_none() {
+            self.icon_probe = None;
+        }
+        self.apply_config();
+    }
+
+    /// Put every setting back as it was when the screen opened
+    pub fn revert_settings(&mut self) {
+        if let Some(before) = self.settings_before.clone() {
+            self.config = before;
+            self.apply_config();
+        }
+    }
+
+    /// Leave the settings screen, keeping them
+    pub fn close_settings(&mut self) {
+        self.settings_before = None;
+        self.scene = self.settings_return;
+        self.menu_index = 0;
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Open the keybinds screen, from the settings screen
+    pub fn open_keybinds(&mut self) {
+        self.keybind_capture = None;
+        self.keybind_note = None;
+        self.menu_index = 0;
+        self.scene = Scene::Keybinds;
+    }
+
+    /// Leave the keybinds screen for the settings screen
+    pub fn close_keybinds(&mut self) {
+        self.menu_index = 0;
+        self.scene = Scene::Settings;
+    }
+
+    /// Wait for a key to bind to the selected action
+    pub fn begin_keybind_capture(&mut self, add: bool) {
+        self.keybind_capture = Some(add);
+        self.keybind_note = None;
+    }
+
+    /// Bind the key pressed to the selected action, unless it conflicts;
+    /// `None` (Esc, or a key with no name) gives up waiting
+    pub fn capture_keybind(&mut self, key: Option<String>) {
+        let Some(add) = self.keybind_capture.take() else {
+            return;
+        };
+        let (Some(key), Some(action)) = (key, Action::ALL.get(self.menu_index).copied()) else {
+            return;
+        };
+        self.keybind_note = Some(match self.keybinds.bind(action, &key, add) {
+            Ok(()) => format!("{} is now on {}", action.name(), self.keybinds.label(action)),
+            Err(conflict) => conflict.describe(&key),
+        });
+        self.save_keybinds();
+    }
+
+    /// Put the selected action's default keys back
+    pub fn reset_keybind(&mut self) {
+        if let Some(action) = Action::ALL.get(self.menu_index).copied() {
+            self.keybinds.reset(action);
+            self.keybind_note = Some(format!("{} is back on {}", action.name(), action.defaults().join(" / ")));
+            self.save_keybinds();
+        }
+    }
+
+    fn save_keybinds(&mut self) {
+        if let Err(e) = keybinds::save(&self.keybinds) {
+            self.keybind_note = Some(format!("Could not save keybinds: {}", e));
+        }
+    }
+
+    /// Hold the fight while an auto-pause or the transcript is up, its
+    /// clocks frozen from the first frame held
+    pub fn hold_fight(&mut self, now: Instant) {
+        let Some(combat) = &mut self.combat_state else {
+            self.held = None;
+            return;
+        };
+        if self.held.is_none() && self.paused.is_none() {
+            self.held = Some(combat.freeze(now));
+        }
+        combat.hold_timer();
+    }
+
+    /// Let a held fight go, every window as long as it was when held
+    pub fn release_fight(&mut self, now: Instant) {
+        if let (Some(clock), Some(combat)) = (self.held.take(), &mut self.combat_state) {
+            combat.thaw(&clock, now);
+        }
+    }
+
+    /// Stop the fight and open the pause menu over it
+    pub fn pause_fight(&mut self) {
+        let now = Instant::now();
+        self.release_fight(now);
+        let Some(combat) = &self.combat_state else {
+            return;
+        };
+        self.paused = Some(combat.freeze(now));
+        self.abandon_confirm = false;
+        self.menu_index = 0;
+        self.scene = Scene::Pause;
+    }
+
+    /// Back into the fight, its clocks running from now
+    pub fn resume_fight(&mut self) {
+        if let (Some(clock), Some(combat)) = (self.paused.take(), &mut self.combat_state) {
+            combat.thaw(&clock, Instant::now());
+        }
+        self.abandon_confirm = false;
+        self.menu_index = 0;
+        self.scene = Scene::Combat;
+    }
+
+    /// Give up the run mid-fight; it ends as a death would
+    pub fn abandon_run(&mut self) {
+        if self.training.is_some() {
+            self.leave_training();
+            return;
+        }
+        self.paused = None;
+        self.abandon_confirm = false;
+        if let Some(mut combat) = self.combat_state.take() {
+            self.stats_store.record_words(combat.word_samples.drain(..));
+        }
+        self.current_enemy = None;
+        self.add_message("You lay down the keyboard and walk away from the run.");
+        self.lose_run();
+    }
+
+    /// Bring the running game in line with the config
+    fn apply_config(&mut self) {
+        theme::apply(themes::find(self.config.display.theme.as_deref()));
+        theme::set_markers(self.config.display.typing_markers);
+        let icons = match self.config.display.icons {
+            Some(icons) => icons,
+            None => *self.icon_probe.get_or_insert_with(icon_fallback::probe),
+        };
+        theme::limit_icons(icons);
+        self.typing_feel.layout = self.config.typing.layout;
+        self.pronouncer = Pronouncer::new(&self.config.audio.pronunciation);
+        self.difficulty_director.enabled = self.config.difficulty.adaptive_difficulty;
+        if self.config.display.reduced_motion {
+            self.effects.particles.clear();
+        }
+    }
+
+    /// Open the keyboard heatmap
+    pub fn open_key_heatmap(&mut self) {
+        self.scene = Scene::KeyHeatmap;
+    }
+
+    /// Write the selected theme out as a theme file to start from
+    pub fn export_theme(&mut self) {
+        let Some(selected) = self.themes.get(self.menu_index) else {
+            return;
+        };
+        self.theme_note = Some(match themes::export(selected) {
+            Ok(path) => format!("Written to {}", path.display()),
+            Err(e) => format!("Could not write theme: {}", e),
+        });
+    }
+
+    /// Attack type cutoffs in force: the player's own, or their class preset
+    pub fn attack_thresholds(&self) -> AttackThresholds {
+        self.config.typing.attack_thresholds.unwrap_or_else(|| {
+            let class = self.player.as_ref().map(|p| PlayerClass::from_class(&p.class)).unwrap_or(PlayerClass::Freelancer);
+            AttackThresholds::for_class(class)
+        })
+    }
+
+    /// Start the latency tap-along
+    pub fn open_calibration(&mut self) {
+        self.calibration = Some(LatencyCalibration::new(Instant::now()));
+        self.scene = Scene::Calibration;
+    }
+
+    /// Keep the measured latency and persist it
+    pub fn save_calibration(&mut self) {
+        let Some(latency) = self.calibration.take().and_then(|c| c.result_ms()) else {
+            return;
+        };
+        self.config.typing.latency_ms = latency;
+        self.scene = Scene::Title;
+        match save_config(&self.config) {
+            Ok(()) => self.add_message(&format!("Latency set to {} ms.", latency)),
+            Err(e) => self.add_message(&format!("Could not save settings: {}", e)),
+        }
+    }
+
+    /// Push floor/HP/WPM to the status line file (no-op unless enabled)
+    pub fn export_status(&mut self) {
+        if !self.status_line.is_enabled() {
+            return;
+        }
+        match &self.player {
+            Some(player) if self.scene != Scene::GameOver && self.scene != Scene::Victory => {
+                let status = RunStatus {
+                    floor: self.get_current_floor(),
+                    hp: player.hp,
+                    max_hp: player.max_hp,
+                    wpm: self.typing_feel.wpm,
+                    in_combat: self.scene == Scene::Combat,
+                };
+                self.status_line.export(&status);
+            }
+            _ => self.status_line.clear(),
+        }
+    }
+
+    /// Speak the current combat prompt once when it changes
+    pub fn pronounce_prompt(&mut self) {
+        if !self.pronouncer.is_enabled() {
+            return;
+        }
+        match &self.combat_state {
+            Some(combat) if combat.phase == CombatPhase::PlayerTurn => {
+                self.pronouncer.announce(&combat.current_word, self.auto_pause.audio_muted);
+            }
+            Some(_) => {}
+            None => self.pronouncer.reset(),
+        }
+    }
+
+    /// Note a won fight (and how close it was) in the run chronicle
+    fn chronicle_victory(&mut self, enemy_name: &str, is_boss: bool) {
+        let floor = self.get_current_floor() as u32;
+        let spared = self.combat_state.as_ref().is_some_and(|c| c.phase == CombatPhase::Spared);
+        let enemy = enemy_name.to_string();
+        self.run_chronicle.record(match (spared, is_boss) {
+            (true, _) => RunEvent::Spared { enemy, floor },
+            (false, true) => RunEvent::BossDefeated { enemy, floor },
+            (false, false) => RunEvent::Defeated { enemy, floor },
+        });
+        if let Some(player) = &self.player {
+            if player.hp * 4 <= player.max_hp {
+                self.run_chronicle.record(RunEvent::NearDeath { floor });
+                self.cut_scar(enemy_name, floor);
+            }
+        }
+    }
+
+    /// Keep a near-death survived as a scar
+    fn cut_scar(&mut self, enemy_name: &str, floor: u32) {
+        let scar = Scar {
+            enemy: enemy_name.to_string(),
+            floor,
+            zone: self.dungeon.as_ref().map_or_else(|| "Unknown".to_string(), |d| d.get_zone_name()),
+            word: self.combat_state.as_ref().and_then(|c| c.last_failed_word.clone()),
+            descent: self.world_state.counter(DESCENTS),
+        };
+        self.add_message(&format!("A new scar: {}.", scar.title()));
+        self.scars.cut(scar);
+        if let Err(e) = scars::save(&self.scars) {
+            self.add_message(&format!("Could not save scars: {}", e));
+        }
+    }
+
+    /// Ask the comprehension question for the current lore, if it has one
+    pub fn begin_comprehension(&mut self) -> bool {
+        let question = self.current_lore.as_ref().and_then(|(title, _)| question_for(title));
+        self.comprehension = question.map(ComprehensionCheck::new);
+        self.comprehension.is_some()
+    }
+
+    /// Grade the typed answer; a correct one grants XP and the fragment's insight
+    pub fn submit_comprehension(&mut self) {
+        let Some(check) = &mut self.comprehension else {
+            return;
+        };
+        if !check.submit() {
+            return;
+        }
+        let question = check.question;
+        let xp = XP_PER_FLOOR * self.get_current_floor().max(1) as u64;
+        self.grant_experience(xp);
+        self.discovered_lore.push((format!("{} (Insight)", question.lore_title), question.insight.to_string()));
+        self.add_message(&format!("You read closely. +{} XP, insight recorded.", xp));
+    }
+
+    /// Close the lore screen, journaling the fragment
+    pub fn finish_lore(&mut self) {
+        self.comprehension = None;
+        if let Some(lore) = self.current_lore.take() {
+            self.run_chronicle.record(RunEvent::LoreFound {
+                title: lore.0.clone(),
+                floor: self.get_current_floor() as u32,
+            });
+            self.discovered_lore.push(lore);
+        }
+        self.scene = Scene::Dungeon;
+    }
+
+    pub fn open_map_prompt(&mut self, mode: MapPromptMode) {
+        self.map_prompt = Some(MapPrompt::new(mode));
+    }
+
+    /// Act on the typed map command. Returns true if the player should
+    /// explore the room ahead.
+    pub fn submit_map_prompt(&mut self) -> bool {
+        let Some(prompt) = self.map_prompt.take() else {
+            return false;
+        };
+        let Some(dungeon) = &mut self.dungeon else {
+            return false;
+        };
+
+        let message = match prompt.mode {
+            MapPromptMode::Name => match clean_name(&prompt.input).and_then(|name| dungeon.name_here(name)) {
+                Some(label) => format!("You name this place {}.", label),
+                None => "That name will not hold.".to_string(),
+            },
+            MapPromptMode::Travel => {
+                match resolve(&prompt.input, &dungeon.floor_rooms(), dungeon.next_coord()) {
+                    Some(Destination::Onward { name }) => {
+                        if let Some(name) = name {
+                            self.add_message(&format!("You name the way ahead '{}'.", name));
+                            if let Some(dungeon) = &mut self.dungeon {
+                                dungeon.next_room_name = Some(name);
+                            }
+                        }
+                        return true;
+                    }
+                    Some(Destination::Return(coord)) if dungeon.here == Some(coord) => {
+                        "You are already there.".to_string()
+                    }
+                    Some(Destination::Return(coord)) => match dungeon.return_to(coord) {
+                        Some(label) => format!("You return to {}.", label),
+                        None => return false,
+                    },
+                    None => format!("No such place on this floor: '{}'.", prompt.input.trim()),
+                }
+            }
+        };
+        self.add_message(&message);
+        false
+    }
+
+    /// Execute the dev console's input line against the loaded content
+    pub fn submit_dev_command(&mut self) {
+        let ctx = ConsoleContext {
+            encounters: &self.encounters,
+            enemies: &self.game_data.enemies,
+            voices: &self.npc_voices,
+            layout: self.config.typing.layout,
+        };
+        self.dev_console.submit(&ctx);
     }
 
     pub fn add_message(&mut self, msg: &str) {
+        self.transcript.record(Source::Message, None, msg);
         self.message_log.push(msg.to_string());
         // Keep only last 10 messages
         if self.message_log.len() > 10 {
@@ -220,23 +1203,188 @@ impl GameState {
         }
     }
 
+    /// Open Trainer Beck's yard at its first lesson
+    pub fn open_training(&mut self) {
+        self.training = Some(Training::default());
+        self.player = Some(Player::new("Trainee".to_string(), Class::Wordsmith));
+        self.scene = Scene::Training;
+    }
+
+    /// Fight the dummy of the lesson up next. Only the settings shape it;
+    /// nothing of a run (patron, curses, ascension) reaches the yard.
+    pub fn begin_lesson(&mut self) {
+        let Some(lesson) = self.training.as_ref().and_then(|t| t.current()) else {
+            return;
+        };
+        let Some(player) = &mut self.player else {
+            return;
+        };
+        player.hp = player.max_hp;
+        let class = player.class;
+        let enemy = lesson.dummy();
+        self.current_enemy = Some(enemy.clone());
+        self.transcript.begin(Source::Combat);
+        let mut combat = CombatState::new(enemy, self.game_data.clone(), 1, 1, None, None);
+        combat.init_immersion(&class);
+        combat.set_class(PlayerClass::from_class(&class));
+        combat.set_impact_params(ImpactParams {
+            latency_ms: self.config.typing.latency_ms,
+            thresholds: self.attack_thresholds(),
+            ..ImpactParams::default()
+        });
+        combat.apply_settings(&self.config.difficulty, TypoPolicy::of(&self.config.typing));
+        combat.set_mode(self.config.combat.mode);
+        combat.battle_log.push(format!("Beck: \"{}.\"", lesson.goal()));
+        self.combat_state = Some(combat);
+        self.effects.clear();
+        self.auto_pause.clear();
+        self.typing_feel.reset_flow();
+        self.scene = Scene::Combat;
+    }
+
+    /// End the lesson's fight once its lesson is learned, or once it is
+    /// over without it (felled, fled, or the trainee down)
+    pub fn check_lesson(&mut self) {
+        let (Some(training), Some(combat)) = (&mut self.training, &self.combat_state) else {
+            return;
+        };
+        let Some(lesson) = training.current() else {
+            return;
+        };
+        let learned = lesson.met(&LessonProgress::of(combat));
+        let over = matches!(combat.phase, CombatPhase::Victory | CombatPhase::Defeat | CombatPhase::Fled | CombatPhase::Spared);
+        if !learned && !over {
+            return;
+        }
+        training.finish(learned);
+        self.combat_state = None;
+        self.current_enemy = None;
+        self.paused = None;
+        if let Some(player) = &mut self.player {
+            player.hp = player.max_hp;
+        }
+        self.scene = Scene::Training;
+    }
+
+    /// Pass over the lesson up next
+    pub fn skip_lesson(&mut self) {
+        if let Some(training) = &mut self.training {
+            training.skip();
+        }
+    }
+
+    /// Leave the yard for the class screen, the tutorial done
+    pub fn finish_training(&mut self) {
+        self.tutorial_progress.mark_completed();
+        self.tutorial_progress.save();
+        self.leave_training();
+        self.scene = Scene::ClassSelect;
+    }
+
+    /// Leave the yard, and the trainee with it
+    fn leave_training(&mut self) {
+        self.training = None;
+        self.combat_state = None;
+        self.current_enemy = None;
+        self.paused = None;
+        self.player = None;
+        self.menu_index = 0;
+        self.scene = Scene::Title;
+    }
+
     pub fn start_combat(&mut self, enemy: Enemy) {
         let enemy_name = enemy.name.clone();
         let zone_name = self.dungeon.as_ref().map(|d| d.get_zone_name()).unwrap_or_else(|| "Unknown".to_string());
         
         self.current_enemy = Some(enemy.clone());
         let difficulty = self.dungeon.as_ref().map(|d| d.current_floor as u32).unwrap_or(1);
+        self.transcript.begin(Source::Combat);
         self.combat_state = Some(CombatState::new(enemy, self.game_data.clone(), difficulty, difficulty, self.active_typing_modifier.clone(), Some(&self.skill_tree)));
         
         // Initialize immersion systems for this combat
+        let thresholds = self.attack_thresholds();
+        let lexicon = self.lexicon_words();
+        let truths = self.player.as_ref()
+            .map(|p| artifacts::stirred_truths(&p.artifacts, &enemy_name))
+            .unwrap_or_default();
         if let Some(ref mut combat) = self.combat_state {
             if let Some(ref player) = self.player {
                 combat.init_immersion(&player.class);
+                combat.set_class(PlayerClass::from_class(&player.class));
+                if let Some(ref seed) = self.narrative_seed {
+                    combat.set_motifs(seed.motifs.iter().map(|m| m.name.to_lowercase()).collect());
+                }
+                let tuned = ImpactParams::with_tuning(
+                    self.game_data.items.impact_of(player.gear.values())
+                        .plus(artifacts::tuning_of(&player.artifacts)),
+                );
+                combat.set_impact_params(ImpactParams {
+                    latency_ms: self.config.typing.latency_ms,
+                    thresholds,
+                    // Level-up choices widen the rhythm window
+                    rhythm_window_ms: tuned.rhythm_window_ms + player.rhythm_bonus_ms,
+                    ..tuned
+                });
+                combat.skill_crit_chance += player.crit_bonus;
+                if artifacts::wards_corruption(&player.artifacts) {
+                    combat.ward_corruption();
+                }
+            }
+            if let Some(patron) = self.patron {
+                combat.apply_patron(patron);
+            }
+            if self.active_challenge.is_some() {
+                // Scores are compared across players, so no one's director
+                // or settings may make the fights easier or harder
+                combat.apply_settings(&DifficultyConfig::from_preset(DifficultyPreset::Normal), TypoPolicy::Forgiving);
+            } else {
+                combat.apply_director(&self.difficulty_director);
+                combat.apply_settings(&self.config.difficulty, TypoPolicy::of(&self.config.typing));
+            }
+            if let Some(ref player) = self.player {
+                combat.apply_curses(curses::load_of(&player.curses));
+            }
+            combat.apply_ascension(self.ascension);
+            combat.set_stitched_prompts(self.run_chronicle.stitch_all(&mut run_rng::stream(Stream::Words)));
+            combat.set_word_pack(self.word_pack.clone());
+            combat.set_lexicon(lexicon);
+            combat.set_mode(self.config.combat.mode);
+            if let Some(name) = true_names::known_for(&combat.enemy.name, &self.discovered_lore) {
+                combat.set_true_name(name);
+            }
+            if self.pronouncer.is_dictation() {
+                combat.disable_aiming();
+            }
+            if self.run_modifiers.has_modifier(&Modifier::WordChain) {
+                combat.enable_word_chain();
+            }
+            if let Some(name) = self.dungeon.as_ref().and_then(|d| d.here_room()).and_then(|r| r.name.as_deref()) {
+                combat.apply_room_name(name);
+            }
+            for (artifact, truth) in &truths {
+                if !self.discovered_lore.iter().any(|(t, _)| *t == artifacts::truth_title(artifact)) {
+                    combat.battle_log.push(format!("✧ {} stirs in your pack: \"{}\"", artifact, truth));
+                }
+            }
+            // The final boss knows how you nearly fell before
+            let final_floor = !self.endless && self.dungeon.as_ref().is_some_and(|d| d.current_floor >= FINAL_FLOOR as i32);
+            if combat.enemy.is_boss && final_floor {
+                if let Some(scar) = self.scars.pick(&mut run_rng::stream(Stream::Dialogue)) {
+                    combat.battle_log.push(scar.taunt(&combat.enemy.name));
+                }
+            }
+        }
+        for (artifact, truth) in truths {
+            let title = artifacts::truth_title(artifact);
+            if !self.discovered_lore.iter().any(|(t, _)| *t == title) {
+                self.discovered_lore.push((title, truth));
             }
         }
         
         // Clear any lingering effects
         self.effects.clear();
+        self.auto_pause.clear();
+        self.typing_feel.reset_flow();
         
         self.scene = Scene::Combat;
         
@@ -250,11 +1398,42 @@ impl GameState {
     }
 
     pub fn end_combat(&mut self, victory: bool) {
+        if let (Some(combat), Some(player)) = (&mut self.combat_state, &mut self.player) {
+            combat.settle_recoil(player);
+        }
+        // A lesson's fight is no part of a run
+        if self.training.is_some() {
+            self.check_lesson();
+            return;
+        }
+        self.last_replay = self.combat_state.as_ref().map(|c| c.replay.clone());
+        if let Err(e) = key_accuracy::save(&self.key_accuracy) {
+            self.add_message(&format!("Could not save key accuracy: {}", e));
+        }
+        
+        if let Some(combat) = &mut self.combat_state {
+            self.stats_store.record_words(combat.word_samples.drain(..));
+        }
+        
+        // Feed this fight's performance to the difficulty director
+        if let Some(combat) = &self.combat_state {
+            if !combat.wpm_samples.is_empty() {
+                let avg_wpm = combat.wpm_samples.iter().sum::<f32>() / combat.wpm_samples.len() as f32;
+                let accuracy = combat.correct_chars as f32 / combat.total_chars.max(1) as f32;
+                self.difficulty_director.record_fight(avg_wpm, accuracy);
+            }
+        }
+        
         if victory {
             if let Some(enemy) = &self.current_enemy {
                 let enemy_name = enemy.name.clone();
-                let xp_reward = ((enemy.xp_reward as f32) * self.skill_tree.get_xp_multiplier()).round() as u64;
-                let gold_reward = ((enemy.gold_reward as f32) * self.run_modifiers.reward_multiplier).round() as u64;
+                let executed = self.combat_state.as_ref().is_some_and(|c| c.executed);
+                // The fight's result already holds the execution bonus
+                let (base_xp, base_gold) = self.combat_state.as_ref()
+                    .and_then(|c| c.result.as_ref())
+                    .map_or((enemy.xp_reward, enemy.gold_reward), |r| (r.xp_gained, r.gold_gained));
+                let xp_reward = ((base_xp as f32) * self.skill_tree.get_xp_multiplier()).round() as u64;
+                let gold_reward = ((base_gold as f32) * self.run_modifiers.reward_multiplier).round() as u64;
                 let is_boss = enemy.is_boss;
                 
                 // Create battle summary
@@ -281,11 +1460,18 @@ impl GameState {
                 
                 self.add_message(&format!("Defeated {}!", enemy_name));
                 
+                self.grant_experience(xp_reward);
                 if let Some(player) = &mut self.player {
-                    player.gain_experience(xp_reward);
                     player.gold += gold_reward;
+                    player.enemies_defeated += 1;
                 }
                 self.total_enemies_defeated += 1;
+                self.note_clues();
+                if let Some(combat) = &self.combat_state {
+                    self.total_overkill += combat.overkill;
+                    self.executions += executed as i32;
+                }
+                self.chronicle_victory(&enemy_name, is_boss);
                 
                 // Emit combat victory event
                 self.event_bus.emit(BusEvent::CombatEnded {
@@ -302,17 +1488,28 @@ impl GameState {
                     source: enemy_name.clone(),
                 });
                 
+                // Winning in a transcendent flow reveals a lore fragment
+                if self.typing_feel.flow_state.grants_lore() {
+                    let floor = self.get_current_floor() as u32;
+                    if let Some(dungeon) = &mut self.dungeon {
+                        if dungeon.pending_lore.is_none() {
+                            dungeon.pending_lore = pick_floor_lore(floor);
+                            self.add_message("In perfect flow, you glimpse something hidden...");
+                        }
+                    }
+                }
+                
                 // Mark boss as defeated for this floor
                 if is_boss {
                     if let Some(dungeon) = &mut self.dungeon {
                         dungeon.boss_defeated = true;
                         
-                        // Final boss on floor 10 = victory!
-                        if dungeon.current_floor >= 10 {
+                        // Final boss on floor 10 = victory! (the endless
+                        // descent has no final boss)
+                        if dungeon.current_floor >= FINAL_FLOOR as i32 && !self.endless {
                             self.current_enemy = None;
                             self.combat_state = None;
-                            self.scene = Scene::Victory;
-                            self.runs_completed += 1;
+                            self.win_run();
                             return;
                         }
                     }
@@ -331,6 +1528,16 @@ impl GameState {
         self.scene = Scene::BattleSummary;
     }
 
+    /// Play back the last fight's typing. False if nothing was recorded.
+    pub fn open_replay(&mut self) -> bool {
+        let Some(replay) = self.last_replay.clone().filter(|r| !r.is_empty()) else {
+            return false;
+        };
+        self.replay_viewer = Some(ReplayViewer::new(replay));
+        self.scene = Scene::Replay;
+        true
+    }
+
     pub fn start_event(&mut self, event: GameEvent) {
         self.current_event = Some(event);
         self.scene = Scene::Event;
@@ -356,78 +1563,581 @@ impl GameState {
         if let Some(dungeon) = &mut self.dungeon {
             dungeon.current_room.cleared = true;
             dungeon.rooms_cleared += 1;
-            
-            // If floor was complete, advance to next floor
-            if should_advance {
-                dungeon.advance_floor();
-            }
         }
         
-        // Show floor advancement message after dungeon borrow ends
         if should_advance {
-            if let Some(dungeon) = &self.dungeon {
-                self.add_message(&format!("Descended to floor {}!", dungeon.current_floor));
+            // The stairs may fork into two zones; descend once one is picked
+            let next_floor = self.get_current_floor() as u32 + 1;
+            if let Some(options) = zone_routes::current().fork(next_floor) {
+                self.route_fork = options;
+                self.menu_index = 0;
+                self.scene = Scene::RouteChoice;
+            } else {
+                self.descend();
             }
         }
     }
 
-    pub fn end_treasure(&mut self) {
-        // Mark treasure room as cleared and increment counter
-        if let Some(dungeon) = &mut self.dungeon {
-            dungeon.current_room.cleared = true;
-            dungeon.rooms_cleared += 1;
+    /// Sleep at the campfire; true if a dream comes
+    pub fn try_dream(&mut self) -> bool {
+        let chapter = dreams::chapter_of(self.get_current_floor());
+        let Some(dream) = self.dreams.next_dream(chapter, &mut run_rng::stream(Stream::Pacing)) else {
+            return false;
+        };
+        self.dream_run = Some(DreamRun::new(dream));
+        self.scene = Scene::Dream;
+        true
+    }
+
+    /// Leave the dream's scene for its words
+    pub fn begin_dream_typing(&mut self) {
+        if let Some(run) = &mut self.dream_run {
+            run.begin_typing(&mut run_rng::stream(Stream::Words));
         }
     }
 
-    pub fn end_shop(&mut self) {
-        self.scene = Scene::Dungeon;
-        self.shop_items.clear();
-        
-        // Mark shop room as cleared and increment counter
-        if let Some(dungeon) = &mut self.dungeon {
-            dungeon.current_room.cleared = true;
-            dungeon.rooms_cleared += 1;
+    /// Grade the dream's words and wake up
+    pub fn wake_from_dream(&mut self) {
+        let Some(run) = &mut self.dream_run else {
+            return;
+        };
+        let dream = run.dream;
+        let Some(grade) = run.wake() else {
+            return;
+        };
+        self.dreams.wake(dream, grade);
+        self.note_clues();
+        if grade.carries() {
+            self.add_message(&format!("You wake remembering: {}", dream.clue));
+        } else {
+            self.add_message("You wake. The dream is already gone.");
         }
-        self.current_npc_dialogue = None;
     }
 
+    /// Leave a dream (woken or not) and the rest site with it
+    pub fn finish_dream(&mut self) {
+        if self.dream_run.take().is_some() {
+            self.end_rest();
+        }
+    }
 
-    pub fn enter_shop(&mut self) {
-        use rand::seq::SliceRandom;
-        
-        let mut rng = rand::thread_rng();
-        let mut items = Vec::new();
-        
-        // Add some consumables
-        let consumables = Item::consumable_pool();
-        for item in consumables.choose_multiple(&mut rng, 2) {
-            items.push(item.clone());
+    /// Take the fork into `zone` and descend into it
+    pub fn choose_route(&mut self, zone: FloorZone) {
+        let next_floor = self.get_current_floor() as u32 + 1;
+        if !self.route_fork.contains(&zone) || !zone_routes::choose(next_floor, zone) {
+            return;
+        }
+        self.route_fork.clear();
+        self.scene = Scene::Dungeon;
+        self.add_message(&format!("You take the stair toward {}.", zone.name()));
+        self.descend();
+    }
+
+    /// Go down to the next floor
+    fn descend(&mut self) {
+        let Some(dungeon) = &mut self.dungeon else {
+            return;
+        };
+        dungeon.advance_floor();
+        let floor = dungeon.current_floor;
+        self.add_message(&format!("Descended to floor {}!", floor));
+        self.note_clues();
+        self.note_revelation();
+        if self.endless {
+            self.note_endless_depth();
         }
+    }
+
+    /// Maybe find a lore artifact in a treasure room; returns the message
+    pub fn find_artifact(&mut self) -> Option<String> {
+        use rand::seq::SliceRandom;
         
-        // Add a joker if lucky
-        if rand::random::<f32>() < 0.3 {
-            let jokers = Item::joker_pool();
-            if let Some(joker) = jokers.choose(&mut rng) {
-                items.push(joker.clone());
+        let player = self.player.as_mut()?;
+        if run_rng::stream(Stream::Map).gen::<f32>() >= ARTIFACT_CHANCE {
+            return None;
+        }
+        let relic: &Relic = artifacts::unfound(&player.artifacts).choose(&mut run_rng::stream(Stream::Map)).copied()?;
+        player.artifacts.push(relic.artifact.to_string());
+        let description = relic.lore().map(|a| a.description).unwrap_or_default();
+        Some(format!("✧ You find {}! {} ({})", relic.artifact, description, relic.boon()))
+    }
+
+    /// Maybe find a treasure chest corrupted, its loot cursed, and offer
+    /// it to the player; returns whether one was found. Likelier, and the
+    /// bargain steeper, the more corrupted the zone.
+    pub fn open_corrupted_chest(&mut self) -> bool {
+        let floor = self.dungeon.as_ref().map(|d| d.current_floor as u32).unwrap_or(1);
+        let corruption = FloorZone::from_floor(floor).corruption_level();
+        if self.player.is_none()
+            || run_rng::stream(Stream::Map).gen::<f32>() >= curses::cursed_chest_chance(corruption)
+        {
+            return false;
+        }
+        let curse = Curse::roll(corruption, &mut run_rng::stream(Stream::Map));
+        self.start_event(GameEvent::corrupted_chest(curse));
+        self.menu_index = 0;
+        true
+    }
+
+    /// Grant XP; each level earned brings skill points and a level-up
+    /// choice, offered once the player is back in the dungeon
+    pub fn grant_experience(&mut self, xp: u64) {
+        let Some(player) = &mut self.player else {
+            return;
+        };
+        let levels = player.gain_experience(xp);
+        let level = player.level;
+        for new_level in (level + 1 - levels)..=level {
+            self.skill_tree.skill_points += SKILL_POINTS_PER_LEVEL;
+            self.event_bus.emit(BusEvent::PlayerLeveledUp {
+                new_level,
+                skill_points: SKILL_POINTS_PER_LEVEL,
+            });
+        }
+        self.pending_level_ups += levels;
+    }
+
+    /// Show the level-up choices if a level is waiting and the player is
+    /// free to pick
+    pub fn offer_level_up(&mut self) {
+        if self.pending_level_ups > 0 && self.scene == Scene::Dungeon {
+            self.menu_index = 0;
+            self.scene = Scene::LevelUp;
+        }
+    }
+
+    /// Spend a waiting level on `choice`
+    pub fn choose_level_up(&mut self, choice: LevelUpChoice) {
+        let Some(player) = &mut self.player else {
+            return;
+        };
+        if self.pending_level_ups == 0 {
+            return;
+        }
+        choice.apply(player);
+        self.pending_level_ups -= 1;
+        self.add_message(&format!("{}: {}.", choice.name(), choice.describe()));
+        if self.pending_level_ups == 0 {
+            self.scene = Scene::Dungeon;
+        }
+    }
+
+    /// Open the quest journal on what the run has set in motion
+    pub fn open_journal(&mut self) {
+        self.journal = journal::build(
+            &self.encounter_tracker,
+            &self.encounters,
+            &self.world_state.ledger,
+            self.get_current_floor(),
+            &self.faction_relations,
+            &self.world_state,
+        );
+        for clue in &self.heard_clues {
+            if !self.journal.clues.contains(clue) {
+                self.journal.clues.push(clue.clone());
+            }
+        }
+        self.journal.clues.extend(self.dreams.clues.iter().map(|c| format!("Dream: {}", c)));
+        self.menu_index = 0;
+        self.scene = Scene::Journal;
+    }
+
+    /// Open the codex on everything the run has uncovered
+    pub fn open_codex(&mut self) {
+        let artifacts = self.player.as_ref().map(|p| p.artifacts.clone()).unwrap_or_default();
+        let tomorrow_texts = prophecies::reckoning(self.world_state.counter(FULFILLED), self.world_state.counter(SUBVERTED));
+        self.codex_entries = codex::build(&CodexSources {
+            fragments: &self.codex,
+            factions: &self.faction_relations,
+            artifacts: &artifacts,
+            tomorrow_texts: tomorrow_texts.as_deref(),
+            scars: self.scars.all(),
+        });
+        self.menu_index = 0;
+        self.scene = Scene::Codex;
+    }
+
+    /// Entries in the codex section being read
+    pub fn codex_page(&self) -> Vec<&CodexEntry> {
+        self.codex_entries.iter().filter(|e| e.section == self.codex_section).collect()
+    }
+
+    /// Pin any clues to your past the run has now uncovered
+    pub fn note_clues(&mut self) {
+        let Some(player) = &self.player else {
+            return;
+        };
+        let found = self.clue_board.refresh(&ClueFacts {
+            chapter: dreams::chapter_of(self.get_current_floor()),
+            enemies_defeated: player.enemies_defeated,
+            dreamt: !self.dreams.clues.is_empty(),
+            // Neither the class relic nor the Living Book was found down here
+            artifacts_found: player.artifacts.iter().filter(|a| Relic::named(a).is_some()).count().saturating_sub(1),
+            notes_broken: self.cipher_notes.iter().filter(|n| n.broken()).count(),
+            factions: &self.faction_relations,
+        });
+        if found > 0 {
+            self.add_message("Something about your past nags at you. [b] Clue board");
+        }
+    }
+
+    /// Reveal the next stages of the hidden truth, if the story has
+    /// reached them
+    pub fn note_revelation(&mut self) {
+        let chapter = dreams::chapter_of(self.get_current_floor());
+        for level in self.revelation.advance(chapter, &self.encounter_tracker) {
+            self.add_message(&format!("󰈸 {}: {}", level.name(), level.text()));
+        }
+    }
+
+    /// Open the clue board
+    pub fn open_clue_board(&mut self) {
+        self.note_clues();
+        self.reflection = None;
+        self.menu_index = 0;
+        self.scene = Scene::ClueBoard;
+    }
+
+    /// Start reflecting on the selected clue, if it isn't understood yet
+    pub fn begin_reflection(&mut self) {
+        if self.clue_board.pinned.get(self.menu_index).is_some_and(|p| !p.understood) {
+            self.reflection = Some(String::new());
+        }
+    }
+
+    /// Grade the reflection typed on the selected clue
+    pub fn submit_reflection(&mut self) {
+        let Some(typed) = self.reflection.take() else {
+            return;
+        };
+        match self.clue_board.reflect(self.menu_index, &typed) {
+            Some(grade) if grade.carries() => self.add_message("It falls into place."),
+            Some(_) => self.add_message("The thought slips away before you can hold it."),
+            None => {}
+        }
+    }
+
+    /// Maybe find one of Cipher's notes in a treasure room; returns the message
+    pub fn find_cipher_note(&mut self) -> Option<String> {
+        let mut rng = run_rng::stream(Stream::Pacing);
+        if rng.gen::<f32>() >= NOTE_CHANCE {
+            return None;
+        }
+        let note = cipher_notes::find(&mut self.world_state, &mut rng)?;
+        let message = format!("󰌆 A note from Cipher, {}, in a cipher of its own. [x] Cipher notes", note.def.found);
+        self.cipher_notes.push(note);
+        self.save_world_state();
+        Some(message)
+    }
+
+    /// Perhaps find a Tomorrow Text about a floor ahead
+    pub fn find_prophecy(&mut self) -> Option<String> {
+        if run_rng::stream(Stream::Pacing).gen::<f32>() >= PROPHECY_CHANCE {
+            return None;
+        }
+        let last_floor = if self.endless { i32::MAX } else { FINAL_FLOOR as i32 };
+        let dungeon = self.dungeon.as_mut()?;
+        let prophecy = prophecies::foretell(dungeon, &self.game_data.enemies, last_floor, &mut run_rng::stream(Stream::Pacing))?;
+        let message = format!("󰜎 A Tomorrow Text, {}: \"{}\"", prophecy.found, prophecy.text());
+        dungeon.prophecies.push(prophecy);
+        Some(message)
+    }
+
+    /// The floor's first fight, if a Tomorrow Text spoke of it: the elite
+    /// it named if it comes true, else the ordinary fight (`None`). Either
+    /// way the prophecy is counted.
+    pub fn foretold_fight(&mut self) -> Option<Enemy> {
+        let floor = self.get_current_floor();
+        let dungeon = self.dungeon.as_mut().filter(|d| d.rooms_cleared == 0)?;
+        let prophecy = prophecies::test(&mut dungeon.prophecies, floor)?;
+        self.world_state.bump(if prophecy.comes_true { FULFILLED } else { SUBVERTED }, 1);
+        self.save_world_state();
+        if !prophecy.comes_true {
+            self.add_message(&format!("The Tomorrow Text said the {} would be waiting here. It was wrong.", prophecy.elite));
+            return None;
+        }
+        self.add_message(&format!("The {} is waiting, just as the Tomorrow Text said.", prophecy.elite));
+        Some(Enemy::zone_elite_data(&self.game_data, &prophecy.elite_id, floor, self.enemy_director()))
+    }
+
+    /// Open the notes Cipher has left this run
+    pub fn open_cipher_notes(&mut self) {
+        self.cipher_guess = None;
+        self.menu_index = 0;
+        self.scene = Scene::Ciphers;
+    }
+
+    /// Start a guess at the selected note, if it isn't broken yet
+    pub fn begin_cipher_guess(&mut self) {
+        if self.cipher_notes.get(self.menu_index).is_some_and(|n| !n.broken()) {
+            self.cipher_guess = Some(String::new());
+        }
+    }
+
+    /// Check the guess typed at the selected note
+    pub fn submit_cipher_guess(&mut self) {
+        let Some(typed) = self.cipher_guess.take() else {
+            return;
+        };
+        let Some(note) = self.cipher_notes.get_mut(self.menu_index) else {
+            return;
+        };
+        match note.guess(typed.trim()) {
+            Guess::Broken => {
+                let (standing, plain) = (note.standing(), note.def.plain);
+                self.faction_relations.modify_standing(Faction::ShadowGuild, standing);
+                self.add_message(&format!("Broken: \"{}\" ({} standing {:+})", plain, Faction::ShadowGuild.name(), standing));
+                self.note_clues();
+            }
+            Guess::Closer { revealed, hint } => {
+                let placed = match revealed {
+                    0 => "Nothing lines up".to_string(),
+                    1 => "One letter falls into place".to_string(),
+                    n => format!("{} letters fall into place", n),
+                };
+                let hint = hint.map(|c| format!("; '{}' gives itself away", c)).unwrap_or_default();
+                self.add_message(&format!("{}{}.", placed, hint));
+            }
+        }
+    }
+
+    /// Start decoding the selected letter, if it still hides a note
+    pub fn begin_decoding(&mut self) {
+        if self.mailbox.get(self.menu_index).is_some_and(|l| l.puzzle().is_some()) {
+            self.decoding = Some(String::new());
+        }
+    }
+
+    /// Check the decoding typed for the selected letter
+    pub fn submit_decoding(&mut self) {
+        let Some(typed) = self.decoding.take() else {
+            return;
+        };
+        let Some(letter) = self.mailbox.get_mut(self.menu_index) else {
+            return;
+        };
+        let Some(plain) = letter.puzzle() else {
+            return;
+        };
+        if letters::decodes(&typed, plain) {
+            letter.decoded = true;
+            if let Some(player) = &mut self.player {
+                player.gold += DECODE_GOLD;
             }
+            self.add_message(&format!("Decoded: \"{}\" (+{} gold)", plain, DECODE_GOLD));
+        } else {
+            self.add_message("The letters won't line up. Not yet.");
+        }
+    }
+
+    /// Spend skill points on the Discipline skill at `index`; returns the message
+    pub fn unlock_discipline(&mut self, index: usize) -> String {
+        let Some(skill) = self.skill_tree.discipline().get(index).cloned() else {
+            return "No such skill.".to_string();
+        };
+        if self.skill_tree.has_skill(&skill.id) {
+            format!("{} is already yours.", skill.name)
+        } else if self.skill_tree.unlock_skill(&skill.id) {
+            format!("Learned {}! {}", skill.name, skill.description)
+        } else if self.skill_tree.skill_points < skill.cost {
+            format!("{} needs {} skill points.", skill.name, skill.cost)
+        } else {
+            format!("{} builds on an earlier skill.", skill.name)
         }
+    }
+
+    pub fn end_treasure(&mut self) {
+        // Mark treasure room as cleared and increment counter
+        if let Some(dungeon) = &mut self.dungeon {
+            dungeon.current_room.cleared = true;
+            dungeon.rooms_cleared += 1;
+        }
+    }
+
+    pub fn end_shop(&mut self) {
+        self.scene = Scene::Dungeon;
+        self.shop = Shop::default();
         
-        self.shop_items = items;
+        // Mark shop room as cleared and increment counter
+        if let Some(dungeon) = &mut self.dungeon {
+            dungeon.current_room.cleared = true;
+            dungeon.rooms_cleared += 1;
+        }
+        self.current_npc_dialogue = None;
+    }
+
+
+    pub fn enter_shop(&mut self) {
+        let floor = self.get_current_floor().max(1) as u32;
+        let known = self.player.as_ref().map(|p| p.lexicons.clone()).unwrap_or_default();
+        let price_mult = shop::price_mult(floor, &self.faction_relations);
+        self.shop = Shop::stock(&self.game_data, floor, price_mult, &known, &mut run_rng::stream(Stream::Map));
         self.scene = Scene::Shop;
         self.menu_index = 0;
         
-        // Generate merchant greeting based on faction standing
-        let greeting = self.get_merchant_greeting();
+        // A shop is a breather: let the pacing say so
+        self.pacing.on_shop_enter();
+        while let Some(beat) = self.pacing.pop_beat() {
+            self.add_message(beat.text());
+        }
+        
+        // Generate merchant greeting based on faction standing, unless a
+        // scar catches the merchant's eye
+        let greeting = self.scars.remark(&mut run_rng::stream(Stream::Dialogue)).unwrap_or_else(|| self.get_merchant_greeting());
+        self.transcript.record(Source::Dialogue, Some("Merchant"), &greeting);
         self.current_npc_dialogue = Some(("Merchant".to_string(), greeting));
+        self.hear_rumor("at the shop");
+    }
+
+    /// Hear a rumor about the floors ahead, if there is one left to hear
+    fn hear_rumor(&mut self, source: &str) {
+        let Some(dungeon) = &mut self.dungeon else {
+            return;
+        };
+        let Some(rumor) = rumors::hear(dungeon, &self.game_data.enemies, source, &mut run_rng::stream(Stream::Pacing)) else {
+            return;
+        };
+        let message = format!("Heard {} ({}): {}.", source, rumor.certainty.name(), rumor.text());
+        dungeon.rumors.push(rumor);
+        self.add_message(&message);
+    }
+
+    /// Buy the offer at `index`; returns the message to show
+    pub fn buy_offer(&mut self, index: usize) -> Option<String> {
+        let offer = self.shop.offers.get(index)?.clone();
+        let player = self.player.as_mut()?;
+        if player.gold < offer.price as u64 {
+            return Some("Not enough gold!".to_string());
+        }
+        let message = match offer.ware {
+            Ware::Item(item) => {
+                player.inventory.push(item);
+                format!("Bought {}!", offer.name)
+            }
+            Ware::Heal { percent } => {
+                if player.hp >= player.max_hp {
+                    return Some("You are already whole.".to_string());
+                }
+                let healed = ((player.max_hp as f32 * percent).round() as i32).min(player.max_hp - player.hp);
+                player.hp += healed;
+                format!("{}: +{} HP.", offer.name, healed)
+            }
+            Ware::Lexicon { id, .. } => {
+                player.lexicons.push(id);
+                format!("You learn the {}. Its words will find their way into your fights.", offer.name)
+            }
+        };
+        player.gold -= offer.price as u64;
+        self.shop.offers.remove(index);
+        if self.menu_index > 0 && self.menu_index >= self.shop.offers.len() {
+            self.menu_index = self.shop.offers.len().saturating_sub(1);
+        }
+        Some(message)
+    }
+
+    /// Words from every lexicon the player has learned
+    pub fn lexicon_words(&self) -> Vec<String> {
+        let Some(player) = &self.player else {
+            return Vec::new();
+        };
+        player.lexicons.iter()
+            .filter_map(|id| self.game_data.shop.get_lexicon(id))
+            .flat_map(|l| l.words.iter().cloned())
+            .collect()
+    }
+
+    /// Enter on the combat command line: a consumable in the bag is used
+    /// by name, anything else is tried as a class ability
+    pub fn submit_combat_command(&mut self) {
+        let (Some(combat), Some(player)) = (&mut self.combat_state, &mut self.player) else {
+            return;
+        };
+        let Some(item) = combat.ability_input.as_deref().and_then(|input| self.game_data.items.consumable_named(input)) else {
+            combat.submit_ability();
+            return;
+        };
+        let slot = player.inventory.iter()
+            .position(|i| matches!(&i.effect, ItemEffect::Combat(id) if *id == item.id));
+        match slot {
+            Some(slot) => {
+                if combat.use_consumable(item, player) {
+                    player.inventory.remove(slot);
+                }
+            }
+            None => {
+                combat.cancel_ability();
+                combat.battle_log.push(format!("You have no {} left.", item.name));
+            }
+        }
+    }
+
+    /// Wear a piece of typing gear from the inventory; whatever it replaces
+    /// goes back in the bag. Returns the message to show.
+    pub fn equip_gear(&mut self, id: &str) -> String {
+        let Some(gear) = self.game_data.items.get_equipment(id).cloned() else {
+            return format!("Nothing called '{}' can be worn.", id);
+        };
+        let Some(player) = &mut self.player else {
+            return String::new();
+        };
+        let replaced = player.wear_gear(gear.slot, gear.id.clone());
+        if let Some(old) = replaced.and_then(|old| self.game_data.items.get_equipment(&old)) {
+            player.inventory.push(Item::from_equipment(old));
+            format!("You swap {} for {}. ({})", old.name, gear.name, gear.impact.summary())
+        } else {
+            format!("You equip {}. ({})", gear.name, gear.impact.summary())
+        }
     }
 
     pub fn enter_rest(&mut self) {
         self.scene = Scene::Rest;
         self.menu_index = 0;
         
-        // Generate Temple of Dawn greeting for rest sites
-        let greeting = self.generate_npc_dialogue(Faction::TempleOfDawn, DialogueContext::Greeting);
+        // Generate Temple of Dawn greeting for rest sites; a healer notices scars
+        let greeting = self.scars.remark(&mut run_rng::stream(Stream::Dialogue))
+            .unwrap_or_else(|| self.generate_npc_dialogue(Faction::TempleOfDawn, DialogueContext::Greeting));
+        self.transcript.record(Source::Dialogue, Some("Healer"), &greeting);
         self.current_npc_dialogue = Some(("Healer".to_string(), greeting));
+        self.camp_talk = self.companions.present().iter()
+            .map(|c| (c.name.to_string(), self.npc_line(c.id, DialogueContext::Greeting).unwrap_or_default()))
+            .collect();
+        for (name, line) in self.camp_talk.iter().filter(|(_, l)| !l.is_empty()) {
+            self.transcript.record(Source::Dialogue, Some(name), line);
+        }
+    }
+    
+    /// Take companion `index`'s service at the campfire
+    pub fn use_companion_service(&mut self, index: usize) {
+        let Some(companion) = self.companions.present().get(index).copied() else {
+            return;
+        };
+        let floor = self.get_current_floor().max(1) as u64;
+        if self.player.is_none() {
+            return;
+        }
+        let message = match companion.service {
+            CompanionService::Repairs => {
+                let Some(player) = &mut self.player else { return };
+                let hp = (player.max_hp as f32 * 0.2) as i32;
+                let mp = (player.max_mp as f32 * 0.2) as i32;
+                player.heal(hp);
+                player.restore_mp(mp);
+                format!("{} patches you up. Recovered {} HP and {} MP.", companion.name, hp, mp)
+            }
+            CompanionService::Drill => {
+                let xp = 15 * floor;
+                self.grant_experience(xp);
+                format!("{} drills you on the old forms. Gained {} XP.", companion.name, xp)
+            }
+            CompanionService::Secrets => {
+                let gold = 12 * floor;
+                if let Some(player) = &mut self.player {
+                    player.gold += gold;
+                }
+                format!("{} sells what you overheard. Gained {} gold.", companion.name, gold)
+            }
+        };
+        self.add_message(&message);
     }
     
     /// Generate faction-appropriate NPC dialogue
@@ -440,6 +2150,13 @@ impl GameState {
         }
     }
     
+    /// A procedural line in a named NPC's own voice
+    pub fn npc_line(&self, npc: &str, context: DialogueContext) -> Option<String> {
+        let voice = self.npc_voices.get(npc)?;
+        let all: Vec<&NpcVoice> = self.npc_voices.values().collect();
+        Some(voice.generate_line(context, &all, &mut rand::thread_rng()))
+    }
+    
     /// Get a greeting from a merchant based on faction standings
     pub fn get_merchant_greeting(&self) -> String {
         let mut rng = rand::thread_rng();
@@ -465,64 +2182,350 @@ impl GameState {
     
     /// Try to trigger an authored encounter for the current location
     pub fn try_trigger_encounter(&mut self) -> bool {
-        let floor = self.get_current_floor();
-        let location = format!("floor_{}", floor);
-        
-        // Find a valid encounter for this location
-        let valid_encounter = self.encounters.values()
-            .find(|e| {
-                // Check location
-                e.valid_locations.iter().any(|loc| loc == &location || loc == "any")
-                // Check not already completed (unless repeatable)
-                && (e.repeatable || !self.encounter_tracker.has_completed(&e.id))
-                // Check chapter requirements
-                && e.requirements.min_chapter.map_or(true, |min| floor >= min as i32)
-                && e.requirements.max_chapter.map_or(true, |max| floor <= max as i32)
-            })
-            .cloned();
-        
-        if let Some(encounter) = valid_encounter {
-            self.current_encounter = Some(encounter);
+        let lore = self.codex.discovery_order.clone();
+        let ctx = SchedulingContext {
+            floor: self.get_current_floor(),
+            tracker: &self.encounter_tracker,
+            factions: &self.faction_relations,
+            world: &self.world_state,
+            lore,
+            npcs: &self.npc_memory,
+            ideology: &self.ideology,
+        };
+        let picked = self.encounter_scheduler.pick(&self.encounters, &ctx, &mut run_rng::stream(Stream::Map));
+        
+        if let Some(encounter) = picked {
+            self.transcript.record(Source::Encounter, Some(&encounter.title), &paragraphs(&encounter.content.description).join(" "));
+            self.encounter_run = Some(EncounterRun::new(encounter));
+            self.scene = Scene::Encounter;
             return true;
         }
         false
     }
     
-    /// Resolve an encounter choice
-    pub fn resolve_encounter(&mut self, choice_idx: usize) {
-        if let Some(encounter) = self.current_encounter.take() {
-            if let Some(choice) = encounter.choices.get(choice_idx) {
-                // Record the choice
-                self.encounter_tracker.complete_encounter(&encounter.id, &choice.id);
-                
-                // Apply consequences
-                let cons = &encounter.consequences;
-                for (faction_name, change) in &cons.reputation_changes {
-                    // Try to map faction name to enum
-                    let faction: Option<Faction> = match faction_name.as_str() {
-                        "MagesGuild" => Some(Faction::MagesGuild),
-                        "TempleOfDawn" => Some(Faction::TempleOfDawn),
-                        "ShadowGuild" => Some(Faction::ShadowGuild),
-                        "MerchantConsortium" => Some(Faction::MerchantConsortium),
-                        "RangersOfTheWild" => Some(Faction::RangersOfTheWild),
-                        _ => None,
-                    };
-                    if let Some(f) = faction {
-                        self.faction_relations.modify_standing(f, *change);
-                    }
-                }
-                
-                // Emit event
-                self.event_bus.emit(BusEvent::RandomEncounter {
-                    encounter_type: encounter.title.clone(),
-                    location: format!("floor_{}", self.get_current_floor()),
-                });
-                
-                self.add_message(&format!("Completed: {}", encounter.title));
+    /// A room was entered: time passes, and a new floor brings new weather
+    pub fn note_room_entered(&mut self) {
+        let floor = self.get_current_floor();
+        self.encounter_scheduler.note_room(floor, &mut run_rng::stream(Stream::Map));
+        self.search_rumored_cache();
+        if let Some(dungeon) = &self.dungeon {
+            let room_type = dungeon.current_room.room_type.name().to_lowercase();
+            self.pacing.on_room_enter(floor as u32, &room_type);
+        }
+        while let Some(beat) = self.pacing.pop_beat() {
+            self.add_message(beat.text());
+            if let PacingBeat::MemoryFlash { lore_key: Some(key), .. } = beat {
+                self.remember(&key);
+            }
+        }
+        // The voice you lean toward speaks most
+        let mut rng = run_rng::stream(Stream::Pacing);
+        if rng.gen::<f32>() < VOICE_CHANCE {
+            if let Some((voice, line)) = self.ideology.voice(&mut rng) {
+                self.add_message(&format!("{}: \"{}\"", voice.name, line));
             }
         }
     }
 
+    /// On the last floors, once a run, show what its choices have made of
+    /// you; true if the reveal is showing
+    pub fn reveal_ideology(&mut self) -> bool {
+        let due = self.get_current_floor() >= REVEAL_FLOOR
+            && self.dungeon.as_ref().is_some_and(|d| d.rooms_cleared == 0);
+        if self.ideology_revealed || !due {
+            return false;
+        }
+        self.ideology_revealed = true;
+        self.scene = Scene::Ideology;
+        true
+    }
+
+    /// Keep a memory that flashed past in the gallery, and if it makes a
+    /// chapter's memories whole, let them add up to something
+    fn remember(&mut self, key: &str) {
+        let whole = memories::remember(&mut self.world_state, key);
+        self.save_world_state();
+        let Some(chapter) = whole else {
+            return;
+        };
+        if let Some(player) = &mut self.player {
+            player.max_hp += WHOLE_CHAPTER_HP;
+            player.hp += WHOLE_CHAPTER_HP;
+        }
+        self.add_message(&format!(
+            "The memories of chapter {} fit together. You remember who you were a little better. +{} max HP [m] Memories",
+            chapter, WHOLE_CHAPTER_HP,
+        ));
+    }
+
+    /// Open the gallery of memories seen
+    pub fn open_memories(&mut self) {
+        self.memory_replay = None;
+        self.menu_index = 0;
+        self.scene = Scene::Memories;
+    }
+
+    /// Replay the selected memory, if it has been seen
+    pub fn replay_memory(&mut self) {
+        if memories::MEMORIES.get(self.menu_index).is_some_and(|m| memories::seen(&self.world_state, m)) {
+            self.memory_replay = Some(Instant::now());
+        }
+    }
+
+    /// Look for the cache a rumor put in the room just entered
+    fn search_rumored_cache(&mut self) {
+        let Some(dungeon) = &mut self.dungeon else {
+            return;
+        };
+        let (floor, row) = (dungeon.current_floor, dungeon.rooms_cleared.max(0) as usize);
+        let Some(&col) = dungeon.floor_map.path.get(row) else {
+            return;
+        };
+        let cache = RumorSubject::Cache { floor, row, col };
+        let Some(index) = dungeon.rumors.iter().position(|r| r.subject == cache) else {
+            return;
+        };
+        if dungeon.rumors.remove(index).holds {
+            if let Some(player) = &mut self.player {
+                player.gold += CACHE_GOLD;
+            }
+            self.add_message(&format!("The rumor was true: a cache behind a loose stone. +{} gold", CACHE_GOLD));
+        } else {
+            self.add_message("Nothing hides here. The rumor was only a rumor.");
+        }
+    }
+    
+    /// Take a choice in the running encounter; a typed choice waits for
+    /// its typing first
+    pub fn choose_encounter(&mut self, choice_idx: usize) {
+        let Some(run) = &mut self.encounter_run else {
+            return;
+        };
+        if let Some(choice) = run.choose(choice_idx) {
+            run.settle(choice);
+            let narrative = run.encounter.consequences.narrative_result.clone();
+            self.resolve_encounter(choice, true);
+            self.conclude_encounter(vec![narrative]);
+        }
+    }
+    
+    /// Talk with whoever is in the current encounter
+    pub fn start_conversation(&mut self) {
+        let Some(tree) = self.encounter_run.as_ref().and_then(|r| dialogue_tree::conversation_for(&r.encounter)) else {
+            return;
+        };
+        let mut runner = DialogueRunner::new(tree);
+        // Someone you've met before may greet you as they remember you
+        if let Some(speaker) = runner.node().map(|n| n.speaker.clone()) {
+            let id = npc_memory::npc_id(&speaker);
+            let name = self.npc_voices.get(id.as_str()).map_or(speaker.as_str(), |v| v.name);
+            // ...and otherwise in their own voice
+            let greeting = self.npc_memory.greeting(&speaker, name)
+                .or_else(|| self.npc_line(&id, DialogueContext::Greeting));
+            if let Some(greeting) = greeting {
+                runner.transcript.insert(0, (speaker.clone(), greeting));
+            }
+            self.encounter_tracker.meet_npc(&id);
+        }
+        self.transcript.begin(Source::Dialogue);
+        self.conversation = Some(runner);
+        self.note_revealed(0);
+        self.menu_index = 0;
+        self.scene = Scene::Conversation;
+    }
+
+    /// Pick the `choice`th option in the conversation
+    pub fn choose_dialogue(&mut self, choice: usize) {
+        let floor = self.get_current_floor();
+        let Some(runner) = &mut self.conversation else {
+            return;
+        };
+        let heard = runner.revealed.len();
+        let gate = DialogueGate {
+            factions: &self.faction_relations,
+            world: &self.world_state,
+            lore: &self.codex.discovery_order,
+            best_wpm: self.best_wpm,
+            revelation: self.revelation,
+            npcs: &self.npc_memory,
+            floor,
+            tracker: &self.encounter_tracker,
+        };
+        runner.choose(choice, &gate);
+        self.menu_index = 0;
+        self.note_revealed(heard);
+    }
+
+    /// The options the conversation offers right now
+    pub fn dialogue_options(&self) -> Vec<OptionView> {
+        let Some(runner) = &self.conversation else {
+            return Vec::new();
+        };
+        runner.options(&DialogueGate {
+            factions: &self.faction_relations,
+            world: &self.world_state,
+            lore: &self.codex.discovery_order,
+            best_wpm: self.best_wpm,
+            revelation: self.revelation,
+            npcs: &self.npc_memory,
+            floor: self.get_current_floor(),
+            tracker: &self.encounter_tracker,
+        })
+    }
+
+    /// Move the conversation on, or leave it once it has ended
+    pub fn advance_dialogue(&mut self) {
+        let Some(runner) = &mut self.conversation else {
+            return;
+        };
+        if runner.ended {
+            self.leave_conversation();
+            return;
+        }
+        let heard = runner.revealed.len();
+        runner.advance();
+        self.note_revealed(heard);
+    }
+
+    /// Grade a typing check in the conversation
+    pub fn submit_dialogue_typing(&mut self) {
+        let Some(runner) = &mut self.conversation else {
+            return;
+        };
+        let heard = runner.revealed.len();
+        runner.submit_typing();
+        self.menu_index = 0;
+        self.note_revealed(heard);
+    }
+
+    /// Back to the encounter's choices
+    pub fn leave_conversation(&mut self) {
+        self.conversation = None;
+        self.menu_index = 0;
+        self.scene = Scene::Encounter;
+    }
+
+    /// Tell the player about clues heard since the first `heard`
+    fn note_revealed(&mut self, heard: usize) {
+        let clues: Vec<String> = self.conversation.iter().flat_map(|r| r.revealed.iter().skip(heard).cloned()).collect();
+        for clue in clues {
+            self.add_message(&format!("Noted: {}", clue));
+            if !self.heard_clues.contains(&clue) {
+                self.heard_clues.push(clue);
+            }
+        }
+    }
+    
+    /// Grade the typing of a typed choice and resolve it
+    pub fn submit_encounter_typing(&mut self) {
+        let Some(run) = &self.encounter_run else {
+            return;
+        };
+        let Some((choice, grade)) = run.grade() else {
+            return;
+        };
+        let mut text: Vec<String> = run.typing_narrative(grade).into_iter().collect();
+        // A ritual's every outcome has its consequences
+        if let Some(consequences) = run.ritual().map(|r| r.consequences(grade).clone()) {
+            text.push(consequences.narrative_result.clone());
+            if let Some(run) = &mut self.encounter_run {
+                run.encounter.consequences = consequences;
+                run.ritual_started = None;
+            }
+            self.resolve_encounter(choice, true);
+            self.conclude_encounter(text);
+            return;
+        }
+        let Some(run) = &mut self.encounter_run else {
+            return;
+        };
+        run.settle(choice);
+        if grade.carries() {
+            text.push(run.encounter.consequences.narrative_result.clone());
+        } else {
+            text.push("The words slip away from you, and with them the moment.".to_string());
+        }
+        self.resolve_encounter(choice, grade.carries());
+        self.conclude_encounter(text);
+    }
+    
+    fn conclude_encounter(&mut self, text: Vec<String>) {
+        if let Some(run) = &mut self.encounter_run {
+            for line in text.iter().filter(|l| !l.is_empty()) {
+                self.transcript.record(Source::Encounter, Some(&run.encounter.title), &paragraphs(line).join(" "));
+            }
+            run.conclude(text);
+        }
+    }
+    
+    /// Leave a finished encounter, clearing its room
+    pub fn finish_encounter(&mut self) {
+        if matches!(self.encounter_run.as_ref().map(|r| &r.stage), Some(EncounterStage::Outcome { .. })) {
+            self.encounter_run = None;
+            self.end_event();
+        }
+    }
+    
+    /// Resolve an encounter choice: it is always recorded, its consequences
+    /// only apply if `carried`
+    pub fn resolve_encounter(&mut self, choice_idx: usize, carried: bool) {
+        let Some(encounter) = self.encounter_run.as_ref().map(|r| r.encounter.clone()) else {
+            return;
+        };
+        let Some(choice) = encounter.choices.get(choice_idx) else {
+            return;
+        };
+        // Record the choice
+        self.encounter_tracker.complete_encounter(&encounter.id, &choice.id);
+        self.run_log.note_choice(&encounter, choice, carried);
+        self.ideology.on_choice(&encounter.id, &choice.id);
+        self.add_message(&format!("Completed: {}", encounter.title));
+        for (npc, change) in self.npc_memory.on_choice(&encounter, &choice.id, carried) {
+            let name = self.npc_voices.get(npc.as_str()).map_or(npc.as_str(), |v| v.name);
+            self.run_log.note_opinion(name, change);
+            self.add_message(&format!("{} will remember that.", name));
+        }
+        if !carried {
+            return;
+        }
+        
+        for change in self.companions.on_choice(&encounter.id, &choice.id) {
+            self.add_message(&change.message());
+        }
+        for message in npc_lifecycle::on_choice(&mut self.world_state, &encounter.id, &choice.id) {
+            self.add_message(message);
+        }
+        
+        // Apply consequences
+        let mut no_inventory = Vec::new();
+        let applied = consequence_engine::apply(&encounter, &mut ConsequenceTargets {
+            factions: &mut self.faction_relations,
+            codex: &mut self.codex,
+            inventory: self.player.as_mut().map_or(&mut no_inventory, |p| &mut p.inventory),
+            items: &self.game_data.items,
+            world: &mut self.world_state,
+            tracker: &mut self.encounter_tracker,
+            scheduler: &mut self.encounter_scheduler,
+        });
+        for message in applied.messages() {
+            self.add_message(&message);
+        }
+        self.ideology.on_standing(&applied.reputation);
+        if self.player.as_mut().is_some_and(|p| living_book::carry(&self.world_state, &mut p.artifacts)) {
+            self.add_message(&format!("{} is yours to carry now.", living_book::BOOK));
+        }
+        self.note_clues();
+        self.note_revelation();
+        self.world_state.bump(ENCOUNTERS_RESOLVED, 1);
+        self.save_world_state();
+        
+        // Emit event
+        self.event_bus.emit(BusEvent::RandomEncounter {
+            encounter_type: encounter.title.clone(),
+            location: format!("floor_{}", self.get_current_floor()),
+        });
+    }
+
 
     
     /// Get enemy health multiplier from run modifiers
@@ -573,32 +2576,228 @@ impl GameState {
 
     pub fn check_game_over(&mut self) -> bool {
         if let Some(player) = &self.player {
+            if player.hp <= 0 && self.training.is_some() {
+                self.check_lesson();
+                return true;
+            }
             if player.hp <= 0 {
-                // Award Ink based on progress
-                let floor = self.get_current_floor() as u64;
-                let ink_earned = floor * 10 + (self.total_enemies_defeated as u64 * 2) 
-                    + (self.total_words_typed as u64);
-                self.meta_progress.current_ink += ink_earned;
-                self.meta_progress.total_ink += ink_earned;
-                self.meta_progress.runs_attempted += 1;
-                self.add_message(&format!("󰙤 Earned {} Ink from this run", ink_earned));
-                
-                self.scene = Scene::GameOver;
+                self.lose_run();
                 return true;
             }
         }
         false
     }
 
+    /// The run is lost: award its Ink, settle its records and show it
+    fn lose_run(&mut self) {
+        // Award Ink based on progress
+        let floor = self.get_current_floor() as u64;
+        let ink_earned = floor * 10 + (self.total_enemies_defeated as u64 * 2) 
+            + (self.total_words_typed as u64);
+        self.meta_progress.current_ink += ink_earned;
+        self.meta_progress.total_ink += ink_earned;
+        self.meta_progress.runs_attempted += 1;
+        self.add_message(&format!("󰙤 Earned {} Ink from this run", ink_earned));
+        // An endless descent was already won; only its depth counts now
+        if !self.endless {
+            self.finish_challenge(false);
+            self.record_ascension(false);
+            self.write_epilogue(false, ink_earned);
+        }
+        
+        self.scene = Scene::GameOver;
+    }
+
     pub fn check_victory(&mut self) -> bool {
-        if let Some(dungeon) = &self.dungeon {
-            if dungeon.current_floor > 10 {
-                self.scene = Scene::Victory;
-                self.runs_completed += 1;
-                return true;
-            }
+        if self.endless || self.dungeon.as_ref().is_none_or(|d| d.current_floor <= FINAL_FLOOR as i32) {
+            return false;
         }
-        false
+        self.win_run();
+        true
+    }
+
+    /// The run is won: settle its ending and show it
+    fn win_run(&mut self) {
+        // A mystery ending the run has earned outranks its patron's
+        let mystery = endings::resolve(&self.ending_context());
+        let ending = match mystery {
+            Some(m) => m.id_title(),
+            None => self.patron.map(|p| p.ending()).unwrap_or(UNBOUND_ENDING),
+        };
+        self.meta_progress.endings_seen.insert(ending.0.to_string());
+        self.world_state.ledger.record(ChangeKind::EndingAchieved, ending.0, ending.1);
+        self.save_world_state();
+        self.run_ending = Some(ending);
+        self.finish_challenge(true);
+        self.record_ascension(true);
+        self.write_epilogue(true, 0);
+        self.finale = mystery.map(Finale::new);
+        self.scene = if self.finale.is_some() { Scene::Finale } else { Scene::Victory };
+        self.runs_completed += 1;
+    }
+
+    /// Keep going past the final floor after a victory. The zones come
+    /// round again with longer, mixed words and enemies keep scaling; the
+    /// run ends only in death.
+    pub fn descend_endless(&mut self) {
+        if self.scene != Scene::Victory || self.player.is_none() {
+            return;
+        }
+        let Some(dungeon) = &mut self.dungeon else {
+            return;
+        };
+        dungeon.advance_floor();
+        self.endless = true;
+        self.scene = Scene::Dungeon;
+        self.add_message("Past the Breach the stairs go on. There is no bottom.");
+        self.note_endless_depth();
+    }
+
+    /// Record the floor reached in the endless descent
+    fn note_endless_depth(&mut self) {
+        let floor = self.get_current_floor() as i64;
+        if self.world_state.raise(DEEPEST_DESCENT, floor) {
+            self.add_message(&format!("Deepest descent yet: floor {}.", floor));
+        }
+        self.save_world_state();
+    }
+
+    fn save_world_state(&mut self) {
+        if let Err(e) = world_state::save_world_state(&self.world_state) {
+            self.add_message(&format!("Could not save world state: {}", e));
+        }
+    }
+
+    /// What the run brings to the end, for resolving the mystery endings
+    fn ending_context(&self) -> EndingContext {
+        EndingContext {
+            patron: self.patron,
+            artifacts: self.player.as_ref().map(|p| p.artifacts.clone()).unwrap_or_default(),
+            curses: self.player.as_ref().map_or(0, |p| p.curses.len()),
+            standings: self.faction_relations.standings.iter().map(|(f, s)| (*f, *s)).collect(),
+            lore_titles: self.discovered_lore.iter().map(|(title, _)| title.clone()).collect(),
+            ideology: self.ideology,
+        }
+    }
+
+    /// Turn the final sequence's page, on to the victory screen at its end
+    pub fn advance_finale(&mut self) {
+        if !self.finale.as_mut().is_some_and(|f| f.advance()) {
+            self.scene = Scene::Victory;
+        }
+    }
+
+    /// Start a fresh timeline: world flags and faction history are wiped,
+    /// typing stats, Ink and cosmetics carry over
+    pub fn start_fresh_timeline(&mut self) {
+        self.meta_progress.start_new_timeline();
+        self.world_state.start_fresh();
+        self.save_world_state();
+        self.faction_relations = FactionRelations::new();
+        self.encounter_tracker = EncounterTracker::new();
+        self.codex = LoreJournal::new();
+        self.heard_clues.clear();
+        self.npc_memory = NpcMemory::new();
+        self.companions = CompanionRoster::new();
+        self.run_chronicle.clear();
+        self.timeline_confirm = false;
+        self.add_message(&format!("Timeline {} begins. The world has forgotten you.", self.world_state.ledger.timeline));
+    }
+
+    /// Put the finished run on the ascension leaderboard, unlocking the
+    /// next level on a victory at the highest
+    fn record_ascension(&mut self, victory: bool) {
+        let score = challenges::run_score(
+            self.get_current_floor(),
+            self.total_enemies_defeated,
+            self.total_words_typed,
+            self.best_wpm,
+            victory,
+        );
+        let run = AscensionRun {
+            level: self.ascension,
+            class: self.player.as_ref().map(|p| p.class.name().to_string()).unwrap_or_default(),
+            floor: self.get_current_floor(),
+            victory,
+            score: ascension::ascended_score(score, self.ascension),
+        };
+        self.last_run = Some(run.clone());
+        let (place, unlocked) = self.ascension_record.record(run);
+        if let Some(place) = place {
+            self.add_message(&format!("⇑ #{} on the ascension leaderboard", place));
+        }
+        if let Some(level) = unlocked {
+            self.add_message(&format!("⇑ Ascension {} unlocked!", level));
+        }
+        if let Err(e) = ascension::save_record(&self.ascension_record) {
+            self.add_message(&format!("Could not save ascension record: {}", e));
+        }
+    }
+
+    /// Write the finished run's epilogue and keep it in the run history
+    fn write_epilogue(&mut self, victory: bool, ink_earned: u64) {
+        let class = self.player.as_ref().map(|p| p.class.name().to_string()).unwrap_or_default();
+        let clues: Vec<String> = self.clue_board.pinned.iter().map(|p| p.clue.description.clone()).collect();
+        let near_miss = endings::nearest(&self.ending_context()).map(|(e, missing)| (e.id_title().1, missing));
+        self.epilogue = epilogue::compose(&EpilogueSources {
+            victory,
+            class: &class,
+            floor: self.get_current_floor(),
+            ending: self.run_ending,
+            near_miss: near_miss.as_ref().map(|(title, missing)| (*title, missing.as_str())),
+            log: &self.run_log,
+            factions: &self.faction_relations,
+            clues: &clues,
+        }, &mut run_rng::stream(Stream::Pacing));
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.meta_progress.record_run(RunSummary {
+            timestamp,
+            class,
+            floors_reached: self.get_current_floor(),
+            victory,
+            ending: self.run_ending.map(|(id, _)| id.to_string()).unwrap_or_default(),
+            duration_seconds: 0,
+            ink_earned,
+            stats: RunStats {
+                enemies_killed: self.total_enemies_defeated.max(0) as u32,
+                words_typed: self.total_words_typed.max(0) as u32,
+                ..RunStats::default()
+            },
+            modifiers: self.run_modifiers.active.iter().map(|m| m.modifier.name().to_string()).collect(),
+            heat: self.get_heat_level(),
+            epilogue: self.epilogue.clone(),
+        });
+        if let Err(e) = meta_progression::save_run_history(&self.meta_progress.run_history) {
+            self.add_message(&format!("Could not save run history: {}", e));
+        }
+        self.stats_store.finish_run(timestamp, victory);
+        if let Err(e) = stats_store::save(&self.stats_store) {
+            self.add_message(&format!("Could not save stats: {}", e));
+        }
+    }
+
+    /// Open the stats dashboard, to go back to `from`
+    pub fn open_dashboard(&mut self, from: Scene) {
+        self.dashboard_return = from;
+        self.dashboard_all_runs = self.player.is_none();
+        self.scene = Scene::Dashboard;
+    }
+
+    /// Cycle the ascension level on the class screen, through those unlocked
+    pub fn cycle_ascension(&mut self) {
+        self.ascension = self.ascension_record.next_level(self.ascension);
+    }
+
+    /// Cycle the patron pledge on the class screen: none → each patron → none
+    pub fn cycle_patron(&mut self) {
+        self.patron = Patron::cycle(self.patron);
+    }
+
+    /// Cycle the setting on the class screen
+    pub fn cycle_setting(&mut self) {
+        self.setting = self.setting.cycle();
     }
 
     pub fn get_current_floor(&self) -> i32 {
@@ -655,7 +2854,7 @@ impl GameState {
                 self.add_message(&format!("Your standing with {:?} has {} ({})", faction, direction, reason));
             }
             BusEvent::PlayerLeveledUp { new_level, skill_points } => {
-                self.add_message(&format!("Level up! Now level {} (+{} skill points)", new_level, skill_points));
+                self.add_message(&format!("Level up! Now level {} (+{} skill points, [k] to spend)", new_level, skill_points));
             }
             // Add more event handlers as systems get wired up
             _ => {
@@ -673,16 +2872,71 @@ impl GameState {
     /// Update visual effects each frame (call in main loop)
     pub fn update_effects(&mut self) {
         self.effects.update();
+        let weather = if self.scene == Scene::Combat && !self.config.display.reduced_motion {
+            Weather::of_zone(FloorZone::from_floor(self.get_current_floor() as u32))
+        } else {
+            Weather::Clear
+        };
+        self.effects.particles.step(Instant::now(), weather, &mut rand::thread_rng());
+    }
+
+    /// Take whatever the fight and the conversation have said since last
+    /// frame into the transcript
+    pub fn update_transcript(&mut self) {
+        if let Some(combat) = &self.combat_state {
+            self.transcript.follow_combat(&combat.battle_log);
+        }
+        if let Some(runner) = &self.conversation {
+            self.transcript.follow_dialogue(&runner.transcript);
+        }
+    }
+
+    /// Tell the screen reader log what has changed, when it is on
+    pub fn update_screen_reader(&mut self) {
+        if self.config.display.screen_reader {
+            let facts = screen_reader::describe(self);
+            self.reader_log.update(facts, &self.transcript);
+        }
+    }
+
+    /// Turn the screen reader mode on or off
+    pub fn toggle_screen_reader(&mut self) {
+        self.config.display.screen_reader = !self.config.display.screen_reader;
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Turn reduced motion on or off: no particles, no screen shake
+    pub fn toggle_reduced_motion(&mut self) {
+        self.config.display.reduced_motion = !self.config.display.reduced_motion;
+        if self.config.display.reduced_motion {
+            self.effects.particles.clear();
+        }
+        if let Err(e) = save_config(&self.config) {
+            self.add_message(&format!("Could not save settings: {}", e));
+        }
+    }
+
+    /// Open the transcript pane, or close it
+    pub fn toggle_transcript(&mut self) {
+        self.transcript_view = match self.transcript_view {
+            Some(_) => None,
+            None => Some(TranscriptView::default()),
+        };
     }
     
     /// Trigger damage number and screen shake when player hits enemy
     pub fn effect_player_damage(&mut self, damage: i32, is_crit: bool) {
         self.effects.add_damage(damage, is_crit);
         
-        // Bigger shake for crits
+        // Bigger shake for crits, and sparks off the enemy
         if is_crit {
             self.effects.screen_shake = Some(crate::ui::effects::ScreenShake::medium());
             self.effects.hit_flash = Some(crate::ui::effects::HitFlash::critical());
+            if !self.config.display.reduced_motion {
+                self.effects.particles.sparks(0.5, 0.2, &mut rand::thread_rng());
+            }
         } else if damage > 20 {
             self.effects.screen_shake = Some(crate::ui::effects::ScreenShake::light());
         }
@@ -698,9 +2952,9 @@ impl GameState {
         self.effects.add_combo(combo);
     }
     
-    /// Trigger keystroke ripple effect
-    pub fn effect_keystroke(&mut self, correct: bool) {
-        self.effects.keystroke(correct);
+    /// Trigger a keystroke's ripple, damage number, flash and shake
+    pub fn effect_keystroke(&mut self, feedback: &KeystrokeFeedback) {
+        self.effects.keystroke_impact(feedback);
     }
     
     /// Victory effects
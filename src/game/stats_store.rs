@@ -0,0 +1,188 @@
+//! Stats Store - How every word has gone, run after run
+//!
+//! Each word that lands in a fight is kept for the run: how fast it was
+//! typed, how cleanly, the damage it did and the attack it made. When the
+//! run ends, its words are folded into a record of the run:
+//! - the dashboard (`d` on the title screen or the stats screen) charts
+//!   the run so far word by word, and every run kept run by run
+//! - speed, accuracy and damage per word are drawn as sparklines, and the
+//!   attacks made as bars
+//!
+//! Design: a fight keeps its own words on CombatState and hands them over
+//! as it ends, as it does its speed for the difficulty director. Only the
+//! records of finished runs are kept between sessions, in a file of their
+//! own beside the config, and only the last `RUNS_KEPT`.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::config::get_config_dir;
+use super::typing_impact::AttackType;
+
+/// Finished runs kept
+pub const RUNS_KEPT: usize = 100;
+
+/// Words of the current run kept
+pub const WORDS_KEPT: usize = 1000;
+
+/// The attacks, in the order they are counted and charted
+pub const ATTACKS: [AttackType; 5] = [
+    AttackType::Deliberate,
+    AttackType::Flurry,
+    AttackType::Precision,
+    AttackType::Frantic,
+    AttackType::Standard,
+];
+
+/// A word that landed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WordSample {
+    pub wpm: f32,
+    /// Share of the word's keys typed right (0.0 - 1.0)
+    pub accuracy: f32,
+    pub damage: i32,
+    pub attack: AttackType,
+}
+
+/// A run's words, added up
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Tally {
+    pub words: u32,
+    pub wpm: f32,
+    pub accuracy: f32,
+    pub damage_per_word: f32,
+    /// Words of each attack, in the order of `ATTACKS`
+    pub attacks: [u32; 5],
+}
+
+impl Tally {
+    pub fn of(words: &[WordSample]) -> Self {
+        let n = words.len().max(1) as f32;
+        let mut attacks = [0; 5];
+        for word in words {
+            if let Some(i) = ATTACKS.iter().position(|a| *a == word.attack) {
+                attacks[i] += 1;
+            }
+        }
+        Self {
+            words: words.len() as u32,
+            wpm: words.iter().map(|w| w.wpm).sum::<f32>() / n,
+            accuracy: words.iter().map(|w| w.accuracy).sum::<f32>() / n,
+            damage_per_word: words.iter().map(|w| w.damage).sum::<i32>() as f32 / n,
+            attacks,
+        }
+    }
+}
+
+/// A finished run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// When it ended (unix timestamp)
+    pub timestamp: u64,
+    pub victory: bool,
+    pub tally: Tally,
+}
+
+/// The words of the run under way, and the records of runs finished
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsStore {
+    pub runs: Vec<RunRecord>,
+    #[serde(skip)]
+    pub current: Vec<WordSample>,
+}
+
+impl StatsStore {
+    /// Keep the words of a fight just ended
+    pub fn record_words(&mut self, words: impl IntoIterator<Item = WordSample>) {
+        self.current.extend(words);
+        let over = self.current.len().saturating_sub(WORDS_KEPT);
+        self.current.drain(..over);
+    }
+
+    /// Fold the run's words into a record of it, if any landed
+    pub fn finish_run(&mut self, timestamp: u64, victory: bool) {
+        if self.current.is_empty() {
+            return;
+        }
+        self.runs.push(RunRecord { timestamp, victory, tally: Tally::of(&self.current) });
+        self.current.clear();
+        let over = self.runs.len().saturating_sub(RUNS_KEPT);
+        self.runs.drain(..over);
+    }
+
+    /// Words of each attack across every run kept and the one under way
+    pub fn all_attacks(&self) -> [u32; 5] {
+        let mut attacks = Tally::of(&self.current).attacks;
+        for run in &self.runs {
+            for (total, count) in attacks.iter_mut().zip(run.tally.attacks) {
+                *total += count;
+            }
+        }
+        attacks
+    }
+}
+
+fn store_path() -> PathBuf {
+    get_config_dir().join("stats.ron")
+}
+
+pub fn load() -> StatsStore {
+    fs::read_to_string(store_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(store: &StatsStore) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(store, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(store_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(wpm: f32, accuracy: f32, damage: i32, attack: AttackType) -> WordSample {
+        WordSample { wpm, accuracy, damage, attack }
+    }
+
+    #[test]
+    fn test_a_run_is_tallied_word_by_word() {
+        let tally = Tally::of(&[
+            word(40.0, 1.0, 12, AttackType::Precision),
+            word(80.0, 0.5, 6, AttackType::Frantic),
+            word(60.0, 0.9, 12, AttackType::Precision),
+        ]);
+        assert_eq!(tally.words, 3);
+        assert_eq!(tally.wpm, 60.0);
+        assert_eq!(tally.damage_per_word, 10.0);
+        assert_eq!(tally.attacks, [0, 0, 2, 1, 0]);
+        assert_eq!(Tally::of(&[]), Tally::default());
+    }
+
+    #[test]
+    fn test_finished_runs_are_kept_and_the_oldest_go_first() {
+        let mut store = StatsStore::default();
+        store.finish_run(1, false);
+        assert!(store.runs.is_empty());
+
+        store.record_words(vec![word(50.0, 1.0, 10, AttackType::Flurry); WORDS_KEPT + 5]);
+        assert_eq!(store.current.len(), WORDS_KEPT);
+        assert_eq!(store.all_attacks(), [0, WORDS_KEPT as u32, 0, 0, 0]);
+        for timestamp in 0..RUNS_KEPT as u64 + 1 {
+            store.record_words([word(50.0, 1.0, 10, AttackType::Standard)]);
+            store.finish_run(timestamp, true);
+        }
+        assert!(store.current.is_empty());
+        assert_eq!(store.runs.len(), RUNS_KEPT);
+        assert_eq!(store.runs[0].timestamp, 1);
+        // The run of flurries was the oldest, and has gone
+        assert_eq!(store.all_attacks(), [0, 0, 0, 0, RUNS_KEPT as u32]);
+
+        let text = ron::to_string(&store).unwrap();
+        assert_eq!(ron::from_str::<StatsStore>(&text).unwrap().runs, store.runs);
+    }
+}
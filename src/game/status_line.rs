@@ -0,0 +1,190 @@
+//! Status Line Export - Run state for tmux/zellij status bars
+//!
+//! Writes a compact one-line summary (floor, HP, WPM) to a file whenever it
+//! changes, mid-fight included, so terminal-multiplexer users can watch a run
+//! from another pane. Point your status bar at the file:
+//!
+//! ```text
+//! set -g status-right '#(cat ~/.cache/keyboard-warrior/status)'
+//! ```
+//!
+//! Only a file is written; there is no socket to connect to, so a status
+//! bar reads the file on its own refresh.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Environment variable that overrides the configured status file path
+pub const STATUS_FILE_ENV: &str = "KEYBOARD_WARRIOR_STATUS_FILE";
+
+/// Output flavor for the status string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StatusFormat {
+    /// Plain text (zellij, polybar, anything)
+    #[default]
+    Plain,
+    /// tmux `#[fg=...]` color directives
+    Tmux,
+}
+
+/// Status line configuration (lives in `DisplayConfig`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatusLineConfig {
+    /// Whether to write the status file at all
+    pub enabled: bool,
+    /// Where to write it (defaults to the cache dir)
+    pub path: Option<PathBuf>,
+    /// Output flavor
+    pub format: StatusFormat,
+}
+
+/// Snapshot of what the status line shows
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunStatus {
+    pub floor: i32,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub wpm: f32,
+    pub in_combat: bool,
+}
+
+impl RunStatus {
+    /// Render the status string
+    pub fn format(&self, format: StatusFormat) -> String {
+        let combat = if self.in_combat { " ⚔" } else { "" };
+        match format {
+            StatusFormat::Plain => format!(
+                "KW F{} HP {}/{} {:.0}wpm{}",
+                self.floor, self.hp, self.max_hp, self.wpm, combat
+            ),
+            StatusFormat::Tmux => {
+                let hp_color = match self.hp as f32 / self.max_hp.max(1) as f32 {
+                    r if r <= 0.25 => "red",
+                    r if r <= 0.5 => "yellow",
+                    _ => "green",
+                };
+                format!(
+                    "KW F{} #[fg={}]HP {}/{}#[default] {:.0}wpm{}",
+                    self.floor, hp_color, self.hp, self.max_hp, self.wpm, combat
+                )
+            }
+        }
+    }
+}
+
+/// Writes the status file, skipping redundant writes
+#[derive(Debug, Clone)]
+pub struct StatusLineExporter {
+    path: Option<PathBuf>,
+    format: StatusFormat,
+    last_written: Option<String>,
+}
+
+impl StatusLineExporter {
+    /// Build from config; the environment variable always wins and enables export
+    pub fn from_config(config: &StatusLineConfig) -> Self {
+        let path = match std::env::var_os(STATUS_FILE_ENV) {
+            Some(p) if !p.is_empty() => Some(PathBuf::from(p)),
+            _ if config.enabled => Some(config.path.clone().unwrap_or_else(default_status_path)),
+            _ => None,
+        };
+        Self {
+            path,
+            format: config.format,
+            last_written: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Write the status if it changed since the last export
+    pub fn export(&mut self, status: &RunStatus) {
+        let line = status.format(self.format);
+        if self.last_written.as_deref() == Some(line.as_str()) {
+            return;
+        }
+        if self.write(&line) {
+            self.last_written = Some(line);
+        }
+    }
+
+    /// Blank the status (run over / game closed), if it isn't already
+    pub fn clear(&mut self) {
+        if self.last_written.as_deref() == Some("") {
+            return;
+        }
+        if self.write("") {
+            self.last_written = Some(String::new());
+        }
+    }
+
+    /// Write via a temp file + rename so readers never see a half-written line
+    fn write(&self, line: &str) -> bool {
+        let Some(path) = &self.path else {
+            return false;
+        };
+        if let Some(dir) = path.parent() {
+            if fs::create_dir_all(dir).is_err() {
+                return false;
+            }
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, format!("{}\n", line)).is_ok() && fs::rename(&tmp, path).is_ok()
+    }
+}
+
+/// Default status file location
+pub fn default_status_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("keyboard-warrior")
+        .join("status")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(hp: i32) -> RunStatus {
+        RunStatus { floor: 3, hp, max_hp: 60, wpm: 61.6, in_combat: true }
+    }
+
+    #[test]
+    fn test_plain_and_tmux_lines_color_hp_by_how_much_is_left() {
+        assert_eq!(status(45).format(StatusFormat::Plain), "KW F3 HP 45/60 62wpm ⚔");
+        assert_eq!(status(45).format(StatusFormat::Tmux), "KW F3 #[fg=green]HP 45/60#[default] 62wpm ⚔");
+        // Half and a quarter are the edges of yellow and red
+        assert!(status(30).format(StatusFormat::Tmux).contains("#[fg=yellow]"));
+        assert!(status(31).format(StatusFormat::Tmux).contains("#[fg=green]"));
+        assert!(status(15).format(StatusFormat::Tmux).contains("#[fg=red]"));
+        assert!(status(16).format(StatusFormat::Tmux).contains("#[fg=yellow]"));
+        let resting = RunStatus { in_combat: false, ..status(45) };
+        assert_eq!(resting.format(StatusFormat::Plain), "KW F3 HP 45/60 62wpm");
+    }
+
+    #[test]
+    fn test_the_file_is_written_only_when_the_line_changes() {
+        let dir = std::env::temp_dir().join(format!("kw-status-{}", std::process::id()));
+        let path = dir.join("status");
+        let mut exporter = StatusLineExporter { path: Some(path.clone()), format: StatusFormat::Plain, last_written: None };
+        exporter.export(&status(45));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "KW F3 HP 45/60 62wpm ⚔\n");
+
+        // The same line again leaves the file alone
+        fs::remove_file(&path).unwrap();
+        exporter.export(&status(45));
+        assert!(!path.exists());
+        exporter.export(&status(40));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "KW F3 HP 40/60 62wpm ⚔\n");
+
+        exporter.clear();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\n");
+        fs::remove_file(&path).unwrap();
+        exporter.clear();
+        assert!(!path.exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
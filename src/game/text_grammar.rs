@@ -0,0 +1,214 @@
+//! Text Grammar - Tracery-style templates for generated text
+//!
+//! A grammar is a set of named rules, each a list of alternatives written
+//! as data. Expanding a rule picks one alternative and fills it in:
+//! - `#enemy#` fills a slot the moment provides (for combat: enemy, zone,
+//!   momentum, motif, damage)
+//! - `#hit_goblin#` expands another rule of the grammar
+//! - `#enemy.capitalize#`, `#motif.a#`, `#zone.lower#` modify what was filled in
+//!
+//! The combat grammar is bundled from `data/dialogue/combat.ron`. Files
+//! dropped into `<config>/dialogue/` add alternatives to its rules, or new
+//! rules, so writers can widen what enemies say without recompiling.
+//!
+//! Design: packs only ever add; a rule can't be taken away. Expansion stops
+//! at a fixed depth so a rule that names itself can't hang a fight, and
+//! `check` reports a `#name#` that is neither slot nor rule.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use rand::prelude::*;
+
+use super::config::get_config_dir;
+
+/// Slots a combat moment can fill; anything else between hashes names a rule
+pub const COMBAT_SLOTS: [&str; 5] = ["enemy", "zone", "momentum", "motif", "damage"];
+
+/// How deep rules may name rules before expansion gives up
+const MAX_DEPTH: usize = 8;
+
+const BUNDLED_COMBAT: &str = include_str!("../../data/dialogue/combat.ron");
+
+static COMBAT: OnceLock<Grammar> = OnceLock::new();
+
+/// Named rules, each a list of alternatives
+#[derive(Debug, Clone, Default)]
+pub struct Grammar {
+    rules: HashMap<String, Vec<String>>,
+}
+
+impl Grammar {
+    /// Parse a RON map of rule name to alternatives
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let rules: HashMap<String, Vec<String>> = ron::from_str(text).map_err(|e| e.to_string())?;
+        if let Some((name, _)) = rules.iter().find(|(_, alts)| alts.is_empty()) {
+            return Err(format!("rule '{}' has no alternatives", name));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Add another grammar's alternatives to this one's
+    pub fn extend(&mut self, other: Grammar) {
+        for (name, alts) in other.rules {
+            self.rules.entry(name).or_default().extend(alts);
+        }
+    }
+
+    pub fn has(&self, rule: &str) -> bool {
+        self.rules.contains_key(rule)
+    }
+
+    /// Expand the first of `rules` the grammar has
+    pub fn expand_first<R: Rng>(&self, rules: &[String], slots: &[(&str, String)], rng: &mut R) -> Option<String> {
+        let rule = rules.iter().find(|r| self.has(r))?;
+        let alt = self.rules[rule.as_str()].choose(rng)?;
+        Some(self.flatten(alt, slots, rng, 0))
+    }
+
+    fn flatten<R: Rng>(&self, text: &str, slots: &[(&str, String)], rng: &mut R, depth: usize) -> String {
+        let mut out = String::new();
+        let mut parts = text.split('#');
+        out.push_str(parts.next().unwrap_or_default());
+        // Odd pieces sit between hashes; an unclosed hash is kept as written
+        while let Some(tag) = parts.next() {
+            match parts.next() {
+                Some(rest) => {
+                    out.push_str(&self.fill(tag, slots, rng, depth));
+                    out.push_str(rest);
+                }
+                None => {
+                    out.push('#');
+                    out.push_str(tag);
+                }
+            }
+        }
+        out
+    }
+
+    fn fill<R: Rng>(&self, tag: &str, slots: &[(&str, String)], rng: &mut R, depth: usize) -> String {
+        let mut pieces = tag.split('.');
+        let name = pieces.next().unwrap_or_default();
+        let mut text = if let Some((_, value)) = slots.iter().find(|(slot, _)| *slot == name) {
+            value.clone()
+        } else if depth >= MAX_DEPTH {
+            String::new()
+        } else if let Some(alt) = self.rules.get(name).and_then(|alts| alts.choose(rng)) {
+            self.flatten(alt, slots, rng, depth + 1)
+        } else {
+            // Left visible so a writer can see what didn't resolve
+            format!("#{}#", tag)
+        };
+        for modifier in pieces {
+            text = apply_modifier(modifier, text);
+        }
+        text
+    }
+
+    /// Every `#name#` that is neither one of `slots` nor a rule, as "rule: name"
+    pub fn check(&self, slots: &[&str]) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (rule, alts) in &self.rules {
+            for alt in alts {
+                for tag in alt.split('#').skip(1).step_by(2) {
+                    let name = tag.split('.').next().unwrap_or_default();
+                    if !slots.contains(&name) && !self.has(name) {
+                        problems.push(format!("{}: #{}#", rule, tag));
+                    }
+                }
+            }
+        }
+        problems.sort();
+        problems
+    }
+}
+
+fn apply_modifier(modifier: &str, text: String) -> String {
+    match modifier {
+        "capitalize" => {
+            let mut chars = text.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => text,
+            }
+        }
+        "lower" => text.to_lowercase(),
+        "a" => {
+            let vowel = text.chars().next().is_some_and(|c| "aeiouAEIOU".contains(c));
+            format!("{} {}", if vowel { "an" } else { "a" }, text)
+        }
+        _ => text,
+    }
+}
+
+/// Where dialogue packs live
+pub fn packs_dir() -> PathBuf {
+    get_config_dir().join("dialogue")
+}
+
+/// The bundled combat grammar with every readable pack added to it
+fn load_combat() -> Grammar {
+    let mut grammar = Grammar::parse(BUNDLED_COMBAT)
+        .unwrap_or_else(|e| panic!("bundled combat dialogue is broken: {}", e));
+    let Ok(entries) = fs::read_dir(packs_dir()) else {
+        return grammar;
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "ron"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        // Like challenges, a broken pack is skipped rather than fatal
+        if let Some(pack) = fs::read_to_string(&path).ok().and_then(|text| Grammar::parse(&text).ok()) {
+            grammar.extend(pack);
+        }
+    }
+    grammar
+}
+
+/// The combat dialogue grammar, loaded once
+pub fn combat() -> &'static Grammar {
+    COMBAT.get_or_init(load_combat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansion_fills_slots_rules_and_modifiers() {
+        let grammar = Grammar::parse(r##"{
+            "greet": ["#who.capitalize# sees #thing.a#."],
+            "who": ["#enemy#"],
+            "thing": ["owl"],
+            "loop": ["#loop#!"],
+        }"##).unwrap();
+        let slots = [("enemy", "goblin".to_string())];
+        let mut rng = StdRng::seed_from_u64(1);
+        let rules = ["greet_goblin".to_string(), "greet".to_string()];
+        assert_eq!(grammar.expand_first(&rules, &slots, &mut rng).unwrap(), "Goblin sees an owl.");
+        assert!(grammar.expand_first(&["missing".to_string()], &slots, &mut rng).is_none());
+
+        // A rule naming itself stops at the depth limit
+        let looped = grammar.expand_first(&["loop".to_string()], &slots, &mut rng).unwrap();
+        assert_eq!(looped, "!".repeat(MAX_DEPTH + 1));
+
+        // Packs add alternatives, and a stray name is reported
+        let mut grammar = grammar;
+        grammar.extend(Grammar::parse(r##"{"thing": ["#nest#"]}"##).unwrap());
+        assert_eq!(grammar.check(&["enemy"]), vec!["thing: #nest#".to_string()]);
+    }
+
+    #[test]
+    fn test_bundled_combat_grammar_resolves() {
+        let grammar = Grammar::parse(BUNDLED_COMBAT).unwrap();
+        assert!(grammar.check(&COMBAT_SLOTS).is_empty(), "{:?}", grammar.check(&COMBAT_SLOTS));
+        for kind in ["intro", "hit", "attack", "riposte", "death", "gloat", "enrage"] {
+            assert!(grammar.has(kind), "no fallback for {}", kind);
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Training - Trainer Beck's yard, the floor before the first floor
+//!
+//! Five lessons, each a real fight against something that will not kill
+//! you, each over the moment you have done what it teaches:
+//! - keystrokes: every key that lands is part of the blow
+//! - attack types: clean, fast, slow and sloppy words strike differently
+//! - rhythm: keys at even gaps steady your hands and your damage
+//! - defense: a flawless word readies a riposte, the Guarded stance softens
+//!   what gets through
+//! - sparing: something beaten can be let go with `/spare`
+//!
+//! The yard opens the first time a new game is started, and from the
+//! typing tutorial on the title screen once it is done. Between fights
+//! Beck says what comes next; `s` skips a lesson, and `Esc` leaves the yard
+//! for the class screen, for anyone who has done this before.
+//!
+//! Design: a lesson only knows what it asks and how to read the fight for
+//! it, from a `LessonProgress` taken off CombatState every frame. Running
+//! the fights, and ending one when its lesson is learned, is GameState's.
+
+use super::combat::{CombatPhase, CombatState};
+use super::enemy::{Enemy, EnemyType};
+use super::keybinds::Action;
+use super::typing_impact::AttackType;
+use crate::data::damage_types::Resistances;
+
+/// Words landed to pass the first lesson
+const WORDS_TO_LAND: i32 = 3;
+
+/// Different attacks to make in the second
+const ATTACKS_TO_MAKE: usize = 2;
+
+/// Steady words to type in the third
+const STEADY_WORDS: u32 = 2;
+
+/// Blows to turn aside in the fourth
+const BLOWS_TO_TURN: u32 = 2;
+
+/// One of Beck's lessons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lesson {
+    Keystrokes,
+    AttackTypes,
+    Rhythm,
+    Defense,
+    Sparing,
+}
+
+impl Lesson {
+    pub const ALL: [Lesson; 5] = [Self::Keystrokes, Self::AttackTypes, Self::Rhythm, Self::Defense, Self::Sparing];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Keystrokes => "Every Key a Blow",
+            Self::AttackTypes => "Four Ways to Hit",
+            Self::Rhythm => "Keep Time",
+            Self::Defense => "Turn It Aside",
+            Self::Sparing => "Mercy",
+        }
+    }
+
+    /// What Beck says before the fight
+    pub fn briefing(&self) -> &'static [&'static str] {
+        match self {
+            Self::Keystrokes => &[
+                "Name's Beck. I train the ones who go down. Most of them come back up.",
+                "Down there you fight with your hands. Every key that lands is part of the blow; every slip takes a piece of it away.",
+                "The dummy won't hit back hard. Type what it shows you, and type it right.",
+            ],
+            Self::AttackTypes => &[
+                "How you type a word decides how it hits.",
+                "No slips at all is a Precision strike, the hardest there is. Fast and clean is a Flurry. Slow and clean is a Heavy Blow. Fast and messy is a Wild Swing, and it hits soft.",
+                "Show me two of them. Any two.",
+            ],
+            Self::Rhythm => &[
+                "Hands that hurry tire. Hands that keep time don't.",
+                "Keep the gaps between your keys even, the same beat start to finish. A steady word eases your fatigue, and your flow builds with it.",
+                "The Focused stance counts a steady rhythm double. Find a beat and hold it.",
+            ],
+            Self::Defense => &[
+                "Now it hits back. Not hard. Hard enough.",
+                "Type a word flawless and quick, and you ready a riposte: land the next word inside two seconds, and its blow goes back the way it came.",
+                "Can't manage that, switch to the Guarded stance and let the blows land soft. Turn two of them aside.",
+            ],
+            Self::Sparing => &[
+                "Last thing, and the thing most of them forget.",
+                "Not every fight ends in a body. Beat something under a quarter of its health, then type /spare and Enter, and let it go.",
+                "The dungeon remembers mercy. So do I. Don't kill this one.",
+            ],
+        }
+    }
+
+    /// What the lesson asks, as the fight shows it
+    pub fn goal(&self) -> &'static str {
+        match self {
+            Self::Keystrokes => "Land three words",
+            Self::AttackTypes => "Land two different kinds of attack",
+            Self::Rhythm => "Type two words with a steady rhythm",
+            Self::Defense => "Turn two blows aside: riposte them, or take them Guarded",
+            Self::Sparing => "Wear it under a quarter of its health, then /spare it",
+        }
+    }
+
+    /// How far the fight has come toward the goal
+    pub fn count(&self, progress: &LessonProgress) -> String {
+        match self {
+            Self::Keystrokes => format!("{}/{}", progress.words.min(WORDS_TO_LAND), WORDS_TO_LAND),
+            Self::AttackTypes => format!("{}/{}", progress.attacks.len().min(ATTACKS_TO_MAKE), ATTACKS_TO_MAKE),
+            Self::Rhythm => format!("{}/{}", progress.steady_words.min(STEADY_WORDS), STEADY_WORDS),
+            Self::Defense => format!("{}/{}", progress.blows_turned.min(BLOWS_TO_TURN), BLOWS_TO_TURN),
+            Self::Sparing => format!("{}% health", progress.enemy_hp_percent),
+        }
+    }
+
+    /// Whether the fight has done what the lesson asks
+    pub fn met(&self, progress: &LessonProgress) -> bool {
+        match self {
+            Self::Keystrokes => progress.words >= WORDS_TO_LAND,
+            Self::AttackTypes => progress.attacks.len() >= ATTACKS_TO_MAKE,
+            Self::Rhythm => progress.steady_words >= STEADY_WORDS,
+            Self::Defense => progress.blows_turned >= BLOWS_TO_TURN,
+            Self::Sparing => progress.spared,
+        }
+    }
+
+    /// What Beck says when the lesson is learned
+    pub fn praise(&self) -> &'static str {
+        match self {
+            Self::Keystrokes => "Good. Clean keys, clean hits. That's the whole trade, really.",
+            Self::AttackTypes => "There. Now you know what your hands are doing.",
+            Self::Rhythm => "Hear that? That's a beat. Keep it when it gets loud down there.",
+            Self::Defense => "Nothing that lands is the same as nothing that hurts. Remember it.",
+            Self::Sparing => "It'll remember you. Might even help you, one day.",
+        }
+    }
+
+    /// What Beck says when the fight ends without it
+    pub fn retry(&self) -> &'static str {
+        match self {
+            Self::Keystrokes => "Up you get. Slower this time, and right.",
+            Self::AttackTypes => "Same hit every time. Change something: your speed, your care.",
+            Self::Rhythm => "You're rushing the ends and dragging the middle. Again.",
+            Self::Defense => "You took those on the chin. Flawless and quick, or Guarded. Again.",
+            Self::Sparing => "I said don't kill it. Stop short, and /spare it. Again.",
+        }
+    }
+
+    /// The keys the lesson leans on, beyond typing
+    pub fn actions(&self) -> &'static [Action] {
+        match self {
+            Self::Keystrokes | Self::AttackTypes | Self::Sparing => &[],
+            Self::Rhythm | Self::Defense => &[Action::Stance],
+        }
+    }
+
+    /// What the lesson is fought against
+    pub fn dummy(&self) -> Enemy {
+        let (name, hp, attack) = match self {
+            // Too tough to fall before the lesson is learned
+            Self::Keystrokes | Self::AttackTypes | Self::Rhythm => ("Straw Dummy", 400, 0),
+            Self::Defense => ("Swinging Dummy", 400, 4),
+            Self::Sparing => ("Sparring Shade", 80, 2),
+        };
+        Enemy {
+            name: name.to_string(),
+            max_hp: hp,
+            current_hp: hp,
+            attack_power: attack,
+            defense: 0,
+            xp_reward: 0,
+            gold_reward: 0,
+            enemy_type: EnemyType::Normal,
+            ascii_art: "\n   ,-.\n  ( o )\n  -|X|-\n   |_|\n   / \\\n".to_string(),
+            battle_cry: "The dummy creaks on its post.".to_string(),
+            defeat_message: "The dummy sags on its post.".to_string(),
+            spare_condition: None,
+            is_boss: false,
+            typing_theme: "easy".to_string(),
+            attack_messages: vec!["swings its straw arm".to_string(), "lurches on its post".to_string()],
+            resistances: Resistances::default(),
+        }
+    }
+}
+
+/// What a lesson reads off the fight
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LessonProgress {
+    pub words: i32,
+    /// The different attacks made
+    pub attacks: Vec<AttackType>,
+    pub steady_words: u32,
+    pub blows_turned: u32,
+    pub enemy_hp_percent: i32,
+    pub spared: bool,
+}
+
+impl LessonProgress {
+    pub fn of(combat: &CombatState) -> Self {
+        let mut attacks: Vec<AttackType> = Vec::new();
+        for sample in &combat.word_samples {
+            if !attacks.contains(&sample.attack) {
+                attacks.push(sample.attack);
+            }
+        }
+        Self {
+            words: combat.words_correct,
+            attacks,
+            steady_words: combat.steady_words,
+            blows_turned: combat.blows_turned,
+            enemy_hp_percent: combat.enemy.current_hp.max(0) * 100 / combat.enemy.max_hp.max(1),
+            spared: combat.phase == CombatPhase::Spared,
+        }
+    }
+}
+
+/// A walk through the yard
+#[derive(Debug, Clone, Default)]
+pub struct Training {
+    /// Index of the lesson up next
+    pub lesson: usize,
+    /// How the last fight went: learned or not
+    pub last: Option<bool>,
+}
+
+impl Training {
+    /// The lesson up next, until the yard is done
+    pub fn current(&self) -> Option<Lesson> {
+        Lesson::ALL.get(self.lesson).copied()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// A fight is over; a lesson learned moves on to the next
+    pub fn finish(&mut self, learned: bool) {
+        if learned {
+            self.lesson += 1;
+        }
+        self.last = Some(learned);
+    }
+
+    pub fn skip(&mut self) {
+        self.lesson += 1;
+        self.last = None;
+    }
+
+    /// What Beck says about the last fight, if there was one
+    pub fn verdict(&self) -> Option<&'static str> {
+        let last = self.last?;
+        if last {
+            self.lesson.checked_sub(1).and_then(|i| Lesson::ALL.get(i)).map(|l| l.praise())
+        } else {
+            self.current().map(|l| l.retry())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_lesson_reads_its_own_part_of_the_fight() {
+        let mut progress = LessonProgress { words: 3, ..LessonProgress::default() };
+        assert!(Lesson::Keystrokes.met(&progress));
+        assert!(!Lesson::AttackTypes.met(&progress));
+
+        progress.attacks = vec![AttackType::Precision, AttackType::Flurry];
+        assert!(Lesson::AttackTypes.met(&progress));
+        assert_eq!(Lesson::AttackTypes.count(&progress), "2/2");
+
+        progress.steady_words = 1;
+        progress.blows_turned = 5;
+        assert!(!Lesson::Rhythm.met(&progress));
+        assert!(Lesson::Defense.met(&progress));
+        assert_eq!(Lesson::Defense.count(&progress), "2/2");
+
+        // Felled is not spared
+        progress.enemy_hp_percent = 0;
+        assert!(!Lesson::Sparing.met(&progress));
+        progress.spared = true;
+        assert!(Lesson::Sparing.met(&progress));
+
+        // Only the sparing dummy can be worn down in one lesson's time
+        assert!(Lesson::Sparing.dummy().max_hp < Lesson::Keystrokes.dummy().max_hp);
+    }
+
+    #[test]
+    fn test_the_yard_moves_on_only_when_a_lesson_is_learned_or_skipped() {
+        let mut training = Training::default();
+        assert_eq!(training.current(), Some(Lesson::Keystrokes));
+        assert_eq!(training.verdict(), None);
+
+        training.finish(false);
+        assert_eq!(training.current(), Some(Lesson::Keystrokes));
+        assert_eq!(training.verdict(), Some(Lesson::Keystrokes.retry()));
+
+        training.finish(true);
+        assert_eq!(training.current(), Some(Lesson::AttackTypes));
+        assert_eq!(training.verdict(), Some(Lesson::Keystrokes.praise()));
+
+        for _ in 0..3 {
+            training.skip();
+        }
+        assert_eq!(training.current(), Some(Lesson::Sparing));
+        training.finish(true);
+        assert!(training.is_done());
+        assert_eq!(training.verdict(), Some(Lesson::Sparing.praise()));
+    }
+}
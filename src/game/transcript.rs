@@ -0,0 +1,225 @@
+//! Transcript - Everything said this session, kept to be read again
+//!
+//! Lines go by fast in a game made of text: a message scrolls off, the
+//! combat log fills, an encounter's outcome is gone once the room is left.
+//! The transcript keeps them all for the session, each stamped with the
+//! time it was said:
+//! - every line has a source: a message, dialogue, an encounter or the
+//!   combat log
+//! - dialogue is kept with who said it, and encounter text with the
+//!   encounter it came from
+//! - the transcript pane (`F2` anywhere) scrolls back through it, filtered
+//!   by source or by speaker
+//!
+//! Design: a bounded buffer on GameState. Messages and encounter text are
+//! written to it where they are made. The combat log and conversations
+//! already keep logs of their own, so the transcript follows them, taking
+//! each line as it appears.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+/// Most lines kept; the oldest go first
+pub const CAPACITY: usize = 2000;
+
+/// Where a line of the transcript came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Message,
+    Dialogue,
+    Encounter,
+    Combat,
+}
+
+impl Source {
+    pub const ALL: [Source; 4] = [Source::Message, Source::Dialogue, Source::Encounter, Source::Combat];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Message => "Messages",
+            Self::Dialogue => "Dialogue",
+            Self::Encounter => "Encounters",
+            Self::Combat => "Combat",
+        }
+    }
+}
+
+/// A line of the transcript
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub at: DateTime<Local>,
+    pub source: Source,
+    /// Who said it, or the encounter it came from
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+impl Entry {
+    pub fn stamp(&self) -> String {
+        self.at.format("%H:%M:%S").to_string()
+    }
+}
+
+/// Which lines the transcript pane shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    All,
+    Source(Source),
+    Speaker(String),
+}
+
+impl Filter {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::All => "All",
+            Self::Source(source) => source.name(),
+            Self::Speaker(speaker) => speaker,
+        }
+    }
+
+    fn admits(&self, entry: &Entry) -> bool {
+        match self {
+            Self::All => true,
+            Self::Source(source) => entry.source == *source,
+            Self::Speaker(speaker) => entry.speaker.as_ref() == Some(speaker),
+        }
+    }
+}
+
+/// Everything said this session
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    entries: VecDeque<Entry>,
+    /// Lines of the running fight's log already taken
+    combat_taken: usize,
+    /// Lines of the running conversation already taken
+    dialogue_taken: usize,
+    /// Lines ever recorded, kept or not
+    recorded: usize,
+}
+
+impl Transcript {
+    pub fn record(&mut self, source: Source, speaker: Option<&str>, text: &str) {
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry {
+            at: Local::now(),
+            source,
+            speaker: speaker.map(str::to_string),
+            text: text.to_string(),
+        });
+        self.recorded += 1;
+    }
+
+    /// Lines ever recorded; a mark to ask for the lines `since`
+    pub fn recorded(&self) -> usize {
+        self.recorded
+    }
+
+    /// The lines recorded after `mark`, as many as are still kept
+    pub fn since(&self, mark: usize) -> impl Iterator<Item = &Entry> {
+        let new = self.recorded.saturating_sub(mark).min(self.entries.len());
+        self.entries.iter().skip(self.entries.len() - new)
+    }
+
+    /// A new fight or conversation: its log is followed from the start
+    pub fn begin(&mut self, source: Source) {
+        match source {
+            Source::Combat => self.combat_taken = 0,
+            Source::Dialogue => self.dialogue_taken = 0,
+            Source::Message | Source::Encounter => {}
+        }
+    }
+
+    /// Take the lines of the running fight's log not yet taken
+    pub fn follow_combat(&mut self, log: &[String]) {
+        for line in log.iter().skip(self.combat_taken) {
+            self.record(Source::Combat, None, line);
+        }
+        self.combat_taken = log.len();
+    }
+
+    /// Take the lines of the running conversation not yet taken
+    pub fn follow_dialogue(&mut self, log: &[(String, String)]) {
+        for (speaker, line) in log.iter().skip(self.dialogue_taken) {
+            self.record(Source::Dialogue, Some(speaker), line);
+        }
+        self.dialogue_taken = log.len();
+    }
+
+    /// Every filter: all lines, each source, then each speaker in the order
+    /// they were first heard
+    pub fn filters(&self) -> Vec<Filter> {
+        let mut filters = vec![Filter::All];
+        filters.extend(Source::ALL.map(Filter::Source));
+        for speaker in self.entries.iter().filter_map(|e| e.speaker.as_ref()) {
+            let filter = Filter::Speaker(speaker.clone());
+            if !filters.contains(&filter) {
+                filters.push(filter);
+            }
+        }
+        filters
+    }
+
+    /// The lines a filter admits, oldest first
+    pub fn lines<'a>(&'a self, filter: &'a Filter) -> impl Iterator<Item = &'a Entry> {
+        self.entries.iter().filter(move |e| filter.admits(e))
+    }
+}
+
+/// The transcript pane, while it is open
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TranscriptView {
+    /// Index into `Transcript::filters`
+    pub filter: usize,
+    /// Lines scrolled back from the newest
+    pub scroll: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_followed_logs_are_taken_once_and_from_the_start_when_new() {
+        let mut transcript = Transcript::default();
+        let mut log = vec!["Type to attack!".to_string()];
+        transcript.follow_combat(&log);
+        log.push("You strike for 12.".to_string());
+        transcript.follow_combat(&log);
+        transcript.follow_combat(&log);
+        assert_eq!(transcript.lines(&Filter::All).count(), 2);
+
+        transcript.begin(Source::Combat);
+        transcript.follow_combat(&["Type to attack!".to_string()]);
+        assert_eq!(transcript.lines(&Filter::Source(Source::Combat)).count(), 3);
+
+        let mark = transcript.recorded();
+        for i in 0..CAPACITY {
+            transcript.record(Source::Message, None, &i.to_string());
+        }
+        assert_eq!(transcript.lines(&Filter::All).count(), CAPACITY);
+        assert_eq!(transcript.since(mark).count(), CAPACITY);
+        assert_eq!(transcript.since(transcript.recorded() - 1).next().unwrap().text, (CAPACITY - 1).to_string());
+        assert_eq!(transcript.lines(&Filter::Source(Source::Combat)).count(), 0);
+    }
+
+    #[test]
+    fn test_speakers_can_be_filtered_in_the_order_heard() {
+        let mut transcript = Transcript::default();
+        transcript.follow_dialogue(&[
+            ("Vera".to_string(), "You again.".to_string()),
+            ("You".to_string(), "Me again.".to_string()),
+            ("Vera".to_string(), "Sit down.".to_string()),
+        ]);
+        transcript.record(Source::Message, None, "You rest.");
+        let filters = transcript.filters();
+        assert_eq!(filters.len(), 1 + Source::ALL.len() + 2);
+        assert_eq!(filters[5], Filter::Speaker("Vera".to_string()));
+        assert_eq!(transcript.lines(&filters[5]).map(|e| e.text.as_str()).collect::<Vec<_>>(), ["You again.", "Sit down."]);
+        assert_eq!(transcript.lines(&Filter::Source(Source::Dialogue)).count(), 3);
+        assert_eq!(transcript.lines(&Filter::All).last().unwrap().stamp().len(), 8);
+    }
+}
@@ -0,0 +1,88 @@
+//! True Names - Some bosses still answer to who they were
+//!
+//! The Hollow Knight was Sir Aldric; the Void Herald is everyone the ritual
+//! consumed. Their true names are hidden in lore fragments. Once one has
+//! been read, speaking the name mid-fight on the command line (`/Sir
+//! Aldric`) reaches what is left of them: the boss falters, taking heavy
+//! damage, and the way to spare it (`/spare`) opens.
+//!
+//! Design: the names live in the deep lore's boss records; GameState hands
+//! CombatState the name when a discovered fragment revealed it.
+
+use super::deep_lore::get_dungeon_lore;
+
+/// Share of the boss's max HP lost when its name is spoken
+pub const TRUE_NAME_DAMAGE: f32 = 0.35;
+/// What the player types on the command line to spare an enemy
+pub const SPARE_KEYWORD: &str = "spare";
+
+/// A boss's true name, known to the player
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrueName {
+    pub phrase: String,
+    /// The fragment the name was read in
+    pub source: String,
+    /// Whether it has been spoken this fight
+    pub spoken: bool,
+}
+
+impl TrueName {
+    /// Whether a command-line entry speaks this name (case and spacing aside)
+    pub fn matches(&self, input: &str) -> bool {
+        normalize(input) == normalize(&self.phrase)
+    }
+}
+
+/// The true name of a boss, if one of the discovered fragments revealed it
+pub fn known_for(enemy_name: &str, discovered: &[(String, String)]) -> Option<TrueName> {
+    let boss = get_dungeon_lore().bosses.into_iter().find(|b| {
+        b.name == enemy_name || b.name.trim_start_matches("The ") == enemy_name
+    })?;
+    let read = discovered.iter().any(|(title, _)| *title == boss.revealed_by);
+    read.then_some(TrueName {
+        phrase: boss.true_name,
+        source: boss.revealed_by,
+        spoken: false,
+    })
+}
+
+/// Whether a command-line entry asks to spare the enemy
+pub fn is_spare_command(input: &str) -> bool {
+    normalize(input) == SPARE_KEYWORD
+}
+
+fn normalize(input: &str) -> String {
+    input
+        .trim()
+        .trim_start_matches('/')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(title: &str) -> Vec<(String, String)> {
+        vec![(title.to_string(), String::new())]
+    }
+
+    #[test]
+    fn test_name_needs_its_fragment() {
+        assert_eq!(known_for("The Hollow Knight", &found("Faded Tapestry")), None);
+        let name = known_for("The Hollow Knight", &found("Knight's Final Letter")).unwrap();
+        assert_eq!(name.phrase, "Sir Aldric");
+        assert!(known_for("Hollow Knight", &found("Knight's Final Letter")).is_some());
+        assert!(known_for("Goblin", &found("Knight's Final Letter")).is_none());
+    }
+
+    #[test]
+    fn test_speaking_ignores_case_and_spacing() {
+        let name = known_for("The Void Herald", &found("Names in the Dark")).unwrap();
+        assert!(name.matches("/we  were THE price "));
+        assert!(!name.matches("we were"));
+        assert!(is_spare_command(" /Spare"));
+    }
+}
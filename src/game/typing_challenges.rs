@@ -0,0 +1,154 @@
+//! Typing Challenges - Prompts that must be solved before they are typed
+//!
+//! Some enemies don't show the word you have to type, only a jumbled form
+//! of it:
+//! - Spectral enemies (wisps, wraiths, phantoms) scramble a word's inner
+//!   letters, keeping the first and last in place
+//! - Void enemies present full anagrams
+//!
+//! The player reads the jumble, works out the real word and types that.
+//! Sentences are only ever scrambled word by word, never anagrammed.
+//!
+//! Design: CombatState rolls a puzzle whenever it picks a prompt and keeps
+//! the answer alongside the jumble; the prompt renderer reads the kind to
+//! decide how to draw it.
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::enemy::Enemy;
+use super::typing_impact::graphemes;
+
+/// Chance a prompt from a puzzling enemy is jumbled
+const PUZZLE_CHANCE: f32 = 0.5;
+/// Extra seconds to work out a jumbled prompt
+pub const PUZZLE_TIME_BONUS: f32 = 2.0;
+/// Shuffles tried before giving up on a jumble that differs from the word
+const SHUFFLE_TRIES: usize = 8;
+/// Names that mark an enemy as spectral
+const SPECTRAL_NAMES: [&str; 8] = ["Spectral", "Wisp", "Wraith", "Phantom", "Specter", "Shade", "Echo", "Ghost"];
+
+/// How an enemy's prompts are presented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypingChallengeKind {
+    /// Shown as written
+    #[default]
+    Plain,
+    /// Inner letters of each word shuffled
+    Scramble,
+    /// Every letter of the word shuffled
+    Anagram,
+}
+
+impl TypingChallengeKind {
+    /// The kind of puzzle an enemy sets
+    pub fn for_enemy(enemy: &Enemy) -> Self {
+        if matches!(enemy.typing_theme.as_str(), "void" | "chaos") || enemy.name.contains("Void") {
+            Self::Anagram
+        } else if SPECTRAL_NAMES.iter().any(|name| enemy.name.contains(name)) {
+            Self::Scramble
+        } else {
+            Self::Plain
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Plain => "Type!",
+            Self::Scramble => "🔀 UNSCRAMBLE",
+            Self::Anagram => "🔀 ANAGRAM",
+        }
+    }
+
+    /// Maybe jumble a prompt; None if it is shown as written
+    pub fn roll(&self, answer: &str, rng: &mut impl Rng) -> Option<Puzzle> {
+        if *self == Self::Plain || rng.gen::<f32>() >= PUZZLE_CHANCE {
+            return None;
+        }
+        // Whole-prompt anagrams are for single words only
+        let kind = if answer.split_whitespace().count() > 1 { Self::Scramble } else { *self };
+        let shown = jumble(kind, answer, rng);
+        (shown != answer).then(|| Puzzle { kind, answer: answer.to_string(), shown })
+    }
+}
+
+/// A jumbled prompt and the word it hides
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub kind: TypingChallengeKind,
+    pub answer: String,
+    pub shown: String,
+}
+
+/// Shuffle each word of the prompt, leaving spaces and punctuation alone
+fn jumble(kind: TypingChallengeKind, prompt: &str, rng: &mut impl Rng) -> String {
+    prompt
+        .split(' ')
+        .map(|word| jumble_word(kind, word, rng))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn jumble_word(kind: TypingChallengeKind, word: &str, rng: &mut impl Rng) -> String {
+    let chars = graphemes(word);
+    // Only letters move; trailing punctuation stays where it is
+    let letters = chars.iter().take_while(|g| g.chars().all(char::is_alphabetic)).count();
+    let range = match kind {
+        TypingChallengeKind::Plain => return word.to_string(),
+        TypingChallengeKind::Scramble if letters >= 4 => 1..letters - 1,
+        TypingChallengeKind::Anagram if letters >= 2 => 0..letters,
+        _ => return word.to_string(),
+    };
+    let mut shuffled = chars.clone();
+    for _ in 0..SHUFFLE_TRIES {
+        shuffled[range.clone()].shuffle(rng);
+        if shuffled != chars {
+            break;
+        }
+    }
+    shuffled.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn sorted(word: &str) -> Vec<char> {
+        let mut chars: Vec<char> = word.chars().collect();
+        chars.sort_unstable();
+        chars
+    }
+
+    #[test]
+    fn test_scramble_keeps_the_ends() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            let shown = jumble(TypingChallengeKind::Scramble, "lantern whispers.", &mut rng);
+            assert!(shown.starts_with('l'));
+            assert!(shown.contains(" w"));
+            assert!(shown.ends_with("s."));
+            assert_eq!(sorted(&shown), sorted("lantern whispers."));
+        }
+    }
+
+    #[test]
+    fn test_anagram_uses_the_same_letters() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            let puzzle = TypingChallengeKind::Anagram.roll("quill", &mut rng);
+            if let Some(puzzle) = puzzle {
+                assert_ne!(puzzle.shown, "quill");
+                assert_eq!(sorted(&puzzle.shown), sorted("quill"));
+                assert_eq!(puzzle.answer, "quill");
+            }
+        }
+        // Sentences are only ever scrambled
+        let puzzle = (0..200)
+            .find_map(|_| TypingChallengeKind::Anagram.roll("the lantern burns", &mut rng))
+            .expect("a sentence rolls a puzzle now and then");
+        assert_eq!(puzzle.kind, TypingChallengeKind::Scramble);
+        assert_eq!(TypingChallengeKind::Plain.roll("quill", &mut rng), None);
+    }
+}
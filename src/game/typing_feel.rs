@@ -10,9 +10,30 @@
 //! - Perfect words feel AMAZING
 //! - Speed should feel powerful
 
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
+use super::keyboard_layout::{KeyboardLayout, KeyErrorKind};
+use super::typing_impact::{graphemes, graphemes_match, text_matches};
+
+/// Flow meter ceiling
+pub const FLOW_MAX: f32 = 100.0;
+/// Meter level where the rhythm becomes Flowing
+pub const FLOW_FLOWING_AT: f32 = 40.0;
+/// Meter level where the rhythm becomes Transcendent
+pub const FLOW_TRANSCENDENT_AT: f32 = 85.0;
+/// Meter gained per correct keystroke at a steady cadence
+const FLOW_GAIN_PER_KEY: f32 = 2.0;
+/// Extra meter for finishing a word without mistakes
+const FLOW_PERFECT_WORD_BONUS: f32 = 5.0;
+/// Meter lost on a wrong keystroke
+const FLOW_ERROR_LOSS: f32 = 30.0;
+/// Clean keystrokes needed to shake off Recovering
+const FLOW_RECOVERY_KEYSTROKES: u32 = 10;
+/// Pauses longer than this (seconds) don't count as sustained typing
+const FLOW_SUSTAINED_CADENCE: f32 = 1.0;
+
 /// Typing feedback state - tracks moment-to-moment feel
 #[derive(Debug, Clone)]
 pub struct TypingFeel {
@@ -28,6 +49,10 @@ pub struct TypingFeel {
     pub keystroke_cadence: f32,
     /// Current flow state
     pub flow_state: FlowState,
+    /// Flow meter (0 - FLOW_MAX), fed by sustained accurate typing
+    pub flow_meter: f32,
+    /// Clean keystrokes still needed before Recovering ends
+    pub flow_recovery: u32,
     /// Pending visual effects
     pub pending_effects: Vec<TypingEffect>,
     /// Current accuracy (rolling average)
@@ -44,6 +69,8 @@ pub struct TypingFeel {
     pub screen_shake: f32,
     /// Color flash state
     pub color_flash: Option<ColorFlash>,
+    /// Player's keyboard layout (for error classification)
+    pub layout: KeyboardLayout,
 }
 
 /// Flow state - how "in the zone" the player is
@@ -59,6 +86,23 @@ pub enum FlowState {
     Recovering,
 }
 
+impl FlowState {
+    /// Damage multiplier granted (or taken away) by this state
+    pub fn damage_multiplier(&self) -> f32 {
+        match self {
+            Self::Building => 1.0,
+            Self::Flowing => 1.1,
+            Self::Transcendent => 1.25,
+            Self::Recovering => 0.85,
+        }
+    }
+
+    /// Whether victories in this state reveal a lore fragment
+    pub fn grants_lore(&self) -> bool {
+        matches!(self, Self::Transcendent)
+    }
+}
+
 /// Visual effects that should trigger
 #[derive(Debug, Clone)]
 pub enum TypingEffect {
@@ -121,6 +165,8 @@ impl TypingFeel {
             last_keystroke: None,
             keystroke_cadence: 0.0,
             flow_state: FlowState::Building,
+            flow_meter: 0.0,
+            flow_recovery: 0,
             pending_effects: Vec::new(),
             accuracy: 1.0,
             wpm: 0.0,
@@ -129,6 +175,7 @@ impl TypingFeel {
             word_total: 0,
             screen_shake: 0.0,
             color_flash: None,
+            layout: KeyboardLayout::default(),
         }
     }
 
@@ -143,17 +190,21 @@ impl TypingFeel {
         let now = Instant::now();
         
         // Update cadence
+        let mut sustained = true;
         if let Some(last) = self.last_keystroke {
             let elapsed = now.duration_since(last).as_secs_f32();
             // Smooth the cadence
             self.keystroke_cadence = self.keystroke_cadence * 0.7 + elapsed * 0.3;
+            sustained = elapsed <= FLOW_SUSTAINED_CADENCE;
         }
         self.last_keystroke = Some(now);
         
         if correct {
             self.on_correct_keystroke(char_index);
+            self.feed_flow(sustained);
         } else {
             self.on_incorrect_keystroke(char_index, expected, got);
+            self.break_flow();
         }
         
         self.update_flow_state();
@@ -182,18 +233,53 @@ impl TypingFeel {
         }
     }
 
+    /// A clean keystroke: hesitant or recovering typing builds flow at half rate
+    fn feed_flow(&mut self, sustained: bool) {
+        let mut gain = FLOW_GAIN_PER_KEY;
+        if !sustained {
+            gain *= 0.5;
+        }
+        if self.flow_recovery > 0 {
+            self.flow_recovery -= 1;
+            gain *= 0.5;
+        }
+        self.flow_meter = (self.flow_meter + gain).min(FLOW_MAX);
+    }
+
+    /// A wrong keystroke drains the meter and starts a recovery window
+    fn break_flow(&mut self) {
+        self.flow_meter = (self.flow_meter - FLOW_ERROR_LOSS).max(0.0);
+        self.flow_recovery = FLOW_RECOVERY_KEYSTROKES;
+    }
+
+    /// Drop all flow (new fight)
+    pub fn reset_flow(&mut self) {
+        self.flow_meter = 0.0;
+        self.flow_recovery = 0;
+        self.update_flow_state();
+    }
+
+    /// Flow meter as a 0-100 percentage
+    pub fn flow_percent(&self) -> u16 {
+        (self.flow_meter / FLOW_MAX * 100.0).round() as u16
+    }
+
     fn on_incorrect_keystroke(&mut self, char_index: usize, expected: char, got: char) {
         // Visual feedback
         self.pending_effects.push(TypingEffect::CharIncorrect { 
             char_index, 
             expected, 
-            got 
+            got,
         });
         
-        // Error flash
+        // Error flash, softer for a slip onto a neighbouring key or the wrong case
+        let intensity = match self.layout.classify_error(expected, got) {
+            KeyErrorKind::CaseSlip | KeyErrorKind::Adjacent => 0.3,
+            _ => 0.5,
+        };
         self.color_flash = Some(ColorFlash {
             color: FlashColor::Red,
-            intensity: 0.5,
+            intensity,
             duration_ms: 100,
             started: Instant::now(),
         });
@@ -211,13 +297,14 @@ impl TypingFeel {
 
     /// Called when a word is completed
     pub fn on_word_complete(&mut self, word: &str, typed: &str, time_taken: f32) {
-        let word_len = word.len();
-        let correct_chars = word.chars()
-            .zip(typed.chars())
-            .filter(|(a, b)| a == b)
+        let word_graphemes = graphemes(word);
+        let word_len = word_graphemes.len();
+        let correct_chars = word_graphemes.iter()
+            .zip(graphemes(typed))
+            .filter(|(a, b)| graphemes_match(a, b))
             .count();
         
-        let accuracy = correct_chars as f32 / word_len as f32;
+        let accuracy = correct_chars as f32 / word_len.max(1) as f32;
         let wpm = if time_taken > 0.0 {
             (word_len as f32 / 5.0) / (time_taken / 60.0)
         } else {
@@ -228,13 +315,17 @@ impl TypingFeel {
         self.accuracy = self.accuracy * 0.8 + accuracy * 0.2;
         self.wpm = self.wpm * 0.8 + wpm * 0.2;
         
-        let is_perfect = word == typed;
+        let is_perfect = text_matches(word, typed);
         
         if is_perfect {
             self.on_perfect_word(word, wpm);
+            if self.flow_recovery == 0 {
+                self.flow_meter = (self.flow_meter + FLOW_PERFECT_WORD_BONUS).min(FLOW_MAX);
+            }
         } else {
             self.on_imperfect_word(word, typed);
         }
+        self.update_flow_state();
         
         self.pending_effects.push(TypingEffect::WordComplete {
             word: word.to_string(),
@@ -302,12 +393,12 @@ impl TypingFeel {
     fn update_flow_state(&mut self) {
         let old_state = self.flow_state;
         
-        self.flow_state = if self.combo >= 20 && self.accuracy >= 0.95 && self.wpm >= 80.0 {
+        self.flow_state = if self.flow_recovery > 0 {
+            FlowState::Recovering
+        } else if self.flow_meter >= FLOW_TRANSCENDENT_AT {
             FlowState::Transcendent
-        } else if self.combo >= 5 && self.accuracy >= 0.85 {
+        } else if self.flow_meter >= FLOW_FLOWING_AT {
             FlowState::Flowing
-        } else if self.combo == 0 && self.accuracy < 0.7 {
-            FlowState::Recovering
         } else {
             FlowState::Building
         };
@@ -548,3 +639,47 @@ pub fn generate_satisfying_prompt(difficulty: u32, prefer_rhythmic: bool) -> Str
         words.join(" ")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sustained_typing_reaches_transcendence() {
+        let mut feel = TypingFeel::new();
+        for i in 0..60 {
+            feel.on_keystroke(true, i, 'a', 'a');
+        }
+        assert_eq!(feel.flow_state, FlowState::Transcendent);
+        assert!(feel.flow_state.grants_lore());
+        assert!(feel.flow_state.damage_multiplier() > 1.0);
+    }
+
+    #[test]
+    fn test_a_slip_to_a_neighbouring_key_flashes_softer() {
+        let flash = |got| {
+            let mut feel = TypingFeel::new();
+            feel.on_keystroke(false, 0, 'a', got);
+            feel.color_flash.map(|f| f.intensity)
+        };
+        assert!(flash('s') < flash('p'));
+    }
+
+    #[test]
+    fn test_error_starts_recovery() {
+        let mut feel = TypingFeel::new();
+        for i in 0..30 {
+            feel.on_keystroke(true, i, 'a', 'a');
+        }
+        let before = feel.flow_meter;
+        feel.on_keystroke(false, 30, 'a', 's');
+        assert_eq!(feel.flow_state, FlowState::Recovering);
+        assert!(feel.flow_meter < before);
+        assert!(feel.flow_state.damage_multiplier() < 1.0);
+
+        for i in 0..FLOW_RECOVERY_KEYSTROKES as usize {
+            feel.on_keystroke(true, i, 'a', 'a');
+        }
+        assert_ne!(feel.flow_state, FlowState::Recovering);
+    }
+}
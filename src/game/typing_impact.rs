@@ -7,6 +7,145 @@
 
 use std::time::Instant;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::data::items::ImpactTuning;
+use super::stances::scale_bonus;
+use super::latency;
+use super::player_avatar::PlayerClass;
+
+/// Tunable numbers behind each keystroke's impact (gear adjusts these)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpactParams {
+    /// Drift (ms) from the recent average that still earns the full rhythm bonus
+    pub rhythm_window_ms: u32,
+    /// Ceiling on the speed damage multiplier
+    pub speed_cap: f32,
+    /// Wrong keystrokes per word forgiven when judging accuracy
+    pub typo_forgiveness: u32,
+    /// Pending damage built by each correct keystroke
+    pub base_damage: f32,
+    /// Scale on the speed bonus above 1x (stances adjust this)
+    pub speed_bonus_mult: f32,
+    /// Scale on the rhythm bonus above 1x (stances adjust this)
+    pub rhythm_bonus_mult: f32,
+    /// Calibrated terminal latency (ms) taken off the wait for the first key
+    pub latency_ms: u32,
+    /// Speed and accuracy cutoffs for each attack type
+    pub thresholds: AttackThresholds,
+}
+
+impl Default for ImpactParams {
+    fn default() -> Self {
+        Self {
+            rhythm_window_ms: 30,
+            speed_cap: 2.0,
+            typo_forgiveness: 0,
+            base_damage: 1.5,
+            speed_bonus_mult: 1.0,
+            rhythm_bonus_mult: 1.0,
+            latency_ms: 0,
+            thresholds: AttackThresholds::default(),
+        }
+    }
+}
+
+impl ImpactParams {
+    /// Base parameters with worn gear applied
+    pub fn with_tuning(tuning: ImpactTuning) -> Self {
+        let base = Self::default();
+        Self {
+            rhythm_window_ms: base.rhythm_window_ms + tuning.rhythm_window_ms,
+            speed_cap: base.speed_cap + tuning.speed_cap,
+            typo_forgiveness: base.typo_forgiveness + tuning.typo_forgiveness,
+            base_damage: base.base_damage + tuning.base_damage,
+            ..base
+        }
+    }
+}
+
+/// Speed and accuracy a word needs to count as each attack type.
+/// Players can set their own in the config; otherwise their class decides.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AttackThresholds {
+    /// Precision: at least this accuracy, at least this WPM
+    pub precision_accuracy: f32,
+    pub precision_wpm: f32,
+    /// Flurry: at least this accuracy, at least this WPM
+    pub flurry_accuracy: f32,
+    pub flurry_wpm: f32,
+    /// Deliberate: under this WPM, at least this accuracy
+    pub deliberate_max_wpm: f32,
+    pub deliberate_accuracy: f32,
+    /// Frantic: at least this WPM, under this accuracy
+    pub frantic_wpm: f32,
+    pub frantic_max_accuracy: f32,
+}
+
+impl Default for AttackThresholds {
+    fn default() -> Self {
+        Self {
+            precision_accuracy: 0.99,
+            precision_wpm: 80.0,
+            flurry_accuracy: 0.95,
+            flurry_wpm: 100.0,
+            deliberate_max_wpm: 40.0,
+            deliberate_accuracy: 0.95,
+            frantic_wpm: 70.0,
+            frantic_max_accuracy: 0.85,
+        }
+    }
+}
+
+impl AttackThresholds {
+    /// Each class's preset: careful writers reach Precision sooner,
+    /// Codebreakers' fast hands are held to a higher Precision bar
+    pub fn for_class(class: PlayerClass) -> Self {
+        let base = Self::default();
+        match class {
+            PlayerClass::Freelancer => base,
+            PlayerClass::Wordsmith => Self { precision_wpm: 65.0, flurry_wpm: 90.0, ..base },
+            PlayerClass::Codebreaker => Self { precision_wpm: 85.0, flurry_accuracy: 0.93, flurry_wpm: 90.0, ..base },
+            PlayerClass::Chronicler => Self {
+                precision_accuracy: 0.98,
+                precision_wpm: 55.0,
+                flurry_wpm: 85.0,
+                deliberate_max_wpm: 45.0,
+                ..base
+            },
+        }
+    }
+
+    /// The kind of attack a word typed at this speed and accuracy makes
+    pub fn classify(&self, wpm: f32, accuracy: f32) -> AttackType {
+        if accuracy >= self.precision_accuracy && wpm >= self.precision_wpm {
+            AttackType::Precision
+        } else if accuracy >= self.flurry_accuracy && wpm >= self.flurry_wpm {
+            AttackType::Flurry
+        } else if wpm < self.deliberate_max_wpm && accuracy >= self.deliberate_accuracy {
+            AttackType::Deliberate
+        } else if wpm >= self.frantic_wpm && accuracy < self.frantic_max_accuracy {
+            AttackType::Frantic
+        } else {
+            AttackType::Standard
+        }
+    }
+
+    /// One line per attack type, for the stats screen
+    pub fn describe(&self) -> Vec<String> {
+        vec![
+            format!("{} {}: {:.0}+ WPM, {:.0}%+ acc", AttackType::Precision.icon(), AttackType::Precision.name(),
+                self.precision_wpm, self.precision_accuracy * 100.0),
+            format!("{} {}: {:.0}+ WPM, {:.0}%+ acc", AttackType::Flurry.icon(), AttackType::Flurry.name(),
+                self.flurry_wpm, self.flurry_accuracy * 100.0),
+            format!("{} {}: under {:.0} WPM, {:.0}%+ acc", AttackType::Deliberate.icon(), AttackType::Deliberate.name(),
+                self.deliberate_max_wpm, self.deliberate_accuracy * 100.0),
+            format!("{} {}: {:.0}+ WPM, under {:.0}% acc", AttackType::Frantic.icon(), AttackType::Frantic.name(),
+                self.frantic_wpm, self.frantic_max_accuracy * 100.0),
+        ]
+    }
+}
 
 /// Tracks typing and translates it to combat impact frame-by-frame
 #[derive(Debug, Clone)]
@@ -23,6 +162,8 @@ pub struct TypingImpact {
     pub attack_type: AttackType,
     /// Whether last keystroke was correct
     pub last_correct: bool,
+    /// Rhythm, speed, forgiveness and damage parameters
+    pub params: ImpactParams,
 }
 
 /// Sequence of keystrokes forming an attack
@@ -161,6 +302,7 @@ impl TypingImpact {
             impact_intensity: 0.0,
             attack_type: AttackType::Standard,
             last_correct: true,
+            params: ImpactParams::default(),
         }
     }
     
@@ -179,10 +321,18 @@ impl TypingImpact {
     
     /// Process a keystroke during combat
     pub fn on_keystroke(&mut self, ch: char, correct: bool) -> KeystrokeResult {
-        let now = Instant::now();
+        self.on_keystroke_at(ch, correct, Instant::now())
+    }
+    
+    /// Process a keystroke stamped when it was read from the terminal
+    pub fn on_keystroke_at(&mut self, ch: char, correct: bool, now: Instant) -> KeystrokeResult {
         let interval = self.current_attack.keystrokes.last()
-            .map(|k| now.duration_since(k.timestamp).as_millis() as u32)
+            .map(|k| now.saturating_duration_since(k.timestamp).as_millis() as u32)
             .unwrap_or(0);
+        if self.current_attack.keystrokes.is_empty() {
+            let started_at = self.current_attack.started_at;
+            self.current_attack.started_at = latency::compensate(started_at, now, self.params.latency_ms);
+        }
         
         self.current_attack.keystrokes.push(Keystroke {
             char: ch,
@@ -215,17 +365,18 @@ impl TypingImpact {
         }
         
         // Base damage per correct keystroke
-        let base = 1.5;
+        let base = self.params.base_damage;
         
-        // Speed bonus: faster = more damage (up to 2x at 50ms intervals)
+        // Speed bonus: faster = more damage (up to 2x at 100ms intervals, more with gear)
         let speed_mult = if interval_ms > 0 {
-            (200.0 / interval_ms as f32).min(2.0).max(0.5)
+            (200.0 / interval_ms as f32).min(self.params.speed_cap).max(0.5)
         } else {
             1.0
         };
+        let speed_mult = scale_bonus(speed_mult, self.params.speed_bonus_mult);
         
         // Rhythm bonus: consistent intervals feel better and do more
-        let rhythm_mult = self.calculate_rhythm_bonus(interval_ms);
+        let rhythm_mult = scale_bonus(self.calculate_rhythm_bonus(interval_ms), self.params.rhythm_bonus_mult);
         
         let damage = base * speed_mult * rhythm_mult;
         
@@ -257,11 +408,12 @@ impl TypingImpact {
         let variance = (current_interval as i32 - avg as i32).abs() as u32;
         
         // Low variance (consistent rhythm) = up to 50% bonus
-        if variance < 30 {
+        let window = self.params.rhythm_window_ms;
+        if variance < window {
             1.5
-        } else if variance < 60 {
+        } else if variance < window * 2 {
             1.25
-        } else if variance < 100 {
+        } else if variance < window * 10 / 3 {
             1.1
         } else {
             1.0
@@ -271,8 +423,11 @@ impl TypingImpact {
     /// Complete the current word and calculate final damage
     pub fn complete_word(&mut self, base_damage: i32) -> WordCompletionResult {
         let elapsed = self.current_attack.started_at.elapsed();
-        let char_count = self.current_attack.typed.len();
+        let char_count = self.current_attack.keystrokes.len();
         let correct_count = self.current_attack.keystrokes.iter().filter(|k| k.correct).count();
+        // Forgiven typos count as if they were right
+        let forgiven = (char_count - correct_count).min(self.params.typo_forgiveness as usize);
+        let correct_count = correct_count + forgiven;
         
         let accuracy = if char_count > 0 {
             correct_count as f32 / char_count as f32
@@ -308,13 +463,7 @@ impl TypingImpact {
     }
     
     fn determine_attack_type(&self, wpm: f32, accuracy: f32) -> AttackType {
-        match (wpm, accuracy) {
-            (w, a) if a >= 0.99 && w >= 80.0 => AttackType::Precision,
-            (w, a) if a >= 0.95 && w >= 100.0 => AttackType::Flurry,
-            (w, a) if w < 40.0 && a >= 0.95 => AttackType::Deliberate,
-            (w, a) if w >= 70.0 && a < 0.85 => AttackType::Frantic,
-            _ => AttackType::Standard,
-        }
+        self.params.thresholds.classify(wpm, accuracy)
     }
     
     fn generate_attack_message(&self, damage: i32, perfect: bool) -> String {
@@ -348,11 +497,86 @@ impl TypingImpact {
     pub fn reset(&mut self) {
         *self = Self::new();
     }
+    
+    /// Whether typing `ch` next would be correct for the current word.
+    /// Compares grapheme clusters, so combining marks and multi-codepoint
+    /// characters are judged as the player sees them.
+    pub fn check_char(&self, ch: char) -> bool {
+        let mut typed = self.current_attack.typed.clone();
+        typed.push(ch);
+        last_grapheme_correct(&self.current_attack.word, &typed)
+    }
+}
+
+// ============================================================================
+// Unicode-aware comparison (diacritics, ß, CJK)
+// ============================================================================
+
+/// Split text into user-perceived characters (extended grapheme clusters)
+pub fn graphemes(text: &str) -> Vec<&str> {
+    text.graphemes(true).collect()
+}
+
+/// Number of user-perceived characters in `text`
+pub fn grapheme_len(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Drop the last user-perceived character (a base letter and its marks together)
+pub fn pop_grapheme(text: &mut String) {
+    if let Some((idx, _)) = text.grapheme_indices(true).next_back() {
+        text.truncate(idx);
+    }
+}
+
+/// Compare two graphemes the way a typist would: precomposed and
+/// decomposed forms of the same accented letter are equal
+pub fn graphemes_match(expected: &str, typed: &str) -> bool {
+    expected == typed || expected.nfc().eq(typed.nfc())
+}
+
+/// Whether `typed` is `expected` begun but not finished: a base letter
+/// still waiting for the combining marks that complete it
+pub fn grapheme_pending(expected: &str, typed: &str) -> bool {
+    let expected: String = expected.nfd().collect();
+    let typed: String = typed.nfd().collect();
+    expected.len() > typed.len() && expected.starts_with(&typed)
+}
+
+/// Whether the whole typed text matches the expected text, grapheme by grapheme
+pub fn text_matches(expected: &str, typed: &str) -> bool {
+    let expected: Vec<&str> = graphemes(expected);
+    let typed: Vec<&str> = graphemes(typed);
+    expected.len() == typed.len()
+        && expected.iter().zip(typed.iter()).all(|(e, t)| graphemes_match(e, t))
+}
+
+/// The most recently typed grapheme and the one at its position in `expected`
+fn last_graphemes<'a>(expected: &'a str, typed: &'a str) -> Option<(Option<&'a str>, &'a str)> {
+    let typed_g = graphemes(typed);
+    let last = *typed_g.last()?;
+    Some((graphemes(expected).get(typed_g.len() - 1).copied(), last))
+}
+
+/// Whether the most recently typed grapheme matches its position in
+/// `expected`, or is a base letter its marks have yet to follow
+pub fn last_grapheme_correct(expected: &str, typed: &str) -> bool {
+    let Some((expected, last)) = last_graphemes(expected, typed) else {
+        return true;
+    };
+    expected.is_some_and(|e| graphemes_match(e, last) || grapheme_pending(e, last))
+}
+
+/// Whether the most recently typed grapheme still waits on a combining
+/// mark, so the word should not be judged yet
+pub fn last_grapheme_pending(expected: &str, typed: &str) -> bool {
+    last_graphemes(expected, typed).is_some_and(|(e, last)| e.is_some_and(|e| grapheme_pending(e, last)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
     
     #[test]
     fn test_attack_types() {
@@ -360,6 +584,18 @@ mod tests {
         assert_eq!(AttackType::Frantic.damage_multiplier(), 0.9);
     }
     
+    #[test]
+    fn test_attack_thresholds() {
+        let base = AttackThresholds::default();
+        assert_eq!(base.classify(85.0, 1.0), AttackType::Precision);
+        assert_eq!(base.classify(60.0, 1.0), AttackType::Standard);
+        assert_eq!(base.classify(30.0, 1.0), AttackType::Deliberate);
+        assert_eq!(base.classify(90.0, 0.5), AttackType::Frantic);
+        // A Chronicler's slower, careful hands still land Precision
+        assert_eq!(AttackThresholds::for_class(PlayerClass::Chronicler).classify(60.0, 1.0), AttackType::Precision);
+        assert_eq!(AttackThresholds::for_class(PlayerClass::Freelancer), base);
+    }
+
     #[test]
     fn test_keystroke_damage() {
         let mut impact = TypingImpact::new();
@@ -369,4 +605,71 @@ mod tests {
         assert!(result.correct);
         assert!(result.damage_this_stroke > 0.0);
     }
+    
+    #[test]
+    fn test_latency_comes_off_the_first_key_only() {
+        let mut impact = TypingImpact::new();
+        impact.params.latency_ms = 80;
+        impact.start_word("ab".to_string());
+        let start = impact.current_attack.started_at;
+        impact.on_keystroke_at('a', true, start + Duration::from_millis(300));
+        impact.on_keystroke_at('b', true, start + Duration::from_millis(450));
+        assert_eq!(impact.current_attack.started_at, start + Duration::from_millis(80));
+        assert_eq!(impact.current_attack.keystrokes[1].interval_ms, 150);
+    }
+
+    #[test]
+    fn test_gear_params() {
+        let tuning = ImpactTuning { speed_cap: 1.0, typo_forgiveness: 1, base_damage: 0.5, ..Default::default() };
+        let params = ImpactParams::with_tuning(tuning);
+        assert_eq!(params.speed_cap, 3.0);
+        assert_eq!(params.rhythm_window_ms, ImpactParams::default().rhythm_window_ms);
+        
+        let mut impact = TypingImpact::new();
+        impact.params = params;
+        impact.start_word("ab".to_string());
+        impact.on_keystroke('x', false);
+        impact.on_keystroke('b', true);
+        // One typo forgiven: judged as a perfect word
+        assert!(impact.complete_word(10).perfect);
+    }
+    
+    #[test]
+    fn test_unicode_grapheme_comparison() {
+        // Precomposed vs decomposed accents
+        assert!(text_matches("café", "cafe\u{0301}"));
+        assert!(!text_matches("café", "cafe"));
+        
+        // ß and CJK are single user-perceived characters
+        assert_eq!(grapheme_len("straße"), 6);
+        assert_eq!(grapheme_len("剣士"), 2);
+        assert!(last_grapheme_correct("剣士", "剣"));
+        assert!(!last_grapheme_correct("剣士", "士"));
+        
+        let mut typed = "cafe\u{0301}".to_string();
+        pop_grapheme(&mut typed);
+        assert_eq!(typed, "caf");
+
+        // Beyond Latin: a double-marked ǘ, Vietnamese, and Hangul typed as jamo
+        assert!(text_matches("lǘ", "lu\u{0308}\u{0301}"));
+        assert!(text_matches("Việt", "Vie\u{0323}\u{0302}t"));
+        assert!(text_matches("한", "\u{1112}\u{1161}\u{11AB}"));
+
+        // A bare base letter is not wrong yet, only unfinished
+        assert!(last_grapheme_correct("café", "cafe"));
+        assert!(last_grapheme_pending("café", "cafe"));
+        assert!(!last_grapheme_pending("café", "cafe\u{0301}"));
+        assert!(!last_grapheme_correct("café", "cafa"));
+    }
+    
+    #[test]
+    fn test_check_char_uses_graphemes() {
+        let mut impact = TypingImpact::new();
+        impact.start_word("straße".to_string());
+        for ch in "stra".chars() {
+            impact.on_keystroke(ch, true);
+        }
+        assert!(impact.check_char('ß'));
+        assert!(!impact.check_char('s'));
+    }
 }
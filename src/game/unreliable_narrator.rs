@@ -0,0 +1,149 @@
+//! Unreliable Narrator - Scenes told wrong until you know better
+//!
+//! With the unreliable narrator on, a few passages of the encounters are
+//! told subtly wrong. Nothing marks them: the narrator believes what it
+//! says, and so does the player, until the codex knows better:
+//! - each lie is corrected by one lore fragment
+//! - once that fragment is in the codex, the lie is struck through where it
+//!   stands and the truth written after it
+//!
+//! The lies are small and the corrections come late, most of them from the
+//! Living Book, so a player who reads far enough goes back through scenes
+//! they thought they knew.
+//!
+//! Design: the corrections are data in `CORRECTIONS`, each naming the
+//! passage of an encounter's description it stands over. `narrate` splits a
+//! paragraph into what is told plainly, what is lied about and what has
+//! been corrected, and the renderer styles each; the authored text itself
+//! is never changed, so with the narrator off it reads as written.
+
+/// A passage the narrator tells wrong, and what corrects it
+#[derive(Debug, Clone, Copy)]
+pub struct Correction {
+    /// Encounter whose description holds the passage
+    pub encounter: &'static str,
+    /// The passage as authored, with its indentation folded away
+    pub passage: &'static str,
+    /// What the narrator says in its place
+    pub told: &'static str,
+    /// What it is corrected to
+    pub truth: &'static str,
+    /// Lore fragment that corrects it
+    pub corrected_by: &'static str,
+}
+
+pub static CORRECTIONS: [Correction; 6] = [
+    Correction {
+        encounter: "haven_stranger_arrival",
+        passage: "Several patrons look at you. Word travels fast in Haven.",
+        told: "Several patrons look at you. Word travels fast in Haven.",
+        truth: "Several patrons look at you, and none of them are surprised. It was written long ago that someone would come asking.",
+        corrected_by: "tomorrow_text_7",
+    },
+    Correction {
+        encounter: "athenaeum_living_book",
+        passage: "'THE LAST ONE DIED BEFORE FINISHING MY FIRST CHAPTER'",
+        told: "'THE LAST ONE WAS NO ONE YOU KNEW'",
+        truth: "'THE LAST ONE WAS YOU'",
+        corrected_by: "book_the_last_reader",
+    },
+    Correction {
+        encounter: "corruption_memory_echo",
+        passage: "lit by windows that look out onto a city of spires.",
+        told: "lit by windows that look out onto a city you have never seen.",
+        truth: "lit by windows that look out onto Logos Prime, over the Street of Small Words where you lived.",
+        corrected_by: "book_city_of_doors",
+    },
+    Correction {
+        encounter: "corruption_memory_echo",
+        passage: "But these aren't your hands.",
+        told: "But these aren't your hands.",
+        truth: "These are your hands. They always were.",
+        corrected_by: "player_previous_life",
+    },
+    Correction {
+        encounter: "corruption_memory_echo",
+        passage: "A figure stands in the doorway, silhouetted by lamplight.",
+        told: "A stranger stands in the doorway, silhouetted by lamplight.",
+        truth: "The one you married stands in the doorway, silhouetted by lamplight.",
+        corrected_by: "first_speaker_journal_1",
+    },
+    Correction {
+        encounter: "mechanist_breakdown",
+        passage: "Its keys are stuck in nonsense patterns.",
+        told: "Its keys are stuck in nonsense patterns.",
+        truth: "Its keys are stuck on one word, typed over and over and crossed out each time: ending.",
+        corrected_by: "book_word_for_ending",
+    },
+];
+
+/// A piece of a paragraph as the narrator tells it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Told<'a> {
+    Plain(&'a str),
+    /// Told wrong, and nothing yet says so
+    Lie(&'static str),
+    /// Told wrong, and known to be
+    Corrected { lie: &'static str, truth: &'static str },
+}
+
+/// Tell a paragraph of an encounter's description, given which lore
+/// fragments are known
+pub fn narrate<'a>(encounter: &str, paragraph: &'a str, known: impl Fn(&str) -> bool) -> Vec<Told<'a>> {
+    let mut told = Vec::new();
+    let mut rest = paragraph;
+    while let Some((at, correction)) = CORRECTIONS.iter()
+        .filter(|c| c.encounter == encounter)
+        .filter_map(|c| rest.find(c.passage).map(|at| (at, c)))
+        .min_by_key(|(at, _)| *at)
+    {
+        if at > 0 {
+            told.push(Told::Plain(&rest[..at]));
+        }
+        told.push(if known(correction.corrected_by) {
+            Told::Corrected { lie: correction.told, truth: correction.truth }
+        } else {
+            Told::Lie(correction.told)
+        });
+        rest = &rest[at + correction.passage.len()..];
+    }
+    if !rest.is_empty() {
+        told.push(Told::Plain(rest));
+    }
+    told
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::encounter_runner::paragraphs;
+    use crate::game::encounter_writing::build_encounters;
+    use crate::game::lore_fragments::build_lore_fragments;
+
+    #[test]
+    fn test_every_lie_stands_over_its_passage_and_is_correctable() {
+        let encounters = build_encounters();
+        let fragments = build_lore_fragments();
+        for c in &CORRECTIONS {
+            let description = paragraphs(&encounters[c.encounter].content.description);
+            assert!(description.iter().any(|p| p.contains(c.passage)), "{} has no passage {:?}", c.encounter, c.passage);
+            assert!(fragments.contains_key(c.corrected_by), "{} is not a lore fragment", c.corrected_by);
+            assert_ne!(c.told, c.truth);
+        }
+    }
+
+    #[test]
+    fn test_lies_are_struck_once_their_lore_is_known() {
+        let paragraph = "You turn. A figure stands in the doorway, silhouetted by lamplight. You can't see their face.";
+        let unknown = narrate("corruption_memory_echo", paragraph, |_| false);
+        assert_eq!(unknown, vec![
+            Told::Plain("You turn. "),
+            Told::Lie(CORRECTIONS[4].told),
+            Told::Plain(" You can't see their face."),
+        ]);
+        let known = narrate("corruption_memory_echo", paragraph, |id| id == "first_speaker_journal_1");
+        assert_eq!(known[1], Told::Corrected { lie: CORRECTIONS[4].told, truth: CORRECTIONS[4].truth });
+        // Only the encounter the passage belongs to tells it wrong
+        assert_eq!(narrate("haven_old_scribe", paragraph, |_| true), vec![Told::Plain(paragraph)]);
+    }
+}
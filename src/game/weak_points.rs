@@ -0,0 +1,136 @@
+//! Weak Points - Every boss has a word it can't bear to hear
+//!
+//! A boss's long sentences hide weak-point words, drawn highlighted in the
+//! prompt. Type one with perfect accuracy (no slips inside it, even ones
+//! you backspaced) and the boss staggers, opening a short window where
+//! your words hit harder. A long transcription is no longer worth the same
+//! per character everywhere - there are places worth aiming for.
+//!
+//! Design: weak points are a pure function of the sentence, so they never
+//! need storing; the tracker only remembers slips and strikes for the
+//! current prompt.
+
+use std::time::Duration;
+
+use super::typing_impact::graphemes;
+
+/// Fewest words a prompt needs before it hides a weak point
+const MIN_WORDS: usize = 4;
+/// Shortest word that can be a weak point (in graphemes)
+const MIN_WEAK_LEN: usize = 5;
+/// Weak points hidden per this many words (at least one)
+const WORDS_PER_WEAK_POINT: usize = 6;
+/// How long a struck boss stays staggered
+pub const STAGGER_WINDOW: Duration = Duration::from_secs(4);
+/// Damage multiplier while the boss is staggered
+pub const STAGGER_MULT: f32 = 1.5;
+
+/// A weak-point word: grapheme positions [start, end) within the prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeakPoint {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl WeakPoint {
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.start && index < self.end
+    }
+}
+
+/// The weak points hidden in a prompt: its longest words, in reading order
+pub fn find(prompt: &str) -> Vec<WeakPoint> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let chars = graphemes(prompt);
+    for (i, g) in chars.iter().enumerate() {
+        let letter = g.chars().next().is_some_and(char::is_alphanumeric);
+        match (letter, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push(WeakPoint { start: s, end: i });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(WeakPoint { start: s, end: chars.len() });
+    }
+    if words.len() < MIN_WORDS {
+        return Vec::new();
+    }
+
+    let count = (words.len() / WORDS_PER_WEAK_POINT).max(1);
+    let mut longest: Vec<WeakPoint> = words.into_iter()
+        .filter(|w| w.end - w.start >= MIN_WEAK_LEN)
+        .collect();
+    // Longest first; earlier words win ties
+    longest.sort_by_key(|w| (std::cmp::Reverse(w.end - w.start), w.start));
+    longest.truncate(count);
+    longest.sort_by_key(|w| w.start);
+    longest
+}
+
+/// Slips and strikes on the current prompt's weak points
+#[derive(Debug, Clone, Default)]
+pub struct WeakPointTracker {
+    /// Grapheme positions where a wrong key landed
+    slips: Vec<usize>,
+    /// Weak points already struck (by start position)
+    struck: Vec<usize>,
+}
+
+impl WeakPointTracker {
+    pub fn reset(&mut self) {
+        self.slips.clear();
+        self.struck.clear();
+    }
+
+    pub fn on_slip(&mut self, index: usize) {
+        self.slips.push(index);
+    }
+
+    /// A correct key landed and `typed_len` graphemes are typed: the weak
+    /// point it finished, if that word was typed without a slip
+    pub fn on_correct(&mut self, weak_points: &[WeakPoint], typed_len: usize) -> Option<WeakPoint> {
+        let finished = weak_points.iter().find(|w| w.end == typed_len)?;
+        if self.struck.contains(&finished.start) || self.slips.iter().any(|i| finished.contains(*i)) {
+            return None;
+        }
+        self.struck.push(finished.start);
+        Some(*finished)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_longest_words() {
+        let prompt = "the ancient archive remembers every word";
+        let points = find(prompt);
+        assert_eq!(points.len(), 1);
+        let chars = graphemes(prompt);
+        assert_eq!(chars[points[0].start..points[0].end].concat(), "remembers");
+        // Short prompts hide nothing
+        assert!(find("strike the archive").is_empty());
+    }
+
+    #[test]
+    fn test_slip_spoils_weak_point() {
+        let points = vec![WeakPoint { start: 4, end: 11 }];
+        let mut tracker = WeakPointTracker::default();
+        assert_eq!(tracker.on_correct(&points, 11), Some(points[0]));
+        // Struck once per prompt
+        assert_eq!(tracker.on_correct(&points, 11), None);
+
+        tracker.reset();
+        tracker.on_slip(6);
+        assert_eq!(tracker.on_correct(&points, 11), None);
+        tracker.reset();
+        tracker.on_slip(2);
+        assert_eq!(tracker.on_correct(&points, 11), Some(points[0]));
+    }
+}
@@ -0,0 +1,81 @@
+//! Word Chain - Every word picks up where the last one left off
+//!
+//! Under the Word Chain modifier each new prompt begins with the final
+//! letter of the previous one ("quill" → "lantern" → "night"). Every word
+//! landed in an unbroken chain adds to a damage bonus; a miss or a timeout
+//! snaps the chain and the bonus starts over.
+//!
+//! Design: the chain only decides the next word's first letter and the
+//! bonus; word selection itself stays with LoreWords.
+
+/// Damage bonus per link in the chain
+pub const CHAIN_BONUS_PER_LINK: f32 = 0.1;
+/// Most bonus an unbroken chain can build
+pub const MAX_CHAIN_BONUS: f32 = 1.0;
+
+/// An unbroken run of chained words
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordChain {
+    /// Words landed since the chain last broke
+    pub links: u32,
+}
+
+impl WordChain {
+    /// A word landed: the chain grows
+    pub fn link(&mut self) {
+        self.links += 1;
+    }
+
+    /// A word was missed; returns how long the chain had grown
+    pub fn snap(&mut self) -> u32 {
+        std::mem::take(&mut self.links)
+    }
+
+    /// Damage multiplier for the current chain
+    pub fn bonus_mult(&self) -> f32 {
+        1.0 + (self.links.saturating_sub(1) as f32 * CHAIN_BONUS_PER_LINK).min(MAX_CHAIN_BONUS)
+    }
+}
+
+/// The letter the next word must begin with: the prompt's last letter
+pub fn next_letter(prompt: &str) -> Option<char> {
+    prompt.chars().rev().find(|c| c.is_alphabetic())
+}
+
+/// Whether `word` continues a chain ending in `letter` (any case)
+pub fn continues(word: &str, letter: char) -> bool {
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    word.chars().next().is_some_and(|c| lower(c) == lower(letter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_letter_skips_punctuation() {
+        assert_eq!(next_letter("quill"), Some('l'));
+        assert_eq!(next_letter("The ink remembers."), Some('s'));
+        assert_eq!(next_letter("..."), None);
+        assert!(continues("Lantern", 'l'));
+        assert!(continues("night", 'N'));
+        assert!(!continues("quill", 'l'));
+    }
+
+    #[test]
+    fn test_bonus_grows_until_snapped() {
+        let mut chain = WordChain::default();
+        chain.link();
+        assert_eq!(chain.bonus_mult(), 1.0);
+        for _ in 0..4 {
+            chain.link();
+        }
+        assert!((chain.bonus_mult() - 1.4).abs() < 1e-6);
+        for _ in 0..50 {
+            chain.link();
+        }
+        assert_eq!(chain.bonus_mult(), 1.0 + MAX_CHAIN_BONUS);
+        assert_eq!(chain.snap(), 55);
+        assert_eq!(chain.bonus_mult(), 1.0);
+    }
+}
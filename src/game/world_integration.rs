@@ -7,6 +7,8 @@ use super::dungeon::{Dungeon, RoomType};
 use super::narrative::{Faction, WorldState, Chapter};
 use super::lore_fragments::{LoreFragment, LoreCategory};
 use super::events::{GameEvent, EventChoice, EventOutcome};
+use super::setting_packs;
+use super::zone_routes;
 use rand::seq::SliceRandom;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -29,25 +31,37 @@ pub enum FloorZone {
 }
 
 impl FloorZone {
+    /// Zone the floor lies in on the run's route (see `zone_routes`)
     pub fn from_floor(floor: u32) -> Self {
-        match floor {
-            1..=2 => FloorZone::ShatteredHalls,
-            3..=4 => FloorZone::SunkenArchives,
-            5..=6 => FloorZone::BlightedGardens,
-            7..=8 => FloorZone::ClockworkDepths,
-            9..=10 => FloorZone::VoidsEdge,
-            _ => FloorZone::TheBreach,
-        }
+        zone_routes::zone_for(floor)
     }
 
+    /// The zone's name in the run's setting
     pub fn name(&self) -> &'static str {
+        setting_packs::pack().zone_name(*self)
+    }
+
+    /// Enemy template (by id) the zone's elite rooms hold
+    pub fn elite_id(&self) -> &'static str {
+        match self {
+            FloorZone::ShatteredHalls => "vowel_vampire",
+            FloorZone::SunkenArchives => "archive_guardian",
+            FloorZone::BlightedGardens => "corrupted_typer",
+            FloorZone::ClockworkDepths => "grammar_golem",
+            FloorZone::VoidsEdge => "void_crawler",
+            FloorZone::TheBreach => "null_word",
+        }
+    }
+
+    /// Places authored encounters name that lie in this zone
+    pub fn encounter_locations(&self) -> &'static [&'static str] {
         match self {
-            FloorZone::ShatteredHalls => "The Shattered Halls",
-            FloorZone::SunkenArchives => "The Sunken Archives",
-            FloorZone::BlightedGardens => "The Blighted Gardens",
-            FloorZone::ClockworkDepths => "The Clockwork Depths",
-            FloorZone::VoidsEdge => "The Void's Edge",
-            FloorZone::TheBreach => "The Breach",
+            FloorZone::ShatteredHalls => &["haven", "haven_inn", "haven_market", "haven_alleys", "shadow_quarter"],
+            FloorZone::SunkenArchives => &["athenaeum", "athenaeum_stacks", "athenaeum_restricted"],
+            FloorZone::BlightedGardens => &["corruption_zone", "whispering_waste"],
+            FloorZone::ClockworkDepths => &["gearhold", "mechanist_workshop"],
+            FloorZone::VoidsEdge => &["corruption_zone", "shadow_quarter", "athenaeum_restricted"],
+            FloorZone::TheBreach => &["corruption_zone"],
         }
     }
 
@@ -188,10 +202,12 @@ pub fn get_floor_milestone(floor: u32) -> Option<StoryMilestone> {
 }
 
 /// Generate a zone-appropriate random event
-pub fn generate_zone_event(zone: FloorZone) -> GameEvent {
-    let mut rng = rand::thread_rng();
+pub fn generate_zone_event(zone: FloorZone, rng: &mut impl Rng) -> GameEvent {
     let events = get_zone_events(zone);
-    events.choose(&mut rng).cloned().unwrap_or_else(|| GameEvent::random())
+    match events.choose(rng) {
+        Some(event) => event.clone(),
+        None => GameEvent::random(rng),
+    }
 }
 
 fn get_zone_events(zone: FloorZone) -> Vec<GameEvent> {
@@ -367,6 +383,12 @@ pub fn get_floor_lore(floor: u32) -> Option<(String, String)> {
         return None;
     }
     
+    pick_floor_lore(floor)
+}
+
+/// Pick a lore fragment for the floor's zone, no roll involved
+pub fn pick_floor_lore(floor: u32) -> Option<(String, String)> {
+    let mut rng = rand::thread_rng();
     let zone = FloorZone::from_floor(floor);
     let lore_pieces = match zone {
         FloorZone::ShatteredHalls => vec![
@@ -393,6 +415,7 @@ pub fn get_floor_lore(floor: u32) -> Option<(String, String)> {
             ("Malachar's Journal", "'I can feel the Veil thinning. So close now. The Stones resonate with something beyond. It calls to me. It knows my name. It says it can give her back.'"),
             ("Survivor's Account", "'I saw him at the moment of Ascension. He reached for the gods—and something reached back. The look on his face... it wasn't triumph. It was horror.'"),
             ("Void-Touched Note", "Text that shifts when you look away: 'YOU CAME BACK. YOU ALWAYS COME BACK. HOW MANY TIMES WILL YOU TRY? HOW MANY TIMES WILL YOU FAIL?'"),
+            ("Names in the Dark", "Scratched into the stone a hundred times, in a hundred different hands, the same words: 'We were the price.' Beneath them, smaller: 'Say it to the Herald. It has forgotten.'"),
         ],
         FloorZone::TheBreach => vec![
             ("The Final Truth", "'I am Malachar. I was Malachar. I will be Malachar. The cycle turns. The Breach remembers. And you—you are me, trying again.'"),
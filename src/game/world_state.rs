@@ -0,0 +1,205 @@
+//! World State - What the world remembers between runs
+//!
+//! Encounters change the world with named flags ("living_book_awakened").
+//! They are kept here, with counters, and outlast the run that set them:
+//! - encounter requirements can ask for a flag, or for its absence
+//! - some flags have long-term consequences felt at the start of every run
+//! - counters keep a tally, like how many times you have descended
+//! - recurring NPCs keep whatever state your choices left them in
+//!
+//! The ledger of lasting changes shown on the Consequences screen is kept
+//! here too, so it outlasts the session.
+//!
+//! A fresh timeline wipes the flags, NPCs and ledger; the counters carry on,
+//! because the descents happened all the same.
+//!
+//! Design: a flat store of flags and counters, saved beside the challenge
+//! bests. GameState writes to it when an encounter resolves and reads it
+//! when a run starts and when encounters are picked.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use super::config::get_config_dir;
+use super::consequences::WorldLedger;
+use super::narrative::Faction;
+use super::npc_lifecycle::NpcState;
+
+/// Counter bumped at the start of every run
+pub const DESCENTS: &str = "descents";
+/// Counter bumped whenever an encounter resolves
+pub const ENCOUNTERS_RESOLVED: &str = "encounters_resolved";
+/// Deepest floor reached in the endless descent
+pub const DEEPEST_DESCENT: &str = "deepest_descent";
+
+/// Flags and counters the world keeps across runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorldState {
+    #[serde(default)]
+    pub flags: BTreeSet<String>,
+    #[serde(default)]
+    pub counters: BTreeMap<String, i64>,
+    /// Recurring NPCs no longer as you first found them
+    #[serde(default)]
+    pub npcs: BTreeMap<String, NpcState>,
+    /// Lasting changes made to the world on this timeline
+    #[serde(default)]
+    pub ledger: WorldLedger,
+}
+
+impl WorldState {
+    /// Set a flag; returns false if it was already set
+    pub fn set_flag(&mut self, flag: &str) -> bool {
+        self.flags.insert(flag.to_string())
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Add `by` to a counter, returning its new value
+    pub fn bump(&mut self, counter: &str, by: i64) -> i64 {
+        let value = self.counters.entry(counter.to_string()).or_insert(0);
+        *value += by;
+        *value
+    }
+
+    /// Raise a counter to `value` if it is higher; true if it was
+    pub fn raise(&mut self, counter: &str, value: i64) -> bool {
+        let best = self.counters.entry(counter.to_string()).or_insert(0);
+        let raised = value > *best;
+        *best = (*best).max(value);
+        raised
+    }
+
+    pub fn counter(&self, counter: &str) -> i64 {
+        self.counters.get(counter).copied().unwrap_or(0)
+    }
+
+    /// Where recurring NPC `id` is in their story
+    pub fn npc_state(&self, id: &str) -> NpcState {
+        self.npcs.get(id).copied().unwrap_or_default()
+    }
+
+    pub fn set_npc_state(&mut self, id: &str, state: NpcState) {
+        self.npcs.insert(id.to_string(), state);
+    }
+
+    /// A fresh timeline: the flags, NPCs and ledger go, the counters stay
+    pub fn start_fresh(&mut self) {
+        self.flags.clear();
+        self.npcs.clear();
+        self.ledger.start_fresh();
+    }
+
+    /// Long-term consequences of the flags set, in flag order
+    pub fn consequences(&self) -> Vec<&'static LongTermConsequence> {
+        CONSEQUENCES.iter().filter(|c| self.has_flag(c.flag)).collect()
+    }
+}
+
+/// What a flag does to every run that follows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aftermath {
+    /// Extra share of time for every word
+    TimeBonus(f32),
+    /// Standing a faction starts each run with
+    Standing(Faction, i32),
+    /// Extra max HP
+    MaxHp(i32),
+    /// Only a change in how the world speaks to you
+    Remembered,
+}
+
+/// A flag's lasting effect on later runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongTermConsequence {
+    pub flag: &'static str,
+    /// Shown when a run starts under it
+    pub message: &'static str,
+    pub aftermath: Aftermath,
+}
+
+pub const CONSEQUENCES: [LongTermConsequence; 4] = [
+    LongTermConsequence {
+        flag: "living_book_awakened",
+        message: "The Living Book turns its own pages for you: more time for every word.",
+        aftermath: Aftermath::TimeBonus(5.0),
+    },
+    LongTermConsequence {
+        flag: "player_memory_fragment_1",
+        message: "A remembered fragment steadies you: +5 max HP.",
+        aftermath: Aftermath::MaxHp(5),
+    },
+    LongTermConsequence {
+        flag: "shadowwriter_contact",
+        message: "The Shadow Guild remembers your dealings.",
+        aftermath: Aftermath::Standing(Faction::ShadowGuild, 10),
+    },
+    LongTermConsequence {
+        flag: "identity_revealed",
+        message: "You know who you were. The dungeon knows you know.",
+        aftermath: Aftermath::Remembered,
+    },
+];
+
+fn state_path() -> PathBuf {
+    get_config_dir().join("world.ron")
+}
+
+pub fn load_world_state() -> WorldState {
+    fs::read_to_string(state_path())
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_world_state(state: &WorldState) -> std::io::Result<()> {
+    fs::create_dir_all(get_config_dir())?;
+    let content = ron::ser::to_string_pretty(state, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fs::write(state_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::consequences::ChangeKind;
+
+    #[test]
+    fn test_flags_and_counters() {
+        let mut world = WorldState::default();
+        assert!(world.set_flag("living_book_awakened"));
+        assert!(!world.set_flag("living_book_awakened"));
+        assert_eq!(world.bump(DESCENTS, 1), 1);
+        assert_eq!(world.bump(DESCENTS, 1), 2);
+        assert!(world.raise(DEEPEST_DESCENT, 12));
+        assert!(!world.raise(DEEPEST_DESCENT, 11));
+        assert_eq!(world.counter(DEEPEST_DESCENT), 12);
+        assert_eq!(world.consequences().len(), 1);
+
+        world.ledger.record(ChangeKind::WorldShift, "living_book_awakened", "The book woke");
+        world.start_fresh();
+        assert!(!world.has_flag("living_book_awakened"));
+        assert!(world.ledger.changes.is_empty());
+        assert_eq!(world.counter(DESCENTS), 2);
+        assert!(world.consequences().is_empty());
+    }
+
+    #[test]
+    fn test_round_trips_through_ron() {
+        let mut world = WorldState::default();
+        world.set_flag("shadowwriter_contact");
+        world.bump(ENCOUNTERS_RESOLVED, 3);
+        world.ledger.record(ChangeKind::EndingAchieved, "unbound", "The Unbound");
+        let text = ron::to_string(&world).unwrap();
+        let back: WorldState = ron::from_str(&text).unwrap();
+        assert!(back.has_flag("shadowwriter_contact"));
+        assert_eq!(back.counter(ENCOUNTERS_RESOLVED), 3);
+        assert!(back.ledger.has(ChangeKind::EndingAchieved, "unbound"));
+        // Older files without counters still load
+        assert!(ron::from_str::<WorldState>("(flags: [])").is_ok());
+    }
+}
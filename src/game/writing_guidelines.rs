@@ -414,6 +414,18 @@ pub fn location_tones() -> HashMap<String, LocationTone> {
     tones
 }
 
+/// Key of the location tone a place an encounter names is written in
+pub fn tone_of(location: &str) -> &'static str {
+    match location {
+        "whispering_waste" => "corruption_zone",
+        "mechanist_workshop" => "gearhold",
+        _ => ["haven", "athenaeum", "corruption_zone", "gearhold", "shadow_quarter", "grove"]
+            .into_iter()
+            .find(|tone| location.starts_with(tone))
+            .unwrap_or("haven"),
+    }
+}
+
 // ============================================================================
 // RECURRING MOTIFS
 // ============================================================================
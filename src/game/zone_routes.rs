@@ -0,0 +1,157 @@
+//! Zone Routes - Which zone each floor lies in, and the forks between them
+//!
+//! The descent is laid out in tiers of two floors. Some tiers fork: at the
+//! stairway into them the player picks one of two zones, and the pick holds
+//! for both floors. A zone walked in an earlier tier is not offered again.
+//! - Floors 1-2: The Shattered Halls
+//! - Floors 3-4: The Sunken Archives or The Blighted Gardens
+//! - Floors 5-6: The Blighted Gardens or The Clockwork Depths
+//! - Floors 7-8: The Clockwork Depths or The Sunken Archives
+//! - Floors 9-10: The Void's Edge
+//!
+//! Past the last tier lies the Breach. Each zone brings its own word pools,
+//! encounters and elite, so the route decides what the run is typed in.
+//!
+//! Design: the route is thread-local, like the run's random streams, so
+//! `FloorZone::from_floor` (and every word pool, encounter and hazard keyed
+//! off it) follows the route without it being threaded through. A run that
+//! takes no forks walks the first option of every tier.
+
+use std::cell::RefCell;
+
+use super::world_integration::FloorZone;
+
+/// Floors sharing a zone, and the zones on offer there
+#[derive(Debug, Clone, Copy)]
+pub struct ZoneTier {
+    pub first_floor: u32,
+    pub last_floor: u32,
+    pub options: &'static [FloorZone],
+}
+
+pub const ZONE_TIERS: [ZoneTier; 5] = [
+    ZoneTier { first_floor: 1, last_floor: 2, options: &[FloorZone::ShatteredHalls] },
+    ZoneTier { first_floor: 3, last_floor: 4, options: &[FloorZone::SunkenArchives, FloorZone::BlightedGardens] },
+    ZoneTier { first_floor: 5, last_floor: 6, options: &[FloorZone::BlightedGardens, FloorZone::ClockworkDepths] },
+    ZoneTier { first_floor: 7, last_floor: 8, options: &[FloorZone::ClockworkDepths, FloorZone::SunkenArchives] },
+    ZoneTier { first_floor: 9, last_floor: 10, options: &[FloorZone::VoidsEdge] },
+];
+
+/// The zones picked at each fork so far
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneRoute {
+    picks: [Option<FloorZone>; ZONE_TIERS.len()],
+}
+
+impl ZoneRoute {
+    /// Index of the tier `floor` lies in
+    pub fn tier_of(floor: u32) -> Option<usize> {
+        ZONE_TIERS.iter().position(|t| (t.first_floor..=t.last_floor).contains(&floor))
+    }
+
+    /// Zones the tier offers, less those walked before it
+    pub fn options(&self, tier: usize) -> Vec<FloorZone> {
+        let walked: Vec<FloorZone> = (0..tier).map(|t| self.zone(t)).collect();
+        let fresh: Vec<FloorZone> = ZONE_TIERS[tier].options.iter().copied().filter(|z| !walked.contains(z)).collect();
+        if fresh.is_empty() {
+            ZONE_TIERS[tier].options.to_vec()
+        } else {
+            fresh
+        }
+    }
+
+    /// Zone of a tier: the one picked, or else its first option
+    pub fn zone(&self, tier: usize) -> FloorZone {
+        self.picks[tier].unwrap_or_else(|| self.options(tier)[0])
+    }
+
+    pub fn zone_for(&self, floor: u32) -> FloorZone {
+        Self::tier_of(floor).map_or(FloorZone::TheBreach, |tier| self.zone(tier))
+    }
+
+    /// The choice waiting at the stairway down to `floor`, if it opens a
+    /// fork not yet taken
+    pub fn fork(&self, floor: u32) -> Option<Vec<FloorZone>> {
+        let tier = Self::tier_of(floor)?;
+        let options = self.options(tier);
+        (ZONE_TIERS[tier].first_floor == floor && self.picks[tier].is_none() && options.len() > 1).then_some(options)
+    }
+
+    /// Take `zone` at the fork leading to `floor`; false if it isn't offered
+    pub fn choose(&mut self, floor: u32, zone: FloorZone) -> bool {
+        let Some(tier) = Self::tier_of(floor) else {
+            return false;
+        };
+        if !self.options(tier).contains(&zone) {
+            return false;
+        }
+        self.picks[tier] = Some(zone);
+        true
+    }
+}
+
+thread_local! {
+    static ROUTE: RefCell<ZoneRoute> = RefCell::new(ZoneRoute::default());
+}
+
+/// Start a run on an untaken route
+pub fn reset() {
+    ROUTE.with(|r| *r.borrow_mut() = ZoneRoute::default());
+}
+
+/// The route this run is on
+pub fn current() -> ZoneRoute {
+    ROUTE.with(|r| r.borrow().clone())
+}
+
+/// Zone `floor` lies in on the current route
+pub fn zone_for(floor: u32) -> FloorZone {
+    ROUTE.with(|r| r.borrow().zone_for(floor))
+}
+
+/// Take a fork on the current route
+pub fn choose(floor: u32, zone: FloorZone) -> bool {
+    ROUTE.with(|r| r.borrow_mut().choose(floor, zone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_untaken_route_is_the_classic_descent() {
+        let route = ZoneRoute::default();
+        let zones: Vec<FloorZone> = [1, 3, 5, 7, 9, 11].iter().map(|f| route.zone_for(*f)).collect();
+        assert_eq!(zones, vec![
+            FloorZone::ShatteredHalls,
+            FloorZone::SunkenArchives,
+            FloorZone::BlightedGardens,
+            FloorZone::ClockworkDepths,
+            FloorZone::VoidsEdge,
+            FloorZone::TheBreach,
+        ]);
+        assert!(route.fork(1).is_none());
+        assert!(route.fork(4).is_none());
+        assert_eq!(route.fork(3), Some(vec![FloorZone::SunkenArchives, FloorZone::BlightedGardens]));
+    }
+
+    #[test]
+    fn test_forks_skip_walked_zones() {
+        let mut route = ZoneRoute::default();
+        assert!(route.choose(3, FloorZone::BlightedGardens));
+        assert_eq!(route.zone_for(4), FloorZone::BlightedGardens);
+        assert!(route.fork(3).is_none());
+        // The Gardens are behind us, so floors 5-6 have no fork left
+        assert!(route.fork(5).is_none());
+        assert_eq!(route.zone_for(5), FloorZone::ClockworkDepths);
+        assert_eq!(route.zone_for(7), FloorZone::SunkenArchives);
+        assert!(!route.choose(5, FloorZone::BlightedGardens));
+
+        // The thread's route follows its picks
+        reset();
+        assert!(choose(3, FloorZone::BlightedGardens));
+        assert_eq!(zone_for(3), FloorZone::BlightedGardens);
+        assert_eq!(current(), route);
+        reset();
+    }
+}
@@ -9,44 +9,117 @@ mod data;
 mod ui;
 
 use std::io;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
 
 use game::state::{GameState, Scene};
 use game::player::{Player, Class};
 use game::enemy::Enemy;
 use game::world_integration::{get_floor_milestone, generate_zone_event, FloorZone};
 use game::dungeon::RoomType;
+use game::{memories, prophecies};
 use game::combat::CombatPhase;
+use game::auto_pause::PauseReason;
+use game::pause::PauseOption;
+use game::settings::Setting;
+use game::keybinds::{Action, Context};
+use game::typing_impact::{graphemes, grapheme_len, last_grapheme_correct, text_matches};
+use game::sentence_stitching::RunEvent;
+use game::map_travel::MapPromptMode;
+use game::encounter_runner::EncounterStage;
+use game::dreams::DreamStage;
+use game::leveling::LevelUpChoice;
+use game::run_rng::{self, Stream};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Setup better panic messages for debugging
     better_panic::install();
+
+    // `--import-challenge <file>` installs a challenge and exits
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--import-challenge") {
+        let Some(path) = args.get(pos + 1) else {
+            eprintln!("Usage: keyboard-warrior --import-challenge <file.ron>");
+            std::process::exit(2);
+        };
+        match game::challenges::import(std::path::Path::new(path)) {
+            Ok(challenge) => println!("Installed challenge '{}' (target {})", challenge.name, challenge.target_score),
+            Err(e) => {
+                eprintln!("Could not import challenge: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    // `--check-encounters [file]` checks an encounter pack (or every
+    // installed one) and exits
+    if let Some(pos) = args.iter().position(|a| a == "--check-encounters") {
+        let checked = match args.get(pos + 1) {
+            Some(path) => vec![game::encounter_data::load_pack(std::path::Path::new(path))],
+            None => {
+                let (encounters, errors) = game::encounter_data::load_packs();
+                println!("{} pack encounters in {}", encounters.len(), game::encounter_data::packs_dir().display());
+                errors.into_iter().map(Err).collect()
+            }
+        };
+        let mut failed = false;
+        for result in checked {
+            match result {
+                Ok(encounters) => println!("OK: {} encounters", encounters.len()),
+                Err(e) => {
+                    eprintln!("Broken encounter pack: {}", e);
+                    failed = true;
+                }
+            }
+        }
+        std::process::exit(if failed { 1 } else { 0 });
+    }
+
+    // `--seed <n>` plays every ordinary run from the same seed
+    let seed = match args.iter().position(|a| a == "--seed") {
+        Some(pos) => match args.get(pos + 1).and_then(|s| s.parse::<u64>().ok()) {
+            Some(seed) => Some(seed),
+            None => {
+                eprintln!("Usage: keyboard-warrior --seed <number>");
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create game state
     let mut game = GameState::new();
+    game.seed_override = seed;
+    // `--screen-reader` draws for a screen reader from the first frame
+    if args.iter().any(|a| a == "--screen-reader") && !game.config.display.screen_reader {
+        game.toggle_screen_reader();
+    }
 
     // Main game loop
     let result = run_game(&mut terminal, &mut game);
+    game.status_line.clear();
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableFocusChange
     )?;
     terminal.show_cursor()?;
 
@@ -64,36 +137,86 @@ fn run_game(
     let tick_rate = Duration::from_millis(50);
 
     loop {
+        // Keep the multiplexer status line current; it is only rewritten
+        // when the line itself changes
+        game.export_status();
+
         // Render
         terminal.draw(|f| ui::render::render(f, game))?;
 
         // Handle input
         if event::poll(tick_rate)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    // Stamp the key now, before rendering or handling can delay it
+                    game.key_read_at = Instant::now();
+                    // Ctrl+Z arrives as a key in raw mode - suspend ourselves
+                    if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        suspend(terminal, game)?;
+                        continue;
+                    }
+                    // Swallow typing while paused; any key starts the countdown
+                    if game.auto_pause.is_active() {
+                        game.auto_pause.begin_countdown();
+                        continue;
+                    }
                     match handle_input(game, key.code) {
                         InputResult::Quit => break,
                         InputResult::Continue => {}
                     }
                 }
+                Event::FocusLost if game.scene == Scene::Combat => {
+                    game.auto_pause.pause(PauseReason::FocusLost);
+                }
+                Event::FocusGained => {
+                    game.auto_pause.begin_countdown();
+                }
+                _ => {}
             }
         }
 
+        // A terminal too small to play in holds the fight until it grows
+        let size = terminal.size()?;
+        let too_small = ui::layout::fit(Rect::new(0, 0, size.width, size.height)) == ui::layout::Fit::TooSmall;
+        if too_small && game.scene == Scene::Combat {
+            game.auto_pause.pause(PauseReason::TooSmall);
+        } else if game.auto_pause.reason == Some(PauseReason::TooSmall) {
+            game.auto_pause.begin_countdown();
+        }
+
+        // A long gap between frames means we were stopped from outside
+        if game.auto_pause.update() && game.scene == Scene::Combat {
+            game.auto_pause.pause(PauseReason::Stalled);
+            game.auto_pause.begin_countdown();
+        }
+
         // Update visual effects each frame
         game.update_effects();
+        game.update_transcript();
+        game.update_screen_reader();
+        game.pronounce_prompt();
         
         // Track damage for effects (deferred pattern to avoid borrow issues)
         let mut enemy_damage_for_effects: Option<i32> = None;
         
-        // Update combat timer if in combat
-        if let Some(combat) = &mut game.combat_state {
+        // Combat timers stay frozen while paused, counting down or reading back
+        let holding = game.auto_pause.is_active() || game.transcript_view.is_some() || game.paused.is_some();
+        if holding {
+            game.hold_fight(Instant::now());
+        } else {
+            game.release_fight(Instant::now());
+        }
+        if let Some(combat) = game.combat_state.as_mut().filter(|_| !holding) {
             combat.tick();
+            if let Some(player) = &mut game.player {
+                combat.apply_hazard_damage(player);
+            }
             
             // Update immersion system (50ms tick rate)
             combat.immersive_update(50);
             
             // Check for time running out OR enemy turn phase
-            if combat.time_remaining <= 0.0 || combat.phase == CombatPhase::EnemyTurn {
+            if combat.enemy_acts_now() {
                 // Enemy attacks
                 if let Some(player) = &mut game.player {
                     let hp_before = player.hp;
@@ -115,6 +238,11 @@ fn run_game(
             }
         }
         
+        // A lesson's fight ends the moment its lesson is learned
+        if game.scene == Scene::Combat {
+            game.check_lesson();
+        }
+        
         // Apply deferred visual effects (after combat borrow released)
         if let Some(damage) = enemy_damage_for_effects {
             game.effect_enemy_damage(damage);
@@ -122,39 +250,131 @@ fn run_game(
         
         // Process events from the event bus (system reactions)
         game.process_events();
+        game.offer_level_up();
     }
 
     Ok(())
 }
 
+/// Suspend the process (Ctrl+Z), restoring the terminal for the shell and
+/// re-entering the alternate screen once resumed with `fg`
+fn suspend(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    game: &mut GameState,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if game.scene == Scene::Combat {
+        game.auto_pause.pause(PauseReason::Suspended);
+    }
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableFocusChange)?;
+    terminal.show_cursor()?;
+
+    // Blocks here until SIGCONT
+    #[cfg(unix)]
+    signal_hook::low_level::raise(signal_hook::consts::SIGTSTP)?;
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
+    terminal.clear()?;
+
+    game.auto_pause.begin_countdown();
+    Ok(())
+}
+
 enum InputResult {
     Continue,
     Quit,
 }
 
+/// A key's name, as keybinds hold it
+fn key_name(key: KeyCode) -> Option<String> {
+    Some(match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "BackTab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Insert => "Insert".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        _ => return None,
+    })
+}
+
 fn handle_input(game: &mut GameState, key: KeyCode) -> InputResult {
     // Update help system context
     game.help_system.update_context(game.scene);
     
+    // Author console (debug builds) swallows everything while open
+    if cfg!(debug_assertions) {
+        if key == KeyCode::F(12) {
+            game.dev_console.toggle();
+            return InputResult::Continue;
+        }
+        if game.dev_console.visible {
+            return handle_dev_console_input(game, key);
+        }
+    }
+    
+    // The keybinds screen takes the next key whole, to bind it
+    if game.keybind_capture.is_some() {
+        game.capture_keybind(key_name(key).filter(|_| key != KeyCode::Esc));
+        return InputResult::Continue;
+    }
+    let name = key_name(key);
+    let bound = |context| name.as_deref().and_then(|n| game.keybinds.action(context, n));
+    
+    // The transcript pane opens over any scene, typing or not
+    if bound(Context::Anywhere) == Some(Action::Transcript) {
+        game.toggle_transcript();
+        return InputResult::Continue;
+    }
+    if game.transcript_view.is_some() {
+        return handle_transcript_input(game, key);
+    }
+    
     // Help overlay intercepts input when visible
     if game.help_system.visible {
         return handle_help_input(game, key);
     }
     
-    // Global help toggle (? only during combat/tutorial, h elsewhere)
-    // During combat/tutorial, 'h' should go to typing, not help
-    let in_typing_mode = matches!(game.scene, Scene::Combat | Scene::Tutorial);
-    match key {
-        KeyCode::Char('?') if !in_typing_mode => {
-            game.help_system.toggle();
-            return InputResult::Continue;
-        }
-        KeyCode::Char('h') if !in_typing_mode => {
-            game.help_system.toggle();
-            return InputResult::Continue;
-        }
-        _ => {}
+    // Help and the menu keys, except while typing: during combat/tutorial
+    // and any typed answer, 'h' and '?' go to the prompt
+    let in_typing_mode = matches!(game.scene, Scene::Combat | Scene::Tutorial)
+        || game.comprehension.is_some()
+        || game.map_prompt.is_some()
+        || game.encounter_run.as_ref().is_some_and(|r| matches!(r.stage, EncounterStage::Typing { .. }))
+        || game.dream_run.as_ref().is_some_and(|r| matches!(r.stage, DreamStage::Typing { .. }))
+        || game.conversation.as_ref().is_some_and(|r| r.typing.is_some())
+        || game.reflection.is_some()
+        || game.decoding.is_some()
+        || game.cipher_guess.is_some();
+    let menu_action = if in_typing_mode { None } else { bound(Context::Menu) };
+    if menu_action == Some(Action::Help) {
+        game.help_system.toggle();
+        return InputResult::Continue;
     }
+    // Keys bound to menu actions stand in for the keys the screens read;
+    // letters are left alone, as the screens read their own
+    let key = match menu_action {
+        _ if matches!(key, KeyCode::Char(_)) => key,
+        Some(Action::MenuUp) => KeyCode::Up,
+        Some(Action::MenuDown) => KeyCode::Down,
+        Some(Action::Confirm) => KeyCode::Enter,
+        Some(Action::Back) => KeyCode::Esc,
+        _ => key,
+    };
     
     match game.scene {
         Scene::Title => handle_title_input(game, key),
@@ -172,10 +392,56 @@ fn handle_input(game: &mut GameState, key: KeyCode) -> InputResult {
         Scene::Lore => handle_lore_input(game, key),
         Scene::Milestone => handle_milestone_input(game, key),
         Scene::Upgrades => handle_upgrades_input(game, key),
+        Scene::Skills => handle_skills_input(game, key),
+        Scene::Finale => handle_finale_input(game, key),
+        Scene::Journal => handle_journal_input(game, key),
+        Scene::Codex => handle_codex_input(game, key),
+        Scene::ClueBoard => handle_clue_board_input(game, key),
+        Scene::Mailbox => handle_mailbox_input(game, key),
+        Scene::Ciphers => handle_ciphers_input(game, key),
+        Scene::Memories => handle_memories_input(game, key),
+        Scene::Themes => handle_themes_input(game, key),
+        Scene::KeyHeatmap => handle_key_heatmap_input(game, key),
+        Scene::Dashboard => handle_dashboard_input(game, key),
+        Scene::Settings => handle_settings_input(game, key),
+        Scene::Keybinds => handle_keybinds_input(game, key),
+        Scene::Pause => handle_pause_input(game, key),
+        Scene::Training => handle_training_input(game, key),
+        Scene::Ideology => {
+            if key == KeyCode::Enter {
+                game.scene = Scene::Dungeon;
+            }
+            InputResult::Continue
+        }
+        Scene::Encounter => handle_encounter_input(game, key),
+        Scene::Consequences => handle_consequences_input(game, key),
+        Scene::Challenges => handle_challenges_input(game, key),
         Scene::BattleSummary => handle_battle_summary_input(game, key),
+        Scene::Replay => handle_replay_input(game, key),
+        Scene::Calibration => handle_calibration_input(game, key),
+        Scene::LevelUp => handle_level_up_input(game, key),
+        Scene::RouteChoice => handle_route_choice_input(game, key),
+        Scene::Dream => handle_dream_input(game, key),
+        Scene::Conversation => handle_conversation_input(game, key),
     }
 }
 
+/// Handle input when the dev console is open
+fn handle_dev_console_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    match key {
+        KeyCode::Esc => game.dev_console.visible = false,
+        KeyCode::Enter => game.submit_dev_command(),
+        KeyCode::Backspace => {
+            game.dev_console.input.pop();
+        }
+        KeyCode::Up => game.dev_console.history_prev(),
+        KeyCode::Down => game.dev_console.history_next(),
+        KeyCode::Char(c) => game.dev_console.input.push(c),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
 /// Handle input when help overlay is open
 fn handle_help_input(game: &mut GameState, key: KeyCode) -> InputResult {
     match key {
@@ -212,13 +478,18 @@ fn handle_help_input(game: &mut GameState, key: KeyCode) -> InputResult {
 fn handle_title_input(game: &mut GameState, key: KeyCode) -> InputResult {
     match key {
         KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
-        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(5), // Now 5 items
+        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(7), // Now 7 items
         KeyCode::Enter => {
             match game.menu_index {
                 0 => {
-                    // New Game
-                    game.scene = Scene::ClassSelect;
-                    game.menu_index = 0;
+                    // New Game, by way of Beck's yard the first time
+                    game.clear_challenge();
+                    if game.tutorial_progress.should_show_tutorial() {
+                        game.open_training();
+                    } else {
+                        game.scene = Scene::ClassSelect;
+                        game.menu_index = 0;
+                    }
                 }
                 1 => {
                     // Tutorial
@@ -235,6 +506,15 @@ fn handle_title_input(game: &mut GameState, key: KeyCode) -> InputResult {
                     game.add_message("No save file found...");
                 }
                 4 => {
+                    // Consequences (world changes across the profile)
+                    game.scene = Scene::Consequences;
+                    game.menu_index = 0;
+                }
+                5 => {
+                    // Community challenges
+                    game.open_challenges();
+                }
+                6 => {
                     // Quit
                     return InputResult::Quit;
                 }
@@ -242,6 +522,7 @@ fn handle_title_input(game: &mut GameState, key: KeyCode) -> InputResult {
             }
         }
         KeyCode::Char('n') => {
+            game.clear_challenge();
             game.scene = Scene::ClassSelect;
             game.menu_index = 0;
         }
@@ -249,6 +530,22 @@ fn handle_title_input(game: &mut GameState, key: KeyCode) -> InputResult {
             game.scene = Scene::Upgrades;
             game.menu_index = 0;
         }
+        KeyCode::Char('w') => {
+            game.scene = Scene::Consequences;
+            game.menu_index = 0;
+        }
+        KeyCode::Char('x') => game.open_challenges(),
+        KeyCode::Char('l') => game.cycle_keyboard_layout(),
+        KeyCode::Char('c') => game.open_calibration(),
+        KeyCode::Char('m') => game.toggle_combat_mode(),
+        KeyCode::Char('v') => game.toggle_unreliable_narrator(),
+        KeyCode::Char('t') => game.open_themes(),
+        KeyCode::Char('s') => game.toggle_typing_markers(),
+        KeyCode::Char('a') => game.toggle_screen_reader(),
+        KeyCode::Char('p') => game.toggle_reduced_motion(),
+        KeyCode::Char('e') => game.open_key_heatmap(),
+        KeyCode::Char('d') => game.open_dashboard(Scene::Title),
+        KeyCode::Char('o') => game.open_settings(Scene::Title),
         KeyCode::Char('q') => return InputResult::Quit,
         _ => {}
     }
@@ -257,21 +554,20 @@ fn handle_title_input(game: &mut GameState, key: KeyCode) -> InputResult {
 
 fn handle_class_select_input(game: &mut GameState, key: KeyCode) -> InputResult {
     match key {
-        KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
-        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(5),
+        // A class-locked challenge keeps the cursor on its class
+        KeyCode::Up | KeyCode::Char('k') if game.locked_class().is_none() => game.move_menu_up(),
+        KeyCode::Down | KeyCode::Char('j') if game.locked_class().is_none() => game.move_menu_down(5),
         KeyCode::Enter => {
-            let class = match game.menu_index {
-                0 => Class::Wordsmith,
-                1 => Class::Scribe,
-                2 => Class::Spellweaver,
-                3 => Class::Barbarian,
-                4 => Class::Trickster,
-                _ => Class::Wordsmith,
-            };
+            let class = game.locked_class()
+                .unwrap_or_else(|| Class::ALL.get(game.menu_index).copied().unwrap_or(Class::Wordsmith));
             let player = Player::new("Hero".to_string(), class);
             game.start_new_game(player);
         }
+        KeyCode::Char('p') => game.cycle_patron(),
+        KeyCode::Char('a') => game.cycle_ascension(),
+        KeyCode::Char('s') => game.cycle_setting(),
         KeyCode::Esc => {
+            game.clear_challenge();
             game.scene = Scene::Title;
             game.menu_index = 0;
         }
@@ -281,84 +577,26 @@ fn handle_class_select_input(game: &mut GameState, key: KeyCode) -> InputResult
 }
 
 fn handle_dungeon_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    // Typing a destination or a room name on the map
+    if game.map_prompt.is_some() {
+        return handle_map_prompt_input(game, key);
+    }
+    
     match key {
-        KeyCode::Char('e') | KeyCode::Enter => {
-            // First check for pending lore discovery from previous room
-            if let Some(dungeon) = &game.dungeon {
-                if let Some(lore) = dungeon.pending_lore.clone() {
-                    if let Some(d) = &mut game.dungeon {
-                        d.pending_lore = None;
-                    }
-                    game.current_lore = Some(lore);
-                    game.scene = Scene::Lore;
-                    return InputResult::Continue;
-                }
-            }
-            
-            // Check for milestone events at special floors
-            let floor = game.get_current_floor();
-            if let Some(milestone) = get_floor_milestone(floor as u32) {
-                // Only show milestone once per floor (on first room) and if not already shown
-                if !game.milestones_shown.contains(&(floor as u32)) {
-                    if let Some(dungeon) = &game.dungeon {
-                        if dungeon.rooms_cleared == 0 && dungeon.current_room.room_type == RoomType::Start {
-                            game.milestones_shown.insert(floor as u32);
-                            game.current_milestone = Some(milestone.description);
-                            game.scene = Scene::Milestone;
-                            return InputResult::Continue;
-                        }
-                    }
-                }
+        KeyCode::Char('e') | KeyCode::Enter => explore(game),
+        // Pick which room ahead to take
+        KeyCode::Left | KeyCode::Right => {
+            if let Some(dungeon) = &mut game.dungeon {
+                dungeon.cycle_route(if key == KeyCode::Left { -1 } else { 1 });
             }
-
-            // Explore - go to next room
+        }
+        KeyCode::Char(c @ '1'..='9') => {
             if let Some(dungeon) = &mut game.dungeon {
-                let room = dungeon.generate_next_room();
-                match room.room_type {
-                    RoomType::Start => {
-                        // Starting room - just a message
-                        game.add_message("You enter the dungeon...");
-                    }
-                    RoomType::Combat => {
-                        let floor = game.get_current_floor();
-                        let enemy = Enemy::random_for_floor(floor);
-                        game.start_combat(enemy);
-                    }
-                    RoomType::Elite => {
-                        let floor = game.get_current_floor();
-                        let enemy = Enemy::random_elite(floor);
-                        game.start_combat(enemy);
-                    }
-                    RoomType::Boss => {
-                        let floor = game.get_current_floor();
-                        let enemy = Enemy::random_boss(floor);
-                        game.start_combat(enemy);
-                    }
-                    RoomType::Treasure => {
-                        // Give random item
-                        let item = game::items::Item::random_consumable();
-                        if let Some(player) = &mut game.player {
-                            player.inventory.push(item.clone());
-                            game.add_message(&format!("Found {}!", item.name));
-                        }
-                        game.end_treasure();
-                    }
-                    RoomType::Shop => {
-                        game.enter_shop();
-                    }
-                    RoomType::Rest => {
-                        game.enter_rest();
-                    }
-                    RoomType::Event => {
-                        // Use zone-specific events for more variety
-                        let floor = game.get_current_floor();
-                        let zone = FloorZone::from_floor(floor as u32);
-                        let event = generate_zone_event(zone);
-                        game.start_event(event);
-                    }
-                }
+                dungeon.pick_route(c as usize - '1' as usize);
             }
         }
+        KeyCode::Char('t') => game.open_map_prompt(MapPromptMode::Travel),
+        KeyCode::Char('n') => game.open_map_prompt(MapPromptMode::Name),
         KeyCode::Char('i') => {
             game.scene = Scene::Inventory;
             game.menu_index = 0;
@@ -366,24 +604,184 @@ fn handle_dungeon_input(game: &mut GameState, key: KeyCode) -> InputResult {
         KeyCode::Char('s') => {
             game.scene = Scene::Stats;
         }
+        KeyCode::Char('k') => {
+            game.scene = Scene::Skills;
+            game.menu_index = 0;
+        }
+        KeyCode::Char('j') => game.open_journal(),
+        KeyCode::Char('c') => game.open_codex(),
+        KeyCode::Char('b') => game.open_clue_board(),
+        KeyCode::Char('x') => game.open_cipher_notes(),
+        KeyCode::Char('m') => game.open_memories(),
         KeyCode::Char('q') => return InputResult::Quit,
         _ => {}
     }
     InputResult::Continue
 }
 
+/// Step into the room ahead (or show whatever is waiting first)
+fn explore(game: &mut GameState) {
+    // First check for pending lore discovery from previous room
+    if let Some(dungeon) = &game.dungeon {
+        if let Some(lore) = dungeon.pending_lore.clone() {
+            if let Some(d) = &mut game.dungeon {
+                d.pending_lore = None;
+            }
+            game.current_lore = Some(lore);
+            game.scene = Scene::Lore;
+            return;
+        }
+    }
+    
+    // Check for milestone events at special floors
+    let floor = game.get_current_floor();
+    if let Some(milestone) = get_floor_milestone(floor as u32) {
+        // Only show milestone once per floor (on first room) and if not already shown
+        if !game.milestones_shown.contains(&(floor as u32)) {
+            if let Some(dungeon) = &game.dungeon {
+                if dungeon.rooms_cleared == 0 && dungeon.current_room.room_type == RoomType::Start {
+                    game.milestones_shown.insert(floor as u32);
+                    game.current_milestone = Some(milestone.description);
+                    game.scene = Scene::Milestone;
+                    return;
+                }
+            }
+        }
+    }
+
+    // Late in the run, it shows you what your choices have made of you
+    if game.reveal_ideology() {
+        return;
+    }
+
+    // Explore - go to next room
+    if let Some(dungeon) = &mut game.dungeon {
+        let room = dungeon.generate_next_room();
+        game.note_room_entered();
+        match room.room_type {
+            RoomType::Start => {
+                // Starting room - just a message
+                game.add_message("You enter the dungeon...");
+            }
+            RoomType::Combat => {
+                let floor = game.get_current_floor();
+                let enemy = game.foretold_fight()
+                    .unwrap_or_else(|| Enemy::random_for_floor_data(&game.game_data, floor, game.enemy_director()));
+                game.start_combat(enemy);
+            }
+            RoomType::Elite => {
+                // Each zone keeps its own elite, unless a false prophecy
+                // named it for this floor
+                let floor = game.get_current_floor();
+                let elite = FloorZone::from_floor(floor as u32).elite_id();
+                let elite = game.dungeon.as_ref().map_or(elite, |d| prophecies::elite_for(&d.prophecies, floor, elite));
+                let enemy = Enemy::zone_elite_data(&game.game_data, elite, floor, game.enemy_director());
+                game.start_combat(enemy);
+            }
+            RoomType::Boss => {
+                let floor = game.get_current_floor();
+                let enemy = Enemy::random_boss(floor);
+                game.start_combat(enemy);
+            }
+            RoomType::Treasure => {
+                // A lore artifact, cursed loot on offer, or else a random item
+                if let Some(message) = game.find_artifact()
+                    .or_else(|| game.find_cipher_note())
+                    .or_else(|| game.find_prophecy())
+                {
+                    game.add_message(&message);
+                    game.note_clues();
+                } else if game.open_corrupted_chest() {
+                    // The chest's choice clears the room
+                    return;
+                } else {
+                    let item = game::items::Item::random_consumable(&mut run_rng::stream(Stream::Map));
+                    if let Some(player) = &mut game.player {
+                        player.inventory.push(item.clone());
+                        game.add_message(&format!("Found {}!", item.name));
+                    }
+                }
+                game.end_treasure();
+            }
+            RoomType::Shop => {
+                game.enter_shop();
+            }
+            RoomType::Rest => {
+                game.enter_rest();
+            }
+            RoomType::Event | RoomType::Mystery => {
+                // An authored encounter waiting here takes the room
+                if game.try_trigger_encounter() {
+                    return;
+                }
+                // Otherwise use zone-specific events for more variety
+                let floor = game.get_current_floor();
+                let zone = FloorZone::from_floor(floor as u32);
+                let event = generate_zone_event(zone, &mut run_rng::stream(Stream::Map));
+                game.start_event(event);
+            }
+        }
+    }
+}
+
+fn handle_map_prompt_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let Some(prompt) = &mut game.map_prompt else {
+        return InputResult::Continue;
+    };
+    match key {
+        KeyCode::Esc => game.map_prompt = None,
+        KeyCode::Enter => {
+            let onward = game.submit_map_prompt();
+            if onward {
+                explore(game);
+            }
+        }
+        KeyCode::Backspace => {
+            prompt.input.pop();
+        }
+        KeyCode::Char(c) => prompt.input.push(c),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
 fn handle_combat_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let action = key_name(key).and_then(|name| game.keybinds.action(Context::Combat, &name));
     if let Some(combat) = &mut game.combat_state {
         match key {
-            // Tab toggles spell mode
-            KeyCode::Tab => {
+            // Spell mode, on and off
+            _ if action == Some(Action::SpellMode) => {
                 combat.toggle_spell_mode();
                 if combat.spell_mode {
-                    game.add_message("󰊠 SPELL MODE - Press 1-9 to select a spell, Tab to cancel");
+                    let keys = game.keybinds.label(Action::SpellMode);
+                    game.add_message(&format!("󰊠 SPELL MODE - Press 1-9 to select a spell, {} to cancel", keys));
                 } else {
                     game.add_message("Normal attack mode");
                 }
             }
+            // Enter on the command line uses the typed ability or consumable
+            KeyCode::Enter if combat.ability_input.is_some() => {
+                game.submit_combat_command();
+                if game.combat_state.as_ref().is_some_and(|c| matches!(c.phase, CombatPhase::Victory | CombatPhase::Spared)) {
+                    game.end_combat(true);
+                    game.check_victory();
+                }
+            }
+            KeyCode::Esc if combat.ability_input.is_some() => combat.cancel_ability(),
+            // Before typing, trade the word for a full-sentence heavy strike
+            _ if action == Some(Action::HeavyStrike) => {
+                let charged = combat.charge_heavy_strike();
+                if charged {
+                    game.add_message("Heavy strike charged - type the full sentence!");
+                }
+            }
+            // Switch stance between words
+            _ if action == Some(Action::Stance) => {
+                combat.cycle_stance();
+            }
+            // Replay the spoken prompt (pronunciation / dictation)
+            _ if action == Some(Action::ReplayPrompt) => game.pronouncer.replay(),
+            _ if action == Some(Action::Pause) => game.pause_fight(),
             // Number keys select spells when in spell mode
             KeyCode::Char(n) if combat.spell_mode && n.is_ascii_digit() && n != '0' => {
                 let spell_idx = (n as u8 - b'1') as usize;
@@ -398,9 +796,14 @@ fn handle_combat_input(game: &mut GameState, key: KeyCode) -> InputResult {
                     }
                 }
             }
-            KeyCode::Esc => {
+            _ if action == Some(Action::Flee) => {
                 // Flee attempt
-                if combat.try_flee() {
+                if combat.try_flee() && game.training.is_some() {
+                    game.check_lesson();
+                } else if combat.phase == CombatPhase::Fled {
+                    let enemy = combat.enemy.name.clone();
+                    let floor = game.get_current_floor() as u32;
+                    game.run_chronicle.record(RunEvent::Fled { enemy, floor });
                     game.add_message("You fled successfully!");
                     game.combat_state = None;
                     game.current_enemy = None;
@@ -418,28 +821,36 @@ fn handle_combat_input(game: &mut GameState, key: KeyCode) -> InputResult {
             }
             KeyCode::Char(c) => {
                 // Track state before typing for typing_feel updates
-                let word_before = combat.current_word.clone();
-                let typed_len_before = combat.typed_input.len();
-                let word_was_complete = combat.typed_input == combat.current_word;
+                let word_before = combat.target().to_string();
+                let typed_len_before = grapheme_len(&combat.typed_input);
+                let word_was_complete = text_matches(&word_before, &combat.typed_input);
                 
                 // Track enemy HP BEFORE typing (damage is applied in on_char_typed -> on_word_complete)
                 let enemy_hp_before = combat.enemy.current_hp;
                 
+                // Flow bonus/penalty applies to the word this keystroke may finish
+                combat.flow_damage_mult = game.typing_feel.flow_state.damage_multiplier();
+                
                 // Typing input
+                combat.key_read_at = Some(game.key_read_at);
                 combat.on_char_typed(c);
+                let keystroke = combat.immersive.as_mut().and_then(|imm| imm.last_keystroke_feedback.take());
                 
                 // Update typing feel system
-                let typed_len_after = combat.typed_input.len();
+                let typed_len_after = grapheme_len(&combat.typed_input);
                 if typed_len_after > typed_len_before {
                     // A character was accepted
                     let char_index = typed_len_after - 1;
-                    let expected = word_before.chars().nth(char_index).unwrap_or(' ');
-                    let is_correct = c == expected;
+                    let expected = graphemes(&word_before).get(char_index)
+                        .and_then(|g| g.chars().next())
+                        .unwrap_or(' ');
+                    let is_correct = last_grapheme_correct(&word_before, &combat.typed_input);
                     game.typing_feel.on_keystroke(is_correct, char_index, expected, c);
+                    game.key_accuracy.record(game.config.typing.layout, expected, is_correct);
                 }
                 
                 // Check if word completed
-                if combat.typed_input == combat.current_word && !word_was_complete {
+                if text_matches(combat.target(), &combat.typed_input) && !word_was_complete {
                     game.total_words_typed += 1;
                     
                     // Update typing feel with word completion
@@ -454,6 +865,7 @@ fn handle_combat_input(game: &mut GameState, key: KeyCode) -> InputResult {
                     
                     // Calculate damage dealt (using tracked hp from before on_char_typed)
                     let damage_dealt = (enemy_hp_before - combat.enemy.current_hp).max(0);
+                    let crit = combat.last_crit;
                     let current_combo = combat.combo;
                     
                     // Handle spell casting if in spell mode
@@ -480,12 +892,15 @@ fn handle_combat_input(game: &mut GameState, key: KeyCode) -> InputResult {
                     
                     // Trigger visual effects for player attack (deferred to here where borrow is released)
                     if damage_dealt > 0 {
-                        game.effect_player_damage(damage_dealt, false);
+                        game.effect_player_damage(damage_dealt, crit);
                     }
                     if current_combo > 1 {
                         game.effect_combo(current_combo);
                     }
                 }
+                if let Some(keystroke) = keystroke {
+                    game.effect_keystroke(&keystroke);
+                }
             }
             KeyCode::Backspace => {
                 combat.on_backspace();
@@ -505,26 +920,13 @@ fn handle_combat_input(game: &mut GameState, key: KeyCode) -> InputResult {
 }
 
 fn handle_shop_input(game: &mut GameState, key: KeyCode) -> InputResult {
-    let item_count = game.shop_items.len();
+    let item_count = game.shop.offers.len();
     match key {
         KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
         KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(item_count),
         KeyCode::Enter => {
-            if game.menu_index < game.shop_items.len() {
-                let item = game.shop_items[game.menu_index].clone();
-                if let Some(player) = &mut game.player {
-                    if player.gold >= item.price as u64 {
-                        player.gold -= item.price as u64;
-                        player.inventory.push(item.clone());
-                        game.add_message(&format!("Bought {}!", item.name));
-                        game.shop_items.remove(game.menu_index);
-                        if game.menu_index > 0 && game.menu_index >= game.shop_items.len() {
-                            game.menu_index = game.shop_items.len().saturating_sub(1);
-                        }
-                    } else {
-                        game.add_message("Not enough gold!");
-                    }
-                }
+            if let Some(message) = game.buy_offer(game.menu_index) {
+                game.add_message(&message);
             }
         }
         KeyCode::Esc => {
@@ -537,29 +939,38 @@ fn handle_shop_input(game: &mut GameState, key: KeyCode) -> InputResult {
 }
 
 fn handle_rest_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let companions = game.companions.present().len();
     match key {
         KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
-        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(4),
-        KeyCode::Enter | KeyCode::Char('1') | KeyCode::Char('2') | KeyCode::Char('3') => {
+        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(3 + companions),
+        KeyCode::Enter | KeyCode::Char('1'..='9') => {
             let choice = match key {
-                KeyCode::Char('1') => 0,
-                KeyCode::Char('2') => 1,
-                KeyCode::Char('3') => 2,
+                KeyCode::Char(c) => c as usize - '1' as usize,
                 _ => game.menu_index,
             };
+            if choice >= 3 + companions {
+                return InputResult::Continue;
+            }
             
-            if let Some(player) = &mut game.player {
+            if choice >= 3 {
+                game.use_companion_service(choice - 3);
+            } else if let Some(player) = &mut game.player {
                 match choice {
                     0 => {
                         // Rest - heal 30% HP
                         let heal_amount = (player.max_hp as f32 * 0.3) as i32;
                         player.heal(heal_amount);
                         game.add_message(&format!("Rested and recovered {} HP!", heal_amount));
+                        // Sleep may bring a dream before you move on
+                        if game.try_dream() {
+                            game.menu_index = 0;
+                            return InputResult::Continue;
+                        }
                     }
                     1 => {
                         // Train - gain XP
                         let xp = 20 + (player.level * 5) as u64;
-                        player.gain_experience(xp);
+                        game.grant_experience(xp);
                         game.add_message(&format!("Training complete! Gained {} XP.", xp));
                     }
                     2 => {
@@ -610,6 +1021,101 @@ fn handle_event_input(game: &mut GameState, key: KeyCode) -> InputResult {
     InputResult::Continue
 }
 
+fn handle_encounter_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let Some(run) = &mut game.encounter_run else {
+        return InputResult::Continue;
+    };
+    let choice_count = run.encounter.choices.len();
+    match &mut run.stage {
+        EncounterStage::Reading => {
+            match key {
+                KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
+                KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(choice_count),
+                KeyCode::Char(c @ '1'..='9') => game.choose_encounter(c as usize - '1' as usize),
+                KeyCode::Enter => game.choose_encounter(game.menu_index),
+                KeyCode::Char('t') => game.start_conversation(),
+                _ => {}
+            }
+        }
+        EncounterStage::Typing { typed, .. } => match key {
+            KeyCode::Char(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => game.submit_encounter_typing(),
+            KeyCode::Esc => run.stage = EncounterStage::Reading,
+            _ => {}
+        },
+        EncounterStage::Outcome { .. } => {
+            if key == KeyCode::Enter {
+                game.finish_encounter();
+                game.menu_index = 0;
+            }
+        }
+    }
+    InputResult::Continue
+}
+
+fn handle_conversation_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let Some(runner) = &mut game.conversation else {
+        return InputResult::Continue;
+    };
+    if let Some(check) = &mut runner.typing {
+        match key {
+            KeyCode::Char(c) => check.typed.push(c),
+            KeyCode::Backspace => {
+                check.typed.pop();
+            }
+            KeyCode::Enter => game.submit_dialogue_typing(),
+            KeyCode::Esc => runner.cancel_typing(),
+            _ => {}
+        }
+        return InputResult::Continue;
+    }
+    let has_options = runner.node().is_some_and(|n| !n.options.is_empty()) && !runner.ended;
+    match key {
+        KeyCode::Esc => game.leave_conversation(),
+        KeyCode::Up | KeyCode::Char('k') if has_options => game.move_menu_up(),
+        KeyCode::Down | KeyCode::Char('j') if has_options => {
+            let count = game.dialogue_options().len();
+            game.move_menu_down(count);
+        }
+        KeyCode::Char(c @ '1'..='9') if has_options => game.choose_dialogue(c as usize - '1' as usize),
+        KeyCode::Enter if has_options => game.choose_dialogue(game.menu_index),
+        KeyCode::Enter | KeyCode::Char(' ') => game.advance_dialogue(),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_dream_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let Some(run) = &mut game.dream_run else {
+        return InputResult::Continue;
+    };
+    match &mut run.stage {
+        DreamStage::Dreaming => match key {
+            KeyCode::Enter => game.begin_dream_typing(),
+            KeyCode::Esc => game.finish_dream(),
+            _ => {}
+        },
+        DreamStage::Typing { typed, .. } => match key {
+            KeyCode::Char(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => game.wake_from_dream(),
+            KeyCode::Esc => game.finish_dream(),
+            _ => {}
+        },
+        DreamStage::Waking { .. } => {
+            if key == KeyCode::Enter {
+                game.finish_dream();
+            }
+        }
+    }
+    InputResult::Continue
+}
+
 fn apply_event_outcome(game: &mut GameState, outcome: game::events::EventOutcome) {
     use game::events::EventOutcome;
     
@@ -632,7 +1138,7 @@ fn apply_event_outcome(game: &mut GameState, outcome: game::events::EventOutcome
                 game.add_message(&format!("Lost {} HP!", amount));
             }
             EventOutcome::GainXP(amount) => {
-                player.gain_experience(amount as u64);
+                game.grant_experience(amount as u64);
                 game.add_message(&format!("Gained {} XP!", amount));
             }
             EventOutcome::GainMaxHP(amount) => {
@@ -641,7 +1147,7 @@ fn apply_event_outcome(game: &mut GameState, outcome: game::events::EventOutcome
                 game.add_message(&format!("Max HP increased by {}!", amount));
             }
             EventOutcome::GainItem => {
-                let item = game::items::Item::random_consumable();
+                let item = game::items::Item::random_consumable(&mut run_rng::stream(Stream::Map));
                 player.inventory.push(item.clone());
                 game.add_message(&format!("Found {}!", item.name));
             }
@@ -653,6 +1159,11 @@ fn apply_event_outcome(game: &mut GameState, outcome: game::events::EventOutcome
                 let enemy = Enemy::random_for_floor(floor);
                 game.start_combat(enemy);
             }
+            EventOutcome::TakeCurse(curse) => {
+                let message = format!("☠ You take the {} ({}).", curse.name, curse.describe());
+                player.curses.push(curse);
+                game.add_message(&message);
+            }
             EventOutcome::FactionRep(faction, amount) => {
                 game.faction_relations.modify_standing(faction, amount);
                 let status = game.faction_relations.status(&faction);
@@ -677,12 +1188,18 @@ fn handle_inventory_input(game: &mut GameState, key: KeyCode) -> InputResult {
         KeyCode::Enter => {
             let mut message = None;
             let mut new_menu_index = None;
+            let mut gear = None;
             
             if let Some(player) = &mut game.player {
                 if game.menu_index < player.inventory.len() {
                     let item = player.inventory.remove(game.menu_index);
                     // Apply item effect
                     match &item.effect {
+                        game::items::ItemEffect::Gear(id) => gear = Some(id.clone()),
+                        game::items::ItemEffect::Combat(_) => {
+                            message = Some(format!("Type /{} during a fight to use it.", item.name.to_lowercase()));
+                            player.inventory.insert(game.menu_index, item.clone());
+                        }
                         game::items::ItemEffect::HealHP(amount) => {
                             player.heal(*amount);
                             message = Some(format!("Used {}! Restored {} HP.", item.name, amount));
@@ -701,6 +1218,9 @@ fn handle_inventory_input(game: &mut GameState, key: KeyCode) -> InputResult {
                 }
             }
             
+            if let Some(id) = gear {
+                message = Some(game.equip_gear(&id));
+            }
             if let Some(msg) = message {
                 game.add_message(&msg);
             }
@@ -722,6 +1242,70 @@ fn handle_stats_input(game: &mut GameState, key: KeyCode) -> InputResult {
         KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
             game.scene = Scene::Dungeon;
         }
+        KeyCode::Char('d') => game.open_dashboard(Scene::Stats),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_settings_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
+        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(Setting::ALL.len()),
+        KeyCode::Right | KeyCode::Enter => game.adjust_setting(true),
+        KeyCode::Left => game.adjust_setting(false),
+        KeyCode::Char('u') => game.revert_settings(),
+        KeyCode::Char('b') => game.open_keybinds(),
+        KeyCode::Esc | KeyCode::Char('q') => game.close_settings(),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_keybinds_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
+        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(Action::ALL.len()),
+        KeyCode::Enter => game.begin_keybind_capture(false),
+        KeyCode::Char('a') => game.begin_keybind_capture(true),
+        KeyCode::Char('d') => game.reset_keybind(),
+        KeyCode::Esc | KeyCode::Char('q') => game.close_keybinds(),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_pause_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    // Abandoning asks twice; any other key takes it back
+    if game.abandon_confirm {
+        match key {
+            KeyCode::Enter => game.abandon_run(),
+            _ => game.abandon_confirm = false,
+        }
+        return InputResult::Continue;
+    }
+    let action = key_name(key).and_then(|name| game.keybinds.action(Context::Combat, &name));
+    match key {
+        _ if action == Some(Action::Pause) => game.resume_fight(),
+        KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
+        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(PauseOption::ALL.len()),
+        KeyCode::Enter => match PauseOption::ALL.get(game.menu_index) {
+            Some(PauseOption::Resume) => game.resume_fight(),
+            Some(PauseOption::Settings) => game.open_settings(Scene::Pause),
+            Some(PauseOption::Overview) => game.open_dashboard(Scene::Pause),
+            Some(PauseOption::Abandon) => game.abandon_confirm = true,
+            None => {}
+        },
+        KeyCode::Esc | KeyCode::Char('q') => game.resume_fight(),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_dashboard_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    match key {
+        KeyCode::Tab => game.dashboard_all_runs = !game.dashboard_all_runs,
+        KeyCode::Esc | KeyCode::Char('q') => game.scene = game.dashboard_return,
         _ => {}
     }
     InputResult::Continue
@@ -744,6 +1328,7 @@ fn handle_game_over_input(game: &mut GameState, key: KeyCode) -> InputResult {
 
 fn handle_victory_input(game: &mut GameState, key: KeyCode) -> InputResult {
     match key {
+        KeyCode::Char('e') => game.descend_endless(),
         KeyCode::Char('n') => {
             // New Game+
             *game = GameState::new();
@@ -757,8 +1342,19 @@ fn handle_victory_input(game: &mut GameState, key: KeyCode) -> InputResult {
     InputResult::Continue
 }
 
+/// Page through an ending's final sequence; Esc skips to the victory screen
+fn handle_finale_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    match key {
+        KeyCode::Enter | KeyCode::Char(' ') => game.advance_finale(),
+        KeyCode::Esc => game.scene = Scene::Victory,
+        _ => {}
+    }
+    InputResult::Continue
+}
+
 fn handle_battle_summary_input(game: &mut GameState, key: KeyCode) -> InputResult {
     match key {
+        KeyCode::Char('r') if game.open_replay() => {}
         _ => {
             // Any key dismisses the battle summary
             game.current_battle_summary = None;
@@ -768,13 +1364,103 @@ fn handle_battle_summary_input(game: &mut GameState, key: KeyCode) -> InputResul
     InputResult::Continue
 }
 
+/// Pick what a new level buys
+fn handle_level_up_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let choices = LevelUpChoice::ALL;
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => game.menu_index = game.menu_index.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => game.menu_index = (game.menu_index + 1).min(choices.len() - 1),
+        KeyCode::Char(c @ '1'..='4') => game.choose_level_up(choices[c as usize - '1' as usize]),
+        KeyCode::Enter => game.choose_level_up(choices[game.menu_index.min(choices.len() - 1)]),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+/// Pick which zone the stairs lead down into
+fn handle_route_choice_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let count = game.route_fork.len().max(1);
+    let picked = match key {
+        KeyCode::Up | KeyCode::Left | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+            None
+        }
+        KeyCode::Down | KeyCode::Right | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(count - 1);
+            None
+        }
+        KeyCode::Char(c @ '1'..='9') => game.route_fork.get(c as usize - '1' as usize).copied(),
+        KeyCode::Enter => game.route_fork.get(game.menu_index).copied(),
+        _ => None,
+    };
+    if let Some(zone) = picked {
+        game.choose_route(zone);
+        game.menu_index = 0;
+    }
+    InputResult::Continue
+}
+
+fn handle_replay_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let Some(viewer) = &mut game.replay_viewer else {
+        game.scene = Scene::BattleSummary;
+        return InputResult::Continue;
+    };
+    match key {
+        KeyCode::Char(' ') => viewer.toggle_pause(),
+        KeyCode::Char('s') => viewer.cycle_speed(),
+        KeyCode::Left => viewer.skip_word(false),
+        KeyCode::Right => viewer.skip_word(true),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.replay_viewer = None;
+            game.scene = Scene::BattleSummary;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_calibration_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let Some(calibration) = &mut game.calibration else {
+        game.scene = Scene::Title;
+        return InputResult::Continue;
+    };
+    match key {
+        KeyCode::Char(' ') => calibration.on_tap(game.key_read_at),
+        KeyCode::Enter if calibration.is_done() => game.save_calibration(),
+        KeyCode::Char('r') => game.open_calibration(),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.calibration = None;
+            game.scene = Scene::Title;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
 /// Handle lore discovery popup - any key dismisses
-fn handle_lore_input(game: &mut GameState, _key: KeyCode) -> InputResult {
-    // Save the lore to discovered list
-    if let Some(lore) = game.current_lore.take() {
-        game.discovered_lore.push(lore);
+fn handle_lore_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    // Answering a comprehension question about the passage
+    if let Some(check) = &mut game.comprehension {
+        if check.is_answered() {
+            game.finish_lore();
+            return InputResult::Continue;
+        }
+        match key {
+            KeyCode::Esc => game.finish_lore(),
+            KeyCode::Enter => game.submit_comprehension(),
+            KeyCode::Backspace => {
+                check.input.pop();
+            }
+            KeyCode::Char(c) => check.input.push(c),
+            _ => {}
+        }
+        return InputResult::Continue;
+    }
+    
+    // Done reading - quiz the player if this fragment has a question
+    if !game.begin_comprehension() {
+        game.finish_lore();
     }
-    game.scene = Scene::Dungeon;
     InputResult::Continue
 }
 
@@ -828,6 +1514,305 @@ fn handle_upgrades_input(game: &mut GameState, key: KeyCode) -> InputResult {
     InputResult::Continue
 }
 
+fn handle_skills_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let max_index = game.skill_tree.discipline().len().saturating_sub(1);
+    
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+        }
+        KeyCode::Enter => {
+            let message = game.unlock_discipline(game.menu_index);
+            game.add_message(&message);
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.scene = Scene::Dungeon;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_journal_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let max_index = game.journal.chains.len().saturating_sub(1);
+    
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.scene = Scene::Dungeon;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_codex_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let max_index = game.codex_page().len().saturating_sub(1);
+
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+        }
+        KeyCode::Left | KeyCode::BackTab => {
+            game.codex_section = game.codex_section.cycle(-1);
+            game.menu_index = 0;
+        }
+        KeyCode::Right | KeyCode::Tab => {
+            game.codex_section = game.codex_section.cycle(1);
+            game.menu_index = 0;
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.scene = Scene::Dungeon;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_clue_board_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    if let Some(typed) = &mut game.reflection {
+        match key {
+            KeyCode::Char(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => game.submit_reflection(),
+            KeyCode::Esc => game.reflection = None,
+            _ => {}
+        }
+        return InputResult::Continue;
+    }
+    let max_index = game.clue_board.pinned.len().saturating_sub(1);
+
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+        }
+        KeyCode::Enter => game.begin_reflection(),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.scene = Scene::Dungeon;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_mailbox_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    if let Some(typed) = &mut game.decoding {
+        match key {
+            KeyCode::Char(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => game.submit_decoding(),
+            KeyCode::Esc => game.decoding = None,
+            _ => {}
+        }
+        return InputResult::Continue;
+    }
+    let max_index = game.mailbox.len().saturating_sub(1);
+
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+        }
+        KeyCode::Enter => game.begin_decoding(),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.scene = Scene::Dungeon;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_ciphers_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    if let Some(typed) = &mut game.cipher_guess {
+        match key {
+            KeyCode::Char(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => game.submit_cipher_guess(),
+            KeyCode::Esc => game.cipher_guess = None,
+            _ => {}
+        }
+        return InputResult::Continue;
+    }
+    let max_index = game.cipher_notes.len().saturating_sub(1);
+
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+        }
+        KeyCode::Enter => game.begin_cipher_guess(),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.scene = Scene::Dungeon;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_transcript_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let filters = game.transcript.filters();
+    let Some(view) = &mut game.transcript_view else {
+        return InputResult::Continue;
+    };
+    let filter = view.filter.min(filters.len() - 1);
+    let max_scroll = game.transcript.lines(&filters[filter]).count().saturating_sub(1);
+
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => view.scroll = (view.scroll + 1).min(max_scroll),
+        KeyCode::Down | KeyCode::Char('j') => view.scroll = view.scroll.saturating_sub(1),
+        KeyCode::PageUp => view.scroll = (view.scroll + 10).min(max_scroll),
+        KeyCode::PageDown => view.scroll = view.scroll.saturating_sub(10),
+        KeyCode::End => view.scroll = 0,
+        KeyCode::Left | KeyCode::Right | KeyCode::Tab => {
+            view.filter = if key == KeyCode::Left {
+                (filter + filters.len() - 1) % filters.len()
+            } else {
+                (filter + 1) % filters.len()
+            };
+            view.scroll = 0;
+        }
+        KeyCode::Esc | KeyCode::Char('q') => game.transcript_view = None,
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_memories_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let max_index = memories::MEMORIES.len().saturating_sub(1);
+
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+            game.memory_replay = None;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+            game.memory_replay = None;
+        }
+        KeyCode::Enter => game.replay_memory(),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            game.scene = Scene::Dungeon;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_themes_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let max_index = game.themes.len().saturating_sub(1);
+
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+            game.preview_theme();
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+            game.preview_theme();
+        }
+        KeyCode::Enter => game.choose_theme(),
+        KeyCode::Char('e') => game.export_theme(),
+        KeyCode::Esc | KeyCode::Char('q') => game.cancel_themes(),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_key_heatmap_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    match key {
+        KeyCode::Char('l') => game.cycle_keyboard_layout(),
+        KeyCode::Esc | KeyCode::Char('q') => game.scene = Scene::Title,
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_consequences_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    if game.timeline_confirm {
+        match key {
+            KeyCode::Char('y') | KeyCode::Char('Y') => game.start_fresh_timeline(),
+            _ => game.timeline_confirm = false,
+        }
+        return InputResult::Continue;
+    }
+
+    // menu_index doubles as the scroll offset
+    let max_index = game.world_state.ledger.changes.len();
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            game.menu_index = game.menu_index.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            game.menu_index = (game.menu_index + 1).min(max_index);
+        }
+        KeyCode::Char('f') => game.timeline_confirm = true,
+        KeyCode::Esc => {
+            game.scene = Scene::Title;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_challenges_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => game.move_menu_up(),
+        KeyCode::Down | KeyCode::Char('j') => game.move_menu_down(game.challenges.len()),
+        KeyCode::Enter => game.start_challenge(game.menu_index),
+        KeyCode::Esc => {
+            game.scene = Scene::Title;
+            game.menu_index = 0;
+        }
+        _ => {}
+    }
+    InputResult::Continue
+}
+
+fn handle_training_input(game: &mut GameState, key: KeyCode) -> InputResult {
+    let done = game.training.as_ref().is_none_or(|t| t.is_done());
+    match key {
+        KeyCode::Enter if done => game.finish_training(),
+        KeyCode::Enter => game.begin_lesson(),
+        KeyCode::Char('s') if !done => game.skip_lesson(),
+        // Veterans can leave the yard at any point
+        KeyCode::Esc => game.finish_training(),
+        _ => {}
+    }
+    InputResult::Continue
+}
+
 fn handle_tutorial_input(game: &mut GameState, key: KeyCode) -> InputResult {
     match key {
         KeyCode::Esc => {
@@ -836,15 +1821,11 @@ fn handle_tutorial_input(game: &mut GameState, key: KeyCode) -> InputResult {
             game.menu_index = 0;
         }
         KeyCode::Enter => {
-            // Advance to next step or complete tutorial
+            // Advance to next step, or on to Beck's yard
             if game.tutorial_state.is_step_complete() {
                 let completed = game.tutorial_state.advance();
                 if completed {
-                    // Tutorial complete, save progress and start the game
-                    game.tutorial_progress.mark_completed();
-                    game.tutorial_progress.save();
-                    game.scene = Scene::ClassSelect;
-                    game.menu_index = 0;
+                    game.open_training();
                 }
             }
         }
@@ -852,10 +1833,7 @@ fn handle_tutorial_input(game: &mut GameState, key: KeyCode) -> InputResult {
             // Skip current step (for experienced players)
             let completed = game.tutorial_state.advance();
             if completed {
-                game.tutorial_progress.mark_completed();
-                game.tutorial_progress.save();
-                game.scene = Scene::ClassSelect;
-                game.menu_index = 0;
+                game.open_training();
             }
         }
         KeyCode::Char(c) => {
@@ -869,3 +1847,166 @@ fn handle_tutorial_input(game: &mut GameState, key: KeyCode) -> InputResult {
     }
     InputResult::Continue
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::combat::{CombatResult, CombatState};
+    use game::encounter_runner::EncounterRun;
+    use game::narrative::Faction;
+    use game::stances::Stance;
+
+    #[test]
+    fn test_help_key_types_into_encounter_answer() {
+        let mut game = GameState::new();
+        let encounter = game.encounters.values().next().cloned().expect("authored encounters");
+        let mut run = EncounterRun::new(encounter);
+        run.stage = EncounterStage::Typing { choice: 0, prompt: "hearth".to_string(), typed: String::new() };
+        game.encounter_run = Some(run);
+        game.scene = Scene::Encounter;
+
+        handle_input(&mut game, KeyCode::Char('h'));
+        handle_input(&mut game, KeyCode::Char('?'));
+
+        assert!(!game.help_system.visible);
+        let Some(EncounterStage::Typing { typed, .. }) = game.encounter_run.as_ref().map(|r| &r.stage) else {
+            panic!("left the typing stage");
+        };
+        assert_eq!(typed, "h?");
+    }
+
+    #[test]
+    fn test_a_riposte_window_outlasts_a_focus_loss_hold() {
+        let mut game = GameState::new();
+        game.combat_state = Some(CombatState::new(Enemy::random_for_floor(1), game.game_data.clone(), 1, 1, None, None));
+        game.scene = Scene::Combat;
+        let start = Instant::now();
+        game.combat_state.as_mut().unwrap().riposte_until = Some(start + Duration::from_secs(2));
+
+        game.auto_pause.pause(PauseReason::FocusLost);
+        game.hold_fight(start);
+        game.hold_fight(start + Duration::from_secs(5));
+        game.auto_pause.clear();
+        let resumed = start + Duration::from_secs(10);
+        game.release_fight(resumed);
+
+        let combat = game.combat_state.as_ref().unwrap();
+        assert_eq!(combat.riposte_until, Some(resumed + Duration::from_secs(2)));
+        assert!(game.held.is_none());
+    }
+
+    #[test]
+    fn test_a_remembered_standing_does_not_stack_run_over_run() {
+        let mut game = GameState::new();
+        game.world_state.set_flag("shadowwriter_contact");
+        game.start_new_game(Player::new("Hero".to_string(), Class::Wordsmith));
+        let first = game.faction_relations.standing(&Faction::ShadowGuild);
+        game.start_new_game(Player::new("Hero".to_string(), Class::Wordsmith));
+        assert_eq!(game.faction_relations.standing(&Faction::ShadowGuild), first);
+        assert!(first >= 10);
+    }
+
+    #[test]
+    fn test_an_execution_pays_its_bonus_once() {
+        let mut game = GameState::new();
+        game.player = Some(Player::new("Hero".to_string(), Class::Wordsmith));
+        let mut enemy = Enemy::random_for_floor(1);
+        enemy.gold_reward = 20;
+        game.current_enemy = Some(enemy.clone());
+        let mut combat = CombatState::new(enemy, game.game_data.clone(), 1, 1, None, None);
+        combat.executed = true;
+        combat.phase = CombatPhase::Victory;
+        // What finalizing an execution of a 20-gold enemy records
+        combat.result = Some(CombatResult {
+            victory: true,
+            fled: false,
+            spared: false,
+            xp_gained: 15,
+            gold_gained: 30,
+            turns_taken: 1,
+            max_combo: 1,
+            accuracy: 1.0,
+            avg_wpm: 60.0,
+        });
+        game.combat_state = Some(combat);
+        let gold = game.player.as_ref().unwrap().gold;
+
+        game.end_combat(true);
+        assert_eq!(game.player.as_ref().unwrap().gold - gold, 30);
+    }
+
+    #[test]
+    fn test_recoil_from_the_killing_word_still_lands() {
+        let mut game = GameState::new();
+        game.player = Some(Player::new("Hero".to_string(), Class::Wordsmith));
+        let enemy = Enemy::random_for_floor(1);
+        game.current_enemy = Some(enemy.clone());
+        let mut combat = CombatState::new(enemy, game.game_data.clone(), 1, 1, None, None);
+        combat.disable_aiming();
+        combat.stance = Stance::Aggressive;
+        combat.enemy.current_hp = 1;
+        combat.on_char_typed('#');
+        combat.on_backspace();
+        for c in combat.current_word.clone().chars() {
+            combat.on_char_typed(c);
+        }
+        assert_eq!(combat.phase, CombatPhase::Victory);
+        game.combat_state = Some(combat);
+        let hp = game.player.as_ref().unwrap().hp;
+
+        game.end_combat(true);
+        assert!(game.player.as_ref().unwrap().hp < hp);
+    }
+
+    #[test]
+    fn test_a_class_locked_challenge_goes_through_the_class_screen_without_unlocks() {
+        let mut game = GameState::new();
+        game.meta_progress.unlocks.starting_hp_bonus = 20;
+        let challenge = game::challenges::daily(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        let class = challenge.class_lock.expect("the daily locks a class");
+        game.challenges = vec![challenge];
+        game.start_challenge(0);
+        assert_eq!(game.scene, Scene::ClassSelect);
+
+        handle_class_select_input(&mut game, KeyCode::Char('p'));
+        handle_class_select_input(&mut game, KeyCode::Down);
+        handle_class_select_input(&mut game, KeyCode::Enter);
+        let player = game.player.as_ref().unwrap();
+        assert_eq!(player.class, class);
+        assert!(game.patron.is_some());
+        assert_eq!(player.max_hp, Player::new("Hero".to_string(), class).max_hp);
+    }
+
+    #[test]
+    fn test_a_corrupted_chest_can_be_left_sealed() {
+        use game::curses::{Curse, Drawback};
+        use game::events::GameEvent;
+
+        let curse = Curse { name: "Hungering Blade".to_string(), damage_mult: 1.3, drawback: Drawback::ThinSkin { damage_taken: 0.2 } };
+        let mut game = GameState::new();
+        game.player = Some(Player::new("Hero".to_string(), Class::Wordsmith));
+
+        game.start_event(GameEvent::corrupted_chest(curse.clone()));
+        handle_event_input(&mut game, KeyCode::Char('2'));
+        assert!(game.player.as_ref().unwrap().curses.is_empty());
+        assert_eq!(game.scene, Scene::Dungeon);
+
+        game.start_event(GameEvent::corrupted_chest(curse.clone()));
+        handle_event_input(&mut game, KeyCode::Char('1'));
+        assert_eq!(game.player.as_ref().unwrap().curses, vec![curse]);
+    }
+
+    #[test]
+    fn test_an_npc_who_does_not_remember_you_greets_you_in_their_own_voice() {
+        let mut game = GameState::new();
+        let encounter = game.encounters.get("mechanist_breakdown").cloned().expect("Kaya's encounter");
+        game.encounter_run = Some(EncounterRun::new(encounter));
+
+        game.start_conversation();
+        let runner = game.conversation.as_ref().unwrap();
+        let (speaker, greeting) = &runner.transcript[0];
+        assert_eq!(speaker, "Mechanist Technician");
+        assert!(!greeting.is_empty());
+        assert_eq!(runner.transcript.len(), 2);
+    }
+}
@@ -18,15 +18,28 @@ use ratatui::{
 
 use crate::game::state::GameState;
 use crate::game::combat::CombatPhase;
-use crate::ui::theme::{Palette, Styles};
+use crate::game::typing_impact::{graphemes, graphemes_match, grapheme_len};
+use crate::data::damage_types::Resistances;
+use crate::game::class_abilities::{abilities_for, COMMAND_PREFIX};
+use crate::game::fatigue::REST_WORDS;
+use crate::game::items::ItemEffect;
+use crate::game::keybinds::{Action, Keybinds};
+use crate::game::training::LessonProgress;
+use crate::game::enemy_intent::{Intent, IntentKind};
+use crate::game::boss_bar::BossPlate;
+use crate::ui::{layout, particles};
+use crate::ui::theme::{flow_color, zone_color, Icons, Palette, Styles};
+use unicode_width::UnicodeWidthStr;
 use crate::ui::effects::{TextColor, TextSize, FlashColor};
 
 /// Render the enhanced combat screen
 pub fn render_combat_enhanced(f: &mut Frame, state: &GameState) {
     let area = f.area();
     
-    // Apply screen shake offset if active
-    let render_area = if let Some(ref shake) = state.effects.screen_shake {
+    // Apply screen shake offset if active, and wanted
+    let display = &state.config.display;
+    let shake = state.effects.screen_shake.as_ref().filter(|_| display.screen_shake && !display.reduced_motion);
+    let render_area = if let Some(shake) = shake {
         if shake.is_active() {
             let (ox, oy) = shake.get_offset();
             Rect {
@@ -42,18 +55,31 @@ pub fn render_combat_enhanced(f: &mut Frame, state: &GameState) {
         area
     };
 
-    // Main layout
+    // Main layout: the panels by priority, the typing area first; on a
+    // short terminal the dialogue folds away, then the log, then the art
+    let inner = render_area.height.saturating_sub(2);
+    let [typing, enemy_hp, player, help, enemy_rows, log, dialogue] = layout::allot(inner, &[
+        (5, 5),  // Typing area
+        (3, 3),  // Enemy HP bar
+        (3, 3),  // Player HP + avatar indicator
+        (2, 2),  // Help
+        (10, 3), // Enemy display (with damage states), or just its name
+        (5, 0),  // Battle log
+        (4, 0),  // Combat dialogue / atmosphere
+    ])[..] else {
+        unreachable!("seven panels are allotted seven heights")
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(10), // Enemy display (with damage states)
-            Constraint::Length(3),  // Enemy HP bar
-            Constraint::Length(4),  // Combat dialogue / atmosphere
-            Constraint::Min(5),     // Typing area
-            Constraint::Length(3),  // Player HP + avatar indicator
-            Constraint::Length(5),  // Battle log
-            Constraint::Length(2),  // Help
+            Constraint::Length(enemy_rows),
+            Constraint::Length(enemy_hp),
+            Constraint::Length(dialogue),
+            Constraint::Min(typing),
+            Constraint::Length(player),
+            Constraint::Length(log),
+            Constraint::Length(help),
         ])
         .split(render_area);
 
@@ -62,10 +88,12 @@ pub fn render_combat_enhanced(f: &mut Frame, state: &GameState) {
         render_enemy_section(f, state, combat, enemy, chunks[0]);
 
         // === ENEMY HP BAR ===
-        render_enemy_hp(f, combat, chunks[1]);
+        render_enemy_hp(f, state, combat, chunks[1]);
 
         // === COMBAT DIALOGUE / ATMOSPHERE ===
-        render_combat_dialogue(f, state, combat, chunks[2]);
+        if dialogue > 0 {
+            render_combat_dialogue(f, state, combat, chunks[2]);
+        }
 
         // === TYPING AREA ===
         render_typing_area(f, state, combat, chunks[3]);
@@ -74,10 +102,17 @@ pub fn render_combat_enhanced(f: &mut Frame, state: &GameState) {
         render_player_status(f, state, chunks[4]);
 
         // === BATTLE LOG ===
-        render_battle_log(f, combat, chunks[5]);
+        if log > 0 {
+            render_battle_log(f, combat, chunks[5]);
+        }
 
         // === HELP BAR ===
-        render_combat_help(f, combat, chunks[6]);
+        render_combat_help(f, combat, &state.keybinds, &combat_pouch(state), chunks[6]);
+
+        // === PARTICLES ===
+        if !display.reduced_motion && !display.screen_reader {
+            particles::render(f.buffer_mut(), render_area, &state.effects.particles);
+        }
 
         // === FLOATING EFFECTS OVERLAY ===
         render_floating_effects(f, state, render_area);
@@ -94,12 +129,16 @@ fn render_enemy_section(
     enemy: &crate::game::enemy::Enemy,
     area: Rect,
 ) {
-    // Try to get immersive enemy art if available
-    let enemy_art = if let Some(ref imm) = combat.immersive {
+    // Try to get immersive enemy art if available (none for a screen reader)
+    let enemy_art = if state.config.display.screen_reader {
+        String::new()
+    } else if let Some(ref imm) = combat.immersive {
         imm.enemy_visuals.render_readonly().join("\n")
     } else {
         enemy.ascii_art.clone()
     };
+    // Too big for the panel, the enemy is its name alone
+    let enemy_art = if layout::art_fits(&enemy_art, area) { enemy_art } else { String::new() };
 
     // Determine enemy color based on health
     let hp_pct = combat.enemy.current_hp as f32 / combat.enemy.max_hp as f32;
@@ -121,29 +160,80 @@ fn render_enemy_section(
         enemy.name
     );
 
+    let mut block = Styles::block()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(enemy_color))
+        .title(if combat.enemy.is_boss {
+            Span::styled(" ⚔️ BOSS BATTLE ⚔️ ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        } else {
+            Span::raw("")
+        })
+        .title_bottom(Line::from(resistance_spans(&combat.enemy.resistances)).centered());
+    // Its next blow, over its head
+    if let Some(intent) = state.player.as_ref().and_then(|p| Intent::of(combat, p)) {
+        block = block.title(intent_line(&intent).right_aligned());
+    }
+
     let enemy_widget = Paragraph::new(display_text)
         .style(Style::default().fg(enemy_color))
         .alignment(Alignment::Center)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(enemy_color))
-            .title(if combat.enemy.is_boss {
-                Span::styled(" ⚔️ BOSS BATTLE ⚔️ ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
-            } else {
-                Span::raw("")
-            }));
+        .block(block);
     
     f.render_widget(enemy_widget, area);
 }
 
-fn render_enemy_hp(f: &mut Frame, combat: &crate::game::combat::CombatState, area: Rect) {
+/// The enemy's next blow: its kind, the damage it will do, and when
+fn intent_line(intent: &Intent) -> Line<'static> {
+    let palette = Palette::current();
+    let icons = Icons::current();
+    let (icon, color) = match intent.kind {
+        IntentKind::Strike => (icons.sword, palette.warning),
+        IntentKind::Frenzy => (icons.fire, palette.danger),
+        IntentKind::Punish => (icons.critical, palette.danger),
+        IntentKind::Turned => (icons.defend, palette.success),
+    };
+    let mut spans = vec![
+        Span::styled(format!(" {} {} ", icon, intent.kind.name()), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("{} ~{} ", icons.damage, intent.damage), Style::default().fg(color)),
+    ];
+    if intent.absorbed > 0 {
+        spans.push(Span::styled(format!("{} -{} ", icons.shield, intent.absorbed), Style::default().fg(palette.accent)));
+    }
+    if intent.miss_chance > 0.0 {
+        spans.push(Span::styled(format!("{:.0}% miss ", intent.miss_chance * 100.0), Styles::dim()));
+    }
+    spans.push(Span::styled(format!("{} {} ", icons.timer, intent.timing()), Styles::dim()));
+    Line::from(spans)
+}
+
+/// Weaknesses in green, resistances in red, for the enemy panel border
+fn resistance_spans(resistances: &Resistances) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let weak = resistances.weaknesses();
+    if !weak.is_empty() {
+        let names: Vec<String> = weak.iter().map(|t| format!("{} {}", t.icon(), t.name())).collect();
+        spans.push(Span::styled(format!(" Weak: {} ", names.join(", ")), Style::default().fg(Palette::current().success)));
+    }
+    let resisted = resistances.resisted();
+    if !resisted.is_empty() {
+        let names: Vec<String> = resisted.iter().map(|t| format!("{} {}", t.icon(), t.name())).collect();
+        spans.push(Span::styled(format!(" Resists: {} ", names.join(", ")), Style::default().fg(Palette::current().danger)));
+    }
+    spans
+}
+
+fn render_enemy_hp(f: &mut Frame, state: &GameState, combat: &crate::game::combat::CombatState, area: Rect) {
+    if let Some(plate) = &combat.boss_plate {
+        render_boss_bar(f, state, combat, plate, area);
+        return;
+    }
     let hp_percent = ((combat.enemy.current_hp as f64 / combat.enemy.max_hp as f64) * 100.0) as u16;
     let hp_color = if hp_percent > 50 {
-        Palette::SUCCESS
+        Palette::current().success
     } else if hp_percent > 25 {
-        Palette::WARNING
+        Palette::current().warning
     } else {
-        Palette::DANGER
+        Palette::current().danger
     };
 
     // Add visual flair based on HP
@@ -155,22 +245,88 @@ fn render_enemy_hp(f: &mut Frame, combat: &crate::game::combat::CombatState, are
         format!(" HP: {}/{} ", combat.enemy.current_hp, combat.enemy.max_hp)
     };
 
+    let block = enemy_status_titles(combat, Styles::block().borders(Borders::ALL).title(hp_label));
     let hp_gauge = Gauge::default()
-        .block(Block::default().borders(Borders::ALL).title(hp_label))
+        .block(block)
         .gauge_style(Style::default().fg(hp_color))
         .percent(hp_percent.min(100));
     
     f.render_widget(hp_gauge, area);
 }
 
+/// A boss's health in segments cut at its phases, under its name plate,
+/// framed in the zone's color
+fn render_boss_bar(f: &mut Frame, state: &GameState, combat: &crate::game::combat::CombatState, plate: &BossPlate, area: Rect) {
+    let palette = Palette::current();
+    let zone = zone_color(state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown"));
+    let enemy = &combat.enemy;
+    let hp_pct = enemy.current_hp.max(0) as f32 / enemy.max_hp.max(1) as f32;
+
+    let mut name_plate = vec![Span::styled(format!(" 👑 {} ", enemy.name), Style::default().fg(palette.enemy_hp).add_modifier(Modifier::BOLD))];
+    if let Some(title) = &plate.title {
+        name_plate.push(Span::styled(format!("— {} ", title), Style::default().fg(zone).add_modifier(Modifier::ITALIC)));
+    }
+    let phase = plate.phase(hp_pct).map_or(String::new(), |p| format!("{} · ", p.name));
+    let block = Styles::block()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(zone))
+        .title(Line::from(name_plate))
+        .title_bottom(Line::from(Span::styled(
+            format!(" {}HP {}/{} ", phase, enemy.current_hp.max(0), enemy.max_hp),
+            Style::default().fg(palette.enemy_hp),
+        )).centered());
+    let block = enemy_status_titles(combat, block);
+
+    // Filled up to the health left, with a marker where each phase begins
+    let width = block.inner(area).width as usize;
+    let filled = (hp_pct * width as f32).ceil() as usize;
+    let markers = plate.markers(width);
+    let cells: Vec<Span> = (0..width).map(|i| {
+        if markers.contains(&i) {
+            Span::styled("┃", Style::default().fg(if i < filled { palette.text } else { zone }))
+        } else if i < filled {
+            Span::styled("█", Style::default().fg(palette.enemy_hp))
+        } else {
+            Span::styled("░", Styles::dim())
+        }
+    }).collect();
+
+    f.render_widget(Paragraph::new(Line::from(cells)).block(block), area);
+}
+
+/// Shield pips and the enrage, on the right of an enemy health bar
+fn enemy_status_titles<'a>(combat: &crate::game::combat::CombatState, mut block: Block<'a>) -> Block<'a> {
+    // Shield pips: filled while standing, hollow once shattered
+    let shield = &combat.enemy_shield;
+    if shield.max_pips > 0 {
+        let pips: String = (0..shield.max_pips).map(|i| if i < shield.pips { '◆' } else { '◇' }).collect();
+        let pip_color = if shield.is_up() { Palette::current().accent } else { Color::DarkGray };
+        block = block.title(Line::from(Span::styled(format!(" 🛡 {} ", pips), Style::default().fg(pip_color))).right_aligned());
+    }
+
+    let enrage = &combat.enrage;
+    if enrage.enraged {
+        block = block.title(Line::from(Span::styled(
+            format!(" 😡 ENRAGED x{:.2} ", enrage.attack_mult()),
+            Style::default().fg(Palette::current().danger).add_modifier(Modifier::BOLD),
+        )).right_aligned());
+    } else if enrage.warned {
+        block = block.title(Line::from(Span::styled(" ⏳ restless ", Style::default().fg(Palette::current().warning))).right_aligned());
+    }
+    block
+}
+
 fn render_combat_dialogue(
     f: &mut Frame,
     state: &GameState,
     combat: &crate::game::combat::CombatState,
     area: Rect,
 ) {
-    // Try to get dialogue from immersion system
-    let dialogue_text = if let Some(ref imm) = combat.immersive {
+    // In the training yard, Beck keeps the lesson's goal in view; elsewhere
+    // the dialogue comes from the immersion system
+    let dialogue_text = if let Some(lesson) = state.training.as_ref().and_then(|t| t.current()) {
+        format!("Beck: {} ({})", lesson.goal(), lesson.count(&LessonProgress::of(combat)))
+    } else if let Some(ref imm) = combat.immersive {
         // Check for pending messages - use the feedback message if available
         if let Some(feedback) = &imm.last_word_feedback {
             if !feedback.message.is_empty() {
@@ -197,7 +353,7 @@ fn render_combat_dialogue(
         .style(style)
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
     
     f.render_widget(dialogue, area);
 }
@@ -238,13 +394,13 @@ fn render_typing_area(
         let widget = Paragraph::new(msg)
             .style(Style::default().add_modifier(Modifier::BOLD))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
+            .block(Styles::block().borders(Borders::ALL));
         f.render_widget(widget, area);
         return;
     }
 
     let typed = &combat.typed_input;
-    let target = &combat.current_word;
+    let target = combat.target();
     let mut spans = Vec::new();
 
     // Check for typing ripple effect
@@ -262,34 +418,72 @@ fn render_typing_area(
         None
     };
 
-    for (i, target_char) in target.chars().enumerate() {
-        if i < typed.len() {
-            let typed_char = typed.chars().nth(i).unwrap();
-            if typed_char == target_char {
-                spans.push(Span::styled(
-                    target_char.to_string(),
-                    Style::default()
-                        .fg(Palette::SUCCESS)
-                        .add_modifier(Modifier::BOLD),
-                ));
+    // Walk grapheme clusters, not chars, so accents stay attached to their
+    // letters and wide CJK glyphs are drawn (and cursored) as one cell pair
+    let typed_graphemes = graphemes(typed);
+    let dictation = state.pronouncer.is_dictation();
+    let listen = format!("Listen! [{} replay]", state.keybinds.label(Action::ReplayPrompt));
+    // Inscribing: once the study time is up, the words are written from memory
+    let from_memory = combat.inscription.is_some_and(|i| !i.is_previewing());
+    // A jumbled prompt: the real word stays hidden until it is typed
+    let puzzle = combat.active_puzzle();
+    let weak_points = combat.weak_points();
+    let aim = combat.active_aim();
+    for (i, target_char) in graphemes(target).into_iter().enumerate() {
+        if i < typed_graphemes.len() {
+            if graphemes_match(target_char, typed_graphemes[i]) {
+                spans.push(Span::styled(target_char.to_string(), Styles::typed_correct()));
             } else {
                 spans.push(Span::styled(
                     target_char.to_string(),
-                    Style::default()
-                        .fg(Palette::DANGER)
+                    Styles::typed_wrong()
                         .bg(Color::Rgb(60, 0, 0))
                         .add_modifier(Modifier::CROSSED_OUT),
                 ));
             }
-        } else if i == typed.len() {
+        } else if dictation || from_memory || puzzle.is_some() {
+            // Listen-and-type, inscriptions and puzzles: untyped text stays hidden
+            let masked = if target_char.trim().is_empty() { " " } else { "·" };
+            let style = if i == typed_graphemes.len() {
+                Styles::cursor()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            spans.push(Span::styled(masked.to_string(), style));
+        } else if i == typed_graphemes.len() {
             // Cursor position with optional ripple
-            let mut style = Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            let mut style = Styles::cursor();
             if let Some(m) = ripple_modifier {
                 style = style.add_modifier(m);
             }
+            if combat.corrupted_span.is_some_and(|span| span.contains(i)) {
+                style = style.bg(Palette::current().corrupted);
+            }
             spans.push(Span::styled(target_char.to_string(), style));
+        } else if combat.corrupted_span.is_some_and(|span| span.contains(i)) {
+            // Letters the Unwriting has touched
+            spans.push(Span::styled(
+                target_char.to_string(),
+                Style::default()
+                    .fg(Palette::current().corrupted)
+                    .add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+            ));
+        } else if aim.is_some_and(|a| a.index == i) {
+            // An aimed capital: type it cleanly to wound where it points
+            spans.push(Span::styled(
+                target_char.to_string(),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            ));
+        } else if weak_points.iter().any(|w| w.contains(i)) {
+            // A boss's weak point: type it cleanly to stagger them
+            spans.push(Span::styled(
+                target_char.to_string(),
+                Style::default()
+                    .fg(Palette::current().accent)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            ));
         } else {
             spans.push(Span::styled(
                 target_char.to_string(),
@@ -321,18 +515,78 @@ fn render_typing_area(
         String::new()
     };
 
+    let study = combat.inscription.filter(|i| i.is_previewing())
+        .map(|i| format!("✎ STUDY {:.1}s", i.preview_secs_left()));
+    let channel = combat.channel
+        .map(|c| format!("≋ CHANNEL {} (x{:.1}, {} dmg)", c.words, c.intensity(), c.dealt));
+    let chain = combat.word_chain.filter(|c| c.links > 0)
+        .map(|c| format!("⛓ CHAIN {} (x{:.1})", c.links, c.bonus_mult()));
+    let aimed = aim.map(|a| format!("🎯 AIM: {}", a.location.name()));
+    let clock = if combat.scheduler.clock_runs() {
+        format!("{:.1}s", combat.time_remaining)
+    } else {
+        "your turn".to_string()
+    };
     let title = format!(
-        " ⌨️ Type! | {} | ⏱️ {:.1}s | {}/{} ",
+        " ⌨️ {} | {} | ⏱️ {} | {}/{} ",
+        if combat.hazard.is_some() {
+            "⚠ HAZARD"
+        } else if dictation {
+            listen.as_str()
+        } else if let Some(study) = &study {
+            study
+        } else if from_memory {
+            "✎ FROM MEMORY"
+        } else if let Some(puzzle) = puzzle {
+            puzzle.kind.name()
+        } else if let Some(span) = combat.corrupted_span {
+            span.kind.name()
+        } else if combat.execution {
+            "☠ FINISH IT"
+        } else if combat.staggered() {
+            "🎯 STAGGERED"
+        } else if combat.heavy_strike {
+            "🗡 HEAVY STRIKE"
+        } else if let Some(aimed) = &aimed {
+            aimed
+        } else if let Some(channel) = &channel {
+            channel
+        } else if let Some(chain) = &chain {
+            chain
+        } else {
+            "Type!"
+        },
         combo_display,
-        combat.time_remaining,
-        typed.len(),
-        target.len()
+        clock,
+        typed_graphemes.len(),
+        grapheme_len(target)
     );
 
-    let typing_widget = Paragraph::new(Line::from(spans))
+    let mut lines = Vec::new();
+    if let Some(hazard) = &combat.hazard {
+        // The hazard's prompt cuts in above the word, which waits
+        let rest = hazard.hazard.prompt.get(hazard.typed.len()..).unwrap_or("");
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("⚠ {} ({:.1}s) ", hazard.hazard.name.to_uppercase(), hazard.remaining.max(0.0)),
+                Style::default().fg(Palette::current().danger).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(hazard.typed.clone(), Style::default().fg(Palette::current().success).add_modifier(Modifier::BOLD)),
+            Span::styled(rest.to_string(), Style::default().fg(Color::White).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+        ]));
+    }
+    if let Some(puzzle) = puzzle {
+        lines.push(Line::from(Span::styled(
+            puzzle.shown.clone(),
+            Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD),
+        )));
+    }
+    lines.push(Line::from(spans));
+
+    let typing_widget = Paragraph::new(lines)
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: false })
-        .block(Block::default()
+        .block(Styles::block()
             .borders(Borders::ALL)
             .border_style(combo_style)
             .title(Span::styled(title, combo_style)));
@@ -344,11 +598,11 @@ fn render_player_status(f: &mut Frame, state: &GameState, area: Rect) {
     if let Some(player) = &state.player {
         let hp_pct = (player.hp as f64 / player.max_hp as f64) * 100.0;
         let hp_color = if hp_pct > 50.0 {
-            Palette::SUCCESS
+            Palette::current().success
         } else if hp_pct > 25.0 {
-            Palette::WARNING
+            Palette::current().warning
         } else {
-            Palette::DANGER
+            Palette::current().danger
         };
 
         // Get avatar indicator if available
@@ -359,6 +613,8 @@ fn render_player_status(f: &mut Frame, state: &GameState, area: Rect) {
                     crate::game::player_avatar::AvatarState::Hit => " 💥 ",
                     crate::game::player_avatar::AvatarState::Victory => " 🏆 ",
                     crate::game::player_avatar::AvatarState::Wounded => " 💀 ",
+                    crate::game::player_avatar::AvatarState::Riposte => " ↩️ ",
+                    crate::game::player_avatar::AvatarState::Winded => " 😮‍💨 ",
                     _ => " 🛡️ ",
                 }
             } else {
@@ -375,11 +631,47 @@ fn render_player_status(f: &mut Frame, state: &GameState, area: Rect) {
         };
 
         let hp_gauge = Gauge::default()
-            .block(Block::default().borders(Borders::ALL).title(hp_label))
+            .block(Styles::block().borders(Borders::ALL).title(hp_label))
             .gauge_style(Style::default().fg(hp_color))
             .percent((hp_pct as u16).min(100));
         
-        f.render_widget(hp_gauge, area);
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(20), Constraint::Percentage(35)])
+            .split(area);
+        f.render_widget(hp_gauge, halves[0]);
+        
+        // Stamina, drained by sprinting
+        if let Some(ref combat) = state.combat_state {
+            let fatigue = &combat.fatigue;
+            let (label, color, percent) = if fatigue.fatigued {
+                (format!(" Tired {}/{} ", fatigue.rest_streak, REST_WORDS), Palette::current().danger, 0)
+            } else {
+                let color = if fatigue.stamina_percent() > 50 { Palette::current().success } else { Palette::current().warning };
+                (" Stamina ".to_string(), color, fatigue.stamina_percent())
+            };
+            let stamina_gauge = Gauge::default()
+                .block(Styles::block()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color))
+                    .title(Span::styled(label, Style::default().fg(color))))
+                .gauge_style(Style::default().fg(color))
+                .percent(percent.min(100));
+            f.render_widget(stamina_gauge, halves[1]);
+        }
+        
+        // Flow meter, colored by state
+        let feel = &state.typing_feel;
+        let color = flow_color(feel.flow_state);
+        let flow_label = format!(" 󰔟 {} x{:.2} ", feel.flow_description(), feel.flow_state.damage_multiplier());
+        let flow_gauge = Gauge::default()
+            .block(Styles::block()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color))
+                .title(Span::styled(flow_label, Style::default().fg(color))))
+            .gauge_style(Style::default().fg(color))
+            .percent(feel.flow_percent().min(100));
+        f.render_widget(flow_gauge, halves[2]);
     }
 }
 
@@ -389,45 +681,89 @@ fn render_battle_log(f: &mut Frame, combat: &crate::game::combat::CombatState, a
         .rev()
         .take(4)
         .map(|msg| {
-            let style = if msg.contains("✓") || msg.contains("damage") {
-                Style::default().fg(Color::Green)
-            } else if msg.contains("✗") || msg.contains("💥") {
-                Style::default().fg(Color::Red)
+            // An enemy's blow mentions damage too, so it is checked first
+            let style = if msg.contains("✗") || msg.contains("💥") {
+                Styles::typed_wrong()
+            } else if msg.contains("✓") || msg.contains("damage") {
+                Styles::typed_correct()
             } else if msg.contains("✦") {
-                Style::default().fg(Color::Magenta)
+                Style::default().fg(Palette::current().accent)
             } else {
-                Style::default().fg(Color::Gray)
+                Styles::dim()
             };
             Line::styled(msg.clone(), style)
         })
         .collect();
 
     let log = Paragraph::new(log_lines)
-        .block(Block::default()
+        .block(Styles::block()
             .borders(Borders::ALL)
-            .title(Span::styled(" 📜 Battle Log ", Style::default().fg(Palette::INFO))));
+            .title(Span::styled(" 📜 Battle Log ", Style::default().fg(Palette::current().info))));
     
     f.render_widget(log, area);
 }
 
-fn render_combat_help(f: &mut Frame, combat: &crate::game::combat::CombatState, area: Rect) {
-    let help_spans = if combat.spell_mode {
+/// Combat consumables in the player's bag, as (name, count)
+fn combat_pouch(state: &GameState) -> Vec<(String, usize)> {
+    let mut pouch: Vec<(String, usize)> = Vec::new();
+    let items = state.player.iter().flat_map(|p| &p.inventory);
+    for item in items.filter(|i| matches!(i.effect, ItemEffect::Combat(_))) {
+        match pouch.iter_mut().find(|(name, _)| *name == item.name) {
+            Some((_, count)) => *count += 1,
+            None => pouch.push((item.name.clone(), 1)),
+        }
+    }
+    pouch
+}
+
+fn render_combat_help(f: &mut Frame, combat: &crate::game::combat::CombatState, keybinds: &Keybinds, pouch: &[(String, usize)], area: Rect) {
+    let key = |action| format!("[{}] ", keybinds.label(action));
+    let help_spans = if let Some(input) = &combat.ability_input {
+        let mut spans = vec![
+            Span::styled(format!(" {}{}_ ", COMMAND_PREFIX, input), Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD)),
+        ];
+        for ability in abilities_for(combat.class) {
+            let turns = combat.cooldowns.turns_left(*ability, combat.turn);
+            let (label, color) = if turns > 0 {
+                (format!(" {} ({}) ", ability.keyword(), turns), Palette::current().text_dim)
+            } else {
+                (format!(" {} ", ability.keyword()), Palette::current().success)
+            };
+            spans.push(Span::styled(label, Style::default().fg(color)));
+        }
+        for (name, count) in pouch {
+            spans.push(Span::styled(format!(" {} x{} ", name.to_lowercase(), count), Style::default().fg(Palette::current().accent)));
+        }
+        spans.push(Span::styled(" [Enter] ", Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw("Use  "));
+        spans.push(Span::styled("[Esc] ", Style::default().fg(Color::Red)));
+        spans.push(Span::raw("Cancel"));
+        spans
+    } else if combat.spell_mode {
         vec![
             Span::styled(" [1-9] ", Style::default().fg(Color::Yellow)),
             Span::raw("Cast Spell  "),
-            Span::styled("[Tab] ", Style::default().fg(Color::Cyan)),
+            Span::styled(key(Action::SpellMode), Style::default().fg(Color::Cyan)),
             Span::raw("Cancel  "),
-            Span::styled("[Esc] ", Style::default().fg(Color::Red)),
+            Span::styled(key(Action::Flee), Style::default().fg(Color::Red)),
             Span::raw("Flee"),
         ]
     } else {
         vec![
             Span::styled(" [a-z] ", Style::default().fg(Color::Yellow)),
             Span::raw("Type  "),
-            Span::styled("[Tab] ", Style::default().fg(Color::Magenta)),
+            Span::styled(key(Action::SpellMode), Style::default().fg(Color::Magenta)),
             Span::raw("Spells  "),
-            Span::styled("[Esc] ", Style::default().fg(Color::Red)),
+            Span::styled(key(Action::Stance), Style::default().fg(Color::Magenta)),
+            Span::raw(format!("{} {}  ", combat.stance.icon(), combat.stance.name())),
+            Span::styled(key(Action::HeavyStrike), Style::default().fg(Palette::current().warning)),
+            Span::raw("Heavy  "),
+            Span::styled("[/] ", Style::default().fg(Palette::current().success)),
+            Span::raw("Ability  "),
+            Span::styled(key(Action::Flee), Style::default().fg(Color::Red)),
             Span::raw("Flee  "),
+            Span::styled(key(Action::Pause), Style::default().fg(Color::Cyan)),
+            Span::raw("Pause  "),
             Span::styled("[?] ", Style::default().fg(Color::Cyan)),
             Span::raw("Help"),
         ]
@@ -476,10 +812,11 @@ fn render_floating_effects(f: &mut Frame, state: &GameState, area: Rect) {
                 .style(style)
                 .alignment(Alignment::Center);
 
+            let text_width = text.text.width() as u16;
             let text_area = Rect {
-                x: x.saturating_sub(text.text.len() as u16 / 2),
+                x: x.saturating_sub(text_width / 2),
                 y,
-                width: (text.text.len() as u16).min(area.width),
+                width: text_width.min(area.width),
                 height: 1,
             };
 
@@ -499,7 +836,7 @@ fn render_hit_flash(f: &mut Frame, state: &GameState, area: Rect) {
             };
 
             // Render a brief border flash
-            let flash_block = Block::default()
+            let flash_block = Styles::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(color).add_modifier(Modifier::BOLD));
             
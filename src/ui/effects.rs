@@ -1,14 +1,23 @@
 //! Visual Effects System - The "Juice" that makes combat feel impactful
 //!
 //! This module provides:
-//! - Floating damage numbers
-//! - Screen shake effects
-//! - Hit flash overlays
+//! - Floating damage numbers, for each word and each key that lands
+//! - Screen shake effects, light ones for a hard or wrong key
+//! - Hit flash overlays, a brief one for a fast or wrong key
 //! - Combo pulse animations
 //! - Combat message styling
 
 use std::time::Instant;
 
+use super::particles::ParticleField;
+use crate::game::combat_immersion::KeystrokeFeedback;
+
+/// A key lands hard enough to flash from this visual intensity
+const KEY_FLASH_INTENSITY: f32 = 0.75;
+
+/// A key shakes the screen from this much `screen_shake`
+const KEY_SHAKE: f32 = 0.1;
+
 /// A floating text element (damage numbers, status text, etc.)
 #[derive(Debug, Clone)]
 pub struct FloatingText {
@@ -91,6 +100,21 @@ impl FloatingText {
         }
     }
 
+    /// The damage a single key adds to the word, small and quick to go
+    pub fn stroke(amount: i32, x: f32, y: f32) -> Self {
+        Self {
+            text: format!("-{}", amount),
+            x,
+            y,
+            velocity_y: -1.0,
+            opacity: 1.0,
+            color: TextColor::Damage,
+            size: TextSize::Small,
+            created_at: Instant::now(),
+            lifetime_ms: 500,
+        }
+    }
+
     pub fn perfect(x: f32, y: f32) -> Self {
         Self {
             text: "✨ PERFECT!".to_string(),
@@ -177,6 +201,15 @@ impl ScreenShake {
         }
     }
 
+    /// A key's shake, from its `screen_shake` amount
+    pub fn keystroke(amount: f32) -> Self {
+        Self {
+            intensity: (amount * 10.0).min(2.0),
+            duration_ms: 80,
+            created_at: Instant::now(),
+        }
+    }
+
     pub fn is_active(&self) -> bool {
         (self.created_at.elapsed().as_millis() as u64) < self.duration_ms
     }
@@ -241,6 +274,15 @@ impl HitFlash {
         }
     }
 
+    /// A key's flash, longer the harder it landed
+    pub fn keystroke(correct: bool, intensity: f32) -> Self {
+        Self {
+            color: if correct { FlashColor::White } else { FlashColor::Red },
+            duration_ms: 20 + (60.0 * intensity) as u64,
+            created_at: Instant::now(),
+        }
+    }
+
     pub fn is_active(&self) -> bool {
         (self.created_at.elapsed().as_millis() as u64) < self.duration_ms
     }
@@ -262,6 +304,7 @@ pub struct EffectsManager {
     pub hit_flash: Option<HitFlash>,
     pub combo_pulse: Option<ComboPulse>,
     pub typing_ripple: Option<TypingRipple>,
+    pub particles: ParticleField,
 }
 
 /// Combo counter pulse animation
@@ -337,6 +380,7 @@ impl EffectsManager {
         self.hit_flash = None;
         self.combo_pulse = None;
         self.typing_ripple = None;
+        self.particles.clear();
     }
     
     pub fn update(&mut self) {
@@ -432,6 +476,24 @@ impl EffectsManager {
         });
     }
 
+    /// A key's own impact: its damage floats up from the typing area, and a
+    /// hard or wrong key flashes and shakes, unless a bigger hit is still
+    /// showing
+    pub fn keystroke_impact(&mut self, feedback: &KeystrokeFeedback) {
+        self.keystroke(feedback.correct);
+        if feedback.correct && feedback.damage_dealt > 0 {
+            // Step across so quick keys don't stack on one spot
+            let x = 0.42 + (self.floating_texts.len() % 5) as f32 * 0.04;
+            self.floating_texts.push(FloatingText::stroke(feedback.damage_dealt, x, 0.6));
+        }
+        if feedback.visual_intensity >= KEY_FLASH_INTENSITY && !self.hit_flash.as_ref().is_some_and(HitFlash::is_active) {
+            self.hit_flash = Some(HitFlash::keystroke(feedback.correct, feedback.visual_intensity));
+        }
+        if feedback.screen_shake >= KEY_SHAKE && !self.screen_shake.as_ref().is_some_and(ScreenShake::is_active) {
+            self.screen_shake = Some(ScreenShake::keystroke(feedback.screen_shake));
+        }
+    }
+
     /// Check if any effects are active
     pub fn has_active_effects(&self) -> bool {
         !self.floating_texts.is_empty()
@@ -528,6 +590,34 @@ mod tests {
         assert!(mgr.screen_shake.is_some());
     }
 
+    #[test]
+    fn test_keys_float_their_damage_and_hard_ones_flash_and_shake() {
+        use crate::game::combat_immersion::SpeedRating;
+        let key = |correct, damage_dealt, visual_intensity, screen_shake| KeystrokeFeedback {
+            character: 'a',
+            correct,
+            damage_dealt,
+            rhythm_bonus: false,
+            speed_rating: SpeedRating::Normal,
+            visual_intensity,
+            screen_shake,
+        };
+        let mut mgr = EffectsManager::new();
+        mgr.keystroke_impact(&key(true, 1, 0.5, 0.04));
+        assert_eq!(mgr.floating_texts[0].text, "-1");
+        assert!(mgr.hit_flash.is_none() && mgr.screen_shake.is_none());
+
+        mgr.keystroke_impact(&key(false, 0, 0.8, 0.1));
+        assert_eq!(mgr.floating_texts.len(), 1);
+        assert_eq!(mgr.hit_flash.as_ref().map(|f| f.color), Some(FlashColor::Red));
+        assert_eq!(mgr.screen_shake.as_ref().map(|s| s.get_offset().0.abs() <= 1), Some(true));
+
+        // A crit's flash is not cut short by the next key
+        mgr.add_damage(40, true);
+        mgr.keystroke_impact(&key(true, 3, 1.0, 0.12));
+        assert_eq!(mgr.hit_flash.as_ref().map(|f| f.color), Some(FlashColor::Yellow));
+    }
+
     #[test]
     fn test_combo_pulse() {
         let pulse = ComboPulse::new(5);
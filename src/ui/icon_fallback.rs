@@ -0,0 +1,184 @@
+//! Icon Fallback - Drawing the game without a Nerd Font
+//!
+//! The UI's icons are Nerd Font glyphs, and a terminal without one draws
+//! them as empty boxes. Before the first frame the game works out what the
+//! terminal can draw:
+//! - a terminal that can't take UTF-8 (the Linux console, a `C` locale)
+//!   gets plain ASCII, icons and borders alike
+//! - otherwise the installed fonts are asked for a Nerd Font; with none,
+//!   icons are drawn as plain Unicode symbols
+//! - `display.icons` in `config.ron` (`Some(ascii)`, `Some(unicode)`,
+//!   `Some(nerd)`) settles it instead, for when the probe guesses wrong
+//!
+//! Design: icons are drawn from `Icons` where the UI asks for one, but many
+//! are written straight into titles and messages. Rather than chase each,
+//! every frame is swept once it is drawn, and any Nerd Font glyph left in it
+//! is swapped for its stand-in. Each stand-in is one cell wide, as the
+//! glyph was, so nothing drawn around it moves.
+
+use std::process::Command;
+
+use ratatui::buffer::Buffer;
+
+use super::theme::{IconStyle, Icons};
+
+/// Stand-ins for the glyphs drawn outside `Icons`: (glyph, Unicode, ASCII)
+static FALLBACKS: [(char, &str, &str); 32] = [
+    ('\u{f02d6}', "?", "?"),  // help
+    ('\u{f00ba}', "❐", "="),  // codex
+    ('\u{f01a7}', "▤", "="),  // treasure
+    ('\u{f0664}', "✎", "~"),  // ink
+    ('\u{f0400}', "✎", "~"),  // ink
+    ('\u{f0100}', "✶", "*"),  // arcane
+    ('\u{f04b2}', "☾", "z"),  // rest
+    ('\u{f0494}', "↕", "|"),  // navigate
+    ('\u{f048d}', "↕", "|"),  // browse
+    ('\u{f0206}', "→", ">"),  // flee
+    ('\u{f0311}', "↵", ">"),  // confirm
+    ('\u{f0703}', "⚑", "F"),  // faction
+    ('\u{f032a}', "⚑", "F"),  // faction
+    ('\u{f01ee}', "✉", "@"),  // letters
+    ('\u{f068c}', "☠", "X"),  // elite
+    ('\u{f02a0}', "✧", "*"),  // spell
+    ('\u{f070e}', "✦", "*"),  // sparkle
+    ('\u{f0369}', "“", "\""), // say
+    ('\u{f0403}', "•", "*"),  // pin
+    ('\u{f09a8}', "≡", "="),  // transcript
+    ('\u{f039f}', "≡", "="),  // log
+    ('\u{f0d45}', "≡", "="),  // battle log
+    ('\u{f0208}', "◉", "o"),  // eye
+    ('\u{f10f5}', "▲", "^"),  // upgrades
+    ('\u{f082e}', "❐", "="),  // journal
+    ('\u{f0219}', "❐", "="),  // lore
+    ('\u{f03d7}', "⚙", "*"),  // systems
+    ('\u{f05fc}', "✕", "x"),  // quit
+    ('\u{f051a}', "➤", ">"),  // speed
+    ('\u{f012a}', "▤", "#"),  // stats
+    ('\u{f0b87}', "◎", "o"),  // objective
+    ('\u{f03e4}', "‖", "|"),  // paused
+];
+
+/// Whether a character is a Nerd Font glyph (they sit in the private use
+/// areas)
+fn is_glyph(c: char) -> bool {
+    matches!(c, '\u{e000}'..='\u{f8ff}' | '\u{f0000}'..='\u{fffff}')
+}
+
+/// The stand-in for the Nerd Font glyph `c` in `style`
+pub fn fallback(c: char, style: IconStyle) -> &'static str {
+    let pick = |unicode, ascii| if style == IconStyle::Ascii { ascii } else { unicode };
+    let (nerd, unicode, ascii) = (Icons::NERD.all(), Icons::UNICODE.all(), Icons::ASCII.all());
+    if let Some(i) = nerd.iter().position(|g| g.chars().eq([c])) {
+        return pick(unicode[i], ascii[i]);
+    }
+    match FALLBACKS.iter().find(|(glyph, _, _)| *glyph == c) {
+        Some((_, unicode, ascii)) => pick(unicode, ascii),
+        None => pick("•", "*"),
+    }
+}
+
+/// A box-drawing or shading character as ASCII, if it is one
+fn ascii_box(c: char) -> Option<&'static str> {
+    Some(match c {
+        '─' | '━' | '═' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '╴' | '╶' | '╸' | '╺' => "-",
+        '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '╵' | '╷' | '╹' | '╻' => "|",
+        '\u{2500}'..='\u{257f}' => "+",
+        '█' | '▓' | '▒' => "#",
+        '░' => ".",
+        _ => return None,
+    })
+}
+
+/// Swap every glyph in a drawn frame the terminal can't draw for its
+/// stand-in in `style`
+pub fn sweep(buffer: &mut Buffer, style: IconStyle) {
+    if style == IconStyle::Nerd {
+        return;
+    }
+    for cell in buffer.content.iter_mut() {
+        let mut chars = cell.symbol().chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let stand_in = if is_glyph(c) {
+            Some(fallback(c, style))
+        } else if style == IconStyle::Ascii {
+            ascii_box(c)
+        } else {
+            None
+        };
+        if let Some(stand_in) = stand_in {
+            cell.set_symbol(stand_in);
+        }
+    }
+}
+
+/// What a terminal can draw, from its `TERM`, its locale, its
+/// `TERM_PROGRAM` and the font families installed (None if they couldn't
+/// be listed)
+fn judge(term: &str, locale: &str, program: &str, fonts: Option<&str>) -> IconStyle {
+    let utf8 = locale.to_lowercase().replace('-', "").contains("utf8");
+    if matches!(term, "linux" | "dumb" | "vt100" | "vt220") || (!locale.is_empty() && !utf8) {
+        return IconStyle::Ascii;
+    }
+    // WezTerm carries the Nerd Font symbols itself
+    if program == "WezTerm" {
+        return IconStyle::Nerd;
+    }
+    match fonts {
+        Some(fonts) if !fonts.to_lowercase().contains("nerd font") => IconStyle::Unicode,
+        _ => IconStyle::Nerd,
+    }
+}
+
+/// Work out what this terminal can draw
+pub fn probe() -> IconStyle {
+    let var = |key| std::env::var(key).unwrap_or_default();
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].into_iter().map(var).find(|v| !v.is_empty()).unwrap_or_default();
+    let fonts = Command::new("fc-list")
+        .args([":", "family"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).into_owned());
+    judge(&var("TERM"), &locale, &var("TERM_PROGRAM"), fonts.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_terminals_are_judged_by_locale_and_fonts() {
+        assert_eq!(judge("linux", "en_US.UTF-8", "", None), IconStyle::Ascii);
+        assert_eq!(judge("xterm-256color", "C", "", None), IconStyle::Ascii);
+        assert_eq!(judge("xterm-256color", "en_US.utf8", "", Some("DejaVu Sans\nNoto Sans")), IconStyle::Unicode);
+        assert_eq!(judge("xterm-256color", "en_US.UTF-8", "", Some("JetBrainsMono Nerd Font")), IconStyle::Nerd);
+        assert_eq!(judge("xterm-256color", "en_US.UTF-8", "WezTerm", Some("DejaVu Sans")), IconStyle::Nerd);
+        // Fonts that can't be listed are trusted to have the glyphs
+        assert_eq!(judge("xterm-256color", "", "", None), IconStyle::Nerd);
+    }
+
+    #[test]
+    fn test_sweeping_leaves_no_glyph_and_moves_nothing() {
+        let area = Rect::new(0, 0, 12, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(0, 0, format!("{}│{} Tab", Icons::NERD.heart, '\u{f02d6}'), Style::default());
+        let before = buffer.clone();
+
+        sweep(&mut buffer, IconStyle::Nerd);
+        assert_eq!(buffer, before);
+
+        sweep(&mut buffer, IconStyle::Unicode);
+        assert_eq!(buffer.cell((0, 0)).unwrap().symbol(), Icons::UNICODE.heart);
+        assert_eq!(buffer.cell((1, 0)).unwrap().symbol(), "│");
+        assert_eq!(buffer.cell((4, 0)).unwrap().symbol(), "T");
+
+        let mut buffer = before;
+        sweep(&mut buffer, IconStyle::Ascii);
+        let drawn: String = buffer.content.iter().map(|c| c.symbol()).collect();
+        assert_eq!(drawn, format!("{}|? Tab     ", Icons::ASCII.heart));
+    }
+}
@@ -0,0 +1,153 @@
+//! Layout - Fitting the screens to the terminal
+//!
+//! The screens are laid out for a terminal of 80x24 or more, and the fight
+//! for a good deal more than that. On a smaller one they give way rather
+//! than break:
+//! - narrower than 80 columns, panels that sit side by side (a list and its
+//!   details) are stacked one above the other
+//! - too short for the whole fight, its panels fold away in turn: the
+//!   dialogue first, then the battle log, then the enemy's art, which
+//!   leaves the enemy's name in its place
+//! - under 60x20 nothing fits at all, and a guard screen asks for a larger
+//!   window, holding the fight until it gets one
+//!
+//! Design: each screen asks the size class of its own area as it lays
+//! itself out, so nothing about the terminal is kept. The fight's panels
+//! are allotted rows by priority, the typing area first, since a fight can
+//! be played without anything else.
+
+use std::rc::Rc;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::theme::{Palette, Styles};
+
+/// The smallest terminal anything is drawn in
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 20;
+
+/// The terminal the screens are laid out for
+pub const FULL_WIDTH: u16 = 80;
+pub const FULL_HEIGHT: u16 = 24;
+
+/// How well an area fits the screens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fit {
+    /// Under the minimum: only the guard screen is drawn
+    TooSmall,
+    /// Drawn, with panels folded or stacked
+    Compact,
+    Full,
+}
+
+pub fn fit(area: Rect) -> Fit {
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        Fit::TooSmall
+    } else if area.width < FULL_WIDTH || area.height < FULL_HEIGHT {
+        Fit::Compact
+    } else {
+        Fit::Full
+    }
+}
+
+/// Whether panels side by side must be stacked instead
+pub fn narrow(area: Rect) -> bool {
+    area.width < FULL_WIDTH
+}
+
+/// Split `area` into panels side by side, or one above the other when it
+/// is narrow
+pub fn beside<const N: usize>(area: Rect, constraints: [Constraint; N]) -> Rc<[Rect]> {
+    let direction = if narrow(area) { Direction::Vertical } else { Direction::Horizontal };
+    Layout::default().direction(direction).constraints(constraints).split(area)
+}
+
+/// Rows of ASCII art a panel must show of it, at the least, for it to be
+/// drawn at all
+const ART_ROWS: u16 = 3;
+
+/// Whether ASCII art is drawn in a bordered panel of `area`: it must fit
+/// across, and its first rows must fit with a row to spare for a caption
+pub fn art_fits(art: &str, area: Rect) -> bool {
+    let rows = (art.trim_matches('\n').lines().count() as u16).min(ART_ROWS);
+    let width = art.lines().map(|l| l.width()).max().unwrap_or(0) as u16;
+    rows + 3 <= area.height && width + 2 <= area.width
+}
+
+/// Rows for panels stacked in `height`, given in order of priority as the
+/// rows each wants and the fewest it can do with. A panel gets what it
+/// wants if that still leaves those after it their fewest, else its fewest
+/// if that is left, else nothing.
+pub fn allot(height: u16, panels: &[(u16, u16)]) -> Vec<u16> {
+    let mut left = height;
+    panels.iter().enumerate().map(|(i, &(want, fewest))| {
+        let after: u16 = panels[i + 1..].iter().map(|p| p.1).sum();
+        let rows = if want + after <= left {
+            want
+        } else if fewest <= left {
+            fewest
+        } else {
+            0
+        };
+        left -= rows;
+        rows
+    }).collect()
+}
+
+/// The guard screen, in place of everything while the terminal is too
+/// small
+pub fn render_too_small(f: &mut Frame) {
+    let area = f.area();
+    let lines = vec![
+        Line::from(Span::styled("Terminal too small", Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(format!("{}x{} is needed, this is {}x{}.", MIN_WIDTH, MIN_HEIGHT, area.width, area.height)),
+        Line::from(Span::styled("Enlarge the window; the fight waits.", Styles::dim())),
+    ];
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let text = Rect::new(area.x, area.y + top, area.width, area.height - top);
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center).wrap(Wrap { trim: true }), text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizes_are_classed_and_narrow_panels_stack() {
+        assert_eq!(fit(Rect::new(0, 0, 59, 40)), Fit::TooSmall);
+        assert_eq!(fit(Rect::new(0, 0, 120, 19)), Fit::TooSmall);
+        assert_eq!(fit(Rect::new(0, 0, 70, 30)), Fit::Compact);
+        assert_eq!(fit(Rect::new(0, 0, 80, 24)), Fit::Full);
+
+        let wide = beside(Rect::new(0, 0, 100, 20), [Constraint::Percentage(40), Constraint::Percentage(60)]);
+        assert_eq!((wide[0].y, wide[1].y), (0, 0));
+        let stacked = beside(Rect::new(0, 0, 60, 20), [Constraint::Percentage(40), Constraint::Percentage(60)]);
+        assert_eq!((stacked[0].x, stacked[1].x), (0, 0));
+        assert!(stacked[1].y > 0);
+
+        assert!(art_fits(" /\\\n/__\\", Rect::new(0, 0, 6, 5)));
+        assert!(!art_fits(" /\\\n/__\\", Rect::new(0, 0, 6, 4)));
+        assert!(!art_fits(" /\\\n/__\\", Rect::new(0, 0, 5, 5)));
+        assert!(art_fits(&"\n|\n".repeat(12), Rect::new(0, 0, 6, 10)));
+    }
+
+    #[test]
+    fn test_rows_go_by_priority_and_panels_fold_away() {
+        // Typing, status, enemy with art, battle log
+        let panels = [(5, 5), (3, 3), (9, 3), (5, 3)];
+        assert_eq!(allot(30, &panels), [5, 3, 9, 5]);
+        assert_eq!(allot(20, &panels), [5, 3, 9, 3]);
+        assert_eq!(allot(16, &panels), [5, 3, 3, 5]);
+        assert_eq!(allot(12, &panels), [5, 3, 3, 0]);
+        assert_eq!(allot(4, &panels), [0, 3, 0, 0]);
+    }
+}
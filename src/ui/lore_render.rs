@@ -3,23 +3,25 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::Span,
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap},
     Frame,
 };
+use crate::game::comprehension::ComprehensionCheck;
+use crate::game::endings::MysteryEnding;
 use crate::game::state::GameState;
-use super::theme::Palette;
+use super::theme::{Palette, Styles};
 
 /// Render a lore discovery popup - atmospheric and mysterious
 pub fn render_lore_discovery(f: &mut Frame, state: &GameState) {
     let area = f.area();
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(10, 10, 15)));
+    let bg = Styles::block().style(Style::default().bg(Color::Rgb(10, 10, 15)));
     f.render_widget(bg, area);
     
     let popup_width = area.width.min(70);
     let popup_height = area.height.min(20);
     let popup_area = Rect::new((area.width - popup_width) / 2, (area.height - popup_height) / 2, popup_width, popup_height);
-    let clear = Block::default().style(Style::default().bg(Palette::BG_PANEL));
+    let clear = Styles::block().style(Style::default().bg(Palette::current().bg_panel));
     f.render_widget(clear, popup_area);
     
     if let Some((title, content)) = &state.current_lore {
@@ -30,24 +32,68 @@ pub fn render_lore_discovery(f: &mut Frame, state: &GameState) {
         let title_widget = Paragraph::new(title_text)
             .style(Style::default().fg(Color::Rgb(255, 215, 0)).add_modifier(Modifier::BOLD))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Rgb(255, 215, 0))).border_type(BorderType::Double));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Color::Rgb(255, 215, 0))).border_type(BorderType::Double));
         f.render_widget(title_widget, chunks[0]);
         
+        // Once the question is up the passage is hidden - answer from memory
+        if let Some(check) = &state.comprehension {
+            render_comprehension(f, check, chunks[1], chunks[2]);
+            return;
+        }
+        
         let content_widget = Paragraph::new(content.clone())
-            .style(Style::default().fg(Palette::TEXT).add_modifier(Modifier::ITALIC))
+            .style(Style::default().fg(Palette::current().text).add_modifier(Modifier::ITALIC))
             .alignment(Alignment::Left).wrap(Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Palette::TEXT_DIM)).padding(Padding::horizontal(1)));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().text_dim)).padding(Padding::horizontal(1)));
         f.render_widget(content_widget, chunks[1]);
         
-        let hint = Paragraph::new("[ Press any key to continue ]").style(Style::default().fg(Palette::TEXT_DIM)).alignment(Alignment::Center);
+        let hint = Paragraph::new("[ Press any key to continue ]").style(Style::default().fg(Palette::current().text_dim)).alignment(Alignment::Center);
         f.render_widget(hint, chunks[2]);
     }
 }
 
+/// Render the comprehension question, the typed answer, and the verdict
+fn render_comprehension(f: &mut Frame, check: &ComprehensionCheck, body: Rect, hint_area: Rect) {
+    let mut lines = vec![
+        Line::from(Span::styled(check.question.question, Style::default().fg(Palette::current().text).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Palette::current().warning)),
+            Span::styled(check.input.as_str(), Style::default().fg(Color::White)),
+            Span::styled(if check.is_answered() { "" } else { "_" }, Style::default().fg(Palette::current().text_dim)),
+        ]),
+        Line::from(""),
+    ];
+    match check.correct {
+        Some(true) => {
+            lines.push(Line::from(Span::styled("✓ You remember.", Style::default().fg(Palette::current().success).add_modifier(Modifier::BOLD))));
+            lines.push(Line::from(Span::styled(check.question.insight, Style::default().fg(Color::Rgb(255, 215, 0)).add_modifier(Modifier::ITALIC))));
+        }
+        Some(false) => {
+            let answer = check.question.answers.first().copied().unwrap_or("");
+            lines.push(Line::from(Span::styled(format!("✗ The answer was: {}", answer), Style::default().fg(Palette::current().danger))));
+        }
+        None => {}
+    }
+    
+    let widget = Paragraph::new(lines)
+        .alignment(Alignment::Left).wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).title(" What do you remember? ").border_style(Style::default().fg(Palette::current().text_dim)).padding(Padding::horizontal(1)));
+    f.render_widget(widget, body);
+    
+    let hint_text = if check.is_answered() {
+        "[ Press any key to continue ]"
+    } else {
+        "[ Type your answer · Enter to answer · Esc to skip ]"
+    };
+    let hint = Paragraph::new(hint_text).style(Style::default().fg(Palette::current().text_dim)).alignment(Alignment::Center);
+    f.render_widget(hint, hint_area);
+}
+
 /// Render a milestone story event - dramatic and important
 pub fn render_milestone(f: &mut Frame, state: &GameState) {
     let area = f.area();
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 10)));
+    let bg = Styles::block().style(Style::default().bg(Color::Rgb(5, 5, 10)));
     f.render_widget(bg, area);
     
     let popup_width = area.width.min(80);
@@ -63,13 +109,13 @@ pub fn render_milestone(f: &mut Frame, state: &GameState) {
         let title_widget = Paragraph::new(title)
             .style(Style::default().fg(Color::Rgb(220, 20, 60)).add_modifier(Modifier::BOLD))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Rgb(139, 0, 0))).border_type(BorderType::Thick));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Color::Rgb(139, 0, 0))).border_type(BorderType::Thick));
         f.render_widget(title_widget, chunks[0]);
         
         let content_widget = Paragraph::new(milestone_text.clone())
-            .style(Style::default().fg(Palette::TEXT))
+            .style(Style::default().fg(Palette::current().text))
             .alignment(Alignment::Center).wrap(Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Rgb(100, 0, 0))).padding(Padding::uniform(1)));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Color::Rgb(100, 0, 0))).padding(Padding::uniform(1)));
         f.render_widget(content_widget, chunks[1]);
         
         let hint = Paragraph::new("[ Press ENTER to face your destiny ]")
@@ -78,3 +124,48 @@ pub fn render_milestone(f: &mut Frame, state: &GameState) {
         f.render_widget(hint, chunks[2]);
     }
 }
+
+/// Render the final sequence of a mystery ending, one page at a time
+pub fn render_finale(f: &mut Frame, state: &GameState) {
+    let Some(finale) = &state.finale else {
+        return;
+    };
+    let area = f.area();
+    let (bg, accent) = match finale.ending {
+        MysteryEnding::ThirdGrammar => (Color::Rgb(8, 8, 20), Color::Rgb(180, 140, 255)),
+        MysteryEnding::FinalRest => (Color::Rgb(12, 10, 5), Color::Rgb(255, 200, 120)),
+        MysteryEnding::DarkAscension => (Color::Rgb(3, 0, 5), Color::Rgb(160, 30, 90)),
+    };
+    f.render_widget(Styles::block().style(Style::default().bg(bg)), area);
+
+    let popup_width = area.width.min(76);
+    let popup_height = area.height.min(20);
+    let popup_area = Rect::new((area.width - popup_width) / 2, (area.height - popup_height) / 2, popup_width, popup_height);
+    let chunks = Layout::default().direction(Direction::Vertical).margin(1)
+        .constraints([Constraint::Length(3), Constraint::Min(8), Constraint::Length(2)]).split(popup_area);
+
+    let title = format!("═══ {} ═══", finale.ending.id_title().1.to_uppercase());
+    let title_widget = Paragraph::new(title)
+        .style(Style::default().fg(accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(accent)).border_type(BorderType::Double));
+    f.render_widget(title_widget, chunks[0]);
+
+    let text_style = if finale.is_last() {
+        Style::default().fg(accent).add_modifier(Modifier::ITALIC)
+    } else {
+        Style::default().fg(Palette::current().text)
+    };
+    let page = Paragraph::new(finale.current().to_string())
+        .style(text_style)
+        .alignment(Alignment::Center).wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(accent)).padding(Padding::uniform(1)));
+    f.render_widget(page, chunks[1]);
+
+    let hint = Line::from(vec![
+        Span::styled(format!("{}/{}  ", finale.page + 1, finale.pages.len()), Style::default().fg(Color::DarkGray)),
+        Span::styled(if finale.is_last() { "[Enter] The end" } else { "[Enter] Continue" }, Style::default().fg(accent)),
+        Span::styled("  [Esc] Skip", Style::default().fg(Color::DarkGray)),
+    ]);
+    f.render_widget(Paragraph::new(hint).alignment(Alignment::Center), chunks[2]);
+}
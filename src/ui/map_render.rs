@@ -0,0 +1,167 @@
+//! Floor map rendering - the rooms of a floor and the paths between them
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use crate::game::dungeon::{Dungeon, RoomType};
+use super::theme::{Icons, Palette, Styles};
+
+pub fn room_icon(room_type: RoomType) -> &'static str {
+    match room_type {
+        RoomType::Combat => Icons::current().combat,
+        RoomType::Elite => Icons::current().skull,
+        RoomType::Boss => Icons::current().crown,
+        RoomType::Treasure => Icons::current().treasure,
+        RoomType::Rest => Icons::current().rest,
+        RoomType::Shop => Icons::current().shop,
+        RoomType::Event => Icons::current().event,
+        RoomType::Mystery => Icons::current().mystery,
+        RoomType::Start => Icons::current().door,
+    }
+}
+
+/// The floor map: the way on at the top, the entrance at the bottom
+pub fn render_floor_map(f: &mut Frame, dungeon: &Dungeon, area: Rect) {
+    let width = area.width.saturating_sub(2) as usize;
+    let map = &dungeon.floor_map;
+    let cleared = dungeon.rooms_cleared.max(0) as usize;
+    let choices = dungeon.route_choices();
+    let picked = dungeon.chosen_route();
+
+    let mut lines = vec![route_line(dungeon, &choices, picked)];
+    if let Some(here) = dungeon.here_room() {
+        lines.push(Line::from(Span::styled(format!("You are at {}", here.label()), Styles::dim())));
+    }
+
+    let stairs_style = if cleared >= map.depth() { Styles::success() } else { Styles::dim() };
+    lines.push(Line::from(Span::styled(format!("{} Stairs down", Icons::current().arrow_down), stairs_style)));
+
+    for row in (0..map.depth()).rev() {
+        if row + 1 < map.depth() {
+            lines.push(path_line(dungeon, row, width));
+        }
+        let mut cells = vec![(" ".to_string(), Style::default()); width];
+        let count = map.rows[row].len();
+        for (col, node) in map.rows[row].iter().enumerate() {
+            let style = if map.on_path(row, col) && row < cleared {
+                Styles::success()
+            } else if row == cleared && picked == Some(col) {
+                Style::default().fg(Palette::current().combo).add_modifier(Modifier::BOLD)
+            } else if row == cleared && choices.contains(&col) {
+                Styles::normal()
+            } else {
+                Styles::dim()
+            };
+            let (open, close) = if row == cleared && choices.contains(&col) { ("[", "]") } else { (" ", " ") };
+            let x = node_x(col, count, width);
+            put(&mut cells, x.wrapping_sub(1), open, style);
+            put(&mut cells, x, room_icon(node.room_type), style);
+            put(&mut cells, x + 1, close, style);
+            if let Some(rumor) = dungeon.rumors.iter().find(|r| r.marks(dungeon.current_floor, row, col, node.room_type)) {
+                put(&mut cells, x + 2, rumor.certainty.mark(), Style::default().fg(Palette::current().warning));
+            }
+        }
+        lines.push(to_line(cells));
+    }
+
+    // What has been heard of this floor and those below it
+    for rumor in dungeon.rumors.iter().filter(|r| r.subject.floor() >= dungeon.current_floor) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", rumor.certainty.mark()), Style::default().fg(Palette::current().warning)),
+            Span::styled(format!("{} ({} {})", rumor.text(), rumor.certainty.name(), rumor.source), Styles::dim()),
+        ]));
+    }
+    for prophecy in dungeon.prophecies.iter().filter(|p| !p.tested) {
+        lines.push(Line::from(vec![
+            Span::styled("󰜎 ", Style::default().fg(Palette::current().secondary)),
+            Span::styled(format!("{} (a Tomorrow Text)", prophecy.text()), Styles::dim()),
+        ]));
+    }
+
+    let block = Styles::block()
+        .borders(Borders::ALL)
+        .title(Span::styled(format!(" {} Floor {} Map ", Icons::current().map, dungeon.current_floor), Style::default().fg(Palette::current().primary)));
+    let paragraph = Paragraph::new(lines).alignment(Alignment::Left).block(block);
+    f.render_widget(paragraph, area);
+}
+
+/// The rooms the player can take next, numbered for picking
+fn route_line(dungeon: &Dungeon, choices: &[usize], picked: Option<usize>) -> Line<'static> {
+    let row = dungeon.rooms_cleared.max(0) as usize;
+    if choices.is_empty() {
+        return Line::from(Span::styled("The floor is cleared - the stairs lead down", Styles::success()));
+    }
+    let mut spans = vec![Span::styled("Paths ahead: ", Styles::dim())];
+    for (i, &col) in choices.iter().enumerate() {
+        let room_type = dungeon.floor_map.rows[row][col].room_type;
+        let style = if picked == Some(col) {
+            Style::default().fg(Palette::current().combo).add_modifier(Modifier::BOLD)
+        } else {
+            Styles::normal()
+        };
+        spans.push(Span::styled(format!("[{}] {} {}  ", i + 1, room_icon(room_type), room_type.name()), style));
+    }
+    Line::from(spans)
+}
+
+/// Paths from `row` up to the row after it
+fn path_line(dungeon: &Dungeon, row: usize, width: usize) -> Line<'static> {
+    let map = &dungeon.floor_map;
+    let (here, ahead) = (map.rows[row].len(), map.rows[row + 1].len());
+    let mut cells = vec![(" ".to_string(), Style::default()); width];
+    // Paths on the route are drawn last so they show through crossings
+    for taken in [false, true] {
+        for (col, node) in map.rows[row].iter().enumerate() {
+            for &target in &node.next {
+                let on_route = map.on_path(row, col) && map.on_path(row + 1, target);
+                if on_route != taken {
+                    continue;
+                }
+                let (from, to) = (node_x(col, here, width), node_x(target, ahead, width));
+                let glyph = if to > from + 1 {
+                    "╱"
+                } else if to + 1 < from {
+                    "╲"
+                } else {
+                    "│"
+                };
+                let style = if taken { Styles::success() } else { Style::default().fg(Palette::current().border) };
+                put(&mut cells, (from + to) / 2, glyph, style);
+            }
+        }
+    }
+    to_line(cells)
+}
+
+/// Column of the `col`th of `count` rooms spread across `width`
+fn node_x(col: usize, count: usize, width: usize) -> usize {
+    (2 * col + 1) * width / (2 * count.max(1))
+}
+
+fn put(cells: &mut [(String, Style)], x: usize, text: &str, style: Style) {
+    if let Some(cell) = cells.get_mut(x) {
+        *cell = (text.to_string(), style);
+    }
+}
+
+/// Join runs of equally styled cells into spans
+fn to_line(cells: Vec<(String, Style)>) -> Line<'static> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut run = String::new();
+    let mut run_style = Style::default();
+    for (text, style) in cells {
+        if style != run_style && !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_style = style;
+        run.push_str(&text);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, run_style));
+    }
+    Line::from(spans)
+}
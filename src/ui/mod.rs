@@ -3,8 +3,15 @@
 
 pub mod render;
 pub mod theme;
+pub mod themes;
+pub mod icon_fallback;
+pub mod screen_reader;
+pub mod layout;
 pub mod lore_render;
 pub mod effects;
+pub mod particles;
 pub mod combat_render;
 pub mod spell_ui;
 pub mod stats_summary;
+pub mod replay_render;
+pub mod map_render;
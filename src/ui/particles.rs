@@ -0,0 +1,245 @@
+//! Particles - Sparks, motes and dust drawn over the fight
+//!
+//! A handful of characters that live for a moment and move as they live:
+//! - a critical hit throws sparks out from the enemy
+//! - at the Void's Edge and in the Breach, corruption motes drift up
+//!   through the fight
+//! - in the Shattered Halls, dust sifts down from the ruined ceilings
+//!
+//! Particles are drawn only on blank cells, so they never cover a word.
+//! Reduced motion (`p` on the title screen) turns them off, along with
+//! the screen shake, for slow terminals and for anyone motion bothers.
+//!
+//! Design: a particle lives in fractions of the area it is drawn in, as
+//! the floating texts do, so the field knows nothing of the terminal. It is
+//! stepped by the clock rather than by frames, and never holds more than
+//! `MAX_PARTICLES`, so a slow terminal draws fewer rather than falling
+//! behind.
+
+use std::time::Instant;
+
+use rand::prelude::*;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+};
+
+use super::theme::Palette;
+use crate::game::world_integration::FloorZone;
+
+/// Most particles alive at once
+pub const MAX_PARTICLES: usize = 60;
+
+/// Sparks thrown by a critical hit
+const SPARKS: usize = 10;
+
+/// A character that moves and fades
+#[derive(Debug, Clone)]
+pub struct Particle {
+    /// Position, as fractions of the area
+    pub x: f32,
+    pub y: f32,
+    /// Velocity, in fractions of the area a second
+    pub vx: f32,
+    pub vy: f32,
+    /// Pull downward, in fractions of the area a second, a second
+    pub gravity: f32,
+    pub glyph: char,
+    pub color: Color,
+    pub age_ms: u64,
+    pub life_ms: u64,
+}
+
+impl Particle {
+    fn is_dead(&self) -> bool {
+        self.age_ms >= self.life_ms || !(0.0..1.0).contains(&self.x) || !(0.0..1.0).contains(&self.y)
+    }
+}
+
+/// What drifts through a zone's fights
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weather {
+    Clear,
+    /// Corruption motes, rising
+    Motes,
+    /// Dust, falling
+    Dust,
+}
+
+impl Weather {
+    pub fn of_zone(zone: FloorZone) -> Self {
+        match zone {
+            FloorZone::VoidsEdge | FloorZone::TheBreach => Self::Motes,
+            FloorZone::ShatteredHalls => Self::Dust,
+            _ => Self::Clear,
+        }
+    }
+
+    /// Particles born a second
+    fn rate(&self) -> f32 {
+        match self {
+            Self::Clear => 0.0,
+            Self::Motes => 4.0,
+            Self::Dust => 5.0,
+        }
+    }
+
+    fn particle<R: Rng>(&self, rng: &mut R) -> Particle {
+        let x = rng.gen_range(0.0..1.0);
+        match self {
+            Self::Motes => Particle {
+                x,
+                y: rng.gen_range(0.3..0.99),
+                vx: rng.gen_range(-0.02..0.02),
+                vy: -rng.gen_range(0.03..0.08),
+                gravity: 0.0,
+                glyph: *['∙', '°', '⁘', '·'].choose(rng).unwrap_or(&'·'),
+                color: Palette::current().corrupted,
+                age_ms: 0,
+                life_ms: rng.gen_range(2500..4500),
+            },
+            _ => Particle {
+                x,
+                y: rng.gen_range(0.0..0.2),
+                vx: rng.gen_range(-0.01..0.01),
+                vy: rng.gen_range(0.04..0.09),
+                gravity: 0.0,
+                glyph: *['.', '·', ','].choose(rng).unwrap_or(&'.'),
+                color: Palette::current().text_dim,
+                age_ms: 0,
+                life_ms: rng.gen_range(3000..6000),
+            },
+        }
+    }
+}
+
+/// The particles alive over the fight
+#[derive(Debug, Clone, Default)]
+pub struct ParticleField {
+    pub particles: Vec<Particle>,
+    last_step: Option<Instant>,
+    /// Particles owed by the weather, less than one so far
+    owed: f32,
+}
+
+impl ParticleField {
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.owed = 0.0;
+    }
+
+    fn add(&mut self, particle: Particle) {
+        if self.particles.len() < MAX_PARTICLES {
+            self.particles.push(particle);
+        }
+    }
+
+    /// Throw sparks out from (`x`, `y`)
+    pub fn sparks<R: Rng>(&mut self, x: f32, y: f32, rng: &mut R) {
+        for _ in 0..SPARKS {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = rng.gen_range(0.15..0.4);
+            self.add(Particle {
+                x,
+                y,
+                // Cells are twice as tall as they are wide
+                vx: angle.cos() * speed,
+                vy: angle.sin() * speed * 0.5,
+                gravity: 0.5,
+                glyph: *['*', '+', '✦', '·'].choose(rng).unwrap_or(&'*'),
+                color: if rng.gen_bool(0.5) { Palette::current().warning } else { Palette::current().legendary },
+                age_ms: 0,
+                life_ms: rng.gen_range(350..700),
+            });
+        }
+    }
+
+    /// Move and age every particle to `now`, and let the weather add more
+    pub fn step<R: Rng>(&mut self, now: Instant, weather: Weather, rng: &mut R) {
+        let elapsed = self.last_step.map_or(0, |last| now.saturating_duration_since(last).as_millis() as u64);
+        self.last_step = Some(now);
+        // A long gap (a pause, a menu) is not played back all at once
+        let ms = elapsed.min(250);
+        let dt = ms as f32 / 1000.0;
+        for p in &mut self.particles {
+            p.vy += p.gravity * dt;
+            p.x += p.vx * dt;
+            p.y += p.vy * dt;
+            p.age_ms += ms;
+        }
+        self.particles.retain(|p| !p.is_dead());
+
+        self.owed += weather.rate() * dt;
+        while self.owed >= 1.0 {
+            self.owed -= 1.0;
+            let particle = weather.particle(rng);
+            self.add(particle);
+        }
+    }
+}
+
+/// Draw the particles over `area`, on blank cells only
+pub fn render(buffer: &mut Buffer, area: Rect, field: &ParticleField) {
+    for p in &field.particles {
+        let x = area.x + (p.x * area.width as f32) as u16;
+        let y = area.y + (p.y * area.height as f32) as u16;
+        let Some(cell) = buffer.cell_mut((x, y)) else {
+            continue;
+        };
+        if cell.symbol() != " " {
+            continue;
+        }
+        let mut style = Style::default().fg(p.color);
+        if p.age_ms * 3 > p.life_ms * 2 {
+            style = style.add_modifier(Modifier::DIM);
+        }
+        cell.set_char(p.glyph).set_style(style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_particles_move_fade_and_die_and_the_weather_keeps_coming() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut field = ParticleField::default();
+        let start = Instant::now();
+        field.step(start, Weather::Clear, &mut rng);
+        field.sparks(0.5, 0.3, &mut rng);
+        assert_eq!(field.particles.len(), SPARKS);
+        field.step(start + Duration::from_millis(100), Weather::Clear, &mut rng);
+        assert!(field.particles.iter().all(|p| p.x != 0.5 || p.y != 0.3));
+        for i in 2..10 {
+            field.step(start + Duration::from_millis(i * 100), Weather::Clear, &mut rng);
+        }
+        assert!(field.particles.is_empty());
+
+        for i in 10..30 {
+            field.step(start + Duration::from_millis(i * 100), Weather::Dust, &mut rng);
+        }
+        assert!(field.particles.len() >= 9);
+        assert!(field.particles.iter().all(|p| p.vy > 0.0));
+        for _ in 0..MAX_PARTICLES {
+            field.sparks(0.5, 0.5, &mut rng);
+        }
+        assert_eq!(field.particles.len(), MAX_PARTICLES);
+        assert_eq!(Weather::of_zone(FloorZone::TheBreach), Weather::Motes);
+    }
+
+    #[test]
+    fn test_particles_are_drawn_only_on_blank_cells() {
+        let mut field = ParticleField::default();
+        let dust = |x| Particle { x, y: 0.0, vx: 0.0, vy: 0.0, gravity: 0.0, glyph: '.', color: Color::Gray, age_ms: 0, life_ms: 1000 };
+        field.particles = vec![dust(0.0), dust(0.5)];
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(2, 0, "ab", Style::default());
+        render(&mut buffer, area, &field);
+        let drawn: String = buffer.content.iter().map(|c| c.symbol()).collect();
+        assert_eq!(drawn, ". ab");
+    }
+}
@@ -4,16 +4,56 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap, Clear, Tabs},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Gauge, List, ListItem, Paragraph, Sparkline, Wrap, Clear, Tabs},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 use crate::game::state::{GameState, Scene};
 use crate::game::combat::CombatPhase;
+use crate::game::map_travel::MapPromptMode;
+use crate::game::consequences::ChangeKind;
+use crate::game::challenges;
+use crate::game::codex::{self, CodexSection};
+use crate::game::item_lore;
+use crate::game::letters::{CIPHER_SHIFT, DECODE_GOLD};
+use crate::game::cipher_notes::{BREAK_STANDING, HINT_COST};
+use crate::game::memories::{self, MEMORIES, WHOLE_CHAPTER_HP};
+use crate::game::transcript::{Source, TranscriptView};
+use crate::game::ideology::{Ideology, Pole};
+use crate::game::endings;
+use crate::game::latency::CALIBRATION_BEATS;
+use crate::game::key_accuracy::{KeyRecord, MIN_PRESSES};
+use crate::game::stats_store::{Tally, ATTACKS};
+use crate::game::settings::Setting;
+use crate::game::keybinds::{Action, Context};
+use crate::game::pause::PauseOption;
+use crate::game::training::Lesson;
+use crate::game::player_avatar::PlayerClass;
 use crate::game::help_system::{HelpSystem, HelpTab, TipPriority};
-use crate::ui::theme::{Palette, Icons, Styles, hp_color, combo_color, wpm_color, accuracy_color, zone_color};
-use crate::ui::lore_render::{render_lore_discovery, render_milestone};
+use crate::game::artifacts::Relic;
+use crate::game::skills::SkillTreeType;
+use crate::game::ascension;
+use crate::game::encounter_runner::{paragraphs, EncounterRun, EncounterStage};
+use crate::game::naming_ritual::{self, Ritual};
+use crate::game::unreliable_narrator::{self, Told};
+use crate::game::lore_fragments::LoreJournal;
+use crate::game::dreams::DreamStage;
+use crate::game::leveling::LevelUpChoice;
+use crate::game::world_state::DEEPEST_DESCENT;
+use crate::game::typing_impact::{graphemes, graphemes_match, grapheme_len, AttackType};
+use crate::ui::{icon_fallback, screen_reader, themes};
+use crate::ui::layout::{self, Fit};
+use crate::ui::theme::{self, Palette, Icons, Styles, hp_color, combo_color, wpm_color, accuracy_color, zone_color};
+use crate::ui::lore_render::{render_finale, render_lore_discovery, render_milestone};
+use crate::ui::map_render::render_floor_map;
 
 pub fn render(f: &mut Frame, state: &GameState) {
+    // Nothing fits a terminal this small, so nothing else is drawn
+    if layout::fit(f.area()) == Fit::TooSmall {
+        layout::render_too_small(f);
+        return;
+    }
+
     // Render the main scene
     match state.scene {
         Scene::Title => render_title(f, state),
@@ -31,6 +71,34 @@ pub fn render(f: &mut Frame, state: &GameState) {
         Scene::Lore => render_lore_discovery(f, state),
         Scene::Milestone => render_milestone(f, state),
         Scene::Upgrades => render_upgrades(f, state),
+        Scene::Skills => render_skills(f, state),
+        Scene::Finale => render_finale(f, state),
+        Scene::Journal => render_journal(f, state),
+        Scene::Codex => render_codex(f, state),
+        Scene::ClueBoard => render_clue_board(f, state),
+        Scene::Mailbox => render_mailbox(f, state),
+        Scene::Ciphers => render_ciphers(f, state),
+        Scene::Memories => render_memories(f, state),
+        Scene::Ideology => render_ideology(f, state),
+        Scene::Themes => render_themes(f, state),
+        Scene::KeyHeatmap => render_key_heatmap(f, state),
+        Scene::Dashboard => render_dashboard(f, state),
+        Scene::Settings => render_settings(f, state),
+        Scene::Keybinds => render_keybinds(f, state),
+        Scene::Pause => {
+            crate::ui::combat_render::render_combat_enhanced(f, state);
+            render_pause_menu(f, state);
+        }
+        Scene::Training => render_training(f, state),
+        Scene::Encounter => render_encounter(f, state),
+        Scene::LevelUp => render_level_up(f, state),
+        Scene::RouteChoice => render_route_choice(f, state),
+        Scene::Dream => render_dream(f, state),
+        Scene::Conversation => render_conversation(f, state),
+        Scene::Consequences => render_consequences(f, state),
+        Scene::Challenges => render_challenges(f, state),
+        Scene::Replay => crate::ui::replay_render::render_replay(f, state),
+        Scene::Calibration => render_calibration(f, state),
         Scene::BattleSummary => {
             if let Some(summary) = &state.current_battle_summary {
                 crate::ui::stats_summary::render_battle_summary(f, summary);
@@ -38,13 +106,323 @@ pub fn render(f: &mut Frame, state: &GameState) {
         },
     }
     
+    // Auto-pause / resume countdown sits above the scene
+    if state.auto_pause.is_active() {
+        render_pause_overlay(f, state);
+    }
+    
     // Render help overlay on top if visible
     if state.help_system.visible {
         render_help_overlay(f, &state.help_system, state);
     }
     
+    if let Some(view) = &state.transcript_view {
+        render_transcript(f, state, view);
+    }
+    
     // Always render bottom bar with hint or help reminder
     render_bottom_bar(f, state);
+    
+    if state.dev_console.visible {
+        render_dev_console(f, state);
+    }
+    
+    if state.config.display.screen_reader {
+        screen_reader::strip_art(f.buffer_mut());
+        screen_reader::render(f, state);
+    }
+    
+    // Whatever the terminal can't draw is swapped for what it can
+    icon_fallback::sweep(f.buffer_mut(), theme::icon_style());
+}
+
+/// Break `text` into rows no wider than `width`, between words where it can
+fn wrap_rows(text: &str, width: usize) -> Vec<String> {
+    let mut rows = vec![String::new()];
+    for word in text.split_whitespace() {
+        let row = rows.last_mut().expect("rows start with one");
+        if !row.is_empty() && row.width() + 1 + word.width() > width {
+            rows.push(String::new());
+        }
+        let row = rows.last_mut().expect("rows start with one");
+        if !row.is_empty() {
+            row.push(' ');
+        }
+        row.push_str(word);
+    }
+    rows
+}
+
+/// Render the session transcript over whatever scene is showing
+fn render_transcript(f: &mut Frame, state: &GameState, view: &TranscriptView) {
+    let area = f.area();
+    let popup = Rect::new(area.width / 20, area.height / 20, area.width - area.width / 10, area.height - area.height / 10);
+    f.render_widget(Clear, popup);
+
+    let filters = state.transcript.filters();
+    let filter = &filters[view.filter.min(filters.len() - 1)];
+    let block = Styles::block()
+        .title(Span::styled(format!(" 󰦨 TRANSCRIPT · {} ", filter.name()), Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)))
+        .title_alignment(Alignment::Center)
+        .title_bottom(Line::from(vec![
+            Span::styled(" [j/k/PgUp/PgDn] ", Styles::keybind()),
+            Span::raw("Scroll  "),
+            Span::styled("[←/→] ", Styles::keybind()),
+            Span::raw("Filter  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Close "),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Palette::current().border))
+        .style(Style::default().bg(Palette::current().bg_panel));
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    // Lines up to the scroll point, wrapped, newest at the bottom
+    let entries: Vec<_> = state.transcript.lines(filter).collect();
+    let shown = &entries[..entries.len().saturating_sub(view.scroll)];
+    let mut rows: Vec<Line> = Vec::new();
+    for entry in shown.iter().rev() {
+        let color = match entry.source {
+            Source::Message => Palette::current().text,
+            Source::Dialogue => Palette::current().accent,
+            Source::Encounter => Palette::current().secondary,
+            Source::Combat => Palette::current().warning,
+        };
+        let head = match &entry.speaker {
+            Some(speaker) => format!("{} {}: ", entry.stamp(), speaker),
+            None => format!("{} ", entry.stamp()),
+        };
+        let width = (inner.width as usize).saturating_sub(head.width()).max(10);
+        let mut wrapped: Vec<Line> = wrap_rows(&entry.text, width).into_iter().enumerate().map(|(i, row)| {
+            let lead = if i == 0 {
+                Span::styled(head.clone(), Style::default().fg(color).add_modifier(Modifier::BOLD))
+            } else {
+                Span::raw(" ".repeat(head.width()))
+            };
+            Line::from(vec![lead, Span::styled(row, Style::default().fg(Palette::current().text))])
+        }).collect();
+        wrapped.reverse();
+        rows.extend(wrapped);
+        if rows.len() >= inner.height as usize {
+            break;
+        }
+    }
+    rows.truncate(inner.height as usize);
+    rows.reverse();
+    if rows.is_empty() {
+        rows.push(Line::from(Span::styled("Nothing has been said yet.", Styles::dim())));
+    }
+    f.render_widget(Paragraph::new(rows), inner);
+}
+
+/// Render the author console across the top half of the screen
+fn render_dev_console(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let console_area = Rect::new(0, 0, area.width, (area.height / 2).max(6).min(area.height));
+    f.render_widget(Clear, console_area);
+    
+    // Leave room for borders and the prompt line
+    let visible_rows = console_area.height.saturating_sub(3) as usize;
+    let console = &state.dev_console;
+    let start = console.output.len().saturating_sub(visible_rows);
+    let mut lines: Vec<Line> = console.output[start..].iter()
+        .map(|l| Line::from(Span::styled(l.as_str(), Style::default().fg(Palette::current().text))))
+        .collect();
+    lines.push(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Palette::current().warning)),
+        Span::styled(console.input.as_str(), Style::default().fg(Color::White)),
+        Span::styled("_", Styles::dim()),
+    ]));
+    
+    let widget = Paragraph::new(lines)
+        .block(Styles::block()
+            .title(" dev console (F12) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .style(Style::default().bg(Color::Black)));
+    f.render_widget(widget, console_area);
+}
+
+/// Render the auto-pause notice or the 3-2-1 resume countdown
+fn render_pause_overlay(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let popup_width = 44.min(area.width);
+    let popup_height = 7.min(area.height);
+    let popup_area = Rect::new(
+        (area.width - popup_width) / 2,
+        (area.height - popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+    
+    f.render_widget(Clear, popup_area);
+    
+    let lines = if let Some(reason) = state.auto_pause.reason {
+        vec![
+            Line::from(""),
+            Line::from(Span::styled("󰏤 PAUSED", Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(reason.message(), Style::default().fg(Palette::current().text))),
+            Line::from(""),
+            Line::from(Span::styled("Press any key to resume", Styles::dim())),
+        ]
+    } else {
+        let count = state.auto_pause.countdown_value().unwrap_or(1);
+        vec![
+            Line::from(""),
+            Line::from(Span::styled("Resuming in", Style::default().fg(Palette::current().text))),
+            Line::from(""),
+            Line::from(Span::styled(count.to_string(), Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD))),
+        ]
+    };
+    
+    let popup = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black)));
+    f.render_widget(popup, popup_area);
+}
+
+/// Render the pause menu over the stopped fight
+fn render_pause_menu(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let palette = Palette::current();
+    let icons = Icons::current();
+    let popup_width = 60.min(area.width);
+    let popup_height = 16.min(area.height);
+    let popup_area = Rect::new(
+        (area.width - popup_width) / 2,
+        (area.height - popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+    f.render_widget(Clear, popup_area);
+
+    let mut lines = vec![
+        Line::from(Span::styled("󰏤 PAUSED", Style::default().fg(palette.warning).add_modifier(Modifier::BOLD))),
+    ];
+    // Where the fight stopped: the word timer, any window open, damage waiting
+    if let (Some(clock), Some(combat)) = (&state.paused, &state.combat_state) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} {} {}/{}", icons.skull, combat.enemy.name, combat.enemy.current_hp.max(0), combat.enemy.max_hp), Style::default().fg(palette.enemy_hp)),
+            Span::styled(format!("   {} {:.1}s left", icons.timer, clock.time_remaining), Style::default().fg(palette.text)),
+        ]));
+        let windows: Vec<String> = [
+            clock.riposte_ms.map(|ms| format!("Riposte {:.1}s", ms as f32 / 1000.0)),
+            clock.stagger_ms.map(|ms| format!("Stagger {:.1}s", ms as f32 / 1000.0)),
+            clock.study_ms.map(|ms| format!("Study {:.1}s", ms as f32 / 1000.0)),
+            clock.hazard_secs.map(|secs| format!("Hazard {:.1}s", secs)),
+            (clock.pending_heal > 0).then(|| format!("+{} heal", clock.pending_heal)),
+            (clock.pending_recoil > 0).then(|| format!("{} recoil", clock.pending_recoil)),
+            (clock.pending_hazard_damage > 0).then(|| format!("{} hazard damage", clock.pending_hazard_damage)),
+        ].into_iter().flatten().collect();
+        if !windows.is_empty() {
+            lines.push(Line::from(Span::styled(windows.join(" · "), Style::default().fg(palette.text_dim))));
+        }
+    }
+    lines.push(Line::from(""));
+
+    for (i, option) in PauseOption::ALL.iter().enumerate() {
+        let style = if i == state.menu_index {
+            Style::default().fg(palette.primary).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(palette.text)
+        };
+        let marker = if i == state.menu_index { icons.arrow_right } else { " " };
+        lines.push(Line::from(Span::styled(format!("{} {:<14}", marker, option.name()), style)));
+    }
+    lines.push(Line::from(""));
+
+    let selected = PauseOption::ALL.get(state.menu_index).copied().unwrap_or(PauseOption::Resume);
+    lines.push(if state.abandon_confirm {
+        Line::from(Span::styled("Give up this run? Enter to abandon, any other key to stay.", Style::default().fg(palette.danger).add_modifier(Modifier::BOLD)))
+    } else {
+        Line::from(Span::styled(selected.describe(), Styles::dim()))
+    });
+    lines.push(Line::from(vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Select  "),
+        Span::styled(" [Enter] ", Styles::keybind()),
+        Span::raw("Pick  "),
+        Span::styled(format!(" [Esc/{}] ", state.keybinds.label(Action::Pause)), Styles::keybind()),
+        Span::raw("Resume"),
+    ]));
+
+    let popup = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette.warning))
+            .style(Style::default().bg(palette.bg_dark)));
+    f.render_widget(popup, popup_area);
+}
+
+/// Trainer Beck between lessons: how the last went, and what comes next
+fn render_training(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let palette = Palette::current();
+    let icons = Icons::current();
+    let Some(training) = &state.training else {
+        return;
+    };
+
+    let header = match training.current() {
+        Some(lesson) => format!("THE TRAINING YARD — Lesson {}/{}: {}", training.lesson + 1, Lesson::ALL.len(), lesson.title()),
+        None => "THE TRAINING YARD".to_string(),
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(header, Styles::title())),
+        Line::from(""),
+    ];
+    if let Some(verdict) = training.verdict() {
+        let color = if training.last == Some(true) { palette.success } else { palette.warning };
+        lines.push(Line::from(Span::styled(format!("Beck: \"{}\"", verdict), Style::default().fg(color).add_modifier(Modifier::BOLD))));
+        lines.push(Line::from(""));
+    }
+
+    let mut hints = vec![Span::styled(" [Enter] ", Styles::keybind())];
+    match training.current() {
+        Some(lesson) => {
+            for line in lesson.briefing() {
+                lines.push(Line::from(Span::styled(format!("\"{}\"", line), Style::default().fg(palette.text))));
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(format!("{} {}", icons.arrow_right, lesson.goal()), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD))));
+            for action in lesson.actions() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!(" [{}] ", state.keybinds.label(*action)), Styles::keybind()),
+                    Span::styled(action.name(), Styles::dim()),
+                ]));
+            }
+            hints.extend([
+                Span::raw("Fight  "),
+                Span::styled(" [s] ", Styles::keybind()),
+                Span::raw("Skip lesson  "),
+                Span::styled(" [Esc] ", Styles::keybind()),
+                Span::raw("Skip training"),
+            ]);
+        }
+        None => {
+            lines.push(Line::from(Span::styled("\"That's all I've got. The rest, the dungeon teaches. Go pick your trade.\"", Style::default().fg(palette.text))));
+            hints.push(Span::raw("Choose your class"));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(hints));
+
+    let yard = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(palette.border))
+            .title(Span::styled(" Trainer Beck ", Style::default().fg(palette.warning).add_modifier(Modifier::BOLD))));
+    let width = 84.min(area.width);
+    let height = 30.min(area.height);
+    f.render_widget(yard, Rect::new((area.width - width) / 2, (area.height - height) / 2, width, height));
 }
 
 /// Render the help overlay as a centered popup
@@ -63,7 +441,7 @@ fn render_help_overlay(f: &mut Frame, help: &HelpSystem, state: &GameState) {
     f.render_widget(Clear, popup_area);
     
     // Main help block
-    let help_block = Block::default()
+    let help_block = Styles::block()
         .title(" 󰋗 HELP ")
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
@@ -93,8 +471,8 @@ fn render_help_overlay(f: &mut Frame, help: &HelpSystem, state: &GameState) {
     
     let tabs = Tabs::new(tab_titles)
         .select(help.active_tab.index())
-        .style(Style::default().fg(Palette::TEXT))
-        .highlight_style(Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(Palette::current().text))
+        .highlight_style(Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD))
         .divider("│");
     
     f.render_widget(tabs, chunks[0]);
@@ -154,23 +532,23 @@ fn render_help_contextual(f: &mut Frame, help: &HelpSystem, area: Rect) {
         let priority_color = match tip.priority {
             TipPriority::Essential => Color::Green,
             TipPriority::Important => Color::Yellow,
-            TipPriority::Advanced => Palette::ACCENT,
+            TipPriority::Advanced => Palette::current().accent,
             TipPriority::Secret => Color::Red,
         };
         
         lines.push(Line::from(vec![
             Span::styled(format!("  {} ", tip.icon), Style::default().fg(priority_color)),
-            Span::styled(tip.title, Style::default().fg(Palette::TEXT).add_modifier(Modifier::BOLD)),
+            Span::styled(tip.title, Style::default().fg(Palette::current().text).add_modifier(Modifier::BOLD)),
         ]));
         lines.push(Line::from(vec![
             Span::raw("     "),
-            Span::styled(tip.description, Style::default().fg(Palette::TEXT_DIM)),
+            Span::styled(tip.description, Style::default().fg(Palette::current().text_dim)),
         ]));
         lines.push(Line::from(""));
     }
     
     let content = Paragraph::new(lines)
-        .block(Block::default())
+        .block(Styles::block())
         .wrap(Wrap { trim: true });
     
     f.render_widget(content, area);
@@ -191,8 +569,8 @@ fn render_help_keybindings(f: &mut Frame, help: &HelpSystem, area: Rect) {
     lines.push(Line::from(Span::styled("  Global:", Styles::keybind())));
     for binding in bindings.iter().filter(|b| b.context.is_none()).skip(help.scroll_offset) {
         lines.push(Line::from(vec![
-            Span::styled(format!("    {:12}", binding.key), Style::default().fg(Palette::SUCCESS)),
-            Span::styled(binding.action, Style::default().fg(Palette::TEXT)),
+            Span::styled(format!("    {:12}", binding.key), Style::default().fg(Palette::current().success)),
+            Span::styled(binding.action, Style::default().fg(Palette::current().text)),
         ]));
     }
     
@@ -200,13 +578,13 @@ fn render_help_keybindings(f: &mut Frame, help: &HelpSystem, area: Rect) {
     lines.push(Line::from(Span::styled("  Context-Specific:", Styles::keybind())));
     for binding in bindings.iter().filter(|b| b.context.is_some()) {
         lines.push(Line::from(vec![
-            Span::styled(format!("    {:12}", binding.key), Style::default().fg(Palette::ACCENT)),
-            Span::styled(binding.action, Style::default().fg(Palette::TEXT)),
+            Span::styled(format!("    {:12}", binding.key), Style::default().fg(Palette::current().accent)),
+            Span::styled(binding.action, Style::default().fg(Palette::current().text)),
         ]));
     }
     
     let content = Paragraph::new(lines)
-        .block(Block::default())
+        .block(Styles::block())
         .wrap(Wrap { trim: true });
     
     f.render_widget(content, area);
@@ -229,7 +607,7 @@ fn render_help_objectives(f: &mut Frame, help: &HelpSystem, state: &GameState, a
     
     for objective in objectives {
         lines.push(Line::from(vec![
-            Span::styled(objective.clone(), Style::default().fg(Palette::TEXT)),
+            Span::styled(objective.clone(), Style::default().fg(Palette::current().text)),
         ]));
         lines.push(Line::from(""));
     }
@@ -237,16 +615,16 @@ fn render_help_objectives(f: &mut Frame, help: &HelpSystem, state: &GameState, a
     // Add mystery progress hint
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("─── Mystery ───", Style::default().fg(Palette::ACCENT).add_modifier(Modifier::BOLD)),
+        Span::styled("─── Mystery ───", Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
     ]));
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("  󰛓 ", Style::default().fg(Palette::ACCENT)),
+        Span::styled("  󰛓 ", Style::default().fg(Palette::current().accent)),
         Span::styled("\"The Threshold holds secrets yet unrevealed...\"", Styles::dim().add_modifier(Modifier::ITALIC)),
     ]));
     
     let content = Paragraph::new(lines)
-        .block(Block::default())
+        .block(Styles::block())
         .wrap(Wrap { trim: true });
     
     f.render_widget(content, area);
@@ -269,21 +647,21 @@ fn render_help_mechanics(f: &mut Frame, help: &HelpSystem, area: Rect) {
         }
         
         lines.push(Line::from(vec![
-            Span::styled(*title, Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD)),
+            Span::styled(*title, Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD)),
         ]));
         lines.push(Line::from(vec![
-            Span::styled(format!("  {}", subtitle), Style::default().fg(Palette::TEXT_DIM).add_modifier(Modifier::ITALIC)),
+            Span::styled(format!("  {}", subtitle), Style::default().fg(Palette::current().text_dim).add_modifier(Modifier::ITALIC)),
         ]));
         
         for detail in details {
             lines.push(Line::from(vec![
-                Span::styled(format!("    {}", detail), Style::default().fg(Palette::TEXT)),
+                Span::styled(format!("    {}", detail), Style::default().fg(Palette::current().text)),
             ]));
         }
     }
     
     let content = Paragraph::new(lines)
-        .block(Block::default())
+        .block(Styles::block())
         .wrap(Wrap { trim: true });
     
     f.render_widget(content, area);
@@ -300,7 +678,7 @@ fn render_bottom_bar(f: &mut Frame, state: &GameState) {
     let content = if let Some((icon, message)) = state.hint_manager.current_message() {
         Line::from(vec![
             Span::styled(format!(" {} ", icon), Styles::keybind()),
-            Span::styled(message, Style::default().fg(Palette::TEXT)),
+            Span::styled(message, Style::default().fg(Palette::current().text)),
         ])
     } else {
         // Default help reminder
@@ -313,7 +691,7 @@ fn render_bottom_bar(f: &mut Frame, state: &GameState) {
     };
     
     let bar = Paragraph::new(content)
-        .style(Style::default().bg(Palette::BG_PANEL));
+        .style(Style::default().bg(Palette::current().bg_panel));
     
     f.render_widget(bar, bar_area);
 }
@@ -324,12 +702,13 @@ fn render_title(f: &mut Frame, state: &GameState) {
     // Reserve bottom line for key hints
     let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
     let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let compact = layout::fit(area) == Fit::Compact;
     
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
         .constraints([
-            Constraint::Length(12),
+            Constraint::Length(if compact { 3 } else { 12 }),
             Constraint::Length(3),
             Constraint::Min(5),
         ])
@@ -355,18 +734,24 @@ fn render_title(f: &mut Frame, state: &GameState) {
 │                         v0.5.4  󰌌                                 │
 │  ◈═══════════════════════════════════════════════════════════◈  │
 ╰──────────────────────────────────────────────────────────────────╯╰──────────────────────────────────────────────────────────────────╯"#;
+    // Too small a terminal for the banner gets its words alone
+    let title_art = if compact {
+        "╭──────────────────────╮\n│ 󰌌 KEYBOARD WARRIOR 󰌌 │\n╰──────────────────────╯"
+    } else {
+        title_art
+    };
 
     let title = Paragraph::new(title_art)
-        .style(Style::default().fg(Palette::PRIMARY))
+        .style(Style::default().fg(Palette::current().primary))
         .alignment(Alignment::Center);
     f.render_widget(title, chunks[0]);
 
     // Subtitle with Dr. Baklava icon
     let subtitle = Paragraph::new(Line::from(vec![
-        Span::styled("󰩛 ", Style::default().fg(Palette::ACCENT)),
+        Span::styled("󰩛 ", Style::default().fg(Palette::current().accent)),
         Span::styled("A Roguelike Typing Adventure by Dr. Baklava", 
-            Style::default().fg(Palette::SECONDARY).add_modifier(Modifier::ITALIC)),
-        Span::styled(" 󰩛", Style::default().fg(Palette::ACCENT)),
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::ITALIC)),
+        Span::styled(" 󰩛", Style::default().fg(Palette::current().accent)),
     ]))
     .alignment(Alignment::Center);
     f.render_widget(subtitle, chunks[1]);
@@ -377,6 +762,8 @@ fn render_title(f: &mut Frame, state: &GameState) {
         ("󰂽", "Tutorial", "[T]"),
         ("󰙤", "Upgrades", "[U]"),
         ("󱪙", "Continue", "[C]"),
+        ("◈", "Consequences", "[W]"),
+        ("⚑", "Challenges", "[X]"),
         ("󰅖", "Quit", "[Q]"),
     ];
     
@@ -392,11 +779,11 @@ fn render_title(f: &mut Frame, state: &GameState) {
         .enumerate()
         .map(|(i, (icon, text, key))| {
             let (style, icon_color) = if i == state.menu_index {
-                (Style::default().fg(Palette::SECONDARY).add_modifier(Modifier::BOLD | Modifier::REVERSED),
-                 Palette::SECONDARY)
+                (Style::default().fg(Palette::current().secondary).add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                 Palette::current().secondary)
             } else {
-                (Style::default().fg(Palette::TEXT),
-                 Palette::PRIMARY)
+                (Style::default().fg(Palette::current().text),
+                 Palette::current().primary)
             };
             ListItem::new(Line::from(vec![
                 Span::styled(format!(" {} ", icon), Style::default().fg(icon_color)),
@@ -407,7 +794,7 @@ fn render_title(f: &mut Frame, state: &GameState) {
 
     let menu_title = format!(" 󰍜 Menu{} ", ink_display);
     let menu_widget = List::new(menu)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Palette::BORDER)).title(Span::styled(menu_title, Style::default().fg(Palette::PRIMARY))));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)).title(Span::styled(menu_title, Style::default().fg(Palette::current().primary))));
     f.render_widget(menu_widget, chunks[2]);
     
     // Key hints at bottom
@@ -418,11 +805,33 @@ fn render_title(f: &mut Frame, state: &GameState) {
         Span::raw("Select  "),
         Span::styled("[?] ", Style::default().fg(Color::Cyan)),
         Span::raw("Help  "),
-        Span::styled("[q] ", Style::default().fg(Palette::DANGER)),
+        Span::styled("[l] ", Styles::keybind()),
+        Span::raw(format!("Layout: {}  ", state.config.typing.layout.name())),
+        Span::styled("[c] ", Styles::keybind()),
+        Span::raw(format!("Latency: {} ms  ", state.config.typing.latency_ms)),
+        Span::styled("[m] ", Styles::keybind()),
+        Span::raw(format!("Mode: {}  ", state.config.combat.mode.name())),
+        Span::styled("[v] ", Styles::keybind()),
+        Span::raw(format!("Narrator: {}  ", if state.config.display.unreliable_narrator { "Unreliable" } else { "Reliable" })),
+        Span::styled("[t] ", Styles::keybind()),
+        Span::raw(format!("Theme: {}  ", state.config.display.theme.as_deref().unwrap_or("Default"))),
+        Span::styled("[s] ", Styles::keybind()),
+        Span::raw(format!("Markers: {}  ", if state.config.display.typing_markers { "On" } else { "Off" })),
+        Span::styled("[a] ", Styles::keybind()),
+        Span::raw(format!("Screen reader: {}  ", if state.config.display.screen_reader { "On" } else { "Off" })),
+        Span::styled("[p] ", Styles::keybind()),
+        Span::raw(format!("Motion: {}  ", if state.config.display.reduced_motion { "Reduced" } else { "Full" })),
+        Span::styled("[e] ", Styles::keybind()),
+        Span::raw("Key heatmap  "),
+        Span::styled("[d] ", Styles::keybind()),
+        Span::raw("Dashboard  "),
+        Span::styled("[o] ", Styles::keybind()),
+        Span::raw("Settings  "),
+        Span::styled("[q] ", Style::default().fg(Palette::current().danger)),
         Span::raw("Quit"),
     ]))
     .alignment(Alignment::Center)
-    .style(Style::default().bg(Palette::BG_PANEL));
+    .style(Style::default().bg(Palette::current().bg_panel));
     f.render_widget(hints, hint_area);
 }
 
@@ -437,20 +846,20 @@ fn render_class_select(f: &mut Frame, state: &GameState) {
         .constraints([
             Constraint::Length(3),
             Constraint::Min(10),
-            Constraint::Length(3),
+            Constraint::Length(5),
         ])
         .split(main_area);
 
     let title = Paragraph::new("Choose Your Class")
-        .style(Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
     f.render_widget(title, chunks[0]);
 
     let classes = vec![
         ("Wordsmith", "Balanced fighter. +10% damage, starts with Heal spell.", Color::White),
         ("Scribe", "High MP, spell specialist. +25% MP, learns spells faster.", Color::Blue),
-        ("Spellweaver", "Glass cannon mage. +50% spell damage, -20% HP.", Palette::ACCENT),
+        ("Spellweaver", "Glass cannon mage. +50% spell damage, -20% HP.", Palette::current().accent),
         ("Barbarian", "Tank with raw power. +30% HP, +15% damage, no spells.", Color::Red),
         ("Trickster", "Luck-based chaos. Random bonuses, critical hits, steals.", Color::Green),
     ];
@@ -470,12 +879,25 @@ fn render_class_select(f: &mut Frame, state: &GameState) {
         .collect();
 
     let class_list = List::new(class_items)
-        .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰓥 Classes ", Style::default().fg(Palette::PRIMARY))));
+        .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰓥 Classes ", Style::default().fg(Palette::current().primary))));
     f.render_widget(class_list, chunks[1]);
 
-    let tip = Paragraph::new("Each class has unique abilities and playstyles")
-        .style(Styles::dim().add_modifier(Modifier::ITALIC))
-        .alignment(Alignment::Center);
+    let mut tip_lines = vec![match state.patron {
+        Some(patron) => Line::styled(format!("Pledged to {} ({}): {}", patron.name(), state.setting.pack().faction_name(patron.faction()), patron.mechanic()),
+            Style::default().fg(Palette::current().secondary)),
+        None => Line::styled("Each class has unique abilities and playstyles",
+            Styles::dim().add_modifier(Modifier::ITALIC)),
+    }];
+    if let Some(class) = state.locked_class() {
+        tip_lines.push(Line::styled(format!("This challenge is {} only; unlocks don't carry in", class.name()),
+            Style::default().fg(Palette::current().warning)));
+    }
+    if state.ascension_record.unlocked > 0 {
+        tip_lines.push(Line::styled(ascension::describe(state.ascension), Style::default().fg(Palette::current().warning)));
+    }
+    let setting = state.setting.pack();
+    tip_lines.push(Line::styled(format!("{}: {}", setting.name, setting.premise), Styles::dim()));
+    let tip = Paragraph::new(tip_lines).alignment(Alignment::Center).wrap(Wrap { trim: true });
     f.render_widget(tip, chunks[2]);
     
     // Key hints at bottom
@@ -484,13 +906,19 @@ fn render_class_select(f: &mut Frame, state: &GameState) {
         Span::raw("Navigate  "),
         Span::styled("[Enter] ", Styles::keybind()),
         Span::raw("Select  "),
+        Span::styled("[p] ", Styles::keybind()),
+        Span::raw("Patron  "),
+        Span::styled("[a] ", Styles::keybind()),
+        Span::raw("Ascension  "),
+        Span::styled("[s] ", Styles::keybind()),
+        Span::raw("Setting  "),
         Span::styled("[Esc] ", Styles::keybind()),
         Span::raw("Back  "),
         Span::styled("[?] ", Style::default().fg(Color::Cyan)),
         Span::raw("Help"),
     ]))
     .alignment(Alignment::Center)
-    .style(Style::default().bg(Palette::BG_PANEL));
+    .style(Style::default().bg(Palette::current().bg_panel));
     f.render_widget(hints, hint_area);
 }
 
@@ -499,12 +927,14 @@ fn render_dungeon(f: &mut Frame, state: &GameState) {
     let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
     let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
     
+    // A short terminal gives the map the stats panel's spare rows
+    let stats_rows = if layout::fit(area) == Fit::Full { 5 } else { 3 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
             Constraint::Length(3),
-            Constraint::Length(5),
+            Constraint::Length(stats_rows),
             Constraint::Min(8),
             Constraint::Length(3),
         ])
@@ -518,7 +948,7 @@ fn render_dungeon(f: &mut Frame, state: &GameState) {
     let header = Paragraph::new(format!("Floor {} — {}", floor, zone_name))
         .style(Styles::title())
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&zone_name))));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&zone_name))));
     f.render_widget(header, chunks[0]);
 
     // Player stats
@@ -535,20 +965,15 @@ fn render_dungeon(f: &mut Frame, state: &GameState) {
         );
         
         let stats = Paragraph::new(stats_text)
-            .style(Style::default().fg(Palette::TEXT))
+            .style(Style::default().fg(Palette::current().text))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title(format!(" {} - {} ", player.name, player.class.name())));
+            .block(Styles::block().borders(Borders::ALL).title(format!(" {} - {} ", player.name, player.class.name())));
         f.render_widget(stats, chunks[1]);
     }
 
-    // Room display / map
+    // Floor map
     if let Some(dungeon) = &state.dungeon {
-        let room_display = dungeon.get_ascii_map();
-        let room = Paragraph::new(room_display)
-            .style(Styles::keybind())
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰍋 Dungeon Map ", Style::default().fg(Palette::PRIMARY))));
-        f.render_widget(room, chunks[2]);
+        render_floor_map(f, dungeon, chunks[2]);
     }
 
     // Message log
@@ -559,24 +984,57 @@ fn render_dungeon(f: &mut Frame, state: &GameState) {
         .collect();
     let log = Paragraph::new(messages)
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰎟 Log ", Style::default().fg(Palette::TEXT_DIM))));
+        .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰎟 Log ", Style::default().fg(Palette::current().text_dim))));
     f.render_widget(log, chunks[3]);
 
+    // Typing a destination or name replaces the key hints
+    if let Some(prompt) = &state.map_prompt {
+        let label = match prompt.mode {
+            MapPromptMode::Travel => " Travel to (3-2, a room name, or onward): ",
+            MapPromptMode::Name => " Name this place: ",
+        };
+        let line = Paragraph::new(Line::from(vec![
+            Span::styled(label, Styles::keybind()),
+            Span::styled(format!("{}_", prompt.input), Style::default().fg(Palette::current().cursor).add_modifier(Modifier::BOLD)),
+            Span::styled("  [Enter] go  [Esc] cancel", Styles::dim()),
+        ]))
+        .alignment(Alignment::Center)
+        .style(Style::default().bg(Palette::current().bg_panel));
+        f.render_widget(line, hint_area);
+        return;
+    }
+
     // Key hints at bottom - make EXPLORE very prominent
     let hints = Paragraph::new(Line::from(vec![
         Span::styled(" [Enter/e] ", Styles::typed_correct()),
         Span::styled("EXPLORE ", Styles::typed_correct()),
+        Span::styled("[←/→/1-4] ", Styles::keybind()),
+        Span::raw("Path  "),
+        Span::styled("[t] ", Styles::keybind()),
+        Span::raw("Travel  "),
+        Span::styled("[n] ", Styles::keybind()),
+        Span::raw("Name  "),
         Span::styled("[i] ", Styles::keybind()),
         Span::raw("Inventory  "),
         Span::styled("[s] ", Styles::keybind()),
         Span::raw("Stats  "),
+        Span::styled("[k] ", Styles::keybind()),
+        Span::raw("Skills  "),
+        Span::styled("[j] ", Styles::keybind()),
+        Span::raw("Journal  "),
+        Span::styled("[c] ", Styles::keybind()),
+        Span::raw("Codex  "),
+        Span::styled("[b] ", Styles::keybind()),
+        Span::raw("Clues  "),
+        Span::styled("[m] ", Styles::keybind()),
+        Span::raw("Memories  "),
         Span::styled("[?] ", Style::default().fg(Color::Cyan)),
         Span::raw("Help  "),
-        Span::styled("[q] ", Style::default().fg(Palette::DANGER)),
+        Span::styled("[q] ", Style::default().fg(Palette::current().danger)),
         Span::raw("Quit"),
     ]))
     .alignment(Alignment::Center)
-    .style(Style::default().bg(Palette::BG_PANEL));
+    .style(Style::default().bg(Palette::current().bg_panel));
     f.render_widget(hints, hint_area);
 }
 
@@ -605,14 +1063,14 @@ fn render_combat(f: &mut Frame, state: &GameState) {
         let enemy_widget = Paragraph::new(enemy_display)
             .style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown"))))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
         f.render_widget(enemy_widget, chunks[0]);
 
         // Enemy HP bar
         let hp_percent = ((combat.enemy.current_hp as f64 / combat.enemy.max_hp as f64) * 100.0) as u16;
-        let hp_color = if hp_percent > 50 { Palette::SUCCESS } else if hp_percent > 25 { Palette::WARNING } else { Palette::DANGER };
+        let hp_color = if hp_percent > 50 { Palette::current().success } else if hp_percent > 25 { Palette::current().warning } else { Palette::current().danger };
         let hp_gauge = Gauge::default()
-            .block(Block::default().borders(Borders::ALL).title(format!(" HP: {}/{} ", combat.enemy.current_hp, combat.enemy.max_hp)))
+            .block(Styles::block().borders(Borders::ALL).title(format!(" HP: {}/{} ", combat.enemy.current_hp, combat.enemy.max_hp)))
             .gauge_style(Style::default().fg(hp_color))
             .percent(hp_percent.min(100));
         f.render_widget(hp_gauge, chunks[1]);
@@ -620,13 +1078,13 @@ fn render_combat(f: &mut Frame, state: &GameState) {
         // Typing area - improved for sentences
         let word_display = if combat.phase == CombatPhase::PlayerTurn {
             let typed = &combat.typed_input;
-            let target = &combat.current_word;
+            let target = combat.target();
             let mut spans = Vec::new();
             
-            for (i, target_char) in target.chars().enumerate() {
-                if i < typed.len() {
-                    let typed_char = typed.chars().nth(i).unwrap();
-                    if typed_char == target_char {
+            let typed_graphemes = graphemes(typed);
+            for (i, target_char) in graphemes(target).into_iter().enumerate() {
+                if i < typed_graphemes.len() {
+                    if graphemes_match(target_char, typed_graphemes[i]) {
                         spans.push(Span::styled(
                             target_char.to_string(),
                             Styles::typed_correct()
@@ -637,11 +1095,11 @@ fn render_combat(f: &mut Frame, state: &GameState) {
                             Styles::typed_wrong()
                         ));
                     }
-                } else if i == typed.len() {
+                } else if i == typed_graphemes.len() {
                     // Cursor position - highlight next char
                     spans.push(Span::styled(
                         target_char.to_string(),
-                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                        Styles::cursor()
                     ));
                 } else {
                     spans.push(Span::styled(
@@ -657,11 +1115,11 @@ fn render_combat(f: &mut Frame, state: &GameState) {
         };
 
         // Determine if it's a sentence (longer content)
-        let is_sentence = combat.current_word.len() > 30;
+        let is_sentence = grapheme_len(&combat.current_word) > 30;
         let title_text = if is_sentence {
             format!(" Type the sentence! Combo: {} | Time: {:.1}s | {}/{} chars ", 
                 combat.combo, combat.time_remaining, 
-                combat.typed_input.len(), combat.current_word.len())
+                grapheme_len(&combat.typed_input), grapheme_len(&combat.current_word))
         } else {
             format!(" Type the word! Combo: {} | Time: {:.1}s ", combat.combo, combat.time_remaining)
         };
@@ -669,7 +1127,7 @@ fn render_combat(f: &mut Frame, state: &GameState) {
         let typing_block = Paragraph::new(word_display)
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: false })
-            .block(Block::default()
+            .block(Styles::block()
                 .borders(Borders::ALL)
                 .title(title_text));
         f.render_widget(typing_block, chunks[2]);
@@ -678,8 +1136,8 @@ fn render_combat(f: &mut Frame, state: &GameState) {
         if let Some(player) = &state.player {
             let player_hp = ((player.hp as f64 / player.max_hp as f64) * 100.0) as u16;
             let player_gauge = Gauge::default()
-                .block(Block::default().borders(Borders::ALL).title(format!(" Your HP: {}/{} ", player.hp, player.max_hp)))
-                .gauge_style(Style::default().fg(Palette::SUCCESS))
+                .block(Styles::block().borders(Borders::ALL).title(format!(" Your HP: {}/{} ", player.hp, player.max_hp)))
+                .gauge_style(Style::default().fg(Palette::current().success))
                 .percent(player_hp.min(100));
             f.render_widget(player_gauge, chunks[3]);
         }
@@ -692,7 +1150,7 @@ fn render_combat(f: &mut Frame, state: &GameState) {
             .map(|msg| ListItem::new(msg.as_str()))
             .collect();
         let log = List::new(log_items)
-            .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰵅 Battle Log ", Style::default().fg(Palette::INFO))));
+            .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰵅 Battle Log ", Style::default().fg(Palette::current().info))));
         f.render_widget(log, chunks[4]);
 
         // Help - key hints for combat (context-sensitive)
@@ -702,7 +1160,7 @@ fn render_combat(f: &mut Frame, state: &GameState) {
                 Span::raw("Cast Spell  "),
                 Span::styled("[Tab] ", Style::default().fg(Color::Cyan)),
                 Span::raw("Cancel  "),
-                Span::styled("[Esc] ", Style::default().fg(Palette::DANGER)),
+                Span::styled("[Esc] ", Style::default().fg(Palette::current().danger)),
                 Span::raw("Flee"),
             ]
         } else {
@@ -713,13 +1171,13 @@ fn render_combat(f: &mut Frame, state: &GameState) {
                 Span::raw("󰊠 Spells  "),
                 Span::styled("[Backspace] ", Styles::keybind()),
                 Span::raw("Fix  "),
-                Span::styled("[Esc] ", Style::default().fg(Palette::DANGER)),
+                Span::styled("[Esc] ", Style::default().fg(Palette::current().danger)),
                 Span::raw("Flee"),
             ]
         };
         let help = Paragraph::new(Line::from(help_spans))
             .alignment(Alignment::Center)
-            .style(Style::default().bg(Palette::BG_PANEL));
+            .style(Style::default().bg(Palette::current().bg_panel));
         f.render_widget(help, chunks[5]);
         
         // Render typing feel overlay
@@ -742,33 +1200,34 @@ fn render_shop(f: &mut Frame, state: &GameState) {
         .split(f.area());
 
     let gold = state.player.as_ref().map(|p| p.gold).unwrap_or(0);
-    let header = Paragraph::new(format!("Welcome to the Keyboard Emporium!\n\nYour Gold: {}", gold))
+    let prices = state.shop.price_note().map(|note| format!("  ({})", note)).unwrap_or_default();
+    let header = Paragraph::new(format!("Welcome to the Keyboard Emporium!\n\nYour Gold: {}{}", gold, prices))
         .style(Styles::keybind())
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
     f.render_widget(header, chunks[0]);
 
-    let items: Vec<ListItem> = state.shop_items
+    let items: Vec<ListItem> = state.shop.offers
         .iter()
         .enumerate()
-        .map(|(i, item)| {
+        .map(|(i, offer)| {
             let style = if i == state.menu_index {
                 Styles::keybind().add_modifier(Modifier::BOLD | Modifier::REVERSED)
             } else {
-                Style::default().fg(Palette::TEXT)
+                Style::default().fg(Palette::current().text)
             };
             let text = format!("{} {} - {}g\n  {}", 
-                item.rarity.symbol(),
-                item.name, 
-                item.price,
-                item.description
+                offer.symbol(),
+                offer.name, 
+                offer.price,
+                offer.description
             );
             ListItem::new(text).style(style)
         })
         .collect();
 
     let items_list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰆼 Items for Sale ", Style::default().fg(Palette::SECONDARY))));
+        .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰆼 Items for Sale ", Style::default().fg(Palette::current().secondary))));
     f.render_widget(items_list, chunks[1]);
 
     let help = Paragraph::new("↑/↓ Select | Enter: Buy | Esc: Leave")
@@ -783,6 +1242,7 @@ fn render_rest(f: &mut Frame, state: &GameState) {
         .margin(2)
         .constraints([
             Constraint::Length(8),
+            Constraint::Length(if state.camp_talk.is_empty() { 0 } else { state.camp_talk.len() as u16 + 2 }),
             Constraint::Min(8),
             Constraint::Length(3),
         ])
@@ -800,34 +1260,50 @@ fn render_rest(f: &mut Frame, state: &GameState) {
     let fire = Paragraph::new(campfire)
         .style(Styles::keybind())
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰈸 Campfire ", Style::default().fg(Palette::WARNING))));
+        .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰈸 Campfire ", Style::default().fg(Palette::current().warning))));
     f.render_widget(fire, chunks[0]);
 
-    let options = vec![
-        "[1] Rest (Restore 30% HP)",
-        "[2] Train (Gain some XP)",
-        "[3] Meditate (Restore 50% MP)",
+    if !state.camp_talk.is_empty() {
+        let talk: Vec<Line> = state.camp_talk.iter()
+            .map(|(name, line)| Line::from(vec![
+                Span::styled(format!("{}: ", name), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("\"{}\"", line), Style::default().fg(Palette::current().text).add_modifier(Modifier::ITALIC)),
+            ]))
+            .collect();
+        let companions = Paragraph::new(talk)
+            .wrap(Wrap { trim: true })
+            .block(Styles::block().borders(Borders::ALL).title(Span::styled(" By the Fire ", Style::default().fg(Palette::current().accent))));
+        f.render_widget(companions, chunks[1]);
+    }
+
+    let mut options = vec![
+        "[1] Sleep (Restore 30% HP, perhaps dream)".to_string(),
+        "[2] Train (Gain some XP)".to_string(),
+        "[3] Meditate (Restore 50% MP)".to_string(),
     ];
+    for (i, companion) in state.companions.present().iter().enumerate() {
+        options.push(format!("[{}] {}: {}", i + 4, companion.name, companion.service.describe()));
+    }
     let options_items: Vec<ListItem> = options
         .iter()
         .enumerate()
         .map(|(i, opt)| {
             let style = if i == state.menu_index {
-                Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD)
+                Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Palette::TEXT)
+                Style::default().fg(Palette::current().text)
             };
-            ListItem::new(*opt).style(style)
+            ListItem::new(opt.as_str()).style(style)
         })
         .collect();
     let rest_list = List::new(options_items)
-        .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰣐 Rest Actions ", Style::default().fg(Palette::SUCCESS))));
-    f.render_widget(rest_list, chunks[1]);
+        .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰣐 Rest Actions ", Style::default().fg(Palette::current().success))));
+    f.render_widget(rest_list, chunks[2]);
 
     let help = Paragraph::new("↑/↓ Select | Enter: Confirm | Esc: Leave")
         .style(Styles::dim())
         .alignment(Alignment::Center);
-    f.render_widget(help, chunks[2]);
+    f.render_widget(help, chunks[3]);
 }
 
 fn render_event(f: &mut Frame, state: &GameState) {
@@ -845,22 +1321,22 @@ fn render_event(f: &mut Frame, state: &GameState) {
             .split(f.area());
 
         let title = Paragraph::new(&*event.name)
-            .style(Style::default().fg(Palette::ACCENT).add_modifier(Modifier::BOLD))
+            .style(Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
         f.render_widget(title, chunks[0]);
 
         let art = Paragraph::new(&*event.ascii_art)
-            .style(Style::default().fg(Palette::PRIMARY))
+            .style(Style::default().fg(Palette::current().primary))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
         f.render_widget(art, chunks[1]);
 
         let desc = Paragraph::new(&*event.description)
-            .style(Style::default().fg(Palette::TEXT))
+            .style(Style::default().fg(Palette::current().text))
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
         f.render_widget(desc, chunks[2]);
 
         let choices: Vec<ListItem> = event.choices
@@ -870,13 +1346,13 @@ fn render_event(f: &mut Frame, state: &GameState) {
                 let style = if i == state.menu_index {
                     Styles::keybind().add_modifier(Modifier::BOLD | Modifier::REVERSED)
                 } else {
-                    Style::default().fg(Palette::TEXT)
+                    Style::default().fg(Palette::current().text)
                 };
                 ListItem::new(format!("[{}] {}", i + 1, choice.text)).style(style)
             })
             .collect();
         let choices_list = List::new(choices)
-            .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰋗 Choices ", Style::default().fg(Palette::INFO))));
+            .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰋗 Choices ", Style::default().fg(Palette::current().info))));
         f.render_widget(choices_list, chunks[3]);
 
         let help = Paragraph::new("↑/↓ or 1-3: Select | Enter: Confirm")
@@ -886,6 +1362,337 @@ fn render_event(f: &mut Frame, state: &GameState) {
     }
 }
 
+/// Render an authored encounter: the scene, then its choices, its typing or its outcome
+fn render_encounter(f: &mut Frame, state: &GameState) {
+    let Some(run) = &state.encounter_run else {
+        return;
+    };
+    if let (EncounterStage::Typing { typed, .. }, Some(ritual)) = (&run.stage, run.ritual()) {
+        render_ritual(f, run, ritual, typed);
+        return;
+    }
+    let border = Style::default().fg(zone_color(state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(7),
+            Constraint::Length(2),
+        ])
+        .split(f.area());
+
+    let title = Paragraph::new(run.encounter.title.as_str())
+        .style(Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(border));
+    f.render_widget(title, chunks[0]);
+
+    let scene_lines = match &run.stage {
+        EncounterStage::Outcome { text } => text.iter()
+            .flat_map(|t| paragraphs(t))
+            .flat_map(|p| [Line::from(p), Line::from("")])
+            .collect(),
+        _ => encounter_scene(run, state.config.display.unreliable_narrator.then_some(&state.codex)),
+    };
+    let scene = Paragraph::new(scene_lines)
+        .style(Style::default().fg(Palette::current().text))
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(border));
+    f.render_widget(scene, chunks[1]);
+
+    let help = match &run.stage {
+        EncounterStage::Reading => {
+            let choices: Vec<ListItem> = run.encounter.choices.iter().enumerate().map(|(i, choice)| {
+                let style = if !EncounterRun::available(choice) {
+                    Style::default().fg(Color::DarkGray)
+                } else if i == state.menu_index {
+                    Styles::keybind().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else {
+                    Style::default().fg(Palette::current().text)
+                };
+                let mut text = format!("[{}] {}", i + 1, choice.text);
+                if choice.typing_required {
+                    text.push_str(" ⌨");
+                }
+                if let Some(requires) = &choice.requires {
+                    text.push_str(&format!(" (requires {})", requires));
+                }
+                ListItem::new(text).style(style)
+            }).collect();
+            let list = List::new(choices)
+                .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰋗 Choices ", Style::default().fg(Palette::current().info))));
+            f.render_widget(list, chunks[2]);
+            "↑/↓ or 1-9: Select | Enter: Confirm | t: Talk | ⌨ must be typed"
+        }
+        EncounterStage::Typing { prompt, typed, .. } => {
+            let typing = Paragraph::new(vec![Line::from(prompt_spans(prompt, typed)), Line::from(""), Line::from(Span::styled(format!("> {}", typed), Styles::keybind()))])
+                .wrap(Wrap { trim: false })
+                .block(Styles::block().borders(Borders::ALL).title(Span::styled(" ⌨ Type it true ", Style::default().fg(Palette::current().info))));
+            f.render_widget(typing, chunks[2]);
+            "Enter: Submit | Esc: Choose again"
+        }
+        EncounterStage::Outcome { .. } => "Enter: Continue",
+    };
+    let help = Paragraph::new(help)
+        .style(Styles::dim())
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[3]);
+}
+
+/// A prompt typed out of combat, each letter styled by how it was typed
+fn prompt_spans(prompt: &str, typed: &str) -> Vec<Span<'static>> {
+    let typed: Vec<char> = typed.chars().collect();
+    prompt.chars().enumerate().map(|(i, c)| {
+        let style = match typed.get(i) {
+            Some(t) if *t == c => Styles::typed_correct(),
+            Some(_) => Styles::typed_wrong(),
+            None => Styles::untyped(),
+        };
+        Span::styled(c.to_string(), style)
+    }).collect()
+}
+
+/// Render a naming ritual full screen: the name trembles, and what is
+/// still untyped corrupts the longer it takes
+fn render_ritual(f: &mut Frame, run: &EncounterRun, ritual: &Ritual, typed: &str) {
+    let area = f.area();
+    f.render_widget(Styles::block().style(Style::default().bg(Palette::current().bg_dark)), area);
+    let elapsed = run.ritual_started.map_or(0, |t| t.elapsed().as_millis() as u64);
+    let typed: Vec<char> = typed.chars().collect();
+
+    let mut name_spans: Vec<Span> = Vec::new();
+    for (i, (c, corrupt)) in naming_ritual::corrupted(&ritual.name, typed.len(), elapsed).into_iter().enumerate() {
+        let style = match typed.get(i) {
+            Some(t) if t.eq_ignore_ascii_case(&c) => Styles::typed_correct(),
+            Some(_) => Styles::typed_wrong(),
+            None if corrupt => Style::default().fg(Palette::current().corrupted),
+            None => Style::default().fg(Palette::current().text).add_modifier(Modifier::BOLD),
+        };
+        // Untyped letters tremble; typed ones are held still
+        let shake = if i < typed.len() { 0 } else { naming_ritual::tremble(i, elapsed) };
+        name_spans.push(Span::raw(" ".repeat(shake as usize)));
+        name_spans.push(Span::styled(c.to_string(), style));
+        name_spans.push(Span::raw(" ".repeat(2 - shake as usize)));
+    }
+
+    let dread = (naming_ritual::corruption(elapsed) * 100.0).round() as u32;
+    let lines = vec![
+        Line::from(Span::styled(format!("Speak {}", ritual.subject), Styles::dim().add_modifier(Modifier::ITALIC))),
+        Line::from(""),
+        Line::from(""),
+        Line::from(name_spans),
+        Line::from(""),
+        Line::from(""),
+        Line::from(Span::styled(format!("> {}▌", typed.iter().collect::<String>()), Styles::keybind())),
+        Line::from(""),
+        Line::from(Span::styled(format!("The name is slipping: {}%", dread), Style::default().fg(Palette::current().corrupted))),
+    ];
+    let height = lines.len() as u16;
+    let middle = Rect::new(area.x, area.y + area.height.saturating_sub(height) / 2, area.width, height.min(area.height));
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), middle);
+
+    let help = Paragraph::new("Enter: Speak the name | Esc: Fall silent")
+        .style(Styles::dim())
+        .alignment(Alignment::Center);
+    f.render_widget(help, Rect::new(area.x, area.height.saturating_sub(2), area.width, 1));
+}
+
+/// Render a conversation: what has been said, then the options or a check
+fn render_conversation(f: &mut Frame, state: &GameState) {
+    let (Some(runner), Some(run)) = (&state.conversation, &state.encounter_run) else {
+        return;
+    };
+    let border = Style::default().fg(zone_color(state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(7),
+            Constraint::Length(2),
+        ])
+        .split(f.area());
+
+    let title = Paragraph::new(run.encounter.title.as_str())
+        .style(Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(border));
+    f.render_widget(title, chunks[0]);
+
+    // Newest lines at the bottom; older ones scroll off the top
+    let shown = chunks[1].height.saturating_sub(2) as usize / 3 + 1;
+    let transcript: Vec<Line> = runner.transcript.iter()
+        .skip(runner.transcript.len().saturating_sub(shown))
+        .flat_map(|(speaker, text)| {
+            let color = if speaker == "You" { Palette::current().primary } else { Palette::current().accent };
+            [
+                Line::from(vec![
+                    Span::styled(format!("{}: ", speaker), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                    Span::raw(paragraphs(text).join(" ")),
+                ]),
+                Line::from(""),
+            ]
+        })
+        .collect();
+    let transcript = Paragraph::new(transcript)
+        .style(Style::default().fg(Palette::current().text))
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(border));
+    f.render_widget(transcript, chunks[1]);
+
+    let options = state.dialogue_options();
+    let help = if let Some(check) = &runner.typing {
+        let typing = Paragraph::new(vec![Line::from(prompt_spans(&check.prompt, &check.typed)), Line::from(""), Line::from(Span::styled(format!("> {}", check.typed), Styles::keybind()))])
+            .wrap(Wrap { trim: false })
+            .block(Styles::block().borders(Borders::ALL).title(Span::styled(" ⌨ Say it true ", Style::default().fg(Palette::current().info))));
+        f.render_widget(typing, chunks[2]);
+        "Enter: Submit | Esc: Say something else"
+    } else if runner.ended {
+        "Enter: Back to the encounter"
+    } else if options.is_empty() {
+        "Enter: Continue | Esc: Stop talking"
+    } else {
+        let items: Vec<ListItem> = options.iter().enumerate().map(|(i, option)| {
+            let style = if option.locked.is_some() {
+                Style::default().fg(Color::DarkGray)
+            } else if i == state.menu_index {
+                Styles::keybind().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default().fg(Palette::current().text)
+            };
+            let mut text = format!("[{}] {}", i + 1, option.text);
+            if option.typed {
+                text.push_str(" ⌨");
+            }
+            if let Some(needs) = &option.locked {
+                text.push_str(&format!(" ({})", needs));
+            }
+            ListItem::new(text).style(style)
+        }).collect();
+        let list = List::new(items)
+            .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰍩 Say ", Style::default().fg(Palette::current().info))));
+        f.render_widget(list, chunks[2]);
+        "↑/↓ or 1-9: Select | Enter: Say it | Esc: Stop talking"
+    };
+    let help = Paragraph::new(help)
+        .style(Styles::dim())
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[3]);
+}
+
+/// Render a dream: its scene, then the words it asks for, then waking
+fn render_dream(f: &mut Frame, state: &GameState) {
+    let Some(run) = &state.dream_run else {
+        return;
+    };
+    let border = Style::default().fg(Palette::current().secondary);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(6),
+            Constraint::Length(2),
+        ])
+        .split(f.area());
+
+    let title = Paragraph::new(format!("☾ {} ☾", run.dream.title))
+        .style(Style::default().fg(Palette::current().secondary).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(border));
+    f.render_widget(title, chunks[0]);
+
+    let scene: Vec<Line> = run.dream.scene.iter()
+        .flat_map(|p| [Line::from(Span::styled(*p, Style::default().fg(Palette::current().text).add_modifier(Modifier::ITALIC))), Line::from("")])
+        .collect();
+    let scene = Paragraph::new(scene)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(border));
+    f.render_widget(scene, chunks[1]);
+
+    let help = match &run.stage {
+        DreamStage::Dreaming => "Enter: Reach for the keys | Esc: Wake",
+        DreamStage::Typing { prompt, typed } => {
+            let typing = Paragraph::new(vec![Line::from(prompt_spans(prompt, typed)), Line::from(""), Line::from(Span::styled(format!("> {}", typed), Styles::keybind()))])
+                .wrap(Wrap { trim: false })
+                .block(Styles::block().borders(Borders::ALL).title(Span::styled(" ⌨ Type the dream ", Style::default().fg(Palette::current().secondary))));
+            f.render_widget(typing, chunks[2]);
+            "Enter: Wake | Esc: Wake without finishing"
+        }
+        DreamStage::Waking { grade } => {
+            let text = if grade.carries() {
+                Line::from(vec![
+                    Span::styled("You remember: ", Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+                    Span::styled(run.dream.clue, Style::default().fg(Palette::current().text)),
+                ])
+            } else {
+                Line::from(Span::styled("The words blur. By the time you wake, the dream is gone.", Styles::dim()))
+            };
+            let waking = Paragraph::new(text)
+                .wrap(Wrap { trim: true })
+                .block(Styles::block().borders(Borders::ALL).title(Span::styled(" You wake ", Style::default().fg(Palette::current().secondary))));
+            f.render_widget(waking, chunks[2]);
+            "Enter: Continue"
+        }
+    };
+    let help = Paragraph::new(help)
+        .style(Styles::dim())
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[3]);
+}
+
+/// The encounter's description, dialogue and the details you notice
+/// A paragraph of an encounter's description, told by the unreliable
+/// narrator when it is on: its lies unmarked, its corrected lies struck
+/// through with the truth after them
+fn narrated(encounter: &str, paragraph: String, narrator: Option<&LoreJournal>) -> Line<'static> {
+    let Some(codex) = narrator else {
+        return Line::from(paragraph);
+    };
+    let spans: Vec<Span> = unreliable_narrator::narrate(encounter, &paragraph, |id| codex.has_discovered(id)).into_iter()
+        .flat_map(|told| match told {
+            Told::Plain(text) => vec![Span::raw(text.to_string())],
+            Told::Lie(lie) => vec![Span::raw(lie)],
+            Told::Corrected { lie, truth } => vec![
+                Span::styled(lie, Styles::dim().add_modifier(Modifier::CROSSED_OUT)),
+                Span::raw(" "),
+                Span::styled(truth, Style::default().fg(Palette::current().accent)),
+            ],
+        })
+        .collect();
+    Line::from(spans)
+}
+
+fn encounter_scene(run: &EncounterRun, narrator: Option<&LoreJournal>) -> Vec<Line<'static>> {
+    let content = &run.encounter.content;
+    let mut lines: Vec<Line> = paragraphs(&content.description).into_iter()
+        .flat_map(|p| [narrated(&run.encounter.id, p, narrator), Line::from("")])
+        .collect();
+    let mut speakers: Vec<&str> = Vec::new();
+    for line in content.dialogue.iter().flatten() {
+        if !speakers.contains(&line.speaker.as_str()) {
+            speakers.push(&line.speaker);
+        }
+    }
+    if !speakers.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("[t] ", Styles::keybind()),
+            Span::styled(format!("Talk with {}", speakers.join(" and ")), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+        ]));
+    }
+    if !content.environmental_details.is_empty() {
+        lines.push(Line::from(""));
+    }
+    for detail in &content.environmental_details {
+        lines.push(Line::from(Span::styled(format!("· {}", paragraphs(detail).join(" ")), Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC))));
+    }
+    lines
+}
+
 fn render_inventory(f: &mut Frame, state: &GameState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -898,9 +1705,9 @@ fn render_inventory(f: &mut Frame, state: &GameState) {
         .split(f.area());
 
     let title = Paragraph::new("Inventory")
-        .style(Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
     f.render_widget(title, chunks[0]);
 
     if let Some(player) = &state.player {
@@ -909,9 +1716,9 @@ fn render_inventory(f: &mut Frame, state: &GameState) {
             .enumerate()
             .map(|(i, item)| {
                 let style = if i == state.menu_index {
-                    Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD)
+                    Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Palette::TEXT)
+                    Style::default().fg(Palette::current().text)
                 };
                 let text = format!("{} {} - {}", item.rarity.symbol(), item.name, item.description);
                 ListItem::new(text).style(style)
@@ -922,12 +1729,19 @@ fn render_inventory(f: &mut Frame, state: &GameState) {
             let empty = Paragraph::new("Your inventory is empty...")
                 .style(Styles::dim())
                 .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+                .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
             f.render_widget(empty, chunks[1]);
         } else {
+            let middle = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(5), Constraint::Length(10)])
+                .split(chunks[1]);
             let inv_list = List::new(items)
-                .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󱋣 Items ", Style::default().fg(Palette::SECONDARY))));
-            f.render_widget(inv_list, chunks[1]);
+                .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󱋣 Items ", Style::default().fg(Palette::current().secondary))));
+            f.render_widget(inv_list, middle[0]);
+            if let Some(item) = player.inventory.get(state.menu_index) {
+                render_item_inspect(f, middle[1], state, player, item);
+            }
         }
     }
 
@@ -937,22 +1751,83 @@ fn render_inventory(f: &mut Frame, state: &GameState) {
     f.render_widget(help, chunks[2]);
 }
 
-fn render_stats(f: &mut Frame, state: &GameState) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(2)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(12),
+/// The selected item's lore, its closed tiers redacted
+fn render_item_inspect(f: &mut Frame, area: Rect, state: &GameState, player: &crate::game::player::Player, item: &crate::game::items::Item) {
+    let lines: Vec<Line> = item_lore::inspect(item, &state.faction_relations, &player.artifacts)
+        .into_iter()
+        .map(|line| {
+            let (text, style) = if line.revealed {
+                (line.text, Style::default().fg(Palette::current().text))
+            } else {
+                (codex::redact(&line.text), Style::default().fg(Color::DarkGray))
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<10}", line.tier.name()), Style::default().fg(Palette::current().secondary)),
+                Span::styled(text, style),
+            ])
+        })
+        .collect();
+    let inspect = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(format!(" {} ", item.name), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))));
+    f.render_widget(inspect, area);
+}
+
+/// Worn typing gear, e.g. "Clicky Keycaps, Book of Errata"
+fn gear_line(state: &GameState, player: &crate::game::player::Player) -> String {
+    let mut names: Vec<&str> = player.gear.values()
+        .filter_map(|id| state.game_data.items.get_equipment(id))
+        .map(|e| e.name.as_str())
+        .collect();
+    if names.is_empty() {
+        return "none".to_string();
+    }
+    names.sort();
+    names.join(", ")
+}
+
+/// Carried artifacts and their boons
+fn artifact_line(player: &crate::game::player::Player) -> String {
+    if player.artifacts.is_empty() {
+        return "none".to_string();
+    }
+    player.artifacts.iter()
+        .map(|name| match Relic::named(name) {
+            Some(relic) => format!("{} ({})", name, relic.boon()),
+            None => name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Carried curses and what they give and take
+fn curse_line(player: &crate::game::player::Player) -> String {
+    if player.curses.is_empty() {
+        return "none".to_string();
+    }
+    player.curses.iter()
+        .map(|curse| format!("{} ({})", curse.name, curse.describe()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_stats(f: &mut Frame, state: &GameState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(12),
             Constraint::Length(8),  // Faction standings
             Constraint::Length(3),
         ])
         .split(f.area());
 
     let title = Paragraph::new("Character Stats")
-        .style(Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
     f.render_widget(title, chunks[0]);
 
     if let Some(player) = &state.player {
@@ -978,6 +1853,17 @@ fn render_stats(f: &mut Frame, state: &GameState) {
   - Enemies Defeated: {}
   - Words Typed: {}
   - Best WPM: {:.1}
+  - Executions: {} ({} overkill damage)
+  - Difficulty: {}
+  - Seed: {}
+  - Deepest Descent: {}
+  
+  Gear: {}
+  Artifacts: {}
+  Curses: {}
+  
+  Attack Thresholds ({}):
+  {}
 "#,
             player.name, player.class.name(), player.level,
             player.hp, player.max_hp,
@@ -986,12 +1872,24 @@ fn render_stats(f: &mut Frame, state: &GameState) {
             player.stats.vitality, player.stats.dexterity,
             player.stats.luck,
             player.gold, player.experience, player.experience_to_next_level(),
-            state.total_enemies_defeated, state.total_words_typed, state.best_wpm
+            state.total_enemies_defeated, state.total_words_typed, state.best_wpm,
+            state.executions, state.total_overkill,
+            state.difficulty_director.describe(),
+            state.run_seed,
+            match state.world_state.counter(DEEPEST_DESCENT) {
+                0 => "not yet endless".to_string(),
+                floor => format!("floor {}", floor),
+            },
+            gear_line(state, player),
+            artifact_line(player),
+            curse_line(player),
+            if state.config.typing.attack_thresholds.is_some() { "custom".to_string() } else { format!("{} preset", PlayerClass::from_class(&player.class).name()) },
+            state.attack_thresholds().describe().join("\n  ")
         );
         
         let stats = Paragraph::new(stats_text)
-            .style(Style::default().fg(Palette::TEXT))
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+            .style(Style::default().fg(Palette::current().text))
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
         f.render_widget(stats, chunks[1]);
     }
 
@@ -1008,10 +1906,10 @@ fn render_stats(f: &mut Frame, state: &GameState) {
     let faction_widget = Paragraph::new(faction_text)
         .style(Style::default().fg(Color::Cyan))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
     f.render_widget(faction_widget, chunks[2]);
     
-    let help = Paragraph::new("Press any key to return")
+    let help = Paragraph::new("Esc to return  ·  d for the dashboard")
         .style(Styles::dim())
         .alignment(Alignment::Center);
     f.render_widget(help, chunks[3]);
@@ -1053,19 +1951,21 @@ fn render_game_over(f: &mut Frame, state: &GameState) {
 "#;
 
     let title = Paragraph::new(game_over_art)
-        .style(Style::default().fg(Palette::DANGER))
+        .style(Style::default().fg(Palette::current().danger))
         .alignment(Alignment::Center);
     f.render_widget(title, chunks[0]);
 
     let stats = if let Some(player) = &state.player {
         format!(
-            "󰯈 You reached Floor {} as a Level {} {}\n\n󰓥 Enemies defeated: {}\n󰌌 Words typed: {}\n󰓅 Best WPM: {:.1}\n\n󰙤 Ink Earned: {} (Total: {})\n\n\"The keyboard awaits your return...\"",
+            "󰯈 You reached Floor {} as a Level {} {}{}\n\n󰓥 Enemies defeated: {}\n󰌌 Words typed: {}\n󰓅 Best WPM: {:.1}\n{}\n\n󰙤 Ink Earned: {} (Total: {})\n\n\"The keyboard awaits your return...\"",
             state.get_current_floor(),
             player.level,
             player.class.name(),
+            endless_depth_line(state),
             state.total_enemies_defeated,
             state.total_words_typed,
             state.best_wpm,
+            run_score_line(state),
             state.meta_progress.current_ink,
             state.meta_progress.total_ink
         )
@@ -1074,17 +1974,66 @@ fn render_game_over(f: &mut Frame, state: &GameState) {
     };
 
     let stats_widget = Paragraph::new(stats)
-        .style(Style::default().fg(Palette::TEXT))
+        .style(Style::default().fg(Palette::current().text))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
-    f.render_widget(stats_widget, chunks[1]);
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+    let stats_area = beside_epilogue(f, chunks[1], state);
+    f.render_widget(stats_widget, stats_area);
 
-    let help = Paragraph::new(Line::from(vec![Span::styled("󰓥 ", Style::default().fg(Palette::SUCCESS)), Span::styled("[R] Try Again  ", Styles::keybind()), Span::styled("󰅖 ", Style::default().fg(Palette::DANGER)), Span::styled("[Q] Quit", Style::default().fg(Palette::DANGER))]))
+    let help = Paragraph::new(Line::from(vec![Span::styled("󰓥 ", Style::default().fg(Palette::current().success)), Span::styled("[R] Try Again  ", Styles::keybind()), Span::styled("󰅖 ", Style::default().fg(Palette::current().danger)), Span::styled("[Q] Quit", Style::default().fg(Palette::current().danger))]))
         .style(Styles::keybind())
         .alignment(Alignment::Center);
     f.render_widget(help, chunks[2]);
 }
 
+/// Draw the run's epilogue on the right of `area`, returning what is left
+/// for the stats
+fn beside_epilogue(f: &mut Frame, area: Rect, state: &GameState) -> Rect {
+    if state.epilogue.is_empty() {
+        return area;
+    }
+    let halves = layout::beside(area, [Constraint::Percentage(45), Constraint::Percentage(55)]);
+    let mut lines = Vec::new();
+    for paragraph in &state.epilogue {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(paragraph.clone(), Style::default().fg(Palette::current().text))));
+    }
+    let epilogue = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Epilogue ", Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))));
+    f.render_widget(epilogue, halves[1]);
+    halves[0]
+}
+
+/// How deep the endless descent went, beside the deepest ever
+fn endless_depth_line(state: &GameState) -> String {
+    if !state.endless {
+        return String::new();
+    }
+    format!(
+        "\n∞ Endless descent: floor {} (deepest {})",
+        state.get_current_floor(),
+        state.world_state.counter(DEEPEST_DESCENT)
+    )
+}
+
+/// The finished run's score and ascension, beside the best at that level,
+/// with the seed to replay it from
+fn run_score_line(state: &GameState) -> String {
+    let Some(run) = &state.last_run else {
+        return String::new();
+    };
+    let best = state.ascension_record.leaderboard.iter()
+        .filter(|r| r.level == run.level)
+        .map(|r| r.score)
+        .max()
+        .unwrap_or(run.score);
+    format!("⇑ Ascension {} · Score {} (best {}) · Seed {}", run.level, run.score, best, state.run_seed)
+}
+
 fn render_victory(f: &mut Frame, state: &GameState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -1114,25 +2063,28 @@ fn render_victory(f: &mut Frame, state: &GameState) {
 
     let stats = if let Some(player) = &state.player {
         format!(
-            "󰔰 Congratulations, {}! 󰔰\n\n󰘛 You conquered all 10 floors as a Level {} {}!\n\n󰓥 Enemies defeated: {}\n󰌌 Words typed: {}\n󰓅 Best WPM: {:.1}\n\n★ ★ ★ You are a true Typing Champion! ★ ★ ★\n\n󰩛 Dr. Baklava salutes you 󰩛",
+            "󰔰 Congratulations, {}! 󰔰\n\n󰘛 You conquered all 10 floors as a Level {} {}!\n\n󰓥 Enemies defeated: {}\n󰌌 Words typed: {}\n󰓅 Best WPM: {:.1}\n{}\n\nEnding: {}\n\n★ ★ ★ You are a true Typing Champion! ★ ★ ★\n\n󰩛 Dr. Baklava salutes you 󰩛",
             player.name,
             player.level,
             player.class.name(),
             state.total_enemies_defeated,
             state.total_words_typed,
-            state.best_wpm
+            state.best_wpm,
+            run_score_line(state),
+            state.run_ending.map(|(_, title)| title).unwrap_or("Unwritten")
         )
     } else {
         "󰔰 You have conquered the dungeon! 󰔰".to_string()
     };
 
     let stats_widget = Paragraph::new(stats)
-        .style(Style::default().fg(Palette::TEXT))
+        .style(Style::default().fg(Palette::current().text))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
-    f.render_widget(stats_widget, chunks[1]);
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+    let stats_area = beside_epilogue(f, chunks[1], state);
+    f.render_widget(stats_widget, stats_area);
 
-    let help = Paragraph::new(Line::from(vec![Span::styled("󰓥 ", Style::default().fg(Palette::SUCCESS)), Span::styled("[N] New Game+  ", Styles::keybind()), Span::styled("󰅖 ", Style::default().fg(Palette::DANGER)), Span::styled("[Q] Quit", Style::default().fg(Palette::DANGER))]))
+    let help = Paragraph::new(Line::from(vec![Span::styled("󰓥 ", Style::default().fg(Palette::current().success)), Span::styled("[N] New Game+  ", Styles::keybind()), Span::styled("[E] Descend Endlessly  ", Styles::keybind()), Span::styled("󰅖 ", Style::default().fg(Palette::current().danger)), Span::styled("[Q] Quit", Style::default().fg(Palette::current().danger))]))
         .style(Styles::keybind())
         .alignment(Alignment::Center);
     f.render_widget(help, chunks[2]);
@@ -1148,7 +2100,7 @@ fn render_tutorial(f: &mut Frame, state: &GameState) {
         None => {
             let placeholder = Paragraph::new("Tutorial Complete!")
                 .alignment(Alignment::Center)
-                .style(Style::default().fg(Palette::SUCCESS));
+                .style(Style::default().fg(Palette::current().success));
             f.render_widget(placeholder, area);
             return;
         }
@@ -1178,12 +2130,12 @@ fn render_tutorial(f: &mut Frame, state: &GameState) {
         crate::game::tutorial::TutorialPhase::Complete => "󰄬",
     };
     
-    let title_block = Block::default()
+    let title_block = Styles::block()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
         .title(Span::styled(
             "  Tutorial ",
-            Style::default().fg(Palette::WARNING).add_modifier(Modifier::BOLD),
+            Style::default().fg(Palette::current().warning).add_modifier(Modifier::BOLD),
         ));
     
     let title_text = format!("{} {}", phase_icon, phase.title());
@@ -1194,23 +2146,23 @@ fn render_tutorial(f: &mut Frame, state: &GameState) {
     f.render_widget(title, chunks[0]);
     
     // Narrative and instructions
-    let narrative_block = Block::default()
+    let narrative_block = Styles::block()
         .borders(Borders::ALL)
         .border_style(Styles::dim())
         .title(Span::styled(
             " Story ",
-            Style::default().fg(Palette::ACCENT),
+            Style::default().fg(Palette::current().accent),
         ));
     
     let mut lines = vec![
         Line::from(Span::styled(
             step.narrative,
-            Style::default().fg(Palette::TEXT),
+            Style::default().fg(Palette::current().text),
         )),
         Line::from(""),
         Line::from(Span::styled(
             step.hint,
-            Style::default().fg(Palette::WARNING).add_modifier(Modifier::ITALIC),
+            Style::default().fg(Palette::current().warning).add_modifier(Modifier::ITALIC),
         )),
     ];
     
@@ -1225,7 +2177,7 @@ fn render_tutorial(f: &mut Frame, state: &GameState) {
         };
         lines.push(Line::from(Span::styled(
             combo_text,
-            Style::default().fg(Palette::FLOW_TRANSCENDENT).add_modifier(Modifier::BOLD),
+            Style::default().fg(Palette::current().flow_transcendent).add_modifier(Modifier::BOLD),
         )));
     }
     
@@ -1235,12 +2187,12 @@ fn render_tutorial(f: &mut Frame, state: &GameState) {
     f.render_widget(narrative, chunks[1]);
     
     // Typing area - show target and current input
-    let typing_block = Block::default()
+    let typing_block = Styles::block()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Palette::SUCCESS))
+        .border_style(Style::default().fg(Palette::current().success))
         .title(Span::styled(
             " Type this ",
-            Style::default().fg(Palette::SUCCESS),
+            Style::default().fg(Palette::current().success),
         ));
     
     // Build colored text showing correct/incorrect characters
@@ -1285,7 +2237,7 @@ fn render_tutorial(f: &mut Frame, state: &GameState) {
     // Progress bar showing tutorial completion
     let progress_ratio = tutorial.progress_percent() as f64 / 100.0;
     let progress = Gauge::default()
-        .block(Block::default().borders(Borders::ALL).title(Span::styled(" 󰓎 Progress ", Style::default().fg(Palette::PRIMARY))))
+        .block(Styles::block().borders(Borders::ALL).title(Span::styled(" 󰓎 Progress ", Style::default().fg(Palette::current().primary))))
         .gauge_style(Style::default().fg(Color::Cyan))
         .ratio(progress_ratio)
         .label(format!("{}%", tutorial.progress_percent()));
@@ -1324,22 +2276,22 @@ fn render_upgrades(f: &mut Frame, state: &GameState) {
     // Header with ink display
     let header_text = vec![
         Line::from(vec![
-            Span::styled("󰙤 ", Style::default().fg(Palette::ACCENT)),
-            Span::styled("INK SHOP", Style::default().fg(Palette::PRIMARY).add_modifier(Modifier::BOLD)),
-            Span::styled(" 󰙤", Style::default().fg(Palette::ACCENT)),
+            Span::styled("󰙤 ", Style::default().fg(Palette::current().accent)),
+            Span::styled("INK SHOP", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+            Span::styled(" 󰙤", Style::default().fg(Palette::current().accent)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::raw("Available Ink: "),
-            Span::styled(format!("{}", state.meta_progress.current_ink), Style::default().fg(Palette::ACCENT).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}", state.meta_progress.current_ink), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
             Span::raw("   Total Earned: "),
-            Span::styled(format!("{}", state.meta_progress.total_ink), Style::default().fg(Palette::SECONDARY)),
+            Span::styled(format!("{}", state.meta_progress.total_ink), Style::default().fg(Palette::current().secondary)),
         ]),
     ];
     
     let header = Paragraph::new(header_text)
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Palette::BORDER)));
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
     f.render_widget(header, chunks[0]);
     
     // Upgrades list
@@ -1347,7 +2299,7 @@ fn render_upgrades(f: &mut Frame, state: &GameState) {
     
     let items: Vec<ListItem> = if upgrades.is_empty() {
         vec![ListItem::new(Line::from(vec![
-            Span::styled("No upgrades available yet!", Style::default().fg(Palette::SECONDARY).add_modifier(Modifier::ITALIC)),
+            Span::styled("No upgrades available yet!", Style::default().fg(Palette::current().secondary).add_modifier(Modifier::ITALIC)),
         ]))]
     } else {
         upgrades.iter().enumerate().map(|(i, upgrade)| {
@@ -1355,16 +2307,16 @@ fn render_upgrades(f: &mut Frame, state: &GameState) {
             let can_afford = state.meta_progress.current_ink >= upgrade.cost;
             
             let (style, cost_color) = if is_selected {
-                (Style::default().fg(Palette::SECONDARY).add_modifier(Modifier::REVERSED),
-                 if can_afford { Palette::SUCCESS } else { Palette::DANGER })
+                (Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED),
+                 if can_afford { Palette::current().success } else { Palette::current().danger })
             } else {
-                (Style::default().fg(if can_afford { Palette::TEXT } else { Color::DarkGray }),
-                 if can_afford { Palette::TEXT } else { Color::DarkGray })
+                (Style::default().fg(if can_afford { Palette::current().text } else { Color::DarkGray }),
+                 if can_afford { Palette::current().text } else { Color::DarkGray })
             };
             
             ListItem::new(vec![
                 Line::from(vec![
-                    Span::styled(format!(" {} ", upgrade.category.icon()), Style::default().fg(Palette::PRIMARY)),
+                    Span::styled(format!(" {} ", upgrade.category.icon()), Style::default().fg(Palette::current().primary)),
                     Span::styled(&upgrade.name, style.add_modifier(Modifier::BOLD)),
                     Span::raw(" "),
                     Span::styled(format!("[{} Ink]", upgrade.cost), Style::default().fg(cost_color)),
@@ -1378,10 +2330,10 @@ fn render_upgrades(f: &mut Frame, state: &GameState) {
     };
     
     let list = List::new(items)
-        .block(Block::default()
+        .block(Styles::block()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Palette::BORDER))
-            .title(Span::styled(" 󱃵 Permanent Upgrades ", Style::default().fg(Palette::PRIMARY))));
+            .border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" 󱃵 Permanent Upgrades ", Style::default().fg(Palette::current().primary))));
     f.render_widget(list, chunks[1]);
     
     // Key hints
@@ -1390,69 +2342,1801 @@ fn render_upgrades(f: &mut Frame, state: &GameState) {
         Span::raw("Navigate  "),
         Span::styled("[Enter] ", Styles::keybind()),
         Span::raw("Purchase  "),
-        Span::styled("[Esc] ", Style::default().fg(Palette::WARNING)),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
         Span::raw("Back to Menu"),
     ]))
     .alignment(Alignment::Center)
-    .style(Style::default().bg(Palette::BG_PANEL));
+    .style(Style::default().bg(Palette::current().bg_panel));
     f.render_widget(hints, hint_area);
 }
 
-/// Render typing feel effects overlay on combat screen
-fn render_typing_feel_overlay(f: &mut Frame, state: &GameState, area: Rect) {
-    let feel = &state.typing_feel;
+/// Render the class's Discipline tree, where skill points are spent
+fn render_skills(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
     
-    // Combo display in top-right corner
-    if feel.combo > 0 {
-        let combo_width = 20;
-        let combo_height = 3;
-        let combo_area = Rect::new(
-            area.width.saturating_sub(combo_width + 2),
-            1,
-            combo_width,
-            combo_height,
-        );
-        
-        let combo_text = if feel.combo >= 10 {
-            format!("󱋊 {} COMBO! 󱋊\nx{:.1} DMG", feel.combo, feel.combo_multiplier)
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(10),
+            Constraint::Length(3),
+        ])
+        .split(main_area);
+    
+    let tree = &state.skill_tree;
+    let class = state.player.as_ref().map(|p| PlayerClass::from_class(&p.class)).unwrap_or(PlayerClass::Freelancer);
+    let header_text = vec![
+        Line::from(vec![
+            Span::styled(format!("{} DISCIPLINE", class.name().to_uppercase()), Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(Span::styled(SkillTreeType::Discipline.philosophy(), Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC))),
+        Line::from(vec![
+            Span::raw("Skill Points: "),
+            Span::styled(format!("{}", tree.skill_points), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+        ]),
+    ];
+    let header = Paragraph::new(header_text)
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+    
+    let items: Vec<ListItem> = tree.discipline().iter().enumerate().map(|(i, skill)| {
+        let learned = tree.has_skill(&skill.id);
+        let (mark, color) = if learned {
+            ("✓", Palette::current().success)
+        } else if tree.can_unlock(skill) {
+            ("○", Palette::current().text)
         } else {
-            format!("󱋊 {} Combo\nx{:.1} DMG", feel.combo, feel.combo_multiplier)
+            ("·", Color::DarkGray)
         };
-        
-        let combo_color = if feel.combo >= 20 {
-            Palette::ACCENT
-        } else if feel.combo >= 10 {
-            Color::Red
-        } else if feel.combo >= 5 {
-            Color::Yellow
+        let style = if i == state.menu_index {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
         } else {
-            Color::Cyan
+            Style::default().fg(color)
         };
-        
-        let combo_widget = Paragraph::new(combo_text)
-            .style(Style::default().fg(combo_color).add_modifier(Modifier::BOLD))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
-        f.render_widget(combo_widget, combo_area);
+        ListItem::new(vec![
+            Line::from(vec![
+                Span::styled(format!(" {} ", mark), Style::default().fg(color)),
+                Span::styled(&skill.name, style.add_modifier(Modifier::BOLD)),
+                Span::raw(" "),
+                Span::styled(format!("[{} pt]", skill.cost), Style::default().fg(color)),
+                Span::raw("  "),
+                Span::styled(&skill.description, Style::default().fg(color)),
+            ]),
+            Line::from(vec![
+                Span::raw("   "),
+                Span::styled(&skill.lore, Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC)),
+            ]),
+        ])
+    }).collect();
+    
+    let list = List::new(items)
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Skills ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, chunks[1]);
+    
+    let last = state.message_log.last().map(String::as_str).unwrap_or("");
+    let message = Paragraph::new(last)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Palette::current().text))
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(message, chunks[2]);
+    
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Navigate  "),
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Learn  "),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render the quest journal: open chains, their objectives, clues and world shifts
+fn render_journal(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let journal = &state.journal;
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Min(6),
+        ])
+        .split(main_area);
+    
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("󰠮 QUEST JOURNAL 󰠮", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Open chains: "),
+        Span::styled(format!("{}", journal.chains.len()), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+    
+    let middle = layout::beside(chunks[1], [Constraint::Percentage(45), Constraint::Percentage(55)]);
+    
+    let items: Vec<ListItem> = if journal.chains.is_empty() {
+        vec![ListItem::new(Span::styled("No leads yet. Your choices will open them.", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)))]
+    } else {
+        journal.chains.iter().enumerate().map(|(i, chain)| {
+            let style = if i == state.menu_index {
+                Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(Palette::current().text)
+            };
+            ListItem::new(vec![
+                Line::from(Span::styled(format!(" {} ", chain.title), style.add_modifier(Modifier::BOLD))),
+                Line::from(Span::styled(format!("   from {}", chain.opened_by), Style::default().fg(Color::Gray))),
+            ])
+        }).collect()
+    };
+    let chains = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Active Chains ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(chains, middle[0]);
+    
+    let objectives: Vec<Line> = journal.chains.get(state.menu_index)
+        .map(|chain| chain.objectives.iter().map(|o| {
+            let (mark, color) = if o.done { ("✓", Palette::current().success) } else { ("○", Palette::current().text) };
+            Line::from(vec![
+                Span::styled(format!(" {} ", mark), Style::default().fg(color)),
+                Span::styled(o.text.clone(), Style::default().fg(color)),
+            ])
+        }).collect())
+        .unwrap_or_default();
+    let objectives = Paragraph::new(objectives)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Objectives ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(objectives, middle[1]);
+    
+    let mut known: Vec<Line> = journal.clues.iter()
+        .map(|c| Line::from(vec![Span::styled(" ✧ ", Style::default().fg(Palette::current().accent)), Span::raw(c.clone())]))
+        .collect();
+    known.extend(journal.world_shifts.iter()
+        .map(|w| Line::from(vec![Span::styled(" ◈ ", Style::default().fg(Palette::current().secondary)), Span::raw(w.clone())])));
+    if known.is_empty() {
+        known.push(Line::from(Span::styled("Nothing learned yet.", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))));
     }
+    let clues = Paragraph::new(known)
+        .style(Style::default().fg(Palette::current().text))
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Known Clues ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(clues, chunks[2]);
     
-    // Flow state indicator
-    let flow_desc = feel.flow_description();
-    if !flow_desc.is_empty() {
-        let flow_width = 25;
-        let flow_area = Rect::new(
-            2,
-            1,
-            flow_width,
-            1,
-        );
-        
-        let flow_color = match feel.flow_state {
-            crate::game::typing_feel::FlowState::Transcendent => Palette::ACCENT,
-            crate::game::typing_feel::FlowState::Flowing => Color::Cyan,
-            crate::game::typing_feel::FlowState::Building => Color::Yellow,
-            crate::game::typing_feel::FlowState::Recovering => Color::Red,
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Navigate  "),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+fn render_codex(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let page = state.codex_page();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(8),
+        ])
+        .split(main_area);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("󰂺 LORE CODEX 󰂺", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Complete: "),
+        Span::styled(
+            format!("{:.0}%", codex::completion(&state.codex_entries)),
+            Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD),
+        ),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+
+    let tabs: Vec<Span> = CodexSection::ALL.iter().map(|section| {
+        // Scars are counted, not completed
+        let done = match section {
+            CodexSection::Scars => format!("{}", state.scars.all().len()),
+            _ => format!("{:.0}%", codex::completion(state.codex_entries.iter().filter(|e| e.section == *section))),
         };
+        let style = if *section == state.codex_section {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Span::styled(format!(" {} {} ", section.name(), done), style)
+    }).collect();
+    let tabs = Paragraph::new(Line::from(tabs))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(tabs, chunks[1]);
+
+    let middle = layout::beside(chunks[2], [Constraint::Percentage(40), Constraint::Percentage(60)]);
+
+    let items: Vec<ListItem> = page.iter().enumerate().map(|(i, entry)| {
+        let (mark, color) = if entry.discovered { ("✧", Palette::current().text) } else { ("?", Color::DarkGray) };
+        let style = if i == state.menu_index {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(color)
+        };
+        let title = if entry.discovered { entry.title.clone() } else { codex::redact(&entry.title) };
+        ListItem::new(Span::styled(format!(" {} {} ", mark, title), style))
+    }).collect();
+    let list = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(format!(" {} ", state.codex_section.name()), Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, middle[0]);
+
+    let (title, text) = match page.get(state.menu_index) {
+        Some(entry) if entry.discovered => (
+            Span::styled(format!(" {} ", entry.title), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+            Span::styled(entry.text.clone(), Style::default().fg(Palette::current().text)),
+        ),
+        Some(entry) => (
+            Span::styled(" Undiscovered ", Style::default().fg(Color::DarkGray)),
+            Span::styled(codex::redact(&entry.text), Style::default().fg(Color::DarkGray)),
+        ),
+        None => (Span::raw(""), Span::raw("")),
+    };
+    let detail = Paragraph::new(Line::from(text))
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)).title(title));
+    f.render_widget(detail, middle[1]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [←/→] ", Styles::keybind()),
+        Span::raw("Section  "),
+        Span::styled("[j/k] ", Styles::keybind()),
+        Span::raw("Navigate  "),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+fn render_clue_board(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let pinned = &state.clue_board.pinned;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(5),
+        ])
+        .split(main_area);
+
+    let understood = pinned.iter().filter(|p| p.understood).count();
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("󰐃 CLUE BOARD 󰐃", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Understood: "),
+        Span::styled(format!("{}/{}", understood, pinned.len()), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+
+    let middle = layout::beside(chunks[1], [Constraint::Percentage(40), Constraint::Percentage(60)]);
+
+    let items: Vec<ListItem> = if pinned.is_empty() {
+        vec![ListItem::new(Span::styled("Nothing pinned yet. Keep descending.", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)))]
+    } else {
+        pinned.iter().enumerate().map(|(i, p)| {
+            let (mark, color) = if p.understood { ("✓", Palette::current().success) } else { ("󰐃", Palette::current().text) };
+            let style = if i == state.menu_index {
+                Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(color)
+            };
+            ListItem::new(vec![
+                Line::from(Span::styled(format!(" {} Chapter {}: {} ", mark, p.chapter, p.clue.how_found), style)),
+            ])
+        }).collect()
+    };
+    let list = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Pinned ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, middle[0]);
+
+    let mut detail: Vec<Line> = Vec::new();
+    if let Some(p) = pinned.get(state.menu_index) {
+        detail.push(Line::from(Span::styled(p.clue.description.clone(), Style::default().fg(Palette::current().text))));
+        detail.push(Line::from(""));
+        if p.understood {
+            detail.push(Line::from(vec![
+                Span::styled("It suggests: ", Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+                Span::styled(p.clue.what_it_suggests.clone(), Style::default().fg(Palette::current().text)),
+            ]));
+        } else {
+            detail.push(Line::from(Span::styled("What does it mean? Press Enter to think it through.", Styles::dim())));
+        }
+    }
+    let detail = Paragraph::new(detail)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" The Clue ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(detail, middle[1]);
+
+    let reflecting = match (&state.reflection, pinned.get(state.menu_index)) {
+        (Some(typed), Some(p)) => {
+            let typed: Vec<char> = typed.chars().collect();
+            let spans: Vec<Span> = p.reflection.chars().enumerate().map(|(i, c)| {
+                let style = match typed.get(i) {
+                    Some(t) if *t == c => Style::default().fg(Palette::current().success),
+                    Some(_) => Style::default().fg(Palette::current().danger).add_modifier(Modifier::UNDERLINED),
+                    None => Style::default().fg(Color::Gray),
+                };
+                Span::styled(c.to_string(), style)
+            }).collect();
+            vec![Line::from(spans), Line::from(Span::styled(format!("> {}", typed.iter().collect::<String>()), Styles::keybind()))]
+        }
+        _ => vec![Line::from(Span::styled("Type a clue's reflection to learn what it suggests.", Styles::dim()))],
+    };
+    let reflecting = Paragraph::new(reflecting)
+        .wrap(Wrap { trim: false })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" ⌨ Reflect ", Style::default().fg(Palette::current().secondary))));
+    f.render_widget(reflecting, chunks[2]);
+
+    let hints = if state.reflection.is_some() {
+        Line::from(vec![
+            Span::styled(" [Enter] ", Styles::keybind()),
+            Span::raw("Reflect  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Stop"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" [j/k] ", Styles::keybind()),
+            Span::raw("Navigate  "),
+            Span::styled("[Enter] ", Styles::keybind()),
+            Span::raw("Think it through  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Back"),
+        ])
+    };
+    let hints = Paragraph::new(hints)
+        .alignment(Alignment::Center)
+        .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render the letters waiting at the start of a run
+fn render_mailbox(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let mailbox = &state.mailbox;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(5),
+        ])
+        .split(main_area);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("󰇮 LETTERS 󰇮", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Waiting for you: "),
+        Span::styled(mailbox.len().to_string(), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+
+    let middle = layout::beside(chunks[1], [Constraint::Percentage(35), Constraint::Percentage(65)]);
+
+    let items: Vec<ListItem> = mailbox.iter().enumerate().map(|(i, letter)| {
+        let mark = match (letter.def.coded, letter.decoded) {
+            (Some(_), false) => "󰌆",
+            (Some(_), true) => "✓",
+            (None, _) => "󰇮",
+        };
+        let style = if i == state.menu_index {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Palette::current().text)
+        };
+        ListItem::new(Line::from(Span::styled(format!(" {} {} ", mark, letter.def.from), style)))
+    }).collect();
+    let list = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Mailbox ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, middle[0]);
+
+    let mut letter_lines: Vec<Line> = Vec::new();
+    if let Some(letter) = mailbox.get(state.menu_index) {
+        letter_lines.push(Line::from(Span::styled(letter.def.body, Style::default().fg(Palette::current().text))));
+        if let (Some(coded), Some(plain)) = (letter.cipher_text(), letter.def.coded) {
+            letter_lines.push(Line::from(""));
+            letter_lines.push(Line::from(Span::styled(coded, Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))));
+            if letter.decoded {
+                letter_lines.push(Line::from(Span::styled(format!("\"{}\"", plain), Style::default().fg(Palette::current().success).add_modifier(Modifier::ITALIC))));
+            }
+        }
+        letter_lines.push(Line::from(""));
+        letter_lines.push(Line::from(Span::styled(format!("— {}", letter.def.from), Styles::dim())));
+    }
+    let letter_text = Paragraph::new(letter_lines)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" The Letter ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(letter_text, middle[1]);
+
+    let decoding = match (&state.decoding, mailbox.get(state.menu_index).and_then(|l| l.puzzle())) {
+        (Some(typed), Some(plain)) => {
+            let typed: Vec<char> = typed.chars().collect();
+            let spans: Vec<Span> = plain.chars().enumerate().map(|(i, c)| {
+                // Only what was typed is shown, marked right or wrong
+                match typed.get(i) {
+                    Some(t) if t.eq_ignore_ascii_case(&c) => Span::styled(t.to_string(), Style::default().fg(Palette::current().success)),
+                    Some(t) => Span::styled(t.to_string(), Style::default().fg(Palette::current().danger).add_modifier(Modifier::UNDERLINED)),
+                    None => Span::styled(if c == ' ' { " " } else { "·" }, Style::default().fg(Color::DarkGray)),
+                }
+            }).collect();
+            vec![Line::from(spans)]
+        }
+        _ => vec![Line::from(Span::styled(
+            format!("Cipher moves every letter {} along the alphabet. Type a note back plain for {} gold.", CIPHER_SHIFT, DECODE_GOLD),
+            Styles::dim(),
+        ))],
+    };
+    let decoding = Paragraph::new(decoding)
+        .wrap(Wrap { trim: false })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" ⌨ Decode ", Style::default().fg(Palette::current().secondary))));
+    f.render_widget(decoding, chunks[2]);
+
+    let hints = if state.decoding.is_some() {
+        Line::from(vec![
+            Span::styled(" [Enter] ", Styles::keybind()),
+            Span::raw("Decode  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Stop"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" [j/k] ", Styles::keybind()),
+            Span::raw("Navigate  "),
+            Span::styled("[Enter] ", Styles::keybind()),
+            Span::raw("Decode  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Set out"),
+        ])
+    };
+    let hints = Paragraph::new(hints)
+        .alignment(Alignment::Center)
+        .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render Cipher's notes found this run
+fn render_ciphers(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let notes = &state.cipher_notes;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(5),
+        ])
+        .split(main_area);
+
+    let broken = notes.iter().filter(|n| n.broken()).count();
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("󰌆 CIPHER NOTES 󰌆", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Broken: "),
+        Span::styled(format!("{}/{}", broken, notes.len()), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+
+    let middle = layout::beside(chunks[1], [Constraint::Percentage(35), Constraint::Percentage(65)]);
+
+    let items: Vec<ListItem> = notes.iter().enumerate().map(|(i, note)| {
+        let mark = if note.broken() { "✓" } else { "󰌆" };
+        let style = if i == state.menu_index {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Palette::current().text)
+        };
+        ListItem::new(Line::from(Span::styled(format!(" {} Note {} ", mark, i + 1), style)))
+    }).collect();
+    let list = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Found ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, middle[0]);
+
+    let mut note_lines: Vec<Line> = Vec::new();
+    match notes.get(state.menu_index) {
+        Some(note) => {
+            note_lines.push(Line::from(Span::styled(format!("Found {}.", note.def.found), Styles::dim())));
+            note_lines.push(Line::from(""));
+            note_lines.push(Line::from(Span::styled(note.cipher_text(), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))));
+            let style = if note.broken() { Palette::current().success } else { Palette::current().text };
+            note_lines.push(Line::from(Span::styled(note.partial(), Style::default().fg(style))));
+            note_lines.push(Line::from(""));
+            note_lines.push(Line::from(Span::styled(
+                format!("Guesses: {}  Hints: {}  Worth: {} standing", note.guesses, note.hints, note.standing()),
+                Styles::dim(),
+            )));
+        }
+        None => note_lines.push(Line::from(Span::styled("Cipher hasn't left you anything yet. Look in treasure rooms.", Styles::dim()))),
+    }
+    let note_text = Paragraph::new(note_lines)
+        .wrap(Wrap { trim: false })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" The Note ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(note_text, middle[1]);
+
+    let guessing = match &state.cipher_guess {
+        Some(typed) => vec![Line::from(vec![
+            Span::styled(typed.clone(), Style::default().fg(Palette::current().text)),
+            Span::styled("▌", Style::default().fg(Palette::current().accent)),
+        ])],
+        None => vec![Line::from(Span::styled(
+            format!(
+                "Each letter stands for another. Type a guess at the note: letters in their right place are revealed, \
+                and a wrong guess buys a hint. Broken, a note is worth {} ShadowWriters standing, {} less per hint.",
+                BREAK_STANDING, HINT_COST,
+            ),
+            Styles::dim(),
+        ))],
+    };
+    let guessing = Paragraph::new(guessing)
+        .wrap(Wrap { trim: false })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" ⌨ Guess ", Style::default().fg(Palette::current().secondary))));
+    f.render_widget(guessing, chunks[2]);
+
+    let hints = if state.cipher_guess.is_some() {
+        Line::from(vec![
+            Span::styled(" [Enter] ", Styles::keybind()),
+            Span::raw("Guess  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Stop"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" [j/k] ", Styles::keybind()),
+            Span::raw("Navigate  "),
+            Span::styled("[Enter] ", Styles::keybind()),
+            Span::raw("Guess  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Back"),
+        ])
+    };
+    let hints = Paragraph::new(hints)
+        .alignment(Alignment::Center)
+        .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// An axis of the ideology as a line: one pole, where the run stands, the
+/// other pole
+fn ideology_axis(ideology: &Ideology, left: Pole, right: Pole) -> Line<'static> {
+    const WIDTH: i32 = 21;
+    let at = ((100 - ideology.toward(left)) * (WIDTH - 1) / 200) as usize;
+    let track: String = (0..WIDTH as usize).map(|i| if i == at { '●' } else { '─' }).collect();
+    let lit = |pole: Pole| {
+        let style = if ideology.leans(pole) { Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD) } else { Styles::dim() };
+        Span::styled(format!("{:^10}", pole.name()), style)
+    };
+    Line::from(vec![lit(left), Span::styled(format!(" ◀{}▶ ", track), Style::default().fg(Palette::current().secondary)), lit(right)])
+}
+
+/// Render what the run's choices have made of you
+fn render_ideology(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let ideology = &state.ideology;
+    let heading = Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(Span::styled("WHAT YOU HAVE BECOME", heading)),
+        Line::from(""),
+        Line::from(Span::styled("Every choice you made on the way down leaned you one way or another.", Styles::dim())),
+        Line::from(""),
+        ideology_axis(ideology, Pole::Preserve, Pole::Unwrite),
+        ideology_axis(ideology, Pole::Control, Pole::Release),
+        Line::from(""),
+    ];
+
+    let voice = ideology.strongest().voice();
+    lines.push(Line::from(vec![
+        Span::styled("The loudest voice: ", heading),
+        Span::styled(voice.name, Style::default().fg(Palette::current().accent)),
+    ]));
+    if let Some(line) = voice.lines.first() {
+        lines.push(Line::from(Span::styled(format!("\"{}\"", line), Style::default().fg(Palette::current().text).add_modifier(Modifier::ITALIC))));
+    }
+    lines.push(Line::from(""));
+    if let Some(faction) = ideology.favoured() {
+        lines.push(Line::from(vec![
+            Span::styled("Offers found you most from: ", heading),
+            Span::styled(faction.name(), Style::default().fg(Palette::current().accent)),
+        ]));
+        lines.push(Line::from(""));
+    }
+
+    let closed = endings::closed(ideology);
+    if closed.is_empty() {
+        lines.push(Line::from(Span::styled("No ending is closed to you yet.", Style::default().fg(Palette::current().success))));
+    } else {
+        lines.push(Line::from(Span::styled("Closed to you by what you believe:", heading)));
+        for ending in closed {
+            lines.push(Line::from(Span::styled(format!("✗ {}", ending.id_title().1), Style::default().fg(Palette::current().danger))));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Go on"),
+    ]));
+
+    let reveal = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(reveal, area);
+}
+
+fn render_memories(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let world = &state.world_state;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+        ])
+        .split(main_area);
+
+    let seen = MEMORIES.iter().filter(|m| memories::seen(world, m)).count();
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("◌ MEMORIES ◌", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Remembered: "),
+        Span::styled(format!("{}/{}", seen, MEMORIES.len()), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+
+    let middle = layout::beside(chunks[1], [Constraint::Percentage(35), Constraint::Percentage(65)]);
+
+    let mut items: Vec<ListItem> = Vec::new();
+    for (i, memory) in MEMORIES.iter().enumerate() {
+        if i == 0 || MEMORIES[i - 1].chapter != memory.chapter {
+            let whole = if memories::whole(world, memory.chapter) { " ✓" } else { "" };
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!("Chapter {}{}", memory.chapter, whole),
+                Style::default().fg(Palette::current().primary),
+            ))));
+        }
+        let title = if memories::seen(world, memory) { memory.title } else { "???" };
+        let style = if i == state.menu_index {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Palette::current().text)
+        };
+        items.push(ListItem::new(Line::from(Span::styled(format!("  {} ", title), style))));
+    }
+    let list = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Gallery ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, middle[0]);
+
+    let mut lines: Vec<Line> = Vec::new();
+    match MEMORIES.get(state.menu_index).filter(|m| memories::seen(world, m)) {
+        Some(memory) => {
+            lines.push(Line::from(Span::styled(memory.flash, Style::default().fg(Palette::current().accent).add_modifier(Modifier::ITALIC))));
+            lines.push(Line::from(""));
+            match state.memory_replay {
+                Some(started) => {
+                    let played = memories::replayed(memory, started.elapsed().as_millis() as u64);
+                    lines.push(Line::from(Span::styled(played, Style::default().fg(Palette::current().text))));
+                }
+                None => lines.push(Line::from(Span::styled("Press Enter to remember it in full.", Styles::dim()))),
+            }
+        }
+        None => lines.push(Line::from(Span::styled(
+            format!(
+                "Not yet remembered. Memories flash past as you enter rooms, each in its own chapter of the descent. \
+                A chapter remembered whole adds {} max HP to the run.",
+                WHOLE_CHAPTER_HP,
+            ),
+            Styles::dim(),
+        ))),
+    }
+    let memory = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" The Memory ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(memory, middle[1]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Navigate  "),
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Replay  "),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render the theme picker, drawn in the theme selected
+/// A keycap's color for its error rate, or none while it has too few presses
+fn heat_color(key: &KeyRecord) -> Option<Color> {
+    let palette = Palette::current();
+    key.error_rate().map(|rate| match rate {
+        r if r < 0.03 => palette.success,
+        r if r < 0.08 => palette.warning,
+        _ => palette.danger,
+    })
+}
+
+fn render_key_heatmap(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let palette = Palette::current();
+    let icons = Icons::current();
+    let layout = state.config.typing.layout;
+    let accuracy = &state.key_accuracy;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(9),
+            Constraint::Min(5),
+        ])
+        .split(main_area);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{} KEY ACCURACY", icons.keyboard), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("   {} keys counted across your runs", accuracy.presses()), Style::default().fg(palette.text_dim)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(header, chunks[0]);
+
+    // Rows of keycaps, staggered as on the board, each colored by its misses
+    let wide = chunks[1].width >= 70;
+    let mut lines = vec![Line::from("")];
+    for (row, (legends, stagger)) in layout.rows().iter().zip([0, 1, 3]).enumerate() {
+        let mut spans = vec![Span::raw(" ".repeat(if wide { stagger * 2 } else { stagger }))];
+        for (col, legend) in legends.chars().enumerate() {
+            let key = accuracy.key(row, col);
+            let cap = if wide { format!("  {}  ", legend.to_uppercase()) } else { format!(" {} ", legend.to_uppercase()) };
+            let style = match heat_color(&key) {
+                Some(color) => Style::default().fg(palette.bg_dark).bg(color).add_modifier(Modifier::BOLD),
+                None => Style::default().fg(palette.text_dim).bg(palette.bg_panel),
+            };
+            spans.push(Span::styled(cap, style));
+            spans.push(Span::raw(" "));
+        }
+        lines.push(Line::from(spans));
+        lines.push(Line::from(""));
+    }
+    let legend = |text: &'static str, color: Color| Span::styled(format!(" {} ", text), Style::default().fg(palette.bg_dark).bg(color));
+    lines.push(Line::from(vec![
+        legend("under 3%", palette.success),
+        Span::raw(" "),
+        legend("under 8%", palette.warning),
+        Span::raw(" "),
+        legend("8% or more", palette.danger),
+        Span::raw(" "),
+        Span::styled(format!(" fewer than {} presses ", MIN_PRESSES), Style::default().fg(palette.text_dim).bg(palette.bg_panel)),
+    ]));
+    let keyboard = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border))
+            .title(Span::styled(format!(" {} ", layout.name()), Style::default().fg(palette.primary))));
+    f.render_widget(keyboard, chunks[1]);
+
+    let worst = accuracy.worst(layout);
+    let lines: Vec<Line> = if worst.is_empty() {
+        vec![Line::from(Span::styled(
+            format!("No key has {} presses yet. Fight a few battles and come back.", MIN_PRESSES),
+            Styles::dim(),
+        ))]
+    } else {
+        worst.iter().take(chunks[2].height.saturating_sub(2) as usize).map(|(legend, key)| {
+            let color = heat_color(key).unwrap_or(palette.text_dim);
+            Line::from(vec![
+                Span::styled(format!(" {}  ", legend.to_uppercase()), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:>3.0}% missed", key.error_rate().unwrap_or(0.0) * 100.0), Style::default().fg(palette.text)),
+                Span::styled(format!("  ({} of {} presses)", key.misses, key.presses), Styles::dim()),
+            ])
+        }).collect()
+    };
+    let list = Paragraph::new(lines)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border))
+            .title(Span::styled(" Costliest keys ", Style::default().fg(palette.primary))));
+    f.render_widget(list, chunks[2]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [l] ", Styles::keybind()),
+        Span::raw(format!("Layout: {}  ", layout.name())),
+        Span::styled("[Esc] ", Style::default().fg(palette.warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(palette.bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+fn render_settings(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let palette = Palette::current();
+    let icons = Icons::current();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+        ])
+        .split(main_area);
+
+    let changed = state.settings_before.as_ref()
+        .map_or(0, |before| Setting::ALL.iter().filter(|s| s.value(before) != s.value(&state.config)).count());
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{} SETTINGS", icons.menu), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD)),
+        Span::styled(
+            match changed {
+                0 => "   Nothing changed".to_string(),
+                1 => "   1 change, saved on leaving".to_string(),
+                n => format!("   {} changes, saved on leaving", n),
+            },
+            Style::default().fg(palette.text_dim),
+        ),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(header, chunks[0]);
+
+    let panels = layout::beside(chunks[1], [Constraint::Percentage(55), Constraint::Percentage(45)]);
+
+    // Each section under its heading, the selected setting's value between arrows
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    for (i, setting) in Setting::ALL.iter().enumerate() {
+        if i == 0 || Setting::ALL[i - 1].section() != setting.section() {
+            if i > 0 {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(setting.section().to_uppercase(), Style::default().fg(palette.secondary).add_modifier(Modifier::BOLD))));
+        }
+        let value = setting.value(&state.config);
+        let line = if i == state.menu_index {
+            selected_line = lines.len();
+            Line::from(vec![
+                Span::styled(format!(" {} {:<16}", icons.arrow_right, setting.name()), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{} {} {}", icons.arrow_left, value, icons.arrow_right), Style::default().fg(palette.bg_dark).bg(palette.primary)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled(format!("   {:<16}", setting.name()), Style::default().fg(palette.text)),
+                Span::styled(format!("  {}", value), Style::default().fg(palette.text_dim)),
+            ])
+        };
+        lines.push(line);
+    }
+    let visible = panels[0].height.saturating_sub(2) as usize;
+    let scroll = selected_line.saturating_sub(visible.saturating_sub(2));
+    let list = Paragraph::new(lines)
+        .scroll((scroll as u16, 0))
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(list, panels[0]);
+
+    // The selected setting, and a sample drawn as the settings stand
+    let selected = Setting::ALL.get(state.menu_index).copied().unwrap_or(Setting::ALL[0]);
+    let swatch = |color: Color| Span::styled("  ", Style::default().bg(color));
+    let preview = vec![
+        Line::from(Span::styled(selected.name(), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled(selected.describe(), Style::default().fg(palette.text))),
+        Line::from(""),
+        Line::from(Span::styled("Preview", Style::default().fg(palette.secondary).add_modifier(Modifier::BOLD))),
+        Line::from(vec![
+            Span::styled("swo", Styles::typed_correct()),
+            Span::styled("t", Styles::typed_wrong()),
+            Span::styled("d", Styles::cursor()),
+            Span::styled(" and shield", Styles::untyped()),
+        ]),
+        Line::from(format!("{} {} {} {} {} {}", icons.sword, icons.shield, icons.heart, icons.mana, icons.gold, icons.keyboard)),
+        Line::from(vec![
+            swatch(palette.primary), Span::raw(" "),
+            swatch(palette.success), Span::raw(" "),
+            swatch(palette.warning), Span::raw(" "),
+            swatch(palette.danger), Span::raw(" "),
+            swatch(palette.info),
+        ]),
+    ];
+    let details = Paragraph::new(preview)
+        .wrap(Wrap { trim: true })
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border))
+            .title(Span::styled(format!(" {} ", state.config.display.theme.as_deref().unwrap_or("Default")), Style::default().fg(palette.primary))));
+    f.render_widget(details, panels[1]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Select  "),
+        Span::styled("[←/→] ", Styles::keybind()),
+        Span::raw("Change  "),
+        Span::styled("[u] ", Styles::keybind()),
+        Span::raw("Undo all  "),
+        Span::styled("[b] ", Styles::keybind()),
+        Span::raw("Keybinds  "),
+        Span::styled("[Esc] ", Style::default().fg(palette.warning)),
+        Span::raw("Save and back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(palette.bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+fn render_keybinds(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let palette = Palette::current();
+    let icons = Icons::current();
+    let keybinds = &state.keybinds;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(3),
+        ])
+        .split(main_area);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{} KEYBINDS", icons.keyboard), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD)),
+        Span::styled("   Typing keys are never rebound", Style::default().fg(palette.text_dim)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(header, chunks[0]);
+
+    // Each context under its heading, the actions with their keys
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    for context in Context::ALL {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(context.name().to_uppercase(), Style::default().fg(palette.secondary).add_modifier(Modifier::BOLD))));
+        for (i, action) in Action::ALL.iter().enumerate().filter(|(_, a)| a.context() == context) {
+            let selected = i == state.menu_index;
+            if selected {
+                selected_line = lines.len();
+            }
+            let keys = if selected && state.keybind_capture.is_some() {
+                Span::styled(" press a key... ", Style::default().fg(palette.bg_dark).bg(palette.warning).add_modifier(Modifier::BOLD))
+            } else if selected {
+                Span::styled(format!(" {} ", keybinds.label(*action)), Style::default().fg(palette.bg_dark).bg(palette.primary))
+            } else {
+                Span::styled(format!(" {} ", keybinds.label(*action)), Style::default().fg(palette.text))
+            };
+            let marker = if selected { icons.arrow_right } else { " " };
+            let name_style = if selected { Style::default().fg(palette.primary).add_modifier(Modifier::BOLD) } else { Style::default().fg(palette.text) };
+            let mut spans = vec![Span::styled(format!(" {} {:<22}", marker, action.name()), name_style), keys];
+            if !keybinds.is_default(*action) {
+                spans.push(Span::styled(format!("  default {}", action.defaults().join("/")), Styles::dim()));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    let visible = chunks[1].height.saturating_sub(2) as usize;
+    let scroll = selected_line.saturating_sub(visible.saturating_sub(2));
+    let list = Paragraph::new(lines)
+        .scroll((scroll as u16, 0))
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(list, chunks[1]);
+
+    let note = match (&state.keybind_capture, &state.keybind_note) {
+        (Some(true), _) => Span::styled("Press a key to add to this action. Esc to give up.", Style::default().fg(palette.warning)),
+        (Some(false), _) => Span::styled("Press a key to put in place of this action's keys. Esc to give up.", Style::default().fg(palette.warning)),
+        (None, Some(note)) => Span::styled(note.clone(), Style::default().fg(palette.info)),
+        (None, None) => Span::styled("A key is refused if it types, or if another action could fire on it at the same time.", Styles::dim()),
+    };
+    let note = Paragraph::new(Line::from(note))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(note, chunks[2]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Select  "),
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Replace  "),
+        Span::styled("[a] ", Styles::keybind()),
+        Span::raw("Add  "),
+        Span::styled("[d] ", Styles::keybind()),
+        Span::raw("Default  "),
+        Span::styled("[Esc] ", Style::default().fg(palette.warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(palette.bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// The short name an attack goes by under its bar
+fn attack_label(attack: AttackType) -> &'static str {
+    match attack {
+        AttackType::Deliberate => "Heavy",
+        AttackType::Flurry => "Flurry",
+        AttackType::Precision => "Precise",
+        AttackType::Frantic => "Wild",
+        AttackType::Standard => "Plain",
+    }
+}
+
+fn render_dashboard(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let palette = Palette::current();
+    let icons = Icons::current();
+    let store = &state.stats_store;
+    let all_runs = state.dashboard_all_runs;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(9),
+        ])
+        .split(main_area);
+
+    let tabs = Tabs::new(vec!["This run", "All runs"])
+        .select(usize::from(all_runs))
+        .style(Style::default().fg(palette.text_dim))
+        .highlight_style(Style::default().fg(palette.primary).add_modifier(Modifier::BOLD))
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border))
+            .title(Span::styled(format!(" {} DASHBOARD ", icons.speed), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD))));
+    f.render_widget(tabs, chunks[0]);
+
+    // Word by word through this run, or run by run through every run kept
+    let (tallies, summary): (Vec<Tally>, Tally) = if all_runs {
+        let tallies: Vec<Tally> = store.runs.iter().map(|r| r.tally.clone()).collect();
+        let words = tallies.iter().map(|t| t.words).sum::<u32>();
+        let weight = words.max(1) as f32;
+        let mean = |value: fn(&Tally) -> f32| tallies.iter().map(|t| value(t) * t.words as f32).sum::<f32>() / weight;
+        let summary = Tally {
+            words,
+            wpm: mean(|t| t.wpm),
+            accuracy: mean(|t| t.accuracy),
+            damage_per_word: mean(|t| t.damage_per_word),
+            attacks: store.all_attacks(),
+        };
+        (tallies, summary)
+    } else {
+        let tallies = store.current.iter().map(|w| Tally::of(std::slice::from_ref(w))).collect();
+        (tallies, Tally::of(&store.current))
+    };
+    let counted = if all_runs { format!("{} runs", tallies.len()) } else { format!("{} words", summary.words) };
+    let summary_line = Paragraph::new(Line::from(vec![
+        Span::styled(counted, Style::default().fg(palette.text).add_modifier(Modifier::BOLD)),
+        Span::raw("   "),
+        Span::styled(format!("{} {:.0} WPM", icons.speed, summary.wpm), Style::default().fg(wpm_color(summary.wpm))),
+        Span::raw("   "),
+        Span::styled(format!("{} {:.0}%", icons.accuracy, summary.accuracy * 100.0), Style::default().fg(accuracy_color(summary.accuracy * 100.0))),
+        Span::raw("   "),
+        Span::styled(format!("{} {:.1} a word", icons.damage, summary.damage_per_word), Style::default().fg(palette.warning)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(summary_line, chunks[1]);
+
+    if tallies.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            if all_runs { "No run has ended yet. Finish one and it will be charted here." } else { "No word has landed this run yet. Fight a battle and come back." },
+            Styles::dim(),
+        ))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+        f.render_widget(empty, chunks[2]);
+    } else {
+        let panels = layout::beside(chunks[2], [Constraint::Percentage(65), Constraint::Percentage(35)]);
+        let lines = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+            .split(panels[0]);
+        let width = lines[0].width.saturating_sub(2) as usize;
+        let recent = &tallies[tallies.len().saturating_sub(width)..];
+        let points = |value: fn(&Tally) -> f32| -> Vec<u64> { recent.iter().map(|t| value(t).max(0.0).round() as u64).collect() };
+        let charts = [
+            ("WPM", palette.info, points(|t| t.wpm)),
+            ("Accuracy %", palette.success, points(|t| t.accuracy * 100.0)),
+            ("Damage per word", palette.warning, points(|t| t.damage_per_word)),
+        ];
+        for ((title, color, data), line) in charts.into_iter().zip(lines.iter()) {
+            let peak = data.iter().copied().max().unwrap_or(0);
+            let sparkline = Sparkline::default()
+                .data(&data)
+                .style(Style::default().fg(color))
+                .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border))
+                    .title(Span::styled(format!(" {} ", title), Style::default().fg(color)))
+                    .title(Line::from(Span::styled(format!(" peak {} ", peak), Styles::dim())).right_aligned()));
+            f.render_widget(sparkline, *line);
+        }
+
+        let bars: Vec<Bar> = ATTACKS.iter().zip(summary.attacks).map(|(attack, count)| {
+            Bar::default()
+                .value(count as u64)
+                .label(Line::from(attack_label(*attack)))
+                .style(Style::default().fg(palette.primary))
+                .value_style(Style::default().fg(palette.bg_dark).bg(palette.primary))
+        }).collect();
+        let bar_width = (panels[1].width.saturating_sub(2) / ATTACKS.len() as u16).saturating_sub(1).clamp(1, 7);
+        let chart = BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(bar_width)
+            .bar_gap(1)
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border))
+                .title(Span::styled(" Attacks ", Style::default().fg(palette.primary))));
+        f.render_widget(chart, panels[1]);
+    }
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [Tab] ", Styles::keybind()),
+        Span::raw(if all_runs { "This run  " } else { "All runs  " }),
+        Span::styled("[Esc] ", Style::default().fg(palette.warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(palette.bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+fn render_themes(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let palette = Palette::current();
+    let icons = Icons::current();
+    let selected = theme::current();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+        ])
+        .split(main_area);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{} THEMES", icons.star), Style::default().fg(palette.primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Theme files: "),
+        Span::styled(themes::themes_dir().display().to_string(), Style::default().fg(palette.text_dim)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border)));
+    f.render_widget(header, chunks[0]);
+
+    let middle = layout::beside(chunks[1], [Constraint::Percentage(35), Constraint::Percentage(65)]);
+
+    let mut items: Vec<ListItem> = state.themes.iter().enumerate()
+        .map(|(i, t)| {
+            let style = if i == state.menu_index {
+                Style::default().fg(palette.secondary).add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(palette.text)
+            };
+            let kept = if state.config.display.theme.as_deref().unwrap_or("Default") == t.name { " ✓" } else { "" };
+            ListItem::new(Line::from(Span::styled(format!(" {}{} ", t.name, kept), style)))
+        })
+        .collect();
+    for error in &state.theme_errors {
+        items.push(ListItem::new(Line::from(Span::styled(format!(" {} {}", icons.warning, error), Style::default().fg(palette.danger)))));
+    }
+    let list = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border))
+            .title(Span::styled(" Themes ", Style::default().fg(palette.primary))));
+    f.render_widget(list, middle[0]);
+
+    let swatch = |name: &'static str, color: Color| Span::styled(format!(" {} ", name), Style::default().fg(palette.bg_dark).bg(color));
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Borders: ", Styles::dim()),
+            Span::styled(selected.borders.name(), Style::default().fg(palette.text)),
+            Span::styled("   Icons: ", Styles::dim()),
+            Span::styled(selected.icons.name(), Style::default().fg(palette.text)),
+            Span::styled(format!("   (terminal draws {})", theme::terminal_icons().name()), Styles::dim()),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            swatch("primary", palette.primary),
+            swatch("secondary", palette.secondary),
+            swatch("accent", palette.accent),
+        ]),
+        Line::from(vec![
+            swatch("success", palette.success),
+            swatch("warning", palette.warning),
+            swatch("danger", palette.danger),
+            swatch("info", palette.info),
+        ]),
+        Line::from(vec![
+            swatch("common", palette.common),
+            swatch("rare", palette.rare),
+            swatch("epic", palette.epic),
+            swatch("legendary", palette.legendary),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("keyb", Styles::typed_correct()),
+            Span::styled("p", Styles::typed_wrong()),
+            Span::styled("a", Styles::cursor()),
+            Span::styled("rd warrior", Styles::untyped()),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{} 42/50  ", icons.heart), Style::default().fg(palette.player_hp)),
+            Span::styled(format!("{} 18/30  ", icons.mana), Style::default().fg(palette.mp)),
+            Span::styled(format!("{} x12  ", icons.combo), Styles::combo()),
+            Span::styled(format!("{} 87", icons.gold), Style::default().fg(palette.secondary)),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{} {} {} {} {}", icons.combat, icons.boss, icons.treasure, icons.rest, icons.shop), Style::default().fg(palette.accent)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("The ink remembers what the page forgets.", Style::default().fg(palette.text))),
+        Line::from(Span::styled("Something below knows your face.", Style::default().fg(palette.corrupted).add_modifier(Modifier::ITALIC))),
+    ];
+    let preview = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().bg(palette.bg_panel))
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(palette.border_focus))
+            .title(Span::styled(format!(" {} ", selected.name), Style::default().fg(palette.primary))));
+    f.render_widget(preview, middle[1]);
+
+    let mut hints = vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Preview  "),
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Keep  "),
+        Span::styled("[e] ", Styles::keybind()),
+        Span::raw("Write as file  "),
+        Span::styled("[Esc] ", Style::default().fg(palette.warning)),
+        Span::raw("Back"),
+    ];
+    if let Some(note) = &state.theme_note {
+        hints.push(Span::styled(format!("   {}", note), Styles::dim()));
+    }
+    let hints = Paragraph::new(Line::from(hints))
+        .alignment(Alignment::Center)
+        .style(Style::default().bg(palette.bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render the level-up choices
+fn render_level_up(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let Some(player) = &state.player else {
+        return;
+    };
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+        ])
+        .split(main_area);
+    
+    let mut title = vec![
+        Span::styled("✦ LEVEL UP ✦", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Now level "),
+        Span::styled(format!("{}", player.level), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+    ];
+    if state.pending_level_ups > 1 {
+        title.push(Span::styled(format!("   ({} choices waiting)", state.pending_level_ups), Style::default().fg(Color::Gray)));
+    }
+    let header = Paragraph::new(Line::from(title))
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+    
+    let items: Vec<ListItem> = LevelUpChoice::ALL.iter().enumerate().map(|(i, choice)| {
+        let style = if i == state.menu_index {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Palette::current().text)
+        };
+        ListItem::new(vec![
+            Line::from(vec![
+                Span::styled(format!(" [{}] {} ", i + 1, choice.name()), style.add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  {}", choice.describe()), Style::default().fg(Palette::current().success)),
+            ]),
+            Line::from(Span::styled(format!("     now {}", choice.current(player)), Style::default().fg(Color::Gray))),
+        ])
+    }).collect();
+    let choices = List::new(items)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Choose one ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(choices, chunks[1]);
+    
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [1-4] ", Styles::keybind()),
+        Span::raw("Choose  "),
+        Span::styled("[j/k] ", Styles::keybind()),
+        Span::raw("Navigate  "),
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Confirm"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render the fork in the stairs: the zones on offer for the next floors
+fn render_route_choice(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let next_floor = state.get_current_floor() + 1;
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+        ])
+        .split(main_area);
+    
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("⑂ THE STAIRS FORK ⑂", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw("   Floors "),
+        Span::styled(format!("{}-{}", next_floor, next_floor + 1), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+    
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, state.route_fork.len().max(1) as u32); state.route_fork.len().max(1)])
+        .split(chunks[1]);
+    for (i, zone) in state.route_fork.iter().enumerate() {
+        let selected = i == state.menu_index;
+        let border = if selected { zone_color(zone.name()) } else { Palette::current().border };
+        let elite = state.game_data.enemies.enemies.get(zone.elite_id()).map(|e| e.name.as_str()).unwrap_or("Unknown");
+        let lines = vec![
+            Line::from(Span::styled(zone.description(), Style::default().fg(Palette::current().text))),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Elite: ", Style::default().fg(Color::Gray)),
+                Span::styled(elite, Style::default().fg(Palette::current().danger)),
+            ]),
+        ];
+        let title_style = if selected {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            Style::default().fg(Palette::current().primary)
+        };
+        let panel = Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(border))
+                .title(Span::styled(format!(" [{}] {} ", i + 1, zone.name()), title_style)));
+        f.render_widget(panel, columns[i]);
+    }
+    
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [1-2] ", Styles::keybind()),
+        Span::raw("Choose  "),
+        Span::styled("[←/→] ", Styles::keybind()),
+        Span::raw("Navigate  "),
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Descend"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render the profile-wide ledger of world changes
+fn render_consequences(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let ledger = &state.world_state.ledger;
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(10),
+        ])
+        .split(main_area);
+    
+    let header_text = vec![
+        Line::from(vec![
+            Span::styled("◈ ", Style::default().fg(Palette::current().accent)),
+            Span::styled("CONSEQUENCES", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+            Span::styled(" ◈", Style::default().fg(Palette::current().accent)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Timeline "),
+            Span::styled(format!("{}", ledger.timeline), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+            Span::raw("   Changes: "),
+            Span::styled(format!("{}", ledger.changes.len()), Style::default().fg(Palette::current().secondary)),
+        ]),
+    ];
+    
+    let header = Paragraph::new(header_text)
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+    
+    // One section per kind of change
+    let mut lines: Vec<Line> = Vec::new();
+    for kind in ChangeKind::ALL {
+        let changes = ledger.of_kind(kind);
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", kind.icon()), Style::default().fg(Palette::current().primary)),
+            Span::styled(kind.heading(), Style::default().fg(Palette::current().secondary).add_modifier(Modifier::BOLD)),
+        ]));
+        if changes.is_empty() {
+            lines.push(Line::from(Span::styled("   none yet", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))));
+        }
+        for change in changes {
+            lines.push(Line::from(vec![
+                Span::raw("   "),
+                Span::styled(change.description.as_str(), Style::default().fg(Palette::current().text)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    
+    let list = Paragraph::new(lines)
+        .scroll((state.menu_index as u16, 0))
+        .wrap(Wrap { trim: false })
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" ◈ Permanent World Changes ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, chunks[1]);
+    
+    // Key hints (or the fresh timeline confirmation)
+    let hints = if state.timeline_confirm {
+        Paragraph::new(Line::from(vec![
+            Span::styled(" Wipe the world and begin a fresh timeline? Stats and cosmetics are kept. ", Style::default().fg(Palette::current().warning)),
+            Span::styled("[y] ", Style::default().fg(Palette::current().danger)),
+            Span::raw("Confirm  "),
+            Span::styled("[any] ", Styles::keybind()),
+            Span::raw("Cancel"),
+        ]))
+    } else {
+        Paragraph::new(Line::from(vec![
+            Span::styled(" [j/k] ", Styles::keybind()),
+            Span::raw("Scroll  "),
+            Span::styled("[f] ", Style::default().fg(Palette::current().danger)),
+            Span::raw("Fresh Timeline  "),
+            Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+            Span::raw("Back to Menu"),
+        ]))
+    };
+    f.render_widget(hints.alignment(Alignment::Center).style(Style::default().bg(Palette::current().bg_panel)), hint_area);
+}
+
+fn render_calibration(f: &mut Frame, state: &GameState) {
+    let Some(calibration) = &state.calibration else {
+        return;
+    };
+    let now = std::time::Instant::now();
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(8),
+        ])
+        .split(main_area);
+    
+    let header_text = vec![
+        Line::from(Span::styled("LATENCY CALIBRATION", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Tap Space on every beat. The game measures how late your keys arrive.",
+            Style::default().fg(Palette::current().text_dim),
+        )),
+    ];
+    let header = Paragraph::new(header_text)
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+    
+    let taps = calibration.offsets.len();
+    let mut lines = vec![Line::from("")];
+    if calibration.is_done() {
+        let latency = calibration.result_ms().unwrap_or(0);
+        lines.push(Line::from(Span::styled(
+            format!("Measured latency: {} ms", latency),
+            Style::default().fg(Palette::current().success).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(format!("(currently {} ms)", state.config.typing.latency_ms)));
+    } else {
+        let beat = if calibration.is_lit(now) {
+            Span::styled("●", Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD))
+        } else {
+            Span::styled("○", Style::default().fg(Palette::current().text_dim))
+        };
+        lines.push(Line::from(beat));
+        lines.push(Line::from(""));
+        lines.push(Line::from(if calibration.in_lead_in(now) {
+            "Get ready...".to_string()
+        } else {
+            format!("Taps: {}/{}", taps, CALIBRATION_BEATS)
+        }));
+    }
+    if taps > 0 {
+        let offsets: Vec<String> = calibration.offsets.iter().map(|o| format!("{:+}", o)).collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!("{} ms", offsets.join("  ")), Style::default().fg(Palette::current().text_dim))));
+    }
+    let body = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border_focus)));
+    f.render_widget(body, chunks[1]);
+    
+    let mut hints = vec![
+        Span::styled(" [Space] ", Styles::keybind()),
+        Span::raw("Tap  "),
+    ];
+    if calibration.is_done() {
+        hints.push(Span::styled("[Enter] ", Styles::keybind()));
+        hints.push(Span::raw("Save  "));
+    }
+    hints.extend([
+        Span::styled("[r] ", Styles::keybind()),
+        Span::raw("Restart  "),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+        Span::raw("Back"),
+    ]);
+    let hints = Paragraph::new(Line::from(hints))
+        .alignment(Alignment::Center)
+        .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+fn render_challenges(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(10),
+        ])
+        .split(main_area);
+    
+    let beaten = state.challenges.iter()
+        .filter(|c| state.challenge_bests.best(&c.id).is_some_and(|best| best >= c.target_score))
+        .count();
+    let header_text = vec![
+        Line::from(vec![
+            Span::styled("⚑ ", Style::default().fg(Palette::current().accent)),
+            Span::styled("CHALLENGES", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+            Span::styled(" ⚑", Style::default().fg(Palette::current().accent)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Installed: "),
+            Span::styled(format!("{}", state.challenges.len()), Style::default().fg(Palette::current().accent).add_modifier(Modifier::BOLD)),
+            Span::raw("   Beaten: "),
+            Span::styled(format!("{}", beaten), Style::default().fg(Palette::current().secondary)),
+        ]),
+    ];
+    
+    let header = Paragraph::new(header_text)
+        .alignment(Alignment::Center)
+        .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, chunks[0]);
+    
+    let items: Vec<ListItem> = if state.challenges.is_empty() {
+        vec![
+            ListItem::new(Line::from(Span::styled("No challenges installed yet!", Style::default().fg(Palette::current().secondary).add_modifier(Modifier::ITALIC)))),
+            ListItem::new(Line::from(Span::styled(
+                format!("Drop challenge files into {} or run with --import-challenge <file>", challenges::challenges_dir().display()),
+                Style::default().fg(Color::Gray),
+            ))),
+        ]
+    } else {
+        state.challenges.iter().enumerate().map(|(i, challenge)| {
+            let style = if i == state.menu_index {
+                Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(Palette::current().text)
+            };
+            let best = state.challenge_bests.best(&challenge.id);
+            let best_span = match best {
+                Some(score) if score >= challenge.target_score => Span::styled(format!("★ Best {}", score), Style::default().fg(Palette::current().success)),
+                Some(score) => Span::styled(format!("Best {}", score), Style::default().fg(Palette::current().text)),
+                None => Span::styled("Not attempted", Style::default().fg(Color::DarkGray)),
+            };
+            
+            let mut rules = Vec::new();
+            if let Some(class) = challenge.class_lock {
+                rules.push(format!("{} only", class.name()));
+            }
+            if let Some(pack) = &challenge.word_pack {
+                rules.push(format!("words: {}", pack));
+            }
+            for (modifier, level) in &challenge.modifiers {
+                rules.push(format!("{} {}", modifier.name(), level));
+            }
+            if rules.is_empty() {
+                rules.push("no special rules".to_string());
+            }
+            
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled(" ⚑ ", Style::default().fg(Palette::current().primary)),
+                    Span::styled(&challenge.name, style.add_modifier(Modifier::BOLD)),
+                    Span::styled(if challenge.author.is_empty() { String::new() } else { format!(" by {}", challenge.author) }, Style::default().fg(Color::Gray)),
+                    Span::raw("  "),
+                    Span::styled(format!("[Target {}]", challenge.target_score), Style::default().fg(Palette::current().accent)),
+                    Span::raw(" "),
+                    best_span,
+                ]),
+                Line::from(vec![
+                    Span::raw("   "),
+                    Span::styled(rules.join(" · "), Style::default().fg(Palette::current().secondary)),
+                ]),
+            ];
+            if !challenge.description.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::raw("   "),
+                    Span::styled(&challenge.description, Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC)),
+                ]));
+            }
+            ListItem::new(lines)
+        }).collect()
+    };
+    
+    let list = List::new(items)
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" ⚑ Community Challenges ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, chunks[1]);
+    
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [j/k] ", Styles::keybind()),
+        Span::raw("Navigate  "),
+        Span::styled("[Enter] ", Styles::keybind()),
+        Span::raw("Attempt  "),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+        Span::raw("Back to Menu"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+/// Render typing feel effects overlay on combat screen
+fn render_typing_feel_overlay(f: &mut Frame, state: &GameState, area: Rect) {
+    let feel = &state.typing_feel;
+    
+    // Combo display in top-right corner
+    if feel.combo > 0 {
+        let combo_width = 20;
+        let combo_height = 3;
+        let combo_area = Rect::new(
+            area.width.saturating_sub(combo_width + 2),
+            1,
+            combo_width,
+            combo_height,
+        );
+        
+        let combo_text = if feel.combo >= 10 {
+            format!("󱋊 {} COMBO! 󱋊\nx{:.1} DMG", feel.combo, feel.combo_multiplier)
+        } else {
+            format!("󱋊 {} Combo\nx{:.1} DMG", feel.combo, feel.combo_multiplier)
+        };
+        
+        let combo_color = if feel.combo >= 20 {
+            Palette::current().accent
+        } else if feel.combo >= 10 {
+            Color::Red
+        } else if feel.combo >= 5 {
+            Color::Yellow
+        } else {
+            Color::Cyan
+        };
+        
+        let combo_widget = Paragraph::new(combo_text)
+            .style(Style::default().fg(combo_color).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(zone_color(&state.dungeon.as_ref().map(|d| d.zone_name.as_str()).unwrap_or("Unknown")))));
+        f.render_widget(combo_widget, combo_area);
+    }
+    
+    // Flow state indicator
+    let flow_desc = feel.flow_description();
+    if !flow_desc.is_empty() {
+        let flow_width = 25;
+        let flow_area = Rect::new(
+            2,
+            1,
+            flow_width,
+            1,
+        );
+        
+        let flow_color = crate::ui::theme::flow_color(feel.flow_state);
         
         let flow_text = Span::styled(
             format!("󰔟 {}", flow_desc),
@@ -1473,7 +4157,7 @@ fn render_typing_feel_overlay(f: &mut Frame, state: &GameState, area: Rect) {
         );
         
         let wpm_color = if feel.wpm >= 80.0 {
-            Palette::ACCENT
+            Palette::current().accent
         } else if feel.wpm >= 60.0 {
             Color::Yellow
         } else if feel.wpm >= 40.0 {
@@ -1580,7 +4264,7 @@ fn render_effects_overlay(f: &mut Frame, state: &GameState, area: Rect) {
             };
             
             // Render a flash border
-            let flash_block = Block::default()
+            let flash_block = Styles::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(flash_color).add_modifier(Modifier::BOLD));
             f.render_widget(flash_block, area);
@@ -0,0 +1,162 @@
+//! Combat replay rendering - the last fight's typing, played back
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline, Wrap},
+    Frame,
+};
+use crate::game::combat_replay::{CombatReplay, ReplayViewer};
+use crate::game::state::GameState;
+use super::theme::{Palette, Styles};
+
+pub fn render_replay(f: &mut Frame, state: &GameState) {
+    let Some(viewer) = &state.replay_viewer else {
+        return;
+    };
+    let replay = &viewer.replay;
+    let (current, shown) = replay.frame_at(viewer.position_ms());
+
+    let area = f.area();
+    let main_area = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(2));
+    let hint_area = Rect::new(area.x, area.height.saturating_sub(2), area.width, 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Length(5),  // Prompt being played back
+            Constraint::Length(5),  // Damage per word
+            Constraint::Min(5),     // Word list
+        ])
+        .split(main_area);
+
+    render_header(f, viewer, current, chunks[0]);
+    render_prompt(f, replay, current, shown, chunks[1]);
+    render_damage_timeline(f, replay, current, chunks[2]);
+    render_word_list(f, replay, current, chunks[3]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [Space] ", Styles::keybind()),
+        Span::raw(if viewer.is_playing() { "Pause  " } else { "Play  " }),
+        Span::styled("[←/→] ", Styles::keybind()),
+        Span::raw("Word  "),
+        Span::styled("[s] ", Styles::keybind()),
+        Span::raw("Speed  "),
+        Span::styled("[Esc] ", Style::default().fg(Palette::current().warning)),
+        Span::raw("Back"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().bg(Palette::current().bg_panel));
+    f.render_widget(hints, hint_area);
+}
+
+fn render_header(f: &mut Frame, viewer: &ReplayViewer, current: usize, area: Rect) {
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(if viewer.is_playing() { "▶ " } else { "⏸ " }, Style::default().fg(Palette::current().accent)),
+        Span::styled("REPLAY", Style::default().fg(Palette::current().primary).add_modifier(Modifier::BOLD)),
+        Span::raw(format!("   Word {}/{}", current + 1, viewer.replay.words.len())),
+        Span::styled(format!("   ×{}", viewer.speed()), Style::default().fg(Palette::current().secondary)),
+        Span::raw(format!(
+            "   {:.1}s / {:.1}s",
+            viewer.position_ms() as f32 / 1000.0,
+            viewer.replay.total_ms() as f32 / 1000.0
+        )),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Styles::block().borders(Borders::ALL).border_style(Style::default().fg(Palette::current().border)));
+    f.render_widget(header, area);
+}
+
+/// The prompt with the keys typed so far laid over it
+fn render_prompt(f: &mut Frame, replay: &CombatReplay, current: usize, shown: usize, area: Rect) {
+    let Some(word) = replay.words.get(current) else {
+        return;
+    };
+    let typed = word.typed(shown);
+    let mut spans: Vec<Span> = Vec::new();
+    for (i, expected) in word.prompt.chars().enumerate() {
+        let span = match typed.get(i) {
+            Some((_, true)) => Span::styled(expected.to_string(), Styles::typed_correct()),
+            Some((ch, false)) => Span::styled(ch.to_string(), Styles::typed_wrong()),
+            None if i == typed.len() => Span::styled(expected.to_string(), Styles::cursor()),
+            None => Span::styled(expected.to_string(), Styles::untyped()),
+        };
+        spans.push(span);
+    }
+
+    // Once every key is in, show what the word did
+    let mut title = format!(" {} keys, {} errors ", word.keys.len(), word.errors());
+    if shown >= word.keys.len() {
+        title = if !word.finished {
+            " ⏰ Timed out ".to_string()
+        } else if replay.is_spike(current) {
+            format!(" ▲ SPIKE: {} damage ", word.damage)
+        } else {
+            format!(" {} damage ", word.damage)
+        };
+    }
+    let prompt = Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Palette::current().border_focus))
+            .title(Span::styled(title, Style::default().fg(Palette::current().secondary))));
+    f.render_widget(prompt, area);
+}
+
+fn render_damage_timeline(f: &mut Frame, replay: &CombatReplay, current: usize, area: Rect) {
+    let damage: Vec<u64> = replay.words.iter().map(|w| w.damage.max(0) as u64).collect();
+    // Keep the word being played on screen when the fight was long
+    let width = area.width.saturating_sub(2) as usize;
+    let skip = (current + 1).saturating_sub(width);
+    let sparkline = Sparkline::default()
+        .data(&damage[skip.min(damage.len())..])
+        .style(Style::default().fg(Palette::current().accent))
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Damage per word ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(sparkline, area);
+}
+
+fn render_word_list(f: &mut Frame, replay: &CombatReplay, current: usize, area: Rect) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let first = (current + 1).saturating_sub(visible);
+    let items: Vec<ListItem> = replay.words.iter().enumerate().skip(first).map(|(i, word)| {
+        let marker = if !word.finished {
+            Span::styled(" ⏰ ", Style::default().fg(Palette::current().warning))
+        } else if replay.is_spike(i) {
+            Span::styled(" ▲ ", Style::default().fg(Palette::current().accent))
+        } else {
+            Span::raw("   ")
+        };
+        let style = if i == current {
+            Style::default().fg(Palette::current().secondary).add_modifier(Modifier::REVERSED)
+        } else if i > current {
+            Style::default().fg(Palette::current().text_dim)
+        } else {
+            Style::default().fg(Palette::current().text)
+        };
+        let errors = word.errors();
+        ListItem::new(Line::from(vec![
+            marker,
+            Span::styled(word.prompt.as_str(), style),
+            Span::styled(format!("  {} dmg", word.damage), Style::default().fg(Palette::current().success)),
+            if errors > 0 {
+                Span::styled(format!("  {} ✗", errors), Style::default().fg(Palette::current().danger))
+            } else {
+                Span::raw("")
+            },
+        ]))
+    }).collect();
+
+    let list = List::new(items)
+        .block(Styles::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Palette::current().border))
+            .title(Span::styled(" Words ", Style::default().fg(Palette::current().primary))));
+    f.render_widget(list, area);
+}
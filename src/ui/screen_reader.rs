@@ -0,0 +1,229 @@
+//! Screen Reader - The game as plain, linear text
+//!
+//! With the screen reader mode on (`a` on the title screen, or start the
+//! game with `--screen-reader`), the game is drawn for a terminal screen
+//! reader rather than for the eye:
+//! - box art, borders, icons and enemy portraits are left out
+//! - every change in the state of play is written as a labeled line, in
+//!   order, in a log at the foot of the screen: "HP: 38 of 60", "Enemy:
+//!   Goblin, HP 12 of 30", "Combat: You strike for 12."
+//! - the word to type and what has been typed so far are lines of their
+//!   own, "Prompt:" and "Typed:", with the cursor left at the end of what
+//!   is typed
+//!
+//! Design: the facts of play are described from GameState as labeled
+//! lines, and a line is logged when it changes. What is said in between
+//! comes from the transcript, which already hears all of it. The scene is
+//! still drawn above the log, so its menus can be read, but its art is
+//! swept out once it is drawn.
+
+use std::collections::VecDeque;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    text::Line,
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::game::combat::{CombatPhase, CombatState};
+use crate::game::enemy_intent::Intent;
+use crate::game::state::{GameState, Scene};
+use crate::game::transcript::{Entry, Source, Transcript};
+
+/// Most lines the log keeps
+pub const LOG_CAPACITY: usize = 200;
+
+/// Rows of the log shown at the foot of the screen
+const LOG_ROWS: u16 = 5;
+
+/// Everything that has changed, as said to the screen reader
+#[derive(Debug, Clone, Default)]
+pub struct ReaderLog {
+    lines: VecDeque<String>,
+    /// The facts as last described
+    facts: Vec<String>,
+    /// Transcript mark of the lines already logged
+    heard: usize,
+}
+
+impl ReaderLog {
+    /// Log what the transcript has heard since, then each fact that has
+    /// changed
+    pub fn update(&mut self, facts: Vec<String>, transcript: &Transcript) {
+        let heard: Vec<String> = transcript.since(self.heard).map(labeled).collect();
+        self.heard = transcript.recorded();
+        for line in heard {
+            self.push(line);
+        }
+        for fact in &facts {
+            if !self.facts.contains(fact) {
+                self.push(fact.clone());
+            }
+        }
+        self.facts = facts;
+    }
+
+    fn push(&mut self, line: String) {
+        if self.lines.len() == LOG_CAPACITY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// The newest `n` lines, oldest first
+    pub fn latest(&self, n: usize) -> impl Iterator<Item = &String> {
+        self.lines.iter().skip(self.lines.len().saturating_sub(n))
+    }
+}
+
+/// A transcript line, labeled with who said it or where it came from
+fn labeled(entry: &Entry) -> String {
+    match (&entry.speaker, entry.source) {
+        (Some(speaker), _) => format!("{}: {}", speaker, entry.text),
+        (None, Source::Combat) => format!("Combat: {}", entry.text),
+        (None, _) => entry.text.clone(),
+    }
+}
+
+/// A scene's name, in words
+fn scene_name(scene: Scene) -> String {
+    let mut name = String::new();
+    for c in format!("{:?}", scene).chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push(' ');
+        }
+        name.push(c);
+    }
+    name
+}
+
+/// The word to type, unless the fight is keeping it hidden
+fn prompt(state: &GameState, combat: &CombatState) -> String {
+    let hidden = state.pronouncer.is_dictation()
+        || combat.inscription.is_some_and(|i| !i.is_previewing())
+        || combat.active_puzzle().is_some();
+    if hidden {
+        "hidden, type it from memory or from what you hear".to_string()
+    } else {
+        combat.current_word.clone()
+    }
+}
+
+/// The fight's prompt, if one is waiting to be typed
+fn typing(state: &GameState) -> Option<(String, &str)> {
+    let combat = state.combat_state.as_ref().filter(|c| c.phase == CombatPhase::PlayerTurn)?;
+    (state.scene == Scene::Combat).then(|| (prompt(state, combat), combat.typed_input.as_str()))
+}
+
+/// The facts of play, each a labeled line
+pub fn describe(state: &GameState) -> Vec<String> {
+    let mut facts = vec![format!("Scene: {}", scene_name(state.scene))];
+    if let Some(player) = &state.player {
+        facts.push(format!("HP: {} of {}", player.hp, player.max_hp));
+        facts.push(format!("MP: {} of {}", player.mp, player.max_mp));
+        facts.push(format!("Gold: {}", player.gold));
+        facts.push(format!("Level: {}", player.level));
+    }
+    if let Some(dungeon) = &state.dungeon {
+        facts.push(format!(
+            "Floor: {}, room {} of {}",
+            dungeon.current_floor,
+            dungeon.rooms_cleared + 1,
+            dungeon.rooms_per_floor
+        ));
+    }
+    if let Some(combat) = state.combat_state.as_ref().filter(|_| state.scene == Scene::Combat) {
+        let enemy = &combat.enemy;
+        facts.push(format!("Enemy: {}, HP {} of {}", enemy.name, enemy.current_hp, enemy.max_hp));
+        let hp_pct = enemy.current_hp.max(0) as f32 / enemy.max_hp.max(1) as f32;
+        if let Some(phase) = combat.boss_plate.as_ref().and_then(|plate| plate.phase(hp_pct)) {
+            facts.push(format!("Boss phase: {}", phase.name));
+        }
+        if let Some(intent) = state.player.as_ref().and_then(|p| Intent::of(combat, p)) {
+            facts.push(format!("Intent: {}", intent.describe()));
+        }
+        if combat.combo > 1 {
+            facts.push(format!("Combo: {}", combat.combo));
+        }
+    }
+    if let Some((prompt, _)) = typing(state) {
+        facts.push(format!("Prompt: {}", prompt));
+    }
+    facts
+}
+
+/// Sweep the art out of a drawn frame: borders, box drawing, shading and
+/// icons become blank
+pub fn strip_art(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        let mut chars = cell.symbol().chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let art = matches!(c,
+            '\u{2500}'..='\u{259f}'   // box drawing, block elements
+            | '\u{25c6}'..='\u{25c8}' // the ◆◇◈ of ornamental borders
+            | '\u{e000}'..='\u{f8ff}'
+            | '\u{f0000}'..='\u{fffff}' // Nerd Font icons
+        );
+        if art {
+            cell.set_symbol(" ");
+        }
+    }
+}
+
+/// Draw the log, and the prompt if there is one, at the foot of the screen
+pub fn render(f: &mut Frame, state: &GameState) {
+    let area = f.area();
+    let typing = typing(state);
+    let rows = LOG_ROWS + if typing.is_some() { 2 } else { 0 };
+    // Above the key hints, which stay readable
+    let bottom = area.height.saturating_sub(2);
+    let foot = Rect::new(area.x, bottom.saturating_sub(rows), area.width, rows.min(bottom));
+
+    let mut lines: Vec<Line> = state.reader_log.latest(LOG_ROWS as usize).map(|l| Line::from(l.as_str())).collect();
+    lines.resize(LOG_ROWS as usize, Line::from(""));
+    if let Some((prompt, typed)) = &typing {
+        lines.push(Line::from(format!("Prompt: {}", prompt)));
+        let typed_line = format!("Typed: {}", typed);
+        let x = foot.x + (typed_line.chars().count() as u16).min(foot.width.saturating_sub(1));
+        f.set_cursor_position(Position::new(x, foot.y + foot.height.saturating_sub(1)));
+        lines.push(Line::from(typed_line));
+    }
+    f.render_widget(Clear, foot);
+    f.render_widget(Paragraph::new(lines), foot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_changes_are_logged_once_after_what_was_said() {
+        let mut log = ReaderLog::default();
+        let mut transcript = Transcript::default();
+        let facts = |hp: i32| vec!["Scene: Combat".to_string(), format!("HP: {} of 60", hp)];
+
+        log.update(facts(60), &transcript);
+        transcript.follow_combat(&["The goblin bites for 5.".to_string()]);
+        log.update(facts(55), &transcript);
+        log.update(facts(55), &transcript);
+        assert_eq!(
+            log.latest(10).map(String::as_str).collect::<Vec<_>>(),
+            ["Scene: Combat", "HP: 60 of 60", "Combat: The goblin bites for 5.", "HP: 55 of 60"]
+        );
+        assert_eq!(scene_name(Scene::ClassSelect), "Class Select");
+    }
+
+    #[test]
+    fn test_art_is_swept_and_words_are_kept() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 1));
+        buffer.set_string(0, 0, "╭─ 󰓥 Map █╮", Style::default());
+        strip_art(&mut buffer);
+        let drawn: String = buffer.content.iter().map(|c| c.symbol()).collect();
+        assert_eq!(drawn.trim_end(), "     Map");
+    }
+}
@@ -17,6 +17,7 @@ use ratatui::{
 use crate::game::player::Player;
 use crate::game::spells::{Spell, SpellElement, SpellEffect};
 use crate::game::combat::CombatState;
+use crate::ui::theme::Styles;
 
 /// Spell cooldown tracking for combat
 #[derive(Debug, Clone, Default)]
@@ -147,7 +148,7 @@ fn render_spell_slot(
         Line::from(Span::styled(status, text_style.fg(if can_afford && is_ready { Color::Cyan } else { Color::DarkGray }))),
     ];
 
-    let block = Block::default()
+    let block = Styles::block()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
         .style(if is_selected {
@@ -175,7 +176,7 @@ pub fn render_spell_selection_overlay(
     let overlay_area = centered_rect(70, 60, area);
     f.render_widget(Clear, overlay_area);
 
-    let block = Block::default()
+    let block = Styles::block()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Magenta))
         .title(Span::styled(" ✨ Spells ", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)))
@@ -184,7 +185,7 @@ pub fn render_spell_selection_overlay(
     f.render_widget(block.clone(), overlay_area);
 
     // Inner area for spell list
-    let inner = Block::default().inner(overlay_area);
+    let inner = Styles::block().inner(overlay_area);
 
     let spells = &player.known_spells;
     let lines: Vec<Line> = spells
@@ -128,7 +128,7 @@ pub fn render_battle_summary(f: &mut Frame, summary: &BattleSummary) {
     let title = Paragraph::new(title_text)
         .style(title_style)
         .alignment(Alignment::Center)
-        .block(Block::default()
+        .block(Styles::block()
             .borders(Borders::ALL)
             .border_style(if summary.victory { Style::default().fg(Color::Green) } else { Style::default().fg(Color::Red) })
             .title(if summary.was_boss { " 👑 Boss Battle Complete " } else { " ⚔️ Battle Complete " }));
@@ -138,7 +138,7 @@ pub fn render_battle_summary(f: &mut Frame, summary: &BattleSummary) {
     let stats_lines = build_battle_stats_lines(summary);
     let stats = Paragraph::new(stats_lines)
         .alignment(Alignment::Left)
-        .block(Block::default()
+        .block(Styles::block()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray))
             .title(" 📊 Battle Statistics "));
@@ -147,7 +147,9 @@ pub fn render_battle_summary(f: &mut Frame, summary: &BattleSummary) {
     // Controls
     let controls = Paragraph::new(Line::from(vec![
         Span::styled("[Enter]", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        Span::raw(" Continue"),
+        Span::raw(" Continue  "),
+        Span::styled("[r]", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(" Replay"),
     ]))
         .alignment(Alignment::Center);
     f.render_widget(controls, chunks[2]);
@@ -294,7 +296,7 @@ pub fn render_run_summary(f: &mut Frame, summary: &RunSummary, area: Rect) {
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
         })
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::BOTTOM));
+        .block(Styles::block().borders(Borders::BOTTOM));
     f.render_widget(header, chunks[0]);
     
     // Stats in columns
@@ -309,7 +311,7 @@ pub fn render_run_summary(f: &mut Frame, summary: &RunSummary, area: Rect) {
     // Left column: Combat stats
     let combat_stats = build_combat_stats(summary);
     let combat_widget = Paragraph::new(combat_stats)
-        .block(Block::default()
+        .block(Styles::block()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Red))
             .title(" ⚔️ Combat "));
@@ -318,7 +320,7 @@ pub fn render_run_summary(f: &mut Frame, summary: &RunSummary, area: Rect) {
     // Right column: Typing stats
     let typing_stats = build_typing_stats(summary);
     let typing_widget = Paragraph::new(typing_stats)
-        .block(Block::default()
+        .block(Styles::block()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan))
             .title(" ⌨️ Typing "));
@@ -334,7 +336,7 @@ pub fn render_run_summary(f: &mut Frame, summary: &RunSummary, area: Rect) {
     let ink_widget = Paragraph::new(ink_text)
         .style(Style::default().fg(Color::Magenta))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::TOP));
+        .block(Styles::block().borders(Borders::TOP));
     f.render_widget(ink_widget, chunks[2]);
 }
 
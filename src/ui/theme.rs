@@ -3,149 +3,676 @@
 //! This module provides:
 //! - Color palette with semantic meanings
 //! - Border styles (box drawing characters)
-//! - Nerd Font icons for UI elements
+//! - Nerd Font icons for UI elements, or plain Unicode or ASCII in their
+//!   place
 //! - Style presets for common patterns
+//!
+//! The palette, border set and icon set together make a theme. One theme is
+//! active at a time, and everything drawn asks it for its colors; see
+//! `themes` for the built-in themes and the user's theme files.
+//!
+//! Typing feedback can also be told by shape rather than color: with typing
+//! markers on, letters typed right are underlined and letters typed wrong
+//! struck through, whatever the theme.
+
+use std::cell::{Cell, RefCell};
 
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::border;
+use ratatui::widgets::Block;
+use serde::{Deserialize, Serialize};
 
-/// Color palette - consistent across all UI
-pub struct Palette;
+use crate::game::typing_feel::FlowState;
 
-impl Palette {
+/// Color palette - consistent across all UI, as set by the active theme
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
     // Primary colors
-    pub const PRIMARY: Color = Color::Rgb(0, 200, 200);      // Cyan-ish
-    pub const SECONDARY: Color = Color::Rgb(200, 150, 50);   // Gold
-    pub const ACCENT: Color = Color::Rgb(200, 50, 200);      // Magenta
-    
+    pub primary: Color,
+    pub secondary: Color,
+    pub accent: Color,
+
     // Semantic colors
-    pub const SUCCESS: Color = Color::Rgb(50, 200, 100);     // Green
-    pub const WARNING: Color = Color::Rgb(230, 180, 50);     // Yellow-orange
-    pub const DANGER: Color = Color::Rgb(220, 60, 60);       // Red
-    pub const INFO: Color = Color::Rgb(100, 150, 255);       // Light blue
-    
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub info: Color,
+
     // Combat colors
-    pub const PLAYER_HP: Color = Color::Rgb(80, 200, 80);    // Bright green
-    pub const ENEMY_HP: Color = Color::Rgb(220, 80, 80);     // Red
-    pub const MP: Color = Color::Rgb(80, 130, 230);          // Blue
-    pub const COMBO: Color = Color::Rgb(255, 200, 50);       // Bright gold
-    
+    pub player_hp: Color,
+    pub enemy_hp: Color,
+    pub mp: Color,
+    pub combo: Color,
+
     // Rarity colors
-    pub const COMMON: Color = Color::Rgb(180, 180, 180);     // Gray
-    pub const UNCOMMON: Color = Color::Rgb(80, 200, 80);     // Green
-    pub const RARE: Color = Color::Rgb(80, 150, 255);        // Blue
-    pub const EPIC: Color = Color::Rgb(180, 80, 255);        // Purple
-    pub const LEGENDARY: Color = Color::Rgb(255, 180, 50);   // Orange
-    
+    pub common: Color,
+    pub uncommon: Color,
+    pub rare: Color,
+    pub epic: Color,
+    pub legendary: Color,
+
     // UI colors
-    pub const BG_DARK: Color = Color::Rgb(20, 20, 25);       // Near black
-    pub const BG_PANEL: Color = Color::Rgb(30, 30, 40);      // Panel bg
-    pub const TEXT: Color = Color::Rgb(220, 220, 220);       // White text
-    pub const TEXT_DIM: Color = Color::Rgb(120, 120, 130);   // Muted text
-    pub const BORDER: Color = Color::Rgb(80, 80, 100);       // Border default
-    pub const BORDER_FOCUS: Color = Color::Rgb(100, 180, 200); // Focused border
-    
+    pub bg_dark: Color,
+    pub bg_panel: Color,
+    pub text: Color,
+    pub text_dim: Color,
+    pub border: Color,
+    pub border_focus: Color,
+
     // Typing feedback colors
-    pub const TYPED_CORRECT: Color = Color::Rgb(80, 230, 80);   // Bright green
-    pub const TYPED_WRONG: Color = Color::Rgb(255, 80, 80);     // Bright red
-    pub const UNTYPED: Color = Color::Rgb(100, 100, 110);       // Gray
-    pub const CURSOR: Color = Color::Rgb(100, 200, 255);        // Cyan cursor
-    
+    pub typed_correct: Color,
+    pub typed_wrong: Color,
+    pub untyped: Color,
+    pub cursor: Color,
+    pub corrupted: Color,
+
     // Flow state colors
-    pub const FLOW_BUILDING: Color = Color::Rgb(200, 200, 100);    // Yellow
-    pub const FLOW_FLOWING: Color = Color::Rgb(100, 200, 255);     // Cyan
-    pub const FLOW_TRANSCENDENT: Color = Color::Rgb(255, 100, 255); // Magenta
-    pub const FLOW_RECOVERING: Color = Color::Rgb(200, 100, 100);  // Faded red
-    
+    pub flow_building: Color,
+    pub flow_flowing: Color,
+    pub flow_transcendent: Color,
+    pub flow_recovering: Color,
+
     // Zone-specific colors
-    pub const ZONE_SHATTERED_HALLS: Color = Color::Rgb(140, 140, 160);   // Stone gray
-    pub const ZONE_SUNKEN_ARCHIVES: Color = Color::Rgb(80, 180, 200);    // Deep cyan
-    pub const ZONE_BLIGHTED_GARDENS: Color = Color::Rgb(100, 180, 80);   // Sickly green
-    pub const ZONE_CLOCKWORK_DEPTHS: Color = Color::Rgb(220, 180, 60);   // Brass yellow
-    pub const ZONE_VOIDS_EDGE: Color = Color::Rgb(180, 80, 220);         // Void purple
-    pub const ZONE_THE_BREACH: Color = Color::Rgb(220, 60, 60);          // Blood red
+    pub zone_shattered_halls: Color,
+    pub zone_sunken_archives: Color,
+    pub zone_blighted_gardens: Color,
+    pub zone_clockwork_depths: Color,
+    pub zone_voids_edge: Color,
+    pub zone_the_breach: Color,
 }
 
-/// Nerd Font icons used throughout the UI
-pub struct Icons;
+impl Palette {
+    /// The palette of the Default theme
+    pub const DEFAULT: Palette = Palette {
+        // Primary colors
+        primary: Color::Rgb(0, 200, 200), // Cyan-ish
+        secondary: Color::Rgb(200, 150, 50), // Gold
+        accent: Color::Rgb(200, 50, 200), // Magenta
 
-impl Icons {
+        // Semantic colors
+        success: Color::Rgb(50, 200, 100), // Green
+        warning: Color::Rgb(230, 180, 50), // Yellow-orange
+        danger: Color::Rgb(220, 60, 60), // Red
+        info: Color::Rgb(100, 150, 255), // Light blue
+
+        // Combat colors
+        player_hp: Color::Rgb(80, 200, 80), // Bright green
+        enemy_hp: Color::Rgb(220, 80, 80), // Red
+        mp: Color::Rgb(80, 130, 230), // Blue
+        combo: Color::Rgb(255, 200, 50), // Bright gold
+
+        // Rarity colors
+        common: Color::Rgb(180, 180, 180), // Gray
+        uncommon: Color::Rgb(80, 200, 80), // Green
+        rare: Color::Rgb(80, 150, 255), // Blue
+        epic: Color::Rgb(180, 80, 255), // Purple
+        legendary: Color::Rgb(255, 180, 50), // Orange
+
+        // UI colors
+        bg_dark: Color::Rgb(20, 20, 25), // Near black
+        bg_panel: Color::Rgb(30, 30, 40), // Panel bg
+        text: Color::Rgb(220, 220, 220), // White text
+        text_dim: Color::Rgb(120, 120, 130), // Muted text
+        border: Color::Rgb(80, 80, 100), // Border default
+        border_focus: Color::Rgb(100, 180, 200), // Focused border
+
+        // Typing feedback colors
+        typed_correct: Color::Rgb(80, 230, 80), // Bright green
+        typed_wrong: Color::Rgb(255, 80, 80), // Bright red
+        untyped: Color::Rgb(100, 100, 110), // Gray
+        cursor: Color::Rgb(100, 200, 255), // Cyan cursor
+        corrupted: Color::Rgb(170, 60, 200), // Unwritten purple
+
+        // Flow state colors
+        flow_building: Color::Rgb(200, 200, 100), // Yellow
+        flow_flowing: Color::Rgb(100, 200, 255), // Cyan
+        flow_transcendent: Color::Rgb(255, 100, 255), // Magenta
+        flow_recovering: Color::Rgb(200, 100, 100), // Faded red
+
+        // Zone-specific colors
+        zone_shattered_halls: Color::Rgb(140, 140, 160), // Stone gray
+        zone_sunken_archives: Color::Rgb(80, 180, 200), // Deep cyan
+        zone_blighted_gardens: Color::Rgb(100, 180, 80), // Sickly green
+        zone_clockwork_depths: Color::Rgb(220, 180, 60), // Brass yellow
+        zone_voids_edge: Color::Rgb(180, 80, 220), // Void purple
+        zone_the_breach: Color::Rgb(220, 60, 60), // Blood red
+    };
+
+    /// The active theme's palette
+    pub fn current() -> Palette {
+        THEME.with(|t| t.borrow().palette)
+    }
+}
+
+/// Icons used throughout the UI, in the active theme's icon set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Icons {
     // Navigation & UI
-    pub const ARROW_RIGHT: &'static str = "󰁕";
-    pub const ARROW_LEFT: &'static str = "󰁍";
-    pub const ARROW_UP: &'static str = "󰁝";
-    pub const ARROW_DOWN: &'static str = "󰁅";
-    pub const HELP: &'static str = "󰋗";
-    pub const MENU: &'static str = "󰍜";
-    pub const CLOSE: &'static str = "󰅖";
-    pub const CHECK: &'static str = "󰄬";
-    pub const CROSS: &'static str = "󰅙";
-    pub const INFO: &'static str = "󰋽";
-    pub const WARNING: &'static str = "󰀪";
-    pub const ERROR: &'static str = "󰅚";
-    
+    pub arrow_right: &'static str,
+    pub arrow_left: &'static str,
+    pub arrow_up: &'static str,
+    pub arrow_down: &'static str,
+    pub help: &'static str,
+    pub menu: &'static str,
+    pub close: &'static str,
+    pub check: &'static str,
+    pub cross: &'static str,
+    pub info: &'static str,
+    pub warning: &'static str,
+    pub error: &'static str,
+
     // Game elements
-    pub const SWORD: &'static str = "󰓥";
-    pub const SHIELD: &'static str = "󰒘";
-    pub const HEART: &'static str = "󰣐";
-    pub const MANA: &'static str = "󱠇";
-    pub const GOLD: &'static str = "󰆼";
-    pub const XP: &'static str = "󰓎";
-    pub const LEVEL: &'static str = "󰞋";
-    pub const SKULL: &'static str = "󰯈";
-    pub const CROWN: &'static str = "󰔰";
-    pub const FIRE: &'static str = "󰈸";
-    pub const MAGIC: &'static str = "󱡃";
-    pub const POTION: &'static str = "󱂓";
-    pub const KEY: &'static str = "󰌆";
-    pub const CHEST: &'static str = "󱋣";
-    pub const MAP: &'static str = "󰍋";
-    pub const DUNGEON: &'static str = "󰘛";
-    pub const DOOR: &'static str = "󰞔";
-    
+    pub sword: &'static str,
+    pub shield: &'static str,
+    pub heart: &'static str,
+    pub mana: &'static str,
+    pub gold: &'static str,
+    pub xp: &'static str,
+    pub level: &'static str,
+    pub skull: &'static str,
+    pub crown: &'static str,
+    pub fire: &'static str,
+    pub magic: &'static str,
+    pub potion: &'static str,
+    pub key: &'static str,
+    pub chest: &'static str,
+    pub map: &'static str,
+    pub dungeon: &'static str,
+    pub door: &'static str,
+
     // Classes
-    pub const WORDSMITH: &'static str = "󰜁";
-    pub const SCRIBE: &'static str = "󰯂";
-    pub const SPELLWEAVER: &'static str = "󰺝";
-    pub const BARBARIAN: &'static str = "󰓥";
-    pub const TRICKSTER: &'static str = "󰗎";
-    
+    pub wordsmith: &'static str,
+    pub scribe: &'static str,
+    pub spellweaver: &'static str,
+    pub barbarian: &'static str,
+    pub trickster: &'static str,
+
     // Typing & Combat
-    pub const KEYBOARD: &'static str = "󰌌";
-    pub const COMBO: &'static str = "󱋊";
-    pub const TIMER: &'static str = "󰔟";
-    pub const SPEED: &'static str = "󰓅";
-    pub const ACCURACY: &'static str = "󰇄";
-    pub const TARGET: &'static str = "󰓾";
-    pub const BURST: &'static str = "󰛨";
-    pub const CRITICAL: &'static str = "󱐋";
-    
+    pub keyboard: &'static str,
+    pub combo: &'static str,
+    pub timer: &'static str,
+    pub speed: &'static str,
+    pub accuracy: &'static str,
+    pub target: &'static str,
+    pub burst: &'static str,
+    pub critical: &'static str,
+
     // Status & Effects
-    pub const BUFF: &'static str = "󰁝";
-    pub const DEBUFF: &'static str = "󰁅";
-    pub const HEAL: &'static str = "󰣐";
-    pub const DAMAGE: &'static str = "󱐌";
-    pub const DEFEND: &'static str = "󰒘";
-    pub const STUN: &'static str = "󰒖";
-    pub const POISON: &'static str = "󱂓";
-    pub const BURN: &'static str = "󰈸";
-    
+    pub buff: &'static str,
+    pub debuff: &'static str,
+    pub heal: &'static str,
+    pub damage: &'static str,
+    pub defend: &'static str,
+    pub stun: &'static str,
+    pub poison: &'static str,
+    pub burn: &'static str,
+
     // Rooms/Encounters
-    pub const COMBAT: &'static str = "󰓥";
-    pub const SHOP: &'static str = "󰆼";
-    pub const REST: &'static str = "󰈸";
-    pub const EVENT: &'static str = "󰗀";
-    pub const BOSS: &'static str = "󰯈";
-    pub const TREASURE: &'static str = "󱋣";
-    pub const MYSTERY: &'static str = "󰛓";
-    
+    pub combat: &'static str,
+    pub shop: &'static str,
+    pub rest: &'static str,
+    pub event: &'static str,
+    pub boss: &'static str,
+    pub treasure: &'static str,
+    pub mystery: &'static str,
+
     // Misc
-    pub const STAR: &'static str = "󰓎";
-    pub const SPARK: &'static str = "󱐋";
-    pub const WAVE: &'static str = "󱗿";
-    pub const QUOTE: &'static str = "󰗡";
-    pub const BOOK: &'static str = "󰂽";
-    pub const SCROLL: &'static str = "󱪙";
-    pub const BAKLAVA: &'static str = "󰩛";
+    pub star: &'static str,
+    pub spark: &'static str,
+    pub wave: &'static str,
+    pub quote: &'static str,
+    pub book: &'static str,
+    pub scroll: &'static str,
+    pub baklava: &'static str,
+}
+
+impl Icons {
+    /// Nerd Font glyphs
+    pub const NERD: Icons = Icons {
+        // Navigation & UI
+        arrow_right: "󰁕",
+        arrow_left: "󰁍",
+        arrow_up: "󰁝",
+        arrow_down: "󰁅",
+        help: "󰋗",
+        menu: "󰍜",
+        close: "󰅖",
+        check: "󰄬",
+        cross: "󰅙",
+        info: "󰋽",
+        warning: "󰀪",
+        error: "󰅚",
+
+        // Game elements
+        sword: "󰓥",
+        shield: "󰒘",
+        heart: "󰣐",
+        mana: "󱠇",
+        gold: "󰆼",
+        xp: "󰓎",
+        level: "󰞋",
+        skull: "󰯈",
+        crown: "󰔰",
+        fire: "󰈸",
+        magic: "󱡃",
+        potion: "󱂓",
+        key: "󰌆",
+        chest: "󱋣",
+        map: "󰍋",
+        dungeon: "󰘛",
+        door: "󰞔",
+
+        // Classes
+        wordsmith: "󰜁",
+        scribe: "󰯂",
+        spellweaver: "󰺝",
+        barbarian: "󰓥",
+        trickster: "󰗎",
+
+        // Typing & Combat
+        keyboard: "󰌌",
+        combo: "󱋊",
+        timer: "󰔟",
+        speed: "󰓅",
+        accuracy: "󰇄",
+        target: "󰓾",
+        burst: "󰛨",
+        critical: "󱐋",
+
+        // Status & Effects
+        buff: "󰁝",
+        debuff: "󰁅",
+        heal: "󰣐",
+        damage: "󱐌",
+        defend: "󰒘",
+        stun: "󰒖",
+        poison: "󱂓",
+        burn: "󰈸",
+
+        // Rooms/Encounters
+        combat: "󰓥",
+        shop: "󰆼",
+        rest: "󰈸",
+        event: "󰗀",
+        boss: "󰯈",
+        treasure: "󱋣",
+        mystery: "󰛓",
+
+        // Misc
+        star: "󰓎",
+        spark: "󱐋",
+        wave: "󱗿",
+        quote: "󰗡",
+        book: "󰂽",
+        scroll: "󱪙",
+        baklava: "󰩛",
+    };
+
+    /// Plain Unicode symbols, for fonts without the Nerd Font glyphs
+    pub const UNICODE: Icons = Icons {
+        // Navigation & UI
+        arrow_right: "→",
+        arrow_left: "←",
+        arrow_up: "↑",
+        arrow_down: "↓",
+        help: "?",
+        menu: "≡",
+        close: "✕",
+        check: "✓",
+        cross: "✗",
+        info: "ℹ",
+        warning: "⚠",
+        error: "✖",
+
+        // Game elements
+        sword: "⚔",
+        shield: "▣",
+        heart: "♥",
+        mana: "✧",
+        gold: "¤",
+        xp: "★",
+        level: "▲",
+        skull: "☠",
+        crown: "♛",
+        fire: "♨",
+        magic: "✶",
+        potion: "⚗",
+        key: "⚿",
+        chest: "▤",
+        map: "▦",
+        dungeon: "▥",
+        door: "▯",
+
+        // Classes
+        wordsmith: "✎",
+        scribe: "✍",
+        spellweaver: "✶",
+        barbarian: "⚔",
+        trickster: "♣",
+
+        // Typing & Combat
+        keyboard: "⌨",
+        combo: "»",
+        timer: "◷",
+        speed: "➤",
+        accuracy: "◎",
+        target: "◉",
+        burst: "✸",
+        critical: "ϟ",
+
+        // Status & Effects
+        buff: "↑",
+        debuff: "↓",
+        heal: "♥",
+        damage: "✹",
+        defend: "▣",
+        stun: "✺",
+        poison: "☣",
+        burn: "♨",
+
+        // Rooms/Encounters
+        combat: "⚔",
+        shop: "¤",
+        rest: "♨",
+        event: "✦",
+        boss: "☠",
+        treasure: "▤",
+        mystery: "?",
+
+        // Misc
+        star: "★",
+        spark: "ϟ",
+        wave: "≈",
+        quote: "“",
+        book: "❐",
+        scroll: "§",
+        baklava: "◇",
+    };
+
+    /// Plain ASCII, for terminals that can draw nothing else
+    pub const ASCII: Icons = Icons {
+        // Navigation & UI
+        arrow_right: ">",
+        arrow_left: "<",
+        arrow_up: "^",
+        arrow_down: "v",
+        help: "?",
+        menu: "=",
+        close: "x",
+        check: "+",
+        cross: "x",
+        info: "i",
+        warning: "!",
+        error: "X",
+
+        // Game elements
+        sword: "/",
+        shield: "]",
+        heart: "+",
+        mana: "~",
+        gold: "$",
+        xp: "*",
+        level: "^",
+        skull: "X",
+        crown: "W",
+        fire: "&",
+        magic: "*",
+        potion: "!",
+        key: "k",
+        chest: "=",
+        map: "#",
+        dungeon: "#",
+        door: "D",
+
+        // Classes
+        wordsmith: "W",
+        scribe: "S",
+        spellweaver: "M",
+        barbarian: "B",
+        trickster: "T",
+
+        // Typing & Combat
+        keyboard: "K",
+        combo: "x",
+        timer: "@",
+        speed: ">",
+        accuracy: "%",
+        target: "o",
+        burst: "*",
+        critical: "!",
+
+        // Status & Effects
+        buff: "^",
+        debuff: "v",
+        heal: "+",
+        damage: "*",
+        defend: "]",
+        stun: "z",
+        poison: "%",
+        burn: "&",
+
+        // Rooms/Encounters
+        combat: "x",
+        shop: "$",
+        rest: "&",
+        event: "!",
+        boss: "W",
+        treasure: "=",
+        mystery: "?",
+
+        // Misc
+        star: "*",
+        spark: "'",
+        wave: "~",
+        quote: "\"",
+        book: "=",
+        scroll: "s",
+        baklava: "o",
+    };
+
+    /// Every icon of the set, in the same order in every set
+    pub fn all(&self) -> [&'static str; 64] {
+        [
+            self.arrow_right,
+            self.arrow_left,
+            self.arrow_up,
+            self.arrow_down,
+            self.help,
+            self.menu,
+            self.close,
+            self.check,
+            self.cross,
+            self.info,
+            self.warning,
+            self.error,
+            self.sword,
+            self.shield,
+            self.heart,
+            self.mana,
+            self.gold,
+            self.xp,
+            self.level,
+            self.skull,
+            self.crown,
+            self.fire,
+            self.magic,
+            self.potion,
+            self.key,
+            self.chest,
+            self.map,
+            self.dungeon,
+            self.door,
+            self.wordsmith,
+            self.scribe,
+            self.spellweaver,
+            self.barbarian,
+            self.trickster,
+            self.keyboard,
+            self.combo,
+            self.timer,
+            self.speed,
+            self.accuracy,
+            self.target,
+            self.burst,
+            self.critical,
+            self.buff,
+            self.debuff,
+            self.heal,
+            self.damage,
+            self.defend,
+            self.stun,
+            self.poison,
+            self.burn,
+            self.combat,
+            self.shop,
+            self.rest,
+            self.event,
+            self.boss,
+            self.treasure,
+            self.mystery,
+            self.star,
+            self.spark,
+            self.wave,
+            self.quote,
+            self.book,
+            self.scroll,
+            self.baklava,
+        ]
+    }
+
+    /// The active theme's icons, as far as the terminal can draw them
+    pub fn current() -> Icons {
+        icon_style().set()
+    }
+}
+
+/// Which border set a theme draws its panels with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderStyle {
+    #[default]
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+    Ascii,
+}
+
+impl BorderStyle {
+    pub const ALL: [BorderStyle; 5] = [Self::Plain, Self::Rounded, Self::Double, Self::Thick, Self::Ascii];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Rounded => "rounded",
+            Self::Double => "double",
+            Self::Thick => "thick",
+            Self::Ascii => "ascii",
+        }
+    }
+
+    pub fn set(&self) -> border::Set {
+        match self {
+            Self::Plain => border::PLAIN,
+            Self::Rounded => border::ROUNDED,
+            Self::Double => border::DOUBLE,
+            Self::Thick => border::THICK,
+            Self::Ascii => border::Set {
+                top_left: "+", top_right: "+", bottom_left: "+", bottom_right: "+",
+                vertical_left: "|", vertical_right: "|",
+                horizontal_top: "-", horizontal_bottom: "-",
+            },
+        }
+    }
+}
+
+/// Which icon set a theme draws with, or a terminal can draw; each draws
+/// all that the ones before it can
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconStyle {
+    Ascii,
+    Unicode,
+    #[default]
+    Nerd,
+}
+
+impl IconStyle {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ascii => "ascii",
+            Self::Unicode => "unicode",
+            Self::Nerd => "nerd",
+        }
+    }
+
+    pub fn set(&self) -> Icons {
+        match self {
+            Self::Ascii => Icons::ASCII,
+            Self::Unicode => Icons::UNICODE,
+            Self::Nerd => Icons::NERD,
+        }
+    }
+}
+
+/// A palette, border set and icon set, picked together
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub palette: Palette,
+    pub borders: BorderStyle,
+    pub icons: IconStyle,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "Default".to_string(),
+            palette: Palette::DEFAULT,
+            borders: BorderStyle::Plain,
+            icons: IconStyle::Nerd,
+        }
+    }
+}
+
+thread_local! {
+    static THEME: RefCell<Theme> = RefCell::new(Theme::default());
+    static MARKERS: Cell<bool> = const { Cell::new(false) };
+    static TERMINAL_ICONS: Cell<IconStyle> = const { Cell::new(IconStyle::Nerd) };
+}
+
+/// Draw everything in `theme` from now on
+pub fn apply(theme: Theme) {
+    THEME.with(|t| *t.borrow_mut() = theme);
+}
+
+/// The theme everything is drawn in
+pub fn current() -> Theme {
+    THEME.with(|t| t.borrow().clone())
+}
+
+/// Draw no icons finer than `style`, whatever the theme asks for
+pub fn limit_icons(style: IconStyle) {
+    TERMINAL_ICONS.with(|t| t.set(style));
+}
+
+/// The icons the terminal can draw
+pub fn terminal_icons() -> IconStyle {
+    TERMINAL_ICONS.with(|t| t.get())
+}
+
+/// The icons drawn: the theme's, as far as the terminal can draw them
+pub fn icon_style() -> IconStyle {
+    THEME.with(|t| t.borrow().icons).min(terminal_icons())
+}
+
+/// Mark typed letters by shape as well as color
+pub fn set_markers(on: bool) {
+    MARKERS.with(|m| m.set(on));
+}
+
+fn markers() -> bool {
+    MARKERS.with(|m| m.get())
 }
 
 /// Styled border characters for different UI contexts
@@ -234,132 +761,145 @@ impl BorderSet {
 pub struct Styles;
 
 impl Styles {
+    /// A block drawn with the active theme's border set (ASCII where the
+    /// terminal can draw nothing else)
+    pub fn block() -> Block<'static> {
+        let borders = match terminal_icons() {
+            IconStyle::Ascii => BorderStyle::Ascii,
+            _ => THEME.with(|t| t.borrow().borders),
+        };
+        Block::default().border_set(borders.set())
+    }
+
     // Text styles
     pub fn title() -> Style {
         Style::default()
-            .fg(Palette::PRIMARY)
+            .fg(Palette::current().primary)
             .add_modifier(Modifier::BOLD)
     }
     
     pub fn subtitle() -> Style {
         Style::default()
-            .fg(Palette::SECONDARY)
+            .fg(Palette::current().secondary)
             .add_modifier(Modifier::ITALIC)
     }
     
     pub fn normal() -> Style {
-        Style::default().fg(Palette::TEXT)
+        Style::default().fg(Palette::current().text)
     }
     
     pub fn dim() -> Style {
-        Style::default().fg(Palette::TEXT_DIM)
+        Style::default().fg(Palette::current().text_dim)
     }
     
     pub fn accent() -> Style {
         Style::default()
-            .fg(Palette::ACCENT)
+            .fg(Palette::current().accent)
             .add_modifier(Modifier::BOLD)
     }
     
     // Status styles
     pub fn success() -> Style {
         Style::default()
-            .fg(Palette::SUCCESS)
+            .fg(Palette::current().success)
             .add_modifier(Modifier::BOLD)
     }
     
     pub fn warning() -> Style {
-        Style::default().fg(Palette::WARNING)
+        Style::default().fg(Palette::current().warning)
     }
     
     pub fn danger() -> Style {
         Style::default()
-            .fg(Palette::DANGER)
+            .fg(Palette::current().danger)
             .add_modifier(Modifier::BOLD)
     }
     
     pub fn info() -> Style {
-        Style::default().fg(Palette::INFO)
+        Style::default().fg(Palette::current().info)
     }
     
     // Interactive styles
     pub fn selected() -> Style {
         Style::default()
-            .fg(Palette::SECONDARY)
+            .fg(Palette::current().secondary)
             .add_modifier(Modifier::BOLD | Modifier::REVERSED)
     }
     
     pub fn focused() -> Style {
         Style::default()
-            .fg(Palette::PRIMARY)
+            .fg(Palette::current().primary)
             .add_modifier(Modifier::BOLD)
     }
     
     pub fn keybind() -> Style {
         Style::default()
-            .fg(Palette::SECONDARY)
+            .fg(Palette::current().secondary)
             .add_modifier(Modifier::BOLD)
     }
     
     pub fn hint() -> Style {
         Style::default()
-            .fg(Palette::TEXT_DIM)
+            .fg(Palette::current().text_dim)
             .add_modifier(Modifier::ITALIC)
     }
     
-    // Typing styles
+    // Typing styles (with markers on, the shape tells right from wrong)
     pub fn typed_correct() -> Style {
+        let modifier = if markers() { Modifier::UNDERLINED } else { Modifier::BOLD };
         Style::default()
-            .fg(Palette::TYPED_CORRECT)
-            .add_modifier(Modifier::BOLD)
+            .fg(Palette::current().typed_correct)
+            .add_modifier(modifier)
     }
     
     pub fn typed_wrong() -> Style {
+        let modifier = if markers() { Modifier::BOLD | Modifier::CROSSED_OUT } else { Modifier::UNDERLINED };
         Style::default()
-            .fg(Palette::TYPED_WRONG)
-            .add_modifier(Modifier::UNDERLINED)
+            .fg(Palette::current().typed_wrong)
+            .add_modifier(modifier)
     }
     
     pub fn untyped() -> Style {
-        Style::default().fg(Palette::UNTYPED)
+        Style::default().fg(Palette::current().untyped)
     }
     
     pub fn cursor() -> Style {
+        let modifier = if markers() { Modifier::BOLD | Modifier::REVERSED } else { Modifier::BOLD | Modifier::UNDERLINED };
         Style::default()
-            .fg(Palette::CURSOR)
-            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            .fg(Palette::current().cursor)
+            .add_modifier(modifier)
     }
     
     // Block/Panel styles
     pub fn block_default() -> Style {
         Style::default()
-            .bg(Palette::BG_PANEL)
+            .bg(Palette::current().bg_panel)
     }
     
     pub fn border_default() -> Style {
-        Style::default().fg(Palette::BORDER)
+        Style::default().fg(Palette::current().border)
     }
     
     pub fn border_focus() -> Style {
-        Style::default().fg(Palette::BORDER_FOCUS)
+        Style::default().fg(Palette::current().border_focus)
     }
     
     // Combat styles
     pub fn player_hp() -> Style {
-        Style::default().fg(Palette::PLAYER_HP)
+        Style::default().fg(Palette::current().player_hp)
     }
     
     pub fn enemy_hp() -> Style {
-        Style::default().fg(Palette::ENEMY_HP)
+        Style::default().fg(Palette::current().enemy_hp)
     }
     
     pub fn mp() -> Style {
-        Style::default().fg(Palette::MP)
+        Style::default().fg(Palette::current().mp)
     }
     
     pub fn combo() -> Style {
         Style::default()
-            .fg(Palette::COMBO)
+            .fg(Palette::current().combo)
             .add_modifier(Modifier::BOLD)
     }
 }
@@ -380,66 +920,103 @@ pub fn stat_display(icon: &str, value: impl std::fmt::Display, color: Color) ->
 /// Get the appropriate color for HP percentage
 pub fn hp_color(percent: u16) -> Color {
     if percent > 66 {
-        Palette::SUCCESS
+        Palette::current().success
     } else if percent > 33 {
-        Palette::WARNING
+        Palette::current().warning
     } else {
-        Palette::DANGER
+        Palette::current().danger
     }
 }
 
 /// Get color for combo level
 pub fn combo_color(combo: i32) -> Color {
     if combo >= 25 {
-        Palette::FLOW_TRANSCENDENT
+        Palette::current().flow_transcendent
     } else if combo >= 15 {
-        Palette::DANGER
+        Palette::current().danger
     } else if combo >= 8 {
-        Palette::WARNING
+        Palette::current().warning
     } else if combo >= 3 {
-        Palette::INFO
+        Palette::current().info
     } else {
-        Palette::TEXT_DIM
+        Palette::current().text_dim
+    }
+}
+
+/// Get color for a flow state
+pub fn flow_color(state: FlowState) -> Color {
+    match state {
+        FlowState::Building => Palette::current().flow_building,
+        FlowState::Flowing => Palette::current().flow_flowing,
+        FlowState::Transcendent => Palette::current().flow_transcendent,
+        FlowState::Recovering => Palette::current().flow_recovering,
     }
 }
 
 /// Get color for WPM display
 pub fn wpm_color(wpm: f32) -> Color {
     if wpm >= 100.0 {
-        Palette::FLOW_TRANSCENDENT
+        Palette::current().flow_transcendent
     } else if wpm >= 80.0 {
-        Palette::ACCENT
+        Palette::current().accent
     } else if wpm >= 60.0 {
-        Palette::WARNING
+        Palette::current().warning
     } else if wpm >= 40.0 {
-        Palette::INFO
+        Palette::current().info
     } else {
-        Palette::TEXT
+        Palette::current().text
     }
 }
 
 /// Get color for accuracy display
 pub fn accuracy_color(accuracy: f32) -> Color {
     if accuracy >= 98.0 {
-        Palette::FLOW_TRANSCENDENT
+        Palette::current().flow_transcendent
     } else if accuracy >= 95.0 {
-        Palette::SUCCESS
+        Palette::current().success
     } else if accuracy >= 85.0 {
-        Palette::WARNING
+        Palette::current().warning
     } else {
-        Palette::DANGER
+        Palette::current().danger
     }
 }
 
 /// Get color for a zone based on its name
 pub fn zone_color(zone_name: &str) -> Color {
     match zone_name {
-        "Shattered Halls" => Palette::ZONE_SHATTERED_HALLS,
-        "Sunken Archives" => Palette::ZONE_SUNKEN_ARCHIVES,
-        "Blighted Gardens" => Palette::ZONE_BLIGHTED_GARDENS,
-        "Clockwork Depths" => Palette::ZONE_CLOCKWORK_DEPTHS,
-        "Void's Edge" => Palette::ZONE_VOIDS_EDGE,
-        "The Breach" => Palette::ZONE_THE_BREACH,
-        _ => Palette::PRIMARY, // Default fallback
+        "Shattered Halls" => Palette::current().zone_shattered_halls,
+        "Sunken Archives" => Palette::current().zone_sunken_archives,
+        "Blighted Gardens" => Palette::current().zone_blighted_gardens,
+        "Clockwork Depths" => Palette::current().zone_clockwork_depths,
+        "Void's Edge" => Palette::current().zone_voids_edge,
+        "The Breach" => Palette::current().zone_the_breach,
+        _ => Palette::current().primary, // Default fallback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::themes;
+
+    #[test]
+    fn test_markers_tell_right_from_wrong_by_shape() {
+        set_markers(true);
+        let (right, wrong) = (Styles::typed_correct(), Styles::typed_wrong());
+        assert_ne!(right.add_modifier, wrong.add_modifier);
+        assert!(wrong.add_modifier.contains(Modifier::CROSSED_OUT));
+        // Without them it is the color alone, as before
+        set_markers(false);
+        assert!(!Styles::typed_wrong().add_modifier.contains(Modifier::CROSSED_OUT));
+    }
+
+    #[test]
+    fn test_every_colorblind_theme_colors_right_and_wrong_apart() {
+        for name in ["Protanopia", "Deuteranopia", "Tritanopia"] {
+            apply(themes::find(Some(name)));
+            assert_eq!(current().name, name);
+            assert_ne!(Styles::typed_correct().fg, Styles::typed_wrong().fg, "{}", name);
+        }
+        apply(Theme::default());
     }
 }
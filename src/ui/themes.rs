@@ -0,0 +1,414 @@
+//! Themes - The built-in themes and the player's own theme files
+//!
+//! The game is drawn in one theme at a time, picked in the theme picker on
+//! the title screen (`t`). The picker offers:
+//! - the built-in themes, the Default among them with the palette the game
+//!   has always had, and three safe for colorblind players: their right and
+//!   wrong letters, health and danger are told apart without red and green
+//!   (protanopia, deuteranopia) or blue and yellow (tritanopia)
+//! - theme files the player writes, one TOML file each in the `themes`
+//!   folder of the config directory
+//!
+//! A theme file names its theme, picks a border set and an icon set, and
+//! gives any of the palette's colors; those it leaves out are the Default's:
+//!
+//! ```toml
+//! name = "Dusk"
+//! borders = "rounded"   # plain, rounded, double, thick or ascii
+//! icons = "unicode"     # nerd, unicode or ascii
+//!
+//! [colors]
+//! primary = "#88c0d0"
+//! text = "white"
+//! ```
+//!
+//! Colors are `#rrggbb`, a terminal color name or a palette index. The
+//! picker can also write the theme it has selected out as a file, as a
+//! place to start.
+//!
+//! Design: the themes themselves live in `theme`; this module only knows
+//! where they come from. The picked theme is kept by name in the display
+//! config, and looked up again at startup; a name no longer found falls
+//! back to the Default.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use super::theme::{BorderStyle, IconStyle, Palette, Theme};
+use crate::game::config::get_config_dir;
+
+/// Every color of the palette, by the key a theme file gives it under
+pub const COLORS: [&str; 37] = [
+    "primary",
+    "secondary",
+    "accent",
+    "success",
+    "warning",
+    "danger",
+    "info",
+    "player_hp",
+    "enemy_hp",
+    "mp",
+    "combo",
+    "common",
+    "uncommon",
+    "rare",
+    "epic",
+    "legendary",
+    "bg_dark",
+    "bg_panel",
+    "text",
+    "text_dim",
+    "border",
+    "border_focus",
+    "typed_correct",
+    "typed_wrong",
+    "untyped",
+    "cursor",
+    "corrupted",
+    "flow_building",
+    "flow_flowing",
+    "flow_transcendent",
+    "flow_recovering",
+    "zone_shattered_halls",
+    "zone_sunken_archives",
+    "zone_blighted_gardens",
+    "zone_clockwork_depths",
+    "zone_voids_edge",
+    "zone_the_breach",
+];
+
+fn color_mut<'a>(palette: &'a mut Palette, key: &str) -> Option<&'a mut Color> {
+    Some(match key {
+        "primary" => &mut palette.primary,
+        "secondary" => &mut palette.secondary,
+        "accent" => &mut palette.accent,
+        "success" => &mut palette.success,
+        "warning" => &mut palette.warning,
+        "danger" => &mut palette.danger,
+        "info" => &mut palette.info,
+        "player_hp" => &mut palette.player_hp,
+        "enemy_hp" => &mut palette.enemy_hp,
+        "mp" => &mut palette.mp,
+        "combo" => &mut palette.combo,
+        "common" => &mut palette.common,
+        "uncommon" => &mut palette.uncommon,
+        "rare" => &mut palette.rare,
+        "epic" => &mut palette.epic,
+        "legendary" => &mut palette.legendary,
+        "bg_dark" => &mut palette.bg_dark,
+        "bg_panel" => &mut palette.bg_panel,
+        "text" => &mut palette.text,
+        "text_dim" => &mut palette.text_dim,
+        "border" => &mut palette.border,
+        "border_focus" => &mut palette.border_focus,
+        "typed_correct" => &mut palette.typed_correct,
+        "typed_wrong" => &mut palette.typed_wrong,
+        "untyped" => &mut palette.untyped,
+        "cursor" => &mut palette.cursor,
+        "corrupted" => &mut palette.corrupted,
+        "flow_building" => &mut palette.flow_building,
+        "flow_flowing" => &mut palette.flow_flowing,
+        "flow_transcendent" => &mut palette.flow_transcendent,
+        "flow_recovering" => &mut palette.flow_recovering,
+        "zone_shattered_halls" => &mut palette.zone_shattered_halls,
+        "zone_sunken_archives" => &mut palette.zone_sunken_archives,
+        "zone_blighted_gardens" => &mut palette.zone_blighted_gardens,
+        "zone_clockwork_depths" => &mut palette.zone_clockwork_depths,
+        "zone_voids_edge" => &mut palette.zone_voids_edge,
+        "zone_the_breach" => &mut palette.zone_the_breach,
+        _ => return None,
+    })
+}
+
+/// A theme file, as written
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    name: String,
+    #[serde(default)]
+    borders: BorderStyle,
+    #[serde(default)]
+    icons: IconStyle,
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+/// Read a theme from the text of a theme file
+pub fn parse(text: &str) -> Result<Theme, String> {
+    let file: ThemeFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+    let mut palette = Palette::DEFAULT;
+    for (key, value) in &file.colors {
+        let color = color_mut(&mut palette, key).ok_or_else(|| format!("no color called `{}`", key))?;
+        *color = Color::from_str(value).map_err(|_| format!("`{}` is not a color (for `{}`)", value, key))?;
+    }
+    Ok(Theme { name: file.name, palette, borders: file.borders, icons: file.icons })
+}
+
+/// A theme written out as a theme file, every color given
+pub fn to_file(theme: &Theme) -> String {
+    let mut palette = theme.palette;
+    let mut text = format!(
+        "name = \"{}\"\nborders = \"{}\"\nicons = \"{}\"\n\n[colors]\n",
+        theme.name,
+        theme.borders.name(),
+        theme.icons.name()
+    );
+    for key in COLORS {
+        if let Some(color) = color_mut(&mut palette, key) {
+            text.push_str(&format!("{} = \"{}\"\n", key, color));
+        }
+    }
+    text
+}
+
+/// The themes that come with the game, the Default first
+pub fn builtin() -> Vec<Theme> {
+    vec![
+        Theme::default(),
+        Theme {
+            name: "Solarized Dark".to_string(),
+            palette: Palette {
+                primary: Color::Rgb(42, 161, 152),
+                secondary: Color::Rgb(181, 137, 0),
+                accent: Color::Rgb(211, 54, 130),
+                success: Color::Rgb(133, 153, 0),
+                warning: Color::Rgb(203, 75, 22),
+                danger: Color::Rgb(220, 50, 47),
+                info: Color::Rgb(38, 139, 210),
+                player_hp: Color::Rgb(133, 153, 0),
+                enemy_hp: Color::Rgb(220, 50, 47),
+                mp: Color::Rgb(38, 139, 210),
+                combo: Color::Rgb(181, 137, 0),
+                bg_dark: Color::Rgb(0, 43, 54),
+                bg_panel: Color::Rgb(7, 54, 66),
+                text: Color::Rgb(147, 161, 161),
+                text_dim: Color::Rgb(88, 110, 117),
+                border: Color::Rgb(88, 110, 117),
+                border_focus: Color::Rgb(42, 161, 152),
+                typed_correct: Color::Rgb(133, 153, 0),
+                typed_wrong: Color::Rgb(220, 50, 47),
+                untyped: Color::Rgb(88, 110, 117),
+                cursor: Color::Rgb(38, 139, 210),
+                corrupted: Color::Rgb(108, 113, 196),
+                ..Palette::DEFAULT
+            },
+            borders: BorderStyle::Plain,
+            icons: IconStyle::Nerd,
+        },
+        Theme {
+            name: "Gruvbox".to_string(),
+            palette: Palette {
+                primary: Color::Rgb(142, 192, 124),
+                secondary: Color::Rgb(250, 189, 47),
+                accent: Color::Rgb(211, 134, 155),
+                success: Color::Rgb(184, 187, 38),
+                warning: Color::Rgb(254, 128, 25),
+                danger: Color::Rgb(251, 73, 52),
+                info: Color::Rgb(131, 165, 152),
+                player_hp: Color::Rgb(184, 187, 38),
+                enemy_hp: Color::Rgb(251, 73, 52),
+                mp: Color::Rgb(131, 165, 152),
+                combo: Color::Rgb(250, 189, 47),
+                bg_dark: Color::Rgb(29, 32, 33),
+                bg_panel: Color::Rgb(40, 40, 40),
+                text: Color::Rgb(235, 219, 178),
+                text_dim: Color::Rgb(146, 131, 116),
+                border: Color::Rgb(102, 92, 84),
+                border_focus: Color::Rgb(142, 192, 124),
+                typed_correct: Color::Rgb(184, 187, 38),
+                typed_wrong: Color::Rgb(251, 73, 52),
+                untyped: Color::Rgb(124, 111, 100),
+                cursor: Color::Rgb(131, 165, 152),
+                corrupted: Color::Rgb(177, 98, 134),
+                ..Palette::DEFAULT
+            },
+            borders: BorderStyle::Thick,
+            icons: IconStyle::Nerd,
+        },
+        Theme {
+            name: "Nord".to_string(),
+            palette: Palette {
+                primary: Color::Rgb(136, 192, 208),
+                secondary: Color::Rgb(235, 203, 139),
+                accent: Color::Rgb(180, 142, 173),
+                success: Color::Rgb(163, 190, 140),
+                warning: Color::Rgb(208, 135, 112),
+                danger: Color::Rgb(191, 97, 106),
+                info: Color::Rgb(129, 161, 193),
+                player_hp: Color::Rgb(163, 190, 140),
+                enemy_hp: Color::Rgb(191, 97, 106),
+                mp: Color::Rgb(94, 129, 172),
+                combo: Color::Rgb(235, 203, 139),
+                bg_dark: Color::Rgb(46, 52, 64),
+                bg_panel: Color::Rgb(59, 66, 82),
+                text: Color::Rgb(216, 222, 233),
+                text_dim: Color::Rgb(124, 135, 157),
+                border: Color::Rgb(76, 86, 106),
+                border_focus: Color::Rgb(136, 192, 208),
+                typed_correct: Color::Rgb(163, 190, 140),
+                typed_wrong: Color::Rgb(191, 97, 106),
+                untyped: Color::Rgb(97, 110, 136),
+                cursor: Color::Rgb(143, 188, 187),
+                corrupted: Color::Rgb(180, 142, 173),
+                ..Palette::DEFAULT
+            },
+            borders: BorderStyle::Rounded,
+            icons: IconStyle::Nerd,
+        },
+        Theme {
+            name: "Protanopia".to_string(),
+            palette: Palette {
+                success: Color::Rgb(86, 180, 233),
+                warning: Color::Rgb(240, 228, 66),
+                danger: Color::Rgb(230, 159, 0),
+                player_hp: Color::Rgb(86, 180, 233),
+                enemy_hp: Color::Rgb(230, 159, 0),
+                uncommon: Color::Rgb(86, 180, 233),
+                typed_correct: Color::Rgb(86, 180, 233),
+                typed_wrong: Color::Rgb(230, 159, 0),
+                cursor: Color::Rgb(240, 240, 240),
+                flow_recovering: Color::Rgb(204, 121, 167),
+                ..Palette::DEFAULT
+            },
+            borders: BorderStyle::Plain,
+            icons: IconStyle::Nerd,
+        },
+        Theme {
+            name: "Deuteranopia".to_string(),
+            palette: Palette {
+                success: Color::Rgb(100, 143, 255),
+                warning: Color::Rgb(255, 176, 0),
+                danger: Color::Rgb(254, 97, 0),
+                player_hp: Color::Rgb(100, 143, 255),
+                enemy_hp: Color::Rgb(254, 97, 0),
+                uncommon: Color::Rgb(100, 143, 255),
+                typed_correct: Color::Rgb(100, 143, 255),
+                typed_wrong: Color::Rgb(254, 97, 0),
+                cursor: Color::Rgb(240, 240, 240),
+                flow_recovering: Color::Rgb(220, 38, 127),
+                ..Palette::DEFAULT
+            },
+            borders: BorderStyle::Plain,
+            icons: IconStyle::Nerd,
+        },
+        Theme {
+            name: "Tritanopia".to_string(),
+            palette: Palette {
+                success: Color::Rgb(0, 190, 190),
+                warning: Color::Rgb(255, 150, 200),
+                danger: Color::Rgb(230, 40, 60),
+                player_hp: Color::Rgb(0, 190, 190),
+                enemy_hp: Color::Rgb(230, 40, 60),
+                mp: Color::Rgb(170, 170, 255),
+                combo: Color::Rgb(255, 150, 200),
+                typed_correct: Color::Rgb(0, 190, 190),
+                typed_wrong: Color::Rgb(230, 40, 60),
+                cursor: Color::Rgb(240, 240, 240),
+                ..Palette::DEFAULT
+            },
+            borders: BorderStyle::Plain,
+            icons: IconStyle::Nerd,
+        },
+    ]
+}
+
+/// Where theme files are read from
+pub fn themes_dir() -> PathBuf {
+    get_config_dir().join("themes")
+}
+
+/// The theme files in the themes folder, each read or the reason it
+/// couldn't be, in file name order
+pub fn load_files() -> Vec<Result<Theme, String>> {
+    let Ok(entries) = fs::read_dir(themes_dir()) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|x| x == "toml"))
+        .collect();
+    paths.sort();
+    paths.iter()
+        .map(|path| {
+            let file = path.file_name().unwrap_or_default().to_string_lossy();
+            fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| parse(&text))
+                .map_err(|e| format!("{}: {}", file, e))
+        })
+        .collect()
+}
+
+/// Every theme to pick from, the built-in ones first, and the theme files
+/// that couldn't be read
+pub fn available() -> (Vec<Theme>, Vec<String>) {
+    let mut themes = builtin();
+    let mut errors = Vec::new();
+    for file in load_files() {
+        match file {
+            Ok(theme) => themes.push(theme),
+            Err(e) => errors.push(e),
+        }
+    }
+    (themes, errors)
+}
+
+/// The theme called `name`, or the Default if there is none
+pub fn find(name: Option<&str>) -> Theme {
+    let Some(name) = name else {
+        return Theme::default();
+    };
+    available().0.into_iter().find(|t| t.name == name).unwrap_or_default()
+}
+
+/// Write `theme` out as a theme file in the themes folder
+pub fn export(theme: &Theme) -> std::io::Result<PathBuf> {
+    let dir = themes_dir();
+    fs::create_dir_all(&dir)?;
+    let stem: String = theme.name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let path = dir.join(format!("{}.toml", stem));
+    fs::write(&path, to_file(theme))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_a_theme_file_overrides_only_the_colors_it_gives() {
+        let theme = parse(
+            "name = \"Dusk\"\nborders = \"rounded\"\n\n[colors]\nprimary = \"#88c0d0\"\ntext = \"white\"\n",
+        ).unwrap();
+        assert_eq!(theme.name, "Dusk");
+        assert_eq!(theme.borders, BorderStyle::Rounded);
+        assert_eq!(theme.icons, IconStyle::Nerd);
+        assert_eq!(theme.palette.primary, Color::Rgb(136, 192, 208));
+        assert_eq!(theme.palette.text, Color::White);
+        assert_eq!(theme.palette.danger, Palette::DEFAULT.danger);
+
+        assert!(parse("name = \"Bad\"\n[colors]\nprimry = \"red\"\n").unwrap_err().contains("primry"));
+        assert!(parse("name = \"Bad\"\n[colors]\ntext = \"not a color\"\n").is_err());
+        assert!(parse("name = \"Bad\"\nborders = \"wavy\"\n").is_err());
+    }
+
+    #[test]
+    fn test_every_builtin_theme_survives_being_written_out() {
+        let themes = builtin();
+        assert_eq!(themes[0], Theme::default());
+        for theme in themes {
+            assert_ne!(theme.palette.typed_correct, theme.palette.typed_wrong, "{} can't tell right from wrong", theme.name);
+            assert_eq!(parse(&to_file(&theme)), Ok(theme));
+        }
+        assert_eq!(find(None), Theme::default());
+    }
+}